@@ -38,6 +38,7 @@ use sc_network::{Event, NetworkService};
 use sp_runtime::traits::Block as BlockT;
 use futures::prelude::*;
 use sc_client_api::ExecutorProvider;
+use jsonrpc_pubsub::manager::SubscriptionManager;
 use sp_core::traits::BareCryptoStorePtr;
 
 /// Starts a `ServiceBuilder` for a full service.
@@ -129,6 +130,7 @@ macro_rules! new_full_start {
 				let select_chain = builder.select_chain().cloned()
 					.expect("SelectChain is present for full services or set up failed; qed.");
 				let keystore = builder.keystore().clone();
+				let subscriptions = SubscriptionManager::new(Arc::new(builder.spawn_handle()));
 
 				Ok(move |deny_unsafe| {
 					let deps = node_rpc::FullDeps {
@@ -145,6 +147,7 @@ macro_rules! new_full_start {
 							shared_voter_state: shared_voter_state.clone(),
 							shared_authority_set: shared_authority_set.clone(),
 						},
+						subscriptions: subscriptions.clone(),
 					};
 
 					node_rpc::create_full(deps)
@@ -226,7 +229,7 @@ pub fn new_full_base(
 			env: proposer,
 			block_import,
 			sync_oracle: network.clone(),
-			inherent_data_providers: inherent_data_providers.clone(),
+			create_inherent_data_providers: inherent_data_providers.clone(),
 			force_authoring,
 			babe_link,
 			can_author_with,
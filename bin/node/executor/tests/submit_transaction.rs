@@ -224,7 +224,13 @@ fn submitted_transaction_should_be_valid() {
 		let author = extrinsic.signature.clone().unwrap().0;
 		let address = Indices::lookup(author).unwrap();
 		let data = pallet_balances::AccountData { free: 5_000_000_000_000, ..Default::default() };
-		let account = frame_system::AccountInfo { nonce: 0u32, refcount: 0u8, data };
+		let account = frame_system::AccountInfo {
+			nonce: 0u32,
+			consumers: 0,
+			providers: 1,
+			sufficients: 0,
+			data,
+		};
 		<frame_system::Account<Runtime>>::insert(&address, account);
 
 		// check validity
@@ -46,6 +46,7 @@ use sc_consensus_babe_rpc::BabeRpcHandler;
 use sc_finality_grandpa::{SharedVoterState, SharedAuthoritySet};
 use sc_finality_grandpa_rpc::GrandpaRpcHandler;
 use sc_rpc_api::DenyUnsafe;
+use jsonrpc_pubsub::manager::SubscriptionManager;
 use sp_block_builder::BlockBuilder;
 
 /// Light client extra dependencies.
@@ -92,6 +93,8 @@ pub struct FullDeps<C, P, SC> {
 	pub babe: BabeDeps,
 	/// GRANDPA specific dependencies.
 	pub grandpa: GrandpaDeps,
+	/// The manager of RPC subscriptions.
+	pub subscriptions: SubscriptionManager,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -100,6 +103,7 @@ pub fn create_full<C, P, M, SC>(
 ) -> jsonrpc_core::IoHandler<M> where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error=BlockChainError> + 'static,
+	C: sc_client_api::BlockchainEvents<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
@@ -107,11 +111,11 @@ pub fn create_full<C, P, M, SC>(
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
-	M: jsonrpc_core::Metadata + Default,
+	M: jsonrpc_pubsub::PubSubMetadata + Default + Send + Sync + 'static,
 	SC: SelectChain<Block> +'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
-	use pallet_contracts_rpc::{Contracts, ContractsApi};
+	use pallet_contracts_rpc::{Contracts, ContractsApi, ContractsEvents, ContractsEventsApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
@@ -122,6 +126,7 @@ pub fn create_full<C, P, M, SC>(
 		deny_unsafe,
 		babe,
 		grandpa,
+		subscriptions,
 	} = deps;
 	let BabeDeps {
 		keystore,
@@ -142,6 +147,9 @@ pub fn create_full<C, P, M, SC>(
 	io.extend_with(
 		ContractsApi::to_delegate(Contracts::new(client.clone()))
 	);
+	io.extend_with(
+		ContractsEventsApi::to_delegate(ContractsEvents::new(client.clone(), subscriptions))
+	);
 	io.extend_with(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
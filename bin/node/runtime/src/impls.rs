@@ -19,15 +19,8 @@
 
 use node_primitives::Balance;
 use sp_runtime::traits::Convert;
-use frame_support::traits::{OnUnbalanced, Currency};
-use crate::{Balances, Authorship, NegativeImbalance};
-
-pub struct Author;
-impl OnUnbalanced<NegativeImbalance> for Author {
-	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
-		Balances::resolve_creating(&Authorship::author(), amount);
-	}
-}
+use frame_support::traits::{Currency, Get};
+use crate::Balances;
 
 /// Struct that handles the conversion of Balance -> `u64`. This is used for staking's election
 /// calculation.
@@ -45,6 +38,13 @@ impl Convert<u128, Balance> for CurrencyToVoteHandler {
 	fn convert(x: u128) -> Balance { x * Self::factor() }
 }
 
+/// The total issuance of the native currency, against which the support of a poll is measured.
+pub struct TotalIssuance;
+
+impl Get<Balance> for TotalIssuance {
+	fn get() -> Balance { Balances::total_issuance() }
+}
+
 #[cfg(test)]
 mod multiplier_tests {
 	use super::*;
@@ -27,7 +27,7 @@ use std::{sync::Arc, path::{Path, PathBuf}, collections::BTreeMap};
 use node_primitives::Block;
 use crate::client::{Client, Backend};
 use crate::keyring::*;
-use sc_client_db::PruningMode;
+use sc_client_db::{KeepBlocks, PruningMode};
 use sc_executor::{NativeExecutor, WasmExecutionMethod};
 use sp_consensus::{
 	BlockOrigin, BlockImport, BlockImportParams,
@@ -173,6 +173,7 @@ impl DatabaseType {
 			Self::RocksDb => sc_client_db::DatabaseSettingsSrc::RocksDb {
 				path,
 				cache_size: 512,
+				tuning: Default::default(),
 			},
 			Self::ParityDb => sc_client_db::DatabaseSettingsSrc::ParityDb {
 				path,
@@ -348,12 +349,17 @@ impl BenchDb {
 		let db_config = sc_client_db::DatabaseSettings {
 			state_cache_size: 16*1024*1024,
 			state_cache_child_ratio: Some((0, 100)),
+			state_node_cache_size: 16*1024*1024,
 			pruning: PruningMode::ArchiveAll,
+			keep_blocks: KeepBlocks::All,
+			force_pruning_mode: false,
+			auto_upgrade: true,
 			source: database_type.into_settings(dir.into()),
 		};
 
 		let (client, backend) = sc_service::new_client(
 			db_config,
+			sc_service::DEFAULT_CANONICALIZATION_DELAY,
 			NativeExecutor::new(WasmExecutionMethod::Compiled, None, 8),
 			&keyring.generate_genesis(),
 			None,
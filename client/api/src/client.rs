@@ -161,6 +161,8 @@ pub struct MemoryInfo {
 	pub database_cache: MemorySize,
 	/// Size of the state db.
 	pub state_db: StateDbMemoryInfo,
+	/// Size of the trie node cache.
+	pub node_cache: MemorySize,
 }
 
 /// I/O statistics for client instance.
@@ -188,6 +190,10 @@ pub struct IoInfo {
 	pub state_writes_cache: u64,
 	/// State write (trie nodes) to backend db.
 	pub state_writes_nodes: u64,
+	/// State reads (trie nodes) from backend db.
+	pub state_reads_nodes: u64,
+	/// State reads (trie nodes) served by the node cache.
+	pub state_reads_nodes_cache: u64,
 }
 
 /// Usage statistics for running client instance.
@@ -207,10 +213,12 @@ impl fmt::Display for UsageInfo {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(
 			f,
-			"caches: ({} state, {} db overlay), \
+			"caches: ({} state, {} trie nodes, {} db overlay), \
 			 state db: ({} non-canonical, {} pruning, {} pinned), \
-			 i/o: ({} tx, {} write, {} read, {} avg tx, {}/{} key cache reads/total, {} trie nodes writes)",
+			 i/o: ({} tx, {} write, {} read, {} avg tx, {}/{} key cache reads/total, \
+			 {}/{} node cache reads/total, {} trie nodes writes)",
 			self.memory.state_cache,
+			self.memory.node_cache,
 			self.memory.database_cache,
 			self.memory.state_db.non_canonical,
 			self.memory.state_db.pruning.unwrap_or_default(),
@@ -221,6 +229,8 @@ impl fmt::Display for UsageInfo {
 			self.io.average_transaction_size,
 			self.io.state_reads_cache,
 			self.io.state_reads,
+			self.io.state_reads_nodes_cache,
+			self.io.state_reads_nodes,
 			self.io.state_writes_nodes,
 		)
 	}
@@ -22,13 +22,15 @@
 
 use std::{time, sync::Arc};
 use sc_client_api::backend;
-use codec::Decode;
+use codec::{Decode, Encode};
 use sp_consensus::{evaluation, Proposal, RecordProof};
 use sp_inherents::InherentData;
 use log::{error, info, debug, trace, warn};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, DigestFor, BlakeTwo256},
+	transaction_validity::TransactionValidityError,
+	Percent,
 };
 use sp_transaction_pool::{TransactionPool, InPoolTransaction};
 use sc_telemetry::{telemetry, CONSENSUS_INFO};
@@ -41,6 +43,10 @@ use std::marker::PhantomData;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_proposer_metrics::MetricsLink as PrometheusMetrics;
 
+/// Maximum number of transactions that may fail to apply before we stop trying to include more
+/// and propose the block as is.
+const MAX_FAILED_TRANSACTIONS: usize = 128;
+
 /// Proposer factory.
 pub struct ProposerFactory<A, B, C> {
 	/// The client instance.
@@ -49,6 +55,12 @@ pub struct ProposerFactory<A, B, C> {
 	transaction_pool: Arc<A>,
 	/// Prometheus Link,
 	metrics: PrometheusMetrics,
+	/// Fraction of the proposing duration after which no more transactions are included.
+	soft_deadline: Option<Percent>,
+	/// Fraction of the proposing duration after which the proposal is abandoned.
+	hard_deadline: Option<Percent>,
+	/// Maximum encoded size of the extrinsics of a block.
+	block_size_limit: Option<usize>,
 	/// phantom member to pin the `Backend` type.
 	_phantom: PhantomData<B>,
 }
@@ -63,9 +75,34 @@ impl<A, B, C> ProposerFactory<A, B, C> {
 			client,
 			transaction_pool,
 			metrics: PrometheusMetrics::new(prometheus),
+			soft_deadline: None,
+			hard_deadline: None,
+			block_size_limit: None,
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Stop including transactions once the given fraction of the proposing duration has passed.
+	///
+	/// Defaults to two thirds, which leaves time for finalizing and evaluating the block.
+	pub fn set_soft_deadline(&mut self, fraction: Percent) {
+		self.soft_deadline = Some(fraction);
+	}
+
+	/// Abandon the proposal if building it takes longer than the given fraction of the proposing
+	/// duration.
+	///
+	/// Unset by default.
+	pub fn set_hard_deadline(&mut self, fraction: Percent) {
+		self.hard_deadline = Some(fraction);
+	}
+
+	/// Limit the encoded size of the extrinsics of a block, including inherents, to `limit` bytes.
+	///
+	/// Unset by default.
+	pub fn set_block_size_limit(&mut self, limit: usize) {
+		self.block_size_limit = Some(limit);
+	}
 }
 
 impl<B, Block, C, A> ProposerFactory<A, B, C>
@@ -97,6 +134,9 @@ impl<B, Block, C, A> ProposerFactory<A, B, C>
 			transaction_pool: self.transaction_pool.clone(),
 			now,
 			metrics: self.metrics.clone(),
+			soft_deadline: self.soft_deadline,
+			hard_deadline: self.hard_deadline,
+			block_size_limit: self.block_size_limit,
 			_phantom: PhantomData,
 		};
 
@@ -136,6 +176,9 @@ pub struct Proposer<B, Block: BlockT, C, A: TransactionPool> {
 	transaction_pool: Arc<A>,
 	now: Box<dyn Fn() -> time::Instant + Send + Sync>,
 	metrics: PrometheusMetrics,
+	soft_deadline: Option<Percent>,
+	hard_deadline: Option<Percent>,
+	block_size_limit: Option<usize>,
 	_phantom: PhantomData<B>,
 }
 
@@ -164,9 +207,21 @@ impl<A, B, Block, C> sp_consensus::Proposer<Block> for
 		record_proof: RecordProof,
 	) -> Self::Proposal {
 		tokio_executor::blocking::run(move || {
-			// leave some time for evaluation and block finalization (33%)
-			let deadline = (self.now)() + max_duration - max_duration / 3;
-			self.propose_with(inherent_data, inherent_digests, deadline, record_proof)
+			let now = (self.now)();
+			let fraction = |fraction: Percent| {
+				time::Duration::from_micros(fraction * max_duration.as_micros() as u64)
+			};
+			// leave some time for evaluation and block finalization (33%) by default
+			let soft_deadline = now + self.soft_deadline
+				.map_or(max_duration - max_duration / 3, fraction);
+			let hard_deadline = self.hard_deadline.map(|hard| now + fraction(hard));
+			self.propose_with(
+				inherent_data,
+				inherent_digests,
+				soft_deadline,
+				hard_deadline,
+				record_proof,
+			)
 		})
 	}
 }
@@ -186,6 +241,7 @@ impl<A, B, Block, C> Proposer<B, Block, C, A>
 		inherent_data: InherentData,
 		inherent_digests: DigestFor<Block>,
 		deadline: time::Instant,
+		hard_deadline: Option<time::Instant>,
 		record_proof: RecordProof,
 	) -> Result<Proposal<Block, backend::TransactionFor<B, Block>>, sp_blockchain::Error> {
 		/// If the block is full we will attempt to push at most
@@ -199,7 +255,9 @@ impl<A, B, Block, C> Proposer<B, Block, C, A>
 			record_proof,
 		)?;
 
+		let mut block_size = 0;
 		for inherent in block_builder.create_inherents(inherent_data)? {
+			let inherent_size = inherent.encoded_size();
 			match block_builder.push(inherent) {
 				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() =>
 					warn!("⚠️  Dropping non-mandatory inherent from overweight block."),
@@ -210,13 +268,14 @@ impl<A, B, Block, C> Proposer<B, Block, C, A>
 				Err(e) => {
 					warn!("❗️ Inherent extrinsic returned unexpected error: {}. Dropping.", e);
 				}
-				Ok(_) => {}
+				Ok(_) => block_size += inherent_size,
 			}
 		}
 
 		// proceed with transactions
 		let block_timer = time::Instant::now();
 		let mut skipped = 0;
+		let mut failed = 0;
 		let mut unqueue_invalid = Vec::new();
 		let pending_iterator = match executor::block_on(future::select(
 			self.transaction_pool.ready_at(self.parent_number),
@@ -240,14 +299,36 @@ impl<A, B, Block, C> Proposer<B, Block, C, A>
 					"Consensus deadline reached when pushing block transactions, \
 					proceeding with proposing."
 				);
+				self.report_limit_reached("soft_deadline");
+				break;
+			}
+			if failed >= MAX_FAILED_TRANSACTIONS {
+				debug!("{} transactions failed to apply, proceeding with proposing.", failed);
 				break;
 			}
 
 			let pending_tx_data = pending_tx.data().clone();
 			let pending_tx_hash = pending_tx.hash().clone();
+			let pending_tx_size = pending_tx_data.encoded_size();
+			if self.block_size_limit.map_or(false, |limit| block_size + pending_tx_size > limit) {
+				self.report_limit_reached("block_size");
+				if skipped < MAX_SKIPPED_TRANSACTIONS {
+					skipped += 1;
+					debug!(
+						"Block size limit reached, will try {} more transactions before quitting.",
+						MAX_SKIPPED_TRANSACTIONS - skipped,
+					);
+					continue;
+				} else {
+					debug!("Reached the block size limit, proceed with proposing.");
+					break;
+				}
+			}
+
 			trace!("[{:?}] Pushing to the block.", pending_tx_hash);
 			match sc_block_builder::BlockBuilder::push(&mut block_builder, pending_tx_data) {
 				Ok(()) => {
+					block_size += pending_tx_size;
 					debug!("[{:?}] Pushed to the block.", pending_tx_hash);
 				}
 				Err(ApplyExtrinsicFailed(Validity(e)))
@@ -270,9 +351,19 @@ impl<A, B, Block, C> Proposer<B, Block, C, A>
 						e
 					);
 				}
+				Err(ApplyExtrinsicFailed(Validity(TransactionValidityError::Unknown(e)))) => {
+					// The transaction may become valid later, so it stays in the pool.
+					debug!(
+						"[{:?}] Transaction validity could not be determined: {:?}",
+						pending_tx_hash,
+						e,
+					);
+					failed += 1;
+				}
 				Err(e) => {
 					debug!("[{:?}] Invalid transaction: {}", pending_tx_hash, e);
 					unqueue_invalid.push(pending_tx_hash);
+					failed += 1;
 				}
 			}
 		}
@@ -281,6 +372,14 @@ impl<A, B, Block, C> Proposer<B, Block, C, A>
 
 		let (block, storage_changes, proof) = block_builder.build()?.into_inner();
 
+		if hard_deadline.map_or(false, |hard_deadline| (self.now)() > hard_deadline) {
+			self.report_limit_reached("hard_deadline");
+			return Err(sp_blockchain::Error::Msg(format!(
+				"Hard deadline reached when building block on top of #{}, abandoning the proposal",
+				self.parent_number,
+			)))
+		}
+
 		self.metrics.report(
 			|metrics| {
 				metrics.number_of_transactions.set(block.extrinsics().len() as u64);
@@ -314,6 +413,10 @@ impl<A, B, Block, C> Proposer<B, Block, C, A>
 
 		Ok(Proposal { block, proof, storage_changes })
 	}
+
+	fn report_limit_reached(&self, limit: &str) {
+		self.metrics.report(|metrics| metrics.limits_reached.with_label_values(&[limit]).inc());
+	}
 }
 
 #[cfg(test)]
@@ -323,7 +426,7 @@ mod tests {
 	use parking_lot::Mutex;
 	use sp_consensus::{BlockOrigin, Proposer};
 	use substrate_test_runtime_client::{
-		prelude::*, TestClientBuilder, runtime::{Extrinsic, Transfer}, TestClientBuilderExt,
+		prelude::*, TestClientBuilder, runtime::{Block, Extrinsic, Transfer}, TestClientBuilderExt,
 	};
 	use sp_transaction_pool::{ChainEvent, MaintainedTransactionPool, TransactionSource};
 	use sc_transaction_pool::{BasicPool, FullChainApi};
@@ -444,6 +547,79 @@ mod tests {
 		).map(|r| r.block).unwrap();
 	}
 
+	#[test]
+	fn should_respect_block_size_limit() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::SpawnBlockingExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			Arc::new(FullChainApi::new(client.clone(), None)),
+			None,
+			spawner,
+			client.clone(),
+		);
+
+		futures::executor::block_on(
+			txpool.submit_at(&BlockId::number(0), SOURCE, vec![extrinsic(0), extrinsic(1)])
+		).unwrap();
+
+		futures::executor::block_on(
+			txpool.maintain(chain_event(
+				client.header(&BlockId::Number(0u64))
+					.expect("header get error")
+					.expect("there should be header")
+			))
+		);
+
+		let mut proposer_factory = ProposerFactory::new(client.clone(), txpool.clone(), None);
+		proposer_factory.set_block_size_limit(extrinsic(0).encoded_size() * 3 / 2);
+
+		let proposer = proposer_factory.init_with_now(
+			&client.header(&BlockId::number(0)).unwrap().unwrap(),
+			Box::new(move || time::Instant::now()),
+		);
+
+		let deadline = time::Duration::from_secs(9);
+		let block = futures::executor::block_on(
+			proposer.propose(Default::default(), Default::default(), deadline, RecordProof::No)
+		).map(|r| r.block).unwrap();
+
+		assert_eq!(block.extrinsics().len(), 1);
+		assert_eq!(txpool.ready().count(), 2);
+	}
+
+	#[test]
+	fn should_abandon_proposal_when_hard_deadline_is_reached() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::SpawnBlockingExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			Arc::new(FullChainApi::new(client.clone(), None)),
+			None,
+			spawner,
+			client.clone(),
+		);
+
+		let mut proposer_factory = ProposerFactory::new(client.clone(), txpool.clone(), None);
+		proposer_factory.set_hard_deadline(Percent::from_percent(100));
+
+		let cell = Mutex::new(time::Instant::now());
+		let proposer = proposer_factory.init_with_now(
+			&client.header(&BlockId::number(0)).unwrap().unwrap(),
+			Box::new(move || {
+				let mut value = cell.lock();
+				let old = *value;
+				*value = old + time::Duration::from_secs(2);
+				old
+			})
+		);
+
+		let deadline = time::Duration::from_secs(2);
+		assert!(futures::executor::block_on(
+			proposer.propose(Default::default(), Default::default(), deadline, RecordProof::No)
+		).is_err());
+	}
+
 	#[test]
 	fn proposed_storage_changes_should_match_execute_block_storage_changes() {
 		let (client, backend) = TestClientBuilder::new().build_with_backend();
@@ -592,4 +768,99 @@ mod tests {
 		let block = propose_block(&client, 1, 2, 5);
 		client.import(BlockOrigin::Own, block).unwrap();
 	}
+
+	/// Propose a block on top of genesis from a pool holding `xts`.
+	///
+	/// Returns the block and the number of transactions still ready in the pool.
+	fn propose_at_genesis(client: &Arc<TestClient>, xts: Vec<Extrinsic>) -> (Block, usize) {
+		let spawner = sp_core::testing::SpawnBlockingExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			Arc::new(FullChainApi::new(client.clone(), None)),
+			None,
+			spawner,
+			client.clone(),
+		);
+
+		futures::executor::block_on(
+			txpool.submit_at(&BlockId::number(0), SOURCE, xts)
+		).unwrap();
+
+		futures::executor::block_on(
+			txpool.maintain(chain_event(
+				client.header(&BlockId::Number(0u64))
+					.expect("header get error")
+					.expect("there should be header")
+			))
+		);
+
+		let mut proposer_factory = ProposerFactory::new(client.clone(), txpool.clone(), None);
+		let proposer = proposer_factory.init_with_now(
+			&client.header(&BlockId::number(0)).unwrap().unwrap(),
+			Box::new(move || time::Instant::now()),
+		);
+
+		let deadline = time::Duration::from_secs(9);
+		let block = futures::executor::block_on(
+			proposer.propose(Default::default(), Default::default(), deadline, RecordProof::No)
+		).map(|r| r.block).unwrap();
+
+		(block, txpool.ready().count())
+	}
+
+	#[test]
+	fn should_keep_transactions_with_unknown_validity_in_the_pool() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+
+		let (block, ready) = propose_at_genesis(
+			&client,
+			vec![extrinsic(0), Extrinsic::CannotLookup(vec![1])],
+		);
+
+		assert_eq!(block.extrinsics().len(), 1);
+		assert_eq!(ready, 2);
+	}
+
+	#[test]
+	fn should_remove_invalid_transactions_from_the_pool() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+
+		// Bob cannot afford this transfer, which is only found out when it is applied.
+		let unaffordable = Transfer {
+			amount: u64::max_value(),
+			nonce: 0,
+			from: AccountKeyring::Bob.into(),
+			to: Default::default(),
+		}.into_signed_tx();
+		let (block, ready) = propose_at_genesis(&client, vec![extrinsic(0), unaffordable]);
+
+		assert_eq!(block.extrinsics().len(), 1);
+		assert_eq!(ready, 1);
+	}
+
+	#[test]
+	fn should_stop_building_block_after_too_many_failed_transactions() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+
+		// The failing transactions have a higher priority, so they are all tried before the
+		// transfer is.
+		let mut xts: Vec<_> = (0..MAX_FAILED_TRANSACTIONS as u32)
+			.map(|i| Extrinsic::CannotLookup(i.encode()))
+			.collect();
+		xts.push(extrinsic(0));
+		let (block, ready) = propose_at_genesis(&client, xts);
+
+		assert_eq!(block.extrinsics().len(), 0);
+		assert_eq!(ready, MAX_FAILED_TRANSACTIONS + 1);
+
+		// One failure less is not enough to stop including transactions.
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let mut xts: Vec<_> = (1..MAX_FAILED_TRANSACTIONS as u32)
+			.map(|i| Extrinsic::CannotLookup(i.encode()))
+			.collect();
+		xts.push(extrinsic(0));
+		let (block, _) = propose_at_genesis(&client, xts);
+
+		assert_eq!(block.extrinsics().len(), 1);
+	}
 }
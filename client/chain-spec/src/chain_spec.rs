@@ -20,7 +20,8 @@
 
 use std::{borrow::Cow, fs::File, path::PathBuf, sync::Arc, collections::HashMap};
 use serde::{Serialize, Deserialize};
-use sp_core::storage::{StorageKey, StorageData, ChildInfo, Storage, StorageChild};
+use sp_core::{Bytes, H256, storage::{StorageKey, StorageData, ChildInfo, Storage, StorageChild}};
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT};
 use sp_runtime::BuildStorage;
 use serde_json as json;
 use crate::{RuntimeGenesis, ChainType, extension::GetExtension, Properties};
@@ -95,19 +96,8 @@ impl<G: RuntimeGenesis, E> BuildStorage for ChainSpec<G, E> {
 	fn build_storage(&self) -> Result<Storage, String> {
 		match self.genesis.resolve()? {
 			Genesis::Runtime(gc) => gc.build_storage(),
-			Genesis::Raw(RawGenesis { top: map, children_default: children_map }) => Ok(Storage {
-				top: map.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
-				children_default: children_map.into_iter().map(|(storage_key, child_content)| {
-					let child_info = ChildInfo::new_default(storage_key.0.as_slice());
-					(
-						storage_key.0,
-						StorageChild {
-							data: child_content.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
-							child_info,
-						},
-					)
-				}).collect(),
-			}),
+			Genesis::Raw(raw) => Ok(raw.into_storage()),
+			Genesis::RawFile(file) => file.load(),
 		}
 	}
 
@@ -130,12 +120,100 @@ pub struct RawGenesis {
 	pub children_default: HashMap<StorageKey, GenesisStorage>,
 }
 
+impl RawGenesis {
+	fn into_storage(self) -> Storage {
+		Storage {
+			top: self.top.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
+			children_default: self.children_default.into_iter().map(|(storage_key, child_content)| {
+				let child_info = ChildInfo::new_default(storage_key.0.as_slice());
+				(
+					storage_key.0,
+					StorageChild {
+						data: child_content.into_iter().map(|(k, v)| (k.0, v.0)).collect(),
+						child_info,
+					},
+				)
+			}).collect(),
+		}
+	}
+}
+
+impl From<Storage> for RawGenesis {
+	fn from(storage: Storage) -> Self {
+		let top = storage.top.into_iter()
+			.map(|(k, v)| (StorageKey(k), StorageData(v)))
+			.collect();
+		let children_default = storage.children_default.into_iter()
+			.map(|(sk, child)| (
+				StorageKey(sk),
+				child.data.into_iter()
+					.map(|(k, v)| (StorageKey(k), StorageData(v)))
+					.collect(),
+			))
+			.collect();
+
+		RawGenesis { top, children_default }
+	}
+}
+
+/// Raw storage content for genesis block, read from a storage dump.
+///
+/// The storage dump is either a JSON file holding a [`RawGenesis`], or a raw chain spec, e.g. the
+/// state of a block exported from another chain with `export-state`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct RawGenesisFile {
+	/// Path to the storage dump.
+	pub path: PathBuf,
+	/// The state root the storage dump is expected to have.
+	pub state_root: H256,
+}
+
+impl RawGenesisFile {
+	/// Read the storage dump and check it against the expected state root.
+	fn load(&self) -> Result<Storage, String> {
+		let file = File::open(&self.path)
+			.map_err(|e| format!("Error opening storage dump: {}", e))?;
+		let dump: json::Value = json::from_reader(file)
+			.map_err(|e| format!("Error parsing storage dump: {}", e))?;
+		let raw = match dump.get("genesis").and_then(|genesis| genesis.get("raw")) {
+			Some(raw) => raw.clone(),
+			None => dump,
+		};
+		let storage = json::from_value::<RawGenesis>(raw)
+			.map_err(|e| format!("Error parsing storage dump: {}", e))?
+			.into_storage();
+
+		let root = state_root(&storage);
+		if root != self.state_root {
+			return Err(format!(
+				"Storage dump has state root {:?}, expected {:?}",
+				root,
+				self.state_root,
+			))
+		}
+		Ok(storage)
+	}
+}
+
+/// The state root of the given `storage`, including the roots of its child tries.
+fn state_root(storage: &Storage) -> H256 {
+	let mut top = storage.top.clone();
+	for child in storage.children_default.values().filter(|child| !child.data.is_empty()) {
+		let root = BlakeTwo256::trie_root(child.data.clone().into_iter().collect());
+		top.insert(child.child_info.prefixed_storage_key().into_inner(), root.as_ref().to_vec());
+	}
+	BlakeTwo256::trie_root(top.into_iter().collect())
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
 enum Genesis<G> {
 	Runtime(G),
 	Raw(RawGenesis),
+	RawFile(RawGenesisFile),
 }
 
 /// A configuration of a client. Does not include runtime storage initialization.
@@ -153,6 +231,10 @@ struct ClientSpec<E> {
 	properties: Option<Properties>,
 	#[serde(flatten)]
 	extensions: E,
+	/// Runtime code to use instead of the on-chain one, keyed by the block number from which on
+	/// it is used.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	code_substitutes: HashMap<String, Bytes>,
 	// Never used, left only for backward compatibility.
 	consensus_engine: (),
 	#[serde(skip_serializing)]
@@ -222,6 +304,13 @@ impl<G, E> ChainSpec<G, E> {
 		&self.client_spec.extensions
 	}
 
+	/// Runtime code substitutes, keyed by the block number from which on they are used.
+	pub fn code_substitutes(&self) -> std::collections::BTreeMap<String, Vec<u8>> {
+		self.client_spec.code_substitutes.iter()
+			.map(|(number, code)| (number.clone(), code.0.clone()))
+			.collect()
+	}
+
 	/// Create hardcoded spec.
 	pub fn from_genesis<F: Fn() -> G + 'static + Send + Sync>(
 		name: &str,
@@ -243,6 +332,7 @@ impl<G, E> ChainSpec<G, E> {
 			protocol_id: protocol_id.map(str::to_owned),
 			properties,
 			extensions,
+			code_substitutes: HashMap::new(),
 			consensus_engine: (),
 			genesis: Default::default(),
 		};
@@ -295,22 +385,8 @@ impl<G: RuntimeGenesis, E: serde::Serialize + Clone + 'static> ChainSpec<G, E> {
 
 		};
 		let genesis = match (raw, self.genesis.resolve()?) {
-			(true, Genesis::Runtime(g)) => {
-				let storage = g.build_storage()?;
-				let top = storage.top.into_iter()
-					.map(|(k, v)| (StorageKey(k), StorageData(v)))
-					.collect();
-				let children_default = storage.children_default.into_iter()
-					.map(|(sk, child)| (
-						StorageKey(sk),
-						child.data.into_iter()
-							.map(|(k, v)| (StorageKey(k), StorageData(v)))
-							.collect(),
-					))
-					.collect();
-
-				Genesis::Raw(RawGenesis { top, children_default })
-			},
+			(true, Genesis::Runtime(g)) => Genesis::Raw(g.build_storage()?.into()),
+			(true, Genesis::RawFile(file)) => Genesis::Raw(file.load()?.into()),
 			(_, genesis) => genesis,
 		};
 		let container = Container {
@@ -378,6 +454,10 @@ where
 	fn set_storage(&mut self, storage: Storage) {
 		self.genesis = GenesisSource::Storage(storage);
 	}
+
+	fn code_substitutes(&self) -> std::collections::BTreeMap<String, Vec<u8>> {
+		ChainSpec::code_substitutes(self)
+	}
 }
 
 #[cfg(test)]
@@ -430,4 +510,56 @@ mod tests {
 
 		assert_eq!(spec.extensions().my_property, "Test Extension");
 	}
+
+	#[test]
+	fn should_deserialize_code_substitutes() {
+		let spec = TestSpec2::from_json_bytes(Cow::Owned(
+			include_bytes!("../res/chain_spec2.json").to_vec()
+		)).unwrap();
+
+		let substitutes = spec.code_substitutes();
+		assert_eq!(substitutes.len(), 1);
+		assert_eq!(substitutes["10"], vec![1, 2]);
+	}
+
+	fn spec_with_storage_dump(state_root: H256) -> TestSpec {
+		let spec = json::json!({
+			"name": "Dump",
+			"id": "dump",
+			"bootNodes": [],
+			"telemetryEndpoints": null,
+			"protocolId": null,
+			"properties": null,
+			"consensusEngine": null,
+			"genesis": {
+				"rawFile": {
+					"path": "./res/raw_storage.json",
+					"stateRoot": state_root,
+				},
+			},
+		});
+		TestSpec::from_json_bytes(Cow::Owned(spec.to_string().into_bytes())).unwrap()
+	}
+
+	#[test]
+	fn should_build_genesis_from_storage_dump() {
+		let dump = File::open("./res/raw_storage.json").unwrap();
+		let expected = json::from_reader::<_, RawGenesis>(dump).unwrap().into_storage();
+		let spec = spec_with_storage_dump(state_root(&expected));
+
+		let storage = spec.build_storage().unwrap();
+		assert_eq!(storage.top, expected.top);
+		assert_eq!(storage.children_default[&b"child"[..]].data.len(), 1);
+
+		let raw: json::Value = json::from_str(&spec.as_json(true).unwrap()).unwrap();
+		assert_eq!(raw["genesis"]["raw"]["top"]["0x666f6f"], "0x626172");
+	}
+
+	#[test]
+	fn should_reject_storage_dump_with_wrong_state_root() {
+		let spec = spec_with_storage_dump(H256::repeat_byte(1));
+
+		assert!(spec.build_storage().unwrap_err().contains("state root"));
+		assert!(spec.as_json(true).is_err());
+	}
 }
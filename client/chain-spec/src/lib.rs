@@ -155,6 +155,12 @@ pub trait ChainSpec: BuildStorage + Send {
 	///
 	/// This will be used as storage at genesis.
 	fn set_storage(&mut self, storage: Storage);
+	/// Runtime code substitutes, keyed by the block number from which on they are used.
+	///
+	/// A substitute is used instead of the on-chain runtime code until the on-chain code is
+	/// upgraded, i.e. as long as the spec version of the on-chain code matches the one of the
+	/// substitute.
+	fn code_substitutes(&self) -> std::collections::BTreeMap<String, Vec<u8>>;
 }
 
 impl std::fmt::Debug for dyn ChainSpec {
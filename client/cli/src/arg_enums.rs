@@ -162,6 +162,29 @@ arg_enum! {
 	}
 }
 
+arg_enum! {
+	/// RocksDB compaction profile
+	#[allow(missing_docs)]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub enum DatabaseCompaction {
+		// Optimized for solid state drives.
+		Ssd,
+		// Optimized for hard drives.
+		Hdd,
+		// Detect the kind of drive the database is stored on.
+		Auto,
+	}
+}
+
+impl Into<sc_service::config::RocksDbCompaction> for DatabaseCompaction {
+	fn into(self) -> sc_service::config::RocksDbCompaction {
+		match self {
+			DatabaseCompaction::Ssd => sc_service::config::RocksDbCompaction::Ssd,
+			DatabaseCompaction::Hdd => sc_service::config::RocksDbCompaction::Hdd,
+			DatabaseCompaction::Auto => sc_service::config::RocksDbCompaction::Auto,
+		}
+	}
+}
 
 arg_enum! {
 	/// Whether off-chain workers are enabled.
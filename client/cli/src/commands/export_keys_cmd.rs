@@ -0,0 +1,88 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{CliConfiguration, error, params::{KeystoreParams, SharedParams}};
+use log::info;
+use sc_service::config::KeystoreConfig;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+/// The `export-keys` command used to export all keys of a keystore into a password encrypted
+/// bundle.
+#[derive(Debug, StructOpt)]
+pub struct ExportKeysCmd {
+	/// File to write the bundle to.
+	#[structopt(parse(from_os_str))]
+	pub output: PathBuf,
+
+	/// File that contains the password the bundle is encrypted with. Asked for interactively if
+	/// not given.
+	#[structopt(long = "bundle-password-filename", value_name = "PATH", parse(from_os_str))]
+	pub bundle_password_filename: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl ExportKeysCmd {
+	/// Run the `export-keys` command
+	pub fn run(&self, keystore: &KeystoreConfig) -> error::Result<()> {
+		let keystore = open_keystore(keystore)?;
+		let password = bundle_password(self.bundle_password_filename.as_ref())?;
+		let bundle = keystore.read().export_bundle(&password)
+			.map_err(|e| format!("Failed to export keys: {}", e))?;
+		fs::write(&self.output, bundle)?;
+		info!("Exported keys to {}", self.output.display());
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportKeysCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+}
+
+/// Open the file system keystore of the given configuration.
+pub(crate) fn open_keystore(config: &KeystoreConfig) -> error::Result<sc_keystore::KeyStorePtr> {
+	match config {
+		KeystoreConfig::Path { path, password } => sc_keystore::Store::open(path, password.clone())
+			.map_err(|e| format!("Failed to open keystore: {}", e).into()),
+		_ => Err("A keystore path is required".into()),
+	}
+}
+
+/// Read the bundle password from `file`, or from the terminal if no file is given.
+pub(crate) fn bundle_password(file: Option<&PathBuf>) -> error::Result<String> {
+	match file {
+		Some(file) => Ok(fs::read_to_string(file)?.trim_end_matches('\n').to_string()),
+		#[cfg(not(target_os = "unknown"))]
+		None => rpassword::read_password_from_tty(Some("Bundle password: "))
+			.map_err(|e| format!("{:?}", e).into()),
+		#[cfg(target_os = "unknown")]
+		None => Err("--bundle-password-filename is required".into()),
+	}
+}
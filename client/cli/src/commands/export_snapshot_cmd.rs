@@ -0,0 +1,85 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	CliConfiguration, error, params::{PruningParams, SharedParams, BlockNumberOrHash},
+};
+use log::info;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, str::FromStr, path::PathBuf, sync::Arc};
+use structopt::StructOpt;
+use sc_client_api::{StorageProvider, UsageProvider, BlockBackend};
+
+/// The `export-snapshot` command used to export the state of a given block into a snapshot
+/// directory.
+#[derive(Debug, StructOpt)]
+pub struct ExportSnapshotCmd {
+	/// Directory to write the snapshot to.
+	#[structopt(parse(from_os_str))]
+	pub output: PathBuf,
+
+	/// Block hash or number. Defaults to the last finalized block.
+	#[structopt(long = "block", value_name = "HASH or NUMBER")]
+	pub block: Option<BlockNumberOrHash>,
+
+	/// Maximum size of a snapshot chunk in bytes.
+	#[structopt(long = "chunk-size", value_name = "Bytes", default_value = "4194304")]
+	pub chunk_size: usize,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl ExportSnapshotCmd {
+	/// Run the `export-snapshot` command
+	pub async fn run<B, BA, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT,
+		C: UsageProvider<B> + StorageProvider<B, BA> + BlockBackend<B>,
+		BA: sc_client_api::backend::Backend<B>,
+		B::Hash: FromStr,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		info!("Exporting state snapshot to {}...", self.output.display());
+		let block_id = self.block.as_ref().map(|b| b.parse()).transpose()?;
+		let manifest = sc_service::chain_ops::export_state_snapshot(
+			client,
+			block_id,
+			&self.output,
+			self.chunk_size,
+		)?;
+		info!("Exported {} chunks", manifest.chunks.len());
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
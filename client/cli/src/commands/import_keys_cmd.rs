@@ -0,0 +1,67 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{CliConfiguration, error, params::{KeystoreParams, SharedParams}};
+use super::export_keys_cmd::{bundle_password, open_keystore};
+use log::info;
+use sc_service::config::KeystoreConfig;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+/// The `import-keys` command used to import a bundle written by `export-keys` into a keystore.
+#[derive(Debug, StructOpt)]
+pub struct ImportKeysCmd {
+	/// File holding the bundle.
+	#[structopt(parse(from_os_str))]
+	pub input: PathBuf,
+
+	/// File that contains the password the bundle is encrypted with. Asked for interactively if
+	/// not given.
+	#[structopt(long = "bundle-password-filename", value_name = "PATH", parse(from_os_str))]
+	pub bundle_password_filename: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl ImportKeysCmd {
+	/// Run the `import-keys` command
+	pub fn run(&self, keystore: &KeystoreConfig) -> error::Result<()> {
+		let keystore = open_keystore(keystore)?;
+		let bundle = fs::read(&self.input)?;
+		let password = bundle_password(self.bundle_password_filename.as_ref())?;
+		let imported = keystore.read().import_bundle(&bundle, &password)
+			.map_err(|e| format!("Failed to import keys: {}", e))?;
+		info!("Imported {} keys from {}", imported, self.input.display());
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ImportKeysCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+}
@@ -18,20 +18,28 @@
 mod build_spec_cmd;
 mod check_block_cmd;
 mod export_blocks_cmd;
+mod export_keys_cmd;
+mod export_snapshot_cmd;
 mod export_state_cmd;
 mod import_blocks_cmd;
+mod import_keys_cmd;
 mod purge_chain_cmd;
 mod revert_cmd;
 mod run_cmd;
+mod verify_snapshot_cmd;
 
 pub use self::build_spec_cmd::BuildSpecCmd;
 pub use self::check_block_cmd::CheckBlockCmd;
 pub use self::export_blocks_cmd::ExportBlocksCmd;
+pub use self::export_keys_cmd::ExportKeysCmd;
+pub use self::export_snapshot_cmd::ExportSnapshotCmd;
 pub use self::export_state_cmd::ExportStateCmd;
 pub use self::import_blocks_cmd::ImportBlocksCmd;
+pub use self::import_keys_cmd::ImportKeysCmd;
 pub use self::purge_chain_cmd::PurgeChainCmd;
 pub use self::revert_cmd::RevertCmd;
 pub use self::run_cmd::RunCmd;
+pub use self::verify_snapshot_cmd::VerifySnapshotCmd;
 use std::fmt::Debug;
 use structopt::StructOpt;
 
@@ -62,6 +70,18 @@ pub enum Subcommand {
 
 	/// Export state as raw chain spec.
 	ExportState(ExportStateCmd),
+
+	/// Export the state of a block as a snapshot.
+	ExportSnapshot(ExportSnapshotCmd),
+
+	/// Verify a state snapshot against its block header.
+	VerifySnapshot(VerifySnapshotCmd),
+
+	/// Export all keys of the keystore into a password encrypted bundle.
+	ExportKeys(ExportKeysCmd),
+
+	/// Import the keys of a bundle written by `export-keys` into the keystore.
+	ImportKeys(ImportKeysCmd),
 }
 
 // TODO: move to config.rs?
@@ -229,6 +249,18 @@ macro_rules! substrate_cli_subcommands {
 				}
 			}
 
+			fn auto_upgrade(&self) -> $crate::Result<bool> {
+				match self {
+					$($enum::$variant(cmd) => cmd.auto_upgrade()),*
+				}
+			}
+
+			fn rocksdb_tuning(&self) -> $crate::Result<::sc_service::config::RocksDbTuning> {
+				match self {
+					$($enum::$variant(cmd) => cmd.rocksdb_tuning()),*
+				}
+			}
+
 			fn database(&self) -> $crate::Result<::std::option::Option<$crate::Database>> {
 				match self {
 					$($enum::$variant(cmd) => cmd.database()),*
@@ -241,6 +273,12 @@ macro_rules! substrate_cli_subcommands {
 				}
 			}
 
+			fn state_node_cache_size(&self) -> $crate::Result<usize> {
+				match self {
+					$($enum::$variant(cmd) => cmd.state_node_cache_size()),*
+				}
+			}
+
 			fn state_cache_child_ratio(&self) -> $crate::Result<::std::option::Option<usize>> {
 				match self {
 					$($enum::$variant(cmd) => cmd.state_cache_child_ratio()),*
@@ -254,6 +292,24 @@ macro_rules! substrate_cli_subcommands {
 				}
 			}
 
+			fn keep_blocks(&self) -> $crate::Result<::sc_service::KeepBlocks> {
+				match self {
+					$($enum::$variant(cmd) => cmd.keep_blocks()),*
+				}
+			}
+
+			fn canonicalization_delay(&self) -> $crate::Result<u64> {
+				match self {
+					$($enum::$variant(cmd) => cmd.canonicalization_delay()),*
+				}
+			}
+
+			fn force_pruning_mode(&self) -> $crate::Result<bool> {
+				match self {
+					$($enum::$variant(cmd) => cmd.force_pruning_mode()),*
+				}
+			}
+
 			fn chain_id(&self, is_dev: bool) -> $crate::Result<String> {
 				match self {
 					$($enum::$variant(cmd) => cmd.chain_id(is_dev)),*
@@ -413,5 +469,6 @@ macro_rules! substrate_cli_subcommands {
 }
 
 substrate_cli_subcommands!(
-	Subcommand => BuildSpec, ExportBlocks, ImportBlocks, CheckBlock, Revert, PurgeChain, ExportState
+	Subcommand => BuildSpec, ExportBlocks, ImportBlocks, CheckBlock, Revert, PurgeChain, ExportState,
+	ExportSnapshot, VerifySnapshot, ExportKeys, ImportKeys
 );
@@ -0,0 +1,56 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CliConfiguration, error, params::SharedParams};
+use log::info;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// The `verify-snapshot` command used to check a snapshot written by `export-snapshot`.
+#[derive(Debug, StructOpt)]
+pub struct VerifySnapshotCmd {
+	/// Directory holding the snapshot.
+	#[structopt(parse(from_os_str))]
+	pub input: PathBuf,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl VerifySnapshotCmd {
+	/// Run the `verify-snapshot` command
+	pub fn run<B: BlockT>(&self) -> error::Result<()> {
+		let (header, storage) = sc_service::chain_ops::import_state_snapshot::<B>(&self.input)?;
+		info!(
+			"Snapshot of block #{} ({:?}) is valid: {} top keys, {} child tries",
+			header.number(),
+			header.hash(),
+			storage.top.len(),
+			storage.children_default.len(),
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for VerifySnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
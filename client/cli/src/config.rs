@@ -28,10 +28,11 @@ use names::{Generator, Name};
 use sc_client_api::execution_extensions::ExecutionStrategies;
 use sc_service::config::{
 	BasePath, Configuration, DatabaseConfig, ExtTransport, KeystoreConfig, NetworkConfiguration,
-	NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, Role, RpcMethods,
-	TaskExecutor, TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+	NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, KeepBlocks, Role,
+	RpcMethods, TaskExecutor, TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+	RocksDbTuning,
 };
-use sc_service::{ChainSpec, TracingReceiver};
+use sc_service::{ChainSpec, TracingReceiver, DEFAULT_CANONICALIZATION_DELAY};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 
@@ -41,6 +42,9 @@ pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
 /// default sub directory to store network config
 pub(crate) const DEFAULT_NETWORK_CONFIG_PATH: &'static str = "network";
 
+/// default sub directory to cache compiled runtimes
+pub(crate) const DEFAULT_WASM_CACHE_PATH: &'static str = "wasm-cache";
+
 /// A trait that allows converting an object to a Configuration
 pub trait CliConfiguration: Sized {
 	/// Get the SharedParams for this object
@@ -168,6 +172,24 @@ pub trait CliConfiguration: Sized {
 		Ok(self.database_params().and_then(|x| x.database()))
 	}
 
+	/// Get the RocksDB tuning.
+	///
+	/// By default this is retrieved from `DatabaseParams` if it is available. Otherwise the
+	/// defaults of `RocksDbTuning` are used.
+	fn rocksdb_tuning(&self) -> Result<RocksDbTuning> {
+		self.database_params()
+			.map(|x| x.rocksdb_tuning())
+			.unwrap_or(Ok(Default::default()))
+	}
+
+	/// Returns `Ok(true)` if an outdated database should be upgraded on startup.
+	///
+	/// By default this is retrieved from `DatabaseParams` if it is available. Otherwise it is
+	/// `true`.
+	fn auto_upgrade(&self) -> Result<bool> {
+		Ok(self.database_params().map(|x| !x.no_auto_upgrade).unwrap_or(true))
+	}
+
 	/// Get the database configuration object for the parameters provided
 	fn database_config(
 		&self,
@@ -179,6 +201,7 @@ pub trait CliConfiguration: Sized {
 			Database::RocksDb => DatabaseConfig::RocksDb {
 				path: base_path.join("db"),
 				cache_size,
+				tuning: self.rocksdb_tuning()?,
 			},
 			Database::SubDb => DatabaseConfig::SubDb {
 				path: base_path.join("subdb"),
@@ -198,6 +221,15 @@ pub trait CliConfiguration: Sized {
 			.unwrap_or(Default::default()))
 	}
 
+	/// Get the trie node cache size.
+	///
+	/// By default this is retrieved from `ImportParams` if it is available. Otherwise its `0`.
+	fn state_node_cache_size(&self) -> Result<usize> {
+		Ok(self.import_params()
+			.map(|x| x.state_node_cache_size())
+			.unwrap_or(Default::default()))
+	}
+
 	/// Get the state cache child ratio (if any).
 	///
 	/// By default this is `None`.
@@ -215,6 +247,34 @@ pub trait CliConfiguration: Sized {
 			.unwrap_or(Ok(Default::default()))
 	}
 
+	/// Get the block body pruning mode.
+	///
+	/// By default this is retrieved from `PruningParams` if it is available. Otherwise all block
+	/// bodies are kept.
+	fn keep_blocks(&self) -> Result<KeepBlocks> {
+		self.pruning_params()
+			.map(|x| x.keep_blocks())
+			.unwrap_or(Ok(KeepBlocks::All))
+	}
+
+	/// Get the canonicalization delay.
+	///
+	/// By default this is retrieved from `PruningParams` if it is available. Otherwise it is
+	/// `DEFAULT_CANONICALIZATION_DELAY`.
+	fn canonicalization_delay(&self) -> Result<u64> {
+		Ok(self.pruning_params()
+			.map(|x| x.canonicalization_delay())
+			.unwrap_or(DEFAULT_CANONICALIZATION_DELAY))
+	}
+
+	/// Returns `Ok(true)` if a pruning mode conflicting with the database should be accepted.
+	///
+	/// By default this is retrieved from `PruningParams` if it is available. Otherwise it is
+	/// `false`.
+	fn force_pruning_mode(&self) -> Result<bool> {
+		Ok(self.pruning_params().map(|x| x.force_pruning_mode).unwrap_or(false))
+	}
+
 	/// Get the chain ID (string).
 	///
 	/// By default this is retrieved from `SharedParams`.
@@ -448,10 +508,16 @@ pub trait CliConfiguration: Sized {
 			)?,
 			keystore: self.keystore_config(&config_dir)?,
 			database: self.database_config(&config_dir, database_cache_size, database)?,
+			auto_upgrade: self.auto_upgrade()?,
 			state_cache_size: self.state_cache_size()?,
+			state_node_cache_size: self.state_node_cache_size()?,
 			state_cache_child_ratio: self.state_cache_child_ratio()?,
 			pruning: self.pruning(unsafe_pruning, &role)?,
+			keep_blocks: self.keep_blocks()?,
+			canonicalization_delay: self.canonicalization_delay()?,
+			force_pruning_mode: self.force_pruning_mode()?,
 			wasm_method: self.wasm_method()?,
+			wasm_cache_path: Some(config_dir.join(DEFAULT_WASM_CACHE_PATH)),
 			execution_strategies: self.execution_strategies(is_dev, is_validator)?,
 			rpc_http: self.rpc_http()?,
 			rpc_ws: self.rpc_ws()?,
@@ -16,7 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::arg_enums::Database;
+use crate::arg_enums::{Database, DatabaseCompaction};
+use crate::error;
+use sc_service::config::RocksDbTuning;
 use structopt::StructOpt;
 
 /// Parameters for block import.
@@ -34,6 +36,30 @@ pub struct DatabaseParams {
 	/// Limit the memory the database cache can use.
 	#[structopt(long = "db-cache", value_name = "MiB")]
 	pub database_cache_size: Option<usize>,
+
+	/// Cache size of individual RocksDB columns, taken from the total database cache.
+	///
+	/// Comma separated list of `column=MiB` pairs, e.g. `state=1024,header=64`.
+	#[structopt(long = "db-column-cache", value_name = "COLUMN=MiB", use_delimiter = true)]
+	pub database_column_cache: Vec<String>,
+
+	/// Maximum number of files RocksDB keeps open.
+	#[structopt(long = "db-max-open-files", value_name = "COUNT")]
+	pub database_max_open_files: Option<i32>,
+
+	/// RocksDB compaction profile.
+	#[structopt(
+		long = "db-compaction",
+		value_name = "PROFILE",
+		possible_values = &DatabaseCompaction::variants(),
+		case_insensitive = true,
+		default_value = "Ssd",
+	)]
+	pub database_compaction: DatabaseCompaction,
+
+	/// Exit instead of upgrading a database created by an older version of the node.
+	#[structopt(long = "no-auto-upgrade")]
+	pub no_auto_upgrade: bool,
 }
 
 impl DatabaseParams {
@@ -46,4 +72,24 @@ impl DatabaseParams {
 	pub fn database_cache_size(&self) -> Option<usize> {
 		self.database_cache_size
 	}
+
+	/// Get the RocksDB tuning from the parameters
+	pub fn rocksdb_tuning(&self) -> error::Result<RocksDbTuning> {
+		let column_cache_sizes = self.database_column_cache.iter()
+			.map(|entry| -> error::Result<(String, usize)> {
+				let mut parts = entry.splitn(2, '=');
+				let name = parts.next().unwrap_or_default();
+				let size = parts.next().and_then(|size| size.parse().ok()).ok_or_else(||
+					error::Error::Input(format!("Invalid database column cache {:?}", entry))
+				)?;
+				Ok((name.to_string(), size))
+			})
+			.collect::<error::Result<_>>()?;
+
+		Ok(RocksDbTuning {
+			column_cache_sizes,
+			max_open_files: self.database_max_open_files,
+			compaction: self.database_compaction.into(),
+		})
+	}
 }
@@ -67,6 +67,14 @@ pub struct ImportParams {
 	)]
 	pub state_cache_size: usize,
 
+	/// Specify the size of the trie node cache shared between blocks.
+	#[structopt(
+		long = "state-node-cache-size",
+		value_name = "Bytes",
+		default_value = "33554432"
+	)]
+	pub state_node_cache_size: usize,
+
 	/// Comma separated list of targets for tracing.
 	#[structopt(long = "tracing-targets", value_name = "TARGETS")]
 	pub tracing_targets: Option<String>,
@@ -98,6 +106,11 @@ impl ImportParams {
 		self.state_cache_size
 	}
 
+	/// Specify the trie node cache size.
+	pub fn state_node_cache_size(&self) -> usize {
+		self.state_node_cache_size
+	}
+
 	/// Get the WASM execution method from the parameters
 	pub fn wasm_method(&self) -> sc_service::config::WasmExecutionMethod {
 		self.wasm_method.into()
@@ -55,6 +55,19 @@ pub struct OffchainWorkerParams {
 		value_name = "ENABLE_OFFCHAIN_INDEXING"
 	)]
 	pub indexing_enabled: bool,
+
+	/// Maximum number of offchain workers running at the same time.
+	///
+	/// Defaults to the number of CPUs.
+	#[structopt(long = "offchain-worker-threads", value_name = "COUNT")]
+	pub max_concurrent: Option<usize>,
+
+	/// Maximum number of offchain workers waiting for a free thread.
+	///
+	/// When this many workers are already waiting, the offchain workers of newly imported
+	/// blocks are skipped. By default they are queued without limit.
+	#[structopt(long = "offchain-worker-max-queued", value_name = "COUNT")]
+	pub max_queued: Option<usize>,
 }
 
 impl OffchainWorkerParams {
@@ -73,6 +86,11 @@ impl OffchainWorkerParams {
 
 		let indexing_enabled = enabled && self.indexing_enabled;
 
-		Ok(OffchainWorkerConfig { enabled, indexing_enabled })
+		Ok(OffchainWorkerConfig {
+			enabled,
+			indexing_enabled,
+			max_concurrent: self.max_concurrent,
+			max_queued: self.max_queued,
+		})
 	}
 }
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::error;
-use sc_service::{PruningMode, Role};
+use sc_service::{PruningMode, KeepBlocks, Role, DEFAULT_CANONICALIZATION_DELAY};
 use structopt::StructOpt;
 
 /// Parameters to define the pruning mode
@@ -30,6 +30,24 @@ pub struct PruningParams {
 	/// 256 blocks.
 	#[structopt(long = "pruning", value_name = "PRUNING_MODE")]
 	pub pruning: Option<String>,
+
+	/// Specify the block body pruning mode, a number of finalized blocks to keep or 'archive'.
+	///
+	/// Default is to keep the bodies and justifications of all blocks.
+	#[structopt(long = "blocks-pruning", value_name = "COUNT")]
+	pub blocks_pruning: Option<String>,
+
+	/// Specify the number of blocks after which the state of a block is canonicalized.
+	///
+	/// Forks older than this can no longer be imported. Default is 4096 blocks.
+	#[structopt(long = "canonicalization-delay", value_name = "COUNT")]
+	pub canonicalization_delay: Option<u64>,
+
+	/// Start even if the state pruning mode differs from the one the database was created with.
+	///
+	/// State that was already pruned will not become available again.
+	#[structopt(long = "force-pruning-mode")]
+	pub force_pruning_mode: bool,
 }
 
 impl PruningParams {
@@ -58,4 +76,20 @@ impl PruningParams {
 			}
 		})
 	}
+
+	/// Get the block body pruning value from the parameters
+	pub fn keep_blocks(&self) -> error::Result<KeepBlocks> {
+		Ok(match &self.blocks_pruning {
+			Some(ref s) if s == "archive" => KeepBlocks::All,
+			None => KeepBlocks::All,
+			Some(s) => KeepBlocks::Some(s.parse().map_err(|_| {
+				error::Error::Input("Invalid blocks pruning mode specified".to_string())
+			})?),
+		})
+	}
+
+	/// Get the canonicalization delay from the parameters
+	pub fn canonicalization_delay(&self) -> u64 {
+		self.canonicalization_delay.unwrap_or(DEFAULT_CANONICALIZATION_DELAY)
+	}
 }
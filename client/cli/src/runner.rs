@@ -28,7 +28,7 @@ use log::info;
 use sc_service::{Configuration, TaskType, TaskManager};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 use sp_utils::metrics::{TOKIO_THREADS_ALIVE, TOKIO_THREADS_TOTAL};
-use std::{fmt::Debug, marker::PhantomData, str::FromStr, sync::Arc};
+use std::{fmt::Debug, marker::PhantomData, str::FromStr, sync::Arc, time::Duration};
 use sc_client_api::{UsageProvider, BlockBackend, StorageProvider};
 
 #[cfg(target_family = "unix")]
@@ -93,10 +93,13 @@ pub fn build_runtime() -> std::result::Result<tokio::runtime::Runtime, std::io::
 		.build()
 }
 
+/// Time given to the tasks of the node to end when shutting down.
+const SHUTDOWN_DEADLINE: Duration = Duration::from_secs(10);
+
 fn run_until_exit<FUT, ERR>(
 	mut tokio_runtime: tokio::runtime::Runtime, 
 	future: FUT, 
-	mut task_manager: TaskManager,
+	task_manager: TaskManager,
 ) -> Result<()>
 where
 	FUT: Future<Output = std::result::Result<(), ERR>> + future::Future,
@@ -105,11 +108,13 @@ where
 	let f = future.fuse();
 	pin_mut!(f);
 
-	tokio_runtime.block_on(main(f)).map_err(|e| e.to_string())?;
+	let result = tokio_runtime.block_on(main(f)).map_err(|e| e.to_string());
 
-	task_manager.terminate();
+	tokio_runtime.block_on(task_manager.clean_shutdown(SHUTDOWN_DEADLINE));
 	drop(tokio_runtime);
 
+	result?;
+
 	Ok(())
 }
 
@@ -222,6 +227,13 @@ impl<C: SubstrateCli> Runner<C> {
 				let (client, _, _, task_manager) = builder(self.config)?;
 				run_until_exit(self.tokio_runtime, cmd.run(client, chain_spec), task_manager)
 			},
+			Subcommand::ExportSnapshot(cmd) => {
+				let (client, _, _, task_manager) = builder(self.config)?;
+				run_until_exit(self.tokio_runtime, cmd.run(client), task_manager)
+			},
+			Subcommand::VerifySnapshot(cmd) => cmd.run::<B>(),
+			Subcommand::ExportKeys(cmd) => cmd.run(&self.config.keystore),
+			Subcommand::ImportKeys(cmd) => cmd.run(&self.config.keystore),
 		}
 	}
 
@@ -233,10 +245,11 @@ impl<C: SubstrateCli> Runner<C> {
 	) -> Result<()> {
 		self.print_node_infos();
 		let mut task_manager = initialise(self.config)?;
-		self.tokio_runtime.block_on(main(task_manager.future().fuse()))
-			.map_err(|e| e.to_string())?;
-		task_manager.terminate();
+		let result = self.tokio_runtime.block_on(main(task_manager.future().fuse()))
+			.map_err(|e| e.to_string());
+		self.tokio_runtime.block_on(task_manager.clean_shutdown(SHUTDOWN_DEADLINE));
 		drop(self.tokio_runtime);
+		result?;
 		Ok(())
 	}
 
@@ -64,7 +64,7 @@ use sp_runtime::{
 use sp_runtime::traits::{Block as BlockT, Header, DigestItemFor, Zero, Member};
 use sp_api::ProvideRuntimeApi;
 use sp_core::{traits::BareCryptoStore, crypto::Pair};
-use sp_inherents::{InherentDataProviders, InherentData};
+use sp_inherents::{CreateInherentDataProviders, InherentDataProviders, InherentData};
 use sp_timestamp::{
 	TimestampInherentData, InherentType as TimestampInherent, InherentError as TIError
 };
@@ -138,14 +138,17 @@ impl SlotCompatible for AuraSlotCompatible {
 }
 
 /// Start the aura worker. The returned future should be run in a futures executor.
-pub fn start_aura<B, C, SC, E, I, P, SO, CAW, Error>(
+///
+/// The inherent data providers of every slot are created by `create_inherent_data_providers`
+/// for the best block, with the aura inherent data provider added if they don't contain one.
+pub fn start_aura<B, C, SC, E, I, P, SO, CAW, Error, CIDP>(
 	slot_duration: SlotDuration,
 	client: Arc<C>,
 	select_chain: SC,
 	block_import: I,
 	env: E,
 	sync_oracle: SO,
-	inherent_data_providers: InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 	force_authoring: bool,
 	keystore: KeyStorePtr,
 	can_author_with: CAW,
@@ -163,6 +166,7 @@ pub fn start_aura<B, C, SC, E, I, P, SO, CAW, Error>(
 	Error: std::error::Error + Send + From<sp_consensus::Error> + 'static,
 	SO: SyncOracle + Send + Sync + Clone,
 	CAW: CanAuthorWith<B> + Send,
+	CIDP: CreateInherentDataProviders<B::Hash, ()>,
 {
 	let worker = AuraWorker {
 		client: client.clone(),
@@ -173,16 +177,16 @@ pub fn start_aura<B, C, SC, E, I, P, SO, CAW, Error>(
 		force_authoring,
 		_key_type: PhantomData::<P>,
 	};
-	register_aura_inherent_data_provider(
-		&inherent_data_providers,
-		slot_duration.slot_duration()
-	)?;
-	Ok(sc_consensus_slots::start_slot_worker::<_, _, _, _, _, AuraSlotCompatible, _>(
+	let create_inherent_data_providers = with_aura_inherent_data_provider::<B, _>(
+		create_inherent_data_providers,
+		slot_duration.slot_duration(),
+	);
+	Ok(sc_consensus_slots::start_slot_worker::<_, _, _, _, _, AuraSlotCompatible, _, _>(
 		slot_duration,
 		select_chain,
 		worker,
 		sync_oracle,
-		inherent_data_providers,
+		create_inherent_data_providers,
 		AuraSlotCompatible,
 		can_author_with,
 	))
@@ -492,13 +496,13 @@ fn check_header<C, B: BlockT, P: Pair>(
 }
 
 /// A verifier for Aura blocks.
-pub struct AuraVerifier<C, P> {
+pub struct AuraVerifier<C, P, CIDP> {
 	client: Arc<C>,
 	phantom: PhantomData<P>,
-	inherent_data_providers: sp_inherents::InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 }
 
-impl<C, P> AuraVerifier<C, P>
+impl<C, P, CIDP> AuraVerifier<C, P, CIDP>
 	where P: Send + Sync + 'static
 {
 	fn check_inherents<B: BlockT>(
@@ -506,6 +510,7 @@ impl<C, P> AuraVerifier<C, P>
 		block: B,
 		block_id: BlockId<B>,
 		inherent_data: InherentData,
+		inherent_data_providers: &InherentDataProviders,
 		timestamp_now: u64,
 	) -> Result<(), Error<B>>
 		where C: ProvideRuntimeApi<B>, C::Api: BlockBuilderApi<B, Error = sp_blockchain::Error>
@@ -543,7 +548,7 @@ impl<C, P> AuraVerifier<C, P>
 					},
 					Some(TIError::Other(e)) => Err(Error::Runtime(e.into())),
 					None => Err(Error::DataProvider(
-						self.inherent_data_providers.error_to_string(&i, &e)
+						inherent_data_providers.error_to_string(&i, &e)
 					)),
 				})
 		} else {
@@ -553,7 +558,7 @@ impl<C, P> AuraVerifier<C, P>
 }
 
 #[forbid(deprecated)]
-impl<B: BlockT, C, P> Verifier<B> for AuraVerifier<C, P> where
+impl<B: BlockT, C, P, CIDP> Verifier<B> for AuraVerifier<C, P, CIDP> where
 	C: ProvideRuntimeApi<B> +
 		Send +
 		Sync +
@@ -565,6 +570,7 @@ impl<B: BlockT, C, P> Verifier<B> for AuraVerifier<C, P> where
 	P: Pair + Send + Sync + 'static,
 	P::Public: Send + Sync + Hash + Eq + Clone + Decode + Encode + Debug + 'static,
 	P::Signature: Encode + Decode,
+	CIDP: CreateInherentDataProviders<B::Hash, ()>,
 {
 	fn verify(
 		&mut self,
@@ -573,13 +579,17 @@ impl<B: BlockT, C, P> Verifier<B> for AuraVerifier<C, P> where
 		justification: Option<Justification>,
 		mut body: Option<Vec<B::Extrinsic>>,
 	) -> Result<(BlockImportParams<B, ()>, Option<Vec<(CacheKeyId, Vec<u8>)>>), String> {
-		let mut inherent_data = self.inherent_data_providers
+		let hash = header.hash();
+		let parent_hash = *header.parent_hash();
+		// verification runs on a blocking task of the import queue.
+		let inherent_data_providers = futures::executor::block_on(
+			self.create_inherent_data_providers.create_inherent_data_providers(parent_hash, ())
+		).map_err(|e| e.into_string())?;
+		let mut inherent_data = inherent_data_providers
 			.create_inherent_data()
 			.map_err(|e| e.into_string())?;
 		let (timestamp_now, slot_now, _) = AuraSlotCompatible.extract_timestamp_and_slot(&inherent_data)
 			.map_err(|e| format!("Could not extract timestamp and slot: {:?}", e))?;
-		let hash = header.hash();
-		let parent_hash = *header.parent_hash();
 		let authorities = authorities(self.client.as_ref(), &BlockId::Hash(parent_hash))
 			.map_err(|e| format!("Could not fetch authorities at {:?}: {:?}", parent_hash, e))?;
 
@@ -615,6 +625,7 @@ impl<B: BlockT, C, P> Verifier<B> for AuraVerifier<C, P> where
 							block.clone(),
 							BlockId::Hash(parent_hash),
 							inherent_data,
+							&inherent_data_providers,
 							timestamp_now,
 						).map_err(|e| e.to_string())?;
 					}
@@ -720,17 +731,32 @@ pub type AuraImportQueue<B, Transaction> = BasicQueue<B, Transaction>;
 fn register_aura_inherent_data_provider(
 	inherent_data_providers: &InherentDataProviders,
 	slot_duration: u64,
-) -> Result<(), sp_consensus::Error> {
+) -> Result<(), sp_inherents::Error> {
 	if !inherent_data_providers.has_provider(&INHERENT_IDENTIFIER) {
-		inherent_data_providers
-			.register_provider(InherentDataProvider::new(slot_duration))
-			.map_err(Into::into)
-			.map_err(sp_consensus::Error::InherentData)
+		inherent_data_providers.register_provider(InherentDataProvider::new(slot_duration))
 	} else {
 		Ok(())
 	}
 }
 
+/// Register the aura inherent data provider with all the providers created by
+/// `create_inherent_data_providers`, if not registered already.
+fn with_aura_inherent_data_provider<B: BlockT, CIDP>(
+	create_inherent_data_providers: CIDP,
+	slot_duration: u64,
+) -> impl CreateInherentDataProviders<B::Hash, ()> where
+	CIDP: CreateInherentDataProviders<B::Hash, ()>,
+{
+	move |parent: B::Hash, ()| {
+		let providers = create_inherent_data_providers.create_inherent_data_providers(parent, ());
+		async move {
+			let providers = providers.await?;
+			register_aura_inherent_data_provider(&providers, slot_duration)?;
+			Ok::<_, sp_inherents::Error>(providers)
+		}
+	}
+}
+
 /// A block-import handler for Aura.
 pub struct AuraBlockImport<Block: BlockT, C, I: BlockImport<Block>, P> {
 	inner: I,
@@ -815,13 +841,16 @@ impl<Block: BlockT, C, I, P> BlockImport<Block> for AuraBlockImport<Block, C, I,
 }
 
 /// Start an import queue for the Aura consensus algorithm.
-pub fn import_queue<B, I, C, P, S>(
+///
+/// The inherent data providers checking a block are created by `create_inherent_data_providers`
+/// for its parent, with the aura inherent data provider added if they don't contain one.
+pub fn import_queue<B, I, C, P, S, CIDP>(
 	slot_duration: SlotDuration,
 	block_import: I,
 	justification_import: Option<BoxJustificationImport<B>>,
 	finality_proof_import: Option<BoxFinalityProofImport<B>>,
 	client: Arc<C>,
-	inherent_data_providers: InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 	spawner: &S,
 	registry: Option<&Registry>,
 ) -> Result<AuraImportQueue<B, sp_api::TransactionFor<C, B>>, sp_consensus::Error> where
@@ -834,13 +863,16 @@ pub fn import_queue<B, I, C, P, S>(
 	P::Public: Clone + Eq + Send + Sync + Hash + Debug + Encode + Decode,
 	P::Signature: Encode + Decode,
 	S: sp_core::traits::SpawnNamed,
+	CIDP: CreateInherentDataProviders<B::Hash, ()> + 'static,
 {
-	register_aura_inherent_data_provider(&inherent_data_providers, slot_duration.get())?;
 	initialize_authorities_cache(&*client)?;
 
 	let verifier = AuraVerifier {
 		client: client.clone(),
-		inherent_data_providers,
+		create_inherent_data_providers: with_aura_inherent_data_provider::<B, _>(
+			create_inherent_data_providers,
+			slot_duration.get(),
+		),
 		phantom: PhantomData,
 	};
 
@@ -927,7 +959,7 @@ mod tests {
 	}
 
 	impl TestNetFactory for AuraTestNet {
-		type Verifier = AuraVerifier<PeersFullClient, AuthorityPair>;
+		type Verifier = AuraVerifier<PeersFullClient, AuthorityPair, InherentDataProviders>;
 		type PeerData = ();
 
 		/// Create new test network with peers and given config.
@@ -952,7 +984,7 @@ mod tests {
 					assert_eq!(slot_duration.get(), SLOT_DURATION);
 					AuraVerifier {
 						client,
-						inherent_data_providers,
+						create_inherent_data_providers: inherent_data_providers,
 						phantom: Default::default(),
 					}
 				},
@@ -1016,7 +1048,7 @@ mod tests {
 				&inherent_data_providers, slot_duration.get()
 			).expect("Registers aura inherent data provider");
 
-			aura_futures.push(start_aura::<_, _, _, _, _, AuthorityPair, _, _, _>(
+			aura_futures.push(start_aura::<_, _, _, _, _, AuthorityPair, _, _, _, _>(
 				slot_duration,
 				client.clone(),
 				select_chain,
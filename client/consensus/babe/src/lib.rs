@@ -91,7 +91,7 @@ use sp_runtime::{
 use sp_api::{ProvideRuntimeApi, NumberFor};
 use sc_keystore::KeyStorePtr;
 use parking_lot::Mutex;
-use sp_inherents::{InherentDataProviders, InherentData};
+use sp_inherents::{CreateInherentDataProviders, InherentDataProviders, InherentData};
 use sc_telemetry::{telemetry, CONSENSUS_TRACE, CONSENSUS_DEBUG};
 use sp_consensus::{
 	self, BlockImport, Environment, Proposer, BlockCheckParams,
@@ -323,7 +323,7 @@ impl std::ops::Deref for Config {
 }
 
 /// Parameters for BABE.
-pub struct BabeParams<B: BlockT, C, E, I, SO, SC, CAW> {
+pub struct BabeParams<B: BlockT, C, E, I, SO, SC, CAW, CIDP> {
 	/// The keystore that manages the keys of the node.
 	pub keystore: KeyStorePtr,
 
@@ -344,8 +344,11 @@ pub struct BabeParams<B: BlockT, C, E, I, SO, SC, CAW> {
 	/// A sync oracle
 	pub sync_oracle: SO,
 
-	/// Providers for inherent data.
-	pub inherent_data_providers: InherentDataProviders,
+	/// Creates the inherent data providers for every slot, given the best block.
+	///
+	/// The babe and uncles inherent data providers are added to the created providers if they
+	/// don't contain them.
+	pub create_inherent_data_providers: CIDP,
 
 	/// Force authoring of blocks even if we are offline
 	pub force_authoring: bool,
@@ -358,18 +361,18 @@ pub struct BabeParams<B: BlockT, C, E, I, SO, SC, CAW> {
 }
 
 /// Start the babe worker.
-pub fn start_babe<B, C, SC, E, I, SO, CAW, Error>(BabeParams {
+pub fn start_babe<B, C, SC, E, I, SO, CAW, Error, CIDP>(BabeParams {
 	keystore,
 	client,
 	select_chain,
 	env,
 	block_import,
 	sync_oracle,
-	inherent_data_providers,
+	create_inherent_data_providers,
 	force_authoring,
 	babe_link,
 	can_author_with,
-}: BabeParams<B, C, E, I, SO, SC, CAW>) -> Result<
+}: BabeParams<B, C, E, I, SO, SC, CAW, CIDP>) -> Result<
 	impl futures::Future<Output=()>,
 	sp_consensus::Error,
 > where
@@ -385,6 +388,7 @@ pub fn start_babe<B, C, SC, E, I, SO, CAW, Error>(BabeParams {
 	Error: std::error::Error + Send + From<ConsensusError> + From<I::Error> + 'static,
 	SO: SyncOracle + Send + Sync + Clone,
 	CAW: CanAuthorWith<B> + Send,
+	CIDP: CreateInherentDataProviders<B::Hash, ()>,
 {
 	let config = babe_link.config;
 	let worker = BabeWorker {
@@ -398,12 +402,23 @@ pub fn start_babe<B, C, SC, E, I, SO, CAW, Error>(BabeParams {
 		config: config.clone(),
 	};
 
-	register_babe_inherent_data_provider(&inherent_data_providers, config.slot_duration())?;
-	sc_consensus_uncles::register_uncles_inherent_data_provider(
-		client,
-		select_chain.clone(),
-		&inherent_data_providers,
-	)?;
+	let slot_duration = config.slot_duration();
+	let uncles_chain = select_chain.clone();
+	let create_inherent_data_providers = move |parent: B::Hash, ()| {
+		let providers = create_inherent_data_providers.create_inherent_data_providers(parent, ());
+		let client = client.clone();
+		let select_chain = uncles_chain.clone();
+		async move {
+			let providers = providers.await?;
+			register_babe_inherent_data_provider(&providers, slot_duration)?;
+			sc_consensus_uncles::register_uncles_inherent_data_provider(
+				client,
+				select_chain,
+				&providers,
+			).map_err(|e| e.to_string())?;
+			Ok::<_, sp_inherents::Error>(providers)
+		}
+	};
 
 	info!(target: "babe", "👶 Starting BABE Authorship worker");
 	Ok(sc_consensus_slots::start_slot_worker(
@@ -411,7 +426,7 @@ pub fn start_babe<B, C, SC, E, I, SO, CAW, Error>(BabeParams {
 		select_chain,
 		worker,
 		sync_oracle,
-		inherent_data_providers,
+		create_inherent_data_providers,
 		babe_link.time_source,
 		can_author_with,
 	))
@@ -720,16 +735,16 @@ impl<Block: BlockT> BabeLink<Block> {
 }
 
 /// A verifier for Babe blocks.
-pub struct BabeVerifier<Block: BlockT, Client, SelectChain> {
+pub struct BabeVerifier<Block: BlockT, Client, SelectChain, CIDP> {
 	client: Arc<Client>,
 	select_chain: SelectChain,
-	inherent_data_providers: sp_inherents::InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 	config: Config,
 	epoch_changes: SharedEpochChanges<Block, Epoch>,
 	time_source: TimeSource,
 }
 
-impl<Block, Client, SelectChain> BabeVerifier<Block, Client, SelectChain>
+impl<Block, Client, SelectChain, CIDP> BabeVerifier<Block, Client, SelectChain, CIDP>
 where
 	Block: BlockT,
 	Client: AuxStore + HeaderBackend<Block> + HeaderMetadata<Block> + ProvideRuntimeApi<Block>,
@@ -742,6 +757,7 @@ where
 		block: Block,
 		block_id: BlockId<Block>,
 		inherent_data: InherentData,
+		inherent_data_providers: &InherentDataProviders,
 	) -> Result<(), Error<Block>> {
 		let inherent_res = self.client.runtime_api().check_inherents(
 			&block_id,
@@ -753,7 +769,7 @@ where
 			inherent_res
 				.into_errors()
 				.try_for_each(|(i, e)| {
-					Err(Error::CheckInherents(self.inherent_data_providers.error_to_string(&i, &e)))
+					Err(Error::CheckInherents(inherent_data_providers.error_to_string(&i, &e)))
 				})
 		} else {
 			Ok(())
@@ -841,13 +857,15 @@ where
 	}
 }
 
-impl<Block, Client, SelectChain> Verifier<Block> for BabeVerifier<Block, Client, SelectChain>
+impl<Block, Client, SelectChain, CIDP> Verifier<Block>
+	for BabeVerifier<Block, Client, SelectChain, CIDP>
 where
 	Block: BlockT,
 	Client: HeaderMetadata<Block, Error = sp_blockchain::Error> + HeaderBackend<Block> + ProvideRuntimeApi<Block>
 		+ Send + Sync + AuxStore + ProvideCache<Block>,
 	Client::Api: BlockBuilderApi<Block, Error = sp_blockchain::Error> + BabeApi<Block, Error = sp_blockchain::Error>,
 	SelectChain: sp_consensus::SelectChain<Block>,
+	CIDP: CreateInherentDataProviders<Block::Hash, ()>,
 {
 	fn verify(
 		&mut self,
@@ -866,17 +884,22 @@ where
 		);
 
 		debug!(target: "babe", "We have {:?} logs in this header", header.digest().logs().len());
-		let mut inherent_data = self
-			.inherent_data_providers
+		let hash = header.hash();
+		let parent_hash = *header.parent_hash();
+
+		// verification runs on a blocking task of the import queue.
+		let inherent_data_providers = futures::executor::block_on(
+			self.create_inherent_data_providers.create_inherent_data_providers(parent_hash, ())
+		).map_err(Error::<Block>::Runtime)?;
+		register_babe_inherent_data_provider(&inherent_data_providers, self.config.slot_duration)
+			.map_err(Error::<Block>::Runtime)?;
+		let mut inherent_data = inherent_data_providers
 			.create_inherent_data()
 			.map_err(Error::<Block>::Runtime)?;
 
 		let (_, slot_now, _) = self.time_source.extract_timestamp_and_slot(&inherent_data)
 			.map_err(Error::<Block>::Extraction)?;
 
-		let hash = header.hash();
-		let parent_hash = *header.parent_hash();
-
 		let parent_header_metadata = self.client.header_metadata(parent_hash)
 			.map_err(Error::<Block>::FetchParentHeader)?;
 
@@ -932,6 +955,7 @@ where
 						block.clone(),
 						BlockId::Hash(parent_hash),
 						inherent_data,
+						&inherent_data_providers,
 					)?;
 
 					let (_, inner_body) = block.deconstruct();
@@ -974,13 +998,11 @@ pub type BabeImportQueue<B, Transaction> = BasicQueue<B, Transaction>;
 fn register_babe_inherent_data_provider(
 	inherent_data_providers: &InherentDataProviders,
 	slot_duration: u64,
-) -> Result<(), sp_consensus::Error> {
-	debug!(target: "babe", "Registering");
+) -> Result<(), sp_inherents::Error> {
 	if !inherent_data_providers.has_provider(&sp_consensus_babe::inherents::INHERENT_IDENTIFIER) {
+		debug!(target: "babe", "Registering");
 		inherent_data_providers
 			.register_provider(sp_consensus_babe::inherents::InherentDataProvider::new(slot_duration))
-			.map_err(Into::into)
-			.map_err(sp_consensus::Error::InherentData)
 	} else {
 		Ok(())
 	}
@@ -1358,14 +1380,17 @@ pub fn block_import<Client, Block: BlockT, I>(
 ///
 /// The block import object provided must be the `BabeBlockImport` or a wrapper
 /// of it, otherwise crucial import logic will be omitted.
-pub fn import_queue<Block: BlockT, Client, SelectChain, Inner>(
+///
+/// The inherent data providers checking a block are created by `create_inherent_data_providers`
+/// for its parent, with the babe inherent data provider added if they don't contain one.
+pub fn import_queue<Block: BlockT, Client, SelectChain, Inner, CIDP>(
 	babe_link: BabeLink<Block>,
 	block_import: Inner,
 	justification_import: Option<BoxJustificationImport<Block>>,
 	finality_proof_import: Option<BoxFinalityProofImport<Block>>,
 	client: Arc<Client>,
 	select_chain: SelectChain,
-	inherent_data_providers: InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 	spawner: &impl sp_core::traits::SpawnNamed,
 	registry: Option<&Registry>,
 ) -> ClientResult<BabeImportQueue<Block, sp_api::TransactionFor<Client, Block>>> where
@@ -1375,13 +1400,12 @@ pub fn import_queue<Block: BlockT, Client, SelectChain, Inner>(
 	Client: HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>,
 	Client::Api: BlockBuilderApi<Block> + BabeApi<Block> + ApiExt<Block, Error = sp_blockchain::Error>,
 	SelectChain: sp_consensus::SelectChain<Block> + 'static,
+	CIDP: CreateInherentDataProviders<Block::Hash, ()> + 'static,
 {
-	register_babe_inherent_data_provider(&inherent_data_providers, babe_link.config.slot_duration)?;
-
 	let verifier = BabeVerifier {
 		client,
 		select_chain,
-		inherent_data_providers,
+		create_inherent_data_providers,
 		config: babe_link.config,
 		epoch_changes: babe_link.epoch_changes,
 		time_source: babe_link.time_source,
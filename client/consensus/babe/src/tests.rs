@@ -220,7 +220,7 @@ type TestSelectChain = substrate_test_runtime_client::LongestChain<
 >;
 
 pub struct TestVerifier {
-	inner: BabeVerifier<TestBlock, PeersFullClient, TestSelectChain>,
+	inner: BabeVerifier<TestBlock, PeersFullClient, TestSelectChain, InherentDataProviders>,
 	mutator: Mutator,
 }
 
@@ -316,7 +316,7 @@ impl TestNetFactory for BabeTestNet {
 			inner: BabeVerifier {
 				client: client.clone(),
 				select_chain: longest_chain,
-				inherent_data_providers: data.inherent_data_providers.clone(),
+				create_inherent_data_providers: data.inherent_data_providers.clone(),
 				config: data.link.config.clone(),
 				epoch_changes: data.link.epoch_changes.clone(),
 				time_source: data.link.time_source.clone(),
@@ -424,7 +424,7 @@ fn run_one_test(
 			client,
 			env: environ,
 			sync_oracle: DummyOracle,
-			inherent_data_providers: data.inherent_data_providers.clone(),
+			create_inherent_data_providers: data.inherent_data_providers.clone(),
 			force_authoring: false,
 			babe_link: data.link.clone(),
 			keystore,
@@ -25,7 +25,7 @@ use sp_consensus::{
 	import_queue::{BasicQueue, CacheKeyId, Verifier, BoxBlockImport},
 };
 use sp_blockchain::HeaderBackend;
-use sp_inherents::InherentDataProviders;
+use sp_inherents::CreateInherentDataProviders;
 use sp_runtime::{traits::Block as BlockT, Justification};
 use sc_client_api::backend::{Backend as ClientBackend, Finalizer};
 use sc_transaction_pool::txpool;
@@ -88,14 +88,14 @@ pub fn import_queue<Block, Transaction>(
 }
 
 /// Creates the background authorship task for the manual seal engine.
-pub async fn run_manual_seal<B, CB, E, C, A, SC, S, T>(
+pub async fn run_manual_seal<B, CB, E, C, A, SC, S, T, CIDP>(
 	mut block_import: BoxBlockImport<B, T>,
 	mut env: E,
 	client: Arc<C>,
 	pool: Arc<txpool::Pool<A>>,
 	mut commands_stream: S,
 	select_chain: SC,
-	inherent_data_providers: InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 )
 	where
 		A: txpool::ChainApi<Block=B> + 'static,
@@ -107,6 +107,7 @@ pub async fn run_manual_seal<B, CB, E, C, A, SC, S, T>(
 		<E::Proposer as Proposer<B>>::Error: std::fmt::Display,
 		S: Stream<Item=EngineCommand<<B as BlockT>::Hash>> + Unpin + 'static,
 		SC: SelectChain<B> + 'static,
+		CIDP: CreateInherentDataProviders<<B as BlockT>::Hash, ()>,
 {
 	while let Some(command) = commands_stream.next().await {
 		match command {
@@ -125,7 +126,7 @@ pub async fn run_manual_seal<B, CB, E, C, A, SC, S, T>(
 						env: &mut env,
 						select_chain: &select_chain,
 						block_import: &mut block_import,
-						inherent_data_provider: &inherent_data_providers,
+						create_inherent_data_providers: &create_inherent_data_providers,
 						pool: pool.clone(),
 						client: client.clone(),
 					}
@@ -149,13 +150,13 @@ pub async fn run_manual_seal<B, CB, E, C, A, SC, S, T>(
 /// runs the background authorship task for the instant seal engine.
 /// instant-seal creates a new block for every transaction imported into
 /// the transaction pool.
-pub async fn run_instant_seal<B, CB, E, C, A, SC, T>(
+pub async fn run_instant_seal<B, CB, E, C, A, SC, T, CIDP>(
 	block_import: BoxBlockImport<B, T>,
 	env: E,
 	client: Arc<C>,
 	pool: Arc<txpool::Pool<A>>,
 	select_chain: SC,
-	inherent_data_providers: InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 )
 	where
 		A: txpool::ChainApi<Block=B> + 'static,
@@ -165,7 +166,8 @@ pub async fn run_instant_seal<B, CB, E, C, A, SC, T>(
 		E: Environment<B> + 'static,
 		E::Error: std::fmt::Display,
 		<E::Proposer as Proposer<B>>::Error: std::fmt::Display,
-		SC: SelectChain<B> + 'static
+		SC: SelectChain<B> + 'static,
+		CIDP: CreateInherentDataProviders<<B as BlockT>::Hash, ()>,
 {
 	// instant-seal creates blocks as soon as transactions are imported
 	// into the transaction pool.
@@ -187,7 +189,7 @@ pub async fn run_instant_seal<B, CB, E, C, A, SC, T>(
 		pool,
 		commands_stream,
 		select_chain,
-		inherent_data_providers,
+		create_inherent_data_providers,
 	).await
 }
 
@@ -35,13 +35,13 @@ use sp_consensus::{
 use sp_blockchain::HeaderBackend;
 use std::collections::HashMap;
 use std::time::Duration;
-use sp_inherents::InherentDataProviders;
+use sp_inherents::CreateInherentDataProviders;
 
 /// max duration for creating a proposal in secs
 const MAX_PROPOSAL_DURATION: u64 = 10;
 
 /// params for sealing a new block
-pub struct SealBlockParams<'a, B: BlockT, SC, HB, E, T, P: txpool::ChainApi> {
+pub struct SealBlockParams<'a, B: BlockT, SC, HB, E, T, P: txpool::ChainApi, CIDP> {
 	/// if true, empty blocks(without extrinsics) will be created.
 	/// otherwise, will return Error::EmptyTransactionPool.
 	pub create_empty: bool,
@@ -61,12 +61,12 @@ pub struct SealBlockParams<'a, B: BlockT, SC, HB, E, T, P: txpool::ChainApi> {
 	pub select_chain: &'a SC,
 	/// block import object
 	pub block_import: &'a mut BoxBlockImport<B, T>,
-	/// inherent data provider
-	pub inherent_data_provider: &'a InherentDataProviders,
+	/// creates the inherent data providers for the new block
+	pub create_inherent_data_providers: &'a CIDP,
 }
 
 /// seals a new block with the given params
-pub async fn seal_new_block<B, SC, HB, E, T, P>(
+pub async fn seal_new_block<B, SC, HB, E, T, P, CIDP>(
 	SealBlockParams {
 		create_empty,
 		finalize,
@@ -76,10 +76,10 @@ pub async fn seal_new_block<B, SC, HB, E, T, P>(
 		select_chain,
 		block_import,
 		env,
-		inherent_data_provider,
+		create_inherent_data_providers,
 		mut sender,
 		..
-	}: SealBlockParams<'_, B, SC, HB, E, T, P>
+	}: SealBlockParams<'_, B, SC, HB, E, T, P, CIDP>
 )
 	where
 		B: BlockT,
@@ -89,6 +89,7 @@ pub async fn seal_new_block<B, SC, HB, E, T, P>(
 		<E::Proposer as Proposer<B>>::Error: std::fmt::Display,
 		P: txpool::ChainApi<Block=B>,
 		SC: SelectChain<B>,
+		CIDP: CreateInherentDataProviders<<B as BlockT>::Hash, ()>,
 {
 	let future = async {
 		if pool.validated_pool().status().ready == 0 && !create_empty {
@@ -110,7 +111,10 @@ pub async fn seal_new_block<B, SC, HB, E, T, P>(
 
 		let proposer = env.init(&header)
 			.map_err(|err| Error::StringError(format!("{}", err))).await?;
-		let id = inherent_data_provider.create_inherent_data()?;
+		let id = create_inherent_data_providers
+			.create_inherent_data_providers(header.hash(), ())
+			.await?
+			.create_inherent_data()?;
 		let inherents_len = id.len();
 		let proposal = proposer.propose(id, Default::default(), Duration::from_secs(MAX_PROPOSAL_DURATION), false.into())
 			.map_err(|err| Error::StringError(format!("{}", err))).await?;
@@ -31,9 +31,9 @@ pub use aux_schema::{check_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND};
 
 use codec::{Decode, Encode};
 use sp_consensus::{BlockImport, Proposer, SyncOracle, SelectChain, CanAuthorWith, SlotData, RecordProof};
-use futures::{prelude::*, future::{self, Either}};
+use futures::{prelude::*, future};
 use futures_timer::Delay;
-use sp_inherents::{InherentData, InherentDataProviders};
+use sp_inherents::{CreateInherentDataProviders, InherentData};
 use log::{debug, error, info, warn};
 use sp_runtime::generic::BlockId;
 use sp_runtime::traits::{Block as BlockT, Header, HashFor, NumberFor};
@@ -337,12 +337,15 @@ pub trait SlotCompatible {
 ///
 /// Every time a new slot is triggered, `worker.on_slot` is called and the future it returns is
 /// polled until completion, unless we are major syncing.
-pub fn start_slot_worker<B, C, W, T, SO, SC, CAW>(
+///
+/// The inherent data of every slot is created by the providers `create_inherent_data_providers`
+/// creates for the best block at the start of the slot.
+pub fn start_slot_worker<B, C, W, T, SO, SC, CAW, CIDP>(
 	slot_duration: SlotDuration<T>,
 	client: C,
 	mut worker: W,
 	mut sync_oracle: SO,
-	inherent_data_providers: InherentDataProviders,
+	create_inherent_data_providers: CIDP,
 	timestamp_extractor: SC,
 	can_author_with: CAW,
 ) -> impl Future<Output = ()>
@@ -355,32 +358,36 @@ where
 	SC: SlotCompatible + Unpin,
 	T: SlotData + Clone,
 	CAW: CanAuthorWith<B> + Send,
+	CIDP: CreateInherentDataProviders<B::Hash, ()>,
 {
 	let SlotDuration(slot_duration) = slot_duration;
 
 	// rather than use a timer interval, we schedule our waits ourselves
-	Slots::<SC>::new(
+	let mut slots = Slots::<B, _, _, _>::new(
 		slot_duration.slot_duration(),
-		inherent_data_providers,
+		client,
+		create_inherent_data_providers,
 		timestamp_extractor,
-	).inspect_err(|e| debug!(target: "slots", "Faulty timer: {:?}", e))
-		.try_for_each(move |slot_info| {
+	);
+
+	async move {
+		loop {
+			let (slot_info, chain_head) = match slots.next_slot().await {
+				Ok(next) => next,
+				Err(err) => {
+					debug!(target: "slots", "Faulty timer: {:?}", err);
+					warn!(target: "slots", "Slots stream terminated with an error: {:?}", err);
+					return
+				}
+			};
+
 			// only propose when we are not syncing.
 			if sync_oracle.is_major_syncing() {
 				debug!(target: "slots", "Skipping proposal slot due to sync.");
-				return Either::Right(future::ready(Ok(())));
+				continue;
 			}
 
 			let slot_num = slot_info.number;
-			let chain_head = match client.best_chain() {
-				Ok(x) => x,
-				Err(e) => {
-					warn!(target: "slots", "Unable to author block in slot {}. \
-					no best block header: {:?}", slot_num, e);
-					return Either::Right(future::ready(Ok(())));
-				}
-			};
-
 			if let Err(err) = can_author_with.can_author_with(&BlockId::Hash(chain_head.hash())) {
 				warn!(
 					target: "slots",
@@ -389,22 +396,11 @@ where
 					slot_num,
 					err,
 				);
-				Either::Right(future::ready(Ok(())))
-			} else {
-				Either::Left(
-					worker.on_slot(chain_head, slot_info)
-						.map_err(|e| {
-							warn!(target: "slots", "Encountered consensus error: {:?}", e);
-						})
-						.or_else(|_| future::ready(Ok(())))
-				)
-			}
-		}).then(|res| {
-			if let Err(err) = res {
-				warn!(target: "slots", "Slots stream terminated with an error: {:?}", err);
+			} else if let Err(e) = worker.on_slot(chain_head, slot_info).await {
+				warn!(target: "slots", "Encountered consensus error: {:?}", e);
 			}
-			future::ready(())
-		})
+		}
+	}
 }
 
 /// A header which has been checked
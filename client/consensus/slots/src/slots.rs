@@ -19,11 +19,12 @@
 //! This is used instead of `futures_timer::Interval` because it was unreliable.
 
 use super::SlotCompatible;
-use sp_consensus::Error;
-use futures::{prelude::*, task::Context, task::Poll};
-use sp_inherents::{InherentData, InherentDataProviders};
+use sp_consensus::{Error, SelectChain};
+use sp_inherents::{CreateInherentDataProviders, InherentData};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use log::warn;
 
-use std::{pin::Pin, time::{Duration, Instant}};
+use std::{marker::PhantomData, time::{Duration, Instant}};
 use futures_timer::Delay;
 
 /// Returns current duration since unix epoch.
@@ -83,65 +84,81 @@ pub struct SlotInfo {
 	pub duration: u64,
 }
 
-/// A stream that returns every time there is a new slot.
-pub(crate) struct Slots<SC> {
+/// A source of the slots, returning every time there is a new slot.
+pub(crate) struct Slots<B, C, CIDP, SC> {
 	last_slot: u64,
 	slot_duration: u64,
 	inner_delay: Option<Delay>,
-	inherent_data_providers: InherentDataProviders,
+	client: C,
+	create_inherent_data_providers: CIDP,
 	timestamp_extractor: SC,
+	_block: PhantomData<B>,
 }
 
-impl<SC> Slots<SC> {
-	/// Create a new `Slots` stream.
+impl<B, C, CIDP, SC> Slots<B, C, CIDP, SC> {
+	/// Create a new `Slots` source.
 	pub fn new(
 		slot_duration: u64,
-		inherent_data_providers: InherentDataProviders,
+		client: C,
+		create_inherent_data_providers: CIDP,
 		timestamp_extractor: SC,
 	) -> Self {
 		Slots {
 			last_slot: 0,
 			slot_duration,
 			inner_delay: None,
-			inherent_data_providers,
+			client,
+			create_inherent_data_providers,
 			timestamp_extractor,
+			_block: PhantomData,
 		}
 	}
 }
 
-impl<SC: SlotCompatible + Unpin> Stream for Slots<SC> {
-	type Item = Result<SlotInfo, Error>;
-
-	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+impl<B, C, CIDP, SC> Slots<B, C, CIDP, SC> where
+	B: BlockT,
+	C: SelectChain<B>,
+	CIDP: CreateInherentDataProviders<B::Hash, ()>,
+	SC: SlotCompatible,
+{
+	/// Wait for the next slot and return it, together with the best block to build on.
+	///
+	/// The inherent data of the slot is created by the providers created for the best block.
+	pub async fn next_slot(&mut self) -> Result<(SlotInfo, B::Header), Error> {
 		loop {
 			let slot_duration = self.slot_duration;
-			self.inner_delay = match self.inner_delay.take() {
+			let inner_delay = match self.inner_delay.take() {
 				None => {
 					// schedule wait.
 					let wait_dur = time_until_next(duration_now(), slot_duration);
-					Some(Delay::new(wait_dur))
+					Delay::new(wait_dur)
 				}
-				Some(d) => Some(d),
+				Some(d) => d,
 			};
-
-			if let Some(ref mut inner_delay) = self.inner_delay {
-				match Future::poll(Pin::new(inner_delay), cx) {
-					Poll::Pending => return Poll::Pending,
-					Poll::Ready(()) => {}
-				}
-			}
+			inner_delay.await;
 
 			// timeout has fired.
 
-			let inherent_data = match self.inherent_data_providers.create_inherent_data() {
-				Ok(id) => id,
-				Err(err) => return Poll::Ready(Some(Err(sp_consensus::Error::InherentData(err)))),
+			let chain_head = match self.client.best_chain() {
+				Ok(x) => x,
+				Err(e) => {
+					warn!(
+						target: "slots",
+						"Unable to author block in slot. No best block header: {:?}",
+						e,
+					);
+					// try again in the next slot.
+					continue;
+				}
 			};
+
+			let inherent_data = self.create_inherent_data_providers
+				.create_inherent_data_providers(chain_head.hash(), ())
+				.await
+				.and_then(|providers| providers.create_inherent_data())
+				.map_err(Error::InherentData)?;
 			let result = self.timestamp_extractor.extract_timestamp_and_slot(&inherent_data);
-			let (timestamp, slot_num, offset) = match result {
-				Ok(v) => v,
-				Err(err) => return Poll::Ready(Some(Err(err))),
-			};
+			let (timestamp, slot_num, offset) = result?;
 			// reschedule delay for next slot.
 			let ends_in = offset +
 				time_until_next(Duration::from_millis(timestamp), slot_duration);
@@ -153,14 +170,14 @@ impl<SC: SlotCompatible + Unpin> Stream for Slots<SC> {
 				let last_slot = self.last_slot;
 				self.last_slot = slot_num;
 
-				break Poll::Ready(Some(Ok(SlotInfo {
+				break Ok((SlotInfo {
 					number: slot_num,
 					duration: self.slot_duration,
 					last_number: last_slot,
 					timestamp,
 					ends_at,
 					inherent_data,
-				})))
+				}, chain_head))
 			}
 		}
 	}
@@ -42,6 +42,7 @@ mod storage_cache;
 mod upgrade;
 mod utils;
 mod stats;
+mod metrics;
 #[cfg(feature = "with-parity-db")]
 mod parity_db;
 #[cfg(feature = "with-subdb")]
@@ -83,9 +84,13 @@ use crate::utils::{DatabaseType, Meta, meta_keys, read_db, read_meta};
 use crate::changes_tries_storage::{DbChangesTrieStorage, DbChangesTrieStorageTransaction};
 use sc_state_db::StateDb;
 use sp_blockchain::{CachedHeaderMetadata, HeaderMetadata, HeaderMetadataCache};
-use crate::storage_cache::{CachingState, SyncingCachingState, SharedCache, new_shared_cache};
+use crate::storage_cache::{
+	CachingState, SyncingCachingState, SharedCache, new_shared_cache, SharedNodeCache,
+	new_shared_node_cache,
+};
 use crate::stats::StateUsageStats;
 use log::{trace, debug, warn};
+use prometheus_endpoint::Registry;
 
 // Re-export the Database trait so that one can pass an implementation of it.
 pub use sp_database::Database;
@@ -99,6 +104,9 @@ const MIN_BLOCKS_TO_KEEP_CHANGES_TRIES_FOR: u32 = 32768;
 /// Default value for storage cache child ratio.
 const DEFAULT_CHILD_RATIO: (usize, usize) = (1, 10);
 
+/// Default number of blocks a block stays non-canonical before its state is canonicalized.
+pub const DEFAULT_CANONICALIZATION_DELAY: u64 = 4096;
+
 /// DB-backed patricia trie state, transaction type is an overlay of changes to commit.
 pub type DbState<B> = sp_state_machine::TrieBackend<
 	Arc<dyn sp_state_machine::Storage<HashFor<B>>>, HashFor<B>
@@ -264,12 +272,37 @@ pub struct DatabaseSettings {
 	pub state_cache_size: usize,
 	/// Ratio of cache size dedicated to child tries.
 	pub state_cache_child_ratio: Option<(usize, usize)>,
+	/// Size of the trie node cache shared between all states, in bytes.
+	pub state_node_cache_size: usize,
 	/// Pruning mode.
 	pub pruning: PruningMode,
+	/// Block body and justification pruning.
+	pub keep_blocks: KeepBlocks,
+	/// Open the database even if `pruning` differs from the mode it was created with.
+	///
+	/// State that was already pruned stays unavailable after such a change.
+	pub force_pruning_mode: bool,
+	/// Apply pending database migrations on startup instead of refusing to open the database.
+	pub auto_upgrade: bool,
 	/// Where to find the database.
 	pub source: DatabaseSettingsSrc,
 }
 
+/// How many finalized block bodies and justifications to keep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepBlocks {
+	/// Keep the bodies and justifications of all blocks.
+	All,
+	/// Keep the bodies and justifications of the last `n` finalized blocks.
+	Some(u32),
+}
+
+impl Default for KeepBlocks {
+	fn default() -> Self {
+		KeepBlocks::All
+	}
+}
+
 /// Where to find the database..
 #[derive(Debug, Clone)]
 pub enum DatabaseSettingsSrc {
@@ -279,6 +312,8 @@ pub enum DatabaseSettingsSrc {
 		path: PathBuf,
 		/// Cache size in MiB.
 		cache_size: usize,
+		/// Tuning of the cache split and RocksDB options.
+		tuning: RocksDbTuning,
 	},
 
 	/// Load a ParityDb database from a given path.
@@ -316,6 +351,37 @@ impl DatabaseSettingsSrc {
 	}
 }
 
+/// Tuning of a RocksDB database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RocksDbTuning {
+	/// Cache size in MiB of individual columns, by column name.
+	///
+	/// The rest of the total cache size is split between the other columns, with 90% of it going
+	/// to the state column. RocksDB sizes the write buffers of a column from its cache size.
+	pub column_cache_sizes: Vec<(String, usize)>,
+	/// Maximum number of files RocksDB keeps open. `None` uses the default limit.
+	pub max_open_files: Option<i32>,
+	/// Compaction profile.
+	pub compaction: RocksDbCompaction,
+}
+
+/// Compaction profile of a RocksDB database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RocksDbCompaction {
+	/// Optimized for solid state drives.
+	Ssd,
+	/// Optimized for hard drives.
+	Hdd,
+	/// Detect the kind of drive the database is stored on.
+	Auto,
+}
+
+impl Default for RocksDbCompaction {
+	fn default() -> Self {
+		RocksDbCompaction::Ssd
+	}
+}
+
 impl std::fmt::Display for DatabaseSettingsSrc {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let name = match self {
@@ -342,6 +408,21 @@ pub(crate) mod columns {
 	/// Offchain workers local storage
 	pub const OFFCHAIN: u32 = 9;
 	pub const CACHE: u32 = 10;
+
+	/// Names of the columns, as used to configure them.
+	pub const NAMES: &[(&str, u32)] = &[
+		("meta", META),
+		("state", STATE),
+		("state_meta", STATE_META),
+		("key_lookup", KEY_LOOKUP),
+		("header", HEADER),
+		("body", BODY),
+		("justification", JUSTIFICATION),
+		("changes_trie", CHANGES_TRIE),
+		("aux", AUX),
+		("offchain", OFFCHAIN),
+		("cache", CACHE),
+	];
 }
 
 struct PendingBlock<Block: BlockT> {
@@ -689,18 +770,26 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block> for Bloc
 struct StorageDb<Block: BlockT> {
 	pub db: Arc<dyn Database<DbHash>>,
 	pub state_db: StateDb<Block::Hash, Vec<u8>>,
+	node_cache: SharedNodeCache,
 	prefix_keys: bool,
 }
 
 impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for StorageDb<Block> {
 	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
-		if self.prefix_keys {
-			let key = prefixed_key::<HashFor<Block>>(key, prefix);
-			self.state_db.get(&key, self)
+		let key = if self.prefix_keys {
+			prefixed_key::<HashFor<Block>>(key, prefix)
 		} else {
-			self.state_db.get(key.as_ref(), self)
+			key.as_ref().to_vec()
+		};
+		if let Some(node) = self.node_cache.lock().get(&key) {
+			return Ok(Some(node))
 		}
-		.map_err(|e| format!("Database backend error: {:?}", e))
+		let node = self.state_db.get(&key, self)
+			.map_err(|e| format!("Database backend error: {:?}", e))?;
+		if let Some(node) = &node {
+			self.node_cache.lock().insert(key, node.clone());
+		}
+		Ok(node)
 	}
 }
 
@@ -782,7 +871,8 @@ pub struct Backend<Block: BlockT> {
 	shared_cache: SharedCache<Block>,
 	import_lock: Arc<RwLock<()>>,
 	is_archive: bool,
-	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo)>,
+	keep_blocks: KeepBlocks,
+	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo, (u64, u64))>,
 	state_usage: Arc<StateUsageStats>,
 }
 
@@ -791,19 +881,49 @@ impl<Block: BlockT> Backend<Block> {
 	///
 	/// The pruning window is how old a block must be before the state is pruned.
 	pub fn new(config: DatabaseSettings, canonicalization_delay: u64) -> ClientResult<Self> {
+		Self::new_with_metrics(config, canonicalization_delay, None)
+	}
+
+	/// Create a new instance of database backend, reporting database I/O to the given
+	/// prometheus `registry`.
+	pub fn new_with_metrics(
+		config: DatabaseSettings,
+		canonicalization_delay: u64,
+		registry: Option<&Registry>,
+	) -> ClientResult<Self> {
 		let db = crate::utils::open_database::<Block>(&config, DatabaseType::Full)?;
-		Self::from_database(db as Arc<_>, canonicalization_delay, &config)
+		let db: Arc<dyn Database<DbHash>> = match registry {
+			Some(registry) => Arc::new(metrics::MeteredDatabase::new(db, registry).map_err(|e|
+				sp_blockchain::Error::Backend(format!("Failed to register database metrics: {}", e))
+			)?),
+			None => db,
+		};
+		Self::from_database(db, canonicalization_delay, &config)
 	}
 
 	/// Create new memory-backed client backend for tests.
 	#[cfg(any(test, feature = "test-helpers"))]
 	pub fn new_test(keep_blocks: u32, canonicalization_delay: u64) -> Self {
+		Self::new_test_with_block_pruning(keep_blocks, KeepBlocks::All, canonicalization_delay)
+	}
+
+	/// Create new memory-backed client backend for tests, pruning block bodies as given.
+	#[cfg(any(test, feature = "test-helpers"))]
+	pub fn new_test_with_block_pruning(
+		keep_blocks: u32,
+		block_pruning: KeepBlocks,
+		canonicalization_delay: u64,
+	) -> Self {
 		let db = kvdb_memorydb::create(crate::utils::NUM_COLUMNS);
 		let db = sp_database::as_database(db);
 		let db_setting = DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
+			state_node_cache_size: 0,
 			pruning: PruningMode::keep_blocks(keep_blocks),
+			keep_blocks: block_pruning,
+			force_pruning_mode: false,
+			auto_upgrade: true,
 			source: DatabaseSettingsSrc::Custom(db),
 		};
 
@@ -818,17 +938,26 @@ impl<Block: BlockT> Backend<Block> {
 		let is_archive_pruning = config.pruning.is_archive();
 		let blockchain = BlockchainDb::new(db.clone())?;
 		let meta = blockchain.meta.clone();
-		let map_e = |e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from(
-			format!("State database error: {:?}", e)
-		);
-		let state_db: StateDb<_, _> = StateDb::new(
+		let map_e = |e: sc_state_db::Error<io::Error>| match e {
+			sc_state_db::Error::IncompatiblePruningModes { .. } => sp_blockchain::Error::from(
+				format!(
+					"{:?}. Start with the pruning mode the database was created with, \
+					or force the change if losing the pruned history is acceptable.",
+					e,
+				)
+			),
+			e => sp_blockchain::Error::from(format!("State database error: {:?}", e)),
+		};
+		let state_db: StateDb<_, _> = StateDb::open(
 			config.pruning.clone(),
 			!config.source.supports_ref_counting(),
+			config.force_pruning_mode,
 			&StateMetaDb(&*db),
 		).map_err(map_e)?;
 		let storage_db = StorageDb {
 			db: db.clone(),
 			state_db,
+			node_cache: new_shared_node_cache(config.state_node_cache_size),
 			prefix_keys: !config.source.supports_ref_counting(),
 		};
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
@@ -860,6 +989,7 @@ impl<Block: BlockT> Backend<Block> {
 			),
 			import_lock: Default::default(),
 			is_archive: is_archive_pruning,
+			keep_blocks: config.keep_blocks,
 			io_stats: FrozenForDuration::new(std::time::Duration::from_secs(1)),
 			state_usage: Arc::new(StateUsageStats::new()),
 		})
@@ -1316,6 +1446,8 @@ impl<Block: BlockT> Backend<Block> {
 			}
 		}
 
+		self.prune_blocks(transaction, f_num)?;
+
 		let new_displaced = self.blockchain.leaves.write().finalize_height(f_num);
 		match displaced {
 			x @ &mut None => *x = Some(new_displaced),
@@ -1324,6 +1456,31 @@ impl<Block: BlockT> Backend<Block> {
 
 		Ok(())
 	}
+
+	// remove the body and justification of the canonical block which just dropped out of the
+	// `keep_blocks` window after finalizing `finalized`.
+	fn prune_blocks(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		finalized: NumberFor<Block>,
+	) -> ClientResult<()> {
+		let keep = match self.keep_blocks {
+			KeepBlocks::All => return Ok(()),
+			// the last finalized block is always kept.
+			KeepBlocks::Some(keep) => NumberFor::<Block>::from(keep.max(1)),
+		};
+		if finalized < keep {
+			return Ok(())
+		}
+		let number = finalized - keep;
+		let hash = sc_client_api::blockchain::HeaderBackend::hash(&self.blockchain, number)?;
+		if let Some(hash) = hash {
+			let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
+			transaction.remove(columns::BODY, &lookup_key);
+			transaction.remove(columns::JUSTIFICATION, &lookup_key);
+		}
+		Ok(())
+	}
 }
 
 fn apply_state_commit(transaction: &mut Transaction<DbHash>, commit: sc_state_db::CommitSet<Vec<u8>>) {
@@ -1453,24 +1610,28 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 	}
 
 	fn usage_info(&self) -> Option<UsageInfo> {
-		let (io_stats, state_stats) = self.io_stats.take_or_else(||
-			(
-				// TODO: implement DB stats and cache size retrieval
-				kvdb::IoStats::empty(),
-				self.state_usage.take(),
-			)
-		);
+		let (io_stats, state_stats, (node_cache_hits, node_cache_misses)) =
+			self.io_stats.take_or_else(||
+				(
+					// TODO: implement DB stats and cache size retrieval
+					kvdb::IoStats::empty(),
+					self.state_usage.take(),
+					self.storage.node_cache.lock().take_stats(),
+				)
+			);
 		let database_cache = MemorySize::from_bytes(0);
 		let state_cache = MemorySize::from_bytes(
 			(*&self.shared_cache).lock().used_storage_cache_size(),
 		);
 		let state_db = self.storage.state_db.memory_info();
+		let node_cache = MemorySize::from_bytes(self.storage.node_cache.lock().used_size());
 
 		Some(UsageInfo {
 			memory: MemoryInfo {
 				state_cache,
 				database_cache,
 				state_db,
+				node_cache,
 			},
 			io: IoInfo {
 				transactions: io_stats.transactions,
@@ -1484,6 +1645,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				state_writes_cache: state_stats.overlay_writes.ops,
 				state_reads_cache: state_stats.cache_reads.ops,
 				state_writes_nodes: state_stats.nodes_writes.ops,
+				state_reads_nodes: node_cache_hits + node_cache_misses,
+				state_reads_nodes_cache: node_cache_hits,
 			},
 		})
 	}
@@ -1781,7 +1944,11 @@ pub(crate) mod tests {
 		let backend = Backend::<Block>::new(DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
+			state_node_cache_size: 0,
 			pruning: PruningMode::keep_blocks(1),
+			keep_blocks: KeepBlocks::All,
+			force_pruning_mode: false,
+			auto_upgrade: true,
 			source: DatabaseSettingsSrc::Custom(backing),
 		}, 0).unwrap();
 		assert_eq!(backend.blockchain().info().best_number, 9);
@@ -2288,6 +2455,32 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn prunes_block_bodies_outside_keep_window() {
+		let backend = Backend::<Block>::new_test_with_block_pruning(10, KeepBlocks::Some(2), 10);
+
+		let mut hashes = Vec::new();
+		let mut parent = Default::default();
+		for number in 0..5 {
+			parent = insert_header(&backend, number, parent, None, Default::default());
+			hashes.push(parent);
+		}
+		for window in hashes.windows(2) {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, BlockId::Hash(window[0])).unwrap();
+			op.mark_finalized(BlockId::Hash(window[1]), None).unwrap();
+			backend.commit_operation(op).unwrap();
+		}
+
+		let blockchain = backend.blockchain();
+		for hash in &hashes[..3] {
+			assert_eq!(blockchain.body(BlockId::Hash(*hash)).unwrap(), None);
+		}
+		for hash in &hashes[3..] {
+			assert_eq!(blockchain.body(BlockId::Hash(*hash)).unwrap(), Some(Vec::new()));
+		}
+	}
+
 	#[test]
 	fn test_finalize_non_sequential() {
 		let backend = Backend::<Block>::new_test(10, 10);
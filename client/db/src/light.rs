@@ -588,6 +588,7 @@ impl<Block> Storage<Block> for LightStorage<Block>
 				database_cache,
 				state_cache: Default::default(),
 				state_db: Default::default(),
+				node_cache: Default::default(),
 			},
 			io: IoInfo {
 				transactions: io_stats.transactions,
@@ -602,6 +603,8 @@ impl<Block> Storage<Block> for LightStorage<Block>
 				state_reads_cache: 0,
 				state_writes_cache: 0,
 				state_writes_nodes: 0,
+				state_reads_nodes: 0,
+				state_reads_nodes_cache: 0,
 			}
 		})
 	}
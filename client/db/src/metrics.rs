@@ -0,0 +1,188 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Database I/O metrics.
+
+use std::sync::Arc;
+use std::time::Instant;
+use prometheus_endpoint::{
+	register, PrometheusError, Registry, Opts, CounterVec, Histogram, HistogramOpts, U64,
+	exponential_buckets,
+};
+use sp_database::{Database, Change, ColumnId, Transaction, error};
+use crate::{DbHash, columns};
+
+/// Label used for preimages stored with `Database::store`.
+const PREIMAGE_LABEL: &str = "preimage";
+
+/// Prometheus metrics of database I/O.
+#[derive(Clone)]
+struct DatabaseMetrics {
+	reads: CounterVec<U64>,
+	read_bytes: CounterVec<U64>,
+	writes: CounterVec<U64>,
+	write_bytes: CounterVec<U64>,
+	commit_bytes: Histogram,
+	commit_time: Histogram,
+}
+
+impl DatabaseMetrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			reads: register(CounterVec::new(
+				Opts::new("database_reads", "Number of database reads per column"),
+				&["column"],
+			)?, registry)?,
+			read_bytes: register(CounterVec::new(
+				Opts::new("database_read_bytes", "Bytes read from the database per column"),
+				&["column"],
+			)?, registry)?,
+			writes: register(CounterVec::new(
+				Opts::new("database_writes", "Number of database writes and removals per column"),
+				&["column"],
+			)?, registry)?,
+			write_bytes: register(CounterVec::new(
+				Opts::new("database_write_bytes", "Bytes written to the database per column"),
+				&["column"],
+			)?, registry)?,
+			commit_bytes: register(Histogram::with_opts(
+				HistogramOpts::new("database_commit_bytes", "Size of database transactions")
+					.buckets(exponential_buckets(1024.0, 4.0, 10)?),
+			)?, registry)?,
+			commit_time: register(Histogram::with_opts(
+				HistogramOpts::new(
+					"database_commit_time",
+					"Time taken to commit database transactions, in seconds",
+				),
+			)?, registry)?,
+		})
+	}
+
+	fn tally_read(&self, label: &str, bytes: usize) {
+		self.reads.with_label_values(&[label]).inc();
+		self.read_bytes.with_label_values(&[label]).inc_by(bytes as u64);
+	}
+}
+
+fn column_label(col: ColumnId) -> String {
+	columns::NAMES.iter()
+		.find(|(_, column)| *column == col)
+		.map(|(name, _)| name.to_string())
+		.unwrap_or_else(|| col.to_string())
+}
+
+/// Database wrapper reporting its I/O to prometheus.
+pub struct MeteredDatabase {
+	inner: Arc<dyn Database<DbHash>>,
+	metrics: DatabaseMetrics,
+}
+
+impl MeteredDatabase {
+	/// Wrap `inner`, registering the metrics in `registry`.
+	pub fn new(
+		inner: Arc<dyn Database<DbHash>>,
+		registry: &Registry,
+	) -> Result<Self, PrometheusError> {
+		Ok(MeteredDatabase { inner, metrics: DatabaseMetrics::register(registry)? })
+	}
+}
+
+impl Database<DbHash> for MeteredDatabase {
+	fn commit(&self, transaction: Transaction<DbHash>) -> error::Result<()> {
+		let mut total = 0;
+		for change in &transaction.0 {
+			let (label, bytes) = match change {
+				Change::Set(col, key, value) => (column_label(*col), key.len() + value.len()),
+				Change::Remove(col, key) => (column_label(*col), key.len()),
+				Change::Store(hash, preimage) =>
+					(PREIMAGE_LABEL.into(), hash.len() + preimage.len()),
+				Change::Release(hash) => (PREIMAGE_LABEL.into(), hash.len()),
+			};
+			self.metrics.writes.with_label_values(&[&label]).inc();
+			self.metrics.write_bytes.with_label_values(&[&label]).inc_by(bytes as u64);
+			total += bytes;
+		}
+		self.metrics.commit_bytes.observe(total as f64);
+
+		let started = Instant::now();
+		let result = self.inner.commit(transaction);
+		let elapsed = started.elapsed();
+		self.metrics.commit_time.observe(
+			elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1_000_000_000.0,
+		);
+		result
+	}
+
+	fn get(&self, col: ColumnId, key: &[u8]) -> Option<Vec<u8>> {
+		let value = self.inner.get(col, key);
+		self.metrics.tally_read(&column_label(col), value.as_ref().map_or(0, |v| v.len()));
+		value
+	}
+
+	fn with_get(&self, col: ColumnId, key: &[u8], f: &mut dyn FnMut(&[u8])) {
+		let mut bytes = 0;
+		self.inner.with_get(col, key, &mut |value| {
+			bytes = value.len();
+			f(value)
+		});
+		self.metrics.tally_read(&column_label(col), bytes);
+	}
+
+	fn lookup(&self, hash: &DbHash) -> Option<Vec<u8>> {
+		let preimage = self.inner.lookup(hash);
+		self.metrics.tally_read(PREIMAGE_LABEL, preimage.as_ref().map_or(0, |v| v.len()));
+		preimage
+	}
+
+	fn with_lookup(&self, hash: &DbHash, f: &mut dyn FnMut(&[u8])) {
+		let mut bytes = 0;
+		self.inner.with_lookup(hash, &mut |preimage| {
+			bytes = preimage.len();
+			f(preimage)
+		});
+		self.metrics.tally_read(PREIMAGE_LABEL, bytes);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn io_is_counted_per_column() {
+		let registry = Registry::new();
+		let inner = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let db = MeteredDatabase::new(inner, &registry).unwrap();
+
+		let mut transaction = Transaction::new();
+		transaction.set(columns::BODY, b"key", b"value");
+		transaction.set(columns::STATE, b"node", b"data");
+		transaction.remove(columns::STATE, b"gone");
+		db.commit(transaction).unwrap();
+		assert_eq!(db.get(columns::BODY, b"key"), Some(b"value".to_vec()));
+
+		let metrics = &db.metrics;
+		assert_eq!(metrics.writes.with_label_values(&["state"]).get(), 2);
+		assert_eq!(metrics.write_bytes.with_label_values(&["state"]).get(), 12);
+		assert_eq!(metrics.writes.with_label_values(&["body"]).get(), 1);
+		assert_eq!(metrics.reads.with_label_values(&["body"]).get(), 1);
+		assert_eq!(metrics.read_bytes.with_label_values(&["body"]).get(), 5);
+		assert_eq!(metrics.commit_bytes.get_sample_sum(), 20.0);
+		assert_eq!(metrics.commit_time.get_sample_count(), 1);
+	}
+}
@@ -243,6 +243,61 @@ pub fn new_shared_cache<B: BlockT>(
 	)
 }
 
+/// Shared cache of trie nodes, keyed by their database key.
+///
+/// Trie nodes are addressed by their hash, so unlike the value cache an entry never depends on the
+/// block it was read for and no per-block tracking is needed. Nodes of blocks that are not yet
+/// canonicalized are kept in memory by the state db and don't need to be cached either.
+pub struct NodeCache {
+	lru: LRUMap<Vec<u8>, Vec<u8>>,
+	hits: u64,
+	misses: u64,
+}
+
+impl NodeCache {
+	/// Get a node, counting the lookup as a cache hit or miss.
+	pub fn get(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+		match self.lru.get(key) {
+			Some(node) => {
+				self.hits += 1;
+				Some(node.clone())
+			},
+			None => {
+				self.misses += 1;
+				None
+			},
+		}
+	}
+
+	/// Cache a node read from the database.
+	pub fn insert(&mut self, key: Vec<u8>, node: Vec<u8>) {
+		if self.lru.2 > 0 {
+			self.lru.add(key, node);
+		}
+	}
+
+	/// Returns the used memory size of the node cache in bytes.
+	pub fn used_size(&self) -> usize {
+		self.lru.used_size()
+	}
+
+	/// Returns the number of cache hits and misses since the last call.
+	pub fn take_stats(&mut self) -> (u64, u64) {
+		(std::mem::replace(&mut self.hits, 0), std::mem::replace(&mut self.misses, 0))
+	}
+}
+
+pub type SharedNodeCache = Arc<Mutex<NodeCache>>;
+
+/// Create a new shared trie node cache with given max memory usage.
+pub fn new_shared_node_cache(node_cache_size: usize) -> SharedNodeCache {
+	Arc::new(Mutex::new(NodeCache {
+		lru: LRUMap(LinkedHashMap::new(), 0, node_cache_size),
+		hits: 0,
+		misses: 0,
+	}))
+}
+
 #[derive(Debug)]
 /// Accumulates a list of storage changed in a block.
 struct BlockChanges<B: Header> {
@@ -1314,6 +1369,31 @@ mod tests {
 		);
 		assert_eq!(s.storage(&key).unwrap(), None);
 	}
+
+	#[test]
+	fn node_cache_is_bounded_and_counts_hits() {
+		let cache = new_shared_node_cache(64);
+		let mut cache = cache.lock();
+
+		assert_eq!(cache.get(&[1]), None);
+		cache.insert(vec![1], vec![10; 16]);
+		assert_eq!(cache.get(&[1]), Some(vec![10; 16]));
+		assert_eq!(cache.take_stats(), (1, 1));
+		assert_eq!(cache.take_stats(), (0, 0));
+
+		// Inserting more than fits evicts the least recently used nodes.
+		for i in 2..10u8 {
+			cache.insert(vec![i], vec![i; 16]);
+		}
+		assert!(cache.used_size() <= 64);
+		assert_eq!(cache.get(&[1]), None);
+		assert_eq!(cache.get(&[9]), Some(vec![9; 16]));
+
+		// A zero sized cache stores nothing.
+		let empty = new_shared_node_cache(0);
+		empty.lock().insert(vec![1], vec![1]);
+		assert_eq!(empty.lock().used_size(), 0);
+	}
 }
 
 #[cfg(test)]
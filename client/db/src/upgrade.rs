@@ -26,28 +26,79 @@ use crate::utils::DatabaseType;
 /// Version file name.
 const VERSION_FILE_NAME: &'static str = "db_version";
 
+/// Oldest database version that can still be upgraded.
+const OLDEST_SUPPORTED_VERSION: u32 = 1;
+
+/// A migration of the database at the given path to the next version.
+///
+/// The version file is only bumped once a migration returns, so a migration interrupted by a
+/// crash is run again from the start on the next startup and has to cope with a partially
+/// migrated database.
+type Migration = fn(&Path, DatabaseType) -> sp_blockchain::Result<()>;
+
+/// Ordered database migrations, `MIGRATIONS[i]` upgrades from `OLDEST_SUPPORTED_VERSION + i`.
+const MIGRATIONS: &[Migration] = &[];
+
 /// Current db version.
-const CURRENT_VERSION: u32 = 1;
+const CURRENT_VERSION: u32 = OLDEST_SUPPORTED_VERSION + MIGRATIONS.len() as u32;
 
 /// Upgrade database to current version.
-pub fn upgrade_db<Block: BlockT>(db_path: &Path, _db_type: DatabaseType) -> sp_blockchain::Result<()> {
+///
+/// If `auto_upgrade` is false, an outdated database is left untouched and an error is returned.
+pub fn upgrade_db<Block: BlockT>(
+	db_path: &Path,
+	db_type: DatabaseType,
+	auto_upgrade: bool,
+) -> sp_blockchain::Result<()> {
+	apply_migrations(db_path, db_type, auto_upgrade, MIGRATIONS)
+}
+
+fn apply_migrations(
+	db_path: &Path,
+	db_type: DatabaseType,
+	auto_upgrade: bool,
+	migrations: &[Migration],
+) -> sp_blockchain::Result<()> {
+	let current_version = OLDEST_SUPPORTED_VERSION + migrations.len() as u32;
 	let is_empty = db_path.read_dir().map_or(true, |mut d| d.next().is_none());
-	if !is_empty {
-		let db_version = current_version(db_path)?;
-		match db_version {
-			0 => Err(sp_blockchain::Error::Backend(format!("Unsupported database version: {}", db_version)))?,
-			1 => (),
-			_ => Err(sp_blockchain::Error::Backend(format!("Future database version: {}", db_version)))?,
-		}
+	if is_empty {
+		return update_version(db_path, current_version)
 	}
 
-	update_version(db_path)
-}
+	let db_version = read_version(db_path)?;
+	if db_version < OLDEST_SUPPORTED_VERSION {
+		Err(sp_blockchain::Error::Backend(format!("Unsupported database version: {}", db_version)))?
+	}
+	if db_version > current_version {
+		Err(sp_blockchain::Error::Backend(format!("Future database version: {}", db_version)))?
+	}
+	if db_version < current_version && !auto_upgrade {
+		Err(sp_blockchain::Error::Backend(format!(
+			"Database at {} has version {} and needs to be upgraded to version {}. \
+			Back it up if needed and restart without `--no-auto-upgrade` to upgrade it.",
+			db_path.display(),
+			db_version,
+			current_version,
+		)))?
+	}
 
+	for version in db_version..current_version {
+		log::info!(
+			target: "db",
+			"Upgrading database from version {} to {}",
+			version,
+			version + 1,
+		);
+		migrations[(version - OLDEST_SUPPORTED_VERSION) as usize](db_path, db_type)?;
+		update_version(db_path, version + 1)?;
+	}
+
+	Ok(())
+}
 
 /// Reads current database version from the file at given path.
 /// If the file does not exist returns 0.
-fn current_version(path: &Path) -> sp_blockchain::Result<u32> {
+fn read_version(path: &Path) -> sp_blockchain::Result<u32> {
 	let unknown_version_err = || sp_blockchain::Error::Backend("Unknown database version".into());
 
 	match fs::File::open(version_file_path(path)) {
@@ -66,12 +117,18 @@ fn db_err(err: std::io::Error) -> sp_blockchain::Error {
 	sp_blockchain::Error::Backend(format!("{}", err))
 }
 
-/// Writes current database version to the file.
+/// Writes the given database version to the file.
 /// Creates a new file if the version file does not exist yet.
-fn update_version(path: &Path) -> sp_blockchain::Result<()> {
+///
+/// The version is written to a temporary file which is then renamed, so the version file is never
+/// left half written.
+fn update_version(path: &Path, version: u32) -> sp_blockchain::Result<()> {
 	fs::create_dir_all(path).map_err(db_err)?;
-	let mut file = fs::File::create(version_file_path(path)).map_err(db_err)?;
-	file.write_all(format!("{}", CURRENT_VERSION).as_bytes()).map_err(db_err)?;
+	let tmp_path = path.join(format!("{}.tmp", VERSION_FILE_NAME));
+	let mut file = fs::File::create(&tmp_path).map_err(db_err)?;
+	file.write_all(format!("{}", version).as_bytes()).map_err(db_err)?;
+	file.sync_all().map_err(db_err)?;
+	fs::rename(tmp_path, version_file_path(path)).map_err(db_err)?;
 	Ok(())
 }
 
@@ -85,7 +142,7 @@ fn version_file_path(path: &Path) -> PathBuf {
 #[cfg(test)]
 mod tests {
 	use sc_state_db::PruningMode;
-	use crate::{DatabaseSettings, DatabaseSettingsSrc};
+	use crate::{DatabaseSettings, DatabaseSettingsSrc, KeepBlocks};
 	use crate::tests::Block;
 	use super::*;
 
@@ -101,8 +158,16 @@ mod tests {
 		crate::utils::open_database::<Block>(&DatabaseSettings {
 			state_cache_size: 0,
 			state_cache_child_ratio: None,
+			state_node_cache_size: 0,
 			pruning: PruningMode::ArchiveAll,
-			source: DatabaseSettingsSrc::RocksDb { path: db_path.to_owned(), cache_size: 128 },
+			keep_blocks: KeepBlocks::All,
+			force_pruning_mode: false,
+			auto_upgrade: true,
+			source: DatabaseSettingsSrc::RocksDb {
+				path: db_path.to_owned(),
+				cache_size: 128,
+				tuning: Default::default(),
+			},
 		}, DatabaseType::Full).map(|_| ())
 	}
 
@@ -118,6 +183,37 @@ mod tests {
 		let db_dir = tempfile::TempDir::new().unwrap();
 		open_database(db_dir.path()).unwrap();
 		open_database(db_dir.path()).unwrap();
-		assert_eq!(current_version(db_dir.path()).unwrap(), CURRENT_VERSION);
+		assert_eq!(read_version(db_dir.path()).unwrap(), CURRENT_VERSION);
+	}
+
+	fn mark(path: &Path, name: &str) -> sp_blockchain::Result<()> {
+		fs::write(path.join(name), b"").map_err(db_err)
+	}
+
+	#[test]
+	fn migrations_are_applied_in_order_and_resumed() {
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let path = db_dir.path();
+		create_db(path, Some(OLDEST_SUPPORTED_VERSION));
+
+		let first: Migration = |path, _| mark(path, "first");
+		let failing: Migration = |_, _| Err(sp_blockchain::Error::Backend("interrupted".into()));
+		let second: Migration = |path, _| mark(path, "second");
+
+		// Without automatic upgrades nothing is touched.
+		assert!(apply_migrations(path, DatabaseType::Full, false, &[first, second]).is_err());
+		assert_eq!(read_version(path).unwrap(), OLDEST_SUPPORTED_VERSION);
+		assert!(!path.join("first").exists());
+
+		// An interrupted upgrade keeps the version of the last completed migration.
+		assert!(apply_migrations(path, DatabaseType::Full, true, &[first, failing]).is_err());
+		assert_eq!(read_version(path).unwrap(), OLDEST_SUPPORTED_VERSION + 1);
+
+		// Resuming only runs the remaining migrations.
+		fs::remove_file(path.join("first")).unwrap();
+		apply_migrations(path, DatabaseType::Full, true, &[first, second]).unwrap();
+		assert!(!path.join("first").exists());
+		assert!(path.join("second").exists());
+		assert_eq!(read_version(path).unwrap(), OLDEST_SUPPORTED_VERSION + 2);
 	}
 }
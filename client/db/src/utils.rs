@@ -33,6 +33,8 @@ use sp_runtime::traits::{
 	UniqueSaturatedFrom, UniqueSaturatedInto,
 };
 use crate::{DatabaseSettings, DatabaseSettingsSrc, Database, DbHash};
+#[cfg(any(feature = "with-kvdb-rocksdb", test))]
+use crate::RocksDbCompaction;
 
 /// Number of columns in the db. Must be the same for both full && light dbs.
 /// Otherwise RocksDb will fail to open database && check its type.
@@ -220,35 +222,63 @@ pub fn open_database<Block: BlockT>(
 
 	let db: Arc<dyn Database<DbHash>> = match &config.source {
 		#[cfg(any(feature = "with-kvdb-rocksdb", test))]
-		DatabaseSettingsSrc::RocksDb { path, cache_size } => {
+		DatabaseSettingsSrc::RocksDb { path, cache_size, tuning } => {
 			// first upgrade database to required version
-			crate::upgrade::upgrade_db::<Block>(&path, db_type)?;
+			crate::upgrade::upgrade_db::<Block>(&path, db_type, config.auto_upgrade)?;
 
 			// and now open database assuming that it has the latest version
 			let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
-			let state_col_budget = (*cache_size as f64 * 0.9) as usize;
-			let other_col_budget = (cache_size - state_col_budget) / (NUM_COLUMNS as usize - 1);
 			let mut memory_budget = std::collections::HashMap::new();
+			// Column names refer to the full node database, light databases use other columns.
+			let column_cache_sizes: &[(String, usize)] = match db_type {
+				DatabaseType::Full => &tuning.column_cache_sizes[..],
+				DatabaseType::Light => &[],
+			};
+			for (name, size) in column_cache_sizes {
+				let column = crate::columns::NAMES.iter()
+					.find(|(n, _)| n == name)
+					.map(|(_, column)| *column)
+					.ok_or_else(|| sp_blockchain::Error::Backend(
+						format!("Unknown database column {:?}", name),
+					))?;
+				memory_budget.insert(column, *size);
+			}
+			let configured: usize = memory_budget.values().sum();
+			let remaining = cache_size.saturating_sub(configured);
+			let state_col_budget = if memory_budget.contains_key(&crate::columns::STATE) {
+				0
+			} else {
+				(remaining as f64 * 0.9) as usize
+			};
+			let other_columns = NUM_COLUMNS as usize - memory_budget.len();
+			let other_col_budget = (remaining - state_col_budget) / other_columns.max(1);
 			let path = path.to_str()
 				.ok_or_else(|| sp_blockchain::Error::Backend("Invalid database path".into()))?;
 
 			for i in 0..NUM_COLUMNS {
 				if i == crate::columns::STATE {
-					memory_budget.insert(i, state_col_budget);
+					memory_budget.entry(i).or_insert(state_col_budget);
 				} else {
-					memory_budget.insert(i, other_col_budget);
+					memory_budget.entry(i).or_insert(other_col_budget);
 				}
 			}
 
 			db_config.memory_budget = memory_budget;
+			if let Some(max_open_files) = tuning.max_open_files {
+				db_config.max_open_files = max_open_files;
+			}
+			db_config.compaction = match tuning.compaction {
+				RocksDbCompaction::Ssd => kvdb_rocksdb::CompactionProfile::ssd(),
+				RocksDbCompaction::Hdd => kvdb_rocksdb::CompactionProfile::hdd(),
+				RocksDbCompaction::Auto =>
+					kvdb_rocksdb::CompactionProfile::auto(std::path::Path::new(path)),
+			};
 
 			log::trace!(
 				target: "db",
-				"Open RocksDB database at {}, state column budget: {} MiB, others({}) column cache: {} MiB",
+				"Open RocksDB database at {}, column cache budgets (MiB): {:?}",
 				path,
-				state_col_budget,
-				NUM_COLUMNS,
-				other_col_budget,
+				db_config.memory_budget,
 			);
 
 			let db = kvdb_rocksdb::Database::open(&db_config, &path)
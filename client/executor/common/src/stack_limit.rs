@@ -0,0 +1,70 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Deterministic stack depth metering.
+//!
+//! How deep a runtime can recurse before hitting the native stack limit depends on the executor
+//! and on how it compiles the code. To make stack exhaustion behave the same with every executor,
+//! the code is instrumented to count a logical stack height, derived only from the wasm code
+//! itself, and to trap as soon as that height exceeds a limit.
+
+use crate::error::WasmError;
+use parity_wasm::elements::{deserialize_buffer, serialize, Module as RawModule};
+
+/// The default limit of the logical stack height.
+///
+/// The height of a function frame is the number of its locals and arguments plus the maximal
+/// height of its operand stack.
+pub const DEFAULT_LOGICAL_STACK_LIMIT: u32 = 65536;
+
+/// Instrument `code` to trap once its logical stack height exceeds `limit`.
+pub fn inject_stack_limiter(code: &[u8], limit: u32) -> Result<Vec<u8>, WasmError> {
+	let module: RawModule = deserialize_buffer(code).map_err(|_| WasmError::CantDeserializeWasm)?;
+	let module = pwasm_utils::stack_height::inject_limiter(module, limit)
+		.map_err(|e| WasmError::Other(format!("cannot inject the stack limiter: {:?}", e)))?;
+	serialize(module).map_err(|e| WasmError::Other(format!("cannot serialize the module: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parity_wasm::{builder, elements::Instruction};
+
+	#[test]
+	fn limiter_is_injected() {
+		let module = builder::module()
+			.function()
+				.signature().build()
+				.body()
+					.with_instructions(parity_wasm::elements::Instructions::new(vec![
+						Instruction::Call(0),
+						Instruction::End,
+					]))
+					.build()
+				.build()
+			.build();
+		let code = serialize(module).unwrap();
+
+		let instrumented = inject_stack_limiter(&code, 1024).unwrap();
+		let instrumented: RawModule = deserialize_buffer(&instrumented).unwrap();
+		// The stack height is tracked in a new global.
+		assert_eq!(instrumented.global_section().map(|g| g.entries().len()), Some(1));
+
+		assert!(inject_stack_limiter(&[1, 2, 3], 1024).is_err());
+	}
+}
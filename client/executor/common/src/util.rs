@@ -55,6 +55,14 @@ impl WasmModuleInfo {
 			.unwrap_or(0)
 	}
 
+	/// The payload of the first custom section with the given `name`, if any.
+	pub fn custom_section(&self, name: &str) -> Option<&[u8]> {
+		self.raw_module
+			.custom_sections()
+			.find(|section| section.name() == name)
+			.map(|section| section.payload())
+	}
+
 	/// The number of imports of globals.
 	pub fn imported_globals_count(&self) -> u32 {
 		self.raw_module
@@ -302,6 +302,22 @@ sp_core::wasm_export_functions! {
 		assert_ne!(test_message, message_slice);
 		message_slice.copy_from_slice(test_message);
 	}
+
+	// Check that a fresh heap allocation of `len` bytes contains only zeroes.
+	// After the check succeeds the allocation is filled with non-zero bytes.
+	//
+	// Calling this twice on the same instance hands out the same allocation both times.
+	fn check_and_dirty_heap_allocation(len: u32) {
+		let mut data: Vec<u8> = Vec::with_capacity(len as usize);
+		let allocated = unsafe {
+			sp_std::slice::from_raw_parts_mut(data.as_mut_ptr(), len as usize)
+		};
+
+		assert!(allocated.iter().all(|b| *b == 0));
+		for b in allocated.iter_mut() {
+			*b = 0xff;
+		}
+	}
  }
 
 #[cfg(not(feature = "std"))]
@@ -47,6 +47,8 @@ fn call_in_wasm<E: Externalities>(
 		Some(1024),
 		HostFunctions::host_functions(),
 		8,
+		None,
+		Some(crate::stack_limit::DEFAULT_LOGICAL_STACK_LIMIT),
 	);
 	executor.call_in_wasm(
 		&WASM_BINARY[..],
@@ -129,6 +131,22 @@ fn call_yet_another_not_existing_function(wasm_method: WasmExecutionMethod) {
 	}
 }
 
+#[test_case(WasmExecutionMethod::Interpreted)]
+#[cfg_attr(feature = "wasmtime", test_case(WasmExecutionMethod::Compiled))]
+fn panic_message_is_reported(wasm_method: WasmExecutionMethod) {
+	let mut ext = TestExternalities::default();
+	let mut ext = ext.ext();
+
+	let error = call_in_wasm(
+		"test_panic",
+		&[],
+		wasm_method,
+		&mut ext,
+	).unwrap_err();
+	assert!(error.starts_with("Runtime panicked: "), "{}", error);
+	assert!(error.contains("test panic"), "{}", error);
+}
+
 #[test_case(WasmExecutionMethod::Interpreted)]
 #[cfg_attr(feature = "wasmtime", test_case(WasmExecutionMethod::Compiled))]
 fn panicking_should_work(wasm_method: WasmExecutionMethod) {
@@ -531,6 +549,8 @@ fn should_trap_when_heap_exhausted(wasm_method: WasmExecutionMethod) {
 		Some(17),  // `17` is the initial number of pages compiled into the binary.
 		HostFunctions::host_functions(),
 		8,
+		None,
+		None,
 	);
 	executor.call_in_wasm(
 		&WASM_BINARY[..],
@@ -551,6 +571,8 @@ fn returns_mutable_static(wasm_method: WasmExecutionMethod) {
 		&WASM_BINARY[..],
 		HostFunctions::host_functions(),
 		true,
+		None,
+		None,
 	).expect("Creates runtime");
 
 	let instance = runtime.new_instance().unwrap();
@@ -585,6 +607,8 @@ fn restoration_of_globals(wasm_method: WasmExecutionMethod) {
 		&WASM_BINARY[..],
 		HostFunctions::host_functions(),
 		true,
+		None,
+		None,
 	).expect("Creates runtime");
 	let instance = runtime.new_instance().unwrap();
 
@@ -605,6 +629,8 @@ fn heap_is_reset_between_calls(wasm_method: WasmExecutionMethod) {
 		&WASM_BINARY[..],
 		HostFunctions::host_functions(),
 		true,
+		None,
+		None,
 	).expect("Creates runtime");
 	let instance = runtime.new_instance().unwrap();
 
@@ -621,6 +647,28 @@ fn heap_is_reset_between_calls(wasm_method: WasmExecutionMethod) {
 	instance.call("check_and_set_in_heap", &params).unwrap();
 }
 
+#[test_case(WasmExecutionMethod::Interpreted)]
+#[cfg_attr(feature = "wasmtime", test_case(WasmExecutionMethod::Compiled))]
+fn memory_is_zeroed_between_calls(wasm_method: WasmExecutionMethod) {
+	let runtime = crate::wasm_runtime::create_wasm_runtime_with_code(
+		wasm_method,
+		1024,
+		&WASM_BINARY[..],
+		HostFunctions::host_functions(),
+		true,
+		None,
+		None,
+	).expect("Creates runtime");
+	let instance = runtime.new_instance().unwrap();
+
+	let params = (64u32 * 1024).encode();
+	instance.call("check_and_dirty_heap_allocation", &params).unwrap();
+
+	// The second call is handed out the allocation the first call filled, which must have been
+	// zeroed in between.
+	instance.call("check_and_dirty_heap_allocation", &params).unwrap();
+}
+
 #[test_case(WasmExecutionMethod::Interpreted)]
 #[cfg_attr(feature = "wasmtime", test_case(WasmExecutionMethod::Compiled))]
 fn parallel_execution(wasm_method: WasmExecutionMethod) {
@@ -629,6 +677,8 @@ fn parallel_execution(wasm_method: WasmExecutionMethod) {
 		Some(1024),
 		HostFunctions::host_functions(),
 		8,
+		None,
+		None,
 	));
 	let code_hash = blake2_256(WASM_BINARY).to_vec();
 	let threads: Vec<_> = (0..8).map(|_|
@@ -755,3 +805,24 @@ fn wasm_tracing_should_work(wasm_method: WasmExecutionMethod) {
 	assert_eq!(values.get("wasm").unwrap(), "true");
 	assert_eq!(values.get("is_valid_trace").unwrap(), "true");
 }
+
+#[cfg(feature = "wasmtime")]
+#[test]
+fn compiled_runtime_is_cached_on_disk() {
+	let cache_path = std::env::temp_dir()
+		.join(format!("substrate-wasm-cache-{}", std::process::id()));
+	let runtime = crate::wasm_runtime::create_wasm_runtime_with_code(
+		WasmExecutionMethod::Compiled,
+		1024,
+		&WASM_BINARY[..],
+		HostFunctions::host_functions(),
+		true,
+		Some(&cache_path),
+		None,
+	).expect("Creates runtime");
+	let instance = runtime.new_instance().unwrap();
+	assert_eq!(instance.call("test_empty_return", &[]).unwrap(), vec![0u8; 0]);
+
+	assert!(cache_path.join("cache-config.toml").exists());
+	let _ = std::fs::remove_dir_all(cache_path);
+}
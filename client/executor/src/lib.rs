@@ -47,7 +47,7 @@ pub use sp_core::traits::{Externalities, CallInWasm};
 pub use sp_wasm_interface;
 pub use wasm_runtime::WasmExecutionMethod;
 
-pub use sc_executor_common::{error, sandbox};
+pub use sc_executor_common::{error, sandbox, stack_limit};
 
 /// Provides runtime information.
 pub trait RuntimeInfo {
@@ -80,6 +80,8 @@ mod tests {
 			Some(8),
 			sp_io::SubstrateHostFunctions::host_functions(),
 			8,
+			None,
+			None,
 		);
 		let res = executor.call_in_wasm(
 			&WASM_BINARY[..],
@@ -26,9 +26,9 @@ use sp_core::{
 	NativeOrEncoded, traits::{CodeExecutor, Externalities, RuntimeCode, MissingHostFunctions},
 };
 use log::trace;
-use std::{result, panic::{UnwindSafe, AssertUnwindSafe}, sync::Arc};
+use std::{result, panic::{UnwindSafe, AssertUnwindSafe}, path::PathBuf, sync::Arc};
 use sp_wasm_interface::{HostFunctions, Function};
-use sc_executor_common::wasm_runtime::WasmInstance;
+use sc_executor_common::{stack_limit::DEFAULT_LOGICAL_STACK_LIMIT, wasm_runtime::WasmInstance};
 
 /// Default num of pages for the heap
 const DEFAULT_HEAP_PAGES: u64 = 1024;
@@ -58,6 +58,18 @@ pub fn with_externalities_safe<F, U>(ext: &mut dyn Externalities, f: F) -> Resul
 	)
 }
 
+/// Call `method` of `instance`, turning a panic reported by the runtime into
+/// `Error::RuntimePanicked` with the panic message.
+fn call_instance(instance: &dyn WasmInstance, method: &str, data: &[u8]) -> Result<Vec<u8>> {
+	// A message left behind by an earlier call must not be attributed to this one.
+	let _ = sp_io::take_panic_message();
+
+	instance.call(method, data).map_err(|e| match sp_io::take_panic_message() {
+		Some(message) => Error::RuntimePanicked(message),
+		None => e,
+	})
+}
+
 /// Delegate for dispatching a CodeExecutor call.
 ///
 /// By dispatching we mean that we execute a runtime function specified by it's name.
@@ -89,6 +101,10 @@ pub struct WasmExecutor {
 	cache: Arc<RuntimeCache>,
 	/// The size of the instances cache.
 	max_runtime_instances: usize,
+	/// The directory in which compiled runtimes are cached on disk.
+	cache_path: Option<PathBuf>,
+	/// The limit of the logical stack height runtimes are instrumented with.
+	stack_limit: Option<u32>,
 }
 
 impl WasmExecutor {
@@ -100,18 +116,31 @@ impl WasmExecutor {
 	///
 	/// `default_heap_pages` - Number of 64KB pages to allocate for Wasm execution.
 	/// 	Defaults to `DEFAULT_HEAP_PAGES` if `None` is provided.
+	///
+	/// `cache_path` - Directory in which compiled runtimes are cached on disk, if any.
+	///
+	/// `stack_limit` - Limit of the logical stack height the runtimes are instrumented with, if
+	/// 	any. See [`crate::stack_limit`].
 	pub fn new(
 		method: WasmExecutionMethod,
 		default_heap_pages: Option<u64>,
 		host_functions: Vec<&'static dyn Function>,
 		max_runtime_instances: usize,
+		cache_path: Option<PathBuf>,
+		stack_limit: Option<u32>,
 	) -> Self {
 		WasmExecutor {
 			method,
 			default_heap_pages: default_heap_pages.unwrap_or(DEFAULT_HEAP_PAGES),
 			host_functions: Arc::new(host_functions),
-			cache: Arc::new(RuntimeCache::new(max_runtime_instances)),
+			cache: Arc::new(RuntimeCache::new(
+				max_runtime_instances,
+				cache_path.clone(),
+				stack_limit,
+			)),
 			max_runtime_instances,
+			cache_path,
+			stack_limit,
 		}
 	}
 
@@ -182,16 +211,18 @@ impl sp_core::traits::CallInWasm for WasmExecutor {
 			self.with_instance(&code, ext, allow_missing_host_functions, |instance, _, mut ext| {
 				with_externalities_safe(
 					&mut **ext,
-					move || instance.call(method, call_data),
+					move || call_instance(instance, method, call_data),
 				)
 			}).map_err(|e| e.to_string())
 		} else {
 			let module = crate::wasm_runtime::create_wasm_runtime_with_code(
 				self.method,
-				self.default_heap_pages,
+				crate::wasm_runtime::heap_pages_for(&wasm_code, None, self.default_heap_pages),
 				&wasm_code,
 				self.host_functions.to_vec(),
 				allow_missing_host_functions,
+				self.cache_path.as_deref(),
+				self.stack_limit,
 			)
 				.map_err(|e| format!("Failed to create module: {:?}", e))?;
 
@@ -203,7 +234,7 @@ impl sp_core::traits::CallInWasm for WasmExecutor {
 
 			with_externalities_safe(
 				&mut **ext,
-				move || instance.call(method, call_data),
+				move || call_instance(&**instance, method, call_data),
 			)
 			.and_then(|r| r)
 			.map_err(|e| e.to_string())
@@ -235,6 +266,23 @@ impl<D: NativeExecutionDispatch> NativeExecutor<D> {
 		fallback_method: WasmExecutionMethod,
 		default_heap_pages: Option<u64>,
 		max_runtime_instances: usize,
+	) -> Self {
+		Self::new_with_wasm_cache_path(
+			fallback_method,
+			default_heap_pages,
+			max_runtime_instances,
+			None,
+		)
+	}
+
+	/// Create new instance caching compiled runtimes on disk in `cache_path`.
+	///
+	/// See [`NativeExecutor::new`] for the other parameters.
+	pub fn new_with_wasm_cache_path(
+		fallback_method: WasmExecutionMethod,
+		default_heap_pages: Option<u64>,
+		max_runtime_instances: usize,
+		cache_path: Option<PathBuf>,
 	) -> Self {
 		let mut host_functions = sp_io::SubstrateHostFunctions::host_functions();
 
@@ -245,6 +293,8 @@ impl<D: NativeExecutionDispatch> NativeExecutor<D> {
 			default_heap_pages,
 			host_functions,
 			max_runtime_instances,
+			cache_path,
+			Some(DEFAULT_LOGICAL_STACK_LIMIT),
 		);
 
 		NativeExecutor {
@@ -314,13 +364,15 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeExecutor<D> {
 
 						with_externalities_safe(
 							&mut **ext,
-							move || instance.call(method, data).map(NativeOrEncoded::Encoded)
+							move || call_instance(instance, method, data)
+								.map(NativeOrEncoded::Encoded),
 						)
 					}
 					(false, _, _) => {
 						with_externalities_safe(
 							&mut **ext,
-							move || instance.call(method, data).map(NativeOrEncoded::Encoded)
+							move || call_instance(instance, method, data)
+								.map(NativeOrEncoded::Encoded),
 						)
 					},
 					(true, true, Some(call)) => {
@@ -19,14 +19,16 @@
 //! The primary means of accessing the runtimes is through a cache which saves the reusable
 //! components of the runtime that are expensive to initialize.
 
-use std::sync::Arc;
+use std::{path::{Path, PathBuf}, sync::Arc};
 use crate::error::{Error, WasmError};
 use parking_lot::Mutex;
 use codec::Decode;
 use sp_core::traits::{Externalities, RuntimeCode, FetchRuntimeCode};
 use sp_version::RuntimeVersion;
 use std::panic::AssertUnwindSafe;
-use sc_executor_common::wasm_runtime::{WasmModule, WasmInstance};
+use sc_executor_common::{
+	stack_limit, util::WasmModuleInfo, wasm_runtime::{WasmModule, WasmInstance},
+};
 
 use sp_wasm_interface::Function;
 
@@ -54,8 +56,8 @@ struct VersionedRuntime {
 	wasm_method: WasmExecutionMethod,
 	/// Shared runtime that can spawn instances.
 	module: Box<dyn WasmModule>,
-	/// The number of WebAssembly heap pages this instance was created with.
-	heap_pages: u64,
+	/// The number of heap pages requested through `:heappages`, if any.
+	heap_pages_override: Option<u64>,
 	/// Runtime version according to `Core_version` if any.
 	version: Option<RuntimeVersion>,
 	/// Cached instance pool.
@@ -149,14 +151,28 @@ pub struct RuntimeCache {
 	runtimes: Mutex<[Option<Arc<VersionedRuntime>>; MAX_RUNTIMES]>,
 	/// The size of the instances cache for each runtime.
 	max_runtime_instances: usize,
+	/// The directory in which compiled runtimes are cached on disk.
+	cache_path: Option<PathBuf>,
+	/// The limit of the logical stack height runtimes are instrumented with.
+	stack_limit: Option<u32>,
 }
 
 impl RuntimeCache {
 	/// Creates a new instance of a runtimes cache.
-	pub fn new(max_runtime_instances: usize) -> RuntimeCache {
+	///
+	/// `cache_path` is the directory in which compiled runtimes are cached, if any.
+	/// `stack_limit` is the limit of the logical stack height runtimes are instrumented with, if
+	/// any.
+	pub fn new(
+		max_runtime_instances: usize,
+		cache_path: Option<PathBuf>,
+		stack_limit: Option<u32>,
+	) -> RuntimeCache {
 		RuntimeCache {
 			runtimes: Default::default(),
 			max_runtime_instances,
+			cache_path,
+			stack_limit,
 		}
 	}
 
@@ -205,14 +221,13 @@ impl RuntimeCache {
 		-> Result<R, Error>,
 	{
 		let code_hash = &runtime_code.hash;
-		let heap_pages = runtime_code.heap_pages.unwrap_or(default_heap_pages);
 
 		let mut runtimes = self.runtimes.lock(); // this must be released prior to calling f
 		let pos = runtimes.iter().position(|r| r.as_ref().map_or(
 			false,
 			|r| r.wasm_method == wasm_method &&
 				r.code_hash == *code_hash &&
-				r.heap_pages == heap_pages
+				r.heap_pages_override == runtime_code.heap_pages
 		));
 
 		let runtime = match pos {
@@ -227,10 +242,13 @@ impl RuntimeCache {
 					code_hash.clone(),
 					ext,
 					wasm_method,
-					heap_pages,
+					runtime_code.heap_pages,
+					default_heap_pages,
 					host_functions.into(),
 					allow_missing_func_imports,
 					self.max_runtime_instances,
+					self.cache_path.as_deref(),
+					self.stack_limit,
 				);
 				if let Err(ref err) = result {
 					log::warn!(target: "wasm-runtime", "Cannot create a runtime: {:?}", err);
@@ -261,13 +279,31 @@ impl RuntimeCache {
 }
 
 /// Create a wasm runtime with the given `code`.
+///
+/// `cache_path` is the directory in which the compiled executor caches its modules. If the
+/// compiled executor fails to compile or instantiate the code, the interpreter is used instead.
+///
+/// If `stack_limit` is given, the code is instrumented to trap once its logical stack height
+/// exceeds it, so that stack exhaustion does not depend on the execution method.
+#[cfg_attr(not(feature = "wasmtime"), allow(unused_variables))]
 pub fn create_wasm_runtime_with_code(
 	wasm_method: WasmExecutionMethod,
 	heap_pages: u64,
 	code: &[u8],
 	host_functions: Vec<&'static dyn Function>,
 	allow_missing_func_imports: bool,
+	cache_path: Option<&Path>,
+	stack_limit: Option<u32>,
 ) -> Result<Box<dyn WasmModule>, WasmError> {
+	let instrumented;
+	let code = match stack_limit {
+		Some(limit) => {
+			instrumented = stack_limit::inject_stack_limiter(code, limit)?;
+			&instrumented[..]
+		},
+		None => code,
+	};
+
 	match wasm_method {
 		WasmExecutionMethod::Interpreted =>
 			sc_executor_wasmi::create_runtime(
@@ -277,13 +313,37 @@ pub fn create_wasm_runtime_with_code(
 				allow_missing_func_imports
 			).map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) }),
 		#[cfg(feature = "wasmtime")]
-		WasmExecutionMethod::Compiled =>
-			sc_executor_wasmtime::create_runtime(
+		WasmExecutionMethod::Compiled => {
+			let compiled = sc_executor_wasmtime::create_runtime(
 				code,
 				heap_pages,
-				host_functions,
-				allow_missing_func_imports
-			).map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) }),
+				host_functions.clone(),
+				allow_missing_func_imports,
+				cache_path,
+			)
+				.map_err(|e| e.to_string())
+				.and_then(|runtime| match runtime.new_instance() {
+					Ok(_) => Ok(runtime),
+					Err(e) => Err(e.to_string()),
+				});
+
+			match compiled {
+				Ok(runtime) => Ok(Box::new(runtime)),
+				Err(e) => {
+					log::warn!(
+						target: "wasm-runtime",
+						"Cannot instantiate the compiled runtime, using the interpreter: {}",
+						e,
+					);
+					sc_executor_wasmi::create_runtime(
+						code,
+						heap_pages,
+						host_functions,
+						allow_missing_func_imports
+					).map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) })
+				},
+			}
+		},
 	}
 }
 
@@ -311,19 +371,25 @@ fn create_versioned_wasm_runtime(
 	code_hash: Vec<u8>,
 	ext: &mut dyn Externalities,
 	wasm_method: WasmExecutionMethod,
-	heap_pages: u64,
+	heap_pages_override: Option<u64>,
+	default_heap_pages: u64,
 	host_functions: Vec<&'static dyn Function>,
 	allow_missing_func_imports: bool,
 	max_instances: usize,
+	cache_path: Option<&Path>,
+	stack_limit: Option<u32>,
 ) -> Result<VersionedRuntime, WasmError> {
 	#[cfg(not(target_os = "unknown"))]
 	let time = std::time::Instant::now();
+	let heap_pages = heap_pages_for(code, heap_pages_override, default_heap_pages);
 	let mut runtime = create_wasm_runtime_with_code(
 		wasm_method,
 		heap_pages,
 		&code,
 		host_functions,
 		allow_missing_func_imports,
+		cache_path,
+		stack_limit,
 	)?;
 
 	// Call to determine runtime version.
@@ -358,12 +424,52 @@ fn create_versioned_wasm_runtime(
 		code_hash,
 		module: runtime,
 		version,
-		heap_pages,
+		heap_pages_override,
 		wasm_method,
 		instances,
 	})
 }
 
+/// The largest number of heap pages a runtime can declare for itself.
+///
+/// A runtime declaring more gets this many instead. This does not limit `:heappages`.
+pub const MAX_DECLARED_HEAP_PAGES: u64 = 16384;
+
+/// The number of heap pages to create a runtime from `code` with.
+///
+/// `heap_pages_override`, set through `:heappages`, takes precedence. Otherwise the number
+/// declared by `code` in its `sp_version::HEAP_PAGES_SECTION` is used, capped at
+/// `MAX_DECLARED_HEAP_PAGES`, falling back to `default_heap_pages`.
+pub fn heap_pages_for(
+	code: &[u8],
+	heap_pages_override: Option<u64>,
+	default_heap_pages: u64,
+) -> u64 {
+	if let Some(heap_pages) = heap_pages_override {
+		return heap_pages
+	}
+
+	let declared = WasmModuleInfo::new(code)
+		.and_then(|info| info.custom_section(sp_version::HEAP_PAGES_SECTION).map(<[u8]>::to_vec));
+	match declared.map(|section| u64::decode(&mut &section[..])) {
+		Some(Ok(heap_pages)) if heap_pages > MAX_DECLARED_HEAP_PAGES => {
+			log::warn!(
+				target: "wasm-runtime",
+				"Runtime declares {} heap pages, using the maximum of {}",
+				heap_pages,
+				MAX_DECLARED_HEAP_PAGES,
+			);
+			MAX_DECLARED_HEAP_PAGES
+		},
+		Some(Ok(heap_pages)) => heap_pages,
+		Some(Err(_)) => {
+			log::warn!(target: "wasm-runtime", "Invalid heap pages section in the runtime");
+			default_heap_pages
+		},
+		None => default_heap_pages,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -424,4 +530,25 @@ mod tests {
 		let version = decode_version(&old_runtime_version.encode()).unwrap();
 		assert_eq!(3, version.transaction_version);
 	}
+
+	#[test]
+	fn declared_heap_pages_are_honored_and_capped() {
+		use parity_wasm::elements::{CustomSection, Module, Section};
+
+		let code_declaring = |heap_pages: u64| parity_wasm::serialize(Module::new(vec![
+			Section::Custom(CustomSection::new(
+				sp_version::HEAP_PAGES_SECTION.into(),
+				heap_pages.encode(),
+			)),
+		])).unwrap();
+		let plain = parity_wasm::serialize(Module::default()).unwrap();
+
+		assert_eq!(heap_pages_for(&plain, None, 64), 64);
+		assert_eq!(heap_pages_for(&code_declaring(2048), None, 64), 2048);
+		assert_eq!(heap_pages_for(&code_declaring(2048), Some(128), 64), 128);
+		assert_eq!(
+			heap_pages_for(&code_declaring(MAX_DECLARED_HEAP_PAGES + 1), None, 64),
+			MAX_DECLARED_HEAP_PAGES,
+		);
+	}
 }
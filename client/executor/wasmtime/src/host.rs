@@ -64,6 +64,11 @@ impl HostState {
 		}
 	}
 
+	/// The offset up to which the allocator of this state has handed out memory.
+	pub fn allocator_bumper(&self) -> u32 {
+		self.allocator.borrow().bumper()
+	}
+
 	/// Materialize `HostContext` that can be used to invoke a substrate host `dyn Function`.
 	pub fn materialize<'a>(&'a self) -> HostContext<'a> {
 		HostContext(self)
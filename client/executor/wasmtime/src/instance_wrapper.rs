@@ -279,6 +279,24 @@ impl InstanceWrapper {
 		}
 	}
 
+	/// Zero the memory from the beginning of the linear memory up to `end`.
+	///
+	/// `end` is clamped to the size of the memory. Anything above `end` is left untouched, so
+	/// callers that pass the allocator bumper do not clear writes the runtime made past the
+	/// memory its allocator handed out.
+	pub fn zero_memory(&self, end: u32) {
+		unsafe {
+			// This should be safe since we don't grow up memory while caching this reference and
+			// we give up the reference before returning from this function.
+			let memory = self.memory_as_slice_mut();
+
+			let end = memory.len().min(end as usize);
+			for byte in &mut memory[..end] {
+				*byte = 0;
+			}
+		}
+	}
+
 	/// Allocate some memory of the given size. Returns pointer to the allocated memory region.
 	///
 	/// Returns `Err` in case memory cannot be allocated. Refer to the allocator documentation
@@ -21,6 +21,9 @@ use crate::imports::{Imports, resolve_imports};
 use crate::instance_wrapper::{ModuleWrapper, InstanceWrapper, GlobalsSnapshot};
 use crate::state_holder;
 
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
 use sc_executor_common::{
@@ -64,6 +67,7 @@ impl WasmModule for WasmtimeRuntime {
 			globals_snapshot,
 			heap_pages: self.heap_pages,
 			heap_base,
+			dirty_up_to: Cell::new(0),
 		}))
 	}
 }
@@ -77,6 +81,12 @@ pub struct WasmtimeInstance {
 	imports: Imports,
 	heap_pages: u32,
 	heap_base: u32,
+	/// The offset up to which the memory was written by the previous call: its stack, its data
+	/// segments and the heap handed out by its allocator.
+	///
+	/// Writes the runtime makes above this offset, outside of any allocation, are not tracked and
+	/// thus not cleared before the next call.
+	dirty_up_to: Cell<u32>,
 }
 
 // This is safe because `WasmtimeInstance` does not leak reference to `self.imports`
@@ -88,6 +98,14 @@ impl WasmInstance for WasmtimeInstance {
 		let entrypoint = self.instance_wrapper.resolve_entrypoint(method)?;
 		let allocator = FreeingBumpHeapAllocator::new(self.heap_base);
 
+		// The instance is reused across calls, so whatever the previous call left in memory is
+		// cleared before the data segments are reapplied. Only the part of the memory the
+		// previous call used is cleared, which keeps the cost proportional to that call.
+		//
+		// `dirty_up_to` keeps its value until this call records its own, so a call that fails
+		// before reaching the runtime still leaves the next one clearing a large enough range.
+		self.instance_wrapper.zero_memory(self.dirty_up_to.get());
+
 		self.module_wrapper
 			.data_segments_snapshot()
 			.apply(|offset, contents| {
@@ -102,6 +120,7 @@ impl WasmInstance for WasmtimeInstance {
 			Rc::clone(&self.instance_wrapper),
 			entrypoint,
 			allocator,
+			&self.dirty_up_to,
 		)
 	}
 
@@ -111,18 +130,63 @@ impl WasmInstance for WasmtimeInstance {
 	}
 }
 
+/// Name of the wasmtime cache configuration file written to the cache directory.
+const CACHE_CONFIG_FILE: &str = "cache-config.toml";
+
+/// Set up `config` to cache compiled modules in `cache_path`.
+///
+/// Wasmtime only reads its cache settings from a configuration file, so one pointing at
+/// `cache_path` is written there first.
+fn setup_cache(config: &mut Config, cache_path: &Path) -> std::result::Result<(), String> {
+	fs::create_dir_all(cache_path)
+		.map_err(|e| format!("cannot create cache directory: {}", e))?;
+	let cache_path = cache_path.canonicalize()
+		.map_err(|e| format!("cannot canonicalize cache directory: {}", e))?;
+	let config_path = cache_path.join(CACHE_CONFIG_FILE);
+	fs::write(
+		&config_path,
+		format!("[cache]\nenabled = true\ndirectory = {:?}\n", cache_path.display().to_string()),
+	).map_err(|e| format!("cannot write cache config: {}", e))?;
+	config.cache_config_load(&config_path)
+		.map_err(|e| format!("cannot load cache config: {}", e))?;
+	Ok(())
+}
+
 /// Create a new `WasmtimeRuntime` given the code. This function performs translation from Wasm to
 /// machine code, which can be computationally heavy.
+///
+/// If `cache_path` is given, compiled modules are cached on disk in that directory and reused
+/// across restarts.
 pub fn create_runtime(
 	code: &[u8],
 	heap_pages: u64,
 	host_functions: Vec<&'static dyn Function>,
 	allow_missing_func_imports: bool,
+	cache_path: Option<&Path>,
 ) -> std::result::Result<WasmtimeRuntime, WasmError> {
 	// Create the engine, store and finally the module from the given code.
 	let mut config = Config::new();
 	config.cranelift_opt_level(wasmtime::OptLevel::SpeedAndSize);
 
+	// Every node must execute the runtime the same way, so only the MVP feature set is enabled
+	// regardless of the defaults of the wasmtime version in use.
+	config.wasm_threads(false);
+	config.wasm_reference_types(false);
+	config.wasm_simd(false);
+	config.wasm_bulk_memory(false);
+	config.wasm_multi_value(false);
+
+	if let Some(cache_path) = cache_path {
+		if let Err(e) = setup_cache(&mut config, cache_path) {
+			log::warn!(
+				target: "wasm-runtime",
+				"Compiled runtimes will not be cached in {}: {}",
+				cache_path.display(),
+				e,
+			);
+		}
+	}
+
 	let engine = Engine::new(&config);
 	let store = Store::new(&engine);
 
@@ -142,8 +206,13 @@ fn perform_call(
 	instance_wrapper: Rc<InstanceWrapper>,
 	entrypoint: wasmtime::Func,
 	mut allocator: FreeingBumpHeapAllocator,
+	dirty_up_to: &Cell<u32>,
 ) -> Result<Vec<u8>> {
-	let (data_ptr, data_len) = inject_input_data(&instance_wrapper, &mut allocator, data)?;
+	let (data_ptr, data_len) = inject_input_data(&instance_wrapper, &mut allocator, data)
+		.map_err(|e| {
+			dirty_up_to.set(dirty_up_to.get().max(allocator.bumper()));
+			e
+		})?;
 
 	let host_state = HostState::new(allocator, instance_wrapper.clone());
 	let ret = state_holder::with_initialized_state(&host_state, || {
@@ -163,6 +232,8 @@ fn perform_call(
 			}
 		}
 	});
+	// Recorded whether the call succeeded or trapped, since both may have written to the memory.
+	dirty_up_to.set(host_state.allocator_bumper());
 	let (output_ptr, output_len) = ret?;
 	let output = extract_output_data(&instance_wrapper, output_ptr, output_len)?;
 
@@ -0,0 +1,124 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate. If not, see <http://www.gnu.org/licenses/>.
+
+//! Password encrypted bundles of keys.
+//!
+//! A bundle holds the key type, public key and secret URI of every key of a keystore, encrypted
+//! with ChaCha20-Poly1305 under a key derived from a password with PBKDF2-HMAC-SHA256.
+
+use crate::{Error, Result};
+use chacha20poly1305::{ChaCha20Poly1305, aead::{Aead, NewAead, generic_array::GenericArray}};
+use hmac::Hmac;
+use rand::{RngCore, rngs::OsRng};
+use sha2::Sha256;
+use codec::{Encode, Decode};
+use sp_core::crypto::KeyTypeId;
+
+/// Prefix of every bundle.
+const MAGIC: &[u8; 8] = b"skbundle";
+/// Version of the bundle format.
+const VERSION: u8 = 1;
+/// Number of PBKDF2 rounds used to derive the encryption key.
+const PBKDF2_ROUNDS: usize = 100_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + 1 + SALT_LEN + NONCE_LEN;
+
+/// A key in a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct BundleEntry {
+	/// The key type.
+	pub key_type: [u8; 4],
+	/// The raw public key.
+	pub public: Vec<u8>,
+	/// The secret URI the key is derived from.
+	pub suri: String,
+}
+
+impl BundleEntry {
+	/// The key type of the entry.
+	pub fn key_type(&self) -> KeyTypeId {
+		KeyTypeId(self.key_type)
+	}
+}
+
+fn cipher(password: &str, salt: &[u8]) -> ChaCha20Poly1305 {
+	let mut key = [0u8; 32];
+	pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+	ChaCha20Poly1305::new(GenericArray::clone_from_slice(&key))
+}
+
+/// Encrypt `entries` under `password`.
+pub fn seal(entries: &[BundleEntry], password: &str) -> Vec<u8> {
+	let mut salt = [0u8; SALT_LEN];
+	let mut nonce = [0u8; NONCE_LEN];
+	OsRng.fill_bytes(&mut salt);
+	OsRng.fill_bytes(&mut nonce);
+
+	let ciphertext = cipher(password, &salt)
+		.encrypt(GenericArray::from_slice(&nonce), &entries.encode()[..])
+		.expect("encryption only fails for plaintexts larger than 256 GiB; qed");
+
+	let mut bundle = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+	bundle.extend_from_slice(MAGIC);
+	bundle.push(VERSION);
+	bundle.extend_from_slice(&salt);
+	bundle.extend_from_slice(&nonce);
+	bundle.extend_from_slice(&ciphertext);
+	bundle
+}
+
+/// Decrypt `bundle` with `password`.
+///
+/// Returns `Error::InvalidPassword` if the bundle was encrypted under another password or was
+/// tampered with.
+pub fn open(bundle: &[u8], password: &str) -> Result<Vec<BundleEntry>> {
+	if bundle.len() < HEADER_LEN || &bundle[..MAGIC.len()] != MAGIC {
+		return Err(Error::InvalidBundle)
+	}
+	if bundle[MAGIC.len()] != VERSION {
+		return Err(Error::InvalidBundle)
+	}
+	let salt = &bundle[MAGIC.len() + 1..MAGIC.len() + 1 + SALT_LEN];
+	let nonce = &bundle[MAGIC.len() + 1 + SALT_LEN..HEADER_LEN];
+
+	let plaintext = cipher(password, salt)
+		.decrypt(GenericArray::from_slice(nonce), &bundle[HEADER_LEN..])
+		.map_err(|_| Error::InvalidPassword)?;
+	Decode::decode(&mut &plaintext[..]).map_err(|_| Error::InvalidBundle)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bundle_roundtrip() {
+		let entries = vec![
+			BundleEntry { key_type: *b"gran", public: vec![1; 32], suri: "//Alice".into() },
+			BundleEntry { key_type: *b"babe", public: vec![2; 32], suri: "//Bob".into() },
+		];
+		let bundle = seal(&entries, "password");
+
+		assert_eq!(open(&bundle, "password").unwrap(), entries);
+		assert!(matches!(open(&bundle, "wrong"), Err(Error::InvalidPassword)));
+		assert!(matches!(open(&bundle[..10], "password"), Err(Error::InvalidBundle)));
+
+		let mut tampered = bundle.clone();
+		*tampered.last_mut().unwrap() ^= 1;
+		assert!(matches!(open(&tampered, "password"), Err(Error::InvalidPassword)));
+	}
+}
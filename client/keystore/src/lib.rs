@@ -17,6 +17,10 @@
 //! Keystore (and session key management) for ed25519 based chains like Polkadot.
 
 #![warn(missing_docs)]
+mod bundle;
+
+pub use bundle::BundleEntry;
+
 use std::{collections::{HashMap, HashSet}, path::PathBuf, fs::{self, File}, io::{self, Write}, sync::Arc};
 use sp_core::{
 	crypto::{IsWrappedBy, CryptoTypePublicPair, KeyTypeId, Pair as PairT, ExposeSecret, SecretString, Public},
@@ -56,6 +60,9 @@ pub enum Error {
 	/// Keystore unavailable
 	#[display(fmt="Keystore unavailable")]
 	Unavailable,
+	/// Invalid key bundle
+	#[display(fmt="Invalid key bundle")]
+	InvalidBundle,
 }
 
 /// Keystore Result
@@ -70,6 +77,7 @@ impl From<Error> for TraitError {
 				TraitError::ValidationError(error.to_string())
 			},
 			Error::Unavailable => TraitError::Unavailable,
+			Error::InvalidBundle => TraitError::Other(error.to_string()),
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
 		}
@@ -209,6 +217,20 @@ impl Store {
 		self.insert_ephemeral_from_seed_by_type::<Pair::Generic>(seed, Pair::ID).map(Into::into)
 	}
 
+	/// Remove the key with the given public key and key type.
+	///
+	/// Removes it from the in-memory and the file system store. Removing a key that is not in
+	/// the store is not an error.
+	pub fn remove_by_type(&mut self, key_type: KeyTypeId, public: &[u8]) -> Result<()> {
+		self.additional.remove(&(key_type, public.to_vec()));
+		if let Some(path) = self.key_file_path(public, key_type) {
+			if path.exists() {
+				fs::remove_file(path)?;
+			}
+		}
+		Ok(())
+	}
+
 	/// Get the key phrase for a given public key and key type.
 	fn key_phrase_by_type(&self, public: &[u8], key_type: KeyTypeId) -> Result<String> {
 		if let Some(phrase) = self.get_additional_pair(public, key_type) {
@@ -259,6 +281,45 @@ impl Store {
 			})
 	}
 
+	/// Export all keys of the file system store as a bundle encrypted with `bundle_password`.
+	///
+	/// Keys only held in memory are not exported.
+	pub fn export_bundle(&self, bundle_password: &str) -> Result<Vec<u8>> {
+		let path = self.path.as_ref().ok_or(Error::Unavailable)?;
+		let mut entries = Vec::new();
+		for entry in fs::read_dir(path)? {
+			let path = entry?.path();
+			let name = match path.file_name().and_then(|n| n.to_str()) {
+				Some(name) => name,
+				None => continue,
+			};
+			let (key_type, public) = match hex::decode(name) {
+				Ok(ref hex) if hex.len() > 4 => {
+					let mut key_type = [0u8; 4];
+					key_type.copy_from_slice(&hex[0..4]);
+					(key_type, hex[4..].to_vec())
+				}
+				_ => continue,
+			};
+			let suri = serde_json::from_reader(&File::open(path)?)?;
+			entries.push(BundleEntry { key_type, public, suri });
+		}
+		Ok(bundle::seal(&entries, bundle_password))
+	}
+
+	/// Import all keys of a bundle created with [`Store::export_bundle`] into the file system
+	/// store, returning the number of imported keys.
+	pub fn import_bundle(&self, bundle: &[u8], bundle_password: &str) -> Result<usize> {
+		if self.path.is_none() {
+			return Err(Error::Unavailable)
+		}
+		let entries = bundle::open(bundle, bundle_password)?;
+		for entry in &entries {
+			self.insert_unknown(entry.key_type(), &entry.suri, &entry.public)?;
+		}
+		Ok(entries.len())
+	}
+
 	/// Returns the file path for the given public key and key type.
 	fn key_file_path(&self, public: &[u8], key_type: KeyTypeId) -> Option<PathBuf> {
 		let mut buf = self.path.as_ref()?.clone();
@@ -483,6 +544,28 @@ mod tests {
 		assert_eq!(store.read().public_keys::<ed25519::AppPublic>().unwrap()[0], key.public());
 	}
 
+	#[test]
+	fn removed_keys_are_gone() {
+		let temp_dir = TempDir::new().unwrap();
+		let store = Store::open(temp_dir.path(), None).unwrap();
+
+		let key: ed25519::AppPair = store.write().generate().unwrap();
+		let ephemeral: ed25519::AppPair = store
+			.write()
+			.insert_ephemeral_from_seed("0x3d97c819d68f9bafa7d6e79cb991eebcd77d966c5334c0b94d9e1fa7ad0869dc")
+			.unwrap();
+		let other: ed25519::AppPair = store.write().generate().unwrap();
+
+		for public in &[key.public(), ephemeral.public()] {
+			store.write().remove_by_type(ed25519::AppPublic::ID, public.as_slice()).unwrap();
+			assert!(store.read().key_pair::<ed25519::AppPair>(public).is_err());
+		}
+		assert_eq!(store.read().public_keys::<ed25519::AppPublic>().unwrap(), vec![other.public()]);
+
+		// Removing a key twice is fine.
+		store.write().remove_by_type(ed25519::AppPublic::ID, key.public().as_slice()).unwrap();
+	}
+
 	#[test]
 	fn test_insert_ephemeral_from_seed() {
 		let temp_dir = TempDir::new().unwrap();
@@ -589,4 +672,27 @@ mod tests {
 			store.read().sr25519_public_keys(SR25519).is_empty(),
 		);
 	}
+
+	#[test]
+	fn keys_are_moved_with_a_bundle() {
+		let temp_dir = TempDir::new().unwrap();
+		let store = Store::open(temp_dir.path(), None).unwrap();
+		let ed_key: ed25519::AppPair = store.write().generate().unwrap();
+		let sr_key: sr25519::AppPair = store.write().generate().unwrap();
+
+		let bundle = store.read().export_bundle("secret").unwrap();
+
+		let other_dir = TempDir::new().unwrap();
+		let other = Store::open(other_dir.path(), None).unwrap();
+		assert!(matches!(
+			other.read().import_bundle(&bundle, "wrong"),
+			Err(Error::InvalidPassword)
+		));
+		assert_eq!(other.read().import_bundle(&bundle, "secret").unwrap(), 2);
+
+		let imported: ed25519::AppPair = other.read().key_pair(&ed_key.public()).unwrap();
+		assert_eq!(imported.public(), ed_key.public());
+		let imported: sr25519::AppPair = other.read().key_pair(&sr_key.public()).unwrap();
+		assert_eq!(imported.public(), sr_key.public());
+	}
 }
@@ -400,6 +400,22 @@ where
 			};
 			let is_empty_justification = justification.as_ref().map(|j| j.is_empty()).unwrap_or(false);
 
+			let body = if get_body {
+				match self.chain.block_body(&BlockId::Hash(hash))? {
+					Some(mut extrinsics) => extrinsics.iter_mut()
+						.map(|extrinsic| extrinsic.encode())
+						.collect(),
+					None => {
+						// The body was pruned, answer with the blocks we have so far instead of
+						// sending an empty body for this one.
+						log::trace!(target: "sync", "Missing body for {}, ending response", hash);
+						break
+					}
+				}
+			} else {
+				Vec::new()
+			};
+
 			let block_data = schema::v1::BlockData {
 				hash: hash.encode(),
 				header: if get_header {
@@ -407,15 +423,7 @@ where
 				} else {
 					Vec::new()
 				},
-				body: if get_body {
-					self.chain.block_body(&BlockId::Hash(hash))?
-						.unwrap_or(Vec::new())
-						.iter_mut()
-						.map(|extrinsic| extrinsic.encode())
-						.collect()
-				} else {
-					Vec::new()
-				},
+				body,
 				receipt: Vec::new(),
 				message_queue: Vec::new(),
 				justification: justification.unwrap_or(Vec::new()),
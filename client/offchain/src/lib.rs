@@ -33,7 +33,7 @@
 
 #![warn(missing_docs)]
 
-use std::{fmt, marker::PhantomData, sync::Arc};
+use std::{fmt, marker::PhantomData, sync::{Arc, atomic::{AtomicU64, Ordering}}};
 
 use parking_lot::Mutex;
 use threadpool::ThreadPool;
@@ -46,31 +46,77 @@ use sp_runtime::{generic::BlockId, traits::{self, Header}};
 use futures::{prelude::*, future::ready};
 
 mod api;
+mod session_keys;
 use api::SharedClient;
 
+pub use session_keys::session_keys_rotation_future;
+
 pub use sp_offchain::{OffchainWorkerApi, STORAGE_PREFIX};
 
+/// Limits on the offchain workers running at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkerLimits {
+	/// Maximum number of offchain workers running concurrently.
+	pub max_concurrent: usize,
+	/// Maximum number of offchain workers waiting for a free thread.
+	///
+	/// When all threads are busy and this many workers are already waiting, the workers for a
+	/// newly imported block are skipped. `None` queues them without limit.
+	pub max_queued: Option<usize>,
+}
+
+impl Default for WorkerLimits {
+	fn default() -> Self {
+		WorkerLimits { max_concurrent: num_cpus::get(), max_queued: None }
+	}
+}
+
 /// An offchain workers manager.
 pub struct OffchainWorkers<Client, Storage, Block: traits::Block> {
 	client: Arc<Client>,
 	db: Storage,
 	_block: PhantomData<Block>,
 	thread_pool: Mutex<ThreadPool>,
+	limits: WorkerLimits,
+	skipped_runs: AtomicU64,
 	shared_client: SharedClient,
 }
 
 impl<Client, Storage, Block: traits::Block> OffchainWorkers<Client, Storage, Block> {
 	/// Creates new `OffchainWorkers`.
 	pub fn new(client: Arc<Client>, db: Storage) -> Self {
+		Self::with_limits(client, db, Default::default())
+	}
+
+	/// Creates new `OffchainWorkers` running within the given `limits`.
+	pub fn with_limits(client: Arc<Client>, db: Storage, limits: WorkerLimits) -> Self {
 		let shared_client = SharedClient::new();
 		Self {
 			client,
 			db,
 			_block: PhantomData,
-			thread_pool: Mutex::new(ThreadPool::new(num_cpus::get())),
+			thread_pool: Mutex::new(ThreadPool::new(limits.max_concurrent.max(1))),
+			limits,
+			skipped_runs: AtomicU64::new(0),
 			shared_client,
 		}
 	}
+
+	/// The number of blocks for which the offchain workers were skipped because the worker
+	/// queue was full.
+	pub fn skipped_runs(&self) -> u64 {
+		self.skipped_runs.load(Ordering::Relaxed)
+	}
+
+	/// Whether the workers for another block would exceed the queue limit.
+	fn is_backlogged(&self) -> bool {
+		let max_queued = match self.limits.max_queued {
+			Some(max_queued) => max_queued,
+			None => return false,
+		};
+		let pool = self.thread_pool.lock();
+		pool.active_count() >= pool.max_count() && pool.queued_count() >= max_queued
+	}
 }
 
 impl<Client, Storage, Block: traits::Block> fmt::Debug for OffchainWorkers<
@@ -119,7 +165,15 @@ impl<Client, Storage, Block> OffchainWorkers<
 			}
 		};
 		debug!("Checking offchain workers at {:?}: version:{}", at, version);
-		if version > 0 {
+		if version > 0 && self.is_backlogged() {
+			let skipped = self.skipped_runs.fetch_add(1, Ordering::Relaxed) + 1;
+			warn!(
+				"Skipping offchain workers at {:?}: too many workers queued ({} skipped so far).",
+				at,
+				skipped,
+			);
+			futures::future::Either::Right(futures::future::ready(()))
+		} else if version > 0 {
 			let (api, runner) = api::AsyncApi::new(
 				self.db.clone(),
 				network_state.clone(),
@@ -209,7 +263,11 @@ mod tests {
 	use super::*;
 	use std::sync::Arc;
 	use sc_network::{Multiaddr, PeerId};
-	use substrate_test_runtime_client::{TestClient, runtime::Block};
+	use sp_core::{testing::SR25519, traits::BareCryptoStore};
+	use substrate_test_runtime_client::{
+		TestClient, TestClientBuilder, DefaultTestClientBuilderExt, TestClientBuilderExt,
+		runtime::Block,
+	};
 	use sc_transaction_pool::{BasicPool, FullChainApi};
 	use sp_transaction_pool::{TransactionPool, InPoolTransaction};
 
@@ -267,4 +325,53 @@ mod tests {
 		assert_eq!(pool.0.status().ready, 1);
 		assert_eq!(pool.0.ready().next().unwrap().is_propagable(), false);
 	}
+
+	#[test]
+	fn should_skip_workers_when_backlogged() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let db = sc_client_db::offchain::LocalStorage::new_test();
+		let network_state = Arc::new(MockNetworkStateInfo());
+		let header = client.header(&BlockId::number(0)).unwrap().unwrap();
+		let limits = WorkerLimits { max_concurrent: 1, max_queued: Some(0) };
+		let offchain = OffchainWorkers::with_limits(client, db, limits);
+
+		// given the only thread is busy
+		let (started_tx, started_rx) = std::sync::mpsc::channel();
+		let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+		offchain.spawn_worker(move || {
+			started_tx.send(()).unwrap();
+			let _ = release_rx.recv();
+		});
+		started_rx.recv().unwrap();
+
+		// when
+		futures::executor::block_on(offchain.on_block_imported(&header, network_state, false));
+
+		// then
+		assert_eq!(offchain.skipped_runs(), 1);
+		release_tx.send(()).unwrap();
+	}
+
+	#[test]
+	fn should_remove_stale_session_keys() {
+		let keystore = sc_keystore::Store::new_in_memory();
+		let client = Arc::new(TestClientBuilder::new().set_keystore(keystore.clone()).build());
+		let db = sc_client_db::offchain::LocalStorage::new_test();
+		let network_state = Arc::new(MockNetworkStateInfo());
+		let header = client.header(&BlockId::number(0)).unwrap().unwrap();
+		let offchain = OffchainWorkers::new(client, db);
+		let rotate = || futures::executor::block_on(
+			offchain.rotate_session_keys(&header, network_state.clone(), keystore.clone(), 1)
+		);
+
+		// The test runtime generates new keys on every call and reports all earlier ones as stale.
+		rotate();
+		let first = keystore.read().sr25519_public_keys(SR25519);
+		assert_eq!(first.len(), 1);
+
+		rotate();
+		let second = keystore.read().sr25519_public_keys(SR25519);
+		assert_eq!(second.len(), 1);
+		assert_ne!(first, second);
+	}
 }
@@ -0,0 +1,135 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Automated rotation of the session keys.
+//!
+//! The rotation itself, generating the keys and submitting `set_keys`, is done by the runtime
+//! through the `SessionKeysRotation` runtime api. The node only drives it after every new best
+//! block and removes the keys the runtime reports as stale from its keystore.
+
+use std::sync::Arc;
+
+use futures::{future::{self, Either}, prelude::*};
+use log::{debug, warn};
+use sc_keystore::KeyStorePtr;
+use sc_network::NetworkStateInfo;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_core::{offchain::{self, OffchainStorage}, ExecutionContext, traits::SpawnNamed};
+use sp_runtime::{generic::BlockId, traits::{self, Header}};
+use sp_session::SessionKeysRotation;
+
+use crate::{api, OffchainWorkers, OffchainWorkerApi};
+
+impl<Client, Storage, Block> OffchainWorkers<
+	Client,
+	Storage,
+	Block,
+> where
+	Block: traits::Block,
+	Client: ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	Client::Api: OffchainWorkerApi<Block> + SessionKeysRotation<Block>,
+	Storage: OffchainStorage + 'static,
+{
+	/// Rotate the session keys after the given block, unless they were already rotated in the
+	/// last `period` blocks.
+	///
+	/// The keys of earlier rotations that are neither active nor queued anymore are removed from
+	/// `keystore`, which must be the keystore the runtime generates the new keys in.
+	#[must_use]
+	pub fn rotate_session_keys(
+		&self,
+		header: &Block::Header,
+		network_state: Arc<dyn NetworkStateInfo + Send + Sync>,
+		keystore: KeyStorePtr,
+		period: u32,
+	) -> impl Future<Output = ()> {
+		let at = BlockId::hash(header.hash());
+		match self.client.runtime_api().has_api::<dyn SessionKeysRotation<Block, Error = ()>>(&at) {
+			Ok(true) => (),
+			_ => {
+				debug!("Skipping session key rotation at {:?}: not supported by the runtime", at);
+				return Either::Right(future::ready(()))
+			},
+		}
+
+		let (api, runner) = api::AsyncApi::new(
+			self.db.clone(),
+			network_state,
+			true,
+			self.shared_client.clone(),
+		);
+		let (removed_tx, removed_rx) = futures::channel::oneshot::channel();
+		let header = header.clone();
+		let client = self.client.clone();
+		self.spawn_worker(move || {
+			let runtime = client.runtime_api();
+			let api = Box::new(api);
+			debug!("Rotating session keys at {:?}", at);
+			let context = ExecutionContext::OffchainCall(Some(
+				(api, offchain::Capabilities::all())
+			));
+			match runtime.rotate_session_keys_with_context(&at, context, &header, period) {
+				Ok(stale) => for (public, key_type) in stale {
+					if let Err(e) = keystore.write().remove_by_type(key_type, &public) {
+						warn!("Failed to remove stale session key of type {:?}: {:?}", key_type, e);
+					}
+				},
+				Err(e) => log::error!("Error rotating session keys at {:?}: {:?}", at, e),
+			}
+			let _ = removed_tx.send(());
+		});
+		// The runner is done once the offchain api is dropped, which is before the stale keys
+		// are removed.
+		Either::Left(future::join(runner.process(), removed_rx).map(|_| ()))
+	}
+}
+
+/// Rotate the session keys after every new best block, at most once per `period` blocks.
+///
+/// The rotation is opt-in: a validator that wants it spawns this next to the
+/// [`notification_future`](crate::notification_future) of the offchain workers, with the node's
+/// keystore.
+pub async fn session_keys_rotation_future<Client, Storage, Block, Spawner>(
+	client: Arc<Client>,
+	offchain: Arc<OffchainWorkers<Client, Storage, Block>>,
+	keystore: KeyStorePtr,
+	period: u32,
+	spawner: Spawner,
+	network_state_info: Arc<dyn NetworkStateInfo + Send + Sync>,
+)
+	where
+		Block: traits::Block,
+		Client: ProvideRuntimeApi<Block> + sc_client_api::BlockchainEvents<Block> + Send + Sync + 'static,
+		Client::Api: OffchainWorkerApi<Block> + SessionKeysRotation<Block>,
+		Storage: OffchainStorage + 'static,
+		Spawner: SpawnNamed
+{
+	client.import_notification_stream().for_each(move |n| {
+		if n.is_new_best {
+			spawner.spawn(
+				"offchain-rotate-session-keys",
+				offchain.rotate_session_keys(
+					&n.header,
+					network_state_info.clone(),
+					keystore.clone(),
+					period,
+				).boxed(),
+			);
+		}
+
+		future::ready(())
+	}).await;
+}
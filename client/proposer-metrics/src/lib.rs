@@ -16,7 +16,9 @@
 
 //! Prometheus basic proposer metrics.
 
-use prometheus_endpoint::{register, PrometheusError, Registry, Histogram, HistogramOpts, Gauge, U64};
+use prometheus_endpoint::{
+	register, PrometheusError, Registry, Histogram, HistogramOpts, Gauge, U64, CounterVec, Opts,
+};
 
 /// Optional shareable link to basic authorship metrics.
 #[derive(Clone, Default)]
@@ -43,6 +45,7 @@ impl MetricsLink {
 pub struct Metrics {
     pub block_constructed: Histogram,
     pub number_of_transactions: Gauge<U64>,
+    pub limits_reached: CounterVec<U64>,
 }
 
 impl Metrics {
@@ -61,6 +64,16 @@ impl Metrics {
                     "Number of transactions included in block",
                 )?,
                 registry,
+            )?,
+            limits_reached: register(
+                CounterVec::new(
+                    Opts::new(
+                        "proposer_limits_reached",
+                        "Number of times a proposal limit was reached, by limit",
+                    ),
+                    &["limit"],
+                )?,
+                registry,
             )?,
 		})
     }
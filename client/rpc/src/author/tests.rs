@@ -22,8 +22,8 @@ use std::{mem, sync::Arc};
 use assert_matches::assert_matches;
 use codec::Encode;
 use sp_core::{
-	H256, blake2_256, hexdisplay::HexDisplay, testing::{ED25519, SR25519, KeyStore},
-	traits::BareCryptoStorePtr, ed25519, sr25519,
+	H256, blake2_256, hexdisplay::HexDisplay, testing::{ED25519, SR25519, ECDSA, KeyStore},
+	traits::BareCryptoStorePtr, ed25519, sr25519, ecdsa,
 	crypto::{CryptoTypePublicPair, Pair, Public},
 };
 use rpc::futures::Stream as _;
@@ -253,9 +253,11 @@ fn should_rotate_keys() {
 
 	let ed25519_public_keys = setup.keystore.read().keys(ED25519).unwrap();
 	let sr25519_public_keys = setup.keystore.read().keys(SR25519).unwrap();
+	let ecdsa_public_keys = setup.keystore.read().keys(ECDSA).unwrap();
 
 	assert!(ed25519_public_keys.contains(&CryptoTypePublicPair(ed25519::CRYPTO_ID, session_keys.ed25519.to_raw_vec())));
 	assert!(sr25519_public_keys.contains(&CryptoTypePublicPair(sr25519::CRYPTO_ID, session_keys.sr25519.to_raw_vec())));
+	assert!(ecdsa_public_keys.contains(&CryptoTypePublicPair(ecdsa::CRYPTO_ID, session_keys.ecdsa.to_raw_vec())));
 }
 
 #[test]
@@ -198,13 +198,23 @@ pub type TLightCallExecutor<TBl, TExecDisp> = sc_light::GenesisCallExecutor<
 	>,
 >;
 
-type TFullParts<TBl, TRtApi, TExecDisp> = (
+/// The parts of a full node created by [`new_full_parts`].
+pub type TFullParts<TBl, TRtApi, TExecDisp> = (
 	TFullClient<TBl, TRtApi, TExecDisp>,
 	Arc<TFullBackend<TBl>>,
 	Arc<RwLock<sc_keystore::Store>>,
 	TaskManager,
 );
 
+/// The parts of a light node created by [`new_light_parts`].
+pub type TLightParts<TBl, TRtApi, TExecDisp> = (
+	Arc<TLightClient<TBl, TRtApi, TExecDisp>>,
+	Arc<TLightBackend<TBl>>,
+	Arc<RwLock<sc_keystore::Store>>,
+	TaskManager,
+	Arc<OnDemand<TBl>>,
+);
+
 /// Creates a new full client for the given config.
 pub fn new_full_client<TBl, TRtApi, TExecDisp>(
 	config: &Configuration,
@@ -215,7 +225,12 @@ pub fn new_full_client<TBl, TRtApi, TExecDisp>(
 	new_full_parts(config).map(|parts| parts.0)
 }
 
-fn new_full_parts<TBl, TRtApi, TExecDisp>(
+/// Create the client, backend, keystore and task manager of a full node for the given config.
+///
+/// These are the parts every full node needs, independently of its consensus and networking.
+/// Nodes that don't fit the `ServiceBuilder` can build their import queue, transaction pool and
+/// other components on top of them.
+pub fn new_full_parts<TBl, TRtApi, TExecDisp>(
 	config: &Configuration,
 ) -> Result<TFullParts<TBl, TRtApi, TExecDisp>,	Error> where
 	TBl: BlockT,
@@ -234,10 +249,11 @@ fn new_full_parts<TBl, TRtApi, TExecDisp>(
 		TaskManager::new(config.task_executor.clone(), registry)?
 	};
 
-	let executor = NativeExecutor::<TExecDisp>::new(
+	let executor = NativeExecutor::<TExecDisp>::new_with_wasm_cache_path(
 		config.wasm_method,
 		config.default_heap_pages,
 		config.max_runtime_instances,
+		config.wasm_cache_path.clone(),
 	);
 
 	let chain_spec = &config.chain_spec;
@@ -249,12 +265,26 @@ fn new_full_parts<TBl, TRtApi, TExecDisp>(
 		.cloned()
 		.unwrap_or_default();
 
+	let wasm_runtime_substitutes = chain_spec.code_substitutes().into_iter()
+		.map(|(number, code)| number.parse::<u64>()
+			.map(|number| (number, code))
+			.map_err(|_| Error::Other(format!(
+				"Invalid block number {} of a runtime code substitute",
+				number,
+			)))
+		)
+		.collect::<Result<_, _>>()?;
+
 	let (client, backend) = {
 		let db_config = sc_client_db::DatabaseSettings {
 			state_cache_size: config.state_cache_size,
 			state_cache_child_ratio:
 			config.state_cache_child_ratio.map(|v| (v, 100)),
+			state_node_cache_size: config.state_node_cache_size,
 			pruning: config.pruning.clone(),
+			keep_blocks: config.keep_blocks,
+			force_pruning_mode: config.force_pruning_mode,
+			auto_upgrade: config.auto_upgrade,
 			source: config.database.clone(),
 		};
 
@@ -265,6 +295,7 @@ fn new_full_parts<TBl, TRtApi, TExecDisp>(
 
 		new_client(
 			db_config,
+			config.canonicalization_delay,
 			executor,
 			chain_spec.as_storage_builder(),
 			fork_blocks,
@@ -275,6 +306,7 @@ fn new_full_parts<TBl, TRtApi, TExecDisp>(
 			ClientConfig {
 				offchain_worker_enabled : config.offchain_worker.enabled ,
 				offchain_indexing_api: config.offchain_worker.indexing_enabled,
+				wasm_runtime_substitutes,
 			},
 		)?
 	};
@@ -283,9 +315,75 @@ fn new_full_parts<TBl, TRtApi, TExecDisp>(
 }
 
 
+/// Create the client, backend, keystore, task manager and fetcher of a light node for the given
+/// config.
+///
+/// See [`new_full_parts`].
+pub fn new_light_parts<TBl, TRtApi, TExecDisp>(
+	config: &Configuration,
+) -> Result<TLightParts<TBl, TRtApi, TExecDisp>, Error> where
+	TBl: BlockT,
+	TExecDisp: NativeExecutionDispatch + 'static,
+{
+	let task_manager = {
+		let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
+		TaskManager::new(config.task_executor.clone(), registry)?
+	};
+
+	let keystore = match &config.keystore {
+		KeystoreConfig::Path { path, password } => Keystore::open(
+			path.clone(),
+			password.clone()
+		)?,
+		KeystoreConfig::InMemory => Keystore::new_in_memory(),
+	};
+
+	let executor = NativeExecutor::<TExecDisp>::new_with_wasm_cache_path(
+		config.wasm_method,
+		config.default_heap_pages,
+		config.max_runtime_instances,
+		config.wasm_cache_path.clone(),
+	);
+
+	let db_storage = {
+		let db_settings = sc_client_db::DatabaseSettings {
+			state_cache_size: config.state_cache_size,
+			state_cache_child_ratio:
+				config.state_cache_child_ratio.map(|v| (v, 100)),
+			state_node_cache_size: config.state_node_cache_size,
+			pruning: config.pruning.clone(),
+			keep_blocks: config.keep_blocks,
+			force_pruning_mode: config.force_pruning_mode,
+			auto_upgrade: config.auto_upgrade,
+			source: config.database.clone(),
+		};
+		sc_client_db::light::LightStorage::new(db_settings)?
+	};
+	let light_blockchain = sc_light::new_light_blockchain(db_storage);
+	let fetch_checker = Arc::new(
+		sc_light::new_fetch_checker::<_, TBl, _>(
+			light_blockchain.clone(),
+			executor.clone(),
+			Box::new(task_manager.spawn_handle()),
+		),
+	);
+	let fetcher = Arc::new(sc_network::config::OnDemand::new(fetch_checker));
+	let backend = sc_light::new_light_backend(light_blockchain);
+	let client = Arc::new(light::new_light(
+		backend.clone(),
+		config.chain_spec.as_storage_builder(),
+		executor,
+		Box::new(task_manager.spawn_handle()),
+		config.prometheus_config.as_ref().map(|config| config.registry.clone()),
+	)?);
+
+	Ok((client, backend, keystore, task_manager, fetcher))
+}
+
 /// Create an instance of db-backed client.
 pub fn new_client<E, Block, RA>(
 	settings: DatabaseSettings,
+	canonicalization_delay: u64,
 	executor: E,
 	genesis_storage: &dyn BuildStorage,
 	fork_blocks: ForkBlocks<Block>,
@@ -309,10 +407,17 @@ pub fn new_client<E, Block, RA>(
 		Block: BlockT,
 		E: CodeExecutor + RuntimeInfo,
 {
-	const CANONICALIZATION_DELAY: u64 = 4096;
-
-	let backend = Arc::new(Backend::new(settings, CANONICALIZATION_DELAY)?);
-	let executor = crate::client::LocalCallExecutor::new(backend.clone(), executor, spawn_handle, config.clone());
+	let backend = Arc::new(Backend::new_with_metrics(
+		settings,
+		canonicalization_delay,
+		prometheus_registry.as_ref(),
+	)?);
+	let executor = crate::client::LocalCallExecutor::new(
+		backend.clone(),
+		executor,
+		spawn_handle,
+		config.clone(),
+	)?;
 	Ok((
 		crate::client::Client::new(
 			backend.clone(),
@@ -384,53 +489,8 @@ impl ServiceBuilder<(), (), (), (), (), (), (), (), (), (), ()> {
 		(),
 		TLightBackend<TBl>,
 	>, Error> {
-		let task_manager = {
-			let registry = config.prometheus_config.as_ref().map(|cfg| &cfg.registry);
-			TaskManager::new(config.task_executor.clone(), registry)?
-		};
-
-		let keystore = match &config.keystore {
-			KeystoreConfig::Path { path, password } => Keystore::open(
-				path.clone(),
-				password.clone()
-			)?,
-			KeystoreConfig::InMemory => Keystore::new_in_memory(),
-		};
-
-		let executor = NativeExecutor::<TExecDisp>::new(
-			config.wasm_method,
-			config.default_heap_pages,
-			config.max_runtime_instances,
-		);
-
-		let db_storage = {
-			let db_settings = sc_client_db::DatabaseSettings {
-				state_cache_size: config.state_cache_size,
-				state_cache_child_ratio:
-					config.state_cache_child_ratio.map(|v| (v, 100)),
-				pruning: config.pruning.clone(),
-				source: config.database.clone(),
-			};
-			sc_client_db::light::LightStorage::new(db_settings)?
-		};
-		let light_blockchain = sc_light::new_light_blockchain(db_storage);
-		let fetch_checker = Arc::new(
-			sc_light::new_fetch_checker::<_, TBl, _>(
-				light_blockchain.clone(),
-				executor.clone(),
-				Box::new(task_manager.spawn_handle()),
-			),
-		);
-		let fetcher = Arc::new(sc_network::config::OnDemand::new(fetch_checker));
-		let backend = sc_light::new_light_backend(light_blockchain);
+		let (client, backend, keystore, task_manager, fetcher) = new_light_parts(&config)?;
 		let remote_blockchain = backend.remote_blockchain();
-		let client = Arc::new(light::new_light(
-			backend.clone(),
-			config.chain_spec.as_storage_builder(),
-			executor,
-			Box::new(task_manager.spawn_handle()),
-			config.prometheus_config.as_ref().map(|config| config.registry.clone()),
-		)?);
 
 		Ok(ServiceBuilder {
 			config,
@@ -438,7 +498,7 @@ impl ServiceBuilder<(), (), (), (), (), (), (), (), (), (), ()> {
 			backend,
 			task_manager,
 			keystore,
-			fetcher: Some(fetcher.clone()),
+			fetcher: Some(fetcher),
 			select_chain: None,
 			import_queue: (),
 			finality_proof_request_builder: None,
@@ -932,15 +992,25 @@ ServiceBuilder<
 		// issue, and ideally we would like to fix the network future to take as little time as
 		// possible, but we also take the extra harm-prevention measure to execute the networking
 		// future using `spawn_blocking`.
-		spawn_handle.spawn_blocking(
+		//
+		// The network, transaction pool and offchain worker notifications are essential: the node
+		// is useless without them, so their failure shuts it down instead of leaving it running
+		// in a broken state.
+		let spawn_essential_handle = task_manager.spawn_essential_handle();
+		spawn_essential_handle.spawn_blocking(
 			"network-worker",
 			network_future
 		);
 
 		let offchain_storage = backend.offchain_storage();
 		let offchain_workers = match (config.offchain_worker.clone(), offchain_storage.clone()) {
-			(OffchainWorkerConfig {enabled: true, .. }, Some(db)) => {
-				Some(Arc::new(sc_offchain::OffchainWorkers::new(client.clone(), db)))
+			(OffchainWorkerConfig {enabled: true, max_concurrent, max_queued, .. }, Some(db)) => {
+				let default_limits = sc_offchain::WorkerLimits::default();
+				let limits = sc_offchain::WorkerLimits {
+					max_concurrent: max_concurrent.unwrap_or(default_limits.max_concurrent),
+					max_queued,
+				};
+				Some(Arc::new(sc_offchain::OffchainWorkers::with_limits(client.clone(), db, limits)))
 			},
 			(OffchainWorkerConfig {enabled: true, .. }, None) => {
 				warn!("Offchain workers disabled, due to lack of offchain storage support in backend.");
@@ -950,14 +1020,14 @@ ServiceBuilder<
 		};
 
 		// Inform the tx pool about imported and finalized blocks.
-		spawn_handle.spawn(
+		spawn_essential_handle.spawn(
 			"txpool-notifications",
 			sc_transaction_pool::notification_future(client.clone(), transaction_pool.clone()),
 		);
 
 		// Inform the offchain worker about new imported blocks
 		if let Some(offchain) = offchain_workers.clone() {
-			spawn_handle.spawn(
+			spawn_essential_handle.spawn(
 				"offchain-notifications",
 				sc_offchain::notification_future(
 					config.role.is_authority(),
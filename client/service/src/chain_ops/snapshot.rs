@@ -0,0 +1,267 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! State snapshots.
+//!
+//! A snapshot is a directory holding a [`SnapshotManifest`] and the state of one block split into
+//! chunks. The manifest contains the header of the block and the hash of every chunk, so a
+//! snapshot obtained from an untrusted source can be checked against a known block hash.
+
+use crate::error::Error;
+use codec::{Encode, Decode};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Hash as HashT, HashFor};
+use sp_runtime::generic::BlockId;
+use sp_core::hashing::blake2_256;
+use sp_core::storage::{StorageKey, well_known_keys, ChildInfo, Storage, StorageChild};
+use sc_client_api::{StorageProvider, UsageProvider, BlockBackend};
+
+use std::{collections::BTreeMap, fs, path::{Path, PathBuf}, sync::Arc};
+
+/// Name of the manifest file in a snapshot directory.
+const MANIFEST_FILE: &str = "manifest";
+
+/// Manifest of a state snapshot.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotManifest {
+	/// Encoded header of the block the state belongs to.
+	pub header: Vec<u8>,
+	/// Blake2-256 hashes of the chunks, in order.
+	pub chunks: Vec<[u8; 32]>,
+}
+
+/// An entry of a snapshot chunk.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+struct SnapshotEntry {
+	/// Storage key of the default child trie the entry belongs to, `None` for the top trie.
+	child: Option<Vec<u8>>,
+	key: Vec<u8>,
+	value: Vec<u8>,
+}
+
+fn chunk_path(dir: &Path, index: usize) -> PathBuf {
+	dir.join(format!("chunk-{:06}", index))
+}
+
+/// Export the state at the given `block` as a snapshot into `dir`. If `block` is `None`, the last
+/// finalized block will be used.
+///
+/// Chunks are at most `chunk_size` bytes, unless a single entry is larger.
+pub fn export_state_snapshot<B, BA, C>(
+	client: Arc<C>,
+	block: Option<BlockId<B>>,
+	dir: &Path,
+	chunk_size: usize,
+) -> Result<SnapshotManifest, Error>
+where
+	C: UsageProvider<B> + StorageProvider<B, BA> + BlockBackend<B>,
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+{
+	let block = block.unwrap_or_else(
+		|| BlockId::Hash(client.usage_info().chain.finalized_hash)
+	);
+	let header = client.block(&block)?
+		.ok_or_else(|| Error::Other(format!("Unknown block {}", block)))?
+		.block
+		.header()
+		.clone();
+
+	let empty_key = StorageKey(Vec::new());
+	let mut entries = Vec::new();
+	for (key, value) in client.storage_pairs(&block, &empty_key)? {
+		if key.0.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			let child_key = &key.0[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..];
+			let child_info = ChildInfo::new_default(child_key);
+			for child_entry in client.child_storage_keys(&block, &child_info, &empty_key)? {
+				if let Some(value) = client.child_storage(&block, &child_info, &child_entry)? {
+					entries.push(SnapshotEntry {
+						child: Some(child_key.to_vec()),
+						key: child_entry.0,
+						value: value.0,
+					});
+				}
+			}
+		}
+		entries.push(SnapshotEntry { child: None, key: key.0, value: value.0 });
+	}
+
+	write_snapshot(&header, entries, dir, chunk_size)
+}
+
+fn write_snapshot<H: HeaderT>(
+	header: &H,
+	entries: Vec<SnapshotEntry>,
+	dir: &Path,
+	chunk_size: usize,
+) -> Result<SnapshotManifest, Error> {
+	fs::create_dir_all(dir)?;
+
+	let mut manifest = SnapshotManifest { header: header.encode(), chunks: Vec::new() };
+	let mut chunk = Vec::new();
+	let mut size = 0;
+	let mut write_chunk = |chunk: &mut Vec<SnapshotEntry>| -> Result<(), Error> {
+		let encoded = chunk.encode();
+		fs::write(chunk_path(dir, manifest.chunks.len()), &encoded)?;
+		manifest.chunks.push(blake2_256(&encoded));
+		chunk.clear();
+		Ok(())
+	};
+	for entry in entries {
+		let entry_size = entry.encoded_size();
+		if !chunk.is_empty() && size + entry_size > chunk_size {
+			write_chunk(&mut chunk)?;
+			size = 0;
+		}
+		size += entry_size;
+		chunk.push(entry);
+	}
+	if !chunk.is_empty() {
+		write_chunk(&mut chunk)?;
+	}
+
+	fs::write(dir.join(MANIFEST_FILE), manifest.encode())?;
+	Ok(manifest)
+}
+
+/// Read the snapshot in `dir` and verify it.
+///
+/// Every chunk is checked against its hash in the manifest, and the state built from the chunks
+/// against the state root of the snapshot header. Returns the header and the verified state.
+pub fn import_state_snapshot<B: BlockT>(dir: &Path) -> Result<(B::Header, Storage), Error> {
+	let manifest = SnapshotManifest::decode(&mut &fs::read(dir.join(MANIFEST_FILE))?[..])
+		.map_err(|e| Error::Other(format!("Invalid snapshot manifest: {}", e.what())))?;
+	let header = B::Header::decode(&mut &manifest.header[..])
+		.map_err(|e| Error::Other(format!("Invalid snapshot header: {}", e.what())))?;
+
+	let mut top = BTreeMap::new();
+	let mut children = BTreeMap::<_, BTreeMap<_, _>>::new();
+	for (index, hash) in manifest.chunks.iter().enumerate() {
+		let encoded = fs::read(chunk_path(dir, index))?;
+		if blake2_256(&encoded) != *hash {
+			return Err(Error::Other(format!("Snapshot chunk {} does not match its hash", index)))
+		}
+		let entries = Vec::<SnapshotEntry>::decode(&mut &encoded[..])
+			.map_err(|e| Error::Other(format!("Invalid snapshot chunk {}: {}", index, e.what())))?;
+		for entry in entries {
+			match entry.child {
+				Some(child) => children.entry(child).or_default().insert(entry.key, entry.value),
+				None => top.insert(entry.key, entry.value),
+			};
+		}
+	}
+
+	let root = HashFor::<B>::trie_root(top.clone().into_iter().collect());
+	if root != *header.state_root() {
+		return Err(Error::Other(format!(
+			"Snapshot state root {:?} does not match the header, expected {:?}",
+			root,
+			header.state_root(),
+		)))
+	}
+
+	// The top trie holds the roots of the child tries, which are not part of `Storage::top`.
+	let mut storage = Storage::default();
+	for (child_key, data) in children {
+		let child_info = ChildInfo::new_default(&child_key);
+		let root = HashFor::<B>::trie_root(data.clone().into_iter().collect());
+		let stored_root = top.remove(&child_info.prefixed_storage_key().into_inner());
+		if stored_root.as_ref().map(|r| &r[..]) != Some(root.as_ref()) {
+			return Err(Error::Other(format!(
+				"Snapshot child trie {} does not match its root",
+				sp_core::hexdisplay::HexDisplay::from(&child_key),
+			)))
+		}
+		storage.children_default.insert(child_key, StorageChild {
+			child_info,
+			data: data.into_iter().collect(),
+		});
+	}
+
+	storage.top = top.into_iter().collect();
+	Ok((header, storage))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use substrate_test_runtime_client::runtime::{Block, Header};
+
+	fn entries() -> Vec<SnapshotEntry> {
+		let child_key = b"child".to_vec();
+		let child_data = vec![(b"a".to_vec(), vec![1; 40]), (b"b".to_vec(), vec![2; 40])];
+		let child_root = HashFor::<Block>::trie_root(child_data.clone());
+		let child_info = ChildInfo::new_default(&child_key);
+
+		let mut entries: Vec<_> = child_data.into_iter()
+			.map(|(key, value)| SnapshotEntry { child: Some(child_key.clone()), key, value })
+			.collect();
+		entries.push(SnapshotEntry {
+			child: None,
+			key: child_info.prefixed_storage_key().into_inner(),
+			value: child_root.as_ref().to_vec(),
+		});
+		entries.push(SnapshotEntry { child: None, key: b"top".to_vec(), value: vec![3; 40] });
+		entries
+	}
+
+	fn header_for(entries: &[SnapshotEntry]) -> Header {
+		let top = entries.iter()
+			.filter(|e| e.child.is_none())
+			.map(|e| (e.key.clone(), e.value.clone()))
+			.collect();
+		Header {
+			parent_hash: Default::default(),
+			number: 10,
+			state_root: HashFor::<Block>::trie_root(top),
+			extrinsics_root: Default::default(),
+			digest: Default::default(),
+		}
+	}
+
+	#[test]
+	fn snapshot_roundtrip_is_verified() {
+		let dir = tempfile::tempdir().unwrap();
+		let entries = entries();
+		let header = header_for(&entries);
+
+		let manifest = write_snapshot(&header, entries, dir.path(), 64).unwrap();
+		assert!(manifest.chunks.len() > 1);
+
+		let (imported, storage) = import_state_snapshot::<Block>(dir.path()).unwrap();
+		assert_eq!(imported, header);
+		assert_eq!(storage.top.get(&b"top"[..]), Some(&vec![3; 40]));
+		assert_eq!(storage.top.len(), 1);
+		assert_eq!(storage.children_default[&b"child"[..]].data.len(), 2);
+	}
+
+	#[test]
+	fn tampered_snapshot_is_rejected() {
+		let dir = tempfile::tempdir().unwrap();
+		let entries = entries();
+		let mut header = header_for(&entries);
+		write_snapshot(&header, entries.clone(), dir.path(), 64).unwrap();
+
+		let mut chunk = fs::read(chunk_path(dir.path(), 0)).unwrap();
+		*chunk.last_mut().unwrap() ^= 1;
+		fs::write(chunk_path(dir.path(), 0), chunk).unwrap();
+		assert!(import_state_snapshot::<Block>(dir.path()).is_err());
+
+		// Consistent chunks for a different state root are rejected as well.
+		header.state_root = Default::default();
+		write_snapshot(&header, entries, dir.path(), 64).unwrap();
+		assert!(import_state_snapshot::<Block>(dir.path()).is_err());
+	}
+}
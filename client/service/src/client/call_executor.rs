@@ -19,7 +19,7 @@
 use std::{sync::Arc, panic::UnwindSafe, result, cell::RefCell};
 use codec::{Encode, Decode};
 use sp_runtime::{
-	generic::BlockId, traits::{Block as BlockT, HashFor, NumberFor},
+	generic::BlockId, traits::{Block as BlockT, HashFor, NumberFor}, SaturatedConversion,
 };
 use sp_state_machine::{
 	self, OverlayedChanges, Ext, ExecutionManager, StateMachine, ExecutionStrategy,
@@ -27,10 +27,14 @@ use sp_state_machine::{
 };
 use sc_executor::{RuntimeVersion, RuntimeInfo, NativeVersion};
 use sp_externalities::Extensions;
-use sp_core::{NativeOrEncoded, NeverNativeValue, traits::CodeExecutor, offchain::storage::OffchainOverlayedChanges};
+use sp_core::{
+	NativeOrEncoded, NeverNativeValue, traits::{CodeExecutor, RuntimeCode},
+	offchain::storage::OffchainOverlayedChanges,
+};
 use sp_api::{ProofRecorder, InitializeBlock, StorageTransactionCache};
 use sc_client_api::{backend, call_executor::CallExecutor, CloneableSpawn};
-use super::client::ClientConfig;
+use sp_blockchain::HeaderBackend;
+use super::{client::ClientConfig, wasm_substitutes::WasmSubstitutes};
 
 /// Call executor that executes methods locally, querying all required
 /// data from local backend.
@@ -39,22 +43,58 @@ pub struct LocalCallExecutor<B, E> {
 	executor: E,
 	spawn_handle: Box<dyn CloneableSpawn>,
 	client_config: ClientConfig,
+	wasm_substitutes: WasmSubstitutes,
 }
 
 impl<B, E> LocalCallExecutor<B, E> {
 	/// Creates new instance of local call executor.
+	///
+	/// Fails if the runtime code substitutes of the `client_config` are invalid.
 	pub fn new(
 		backend: Arc<B>,
 		executor: E,
 		spawn_handle: Box<dyn CloneableSpawn>,
 		client_config: ClientConfig,
-	) -> Self {
-		LocalCallExecutor {
+	) -> sp_blockchain::Result<Self> where E: RuntimeInfo {
+		let wasm_substitutes = WasmSubstitutes::new(
+			client_config.wasm_runtime_substitutes.clone(),
+			&executor,
+		)?;
+
+		Ok(LocalCallExecutor {
 			backend,
 			executor,
 			spawn_handle,
 			client_config,
+			wasm_substitutes,
+		})
+	}
+
+	/// Check if there is a substitute for the given on-chain code at the given block and return
+	/// it, or the on-chain code otherwise.
+	fn check_override<'a, Block>(
+		&'a self,
+		onchain_code: RuntimeCode<'a>,
+		id: &BlockId<Block>,
+	) -> sp_blockchain::Result<RuntimeCode<'a>>
+	where
+		Block: BlockT,
+		B: backend::Backend<Block>,
+		E: CodeExecutor + RuntimeInfo + Clone + 'static,
+	{
+		if self.wasm_substitutes.is_empty() {
+			return Ok(onchain_code)
 		}
+
+		let spec_version = CallExecutor::runtime_version(self, id)?.spec_version;
+		let block_number = self.backend.blockchain().block_number_from_id(id)?
+			.ok_or_else(|| sp_blockchain::Error::UnknownBlock(format!("{}", id)))?
+			.saturated_into::<u64>();
+
+		Ok(
+			self.wasm_substitutes.get(spec_version, onchain_code.heap_pages, block_number)
+				.unwrap_or(onchain_code)
+		)
 	}
 }
 
@@ -65,6 +105,7 @@ impl<B, E> Clone for LocalCallExecutor<B, E> where E: Clone {
 			executor: self.executor.clone(),
 			spawn_handle: self.spawn_handle.clone(),
 			client_config: self.client_config.clone(),
+			wasm_substitutes: self.wasm_substitutes.clone(),
 		}
 	}
 }
@@ -98,6 +139,7 @@ where
 		)?;
 		let state = self.backend.state_at(*id)?;
 		let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(&state);
+		let runtime_code = self.check_override(state_runtime_code.runtime_code()?, id)?;
 		let return_data = StateMachine::new(
 			&state,
 			changes_trie,
@@ -107,7 +149,7 @@ where
 			method,
 			call_data,
 			extensions.unwrap_or_default(),
-			&state_runtime_code.runtime_code()?,
+			&runtime_code,
 			self.spawn_handle.clone(),
 		).execute_using_consensus_failure_handler::<_, NeverNativeValue, fn() -> _>(
 			strategy.get_manager(),
@@ -171,6 +213,7 @@ where
 				// It is important to extract the runtime code here before we create the proof
 				// recorder.
 				let runtime_code = state_runtime_code.runtime_code()?;
+				let runtime_code = self.check_override(runtime_code, at)?;
 
 				let backend = sp_state_machine::ProvingBackend::new_with_recorder(
 					trie_state,
@@ -196,6 +239,7 @@ where
 			None => {
 				let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(&state);
 				let runtime_code = state_runtime_code.runtime_code()?;
+				let runtime_code = self.check_override(runtime_code, at)?;
 				let mut state_machine = StateMachine::new(
 					&state,
 					changes_trie_state,
@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substitutes for the on-chain runtime code.
+
+use std::{borrow::Cow, collections::{HashMap, BTreeMap}, sync::Arc};
+use codec::Encode;
+use sc_executor::RuntimeInfo;
+use sp_core::traits::{FetchRuntimeCode, RuntimeCode};
+use sp_state_machine::BasicExternalities;
+
+/// A runtime code substitute, used from `block_number` on.
+#[derive(Debug)]
+struct WasmSubstitute {
+	code: Vec<u8>,
+	hash: Vec<u8>,
+	block_number: u64,
+}
+
+impl WasmSubstitute {
+	fn new(code: Vec<u8>, block_number: u64) -> Self {
+		let hash = sp_core::hashing::blake2_256(&code).encode();
+		Self { code, hash, block_number }
+	}
+
+	fn runtime_code(&self, heap_pages: Option<u64>) -> RuntimeCode {
+		RuntimeCode { code_fetcher: self, hash: self.hash.clone(), heap_pages }
+	}
+}
+
+impl FetchRuntimeCode for WasmSubstitute {
+	fn fetch_runtime_code<'a>(&'a self) -> Option<Cow<'a, [u8]>> {
+		Some(self.code.as_slice().into())
+	}
+}
+
+/// Runtime code substitutes, keyed by their spec version.
+///
+/// A substitute is only used for blocks at or after its block number whose on-chain code has
+/// the same spec version as the substitute. Once the on-chain code is upgraded, which bumps the
+/// spec version, the on-chain code is used again.
+#[derive(Debug, Clone, Default)]
+pub struct WasmSubstitutes {
+	substitutes: Arc<HashMap<u32, WasmSubstitute>>,
+}
+
+impl WasmSubstitutes {
+	/// Create the substitutes from the given code, keyed by the block number from which on it is
+	/// used.
+	///
+	/// Fails if the runtime version of a substitute can not be read, or if two substitutes have
+	/// the same spec version.
+	pub fn new<E: RuntimeInfo>(
+		substitutes: BTreeMap<u64, Vec<u8>>,
+		executor: &E,
+	) -> sp_blockchain::Result<Self> {
+		let mut by_spec_version = HashMap::new();
+		for (block_number, code) in substitutes {
+			let substitute = WasmSubstitute::new(code, block_number);
+			let version = executor.runtime_version(
+				&mut BasicExternalities::new_empty(),
+				&substitute.runtime_code(None),
+			).map_err(|e| sp_blockchain::Error::VersionInvalid(format!(
+				"Runtime code substitute for block {}: {:?}", block_number, e,
+			)))?;
+
+			if by_spec_version.insert(version.spec_version, substitute).is_some() {
+				return Err(sp_blockchain::Error::Msg(format!(
+					"Duplicate runtime code substitute for spec version {}",
+					version.spec_version,
+				)))
+			}
+		}
+
+		Ok(Self { substitutes: Arc::new(by_spec_version) })
+	}
+
+	/// Get the substitute to use instead of on-chain code with the given `spec_version` at
+	/// `block_number`.
+	pub fn get(
+		&self,
+		spec_version: u32,
+		heap_pages: Option<u64>,
+		block_number: u64,
+	) -> Option<RuntimeCode> {
+		self.substitutes.get(&spec_version)
+			.filter(|s| s.block_number <= block_number)
+			.map(|s| s.runtime_code(heap_pages))
+	}
+
+	/// Returns `true` if there are no substitutes.
+	pub fn is_empty(&self) -> bool {
+		self.substitutes.is_empty()
+	}
+}
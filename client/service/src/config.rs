@@ -18,7 +18,10 @@
 
 //! Service configuration.
 
-pub use sc_client_db::{Database, PruningMode, DatabaseSettingsSrc as DatabaseConfig};
+pub use sc_client_db::{
+	Database, PruningMode, KeepBlocks, DatabaseSettingsSrc as DatabaseConfig,
+	DEFAULT_CANONICALIZATION_DELAY, RocksDbTuning, RocksDbCompaction,
+};
 pub use sc_network::Multiaddr;
 pub use sc_network::config::{ExtTransport, MultiaddrWithPeerId, NetworkConfiguration, Role, NodeKeyConfig};
 pub use sc_executor::WasmExecutionMethod;
@@ -52,16 +55,28 @@ pub struct Configuration {
 	pub keystore: KeystoreConfig,
 	/// Configuration for the database.
 	pub database: DatabaseConfig,
+	/// Upgrade an outdated database on startup.
+	pub auto_upgrade: bool,
 	/// Size of internal state cache in Bytes
 	pub state_cache_size: usize,
 	/// Size in percent of cache size dedicated to child tries
 	pub state_cache_child_ratio: Option<usize>,
+	/// Size of the shared trie node cache in Bytes
+	pub state_node_cache_size: usize,
 	/// Pruning settings.
 	pub pruning: PruningMode,
+	/// Block body and justification pruning settings.
+	pub keep_blocks: KeepBlocks,
+	/// Number of blocks after which the state of a block is canonicalized.
+	pub canonicalization_delay: u64,
+	/// Start even if `pruning` conflicts with the pruning mode stored in the database.
+	pub force_pruning_mode: bool,
 	/// Chain configuration.
 	pub chain_spec: Box<dyn ChainSpec>,
 	/// Wasm execution method.
 	pub wasm_method: WasmExecutionMethod,
+	/// Directory in which runtimes compiled by the `Compiled` execution method are cached.
+	pub wasm_cache_path: Option<PathBuf>,
 	/// Execution strategies.
 	pub execution_strategies: ExecutionStrategies,
 	/// RPC over HTTP binding address. `None` if disabled.
@@ -152,6 +167,11 @@ pub struct OffchainWorkerConfig {
 	pub enabled: bool,
 	/// allow writes from the runtime to the offchain worker database.
 	pub indexing_enabled: bool,
+	/// Maximum number of offchain workers running at the same time. `None` uses one per CPU.
+	pub max_concurrent: Option<usize>,
+	/// Maximum number of offchain workers waiting to run before the workers of new blocks are
+	/// skipped. `None` never skips them.
+	pub max_queued: Option<usize>,
 }
 
 /// Configuration of the Prometheus endpoint.
@@ -52,12 +52,13 @@ use sp_utils::{status_sinks, mpsc::{tracing_unbounded, TracingUnboundedReceiver,
 
 pub use self::error::Error;
 pub use self::builder::{
-	new_full_client, new_client,
-	ServiceBuilder, TFullClient, TLightClient, TFullBackend, TLightBackend,
+	new_full_client, new_client, new_full_parts, new_light_parts,
+	ServiceBuilder, TFullParts, TLightParts, TFullClient, TLightClient, TFullBackend, TLightBackend,
 	TFullCallExecutor, TLightCallExecutor, RpcExtensionBuilder,
 };
 pub use config::{
-	BasePath, Configuration, DatabaseConfig, PruningMode, Role, RpcMethods, TaskExecutor, TaskType,
+	BasePath, Configuration, DatabaseConfig, PruningMode, KeepBlocks, Role, RpcMethods, TaskExecutor,
+	TaskType, DEFAULT_CANONICALIZATION_DELAY,
 };
 pub use sc_chain_spec::{
 	ChainSpec, GenericChainSpec, Properties, RuntimeGenesis, Extension as ChainSpecExtension,
@@ -59,6 +59,8 @@ struct PrometheusMetrics {
 	database_cache: Gauge<U64>,
 	state_cache: Gauge<U64>,
 	state_db: GaugeVec<U64>,
+	node_cache: Gauge<U64>,
+	node_cache_reads: GaugeVec<U64>,
 }
 
 impl PrometheusMetrics {
@@ -150,6 +152,16 @@ impl PrometheusMetrics {
 				Opts::new("state_db_cache_bytes", "State DB cache in bytes"),
 				&["subtype"]
 			)?, registry)?,
+			node_cache: register(Gauge::new(
+				"state_node_cache_bytes", "Trie node cache size in bytes",
+			)?, registry)?,
+			node_cache_reads: register(GaugeVec::new(
+				Opts::new(
+					"state_node_cache_reads",
+					"Trie node reads since the last update, by cache hit or miss",
+				),
+				&["result"]
+			)?, registry)?,
 		})
 	}
 }
@@ -421,6 +433,14 @@ impl MetricsService {
 				metrics.state_db.with_label_values(&["pinned"]).set(
 					info.memory.state_db.pinned.as_bytes() as u64,
 				);
+
+				metrics.node_cache.set(info.memory.node_cache.as_bytes() as u64);
+				metrics.node_cache_reads.with_label_values(&["hit"]).set(
+					info.io.state_reads_nodes_cache,
+				);
+				metrics.node_cache_reads.with_label_values(&["miss"]).set(
+					info.io.state_reads_nodes.saturating_sub(info.io.state_reads_nodes_cache),
+				);
 			}
 
 			#[cfg(all(any(unix, windows), not(target_os = "android"), not(target_os = "ios")))]
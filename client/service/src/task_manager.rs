@@ -13,9 +13,10 @@
 
 //! Substrate service tasks management module.
 
-use std::{panic, result::Result, pin::Pin};
+use std::{panic, result::Result, pin::Pin, time::Duration};
+use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
 use exit_future::Signal;
-use log::debug;
+use log::{debug, warn};
 use futures::{
 	Future, FutureExt, StreamExt,
 	future::{select, Either, BoxFuture},
@@ -40,6 +41,23 @@ pub struct SpawnTaskHandle {
 	on_exit: exit_future::Exit,
 	executor: TaskExecutor,
 	metrics: Option<Metrics>,
+	running_tasks: Arc<AtomicUsize>,
+}
+
+/// Counts a task as running until it is dropped.
+struct RunningTask(Arc<AtomicUsize>);
+
+impl RunningTask {
+	fn new(running_tasks: Arc<AtomicUsize>) -> Self {
+		running_tasks.fetch_add(1, Ordering::SeqCst);
+		RunningTask(running_tasks)
+	}
+}
+
+impl Drop for RunningTask {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::SeqCst);
+	}
 }
 
 impl SpawnTaskHandle {
@@ -69,6 +87,7 @@ impl SpawnTaskHandle {
 	) {
 		let on_exit = self.on_exit.clone();
 		let metrics = self.metrics.clone();
+		let running = RunningTask::new(self.running_tasks.clone());
 
 		// Note that we increase the started counter here and not within the future. This way,
 		// we could properly visualize on Prometheus situations where the spawning doesn't work.
@@ -79,6 +98,7 @@ impl SpawnTaskHandle {
 		}
 
 		let future = async move {
+			let _running = running;
 			if let Some(metrics) = metrics {
 				// Add some wrappers around `task`.
 				let task = {
@@ -222,7 +242,11 @@ pub struct TaskManager {
 	/// A receiver for spawned essential-tasks concluding.
 	essential_failed_rx: TracingUnboundedReceiver<()>,
 	/// Things to keep alive until the task manager is dropped.
-	keep_alive: Box<dyn std::any::Any + Send + Sync>,
+	keep_alive: Vec<Box<dyn std::any::Any + Send + Sync>>,
+	/// Number of spawned tasks that have not ended yet.
+	running_tasks: Arc<AtomicUsize>,
+	/// Hooks to run at the end of a clean shutdown.
+	shutdown_hooks: Vec<Box<dyn FnOnce() + Send>>,
 }
 
 impl TaskManager {
@@ -245,7 +269,9 @@ impl TaskManager {
 			metrics,
 			essential_failed_tx,
 			essential_failed_rx,
-			keep_alive: Box::new(()),
+			keep_alive: Vec::new(),
+			running_tasks: Arc::new(AtomicUsize::new(0)),
+			shutdown_hooks: Vec::new(),
 		})
 	}
 
@@ -256,6 +282,7 @@ impl TaskManager {
 			on_exit: self.on_exit.clone(),
 			executor: self.executor.clone(),
 			metrics: self.metrics.clone(),
+			running_tasks: self.running_tasks.clone(),
 		}
 	}
 
@@ -280,9 +307,43 @@ impl TaskManager {
 		}
 	}
 
-	/// Set what the task manager should keep alivei
-	pub(super) fn keep_alive<T: 'static + Send + Sync>(&mut self, to_keep_alive: T) {
-		self.keep_alive = Box::new(to_keep_alive);
+	/// Keep `to_keep_alive` alive until the task manager is dropped.
+	///
+	/// Useful for handles whose drop stops a service, e.g. the RPC servers. Everything kept alive
+	/// is dropped in the order it was added.
+	pub fn keep_alive<T: 'static + Send + Sync>(&mut self, to_keep_alive: T) {
+		self.keep_alive.push(Box::new(to_keep_alive));
+	}
+
+	/// Register a hook to run at the end of [`TaskManager::clean_shutdown`], after all tasks
+	/// have ended.
+	pub fn on_shutdown(&mut self, hook: impl FnOnce() + Send + 'static) {
+		self.shutdown_hooks.push(Box::new(hook));
+	}
+
+	/// Shut down the tasks and services of the task manager.
+	///
+	/// First drops everything kept alive, which e.g. stops the RPC servers, then signals all tasks
+	/// to terminate and waits up to `deadline` for them to end. Finally runs the shutdown hooks in
+	/// the order they were registered.
+	///
+	/// The tasks are driven by the task executor, so this must not block the thread they run on.
+	pub async fn clean_shutdown(mut self, deadline: Duration) {
+		self.keep_alive.clear();
+		self.terminate();
+
+		let give_up = wasm_timer::Instant::now() + deadline;
+		while self.running_tasks.load(Ordering::SeqCst) > 0 && wasm_timer::Instant::now() < give_up {
+			futures_timer::Delay::new(Duration::from_millis(10)).await;
+		}
+		let running = self.running_tasks.load(Ordering::SeqCst);
+		if running > 0 {
+			warn!("{} tasks did not end within {:?} of the shutdown", running, deadline);
+		}
+
+		for hook in std::mem::take(&mut self.shutdown_hooks) {
+			hook();
+		}
 	}
 }
 
@@ -31,7 +31,7 @@ use substrate_test_runtime_client::{
 use sc_client_api::{
 	StorageProvider, BlockBackend, in_mem, BlockchainEvents,
 };
-use sc_client_db::{Backend, DatabaseSettings, DatabaseSettingsSrc, PruningMode};
+use sc_client_db::{Backend, DatabaseSettings, DatabaseSettingsSrc, KeepBlocks, PruningMode};
 use sc_block_builder::BlockBuilderProvider;
 use sc_service::client::{self, Client, LocalCallExecutor, new_in_mem};
 use sp_runtime::traits::{
@@ -1275,10 +1275,15 @@ fn doesnt_import_blocks_that_revert_finality() {
 		DatabaseSettings {
 			state_cache_size: 1 << 20,
 			state_cache_child_ratio: None,
+			state_node_cache_size: 0,
 			pruning: PruningMode::ArchiveAll,
+			keep_blocks: KeepBlocks::All,
+			force_pruning_mode: false,
+			auto_upgrade: true,
 			source: DatabaseSettingsSrc::RocksDb {
 				path: tmp.path().into(),
 				cache_size: 1024,
+				tuning: Default::default(),
 			},
 		},
 		u64::max_value(),
@@ -1476,10 +1481,15 @@ fn returns_status_for_pruned_blocks() {
 		DatabaseSettings {
 			state_cache_size: 1 << 20,
 			state_cache_child_ratio: None,
+			state_node_cache_size: 0,
 			pruning: PruningMode::keep_blocks(1),
+			keep_blocks: KeepBlocks::All,
+			force_pruning_mode: false,
+			auto_upgrade: true,
 			source: DatabaseSettingsSrc::RocksDb {
 				path: tmp.path().into(),
 				cache_size: 1024,
+				tuning: Default::default(),
 			},
 		},
 		u64::max_value(),
@@ -242,12 +242,19 @@ fn node_config<G: RuntimeGenesis + 'static, E: ChainSpecExtension + Clone + 'sta
 		database: DatabaseConfig::RocksDb {
 			path: root.join("db"),
 			cache_size: 128,
+			tuning: Default::default(),
 		},
+		auto_upgrade: true,
 		state_cache_size: 16777216,
 		state_cache_child_ratio: None,
+		state_node_cache_size: 0,
 		pruning: Default::default(),
+		keep_blocks: Default::default(),
+		canonicalization_delay: sc_service::DEFAULT_CANONICALIZATION_DELAY,
+		force_pruning_mode: false,
 		chain_spec: Box::new((*spec).clone()),
 		wasm_method: sc_service::config::WasmExecutionMethod::Interpreted,
+		wasm_cache_path: None,
 		execution_strategies: Default::default(),
 		rpc_http: None,
 		rpc_ipc: None,
@@ -42,7 +42,7 @@ use codec::Codec;
 use std::collections::{HashMap, hash_map::Entry};
 use noncanonical::NonCanonicalOverlay;
 use pruning::RefWindow;
-use log::trace;
+use log::{trace, warn};
 use parity_util_mem::{MallocSizeOf, malloc_size};
 use sc_client_api::{StateDbMemoryInfo, MemorySize};
 
@@ -87,8 +87,13 @@ pub enum Error<E: fmt::Debug> {
 	InvalidBlockNumber,
 	/// Trying to insert block with unknown parent.
 	InvalidParent,
-	/// Invalid pruning mode specified. Contains expected mode.
-	InvalidPruningMode(String),
+	/// The requested pruning mode does not match the one the database was created with.
+	IncompatiblePruningModes {
+		/// Pruning mode stored in the database.
+		stored: String,
+		/// Pruning mode requested on startup.
+		requested: String,
+	},
 }
 
 /// Pinning error type.
@@ -111,7 +116,12 @@ impl<E: fmt::Debug> fmt::Debug for Error<E> {
 			Error::InvalidBlock => write!(f, "Trying to canonicalize invalid block"),
 			Error::InvalidBlockNumber => write!(f, "Trying to insert block with invalid number"),
 			Error::InvalidParent => write!(f, "Trying to insert block with unknown parent"),
-			Error::InvalidPruningMode(e) => write!(f, "Expected pruning mode: {}", e),
+			Error::IncompatiblePruningModes { stored, requested } => write!(
+				f,
+				"Incompatible pruning modes: database uses `{}`, but `{}` was requested",
+				stored,
+				requested,
+			),
 		}
 	}
 }
@@ -198,18 +208,21 @@ struct StateDbSync<BlockHash: Hash, Key: Hash> {
 	non_canonical: NonCanonicalOverlay<BlockHash, Key>,
 	pruning: Option<RefWindow<BlockHash, Key>>,
 	pinned: HashMap<BlockHash, u32>,
+	/// Whether the pruning mode has to be written on the next inserted block.
+	store_mode: bool,
 }
 
 impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<BlockHash, Key> {
 	fn new<D: MetaDb>(
 		mode: PruningMode,
 		ref_counting: bool,
+		force_mode: bool,
 		db: &D,
 	) -> Result<StateDbSync<BlockHash, Key>, Error<D::Error>> {
 		trace!(target: "state-db", "StateDb settings: {:?}. Ref-counting: {}", mode, ref_counting);
 
 		// Check that settings match
-		Self::check_meta(&mode, db)?;
+		let store_mode = Self::check_meta(&mode, force_mode, db)?;
 
 		let non_canonical: NonCanonicalOverlay<BlockHash, Key> = NonCanonicalOverlay::new(db)?;
 		let pruning: Option<RefWindow<BlockHash, Key>> = match mode {
@@ -226,19 +239,40 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 			non_canonical,
 			pruning,
 			pinned: Default::default(),
+			store_mode,
 		})
 	}
 
-	fn check_meta<D: MetaDb>(mode: &PruningMode, db: &D) -> Result<(), Error<D::Error>> {
+	/// Check the requested mode against the one stored in the database.
+	///
+	/// Returns `true` if the requested mode still has to be written to the database, which is the
+	/// case for databases that predate storing the mode and for forced mode changes.
+	fn check_meta<D: MetaDb>(
+		mode: &PruningMode,
+		force_mode: bool,
+		db: &D,
+	) -> Result<bool, Error<D::Error>> {
 		let db_mode = db.get_meta(&to_meta_key(PRUNING_MODE, &())).map_err(Error::Db)?;
 		trace!(target: "state-db",
 			"DB pruning mode: {:?}",
 			db_mode.as_ref().map(|v| std::str::from_utf8(&v))
 		);
 		match &db_mode {
-			Some(v) if v.as_slice() == mode.id() => Ok(()),
-			Some(v) => Err(Error::InvalidPruningMode(String::from_utf8_lossy(v).into())),
-			None => Ok(()),
+			Some(v) if v.as_slice() == mode.id() => Ok(false),
+			Some(v) if force_mode => {
+				warn!(target: "state-db",
+					"Forcing pruning mode change from `{}` to `{}`. \
+					State that has already been pruned will not be available.",
+					String::from_utf8_lossy(v),
+					String::from_utf8_lossy(mode.id()),
+				);
+				Ok(true)
+			},
+			Some(v) => Err(Error::IncompatiblePruningModes {
+				stored: String::from_utf8_lossy(v).into(),
+				requested: String::from_utf8_lossy(mode.id()).into(),
+			}),
+			None => Ok(true),
 		}
 	}
 
@@ -250,9 +284,10 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 		mut changeset: ChangeSet<Key>,
 	) -> Result<CommitSet<Key>, Error<E>> {
 		let mut meta = ChangeSet::default();
-		if number == 0 {
-			// Save pruning mode when writing first block.
+		if number == 0 || self.store_mode {
+			// Save pruning mode when writing first block, or the first block after it changed.
 			meta.inserted.push((to_meta_key(PRUNING_MODE, &()), self.mode.id().into()));
+			self.store_mode = false;
 		}
 
 		match self.mode {
@@ -442,13 +477,28 @@ pub struct StateDb<BlockHash: Hash, Key: Hash> {
 
 impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDb<BlockHash, Key> {
 	/// Creates a new instance. Does not expect any metadata in the database.
+	///
+	/// Fails if the database was created with a different pruning mode.
 	pub fn new<D: MetaDb>(
 		mode: PruningMode,
 		ref_counting: bool,
 		db: &D,
+	) -> Result<StateDb<BlockHash, Key>, Error<D::Error>> {
+		Self::open(mode, ref_counting, false, db)
+	}
+
+	/// Creates a new instance, optionally overriding the pruning mode stored in the database.
+	///
+	/// With `force_mode` set a mismatching mode is only logged and the requested one is stored
+	/// with the next inserted block.
+	pub fn open<D: MetaDb>(
+		mode: PruningMode,
+		ref_counting: bool,
+		force_mode: bool,
+		db: &D,
 	) -> Result<StateDb<BlockHash, Key>, Error<D::Error>> {
 		Ok(StateDb {
-			db: RwLock::new(StateDbSync::new(mode, ref_counting, db)?)
+			db: RwLock::new(StateDbSync::new(mode, ref_counting, force_mode, db)?)
 		})
 	}
 
@@ -663,4 +713,38 @@ mod tests {
 		let state_db: Result<StateDb<H256, H256>, _> = StateDb::new(new_mode, false, &db);
 		assert!(state_db.is_err());
 	}
+
+	#[test]
+	fn forced_mode_change_is_stored() {
+		let mut db = make_db(&[]);
+		let state_db = StateDb::new(PruningMode::ArchiveAll, false, &db).unwrap();
+		db.commit(
+			&state_db
+			.insert_block::<io::Error>(
+				&H256::from_low_u64_be(0),
+				0,
+				&H256::from_low_u64_be(0),
+				make_changeset(&[], &[]),
+			)
+			.unwrap(),
+		);
+
+		let new_mode = PruningMode::keep_blocks(2);
+		let state_db: StateDb<H256, H256> = StateDb::open(new_mode.clone(), false, true, &db)
+			.unwrap();
+		db.commit(
+			&state_db
+			.insert_block::<io::Error>(
+				&H256::from_low_u64_be(1),
+				1,
+				&H256::from_low_u64_be(0),
+				make_changeset(&[], &[]),
+			)
+			.unwrap(),
+		);
+
+		// The new mode is accepted without forcing from now on.
+		assert!(StateDb::<H256, H256>::new(new_mode, false, &db).is_ok());
+		assert!(StateDb::<H256, H256>::new(PruningMode::ArchiveAll, false, &db).is_err());
+	}
 }
@@ -0,0 +1,66 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the assets module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Codec, Encode, Decode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
+
+/// The metadata of an asset, as displayed by wallets.
+#[derive(Eq, PartialEq, Encode, Decode, Default, Clone)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetMetadata {
+	/// The user friendly name of the asset.
+	pub name: Vec<u8>,
+	/// The ticker symbol of the asset.
+	pub symbol: Vec<u8>,
+	/// The number of decimals the asset uses to represent one unit.
+	pub decimals: u8,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetsApi<AssetId> where
+		AssetId: Codec,
+	{
+		/// Returns the metadata of the asset `id`, or `None` if none is set.
+		fn metadata(id: AssetId) -> Option<AssetMetadata>;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_and_deserialize_properly() {
+		let metadata = AssetMetadata {
+			name: b"DOT".to_vec(),
+			symbol: b"D".to_vec(),
+			decimals: 10,
+		};
+
+		let json_str = r#"{"name":[68,79,84],"symbol":[68],"decimals":10}"#;
+
+		assert_eq!(serde_json::to_string(&metadata).unwrap(), json_str);
+		assert_eq!(serde_json::from_str::<AssetMetadata>(json_str).unwrap(), metadata);
+	}
+}
@@ -21,12 +21,16 @@
 //!
 //! ## Overview
 //!
-//! The Assets module provides functionality for asset management of fungible asset classes
-//! with a fixed supply, including:
+//! The Assets module provides functionality for asset management of fungible asset classes,
+//! including:
 //!
 //! * Asset Issuance
 //! * Asset Transfer
+//! * Asset Transfer on behalf of another account (approvals)
 //! * Asset Destruction
+//! * Asset Metadata (name, symbol and decimals)
+//! * Asset Administration (minting, burning, freezing and force transfers by role accounts)
+//! * Asset Class Destruction, spread over several calls for assets with many holders
 //!
 //! To use it in your runtime, you need to implement the assets [`Trait`](./trait.Trait.html).
 //!
@@ -37,7 +41,23 @@
 //! * **Asset issuance:** The creation of a new asset, whose total supply will belong to the
 //!   account that issues the asset.
 //! * **Asset transfer:** The action of transferring assets from one account to another.
+//! * **Approval:** An allowance given by the holder of an asset to a delegate account, which lets
+//!   the delegate transfer up to that amount of the holder's assets. A deposit is reserved from
+//!   the holder for as long as the approval exists.
+//! * **Asset owner:** The account that issued an asset, or the one it was handed over to. Only
+//!   the owner may set its metadata and appoint the other role accounts.
+//! * **Issuer:** The account allowed to mint new units of an asset.
+//! * **Admin:** The account allowed to burn units from any account, thaw accounts or the asset,
+//!   and force transfers between accounts.
+//! * **Freezer:** The account allowed to freeze accounts or the whole asset. Frozen accounts
+//!   (or all accounts of a frozen asset) can't transfer their units of the asset.
+//! * **Metadata:** The name, symbol and number of decimals of an asset, for display by wallets.
+//!   A deposit depending on the length of the name and symbol is reserved from the owner while
+//!   the metadata is set.
 //! * **Asset destruction:** The process of an account removing its entire holding of an asset.
+//! * **Asset class destruction:** The process of removing an asset altogether. It is started by
+//!   the owner, after which the holdings and approvals of the asset are removed in batches whose
+//!   size is given by the caller, and the asset is finally removed once none are left.
 //! * **Fungible asset:** An asset whose units are interchangeable.
 //! * **Non-fungible asset:** An asset for which each unit has unique characteristics.
 //!
@@ -59,6 +79,28 @@
 //! the function caller's account (`origin`) to a `target` account.
 //! * `destroy` - Destroys the entire holding of a fungible asset `id` associated with the account
 //! that called the function.
+//! * `approve_transfer` - Allows a `delegate` account to transfer up to an `amount` of the caller's
+//! units of asset `id`.
+//! * `cancel_approval` - Revokes a previously given approval and refunds its deposit.
+//! * `transfer_approved` - Transfers units of asset `id` on behalf of an `owner` who approved the
+//! caller to do so.
+//! * `set_metadata` - Sets the name, symbol and decimals of an asset owned by the caller.
+//! * `clear_metadata` - Clears the metadata of an asset owned by the caller and refunds its deposit.
+//! * `force_set_metadata` - Sets the metadata of any asset from the `ForceOrigin`.
+//! * `mint` - Mints new units of an asset; callable by its issuer.
+//! * `burn` - Burns units of an asset from any account; callable by its admin.
+//! * `force_transfer` - Moves units of an asset between any accounts; callable by its admin.
+//! * `freeze` / `thaw` - Freezes or thaws an account's holding of an asset; callable by its
+//! freezer and admin respectively.
+//! * `freeze_asset` / `thaw_asset` - Freezes or thaws all holdings of an asset; callable by its
+//! freezer and admin respectively.
+//! * `transfer_ownership` - Hands over ownership of an asset; callable by its owner.
+//! * `set_team` - Appoints the issuer, admin and freezer of an asset; callable by its owner.
+//! * `start_destroy` - Starts the destruction of an asset; callable by its owner or the
+//! `ForceOrigin`.
+//! * `destroy_accounts` / `destroy_approvals` - Remove up to a given number of holdings or
+//! approvals of an asset being destroyed.
+//! * `finish_destroy` - Removes an asset being destroyed once it has no holdings or approvals.
 //!
 //! Please refer to the [`Call`](./enum.Call.html) enum and its associated variants for documentation on each function.
 //!
@@ -67,9 +109,22 @@
 //!
 //! * `balance` - Get the asset `id` balance of `who`.
 //! * `total_supply` - Get the total supply of an asset `id`.
+//! * `approval` - Get the amount of asset `id` that `delegate` may transfer on behalf of `owner`.
+//! * `is_frozen` - Whether `who` can't transfer units of an asset `id` because of freezing.
+//! * `metadata` - Get the metadata of an asset `id`, if any is set. Runtimes can expose it to
+//!   wallets through the `AssetsApi` of `pallet-assets-runtime-api`.
+//! * `do_transfer`, `do_mint` and `do_burn` - Move, mint and burn units of an asset without any
+//!   permission checks, for use by other modules.
 //!
 //! Please refer to the [`Module`](./struct.Module.html) struct for details on publicly available functions.
 //!
+//! ### Implementations
+//!
+//! The Assets module implements the `fungibles::{Inspect, Mutate, Transfer, InspectHold,
+//! MutateHold}` traits of `frame_support`, so that other modules can be written against any set
+//! of fungible assets. Units placed on hold are kept apart from the free units of an account and
+//! can't be transferred until they are released.
+//!
 //! ## Usage
 //!
 //! The following example shows how to use the Assets module in your runtime by exposing public functions to:
@@ -106,8 +161,8 @@
 //! 			let asset_id = Self::next_asset_id();
 //!
 //! 			<NextAssetId<T>>::mutate(|asset_id| *asset_id += 1);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_ALICE), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_BOB), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
+//! 			<Balances<T>>::insert(asset_id, &ACCOUNT_ALICE, TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
+//! 			<Balances<T>>::insert(asset_id, &ACCOUNT_BOB, TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
 //! 			<TotalSupply<T>>::insert(asset_id, TOKENS_FIXED_SUPPLY);
 //!
 //! 			Self::deposit_event(RawEvent::Issued(asset_id, sender, TOKENS_FIXED_SUPPLY));
@@ -133,11 +188,26 @@
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{Parameter, decl_module, decl_event, decl_storage, decl_error, ensure};
-use sp_runtime::traits::{Member, AtLeast32Bit, AtLeast32BitUnsigned, Zero, StaticLookup};
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure,
+	IterableStorageDoubleMap,
+	traits::{
+		Currency, ReservableCurrency, Get, EnsureOrigin, BalanceStatus,
+		fungibles::{self, DepositConsequence, WithdrawConsequence},
+	},
+	weights::Weight,
+};
+use sp_runtime::{RuntimeDebug, DispatchResult, DispatchError};
+use sp_runtime::traits::{Member, AtLeast32Bit, AtLeast32BitUnsigned, Zero, StaticLookup, Saturating,
+	CheckedAdd};
 use frame_system::ensure_signed;
 use sp_runtime::traits::One;
 
+type DepositBalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
 /// The module configuration trait.
 pub trait Trait: frame_system::Trait {
 	/// The overarching event type.
@@ -148,21 +218,91 @@ pub trait Trait: frame_system::Trait {
 
 	/// The arithmetic type of asset identifier.
 	type AssetId: Parameter + AtLeast32Bit + Default + Copy;
+
+	/// The currency in which approval deposits are held.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The amount reserved from the owner for as long as an approval exists.
+	type ApprovalDeposit: Get<DepositBalanceOf<Self>>;
+
+	/// The base amount reserved from the owner for setting the metadata of an asset.
+	type MetadataDepositBase: Get<DepositBalanceOf<Self>>;
+
+	/// The additional amount reserved from the owner per byte of the name and symbol.
+	type MetadataDepositPerByte: Get<DepositBalanceOf<Self>>;
+
+	/// The maximum length of the name and of the symbol of an asset.
+	type StringLimit: Get<u32>;
+
+	/// The origin which may set the metadata of any asset.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+}
+
+/// General information about an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetDetails<AccountId> {
+	/// The account that may set the metadata and appoint the other roles.
+	owner: AccountId,
+	/// The account that may mint new units.
+	issuer: AccountId,
+	/// The account that may burn units, thaw and force transfers.
+	admin: AccountId,
+	/// The account that may freeze.
+	freezer: AccountId,
+	/// Whether transfers of the asset are frozen for all accounts.
+	is_frozen: bool,
+	/// Whether the asset is being destroyed.
+	is_destroying: bool,
+}
+
+/// The metadata of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetMetadata<DepositBalance> {
+	/// The amount reserved from the owner for this metadata.
+	deposit: DepositBalance,
+	/// The user friendly name of the asset.
+	pub name: Vec<u8>,
+	/// The ticker symbol of the asset.
+	pub symbol: Vec<u8>,
+	/// The number of decimals the asset uses to represent one unit.
+	pub decimals: u8,
+}
+
+/// An allowance given by an asset holder to a delegate.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct Approval<Balance, DepositBalance> {
+	/// The amount of the asset the delegate may still transfer.
+	amount: Balance,
+	/// The amount reserved from the owner for this approval.
+	deposit: DepositBalance,
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
 
+		/// The amount reserved from the owner for as long as an approval exists.
+		const ApprovalDeposit: DepositBalanceOf<T> = T::ApprovalDeposit::get();
+
+		/// The base amount reserved from the owner for setting the metadata of an asset.
+		const MetadataDepositBase: DepositBalanceOf<T> = T::MetadataDepositBase::get();
+
+		/// The additional amount reserved from the owner per byte of the name and symbol.
+		const MetadataDepositPerByte: DepositBalanceOf<T> = T::MetadataDepositPerByte::get();
+
+		/// The maximum length of the name and of the symbol of an asset.
+		const StringLimit: u32 = T::StringLimit::get();
+
 		fn deposit_event() = default;
-		/// Issue a new class of fungible assets. There are, and will only ever be, `total`
-		/// such assets and they'll all belong to the `origin` initially. It will have an
-		/// identifier `AssetId` instance: this will be specified in the `Issued` event.
+		/// Issue a new class of fungible assets. Initially there are `total` such assets and
+		/// they'll all belong to the `origin`, which also becomes the owner, issuer, admin and
+		/// freezer of the asset. It will have an identifier `AssetId` instance: this will be
+		/// specified in the `Issued` event.
 		///
 		/// # <weight>
 		/// - `O(1)`
 		/// - 1 storage mutation (codec `O(1)`).
-		/// - 2 storage writes (condec `O(1)`).
+		/// - 3 storage writes (condec `O(1)`).
 		/// - 1 event.
 		/// # </weight>
 		#[weight = 0]
@@ -172,8 +312,16 @@ decl_module! {
 			let id = Self::next_asset_id();
 			<NextAssetId<T>>::mutate(|id| *id += One::one());
 
-			<Balances<T>>::insert((id, &origin), total);
+			<Balances<T>>::insert(id, &origin, total);
 			<TotalSupply<T>>::insert(id, total);
+			<Asset<T>>::insert(id, AssetDetails {
+				owner: origin.clone(),
+				issuer: origin.clone(),
+				admin: origin.clone(),
+				freezer: origin.clone(),
+				is_frozen: false,
+				is_destroying: false,
+			});
 
 			Self::deposit_event(RawEvent::Issued(id, origin, total));
 		}
@@ -193,15 +341,10 @@ decl_module! {
 			#[compact] amount: T::Balance
 		) {
 			let origin = ensure_signed(origin)?;
-			let origin_account = (id, origin.clone());
-			let origin_balance = <Balances<T>>::get(&origin_account);
 			let target = T::Lookup::lookup(target)?;
-			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
-			ensure!(origin_balance >= amount, Error::<T>::BalanceLow);
+			ensure!(!Self::is_frozen(id, &origin), Error::<T>::Frozen);
 
-			Self::deposit_event(RawEvent::Transferred(id, origin, target.clone(), amount));
-			<Balances<T>>::insert(origin_account, origin_balance - amount);
-			<Balances<T>>::mutate((id, target), |balance| *balance += amount);
+			Self::do_transfer(id, origin, target, amount)?;
 		}
 
 		/// Destroy any assets of `id` owned by `origin`.
@@ -215,12 +358,609 @@ decl_module! {
 		#[weight = 0]
 		fn destroy(origin, #[compact] id: T::AssetId) {
 			let origin = ensure_signed(origin)?;
-			let balance = <Balances<T>>::take((id, &origin));
+			let balance = <Balances<T>>::take(id, &origin);
 			ensure!(!balance.is_zero(), Error::<T>::BalanceZero);
 
 			<TotalSupply<T>>::mutate(id, |total_supply| *total_supply -= balance);
 			Self::deposit_event(RawEvent::Destroyed(id, origin, balance));
 		}
+
+		/// Approve `delegate` to transfer up to `amount` of the `origin`'s units of asset `id`.
+		///
+		/// The amount is added on top of any existing approval for the same delegate. The first
+		/// approval for a delegate reserves `ApprovalDeposit` from the `origin`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 1 storage mutation (codec `O(1)`).
+		/// - 1 reserve on first approval.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 2)]
+		fn approve_transfer(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(!details.is_destroying, Error::<T>::Destroying);
+
+			<Approvals<T>>::try_mutate(id, (&owner, &delegate), |maybe_approval| -> DispatchResult {
+				let mut approval = match maybe_approval.take() {
+					Some(approval) => approval,
+					None => {
+						let deposit = T::ApprovalDeposit::get();
+						T::Currency::reserve(&owner, deposit)?;
+						Approval { amount: Zero::zero(), deposit }
+					},
+				};
+				approval.amount = approval.amount.saturating_add(amount);
+				*maybe_approval = Some(approval);
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::ApprovedTransfer(id, owner, delegate, amount));
+		}
+
+		/// Cancel the approval of `delegate` to transfer the `origin`'s units of asset `id` and
+		/// refund its deposit.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 1 storage deletion (codec `O(1)`).
+		/// - 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 2)]
+		fn cancel_approval(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			let approval = <Approvals<T>>::take(id, (&owner, &delegate))
+				.ok_or(Error::<T>::Unapproved)?;
+			T::Currency::unreserve(&owner, approval.deposit);
+
+			Self::deposit_event(RawEvent::ApprovalCancelled(id, owner, delegate));
+		}
+
+		/// Transfer `amount` of asset `id` from `owner` to `destination`, using an approval the
+		/// `owner` previously gave to the `origin`.
+		///
+		/// Once the approval is used up it is removed and its deposit is refunded to the `owner`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 static lookups
+		/// - 3 storage mutations (codec `O(1)`).
+		/// - 1 unreserve when the approval is used up.
+		/// - 2 events.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(6, 4)]
+		fn transfer_approved(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let destination = T::Lookup::lookup(destination)?;
+
+			let key = (owner.clone(), delegate.clone());
+			let mut approval = <Approvals<T>>::get(id, &key).ok_or(Error::<T>::Unapproved)?;
+			ensure!(approval.amount >= amount, Error::<T>::Unapproved);
+			ensure!(!Self::is_frozen(id, &owner), Error::<T>::Frozen);
+
+			Self::do_transfer(id, owner.clone(), destination.clone(), amount)?;
+
+			approval.amount -= amount;
+			if approval.amount.is_zero() {
+				T::Currency::unreserve(&owner, approval.deposit);
+				<Approvals<T>>::remove(id, &key);
+			} else {
+				<Approvals<T>>::insert(id, &key, approval);
+			}
+
+			Self::deposit_event(
+				RawEvent::TransferredApproved(id, owner, delegate, destination, amount)
+			);
+		}
+
+		/// Set the `name`, `symbol` and `decimals` of an asset `id` owned by the `origin`.
+		///
+		/// Reserves `MetadataDepositBase + MetadataDepositPerByte * (name.len + symbol.len)` from
+		/// the `origin`, taking into account the deposit already held for previous metadata.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is the length of the name and symbol, bounded by `StringLimit`.
+		/// - 1 storage read (codec `O(1)`).
+		/// - 1 storage mutation (codec `O(N)`).
+		/// - 1 reserve or unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 2)]
+		fn set_metadata(origin,
+			#[compact] id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8
+		) {
+			let origin = ensure_signed(origin)?;
+			Self::ensure_bounded(&name, &symbol)?;
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.owner, Error::<T>::NoPermission);
+
+			<Metadata<T>>::try_mutate_exists(id, |metadata| -> DispatchResult {
+				let old_deposit = metadata.take().map_or(Zero::zero(), |m| m.deposit);
+				let new_deposit = T::MetadataDepositPerByte::get()
+					.saturating_mul(((name.len() + symbol.len()) as u32).into())
+					.saturating_add(T::MetadataDepositBase::get());
+
+				if new_deposit > old_deposit {
+					T::Currency::reserve(&origin, new_deposit - old_deposit)?;
+				} else {
+					T::Currency::unreserve(&origin, old_deposit - new_deposit);
+				}
+
+				*metadata = Some(AssetMetadata {
+					deposit: new_deposit,
+					name: name.clone(),
+					symbol: symbol.clone(),
+					decimals,
+				});
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+		}
+
+		/// Clear the metadata of an asset `id` owned by the `origin` and refund its deposit.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage read (codec `O(1)`).
+		/// - 1 storage deletion (codec `O(1)`).
+		/// - 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 2)]
+		fn clear_metadata(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.owner, Error::<T>::NoPermission);
+
+			let metadata = <Metadata<T>>::take(id).ok_or(Error::<T>::Unknown)?;
+			T::Currency::unreserve(&origin, metadata.deposit);
+
+			Self::deposit_event(RawEvent::MetadataCleared(id));
+		}
+
+		/// Set the `name`, `symbol` and `decimals` of any asset `id`.
+		///
+		/// The origin of this call must be `ForceOrigin`. No deposit is reserved; a deposit held
+		/// for previous metadata stays reserved from the owner.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is the length of the name and symbol, bounded by `StringLimit`.
+		/// - 1 storage read (codec `O(1)`).
+		/// - 1 storage mutation (codec `O(N)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1)]
+		fn force_set_metadata(origin,
+			#[compact] id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8
+		) {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::ensure_bounded(&name, &symbol)?;
+			ensure!(<Asset<T>>::contains_key(id), Error::<T>::Unknown);
+
+			<Metadata<T>>::mutate_exists(id, |metadata| {
+				let deposit = metadata.take().map_or(Zero::zero(), |m| m.deposit);
+				*metadata = Some(AssetMetadata {
+					deposit,
+					name: name.clone(),
+					symbol: symbol.clone(),
+					decimals,
+				});
+			});
+
+			Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+		}
+
+		/// Mint `amount` new units of asset `id` to the `beneficiary`.
+		///
+		/// The origin must be the issuer of the asset.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 1 storage read (codec `O(1)`).
+		/// - 2 storage mutations (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(4, 2)]
+		fn mint(origin,
+			#[compact] id: T::AssetId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.issuer, Error::<T>::NoPermission);
+
+			Self::do_mint(id, beneficiary, amount)?;
+		}
+
+		/// Burn up to `amount` units of asset `id` from `who`.
+		///
+		/// The origin must be the admin of the asset. If `who` holds less than `amount`, its
+		/// whole holding is burned.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 1 storage read (codec `O(1)`).
+		/// - 2 storage mutations (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 2)]
+		fn burn(origin,
+			#[compact] id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.admin, Error::<T>::NoPermission);
+
+			Self::do_burn(id, who, amount)?;
+		}
+
+		/// Move `amount` units of asset `id` from `source` to `dest`, regardless of freezing.
+		///
+		/// The origin must be the admin of the asset.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 2 static lookups
+		/// - 1 storage read (codec `O(1)`).
+		/// - 2 storage mutations (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(3, 2)]
+		fn force_transfer(origin,
+			#[compact] id: T::AssetId,
+			source: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let source = T::Lookup::lookup(source)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.admin, Error::<T>::NoPermission);
+			ensure!(!details.is_destroying, Error::<T>::Destroying);
+
+			Self::do_transfer(id, source, dest, amount)?;
+		}
+
+		/// Disallow transfers of asset `id` from the account `who`.
+		///
+		/// The origin must be the freezer of the asset.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 1 storage read (codec `O(1)`).
+		/// - 1 storage write (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		fn freeze(origin,
+			#[compact] id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source
+		) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.freezer, Error::<T>::NoPermission);
+
+			<Frozen<T>>::insert(id, &who, true);
+			Self::deposit_event(RawEvent::Frozen(id, who));
+		}
+
+		/// Allow transfers of asset `id` from the account `who` again.
+		///
+		/// The origin must be the admin of the asset.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 1 storage read (codec `O(1)`).
+		/// - 1 storage deletion (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		fn thaw(origin,
+			#[compact] id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source
+		) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.admin, Error::<T>::NoPermission);
+
+			<Frozen<T>>::remove(id, &who);
+			Self::deposit_event(RawEvent::Thawed(id, who));
+		}
+
+		/// Disallow transfers of asset `id` from all accounts.
+		///
+		/// The origin must be the freezer of the asset.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		fn freeze_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			<Asset<T>>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.freezer, Error::<T>::NoPermission);
+				details.is_frozen = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::AssetFrozen(id));
+		}
+
+		/// Allow transfers of asset `id` again.
+		///
+		/// The origin must be the admin of the asset. Accounts frozen individually stay frozen.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		fn thaw_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			<Asset<T>>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.admin, Error::<T>::NoPermission);
+				details.is_frozen = false;
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::AssetThawed(id));
+		}
+
+		/// Hand over the ownership of asset `id` to `owner`.
+		///
+		/// The origin must be the owner of the asset. The deposit held for the metadata of the
+		/// asset is moved to the new owner.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 static lookup
+		/// - 1 storage mutation (codec `O(1)`).
+		/// - 1 storage read (codec `O(1)`).
+		/// - 1 reserve repatriation.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(4, 3)]
+		fn transfer_ownership(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source
+		) {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			<Asset<T>>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.owner, Error::<T>::NoPermission);
+				if details.owner == owner {
+					return Ok(());
+				}
+
+				if let Some(metadata) = <Metadata<T>>::get(id) {
+					T::Currency::repatriate_reserved(
+						&details.owner,
+						&owner,
+						metadata.deposit,
+						BalanceStatus::Reserved,
+					)?;
+				}
+				details.owner = owner.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::OwnerChanged(id, owner));
+		}
+
+		/// Appoint the `issuer`, `admin` and `freezer` of asset `id`.
+		///
+		/// The origin must be the owner of the asset.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 3 static lookups
+		/// - 1 storage mutation (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		fn set_team(origin,
+			#[compact] id: T::AssetId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source
+		) {
+			let origin = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let freezer = T::Lookup::lookup(freezer)?;
+
+			<Asset<T>>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				ensure!(origin == details.owner, Error::<T>::NoPermission);
+				details.issuer = issuer.clone();
+				details.admin = admin.clone();
+				details.freezer = freezer.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::TeamChanged(id, issuer, admin, freezer));
+		}
+
+		/// Start the destruction of asset `id`.
+		///
+		/// The origin must be either the owner of the asset or `ForceOrigin`. From then on the
+		/// asset can't be transferred, minted or approved anymore, and anyone may remove its
+		/// holdings and approvals with `destroy_accounts` and `destroy_approvals`, then the asset
+		/// itself with `finish_destroy`.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 1 storage mutation (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)]
+		fn start_destroy(origin, #[compact] id: T::AssetId) {
+			let maybe_owner = match T::ForceOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			<Asset<T>>::try_mutate(id, |maybe_details| -> DispatchResult {
+				let details = maybe_details.as_mut().ok_or(Error::<T>::Unknown)?;
+				if let Some(owner) = maybe_owner {
+					ensure!(owner == details.owner, Error::<T>::NoPermission);
+				}
+				details.is_destroying = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(RawEvent::DestructionStarted(id));
+		}
+
+		/// Remove up to `max_accounts` holdings of asset `id`, which must be being destroyed.
+		///
+		/// `max_accounts` is the witness the weight of the call is computed from; the units held
+		/// by the removed accounts are taken out of the total supply. Free units are removed
+		/// before units on hold.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is `max_accounts`.
+		/// - 1 storage read (codec `O(1)`).
+		/// - `N` storage iterations and `2 * N` storage deletions (codec `O(1)`).
+		/// - 1 storage mutation (codec `O(1)`).
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1).saturating_add(
+			T::DbWeight::get().reads_writes(1, 2).saturating_mul(*max_accounts as Weight)
+		)]
+		fn destroy_accounts(origin, #[compact] id: T::AssetId, max_accounts: u32) {
+			ensure_signed(origin)?;
+			Self::ensure_destroying(id)?;
+
+			let accounts = <Balances<T>>::iter_prefix(id)
+				.take(max_accounts as usize)
+				.collect::<Vec<_>>();
+			let held = <Held<T>>::iter_prefix(id)
+				.take((max_accounts as usize).saturating_sub(accounts.len()))
+				.collect::<Vec<_>>();
+			let mut burned = T::Balance::zero();
+			for (who, balance) in accounts.iter() {
+				<Balances<T>>::remove(id, who);
+				<Frozen<T>>::remove(id, who);
+				burned = burned.saturating_add(*balance);
+			}
+			for (who, balance) in held.iter() {
+				<Held<T>>::remove(id, who);
+				burned = burned.saturating_add(*balance);
+			}
+			<TotalSupply<T>>::mutate(id, |total_supply| {
+				*total_supply = total_supply.saturating_sub(burned)
+			});
+
+			let destroyed = accounts.len() + held.len();
+			Self::deposit_event(RawEvent::AccountsDestroyed(id, destroyed as u32));
+		}
+
+		/// Remove up to `max_approvals` approvals of asset `id`, which must be being destroyed,
+		/// and refund their deposits.
+		///
+		/// `max_approvals` is the witness the weight of the call is computed from.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is `max_approvals`.
+		/// - 1 storage read (codec `O(1)`).
+		/// - `N` storage iterations and deletions (codec `O(1)`).
+		/// - `N` unreserves.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads(1).saturating_add(
+			T::DbWeight::get().reads_writes(2, 2).saturating_mul(*max_approvals as Weight)
+		)]
+		fn destroy_approvals(origin, #[compact] id: T::AssetId, max_approvals: u32) {
+			ensure_signed(origin)?;
+			Self::ensure_destroying(id)?;
+
+			let approvals = <Approvals<T>>::iter_prefix(id)
+				.take(max_approvals as usize)
+				.collect::<Vec<_>>();
+			for ((owner, delegate), approval) in approvals.iter() {
+				T::Currency::unreserve(owner, approval.deposit);
+				<Approvals<T>>::remove(id, (owner, delegate));
+			}
+
+			Self::deposit_event(RawEvent::ApprovalsDestroyed(id, approvals.len() as u32));
+		}
+
+		/// Remove asset `id`, which must be being destroyed and have no holdings or approvals
+		/// left, and refund the deposit of its metadata.
+		///
+		/// # <weight>
+		/// - `O(1)`
+		/// - 4 storage reads (codec `O(1)`).
+		/// - 4 storage deletions, 1 of them a prefix (codec `O(1)`).
+		/// - 1 unreserve.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(5, 5)]
+		fn finish_destroy(origin, #[compact] id: T::AssetId) {
+			ensure_signed(origin)?;
+			let details = Self::ensure_destroying(id)?;
+			ensure!(<Balances<T>>::iter_prefix(id).next().is_none(), Error::<T>::InUse);
+			ensure!(<Held<T>>::iter_prefix(id).next().is_none(), Error::<T>::InUse);
+			ensure!(<Approvals<T>>::iter_prefix(id).next().is_none(), Error::<T>::InUse);
+
+			if let Some(metadata) = <Metadata<T>>::take(id) {
+				T::Currency::unreserve(&details.owner, metadata.deposit);
+			}
+			<Frozen<T>>::remove_prefix(id);
+			<TotalSupply<T>>::remove(id);
+			<Asset<T>>::remove(id);
+
+			Self::deposit_event(RawEvent::AssetDestroyed(id));
+		}
 	}
 }
 
@@ -236,6 +976,44 @@ decl_event! {
 		Transferred(AssetId, AccountId, AccountId, Balance),
 		/// Some assets were destroyed.
 		Destroyed(AssetId, AccountId, Balance),
+		/// An owner (second) approved a delegate (third) to transfer some assets.
+		ApprovedTransfer(AssetId, AccountId, AccountId, Balance),
+		/// An owner (second) cancelled the approval of a delegate (third).
+		ApprovalCancelled(AssetId, AccountId, AccountId),
+		/// A delegate (third) transferred some assets of an owner (second) to a destination
+		/// (fourth).
+		TransferredApproved(AssetId, AccountId, AccountId, AccountId, Balance),
+		/// The metadata of an asset was set.
+		MetadataSet(AssetId, Vec<u8>, Vec<u8>, u8),
+		/// The metadata of an asset was cleared.
+		MetadataCleared(AssetId),
+		/// Transfers of an asset from an account were frozen.
+		Frozen(AssetId, AccountId),
+		/// Transfers of an asset from an account were thawed.
+		Thawed(AssetId, AccountId),
+		/// Transfers of an asset were frozen for all accounts.
+		AssetFrozen(AssetId),
+		/// Transfers of an asset were thawed for all accounts.
+		AssetThawed(AssetId),
+		/// The owner of an asset changed.
+		OwnerChanged(AssetId, AccountId),
+		/// The issuer (second), admin (third) and freezer (fourth) of an asset changed.
+		TeamChanged(AssetId, AccountId, AccountId, AccountId),
+		/// The destruction of an asset was started.
+		DestructionStarted(AssetId),
+		/// Some holdings of an asset being destroyed were removed.
+		AccountsDestroyed(AssetId, u32),
+		/// Some approvals of an asset being destroyed were removed.
+		ApprovalsDestroyed(AssetId, u32),
+		/// An asset was destroyed.
+		AssetDestroyed(AssetId),
+		/// Some units of an asset of an account were placed on hold.
+		Held(AssetId, AccountId, Balance),
+		/// Some held units of an asset of an account were released.
+		Released(AssetId, AccountId, Balance),
+		/// Some held units of an asset were transferred from a source (second) to a destination
+		/// (third), which holds them if the last parameter is `true`.
+		HeldTransferred(AssetId, AccountId, AccountId, Balance, bool),
 	}
 }
 
@@ -247,19 +1025,66 @@ decl_error! {
 		BalanceLow,
 		/// Balance should be non-zero
 		BalanceZero,
+		/// No approval exists that would allow the transfer
+		Unapproved,
+		/// The given asset or its metadata does not exist
+		Unknown,
+		/// The origin is not allowed to perform this operation on the asset
+		NoPermission,
+		/// The name or symbol is longer than the `StringLimit`
+		BadMetadata,
+		/// The account or the asset is frozen
+		Frozen,
+		/// The total supply would overflow
+		Overflow,
+		/// The asset is being destroyed
+		Destroying,
+		/// The asset is not being destroyed
+		NotDestroying,
+		/// The asset still has holdings or approvals
+		InUse,
 	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Assets {
 		/// The number of units of assets held by any given account.
-		Balances: map hasher(blake2_128_concat) (T::AssetId, T::AccountId) => T::Balance;
+		///
+		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
+		Balances: double_map hasher(twox_64_concat) T::AssetId,
+			hasher(blake2_128_concat) T::AccountId => T::Balance;
 		/// The next asset identifier up for grabs.
 		NextAssetId get(fn next_asset_id): T::AssetId;
 		/// The total unit supply of an asset.
 		///
 		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
 		TotalSupply: map hasher(twox_64_concat) T::AssetId => T::Balance;
+		/// Approved transfers of an asset, keyed by the asset, the owner and the delegate.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
+		Approvals: double_map hasher(twox_64_concat) T::AssetId,
+			hasher(blake2_128_concat) (T::AccountId, T::AccountId)
+			=> Option<Approval<T::Balance, DepositBalanceOf<T>>>;
+		/// General information about an asset.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
+		Asset: map hasher(twox_64_concat) T::AssetId => Option<AssetDetails<T::AccountId>>;
+		/// Accounts that may not transfer their units of an asset.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
+		Frozen: double_map hasher(twox_64_concat) T::AssetId,
+			hasher(blake2_128_concat) T::AccountId => bool;
+		/// The metadata of an asset.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
+		Metadata: map hasher(twox_64_concat) T::AssetId
+			=> Option<AssetMetadata<DepositBalanceOf<T>>>;
+		/// The number of units of assets placed on hold for any given account. These are not part
+		/// of `Balances`, but are part of the total supply.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted, so this is safe.
+		Held: double_map hasher(twox_64_concat) T::AssetId,
+			hasher(blake2_128_concat) T::AccountId => T::Balance;
 	}
 }
 
@@ -269,13 +1094,291 @@ impl<T: Trait> Module<T> {
 
 	/// Get the asset `id` balance of `who`.
 	pub fn balance(id: T::AssetId, who: T::AccountId) -> T::Balance {
-		<Balances<T>>::get((id, who))
+		<Balances<T>>::get(id, who)
+	}
+
+	/// Get the number of units of asset `id` of `who` which are on hold.
+	pub fn held_balance(id: T::AssetId, who: T::AccountId) -> T::Balance {
+		<Held<T>>::get(id, who)
 	}
 
 	/// Get the total supply of an asset `id`.
 	pub fn total_supply(id: T::AssetId) -> T::Balance {
 		<TotalSupply<T>>::get(id)
 	}
+
+	/// Get the amount of asset `id` that `delegate` may transfer on behalf of `owner`.
+	pub fn approval(id: T::AssetId, owner: T::AccountId, delegate: T::AccountId) -> T::Balance {
+		<Approvals<T>>::get(id, (owner, delegate)).map(|a| a.amount).unwrap_or_else(Zero::zero)
+	}
+
+	/// Whether `who` can't transfer units of an asset `id`, either because the account or the
+	/// whole asset is frozen, or because the asset is being destroyed.
+	pub fn is_frozen(id: T::AssetId, who: &T::AccountId) -> bool {
+		<Frozen<T>>::get(id, who) ||
+			<Asset<T>>::get(id).map_or(false, |d| d.is_frozen || d.is_destroying)
+	}
+
+	/// Get the metadata of an asset `id`, if any is set.
+	pub fn metadata(id: T::AssetId) -> Option<AssetMetadata<DepositBalanceOf<T>>> {
+		<Metadata<T>>::get(id)
+	}
+
+	// Public mutables
+
+	/// Move `amount` of asset `id` from `source` to `target`.
+	///
+	/// Freezing is not checked here; callers acting on behalf of `source` should check
+	/// `is_frozen` first.
+	pub fn do_transfer(
+		id: T::AssetId,
+		source: T::AccountId,
+		target: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let source_balance = <Balances<T>>::get(id, &source);
+		ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+		ensure!(source_balance >= amount, Error::<T>::BalanceLow);
+
+		Self::deposit_event(RawEvent::Transferred(id, source.clone(), target.clone(), amount));
+		<Balances<T>>::insert(id, &source, source_balance - amount);
+		<Balances<T>>::mutate(id, &target, |balance| *balance += amount);
+		Ok(())
+	}
+
+	/// Mint `amount` new units of asset `id` into the account of `beneficiary`.
+	///
+	/// Permissions are not checked here.
+	pub fn do_mint(id: T::AssetId, beneficiary: T::AccountId, amount: T::Balance) -> DispatchResult {
+		let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+		ensure!(!details.is_destroying, Error::<T>::Destroying);
+		ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+
+		<TotalSupply<T>>::try_mutate(id, |total_supply| -> DispatchResult {
+			*total_supply = total_supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+			Ok(())
+		})?;
+		<Balances<T>>::mutate(id, &beneficiary, |balance| *balance += amount);
+
+		Self::deposit_event(RawEvent::Issued(id, beneficiary, amount));
+		Ok(())
+	}
+
+	/// Burn up to `amount` units of asset `id` from the account of `who`, returning the amount
+	/// actually burned.
+	///
+	/// Permissions are not checked here.
+	pub fn do_burn(
+		id: T::AssetId,
+		who: T::AccountId,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		let balance = <Balances<T>>::get(id, &who);
+		let burned = amount.min(balance);
+		ensure!(!burned.is_zero(), Error::<T>::BalanceZero);
+
+		<Balances<T>>::insert(id, &who, balance - burned);
+		<TotalSupply<T>>::mutate(id, |total_supply| *total_supply -= burned);
+
+		Self::deposit_event(RawEvent::Destroyed(id, who, burned));
+		Ok(burned)
+	}
+
+	// Private mutables
+
+	/// Set the held balance of `who`, removing the entry once nothing is held.
+	fn set_held(id: T::AssetId, who: &T::AccountId, held: T::Balance) {
+		if held.is_zero() {
+			<Held<T>>::remove(id, who);
+		} else {
+			<Held<T>>::insert(id, who, held);
+		}
+	}
+
+	/// Ensure the asset `id` is being destroyed and return its details.
+	fn ensure_destroying(id: T::AssetId) -> Result<AssetDetails<T::AccountId>, DispatchError> {
+		let details = <Asset<T>>::get(id).ok_or(Error::<T>::Unknown)?;
+		ensure!(details.is_destroying, Error::<T>::NotDestroying);
+		Ok(details)
+	}
+
+	/// Ensure the `name` and `symbol` of an asset are within the `StringLimit`.
+	fn ensure_bounded(name: &[u8], symbol: &[u8]) -> DispatchResult {
+		let limit = T::StringLimit::get() as usize;
+		ensure!(name.len() <= limit && symbol.len() <= limit, Error::<T>::BadMetadata);
+		Ok(())
+	}
+}
+
+impl<T: Trait> fungibles::Inspect<T::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		<TotalSupply<T>>::get(asset)
+	}
+
+	fn minimum_balance(_asset: Self::AssetId) -> Self::Balance {
+		// Assets have no existential deposit.
+		Zero::zero()
+	}
+
+	fn balance(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		<Balances<T>>::get(asset, who).saturating_add(<Held<T>>::get(asset, who))
+	}
+
+	fn reducible_balance(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		_keep_alive: bool,
+	) -> Self::Balance {
+		if Self::is_frozen(asset, who) {
+			Zero::zero()
+		} else {
+			<Balances<T>>::get(asset, who)
+		}
+	}
+
+	fn can_deposit(
+		asset: Self::AssetId,
+		_who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DepositConsequence {
+		match <Asset<T>>::get(asset) {
+			// An asset being destroyed no longer accepts new units.
+			Some(details) if !details.is_destroying => (),
+			_ => return DepositConsequence::UnknownAsset,
+		}
+		// The total supply bounds every balance, so only it needs to be checked.
+		if <TotalSupply<T>>::get(asset).checked_add(&amount).is_none() {
+			return DepositConsequence::Overflow
+		}
+		DepositConsequence::Success
+	}
+
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		if !<Asset<T>>::contains_key(asset) {
+			return WithdrawConsequence::UnknownAsset
+		}
+		if Self::is_frozen(asset, who) {
+			return WithdrawConsequence::Frozen
+		}
+		if <Balances<T>>::get(asset, who) < amount {
+			return WithdrawConsequence::NoFunds
+		}
+		if <TotalSupply<T>>::get(asset) < amount {
+			return WithdrawConsequence::Underflow
+		}
+		WithdrawConsequence::Success
+	}
+}
+
+impl<T: Trait> fungibles::Mutate<T::AccountId> for Module<T> {
+	fn mint_into(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() { return Ok(()) }
+		Self::do_mint(asset, who.clone(), amount)
+	}
+
+	fn burn_from(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		if amount.is_zero() { return Ok(amount) }
+		ensure!(<Asset<T>>::contains_key(asset), Error::<T>::Unknown);
+		ensure!(<Balances<T>>::get(asset, who) >= amount, Error::<T>::BalanceLow);
+		Self::do_burn(asset, who.clone(), amount)
+	}
+}
+
+impl<T: Trait> fungibles::Transfer<T::AccountId> for Module<T> {
+	fn transfer(
+		asset: Self::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		_keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure!(!Self::is_frozen(asset, source), Error::<T>::Frozen);
+		Self::do_transfer(asset, source.clone(), dest.clone(), amount)?;
+		Ok(amount)
+	}
+}
+
+impl<T: Trait> fungibles::InspectHold<T::AccountId> for Module<T> {
+	fn balance_on_hold(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		<Held<T>>::get(asset, who)
+	}
+
+	fn can_hold(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> bool {
+		<Asset<T>>::get(asset).map_or(false, |details| !details.is_destroying) &&
+			!Self::is_frozen(asset, who) &&
+			<Balances<T>>::get(asset, who) >= amount
+	}
+}
+
+impl<T: Trait> fungibles::MutateHold<T::AccountId> for Module<T> {
+	fn hold(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() { return Ok(()) }
+		let details = <Asset<T>>::get(asset).ok_or(Error::<T>::Unknown)?;
+		ensure!(!details.is_destroying, Error::<T>::Destroying);
+		ensure!(!Self::is_frozen(asset, who), Error::<T>::Frozen);
+		let balance = <Balances<T>>::get(asset, who);
+		ensure!(balance >= amount, Error::<T>::BalanceLow);
+
+		<Balances<T>>::insert(asset, who, balance - amount);
+		Self::set_held(asset, who, <Held<T>>::get(asset, who).saturating_add(amount));
+		Self::deposit_event(RawEvent::Held(asset, who.clone(), amount));
+		Ok(())
+	}
+
+	fn release(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		let held = <Held<T>>::get(asset, who);
+		ensure!(best_effort || held >= amount, Error::<T>::BalanceLow);
+		let released = amount.min(held);
+		if released.is_zero() { return Ok(released) }
+
+		Self::set_held(asset, who, held - released);
+		<Balances<T>>::mutate(asset, who, |balance| *balance = balance.saturating_add(released));
+		Self::deposit_event(RawEvent::Released(asset, who.clone(), released));
+		Ok(released)
+	}
+
+	fn transfer_held(
+		asset: Self::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+		on_hold: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		let held = <Held<T>>::get(asset, source);
+		ensure!(best_effort || held >= amount, Error::<T>::BalanceLow);
+		let transferred = amount.min(held);
+		if transferred.is_zero() || source == dest && on_hold { return Ok(transferred) }
+
+		Self::set_held(asset, source, held - transferred);
+		if on_hold {
+			Self::set_held(asset, dest, <Held<T>>::get(asset, dest).saturating_add(transferred));
+		} else {
+			<Balances<T>>::mutate(asset, dest, |balance| {
+				*balance = balance.saturating_add(transferred)
+			});
+		}
+		Self::deposit_event(
+			RawEvent::HeldTransferred(asset, source.clone(), dest.clone(), transferred, on_hold)
+		);
+		Ok(transferred)
+	}
 }
 
 #[cfg(test)]
@@ -284,7 +1387,9 @@ mod tests {
 
 	use frame_support::{impl_outer_origin, assert_ok, assert_noop, parameter_types, weights::Weight};
 	use sp_core::H256;
-	use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+	use sp_runtime::{
+		Perbill, DispatchError, traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+	};
 
 	impl_outer_origin! {
 		pub enum Origin for Test  where system = frame_system {}
@@ -320,20 +1425,48 @@ mod tests {
 		type MaximumBlockLength = MaximumBlockLength;
 		type Version = ();
 		type ModuleToIndex = ();
-		type AccountData = ();
+		type AccountData = pallet_balances::AccountData<u64>;
 		type OnNewAccount = ();
 		type OnKilledAccount = ();
 		type SystemWeightInfo = ();
 	}
+	parameter_types! {
+		pub const ExistentialDeposit: u64 = 1;
+		pub const ApprovalDeposit: u64 = 1;
+		pub const MetadataDepositBase: u64 = 1;
+		pub const MetadataDepositPerByte: u64 = 1;
+		pub const StringLimit: u32 = 4;
+	}
+	impl pallet_balances::Trait for Test {
+		type Balance = u64;
+		type DustRemoval = ();
+		type Event = ();
+		type ExistentialDeposit = ExistentialDeposit;
+		type AccountStore = frame_system::Module<Test>;
+		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
+	}
 	impl Trait for Test {
 		type Event = ();
 		type Balance = u64;
 		type AssetId = u32;
+		type Currency = Balances;
+		type ApprovalDeposit = ApprovalDeposit;
+		type MetadataDepositBase = MetadataDepositBase;
+		type MetadataDepositPerByte = MetadataDepositPerByte;
+		type StringLimit = StringLimit;
+		type ForceOrigin = frame_system::EnsureRoot<u64>;
 	}
 	type Assets = Module<Test>;
+	type Balances = pallet_balances::Module<Test>;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(1, 10), (2, 10)],
+		}.assimilate_storage(&mut t).unwrap();
+		t.into()
 	}
 
 	#[test]
@@ -421,4 +1554,411 @@ mod tests {
 			assert_noop!(Assets::destroy(Origin::signed(2), 0), Error::<Test>::BalanceZero);
 		});
 	}
+
+	#[test]
+	fn approval_lifecycle_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+			assert_eq!(Assets::approval(0, 1, 2), 50);
+			assert_eq!(Balances::reserved_balance(&1), 1);
+
+			assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 40));
+			assert_eq!(Assets::approval(0, 1, 2), 10);
+			assert_eq!(Assets::balance(0, 1), 60);
+			assert_eq!(Assets::balance(0, 3), 40);
+
+			assert_ok!(Assets::cancel_approval(Origin::signed(1), 0, 2));
+			assert_eq!(Assets::approval(0, 1, 2), 0);
+			assert_eq!(Balances::reserved_balance(&1), 0);
+		});
+	}
+
+	#[test]
+	fn approvals_accumulate_with_a_single_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 25));
+			assert_eq!(Assets::approval(0, 1, 2), 75);
+			assert_eq!(Balances::reserved_balance(&1), 1);
+		});
+	}
+
+	#[test]
+	fn using_up_an_approval_refunds_the_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+			assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 2, 50));
+			assert_eq!(Assets::balance(0, 2), 50);
+			assert_eq!(Assets::approval(0, 1, 2), 0);
+			assert_eq!(Balances::reserved_balance(&1), 0);
+		});
+	}
+
+	#[test]
+	fn transferring_more_than_approved_should_not_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 10),
+				Error::<Test>::Unapproved,
+			);
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 50));
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 51),
+				Error::<Test>::Unapproved,
+			);
+			assert_noop!(
+				Assets::cancel_approval(Origin::signed(1), 0, 3),
+				Error::<Test>::Unapproved,
+			);
+		});
+	}
+
+	#[test]
+	fn approving_without_funds_for_the_deposit_should_not_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(3), 100));
+			assert_noop!(
+				Assets::approve_transfer(Origin::signed(3), 0, 2, 50),
+				pallet_balances::Error::<Test, _>::InsufficientBalance,
+			);
+		});
+	}
+
+	#[test]
+	fn set_metadata_reserves_deposit_and_clear_metadata_refunds_it() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(
+				Assets::set_metadata(Origin::signed(1), 0, b"DOT".to_vec(), b"D".to_vec(), 1)
+			);
+			assert_eq!(Balances::reserved_balance(&1), 5);
+			let metadata = Assets::metadata(0).unwrap();
+			assert_eq!(metadata.name, b"DOT".to_vec());
+			assert_eq!(metadata.symbol, b"D".to_vec());
+			assert_eq!(metadata.decimals, 1);
+
+			// Shrinking the metadata refunds part of the deposit.
+			assert_ok!(Assets::set_metadata(Origin::signed(1), 0, b"D".to_vec(), b"D".to_vec(), 1));
+			assert_eq!(Balances::reserved_balance(&1), 3);
+
+			assert_ok!(Assets::clear_metadata(Origin::signed(1), 0));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Assets::metadata(0), None);
+			assert_noop!(Assets::clear_metadata(Origin::signed(1), 0), Error::<Test>::Unknown);
+		});
+	}
+
+	#[test]
+	fn set_metadata_should_check_owner_and_bounds() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, b"DOT".to_vec(), b"D".to_vec(), 10),
+				Error::<Test>::Unknown,
+			);
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(2), 0, b"DOT".to_vec(), b"D".to_vec(), 10),
+				Error::<Test>::NoPermission,
+			);
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, b"POLKA".to_vec(), b"D".to_vec(), 10),
+				Error::<Test>::BadMetadata,
+			);
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, b"DOT".to_vec(), b"DOTDOT".to_vec(), 10),
+				Error::<Test>::BadMetadata,
+			);
+		});
+	}
+
+	#[test]
+	fn force_set_metadata_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_noop!(
+				Assets::force_set_metadata(Origin::signed(1), 0, b"DOT".to_vec(), b"D".to_vec(), 1),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(
+				Assets::force_set_metadata(Origin::root(), 0, b"DOT".to_vec(), b"D".to_vec(), 1)
+			);
+			assert_eq!(Assets::metadata(0).map(|m| m.decimals), Some(1));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_noop!(
+				Assets::force_set_metadata(Origin::root(), 1, b"DOT".to_vec(), b"D".to_vec(), 1),
+				Error::<Test>::Unknown,
+			);
+		});
+	}
+
+	#[test]
+	fn minting_and_burning_require_roles() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_noop!(Assets::mint(Origin::signed(2), 0, 2, 50), Error::<Test>::NoPermission);
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 50));
+			assert_eq!(Assets::balance(0, 2), 50);
+			assert_eq!(Assets::total_supply(0), 150);
+
+			assert_noop!(Assets::burn(Origin::signed(2), 0, 2, 20), Error::<Test>::NoPermission);
+			assert_ok!(Assets::burn(Origin::signed(1), 0, 2, 20));
+			assert_eq!(Assets::balance(0, 2), 30);
+			// Burning more than the holding burns the whole holding.
+			assert_ok!(Assets::burn(Origin::signed(1), 0, 2, 100));
+			assert_eq!(Assets::balance(0, 2), 0);
+			assert_eq!(Assets::total_supply(0), 100);
+		});
+	}
+
+	#[test]
+	fn freezing_accounts_and_assets_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+			assert_noop!(Assets::freeze(Origin::signed(2), 0, 1), Error::<Test>::NoPermission);
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::Frozen);
+			assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 10));
+			assert_ok!(Assets::thaw(Origin::signed(1), 0, 1));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+
+			assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+			assert_noop!(Assets::transfer(Origin::signed(2), 0, 1, 10), Error::<Test>::Frozen);
+			assert_ok!(Assets::approve_transfer(Origin::signed(2), 0, 3, 10));
+			assert_noop!(
+				Assets::transfer_approved(Origin::signed(3), 0, 2, 3, 10),
+				Error::<Test>::Frozen,
+			);
+			assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+			assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 10));
+		});
+	}
+
+	#[test]
+	fn force_transfer_ignores_freezing() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+			assert_noop!(
+				Assets::force_transfer(Origin::signed(2), 0, 2, 3, 10),
+				Error::<Test>::NoPermission,
+			);
+			assert_ok!(Assets::force_transfer(Origin::signed(1), 0, 2, 3, 10));
+			assert_eq!(Assets::balance(0, 2), 40);
+			assert_eq!(Assets::balance(0, 3), 10);
+		});
+	}
+
+	#[test]
+	fn transferring_ownership_and_setting_team_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(
+				Assets::set_metadata(Origin::signed(1), 0, b"DOT".to_vec(), b"D".to_vec(), 1)
+			);
+			assert_eq!(Balances::reserved_balance(&1), 5);
+
+			assert_noop!(
+				Assets::transfer_ownership(Origin::signed(2), 0, 2),
+				Error::<Test>::NoPermission,
+			);
+			assert_ok!(Assets::transfer_ownership(Origin::signed(1), 0, 2));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Balances::reserved_balance(&2), 5);
+			assert_noop!(
+				Assets::set_team(Origin::signed(1), 0, 1, 1, 1),
+				Error::<Test>::NoPermission,
+			);
+
+			// The old owner keeps the other roles until the new owner appoints a new team.
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 10));
+			assert_ok!(Assets::set_team(Origin::signed(2), 0, 3, 3, 3));
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 1, 10), Error::<Test>::NoPermission);
+			assert_ok!(Assets::mint(Origin::signed(3), 0, 1, 10));
+			assert_ok!(Assets::freeze(Origin::signed(3), 0, 1));
+		});
+	}
+
+	#[test]
+	fn destroying_an_asset_in_several_steps_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 3, 10));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 10));
+			assert_ok!(Assets::approve_transfer(Origin::signed(2), 0, 3, 10));
+			assert_ok!(
+				Assets::set_metadata(Origin::signed(1), 0, b"DOT".to_vec(), b"D".to_vec(), 1)
+			);
+			assert_eq!(Balances::reserved_balance(&1), 6);
+
+			assert_noop!(
+				Assets::destroy_accounts(Origin::signed(1), 0, 10),
+				Error::<Test>::NotDestroying,
+			);
+			assert_noop!(Assets::start_destroy(Origin::signed(2), 0), Error::<Test>::NoPermission);
+			assert_ok!(Assets::start_destroy(Origin::signed(1), 0));
+
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::Frozen);
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 2, 10), Error::<Test>::Destroying);
+			assert_noop!(
+				Assets::approve_transfer(Origin::signed(1), 0, 3, 10),
+				Error::<Test>::Destroying,
+			);
+
+			assert_ok!(Assets::destroy_accounts(Origin::signed(4), 0, 2));
+			// One holding is left, and the burned units are gone from the total supply.
+			let remaining = Assets::balance(0, 1) + Assets::balance(0, 2) + Assets::balance(0, 3);
+			assert!(remaining == 80 || remaining == 10);
+			assert_eq!(Assets::total_supply(0), remaining);
+			assert_noop!(Assets::finish_destroy(Origin::signed(4), 0), Error::<Test>::InUse);
+			assert_ok!(Assets::destroy_accounts(Origin::signed(4), 0, 2));
+			assert_eq!(Assets::total_supply(0), 0);
+			assert_noop!(Assets::finish_destroy(Origin::signed(4), 0), Error::<Test>::InUse);
+
+			assert_ok!(Assets::destroy_approvals(Origin::signed(4), 0, 10));
+			assert_eq!(Balances::reserved_balance(&1), 5);
+			assert_eq!(Balances::reserved_balance(&2), 0);
+
+			assert_ok!(Assets::finish_destroy(Origin::signed(4), 0));
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(Assets::metadata(0), None);
+			assert_noop!(Assets::finish_destroy(Origin::signed(4), 0), Error::<Test>::Unknown);
+		});
+	}
+
+	#[test]
+	fn force_origin_can_start_destroy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::start_destroy(Origin::root(), 0));
+			assert_ok!(Assets::destroy_accounts(Origin::signed(1), 0, 1));
+			assert_ok!(Assets::finish_destroy(Origin::signed(1), 0));
+			assert_eq!(Assets::balance(0, 1), 0);
+			assert_noop!(Assets::start_destroy(Origin::root(), 0), Error::<Test>::Unknown);
+		});
+	}
+
+	#[test]
+	fn fungibles_traits_work() {
+		use frame_support::traits::fungibles::{Inspect, Mutate, Transfer};
+		new_test_ext().execute_with(|| {
+			assert_eq!(
+				<Assets as Inspect<u64>>::can_deposit(0, &1, 10),
+				DepositConsequence::UnknownAsset,
+			);
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+
+			assert_ok!(<Assets as Mutate<u64>>::mint_into(0, &2, 50));
+			assert_eq!(<Assets as Inspect<u64>>::balance(0, &2), 50);
+			assert_eq!(<Assets as Inspect<u64>>::total_issuance(0), 150);
+
+			assert_ok!(<Assets as Transfer<u64>>::transfer(0, &2, &3, 20, false), 20);
+			assert_eq!(Assets::balance(0, 3), 20);
+
+			assert_eq!(
+				<Assets as Inspect<u64>>::can_withdraw(0, &3, 30),
+				WithdrawConsequence::NoFunds,
+			);
+			assert_noop!(<Assets as Mutate<u64>>::burn_from(0, &3, 30), Error::<Test>::BalanceLow);
+			assert_ok!(<Assets as Mutate<u64>>::burn_from(0, &3, 20), 20);
+			assert_eq!(<Assets as Inspect<u64>>::total_issuance(0), 130);
+
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+			assert_eq!(<Assets as Inspect<u64>>::reducible_balance(0, &2, false), 0);
+			assert_eq!(
+				<Assets as Inspect<u64>>::can_withdraw(0, &2, 1),
+				WithdrawConsequence::Frozen,
+			);
+			assert_noop!(
+				<Assets as Transfer<u64>>::transfer(0, &2, &3, 1, false),
+				Error::<Test>::Frozen,
+			);
+		});
+	}
+
+	#[test]
+	fn fungibles_hold_traits_work() {
+		use frame_support::traits::fungibles::{Inspect, InspectHold, MutateHold, Transfer};
+		new_test_ext().execute_with(|| {
+			assert!(!<Assets as InspectHold<u64>>::can_hold(0, &1, 10));
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert!(<Assets as InspectHold<u64>>::can_hold(0, &1, 100));
+			assert!(!<Assets as InspectHold<u64>>::can_hold(0, &1, 101));
+
+			assert_ok!(<Assets as MutateHold<u64>>::hold(0, &1, 30));
+			assert_eq!(<Assets as InspectHold<u64>>::balance_on_hold(0, &1), 30);
+			assert_eq!(<Assets as Inspect<u64>>::balance(0, &1), 100);
+			assert_eq!(<Assets as Inspect<u64>>::reducible_balance(0, &1, false), 70);
+			assert_eq!(<Assets as Inspect<u64>>::total_issuance(0), 100);
+
+			// Held units can't be transferred.
+			assert_noop!(
+				<Assets as Transfer<u64>>::transfer(0, &1, &2, 71, false),
+				Error::<Test>::BalanceLow,
+			);
+			assert_noop!(<Assets as MutateHold<u64>>::hold(0, &1, 71), Error::<Test>::BalanceLow);
+
+			assert_noop!(
+				<Assets as MutateHold<u64>>::release(0, &1, 31, false),
+				Error::<Test>::BalanceLow,
+			);
+			assert_ok!(<Assets as MutateHold<u64>>::release(0, &1, 10, false), 10);
+			assert_eq!(Assets::balance(0, 1), 80);
+
+			assert_ok!(<Assets as MutateHold<u64>>::transfer_held(0, &1, &2, 5, false, true), 5);
+			assert_eq!(Assets::held_balance(0, 2), 5);
+			assert_eq!(Assets::balance(0, 2), 0);
+			assert_ok!(<Assets as MutateHold<u64>>::transfer_held(0, &1, &3, 50, true, false), 15);
+			assert_eq!(Assets::held_balance(0, 1), 0);
+			assert_eq!(Assets::balance(0, 3), 15);
+
+			// Frozen accounts can't place units on hold, but held units can still be released.
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+			assert!(!<Assets as InspectHold<u64>>::can_hold(0, &2, 0));
+			assert_ok!(<Assets as MutateHold<u64>>::release(0, &2, 5, false), 5);
+			assert_eq!(Assets::balance(0, 2), 5);
+		});
+	}
+
+	#[test]
+	fn held_units_are_removed_when_destroying_an_asset() {
+		use frame_support::traits::fungibles::MutateHold;
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+			assert_ok!(<Assets as MutateHold<u64>>::hold(0, &2, 10));
+			assert_ok!(Assets::start_destroy(Origin::signed(1), 0));
+
+			assert_ok!(Assets::destroy_accounts(Origin::signed(1), 0, 2));
+			assert_eq!(Assets::total_supply(0), 10);
+			assert_noop!(Assets::finish_destroy(Origin::signed(1), 0), Error::<Test>::InUse);
+
+			assert_ok!(Assets::destroy_accounts(Origin::signed(1), 0, 2));
+			assert_eq!(Assets::total_supply(0), 0);
+			assert_eq!(Assets::held_balance(0, 2), 0);
+			assert_ok!(Assets::finish_destroy(Origin::signed(1), 0));
+		});
+	}
+
+	#[test]
+	fn item_of_exposes_a_single_asset() {
+		use frame_support::traits::fungible::{self, Inspect, Transfer};
+		parameter_types! {
+			pub const AssetZero: u32 = 0;
+		}
+		type AssetZeroItem = fungible::ItemOf<Assets, AssetZero, u64>;
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(<AssetZeroItem as Transfer<u64>>::transfer(&1, &2, 40, true), 40);
+			assert_eq!(<AssetZeroItem as Inspect<u64>>::balance(&2), 40);
+			assert_eq!(<AssetZeroItem as Inspect<u64>>::total_issuance(), 100);
+		});
+	}
 }
@@ -27,7 +27,8 @@
 //!
 //! A module for atomically sending funds from an origin to a target. A proof
 //! is used to allow the target to approve (claim) the swap. If the swap is not
-//! claimed within a specified duration of time, the sender may cancel it.
+//! claimed within a specified duration of time, the sender may cancel it. The duration is bounded
+//! by the `MinimumDuration` and `MaximumDuration` of the runtime.
 //!
 //! ## Interface
 //!
@@ -158,6 +159,12 @@ pub trait Trait: frame_system::Trait {
 	/// to accept the atomic swap request if A generates the proof, and asks that B generates the
 	/// proof instead.
 	type ProofLimit: Get<u32>;
+	/// The minimum duration of a swap.
+	///
+	/// The counterparty needs time to see the swap and create its own before it can be cancelled.
+	type MinimumDuration: Get<Self::BlockNumber>;
+	/// The maximum duration of a swap, bounding how long the funds of the sender can be locked.
+	type MaximumDuration: Get<Self::BlockNumber>;
 }
 
 decl_storage! {
@@ -186,6 +193,10 @@ decl_error! {
 		ClaimActionMismatch,
 		/// Duration has not yet passed for the swap to be cancelled.
 		DurationNotPassed,
+		/// Duration is shorter than the minimum duration.
+		DurationTooShort,
+		/// Duration is longer than the maximum duration.
+		DurationTooLong,
 	}
 }
 
@@ -209,6 +220,15 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
 
+		/// Limit of proof size.
+		const ProofLimit: u32 = T::ProofLimit::get();
+
+		/// The minimum duration of a swap.
+		const MinimumDuration: T::BlockNumber = T::MinimumDuration::get();
+
+		/// The maximum duration of a swap.
+		const MaximumDuration: T::BlockNumber = T::MaximumDuration::get();
+
 		fn deposit_event() = default;
 
 		/// Register a new atomic swap, declaring an intention to send funds from origin to target
@@ -222,7 +242,8 @@ decl_module! {
 		/// - `balance`: Funds to be sent from origin.
 		/// - `duration`: Locked duration of the atomic swap. For safety reasons, it is recommended
 		///   that the revealer uses a shorter duration than the counterparty, to prevent the
-		///   situation where the revealer reveals the proof too late around the end block.
+		///   situation where the revealer reveals the proof too late around the end block. It must
+		///   be within `MinimumDuration` and `MaximumDuration`.
 		#[weight = T::DbWeight::get().reads_writes(1, 1).saturating_add(40_000_000)]
 		fn create_swap(
 			origin,
@@ -232,6 +253,8 @@ decl_module! {
 			duration: T::BlockNumber,
 		) {
 			let source = ensure_signed(origin)?;
+			ensure!(duration >= T::MinimumDuration::get(), Error::<T>::DurationTooShort);
+			ensure!(duration <= T::MaximumDuration::get(), Error::<T>::DurationTooLong);
 			ensure!(
 				!PendingSwaps::<T>::contains_key(&target, hashed_proof),
 				Error::<T>::AlreadyExist
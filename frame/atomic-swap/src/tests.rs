@@ -3,7 +3,7 @@
 use super::*;
 
 use frame_support::{
-	impl_outer_origin, parameter_types, weights::Weight,
+	assert_noop, impl_outer_origin, parameter_types, weights::Weight,
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -61,15 +61,21 @@ impl pallet_balances::Trait for Test {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
 }
 parameter_types! {
 	pub const ProofLimit: u32 = 1024;
 	pub const ExpireDuration: u64 = 100;
+	pub const MinimumDuration: u64 = 10;
+	pub const MaximumDuration: u64 = 10_000;
 }
 impl Trait for Test {
 	type Event = ();
 	type SwapAction = BalanceSwapAction<Test, Balances>;
 	type ProofLimit = ProofLimit;
+	type MinimumDuration = MinimumDuration;
+	type MaximumDuration = MaximumDuration;
 }
 type System = frame_system::Module<Test>;
 type Balances = pallet_balances::Module<Test>;
@@ -152,3 +158,40 @@ fn two_party_successful_swap() {
 		assert_eq!(Balances::free_balance(B), 200 + 50);
 	});
 }
+
+#[test]
+fn swap_duration_is_bounded() {
+	new_test_ext().execute_with(|| {
+		let hashed_proof = blake2_256(&[4, 2]);
+
+		assert_noop!(
+			AtomicSwap::create_swap(
+				Origin::signed(A),
+				B,
+				hashed_proof.clone(),
+				BalanceSwapAction::new(50),
+				MinimumDuration::get() - 1,
+			),
+			Error::<Test>::DurationTooShort,
+		);
+		assert_noop!(
+			AtomicSwap::create_swap(
+				Origin::signed(A),
+				B,
+				hashed_proof.clone(),
+				BalanceSwapAction::new(50),
+				MaximumDuration::get() + 1,
+			),
+			Error::<Test>::DurationTooLong,
+		);
+
+		AtomicSwap::create_swap(
+			Origin::signed(A),
+			B,
+			hashed_proof.clone(),
+			BalanceSwapAction::new(50),
+			MaximumDuration::get(),
+		).unwrap();
+		assert_eq!(Balances::free_balance(A), 100 - 50);
+	});
+}
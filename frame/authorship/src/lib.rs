@@ -24,7 +24,7 @@
 use sp_std::{result, prelude::*};
 use sp_std::collections::btree_set::BTreeSet;
 use frame_support::{decl_module, decl_storage, decl_error, dispatch, ensure};
-use frame_support::traits::{FindAuthor, VerifySeal, Get};
+use frame_support::traits::{FindAuthor, VerifySeal, Get, Currency, OnUnbalanced};
 use codec::{Encode, Decode};
 use frame_system::ensure_none;
 use sp_runtime::traits::{Header as HeaderT, One, Zero};
@@ -223,6 +223,16 @@ decl_module! {
 	}
 }
 
+/// Credits imbalances to the author of the current block, e.g. to hand them a part of the
+/// transaction fees.
+pub struct ToAuthor<T, C>(sp_std::marker::PhantomData<(T, C)>);
+
+impl<T: Trait, C: Currency<T::AccountId>> OnUnbalanced<C::NegativeImbalance> for ToAuthor<T, C> {
+	fn on_nonzero_unbalanced(amount: C::NegativeImbalance) {
+		C::resolve_creating(&Module::<T>::author(), amount);
+	}
+}
+
 impl<T: Trait> Module<T> {
 	/// Fetch the author of the block.
 	///
@@ -35,6 +35,8 @@
 //! - Account creation and removal.
 //! - Managing total issuance.
 //! - Setting and managing locks.
+//! - Migrating accounts after the existential deposit was raised, see the
+//!   [`migration`](./migration/index.html) module.
 //!
 //! ### Terminology
 //!
@@ -65,6 +67,10 @@
 //! - **Lock:** A freeze on a specified amount of an account's free balance until a specified block number. Multiple
 //! locks always operate over the same funds, so they "overlay" rather than "stack".
 //!
+//! - **Named Reserve:** A part of an account's reserved balance that is held under an identifier,
+//! so that it can only be unreserved, slashed or repatriated by referring to that identifier. The
+//! number of named reserves per account is bounded by `MaxReserves`.
+//!
 //! ### Implementations
 //!
 //! The Balances module provides implementations for the following traits. If these traits provide the functionality
@@ -74,6 +80,9 @@
 //! fungible assets system.
 //! - [`ReservableCurrency`](../frame_support/traits/trait.ReservableCurrency.html):
 //! Functions for dealing with assets that can be reserved from an account.
+//! - [`NamedReservableCurrency`](../frame_support/traits/trait.NamedReservableCurrency.html):
+//! Functions for dealing with reserves held under an identifier, so that several modules can
+//! reserve funds of the same account independently.
 //! - [`LockableCurrency`](../frame_support/traits/trait.LockableCurrency.html): Functions for
 //! dealing with accounts that allow liquidity restrictions.
 //! - [`Imbalance`](../frame_support/traits/trait.Imbalance.html): Functions for handling
@@ -87,6 +96,8 @@
 //! ### Dispatchable Functions
 //!
 //! - `transfer` - Transfer some liquid free balance to another account.
+//! - `transfer_keep_alive` - Same as `transfer`, but refuses to kill the sender account.
+//! - `transfer_all` - Transfer the entire transferable balance to another account.
 //! - `set_balance` - Set the balances of a given account. The origin of this call must be root.
 //!
 //! ## Usage
@@ -154,18 +165,21 @@ mod tests;
 mod tests_local;
 mod tests_composite;
 mod benchmarking;
+pub mod migration;
 
 use sp_std::prelude::*;
 use sp_std::{cmp, result, mem, fmt::Debug, ops::BitOr, convert::Infallible};
 use codec::{Codec, Encode, Decode};
 use frame_support::{
 	StorageValue, Parameter, decl_event, decl_storage, decl_module, decl_error, ensure,
-	weights::Weight,
+	weights::{Weight, constants::RocksDbWeight},
 	traits::{
 		Currency, OnKilledAccount, OnUnbalanced, TryDrop, StoredMap,
 		WithdrawReason, WithdrawReasons, LockIdentifier, LockableCurrency, ExistenceRequirement,
-		Imbalance, SignedImbalance, ReservableCurrency, Get, ExistenceRequirement::KeepAlive,
+		Imbalance, SignedImbalance, ReservableCurrency, NamedReservableCurrency, Get,
+		ExistenceRequirement::KeepAlive,
 		ExistenceRequirement::AllowDeath, IsDeadAccount, BalanceStatus as Status,
+		fungible::{self, DepositConsequence, WithdrawConsequence},
 	}
 };
 use sp_runtime::{
@@ -185,6 +199,7 @@ pub trait WeightInfo {
 	fn transfer_keep_alive(u: u32, e: u32, ) -> Weight;
 	fn set_balance(u: u32, e: u32, ) -> Weight;
 	fn set_balance_killing(u: u32, e: u32, ) -> Weight;
+	fn transfer_all() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -193,6 +208,9 @@ impl WeightInfo for () {
 	fn transfer_keep_alive(_u: u32, _e: u32, ) -> Weight { 1_000_000_000 }
 	fn set_balance(_u: u32, _e: u32, ) -> Weight { 1_000_000_000 }
 	fn set_balance_killing(_u: u32, _e: u32, ) -> Weight { 1_000_000_000 }
+	fn transfer_all() -> Weight {
+		(70_000_000 as Weight).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+	}
 }
 
 pub trait Subtrait<I: Instance = DefaultInstance>: frame_system::Trait {
@@ -208,6 +226,12 @@ pub trait Subtrait<I: Instance = DefaultInstance>: frame_system::Trait {
 
 	/// Weight information for the extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
+
+	/// The maximum number of named reserves that can exist on an account.
+	type MaxReserves: Get<u32>;
+
+	/// The identifier of a named reserve.
+	type ReserveIdentifier: Parameter + Member + Ord + Copy;
 }
 
 pub trait Trait<I: Instance = DefaultInstance>: frame_system::Trait {
@@ -229,6 +253,12 @@ pub trait Trait<I: Instance = DefaultInstance>: frame_system::Trait {
 
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
+
+	/// The maximum number of named reserves that can exist on an account.
+	type MaxReserves: Get<u32>;
+
+	/// The identifier of a named reserve.
+	type ReserveIdentifier: Parameter + Member + Ord + Copy;
 }
 
 impl<T: Trait<I>, I: Instance> Subtrait<I> for T {
@@ -236,6 +266,8 @@ impl<T: Trait<I>, I: Instance> Subtrait<I> for T {
 	type ExistentialDeposit = T::ExistentialDeposit;
 	type AccountStore = T::AccountStore;
 	type WeightInfo = <T as Trait<I>>::WeightInfo;
+	type MaxReserves = T::MaxReserves;
+	type ReserveIdentifier = T::ReserveIdentifier;
 }
 
 decl_event!(
@@ -261,6 +293,14 @@ decl_event!(
 		/// Some balance was moved from the reserve of the first account to the second account.
 		/// Final argument indicates the destination balance type.
 		ReserveRepatriated(AccountId, AccountId, Balance, Status),
+		/// An account below the existential deposit was reaped by the existential deposit
+		/// migration, its balance moved to the destination (who, destination, value).
+		Reaped(AccountId, AccountId, Balance),
+		/// An account below the existential deposit was kept by the existential deposit
+		/// migration, the value was minted into it to reach the existential deposit.
+		Upgraded(AccountId, Balance),
+		/// The existential deposit migration has visited every account.
+		ExistentialDepositMigrated,
 	}
 );
 
@@ -282,6 +322,8 @@ decl_error! {
 		ExistingVestingSchedule,
 		/// Beneficiary account must pre-exist
 		DeadAccount,
+		/// Number of named reserves exceed MaxReserves
+		TooManyReserves,
 	}
 }
 
@@ -328,6 +370,16 @@ pub struct BalanceLock<Balance> {
 	pub reasons: Reasons,
 }
 
+/// A single named reserve on an account. There can be many of these on an account, each holding
+/// a distinct part of the reserved balance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReserveData<ReserveIdentifier, Balance> {
+	/// The identifier of this reserve. Only one reserve may be in existence for each identifier.
+	pub id: ReserveIdentifier,
+	/// The amount of the reserved balance held under this identifier.
+	pub amount: Balance,
+}
+
 /// All balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 pub struct AccountData<Balance> {
@@ -403,6 +455,16 @@ decl_storage! {
 		/// NOTE: Should only be accessed when setting, changing and freeing a lock.
 		pub Locks get(fn locks): map hasher(blake2_128_concat) T::AccountId => Vec<BalanceLock<T::Balance>>;
 
+		/// Named reserves on some account balances, sorted by identifier and bounded by
+		/// `MaxReserves`.
+		pub Reserves get(fn reserves):
+			map hasher(blake2_128_concat) T::AccountId
+			=> Vec<ReserveData<T::ReserveIdentifier, T::Balance>>;
+
+		/// The ongoing migration of accounts to the current existential deposit, if any.
+		pub ExistentialDepositMigration get(fn existential_deposit_migration):
+			Option<migration::MigrationState<T::AccountId>>;
+
 		/// Storage version of the pallet.
 		///
 		/// This is set to v2.0.0 for new networks.
@@ -432,8 +494,15 @@ decl_module! {
 		/// The minimum amount required to keep an account open.
 		const ExistentialDeposit: T::Balance = T::ExistentialDeposit::get();
 
+		/// The maximum number of named reserves that can exist on an account.
+		const MaxReserves: u32 = T::MaxReserves::get();
+
 		fn deposit_event() = default;
 
+		fn on_initialize() -> Weight {
+			Self::migrate_existential_deposit_step()
+		}
+
 		/// Transfer some liquid free balance to another account.
 		///
 		/// `transfer` will set the `FreeBalance` of the sender and receiver.
@@ -566,6 +635,39 @@ decl_module! {
 			let dest = T::Lookup::lookup(dest)?;
 			<Self as Currency<_>>::transfer(&transactor, &dest, value, KeepAlive)?;
 		}
+
+		/// Transfer the entire transferable balance from the caller account.
+		///
+		/// NOTE: This function only attempts to transfer _transferable_ balances. This means that
+		/// any locked, reserved, or existential deposits (when `keep_alive` is `true`), will not be
+		/// transferred by this function. To ensure that this function results in a killed account,
+		/// you might need to prepare the account by removing any reference counters, storage
+		/// deposits, etc...
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// - `dest`: The recipient of the transfer.
+		/// - `keep_alive`: A boolean to determine if the `transfer_all` operation should send all
+		///   of the funds the account has, causing the sender account to be killed (false), or
+		///   transfer everything except at least the existential deposit, which will guarantee to
+		///   keep the sender account alive (true).
+		///
+		/// # <weight>
+		/// - Same as transfer, with an additional read of the locks of the caller through the
+		///   account data, which is already in the overlay.
+		/// # </weight>
+		#[weight = T::WeightInfo::transfer_all()]
+		pub fn transfer_all(
+			origin,
+			dest: <T::Lookup as StaticLookup>::Source,
+			keep_alive: bool,
+		) {
+			let transactor = ensure_signed(origin)?;
+			let reducible_balance = Self::reducible_balance(&transactor, keep_alive);
+			let dest = T::Lookup::lookup(dest)?;
+			let keep_alive = if keep_alive { KeepAlive } else { AllowDeath };
+			<Self as Currency<_>>::transfer(&transactor, &dest, reducible_balance, keep_alive)?;
+		}
 	}
 }
 
@@ -589,6 +691,21 @@ impl<T: Trait<I>, I: Instance> Module<T, I> {
 		Self::account(who.borrow()).usable(Reasons::Fee)
 	}
 
+	/// Get the balance of an account that can be transferred away, considering the liquidity
+	/// restrictions and, if `keep_alive` is `true` or the account cannot be killed, the
+	/// existential deposit. Will be at most `free_balance`.
+	pub fn reducible_balance(who: &T::AccountId, keep_alive: bool) -> T::Balance {
+		let account = Self::account(who);
+		if !keep_alive && system::Module::<T>::allow_death(who) {
+			// Liquid balance is what is neither reserved nor locked.
+			account.usable(Reasons::Misc)
+		} else {
+			// The free balance of a kept alive account must not drop below the existential deposit.
+			let min_balance = account.frozen(Reasons::Misc).max(T::ExistentialDeposit::get());
+			account.free.saturating_sub(min_balance)
+		}
+	}
+
 	/// Get the reserved balance of an account.
 	pub fn reserved_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
 		Self::account(who.borrow()).reserved
@@ -696,6 +813,18 @@ impl<T: Trait<I>, I: Instance> Module<T, I> {
 			}
 		}
 	}
+
+	/// Update the named reserves of `who`, removing the entry altogether if there are none left.
+	fn update_reserves(
+		who: &T::AccountId,
+		reserves: &[ReserveData<T::ReserveIdentifier, T::Balance>],
+	) {
+		if reserves.is_empty() {
+			Reserves::<T, I>::remove(who);
+		} else {
+			Reserves::<T, I>::insert(who, reserves);
+		}
+	}
 }
 
 // wrapping these imbalances in a private module is necessary to ensure absolute privacy
@@ -905,6 +1034,8 @@ impl<T: Subtrait<I>, I: Instance> Trait<I> for ElevatedTrait<T, I> {
 	type ExistentialDeposit = T::ExistentialDeposit;
 	type AccountStore = T::AccountStore;
 	type WeightInfo = <T as Subtrait<I>>::WeightInfo;
+	type MaxReserves = T::MaxReserves;
+	type ReserveIdentifier = T::ReserveIdentifier;
 }
 
 impl<T: Trait<I>, I: Instance> Currency<T::AccountId> for Module<T, I> where
@@ -1266,6 +1397,175 @@ impl<T: Trait<I>, I: Instance> ReservableCurrency<T::AccountId> for Module<T, I>
 	}
 }
 
+impl<T: Trait<I>, I: Instance> NamedReservableCurrency<T::AccountId> for Module<T, I>  where
+	T::Balance: MaybeSerializeDeserialize + Debug
+{
+	type ReserveIdentifier = T::ReserveIdentifier;
+
+	fn reserved_balance_named(id: &Self::ReserveIdentifier, who: &T::AccountId) -> Self::Balance {
+		let reserves = Self::reserves(who);
+		reserves.binary_search_by_key(id, |data| data.id)
+			.map(|index| reserves[index].amount)
+			.unwrap_or_else(|_| Zero::zero())
+	}
+
+	/// Move `value` from the free balance from `who` to their reserved balance held under `id`.
+	///
+	/// Is a no-op if value to be reserved is zero.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		who: &T::AccountId,
+		value: Self::Balance
+	) -> DispatchResult {
+		if value.is_zero() { return Ok(()) }
+
+		let mut reserves = Self::reserves(who);
+		match reserves.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => {
+				// this add can't overflow since the reserved balance, which includes the named
+				// reserve, fits into the same data type; but just to be defensive.
+				reserves[index].amount = reserves[index].amount.saturating_add(value);
+			},
+			Err(index) => {
+				ensure!(
+					(reserves.len() as u32) < T::MaxReserves::get(),
+					Error::<T, I>::TooManyReserves,
+				);
+				reserves.insert(index, ReserveData { id: *id, amount: value });
+			},
+		}
+		<Self as ReservableCurrency<_>>::reserve(who, value)?;
+		Self::update_reserves(who, &reserves[..]);
+		Ok(())
+	}
+
+	/// Unreserve some funds held under `id`, returning any amount that was unable to be
+	/// unreserved.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		who: &T::AccountId,
+		value: Self::Balance
+	) -> Self::Balance {
+		if value.is_zero() { return Zero::zero() }
+
+		let mut reserves = Self::reserves(who);
+		let index = match reserves.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => index,
+			Err(_) => return value,
+		};
+		let to_change = cmp::min(reserves[index].amount, value);
+		let remain = <Self as ReservableCurrency<_>>::unreserve(who, to_change);
+		// remain should always be zero but just to be defensive here.
+		let actual = to_change.saturating_sub(remain);
+		reserves[index].amount -= actual;
+		if reserves[index].amount.is_zero() {
+			reserves.remove(index);
+		}
+		Self::update_reserves(who, &reserves[..]);
+		value - actual
+	}
+
+	/// Slash from the reserved balance held under `id`, returning the negative imbalance created,
+	/// and any amount that was unable to be slashed.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		who: &T::AccountId,
+		value: Self::Balance
+	) -> (Self::NegativeImbalance, Self::Balance) {
+		if value.is_zero() { return (NegativeImbalance::zero(), Zero::zero()) }
+
+		let mut reserves = Self::reserves(who);
+		let index = match reserves.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => index,
+			Err(_) => return (NegativeImbalance::zero(), value),
+		};
+		let to_change = cmp::min(reserves[index].amount, value);
+		let (imbalance, remain) = <Self as ReservableCurrency<_>>::slash_reserved(who, to_change);
+		// remain should always be zero but just to be defensive here.
+		let actual = to_change.saturating_sub(remain);
+		reserves[index].amount -= actual;
+		if reserves[index].amount.is_zero() {
+			reserves.remove(index);
+		}
+		Self::update_reserves(who, &reserves[..]);
+		(imbalance, value - actual)
+	}
+
+	/// Move the reserved balance held under `id` of one account into the balance of another,
+	/// according to `status`. If `status` is `Reserved`, the funds end up in the reserve held
+	/// under the same `id` of `beneficiary`.
+	///
+	/// Is a no-op if:
+	/// - the value to be moved is zero; or
+	/// - the `slashed` id equal to `beneficiary` and the `status` is `Reserved`.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: Status,
+	) -> Result<Self::Balance, DispatchError> {
+		if value.is_zero() { return Ok(Zero::zero()) }
+
+		if slashed == beneficiary {
+			return match status {
+				Status::Free => Ok(Self::unreserve_named(id, slashed, value)),
+				Status::Reserved =>
+					Ok(value.saturating_sub(Self::reserved_balance_named(id, slashed))),
+			};
+		}
+
+		let mut from_reserves = Self::reserves(slashed);
+		let from_index = match from_reserves.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => index,
+			Err(_) => return Ok(value),
+		};
+		let to_change = cmp::min(from_reserves[from_index].amount, value);
+
+		let mut to_reserves = Self::reserves(beneficiary);
+		let to_index = match (status, to_reserves.binary_search_by_key(id, |data| data.id)) {
+			(Status::Reserved, Ok(index)) => Some(index),
+			(Status::Reserved, Err(index)) => {
+				ensure!(
+					(to_reserves.len() as u32) < T::MaxReserves::get(),
+					Error::<T, I>::TooManyReserves,
+				);
+				to_reserves.insert(index, ReserveData { id: *id, amount: Zero::zero() });
+				Some(index)
+			},
+			(Status::Free, _) => None,
+		};
+
+		let remain = <Self as ReservableCurrency<_>>::repatriate_reserved(
+			slashed,
+			beneficiary,
+			to_change,
+			status,
+		)?;
+		// remain should always be zero but just to be defensive here.
+		let actual = to_change.saturating_sub(remain);
+
+		from_reserves[from_index].amount -= actual;
+		if from_reserves[from_index].amount.is_zero() {
+			from_reserves.remove(from_index);
+		}
+		Self::update_reserves(slashed, &from_reserves[..]);
+		if let Some(index) = to_index {
+			to_reserves[index].amount = to_reserves[index].amount.saturating_add(actual);
+			if to_reserves[index].amount.is_zero() {
+				to_reserves.remove(index);
+			}
+			Self::update_reserves(beneficiary, &to_reserves[..]);
+		}
+
+		Ok(value - actual)
+	}
+}
+
 /// Implement `OnKilledAccount` to remove the local account, if using local account storage.
 ///
 /// NOTE: You probably won't need to use this! This only needs to be "wired in" to System module
@@ -1355,3 +1655,187 @@ impl<T: Trait<I>, I: Instance> IsDeadAccount<T::AccountId> for Module<T, I> wher
 		!T::AccountStore::is_explicit(who)
 	}
 }
+
+impl<T: Trait<I>, I: Instance> fungible::Inspect<T::AccountId> for Module<T, I> {
+	type Balance = T::Balance;
+
+	fn total_issuance() -> Self::Balance {
+		TotalIssuance::<T, I>::get()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		T::ExistentialDeposit::get()
+	}
+
+	fn balance(who: &T::AccountId) -> Self::Balance {
+		Self::account(who).total()
+	}
+
+	fn reducible_balance(who: &T::AccountId, keep_alive: bool) -> Self::Balance {
+		Self::reducible_balance(who, keep_alive)
+	}
+
+	fn can_deposit(who: &T::AccountId, amount: Self::Balance) -> DepositConsequence {
+		if amount.is_zero() { return DepositConsequence::Success }
+
+		if TotalIssuance::<T, I>::get().checked_add(&amount).is_none() {
+			return DepositConsequence::Overflow
+		}
+
+		let new_total_balance = match Self::account(who).total().checked_add(&amount) {
+			Some(x) => x,
+			None => return DepositConsequence::Overflow,
+		};
+
+		if new_total_balance < T::ExistentialDeposit::get() {
+			return DepositConsequence::BelowMinimum
+		}
+
+		DepositConsequence::Success
+	}
+
+	fn can_withdraw(
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		if amount.is_zero() { return WithdrawConsequence::Success }
+
+		if TotalIssuance::<T, I>::get().checked_sub(&amount).is_none() {
+			return WithdrawConsequence::Underflow
+		}
+
+		let account = Self::account(who);
+		let new_total_balance = match account.total().checked_sub(&amount) {
+			Some(x) => x,
+			None => return WithdrawConsequence::NoFunds,
+		};
+
+		// An account with outstanding references may not be reduced below the existential deposit.
+		let success = if new_total_balance < T::ExistentialDeposit::get() {
+			if system::Module::<T>::allow_death(who) {
+				WithdrawConsequence::ReducedToZero(new_total_balance)
+			} else {
+				return WithdrawConsequence::WouldDie
+			}
+		} else {
+			WithdrawConsequence::Success
+		};
+
+		// Enough free funds to have them be reduced.
+		let new_free_balance = match account.free.checked_sub(&amount) {
+			Some(b) => b,
+			None => return WithdrawConsequence::NoFunds,
+		};
+
+		// Eventual free funds must be no less than the frozen balance.
+		if new_free_balance < account.frozen(Reasons::All) {
+			return WithdrawConsequence::Frozen
+		}
+
+		success
+	}
+}
+
+impl<T: Trait<I>, I: Instance> fungible::Mutate<T::AccountId> for Module<T, I> {
+	fn mint_into(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() { return Ok(()) }
+
+		match <Self as fungible::Inspect<_>>::can_deposit(who, amount) {
+			DepositConsequence::Success => (),
+			DepositConsequence::Overflow => return Err(Error::<T, I>::Overflow.into()),
+			_ => return Err(Error::<T, I>::ExistentialDeposit.into()),
+		}
+
+		Self::mutate_account(who, |account| {
+			// defensive only: checked in `can_deposit`.
+			account.free = account.free.saturating_add(amount);
+		});
+		TotalIssuance::<T, I>::mutate(|t| *t = t.saturating_add(amount));
+		Self::deposit_event(RawEvent::Deposit(who.clone(), amount));
+		Ok(())
+	}
+
+	fn burn_from(
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		if amount.is_zero() { return Ok(Zero::zero()) }
+
+		let dust = match <Self as fungible::Inspect<_>>::can_withdraw(who, amount) {
+			WithdrawConsequence::Success => Zero::zero(),
+			WithdrawConsequence::ReducedToZero(dust) => dust,
+			WithdrawConsequence::WouldDie => return Err(Error::<T, I>::KeepAlive.into()),
+			WithdrawConsequence::Frozen => return Err(Error::<T, I>::LiquidityRestrictions.into()),
+			_ => return Err(Error::<T, I>::InsufficientBalance.into()),
+		};
+
+		// Any dust left behind is handed to `DustRemoval` when the account is reaped, which
+		// also takes care of the total issuance.
+		Self::mutate_account(who, |account| account.free -= amount);
+		TotalIssuance::<T, I>::mutate(|t| *t -= amount);
+		Ok(amount.saturating_add(dust))
+	}
+}
+
+impl<T: Trait<I>, I: Instance> fungible::Transfer<T::AccountId> for Module<T, I> {
+	fn transfer(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		let existence_requirement = if keep_alive { KeepAlive } else { AllowDeath };
+		<Self as Currency<_>>::transfer(source, dest, amount, existence_requirement)?;
+		Ok(amount)
+	}
+}
+
+impl<T: Trait<I>, I: Instance> fungible::InspectHold<T::AccountId> for Module<T, I> {
+	fn balance_on_hold(who: &T::AccountId) -> Self::Balance {
+		Self::account(who).reserved
+	}
+
+	fn can_hold(who: &T::AccountId, amount: Self::Balance) -> bool {
+		<Self as ReservableCurrency<_>>::can_reserve(who, amount)
+	}
+}
+
+impl<T: Trait<I>, I: Instance> fungible::MutateHold<T::AccountId> for Module<T, I> {
+	fn hold(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		<Self as ReservableCurrency<_>>::reserve(who, amount)
+	}
+
+	fn release(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure!(
+			best_effort || Self::account(who).reserved >= amount,
+			Error::<T, I>::InsufficientBalance,
+		);
+		let remain = <Self as ReservableCurrency<_>>::unreserve(who, amount);
+		Ok(amount - remain)
+	}
+
+	fn transfer_held(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		best_effort: bool,
+		on_hold: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure!(
+			best_effort || Self::account(source).reserved >= amount,
+			Error::<T, I>::InsufficientBalance,
+		);
+		let status = if on_hold { Status::Reserved } else { Status::Free };
+		let remain = <Self as ReservableCurrency<_>>::repatriate_reserved(
+			source,
+			dest,
+			amount,
+			status,
+		)?;
+		Ok(amount - remain)
+	}
+}
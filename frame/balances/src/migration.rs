@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for safely changing the existential deposit.
+//!
+//! Raising `ExistentialDeposit` leaves every account whose total balance lies between the old
+//! and the new value in a state the module never produces on its own. A runtime raising it should
+//! call [`Module::start_existential_deposit_migration`](../struct.Module.html) from its
+//! `on_runtime_upgrade`. The module then visits at most `per_block` accounts in each
+//! `on_initialize`, remembering where it stopped in a cursor, and deals with each account found
+//! below the existential deposit according to the configured [`BelowMinimumAction`]. An event is
+//! emitted for every affected account, and `ExistentialDepositMigrated` once all accounts have
+//! been visited.
+
+use codec::{Encode, Decode};
+use sp_std::prelude::*;
+use sp_runtime::{RuntimeDebug, traits::Zero};
+use frame_support::{
+	StorageValue, weights::Weight,
+	traits::{Get, Currency, Imbalance, OnUnbalanced, StoredMap},
+};
+use super::{
+	Trait, Instance, Module, RawEvent, Reserves, ExistentialDepositMigration, NegativeImbalance,
+};
+
+/// The length of the hash in front of every key of a `blake2_128_concat` map.
+const BLAKE2_128_LEN: usize = 16;
+
+/// What to do with an account found below the new existential deposit.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum BelowMinimumAction<AccountId> {
+	/// Move the whole balance of the account to the given destination and reap the account.
+	///
+	/// Accounts which may not be reaped because other modules still reference them are upgraded
+	/// instead.
+	ReapTo(AccountId),
+	/// Keep the account, minting the funds needed to bring it up to the existential deposit.
+	Upgrade,
+}
+
+/// The state of an ongoing existential deposit migration.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MigrationState<AccountId> {
+	/// The prefix of the `blake2_128_concat` map which holds the account data.
+	pub prefix: Vec<u8>,
+	/// The last key visited.
+	pub cursor: Vec<u8>,
+	/// What to do with accounts below the existential deposit.
+	pub action: BelowMinimumAction<AccountId>,
+	/// The maximum number of accounts visited in a block.
+	pub per_block: u32,
+}
+
+impl<T: Trait<I>, I: Instance> Module<T, I> {
+	/// Schedule a migration of all accounts to the current existential deposit.
+	///
+	/// `prefix` is the final prefix of the `blake2_128_concat` map backing `T::AccountStore`, e.g.
+	/// `frame_system::Account::<Runtime>::final_prefix()` when the account data lives in the
+	/// system module. Any migration already in progress is restarted.
+	pub fn start_existential_deposit_migration(
+		prefix: Vec<u8>,
+		action: BelowMinimumAction<T::AccountId>,
+		per_block: u32,
+	) {
+		ExistentialDepositMigration::<T, I>::put(MigrationState {
+			cursor: prefix.clone(),
+			prefix,
+			action,
+			per_block,
+		});
+	}
+
+	/// Advance the ongoing existential deposit migration, if any, by one block's worth of
+	/// accounts.
+	pub(crate) fn migrate_existential_deposit_step() -> Weight {
+		let mut state = match ExistentialDepositMigration::<T, I>::get() {
+			Some(state) => state,
+			None => return T::DbWeight::get().reads(1),
+		};
+
+		let (mut visited, mut affected) = (0u64, 0u64);
+		let mut done = false;
+		for _ in 0..state.per_block {
+			let key = match sp_io::storage::next_key(&state.cursor)
+				.filter(|key| key.starts_with(&state.prefix))
+			{
+				Some(key) => key,
+				None => { done = true; break },
+			};
+			visited += 1;
+			let raw_account = key.get(state.prefix.len() + BLAKE2_128_LEN..).unwrap_or_default();
+			if let Ok(who) = T::AccountId::decode(&mut &raw_account[..]) {
+				if Self::migrate_account(&who, &state.action) {
+					affected += 1;
+				}
+			}
+			state.cursor = key;
+		}
+		// Stopping exactly at the last account only ends the migration in the next block.
+		if done {
+			ExistentialDepositMigration::<T, I>::kill();
+			Self::deposit_event(RawEvent::ExistentialDepositMigrated);
+		} else {
+			ExistentialDepositMigration::<T, I>::put(state);
+		}
+
+		T::DbWeight::get().reads_writes(1 + 2 * visited, 1 + 3 * affected)
+	}
+
+	/// Apply `action` to `who` if their total balance is non-zero but below the existential
+	/// deposit. Returns whether the account was affected.
+	fn migrate_account(who: &T::AccountId, action: &BelowMinimumAction<T::AccountId>) -> bool {
+		let ed = T::ExistentialDeposit::get();
+		let total = T::AccountStore::get(who).total();
+		if total.is_zero() || total >= ed {
+			return false
+		}
+
+		match action {
+			BelowMinimumAction::ReapTo(dest) if frame_system::Module::<T>::allow_death(who) => {
+				// Leaving nothing behind reaps the account without any dust.
+				Self::mutate_account(who, |account| {
+					account.free = Zero::zero();
+					account.reserved = Zero::zero();
+				});
+				Reserves::<T, I>::remove(who);
+				let deposited = Self::deposit_creating(dest, total);
+				// Funds which could not be deposited, e.g. to a new account below the existential
+				// deposit, are handled as dust.
+				if let Ok(dust) = NegativeImbalance::<T, I>::new(total).offset(deposited) {
+					T::DustRemoval::on_unbalanced(dust);
+				}
+				Self::deposit_event(RawEvent::Reaped(who.clone(), dest.clone(), total));
+			},
+			_ => {
+				let minted = ed.saturating_sub(total);
+				// Dropping the imbalance increases the total issuance accordingly.
+				let _ = Self::deposit_into_existing(who, minted);
+				Self::deposit_event(RawEvent::Upgraded(who.clone(), minted));
+			},
+		}
+		true
+	}
+}
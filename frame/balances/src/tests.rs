@@ -43,7 +43,8 @@ macro_rules! decl_tests {
 			assert_noop, assert_ok, assert_err,
 			traits::{
 				LockableCurrency, LockIdentifier, WithdrawReason, WithdrawReasons,
-				Currency, ReservableCurrency, ExistenceRequirement::AllowDeath, StoredMap
+				Currency, ReservableCurrency, NamedReservableCurrency,
+				ExistenceRequirement::AllowDeath, StoredMap
 			}
 		};
 		use pallet_transaction_payment::{ChargeTransactionPayment, Multiplier};
@@ -52,6 +53,10 @@ macro_rules! decl_tests {
 		const ID_1: LockIdentifier = *b"1       ";
 		const ID_2: LockIdentifier = *b"2       ";
 
+		const RESERVE_1: [u8; 8] = *b"reserve1";
+		const RESERVE_2: [u8; 8] = *b"reserve2";
+		const RESERVE_3: [u8; 8] = *b"reserve3";
+
 		pub type System = frame_system::Module<$test>;
 		pub type Balances = Module<$test>;
 
@@ -629,6 +634,58 @@ macro_rules! decl_tests {
 			});
 		}
 
+		#[test]
+		fn transfer_all_works() {
+			<$ext_builder>::default().existential_deposit(100).build().execute_with(|| {
+				// setup
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 200, 0));
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 2, 0, 0));
+				// transfer all and allow death
+				assert_ok!(Balances::transfer_all(Some(1).into(), 2, false));
+				assert_eq!(Balances::total_balance(&1), 0);
+				assert_eq!(Balances::total_balance(&2), 200);
+
+				// setup
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 200, 0));
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 2, 0, 0));
+				// transfer all and keep alive
+				assert_ok!(Balances::transfer_all(Some(1).into(), 2, true));
+				assert_eq!(Balances::total_balance(&1), 100);
+				assert_eq!(Balances::total_balance(&2), 100);
+
+				// setup
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 200, 10));
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 2, 0, 0));
+				// transfer all and allow death w/ reserved, the remaining reserve is dust
+				assert_ok!(Balances::transfer_all(Some(1).into(), 2, false));
+				assert_eq!(Balances::total_balance(&1), 0);
+				assert_eq!(Balances::total_balance(&2), 200);
+
+				// setup
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 200, 10));
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 2, 0, 0));
+				// transfer all and keep alive w/ reserved
+				assert_ok!(Balances::transfer_all(Some(1).into(), 2, true));
+				assert_eq!(Balances::total_balance(&1), 110);
+				assert_eq!(Balances::total_balance(&2), 100);
+			});
+		}
+
+		#[test]
+		fn transfer_all_respects_locks() {
+			<$ext_builder>::default().existential_deposit(100).build().execute_with(|| {
+				assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 300, 0));
+				Balances::set_lock(ID_1, &1, 150, WithdrawReasons::all());
+				assert_eq!(Balances::reducible_balance(&1, true), 150);
+
+				// a lock references the account, so it cannot be killed either way
+				assert_eq!(Balances::reducible_balance(&1, false), 150);
+				assert_ok!(Balances::transfer_all(Some(1).into(), 2, false));
+				assert_eq!(Balances::free_balance(1), 150);
+				assert_eq!(Balances::free_balance(2), 150);
+			});
+		}
+
 		#[test]
 		#[should_panic = "the balance of any account should always be more than existential deposit."]
 		fn cannot_set_genesis_value_below_ed() {
@@ -787,5 +844,159 @@ macro_rules! decl_tests {
 					);
 				});
 		}
+
+		#[test]
+		fn fungible_mint_and_burn_should_work() {
+			<$ext_builder>::default().existential_deposit(10).build().execute_with(|| {
+				assert_eq!(
+					<Balances as fungible::Inspect<_>>::can_deposit(&1, 5),
+					DepositConsequence::BelowMinimum,
+				);
+				assert_noop!(
+					<Balances as fungible::Mutate<_>>::mint_into(&1, 5),
+					Error::<$test, _>::ExistentialDeposit,
+				);
+				assert_ok!(<Balances as fungible::Mutate<_>>::mint_into(&1, 100));
+				assert_eq!(<Balances as fungible::Inspect<_>>::balance(&1), 100);
+				assert_eq!(<Balances as fungible::Inspect<_>>::total_issuance(), 100);
+
+				assert_ok!(<Balances as fungible::Mutate<_>>::burn_from(&1, 30), 30);
+				assert_eq!(Balances::free_balance(1), 70);
+
+				// burning below the existential deposit reaps the account
+				assert_eq!(
+					<Balances as fungible::Inspect<_>>::can_withdraw(&1, 65),
+					WithdrawConsequence::ReducedToZero(5),
+				);
+				assert_ok!(<Balances as fungible::Mutate<_>>::burn_from(&1, 65), 70);
+				assert_eq!(Balances::free_balance(1), 0);
+				assert_eq!(<Balances as fungible::Inspect<_>>::total_issuance(), 0);
+				assert_noop!(
+					<Balances as fungible::Mutate<_>>::burn_from(&1, 1),
+					Error::<$test, _>::InsufficientBalance,
+				);
+			});
+		}
+
+		#[test]
+		fn fungible_hold_should_work() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 100);
+				let _ = Balances::deposit_creating(&2, 1);
+
+				assert!(<Balances as fungible::InspectHold<_>>::can_hold(&1, 60));
+				assert_ok!(<Balances as fungible::MutateHold<_>>::hold(&1, 60));
+				assert_eq!(<Balances as fungible::InspectHold<_>>::balance_on_hold(&1), 60);
+
+				assert_noop!(
+					<Balances as fungible::MutateHold<_>>::release(&1, 70, false),
+					Error::<$test, _>::InsufficientBalance,
+				);
+				assert_ok!(<Balances as fungible::MutateHold<_>>::release(&1, 10, false), 10);
+				assert_eq!(Balances::free_balance(1), 50);
+
+				assert_ok!(
+					<Balances as fungible::MutateHold<_>>::transfer_held(&1, &2, 20, false, true),
+					20,
+				);
+				assert_eq!(Balances::reserved_balance(2), 20);
+				assert_ok!(
+					<Balances as fungible::MutateHold<_>>::transfer_held(&1, &2, 50, true, false),
+					30,
+				);
+				assert_eq!(Balances::reserved_balance(1), 0);
+				assert_eq!(Balances::free_balance(2), 31);
+			});
+		}
+
+		#[test]
+		fn named_reserve_should_work() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+
+				assert_ok!(Balances::reserve_named(&RESERVE_1, &1, 10));
+				assert_ok!(Balances::reserve_named(&RESERVE_2, &1, 20));
+				assert_ok!(Balances::reserve_named(&RESERVE_1, &1, 5));
+				assert_ok!(Balances::reserve(&1, 30));
+
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_1, &1), 15);
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_2, &1), 20);
+				assert_eq!(Balances::reserved_balance(1), 65);
+				assert_eq!(Balances::free_balance(1), 46);
+
+				// only the named reserve is unreserved, the rest is returned
+				assert_eq!(Balances::unreserve_named(&RESERVE_1, &1, 25), 10);
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_1, &1), 0);
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_2, &1), 20);
+				assert_eq!(Balances::reserved_balance(1), 50);
+				assert_eq!(Balances::free_balance(1), 61);
+				assert_eq!(Balances::reserves(1).len(), 1);
+
+				// unknown identifiers are left untouched
+				assert_eq!(Balances::unreserve_named(&RESERVE_3, &1, 5), 5);
+				assert_eq!(Balances::reserved_balance(1), 50);
+
+				let (imbalance, remain) = Balances::slash_reserved_named(&RESERVE_2, &1, 25);
+				assert_eq!((imbalance.peek(), remain), (20, 5));
+				assert_eq!(Balances::reserved_balance(1), 30);
+				assert!(Balances::reserves(1).is_empty());
+				assert!(!<Reserves<$test>>::contains_key(1));
+			});
+		}
+
+		#[test]
+		fn named_reserve_should_respect_max_reserves() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 111);
+
+				assert_ok!(Balances::reserve_named(&RESERVE_1, &1, 10));
+				assert_ok!(Balances::reserve_named(&RESERVE_2, &1, 10));
+				assert_noop!(
+					Balances::reserve_named(&RESERVE_3, &1, 10),
+					Error::<$test, _>::TooManyReserves,
+				);
+				// topping up an existing reserve is still fine
+				assert_ok!(Balances::reserve_named(&RESERVE_1, &1, 10));
+				assert_eq!(Balances::reserved_balance(1), 30);
+
+				// failing to reserve leaves the named reserves untouched
+				assert_noop!(
+					Balances::reserve_named(&RESERVE_1, &1, 100),
+					Error::<$test, _>::InsufficientBalance,
+				);
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_1, &1), 20);
+			});
+		}
+
+		#[test]
+		fn repatriating_named_reserve_should_work() {
+			<$ext_builder>::default().build().execute_with(|| {
+				let _ = Balances::deposit_creating(&1, 110);
+				let _ = Balances::deposit_creating(&2, 1);
+				assert_ok!(Balances::reserve_named(&RESERVE_1, &1, 50));
+				assert_ok!(Balances::reserve(&1, 10));
+
+				assert_ok!(
+					Balances::repatriate_reserved_named(&RESERVE_1, &1, &2, 20, Status::Free),
+					0,
+				);
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_1, &1), 30);
+				assert_eq!(Balances::free_balance(2), 21);
+
+				assert_ok!(
+					Balances::repatriate_reserved_named(&RESERVE_1, &1, &2, 40, Status::Reserved),
+					10,
+				);
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_1, &1), 0);
+				assert_eq!(Balances::reserved_balance(1), 10);
+				assert_eq!(Balances::reserved_balance_named(&RESERVE_1, &2), 30);
+				assert_eq!(Balances::reserved_balance(2), 30);
+
+				assert_noop!(
+					Balances::repatriate_reserved_named(&RESERVE_1, &2, &3, 10, Status::Free),
+					Error::<$test, _>::DeadAccount,
+				);
+			});
+		}
 	}
 }
@@ -95,6 +95,7 @@ impl frame_system::Trait for Test {
 }
 parameter_types! {
 	pub const TransactionByteFee: u64 = 1;
+	pub const MaxReserves: u32 = 2;
 }
 impl pallet_transaction_payment::Trait for Test {
 	type Currency = Module<Test>;
@@ -110,6 +111,8 @@ impl Trait for Test {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = system::Module<Test>;
 	type WeightInfo = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
 }
 
 pub struct ExtBuilder {
@@ -95,6 +95,7 @@ impl frame_system::Trait for Test {
 }
 parameter_types! {
 	pub const TransactionByteFee: u64 = 1;
+	pub const MaxReserves: u32 = 2;
 }
 impl pallet_transaction_payment::Trait for Test {
 	type Currency = Module<Test>;
@@ -115,6 +116,8 @@ impl Trait for Test {
 		u64, super::AccountData<u64>
 	>;
 	type WeightInfo = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
 }
 
 pub struct ExtBuilder {
@@ -202,3 +205,43 @@ fn emit_events_with_no_existential_deposit_suicide_with_dust() {
 			);
 		});
 }
+
+#[test]
+fn existential_deposit_migration_works() {
+	<ExtBuilder>::default()
+		.existential_deposit(1)
+		.build()
+		.execute_with(|| {
+			use frame_support::{storage::StoragePrefixedMap, traits::OnInitialize};
+			use crate::migration::BelowMinimumAction;
+
+			assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 5, 0));
+			assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 2, 50, 0));
+			assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 3, 7, 0));
+			// The lock keeps account 3 from being reaped.
+			Balances::set_lock(ID_1, &3, 7, WithdrawReasons::all());
+			let _ = events();
+
+			EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = 10);
+			Balances::start_existential_deposit_migration(
+				Account::<Test>::final_prefix().to_vec(),
+				BelowMinimumAction::ReapTo(2),
+				2,
+			);
+			Balances::on_initialize(2);
+			assert!(Balances::existential_deposit_migration().is_some());
+			Balances::on_initialize(3);
+			assert_eq!(Balances::existential_deposit_migration(), None);
+
+			// Account 1 was reaped into account 2, account 3 was topped up.
+			assert_eq!(Balances::total_balance(&1), 0);
+			assert_eq!(Balances::free_balance(&2), 55);
+			assert_eq!(Balances::free_balance(&3), 10);
+			assert_eq!(Balances::total_issuance(), 65);
+
+			let events = events();
+			assert!(events.contains(&Event::balances(RawEvent::Reaped(1, 2, 5))));
+			assert!(events.contains(&Event::balances(RawEvent::Upgraded(3, 3))));
+			assert_eq!(events.last(), Some(&Event::balances(RawEvent::ExistentialDepositMigrated)));
+		});
+}
@@ -37,7 +37,35 @@ pub enum BenchmarkSelector {
 }
 
 impl Analysis {
+	/// Analysis of a benchmark without components: the median of its results, which is less
+	/// sensitive to outliers than the mean.
+	fn median_value(r: &Vec<BenchmarkResults>, selector: BenchmarkSelector) -> Option<Self> {
+		if r.is_empty() { return None }
+
+		let mut values: Vec<u128> = r.iter().map(|result|
+			match selector {
+				BenchmarkSelector::ExtrinsicTime => result.extrinsic_time,
+				BenchmarkSelector::StorageRootTime => result.storage_root_time,
+				BenchmarkSelector::Reads => result.reads.into(),
+				BenchmarkSelector::Writes => result.writes.into(),
+			}
+		).collect();
+
+		values.sort();
+		let mid = values.len() / 2;
+
+		Some(Self {
+			base: values[mid],
+			slopes: Vec::new(),
+			names: Vec::new(),
+			value_dists: None,
+			model: None,
+		})
+	}
+
 	pub fn median_slopes(r: &Vec<BenchmarkResults>, selector: BenchmarkSelector) -> Option<Self> {
+		if r[0].components.is_empty() { return Self::median_value(r, selector) }
+
 		let results = r[0].components.iter().enumerate().map(|(i, &(param, _))| {
 			let mut counted = BTreeMap::<Vec<u32>, usize>::new();
 			for result in r.iter() {
@@ -114,6 +142,8 @@ impl Analysis {
 	}
 
 	pub fn min_squares_iqr(r: &Vec<BenchmarkResults>, selector: BenchmarkSelector) -> Option<Self> {
+		if r[0].components.is_empty() { return Self::median_value(r, selector) }
+
 		let mut results = BTreeMap::<Vec<u32>, Vec<u128>>::new();
 		for result in r.iter() {
 			let p = result.components.iter().map(|x| x.1).collect::<Vec<_>>();
@@ -323,4 +353,21 @@ mod tests {
 		assert_eq!(writes.base, 0);
 		assert_eq!(writes.slopes, vec![0, 2]);
 	}
+
+	#[test]
+	fn analysis_without_components_should_use_median_value() {
+		let data = vec![
+			benchmark_result(vec![], 12_000_000, 0, 3, 1),
+			benchmark_result(vec![], 10_000_000, 0, 3, 1),
+			benchmark_result(vec![], 50_000_000, 0, 4, 1),
+		];
+
+		let extrinsic_time = Analysis::median_slopes(&data, BenchmarkSelector::ExtrinsicTime).unwrap();
+		assert_eq!(extrinsic_time.base, 12_000_000);
+		assert!(extrinsic_time.slopes.is_empty());
+
+		let reads = Analysis::min_squares_iqr(&data, BenchmarkSelector::Reads).unwrap();
+		assert_eq!(reads.base, 3);
+		assert!(reads.slopes.is_empty());
+	}
 }
@@ -82,6 +82,9 @@ pub use paste;
 /// Any common parameters that are unused in an arm do not have their instancing expressions
 /// evaluated.
 ///
+/// An arm may also declare no parameters at all. Such a benchmark is run `repeat` times with no
+/// component values, and its weight function takes no arguments.
+///
 /// Example:
 /// ```ignore
 /// benchmarks! {
@@ -791,6 +794,13 @@ macro_rules! impl_benchmark {
 				let components = <SelectedBenchmark as $crate::BenchmarkingSetup<T>>::components(&selected_benchmark);
 				let mut results: Vec<$crate::BenchmarkResults> = Vec::new();
 
+				// The component values to run the benchmark with. A benchmark without components is
+				// run once with no component values.
+				let mut all_components: Vec<Vec<($crate::BenchmarkParameter, u32)>> = Vec::new();
+				if components.is_empty() {
+					all_components.push(Vec::new());
+				}
+
 				// Default number of steps for a component.
 				let mut prev_steps = 10;
 
@@ -827,68 +837,71 @@ macro_rules! impl_benchmark {
 								}
 							)
 							.collect();
+						all_components.push(c);
+					}
+				}
 
-						// Run the benchmark `repeat` times.
-						for _ in 0..repeat {
-							// Set up the externalities environment for the setup we want to
-							// benchmark.
-							let closure_to_benchmark = <
-								SelectedBenchmark as $crate::BenchmarkingSetup<T>
-							>::instance(&selected_benchmark, &c)?;
-
-							// Set the block number to at least 1 so events are deposited.
-							if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
-								frame_system::Module::<T>::set_block_number(1.into());
-							}
-
-							// Commit the externalities to the database, flushing the DB cache.
-							// This will enable worst case scenario for reading from the database.
-							$crate::benchmarking::commit_db();
-
-							// Reset the read/write counter so we don't count operations in the setup process.
-							$crate::benchmarking::reset_read_write_count();
-
-							// Time the extrinsic logic.
-							frame_support::debug::trace!(
-								target: "benchmark",
-								"Start Benchmark: {:?} {:?}", name, component_value
-							);
-
-							let start_extrinsic = $crate::benchmarking::current_time();
-							closure_to_benchmark()?;
-							let finish_extrinsic = $crate::benchmarking::current_time();
-							let elapsed_extrinsic = finish_extrinsic - start_extrinsic;
-							// Commit the changes to get proper write count
-							$crate::benchmarking::commit_db();
-							frame_support::debug::trace!(
-								target: "benchmark",
-								"End Benchmark: {} ns", elapsed_extrinsic
-							);
-							let read_write_count = $crate::benchmarking::read_write_count();
-							frame_support::debug::trace!(
-								target: "benchmark",
-								"Read/Write Count {:?}", read_write_count
-							);
-
-							// Time the storage root recalculation.
-							let start_storage_root = $crate::benchmarking::current_time();
-							$crate::storage_root();
-							let finish_storage_root = $crate::benchmarking::current_time();
-							let elapsed_storage_root = finish_storage_root - start_storage_root;
-
-							results.push($crate::BenchmarkResults {
-								components: c.clone(),
-								extrinsic_time: elapsed_extrinsic,
-								storage_root_time: elapsed_storage_root,
-								reads: read_write_count.0,
-								repeat_reads: read_write_count.1,
-								writes: read_write_count.2,
-								repeat_writes: read_write_count.3,
-							});
-
-							// Wipe the DB back to the genesis state.
-							$crate::benchmarking::wipe_db();
+				for c in all_components {
+					// Run the benchmark `repeat` times.
+					for _ in 0..repeat {
+						// Set up the externalities environment for the setup we want to
+						// benchmark.
+						let closure_to_benchmark = <
+							SelectedBenchmark as $crate::BenchmarkingSetup<T>
+						>::instance(&selected_benchmark, &c)?;
+
+						// Set the block number to at least 1 so events are deposited.
+						if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
+							frame_system::Module::<T>::set_block_number(1.into());
 						}
+
+						// Commit the externalities to the database, flushing the DB cache.
+						// This will enable worst case scenario for reading from the database.
+						$crate::benchmarking::commit_db();
+
+						// Reset the read/write counter so we don't count operations in the setup process.
+						$crate::benchmarking::reset_read_write_count();
+
+						// Time the extrinsic logic.
+						frame_support::debug::trace!(
+							target: "benchmark",
+							"Start Benchmark: {:?}", c
+						);
+
+						let start_extrinsic = $crate::benchmarking::current_time();
+						closure_to_benchmark()?;
+						let finish_extrinsic = $crate::benchmarking::current_time();
+						let elapsed_extrinsic = finish_extrinsic - start_extrinsic;
+						// Commit the changes to get proper write count
+						$crate::benchmarking::commit_db();
+						frame_support::debug::trace!(
+							target: "benchmark",
+							"End Benchmark: {} ns", elapsed_extrinsic
+						);
+						let read_write_count = $crate::benchmarking::read_write_count();
+						frame_support::debug::trace!(
+							target: "benchmark",
+							"Read/Write Count {:?}", read_write_count
+						);
+
+						// Time the storage root recalculation.
+						let start_storage_root = $crate::benchmarking::current_time();
+						$crate::storage_root();
+						let finish_storage_root = $crate::benchmarking::current_time();
+						let elapsed_storage_root = finish_storage_root - start_storage_root;
+
+						results.push($crate::BenchmarkResults {
+							components: c.clone(),
+							extrinsic_time: elapsed_extrinsic,
+							storage_root_time: elapsed_storage_root,
+							reads: read_write_count.0,
+							repeat_reads: read_write_count.1,
+							writes: read_write_count.2,
+							repeat_writes: read_write_count.3,
+						});
+
+						// Wipe the DB back to the genesis state.
+						$crate::benchmarking::wipe_db();
 					}
 				}
 				return Ok(results);
@@ -935,6 +948,13 @@ macro_rules! impl_benchmark {
 				>::components(&selected_benchmark);
 				let mut results: Vec<$crate::BenchmarkResults> = Vec::new();
 
+				// The component values to run the benchmark with. A benchmark without components is
+				// run once with no component values.
+				let mut all_components: Vec<Vec<($crate::BenchmarkParameter, u32)>> = Vec::new();
+				if components.is_empty() {
+					all_components.push(Vec::new());
+				}
+
 				// Default number of steps for a component.
 				let mut prev_steps = 10;
 
@@ -971,67 +991,70 @@ macro_rules! impl_benchmark {
 								}
 							)
 							.collect();
+						all_components.push(c);
+					}
+				}
+
+				for c in all_components {
+					// Run the benchmark `repeat` times.
+					for _ in 0..repeat {
+						// Set up the externalities environment for the setup we want to benchmark.
+						let closure_to_benchmark = <
+							SelectedBenchmark as $crate::BenchmarkingSetupInstance<T, I>
+						>::instance(&selected_benchmark, &c)?;
 
-						// Run the benchmark `repeat` times.
-						for _ in 0..repeat {
-							// Set up the externalities environment for the setup we want to benchmark.
-							let closure_to_benchmark = <
-								SelectedBenchmark as $crate::BenchmarkingSetupInstance<T, I>
-							>::instance(&selected_benchmark, &c)?;
-
-							// Set the block number to at least 1 so events are deposited.
-							if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
-								frame_system::Module::<T>::set_block_number(1.into());
-							}
-
-							// Commit the externalities to the database, flushing the DB cache.
-							// This will enable worst case scenario for reading from the database.
-							$crate::benchmarking::commit_db();
-
-							// Reset the read/write counter so we don't count operations in the setup process.
-							$crate::benchmarking::reset_read_write_count();
-
-							// Time the extrinsic logic.
-							frame_support::debug::trace!(
-								target: "benchmark",
-								"Start Benchmark: {:?} {:?}", name, component_value
-							);
-
-							let start_extrinsic = $crate::benchmarking::current_time();
-							closure_to_benchmark()?;
-							let finish_extrinsic = $crate::benchmarking::current_time();
-							let elapsed_extrinsic = finish_extrinsic - start_extrinsic;
-							// Commit the changes to get proper write count
-							$crate::benchmarking::commit_db();
-							frame_support::debug::trace!(
-								target: "benchmark",
-								"End Benchmark: {} ns", elapsed_extrinsic
-							);
-							let read_write_count = $crate::benchmarking::read_write_count();
-							frame_support::debug::trace!(
-								target: "benchmark",
-								"Read/Write Count {:?}", read_write_count
-							);
-
-							// Time the storage root recalculation.
-							let start_storage_root = $crate::benchmarking::current_time();
-							$crate::storage_root();
-							let finish_storage_root = $crate::benchmarking::current_time();
-							let elapsed_storage_root = finish_storage_root - start_storage_root;
-
-							results.push($crate::BenchmarkResults {
-								components: c.clone(),
-								extrinsic_time: elapsed_extrinsic,
-								storage_root_time: elapsed_storage_root,
-								reads: read_write_count.0,
-								repeat_reads: read_write_count.1,
-								writes: read_write_count.2,
-								repeat_writes: read_write_count.3,
-							});
-
-							// Wipe the DB back to the genesis state.
-							$crate::benchmarking::wipe_db();
+						// Set the block number to at least 1 so events are deposited.
+						if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
+							frame_system::Module::<T>::set_block_number(1.into());
 						}
+
+						// Commit the externalities to the database, flushing the DB cache.
+						// This will enable worst case scenario for reading from the database.
+						$crate::benchmarking::commit_db();
+
+						// Reset the read/write counter so we don't count operations in the setup process.
+						$crate::benchmarking::reset_read_write_count();
+
+						// Time the extrinsic logic.
+						frame_support::debug::trace!(
+							target: "benchmark",
+							"Start Benchmark: {:?}", c
+						);
+
+						let start_extrinsic = $crate::benchmarking::current_time();
+						closure_to_benchmark()?;
+						let finish_extrinsic = $crate::benchmarking::current_time();
+						let elapsed_extrinsic = finish_extrinsic - start_extrinsic;
+						// Commit the changes to get proper write count
+						$crate::benchmarking::commit_db();
+						frame_support::debug::trace!(
+							target: "benchmark",
+							"End Benchmark: {} ns", elapsed_extrinsic
+						);
+						let read_write_count = $crate::benchmarking::read_write_count();
+						frame_support::debug::trace!(
+							target: "benchmark",
+							"Read/Write Count {:?}", read_write_count
+						);
+
+						// Time the storage root recalculation.
+						let start_storage_root = $crate::benchmarking::current_time();
+						$crate::storage_root();
+						let finish_storage_root = $crate::benchmarking::current_time();
+						let elapsed_storage_root = finish_storage_root - start_storage_root;
+
+						results.push($crate::BenchmarkResults {
+							components: c.clone(),
+							extrinsic_time: elapsed_extrinsic,
+							storage_root_time: elapsed_storage_root,
+							reads: read_write_count.0,
+							repeat_reads: read_write_count.1,
+							writes: read_write_count.2,
+							repeat_writes: read_write_count.3,
+						});
+
+						// Wipe the DB back to the genesis state.
+						$crate::benchmarking::wipe_db();
 					}
 				}
 				return Ok(results);
@@ -1060,10 +1083,11 @@ macro_rules! impl_benchmark_test {
 					SelectedBenchmark as $crate::BenchmarkingSetup<T>
 				>::components(&selected_benchmark);
 
-				assert!(
-					components.len() != 0,
-					"You need to add components to your benchmark!",
-				);
+				// A benchmark without components is tested once with no component values.
+				let mut all_components: Vec<Vec<($crate::BenchmarkParameter, u32)>> = Vec::new();
+				if components.is_empty() {
+					all_components.push(Vec::new());
+				}
 				for (_, (name, low, high)) in components.iter().enumerate() {
 					// Test only the low and high value, assuming values in the middle won't break
 					for component_value in vec![low, high] {
@@ -1078,23 +1102,26 @@ macro_rules! impl_benchmark_test {
 								}
 							)
 							.collect();
+						all_components.push(c);
+					}
+				}
 
-						// Set up the verification state
-						let closure_to_verify = <
-							SelectedBenchmark as $crate::BenchmarkingSetup<T>
-						>::verify(&selected_benchmark, &c)?;
+				for c in all_components {
+					// Set up the verification state
+					let closure_to_verify = <
+						SelectedBenchmark as $crate::BenchmarkingSetup<T>
+					>::verify(&selected_benchmark, &c)?;
 
-						// Set the block number to at least 1 so events are deposited.
-						if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
-							frame_system::Module::<T>::set_block_number(1.into());
-						}
+					// Set the block number to at least 1 so events are deposited.
+					if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
+						frame_system::Module::<T>::set_block_number(1.into());
+					}
 
-						// Run verification
-						closure_to_verify()?;
+					// Run verification
+					closure_to_verify()?;
 
-						// Reset the state
-						$crate::benchmarking::wipe_db();
-					}
+					// Reset the state
+					$crate::benchmarking::wipe_db();
 				}
 				Ok(())
 			}
@@ -1114,6 +1141,11 @@ macro_rules! impl_benchmark_test {
 					SelectedBenchmark as $crate::BenchmarkingSetupInstance<T, _>
 				>::components(&selected_benchmark);
 
+				// A benchmark without components is tested once with no component values.
+				let mut all_components: Vec<Vec<($crate::BenchmarkParameter, u32)>> = Vec::new();
+				if components.is_empty() {
+					all_components.push(Vec::new());
+				}
 				for (_, (name, low, high)) in components.iter().enumerate() {
 					// Test only the low and high value, assuming values in the middle won't break
 					for component_value in vec![low, high] {
@@ -1128,23 +1160,26 @@ macro_rules! impl_benchmark_test {
 								}
 							)
 							.collect();
+						all_components.push(c);
+					}
+				}
 
-						// Set up the verification state
-						let closure_to_verify = <
-							SelectedBenchmark as $crate::BenchmarkingSetupInstance<T, _>
-						>::verify(&selected_benchmark, &c)?;
+				for c in all_components {
+					// Set up the verification state
+					let closure_to_verify = <
+						SelectedBenchmark as $crate::BenchmarkingSetupInstance<T, _>
+					>::verify(&selected_benchmark, &c)?;
 
-						// Set the block number to at least 1 so events are deposited.
-						if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
-							frame_system::Module::<T>::set_block_number(1.into());
-						}
+					// Set the block number to at least 1 so events are deposited.
+					if $crate::Zero::is_zero(&frame_system::Module::<T>::block_number()) {
+						frame_system::Module::<T>::set_block_number(1.into());
+					}
 
-						// Run verification
-						closure_to_verify()?;
+					// Run verification
+					closure_to_verify()?;
 
-						// Reset the state
-						$crate::benchmarking::wipe_db();
-					}
+					// Reset the state
+					$crate::benchmarking::wipe_db();
 				}
 				Ok(())
 			}
@@ -165,6 +165,13 @@ benchmarks!{
 	verify {
 		ensure!(m[0] == 0, "You forgot to sort!")
 	}
+
+	no_components {
+		let caller = account::<T::AccountId>("caller", 0, 0);
+	}: set_value(RawOrigin::Signed(caller), 10)
+	verify {
+		assert_eq!(Value::get(), Some(10));
+	}
 }
 
 #[test]
@@ -217,6 +224,22 @@ fn benchmarks_macro_works_for_non_dispatchable() {
 	assert_eq!(closure(), Ok(()));
 }
 
+#[test]
+fn benchmarks_macro_works_without_components() {
+	let selected = SelectedBenchmark::no_components;
+
+	let components = <SelectedBenchmark as BenchmarkingSetup<Test>>::components(&selected);
+	assert!(components.is_empty());
+
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::instance(&selected, &[])
+		.expect("failed to create closure");
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(closure());
+		assert_eq!(Value::get(), Some(10));
+	});
+}
+
 #[test]
 fn benchmarks_macro_verify_works() {
 	// Check postcondition for benchmark `set_value` is valid.
@@ -238,6 +261,7 @@ fn benchmarks_generate_unit_tests() {
 		assert_ok!(test_benchmark_set_value::<Test>());
 		assert_ok!(test_benchmark_other_name::<Test>());
 		assert_ok!(test_benchmark_sort_vector::<Test>());
+		assert_ok!(test_benchmark_no_components::<Test>());
 		assert_err!(test_benchmark_bad_origin::<Test>(), "Bad origin");
 		assert_err!(test_benchmark_bad_verify::<Test>(), "You forgot to sort!");
 	});
@@ -0,0 +1,202 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Child bounties pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::OnInitialize;
+
+use crate::Module as ChildBounties;
+use pallet_treasury::Module as Treasury;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	let balance = T::Currency::minimum_balance().saturating_mul(1_000_000.into());
+	T::Currency::make_free_balance_be(&caller, balance);
+	caller
+}
+
+// Create an active parent bounty, returning its curator and index.
+fn create_parent_bounty<T: Trait>(u: u32) -> Result<(T::AccountId, BountyIndex), &'static str> {
+	let pot_account = Treasury::<T>::account_id();
+	let pot = T::Currency::minimum_balance().saturating_mul(1_000_000_000.into());
+	let _ = T::Currency::make_free_balance_be(&pot_account, pot);
+
+	let proposer = funded_account::<T>("proposer", u);
+	let curator = funded_account::<T>("curator", u);
+	let value = T::BountyValueMinimum::get()
+		.max(T::ChildBountyValueMinimum::get())
+		.saturating_mul(100.into());
+	let fee = value / 2.into();
+
+	Treasury::<T>::propose_bounty(RawOrigin::Signed(proposer).into(), value, vec![])?;
+	let bounty_id = Treasury::<T>::bounty_count() - 1;
+	Treasury::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+	Treasury::<T>::on_initialize(T::BlockNumber::zero());
+	let curator_lookup = T::Lookup::unlookup(curator.clone());
+	Treasury::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup, fee)?;
+	Treasury::<T>::accept_curator(RawOrigin::Signed(curator.clone()).into(), bounty_id)?;
+	Ok((curator, bounty_id))
+}
+
+// Add a child bounty to `parent`, returning its index.
+fn create_child_bounty<T: Trait>(
+	parent_curator: T::AccountId,
+	parent: BountyIndex,
+	d: u32,
+) -> Result<BountyIndex, &'static str> {
+	let value = T::ChildBountyValueMinimum::get().saturating_mul(10.into());
+	let description = vec![0; d as usize];
+	let origin = RawOrigin::Signed(parent_curator).into();
+	ChildBounties::<T>::add_child_bounty(origin, parent, value, description)?;
+	Ok(ChildBounties::<T>::child_bounty_count() - 1)
+}
+
+// Add a child bounty to `parent` with an active child curator, returning the child curator and
+// the child bounty index.
+fn create_active_child_bounty<T: Trait>(
+	parent_curator: T::AccountId,
+	parent: BountyIndex,
+	u: u32,
+) -> Result<(T::AccountId, BountyIndex), &'static str> {
+	let child = create_child_bounty::<T>(parent_curator.clone(), parent, 0)?;
+	let child_curator = funded_account::<T>("child_curator", u);
+	let child_curator_lookup = T::Lookup::unlookup(child_curator.clone());
+	let fee = T::ChildBountyValueMinimum::get();
+	ChildBounties::<T>::propose_curator(
+		RawOrigin::Signed(parent_curator).into(),
+		parent,
+		child,
+		child_curator_lookup,
+		fee,
+	)?;
+	let origin = RawOrigin::Signed(child_curator.clone()).into();
+	ChildBounties::<T>::accept_curator(origin, parent, child)?;
+	Ok((child_curator, child))
+}
+
+benchmarks! {
+	_ { }
+
+	add_child_bounty {
+		let d in 0 .. T::MaximumReasonLength::get();
+		let (curator, parent) = create_parent_bounty::<T>(0)?;
+		let value = T::ChildBountyValueMinimum::get().saturating_mul(10.into());
+		let description = vec![0; d as usize];
+	}: _(RawOrigin::Signed(curator), parent, value, description)
+	verify {
+		assert_eq!(ChildBounties::<T>::parent_child_bounties(parent), 1);
+	}
+
+	propose_curator {
+		let u in 0 .. 1000;
+		let (curator, parent) = create_parent_bounty::<T>(u)?;
+		let child = create_child_bounty::<T>(curator.clone(), parent, 0)?;
+		let child_curator = T::Lookup::unlookup(account("child_curator", u, SEED));
+		let fee = T::ChildBountyValueMinimum::get();
+	}: _(RawOrigin::Signed(curator), parent, child, child_curator, fee)
+
+	accept_curator {
+		let u in 0 .. 1000;
+		let (curator, parent) = create_parent_bounty::<T>(u)?;
+		let child = create_child_bounty::<T>(curator.clone(), parent, 0)?;
+		let child_curator = funded_account::<T>("child_curator", u);
+		let child_curator_lookup = T::Lookup::unlookup(child_curator.clone());
+		let fee = T::ChildBountyValueMinimum::get();
+		ChildBounties::<T>::propose_curator(
+			RawOrigin::Signed(curator).into(),
+			parent,
+			child,
+			child_curator_lookup,
+			fee,
+		)?;
+	}: _(RawOrigin::Signed(child_curator), parent, child)
+
+	// Worst case when the parent curator unassigns and slashes an active child curator.
+	unassign_curator {
+		let u in 0 .. 1000;
+		let (curator, parent) = create_parent_bounty::<T>(u)?;
+		let (_child_curator, child) = create_active_child_bounty::<T>(curator.clone(), parent, u)?;
+	}: _(RawOrigin::Signed(curator), parent, child)
+
+	award_child_bounty {
+		let u in 0 .. 1000;
+		let (curator, parent) = create_parent_bounty::<T>(u)?;
+		let (child_curator, child) = create_active_child_bounty::<T>(curator, parent, u)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", u, SEED));
+	}: _(RawOrigin::Signed(child_curator), parent, child, beneficiary)
+
+	claim_child_bounty {
+		let u in 0 .. 1000;
+		let (curator, parent) = create_parent_bounty::<T>(u)?;
+		let (child_curator, child) = create_active_child_bounty::<T>(curator, parent, u)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", u, SEED));
+		ChildBounties::<T>::award_child_bounty(
+			RawOrigin::Signed(child_curator.clone()).into(),
+			parent,
+			child,
+			beneficiary,
+		)?;
+		system::Module::<T>::set_block_number(T::BountyDepositPayoutDelay::get());
+	}: _(RawOrigin::Signed(child_curator), parent, child)
+	verify {
+		assert!(ChildBounties::<T>::child_bounties(parent, child).is_none());
+	}
+
+	close_child_bounty_added {
+		let u in 0 .. 1000;
+		let (curator, parent) = create_parent_bounty::<T>(u)?;
+		let child = create_child_bounty::<T>(curator.clone(), parent, 0)?;
+	}: close_child_bounty(RawOrigin::Signed(curator), parent, child)
+
+	close_child_bounty_active {
+		let u in 0 .. 1000;
+		let (curator, parent) = create_parent_bounty::<T>(u)?;
+		let (_child_curator, child) = create_active_child_bounty::<T>(curator.clone(), parent, u)?;
+	}: close_child_bounty(RawOrigin::Signed(curator), parent, child)
+	verify {
+		assert!(ChildBounties::<T>::child_bounties(parent, child).is_none());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_child_bounty::<Test>());
+			assert_ok!(test_benchmark_propose_curator::<Test>());
+			assert_ok!(test_benchmark_accept_curator::<Test>());
+			assert_ok!(test_benchmark_unassign_curator::<Test>());
+			assert_ok!(test_benchmark_award_child_bounty::<Test>());
+			assert_ok!(test_benchmark_claim_child_bounty::<Test>());
+			assert_ok!(test_benchmark_close_child_bounty_added::<Test>());
+			assert_ok!(test_benchmark_close_child_bounty_active::<Test>());
+		});
+	}
+}
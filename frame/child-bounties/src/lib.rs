@@ -0,0 +1,645 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Child Bounties Module
+//!
+//! - [`child_bounties::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! A child bounty is a split of a larger treasury bounty, the parent bounty, into smaller pieces
+//! of work. The curator of an active parent bounty can add child bounties, each funded from the
+//! parent bounty account, and assign every child its own curator and curator fee. The child
+//! curator later awards the child bounty to a beneficiary, who can claim the payout after the
+//! same payout delay as for bounties.
+//!
+//! The number of child bounties a parent bounty may have open at once is limited by
+//! `MaxActiveChildBountyCount`. A parent bounty cannot be awarded or closed while it has open
+//! child bounties; the treasury learns about them through its `ChildBountyManager`, which this
+//! module implements.
+//!
+//! ### Terminology
+//!
+//! - **Parent bounty:** An active treasury bounty which child bounties are carved out of.
+//! - **Child bounty:** A part of the parent bounty value, set aside for a smaller body of work.
+//! - **Child curator:** An account managing a child bounty and assigning its beneficiary.
+//! - **Curator deposit:** The `BountyCuratorDeposit` part of the child curator fee, reserved from
+//!   the child curator while the child bounty is active.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `add_child_bounty` - Add a child bounty to an active parent bounty, funding it from the
+//!   parent bounty account.
+//! - `propose_curator` - Propose a curator and a curator fee for a child bounty.
+//! - `accept_curator` - Accept the child curator role, reserving the curator deposit.
+//! - `unassign_curator` - Unassign the curator of a child bounty.
+//! - `award_child_bounty` - Award a child bounty to a beneficiary.
+//! - `claim_child_bounty` - Pay out an awarded child bounty after the payout delay.
+//! - `close_child_bounty` - Close a child bounty, returning its funds to the parent bounty.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod tests;
+mod benchmarking;
+
+use sp_std::prelude::*;
+use frame_support::{decl_module, decl_storage, decl_event, decl_error, ensure};
+use frame_support::traits::{
+	Currency, Get, OnUnbalanced, ReservableCurrency, EnsureOrigin,
+	ExistenceRequirement::{KeepAlive, AllowDeath},
+};
+use frame_support::{dispatch::DispatchResult, weights::Weight};
+use sp_runtime::{RuntimeDebug, traits::{
+	Zero, StaticLookup, AccountIdConversion, Saturating, BadOrigin
+}};
+use frame_system::{self as system, ensure_signed};
+use codec::{Encode, Decode};
+use pallet_treasury::{BountyIndex, BountyStatus, ChildBountyManager};
+
+type BalanceOf<T> = <<T as pallet_treasury::Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub trait Trait: frame_system::Trait + pallet_treasury::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Maximum number of child bounties a parent bounty may have open at the same time.
+	type MaxActiveChildBountyCount: Get<u32>;
+
+	/// Minimum value for a child bounty.
+	type ChildBountyValueMinimum: Get<BalanceOf<Self>>;
+}
+
+/// A child bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ChildBounty<AccountId, Balance, BlockNumber> {
+	/// The parent of this child bounty.
+	parent_bounty: BountyIndex,
+	/// The (total) amount that should be paid if the child bounty is rewarded.
+	value: Balance,
+	/// The child curator fee. Included in value.
+	fee: Balance,
+	/// The deposit of the child curator.
+	curator_deposit: Balance,
+	/// The status of this child bounty.
+	status: ChildBountyStatus<AccountId, BlockNumber>,
+}
+
+/// The status of a child bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ChildBountyStatus<AccountId, BlockNumber> {
+	/// The child bounty is added and funded, waiting for curator assignment.
+	Added,
+	/// A curator has been proposed by the parent bounty curator. Waiting for acceptance from the
+	/// child curator.
+	CuratorProposed {
+		/// The assigned child curator of this child bounty.
+		curator: AccountId,
+	},
+	/// The child bounty is active and waiting to be awarded.
+	Active {
+		/// The child curator of this child bounty.
+		curator: AccountId,
+	},
+	/// The child bounty is awarded and waiting to be released after a delay.
+	PendingPayout {
+		/// The child curator of this child bounty.
+		curator: AccountId,
+		/// The beneficiary of the child bounty.
+		beneficiary: AccountId,
+		/// When the child bounty can be claimed.
+		unlock_at: BlockNumber,
+	},
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as ChildBounties {
+		/// Number of child bounties that have been added.
+		pub ChildBountyCount get(fn child_bounty_count): BountyIndex;
+
+		/// Number of open child bounties per parent bounty.
+		pub ParentChildBounties get(fn parent_child_bounties):
+			map hasher(twox_64_concat) BountyIndex => u32;
+
+		/// Child bounties that have been added, keyed by parent bounty and child bounty index.
+		pub ChildBounties get(fn child_bounties):
+			double_map hasher(twox_64_concat) BountyIndex, hasher(twox_64_concat) BountyIndex
+			=> Option<ChildBounty<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+		/// The description of each child bounty.
+		pub ChildBountyDescriptions get(fn child_bounty_descriptions):
+			map hasher(twox_64_concat) BountyIndex => Option<Vec<u8>>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T>
+	where
+		Balance = BalanceOf<T>,
+		<T as frame_system::Trait>::AccountId,
+	{
+		/// A child bounty is added. \[parent_index, child_index\]
+		Added(BountyIndex, BountyIndex),
+		/// A child bounty is awarded to a beneficiary. \[parent_index, child_index, beneficiary\]
+		Awarded(BountyIndex, BountyIndex, AccountId),
+		/// A child bounty is claimed by the beneficiary.
+		/// \[parent_index, child_index, payout, beneficiary\]
+		Claimed(BountyIndex, BountyIndex, Balance, AccountId),
+		/// A child bounty is cancelled. \[parent_index, child_index\]
+		Canceled(BountyIndex, BountyIndex),
+	}
+);
+
+decl_error! {
+	/// Error for the child bounties module.
+	pub enum Error for Module<T: Trait> {
+		/// No child bounty at that index.
+		InvalidIndex,
+		/// The parent bounty is not in active state.
+		ParentBountyNotActive,
+		/// The parent bounty account does not hold enough free balance to fund the child bounty.
+		InsufficientBountyBalance,
+		/// The parent bounty already has the maximum number of open child bounties.
+		TooManyChildBounties,
+		/// The child bounty status is unexpected.
+		UnexpectedStatus,
+		/// Require the parent or child bounty curator.
+		RequireCurator,
+		/// Invalid child bounty value.
+		InvalidValue,
+		/// Invalid child bounty fee.
+		InvalidFee,
+		/// The description given is just too big.
+		ReasonTooBig,
+		/// The child bounty cannot be claimed because it's still in the payout delay.
+		Premature,
+		/// A child bounty payout is pending.
+		/// To cancel the child bounty, you must unassign and slash the child curator.
+		PendingPayout,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		/// Maximum number of child bounties a parent bounty may have open at the same time.
+		const MaxActiveChildBountyCount: u32 = T::MaxActiveChildBountyCount::get();
+
+		/// Minimum value for a child bounty.
+		const ChildBountyValueMinimum: BalanceOf<T> = T::ChildBountyValueMinimum::get();
+
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Add a new child bounty to an active parent bounty. The child bounty `value` is
+		/// transferred from the parent bounty account to the child bounty account.
+		///
+		/// The dispatch origin for this call must be the curator of the parent bounty.
+		///
+		/// - `parent_bounty_id`: Index of the parent bounty.
+		/// - `value`: The total payment amount of this child bounty, child curator fee included.
+		/// - `description`: The description of this child bounty.
+		///
+		/// # <weight>
+		/// - Complexity: `O(D)` where `D` is the length of `description`.
+		/// - DbReads: `Bounties`, `ParentChildBounties`, `ChildBountyCount`,
+		///   `parent bounty account`
+		/// - DbWrites: `ParentChildBounties`, `ChildBountyCount`, `ChildBounties`,
+		///   `ChildBountyDescriptions`, `parent bounty account`, `child bounty account`
+		/// # </weight>
+		#[weight = 200_000_000 + 1_000 * description.len() as Weight
+			+ T::DbWeight::get().reads_writes(4, 6)]
+		fn add_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] value: BalanceOf<T>,
+			description: Vec<u8>,
+		) {
+			let signer = ensure_signed(origin)?;
+
+			ensure!(
+				description.len() <= T::MaximumReasonLength::get() as usize,
+				Error::<T>::ReasonTooBig,
+			);
+			ensure!(value >= T::ChildBountyValueMinimum::get(), Error::<T>::InvalidValue);
+			ensure!(
+				Self::parent_child_bounties(parent_bounty_id) < T::MaxActiveChildBountyCount::get(),
+				Error::<T>::TooManyChildBounties,
+			);
+
+			let parent_curator = Self::parent_curator(parent_bounty_id)?;
+			ensure!(signer == parent_curator, Error::<T>::RequireCurator);
+
+			let child_bounty_id = Self::child_bounty_count();
+			let parent_bounty_account =
+				pallet_treasury::Module::<T>::bounty_account_id(parent_bounty_id);
+			let child_bounty_account = Self::child_bounty_account_id(child_bounty_id);
+			T::Currency::transfer(&parent_bounty_account, &child_bounty_account, value, KeepAlive)
+				.map_err(|_| Error::<T>::InsufficientBountyBalance)?;
+
+			ChildBountyCount::put(child_bounty_id + 1);
+			ParentChildBounties::mutate(parent_bounty_id, |count| *count += 1);
+
+			let child_bounty = ChildBounty {
+				parent_bounty: parent_bounty_id,
+				value,
+				fee: Zero::zero(),
+				curator_deposit: Zero::zero(),
+				status: ChildBountyStatus::Added,
+			};
+			ChildBounties::<T>::insert(parent_bounty_id, child_bounty_id, &child_bounty);
+			ChildBountyDescriptions::insert(child_bounty_id, description);
+
+			Self::deposit_event(RawEvent::Added(parent_bounty_id, child_bounty_id));
+		}
+
+		/// Propose a curator for a child bounty.
+		///
+		/// The dispatch origin for this call must be the curator of the parent bounty. The parent
+		/// bounty must be active.
+		///
+		/// - `curator`: The account proposed to curate the child bounty.
+		/// - `fee`: The child curator fee, paid out of the child bounty value once it is claimed.
+		///   Must be less than the child bounty value.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `ChildBounties`
+		/// - DbWrites: `ChildBounties`
+		/// # </weight>
+		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(2, 1)]
+		fn propose_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+			curator: <T::Lookup as StaticLookup>::Source,
+			#[compact] fee: BalanceOf<T>,
+		) {
+			let signer = ensure_signed(origin)?;
+			let curator = T::Lookup::lookup(curator)?;
+
+			let parent_curator = Self::parent_curator(parent_bounty_id)?;
+			ensure!(signer == parent_curator, Error::<T>::RequireCurator);
+
+			ChildBounties::<T>::try_mutate(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+					ensure!(
+						child_bounty.status == ChildBountyStatus::Added,
+						Error::<T>::UnexpectedStatus,
+					);
+					ensure!(fee < child_bounty.value, Error::<T>::InvalidFee);
+
+					child_bounty.status = ChildBountyStatus::CuratorProposed { curator };
+					child_bounty.fee = fee;
+					Ok(())
+				}
+			)?;
+		}
+
+		/// Accept the curator role for a child bounty. A deposit of `BountyCuratorDeposit` of the
+		/// child curator fee is reserved and refunded upon successful payout.
+		///
+		/// The dispatch origin for this call must be the proposed child curator. The parent bounty
+		/// must be active.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `ChildBounties`, `curator account`
+		/// - DbWrites: `ChildBounties`, `curator account`
+		/// # </weight>
+		#[weight = 60_000_000 + T::DbWeight::get().reads_writes(3, 2)]
+		fn accept_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let signer = ensure_signed(origin)?;
+			Self::parent_curator(parent_bounty_id)?;
+
+			ChildBounties::<T>::try_mutate(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+					match child_bounty.status {
+						ChildBountyStatus::CuratorProposed { ref curator } => {
+							ensure!(signer == *curator, Error::<T>::RequireCurator);
+
+							let deposit = T::BountyCuratorDeposit::get() * child_bounty.fee;
+							T::Currency::reserve(curator, deposit)?;
+							child_bounty.curator_deposit = deposit;
+
+							child_bounty.status = ChildBountyStatus::Active { curator: signer };
+							Ok(())
+						},
+						_ => Err(Error::<T>::UnexpectedStatus.into()),
+					}
+				}
+			)?;
+		}
+
+		/// Unassign the curator of a child bounty.
+		///
+		/// This function can only be called by the `RejectOrigin` or a signed origin.
+		///
+		/// If it is called by the `RejectOrigin` or by the curator of the parent bounty, we assume
+		/// that the child curator is malicious or inactive, and the curator deposit is slashed.
+		///
+		/// If the origin is the child curator, we take this as a sign they are unable to do their
+		/// job and they willingly give up. They recover their deposit and exit without issue.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `ChildBounties`, `curator account`
+		/// - DbWrites: `ChildBounties`, `curator account`
+		/// # </weight>
+		#[weight = 60_000_000 + T::DbWeight::get().reads_writes(3, 2)]
+		fn unassign_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+			// The curator of the parent bounty has the same rights as the `RejectOrigin`.
+			let parent_curator = Self::parent_curator(parent_bounty_id).ok();
+			let maybe_sender = maybe_sender
+				.filter(|sender| parent_curator.as_ref() != Some(sender));
+
+			ChildBounties::<T>::try_mutate(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+
+					let slash_curator = |curator: &T::AccountId, deposit: &mut BalanceOf<T>| {
+						let imbalance = T::Currency::slash_reserved(curator, *deposit).0;
+						T::ProposalRejection::on_unbalanced(imbalance);
+						*deposit = Zero::zero();
+					};
+
+					match child_bounty.status {
+						ChildBountyStatus::Added => {
+							// No curator to unassign at this point.
+							return Err(Error::<T>::UnexpectedStatus.into())
+						},
+						ChildBountyStatus::CuratorProposed { ref curator } => {
+							// A curator has been proposed, but not accepted yet.
+							ensure!(
+								maybe_sender.map_or(true, |sender| sender == *curator),
+								BadOrigin,
+							);
+						},
+						ChildBountyStatus::Active { ref curator } => {
+							match maybe_sender {
+								None => slash_curator(curator, &mut child_bounty.curator_deposit),
+								Some(sender) => {
+									ensure!(sender == *curator, BadOrigin);
+									// The child curator is willingly giving up their role, so
+									// give back their deposit.
+									let deposit = child_bounty.curator_deposit;
+									let _ = T::Currency::unreserve(curator, deposit);
+									child_bounty.curator_deposit = Zero::zero();
+								},
+							}
+						},
+						ChildBountyStatus::PendingPayout { ref curator, .. } => {
+							// The child bounty is pending payout, so only the `RejectOrigin` or the
+							// parent curator can unassign the child curator, claiming they acted
+							// maliciously.
+							ensure!(maybe_sender.is_none(), BadOrigin);
+							slash_curator(curator, &mut child_bounty.curator_deposit);
+						},
+					}
+
+					child_bounty.status = ChildBountyStatus::Added;
+					Ok(())
+				}
+			)?;
+		}
+
+		/// Award a child bounty to a beneficiary account. The beneficiary will be able to claim
+		/// the funds after `BountyDepositPayoutDelay`.
+		///
+		/// The dispatch origin for this call must be the child curator. The parent bounty must be
+		/// active.
+		///
+		/// - `beneficiary`: The beneficiary account whom will receive the payout.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `ChildBounties`
+		/// - DbWrites: `ChildBounties`
+		/// # </weight>
+		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(2, 1)]
+		fn award_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) {
+			let signer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			Self::parent_curator(parent_bounty_id)?;
+
+			ChildBounties::<T>::try_mutate(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+					match &child_bounty.status {
+						ChildBountyStatus::Active { curator } => {
+							ensure!(signer == *curator, Error::<T>::RequireCurator);
+						},
+						_ => return Err(Error::<T>::UnexpectedStatus.into()),
+					}
+					child_bounty.status = ChildBountyStatus::PendingPayout {
+						curator: signer,
+						beneficiary: beneficiary.clone(),
+						unlock_at: system::Module::<T>::block_number()
+							+ T::BountyDepositPayoutDelay::get(),
+					};
+					Ok(())
+				}
+			)?;
+
+			Self::deposit_event(RawEvent::Awarded(parent_bounty_id, child_bounty_id, beneficiary));
+		}
+
+		/// Claim the payout from an awarded child bounty after the payout delay.
+		///
+		/// The dispatch origin for this call must be _Signed_. Anyone may trigger the payout on
+		/// behalf of the beneficiary.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `ChildBounties`, `child bounty account`, `curator account`,
+		///   `beneficiary account`
+		/// - DbWrites: `ChildBounties`, `ChildBountyDescriptions`, `ParentChildBounties`,
+		///   `child bounty account`, `curator account`, `beneficiary account`
+		/// # </weight>
+		#[weight = 200_000_000 + T::DbWeight::get().reads_writes(4, 6)]
+		fn claim_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let _ = ensure_signed(origin)?;
+
+			ChildBounties::<T>::try_mutate(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.take().ok_or(Error::<T>::InvalidIndex)?;
+					if let ChildBountyStatus::PendingPayout {
+						curator, beneficiary, unlock_at
+					} = child_bounty.status {
+						let now = system::Module::<T>::block_number();
+						ensure!(now >= unlock_at, Error::<T>::Premature);
+
+						let child_bounty_account = Self::child_bounty_account_id(child_bounty_id);
+						let balance = T::Currency::free_balance(&child_bounty_account);
+						// just to be safe.
+						let fee = child_bounty.fee.min(balance);
+						let payout = balance.saturating_sub(fee);
+						let _ = T::Currency::unreserve(&curator, child_bounty.curator_deposit);
+						// should not fail, the child bounty account holds at least `fee + payout`.
+						let account = child_bounty_account;
+						let _ = T::Currency::transfer(&account, &curator, fee, AllowDeath);
+						let _ = T::Currency::transfer(&account, &beneficiary, payout, AllowDeath);
+						Self::remove_child_bounty(parent_bounty_id, child_bounty_id);
+
+						Self::deposit_event(RawEvent::Claimed(
+							parent_bounty_id,
+							child_bounty_id,
+							payout,
+							beneficiary,
+						));
+						Ok(())
+					} else {
+						Err(Error::<T>::UnexpectedStatus.into())
+					}
+				}
+			)?;
+		}
+
+		/// Close a child bounty. The remaining funds of the child bounty are returned to the
+		/// parent bounty account and the child curator deposit is unreserved if possible.
+		///
+		/// The dispatch origin for this call must be the `RejectOrigin` or the curator of the
+		/// parent bounty.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `ChildBounties`, `child bounty account`, `curator account`
+		/// - DbWrites: `ChildBounties`, `ChildBountyDescriptions`, `ParentChildBounties`,
+		///   `child bounty account`, `parent bounty account`, `curator account`
+		/// # </weight>
+		#[weight = 200_000_000 + T::DbWeight::get().reads_writes(4, 6)]
+		fn close_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			if let Ok(signer) = ensure_signed(origin.clone()) {
+				let parent_curator = Self::parent_curator(parent_bounty_id)?;
+				ensure!(signer == parent_curator, BadOrigin);
+			} else {
+				T::RejectOrigin::ensure_origin(origin)?;
+			}
+
+			ChildBounties::<T>::try_mutate(
+				parent_bounty_id,
+				child_bounty_id,
+				|maybe_child_bounty| -> DispatchResult {
+					let child_bounty = maybe_child_bounty.take().ok_or(Error::<T>::InvalidIndex)?;
+					match child_bounty.status {
+						ChildBountyStatus::Added | ChildBountyStatus::CuratorProposed { .. } => {
+							// Nothing extra to do besides the removal of the child bounty below.
+						},
+						ChildBountyStatus::Active { curator } => {
+							// Refund the deposit of the working child curator.
+							let _ = T::Currency::unreserve(&curator, child_bounty.curator_deposit);
+						},
+						ChildBountyStatus::PendingPayout { .. } => {
+							// The child curator must first be unassigned, slashing their deposit.
+							return Err(Error::<T>::PendingPayout.into())
+						},
+					}
+
+					let child_bounty_account = Self::child_bounty_account_id(child_bounty_id);
+					let parent_bounty_account =
+						pallet_treasury::Module::<T>::bounty_account_id(parent_bounty_id);
+					let balance = T::Currency::free_balance(&child_bounty_account);
+					// should not fail, we are moving the whole free balance of the child account.
+					let _ = T::Currency::transfer(
+						&child_bounty_account,
+						&parent_bounty_account,
+						balance,
+						AllowDeath,
+					);
+					Self::remove_child_bounty(parent_bounty_id, child_bounty_id);
+
+					Self::deposit_event(RawEvent::Canceled(parent_bounty_id, child_bounty_id));
+					Ok(())
+				}
+			)?;
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The account ID of a child bounty account.
+	pub fn child_bounty_account_id(id: BountyIndex) -> T::AccountId {
+		// only use two byte prefix to support 16 byte account id (used by test)
+		// "modl" ++ "py/trsry" ++ "cb" is 14 bytes, and two bytes remaining for child bounty index
+		T::ModuleId::get().into_sub_account(("cb", id))
+	}
+
+	/// The curator of the parent bounty, if the parent bounty is active.
+	fn parent_curator(parent_bounty_id: BountyIndex) -> Result<T::AccountId, Error<T>> {
+		let parent_bounty = pallet_treasury::Module::<T>::bounties(parent_bounty_id)
+			.ok_or(Error::<T>::ParentBountyNotActive)?;
+		match parent_bounty.get_status() {
+			BountyStatus::Active { curator, .. } => Ok(curator),
+			_ => Err(Error::<T>::ParentBountyNotActive),
+		}
+	}
+
+	/// Remove the description and the parent's open child bounty entry of a child bounty.
+	fn remove_child_bounty(parent_bounty_id: BountyIndex, child_bounty_id: BountyIndex) {
+		ChildBountyDescriptions::remove(child_bounty_id);
+		ParentChildBounties::mutate_exists(parent_bounty_id, |maybe_count| {
+			match maybe_count {
+				Some(count) if *count > 1 => *count -= 1,
+				_ => *maybe_count = None,
+			}
+		});
+	}
+}
+
+impl<T: Trait> ChildBountyManager for Module<T> {
+	fn child_bounties_count(bounty_id: BountyIndex) -> BountyIndex {
+		Self::parent_child_bounties(bounty_id)
+	}
+}
@@ -0,0 +1,443 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Child bounties pallet tests.
+
+#![cfg(test)]
+
+use super::*;
+use std::cell::RefCell;
+use frame_support::{
+	assert_noop, assert_ok, impl_outer_origin, parameter_types,
+	traits::{Contains, ContainsLengthBound, OnInitialize},
+};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill, Permill, Percent, ModuleId,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+};
+use pallet_treasury::Error as TreasuryError;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u128; // u64 is not enough to hold bytes used to generate bounty account
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type MaximumBlockLength = MaximumBlockLength;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+thread_local! {
+	static TEN_TO_FOURTEEN: RefCell<Vec<u128>> = RefCell::new(vec![10,11,12,13,14]);
+}
+pub struct TenToFourteen;
+impl Contains<u128> for TenToFourteen {
+	fn sorted_members() -> Vec<u128> {
+		TEN_TO_FOURTEEN.with(|v| v.borrow().clone())
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add(new: &u128) {
+		TEN_TO_FOURTEEN.with(|v| {
+			let mut members = v.borrow_mut();
+			members.push(*new);
+			members.sort();
+		})
+	}
+}
+impl ContainsLengthBound for TenToFourteen {
+	fn max_len() -> usize {
+		TEN_TO_FOURTEEN.with(|v| v.borrow().len())
+	}
+	fn min_len() -> usize { 0 }
+}
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: u64 = 1;
+	pub const SpendPeriod: u64 = 2;
+	pub const Burn: Permill = Permill::from_percent(50);
+	pub const TipCountdown: u64 = 1;
+	pub const TipFindersFee: Percent = Percent::from_percent(20);
+	pub const TipReportDepositBase: u64 = 1;
+	pub const TipReportDepositPerByte: u64 = 1;
+	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const BountyDepositBase: u64 = 80;
+	pub const DataDepositPerByte: u64 = 1;
+	pub const BountyDepositPayoutDelay: u64 = 3;
+	pub const BountyUpdatePeriod: u64 = 20;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: u64 = 1;
+	pub const MaximumReasonLength: u32 = 16384;
+	pub const PayoutPeriod: u64 = 5;
+	pub TreasuryAccount: u128 = Treasury::account_id();
+}
+impl pallet_treasury::Trait for Test {
+	type ModuleId = TreasuryModuleId;
+	type Currency = pallet_balances::Module<Test>;
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type RejectOrigin = frame_system::EnsureRoot<u128>;
+	type Tippers = TenToFourteen;
+	type TipCountdown = TipCountdown;
+	type TipFindersFee = TipFindersFee;
+	type TipReportDepositBase = TipReportDepositBase;
+	type TipReportDepositPerByte = TipReportDepositPerByte;
+	type Event = ();
+	type ProposalRejection = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type ParamsOrigin = frame_system::EnsureRoot<u128>;
+	type BountyDepositBase = BountyDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type MaximumReasonLength = MaximumReasonLength;
+	type ChildBountyManager = ChildBounties;
+	type AssetKind = ();
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<u128>;
+	type Paymaster = frame_support::traits::PayFromAccount<Balances, TreasuryAccount, u128>;
+	type BalanceConverter = frame_support::traits::UnityAssetBalanceConversion;
+	type SpendOrigin = frame_system::EnsureNever<u64>;
+	type PayoutPeriod = PayoutPeriod;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const MaxActiveChildBountyCount: u32 = 2;
+	pub const ChildBountyValueMinimum: u64 = 1;
+}
+impl Trait for Test {
+	type Event = ();
+	type MaxActiveChildBountyCount = MaxActiveChildBountyCount;
+	type ChildBountyValueMinimum = ChildBountyValueMinimum;
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Treasury = pallet_treasury::Module<Test>;
+type ChildBounties = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		// Total issuance will be 200 with treasury account initialized at ED.
+		balances: vec![(0, 100), (1, 98), (2, 1)],
+	}.assimilate_storage(&mut t).unwrap();
+	pallet_treasury::GenesisConfig::default().assimilate_storage::<Test>(&mut t).unwrap();
+	t.into()
+}
+
+// Create bounty 0 with a value of 50, with account 4 as its active curator.
+fn create_active_parent_bounty() {
+	System::set_block_number(1);
+	Balances::make_free_balance_be(&Treasury::account_id(), 101);
+	assert_ok!(Treasury::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+	assert_ok!(Treasury::approve_bounty(Origin::root(), 0));
+
+	System::set_block_number(2);
+	<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+	Balances::make_free_balance_be(&4, 10);
+	assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 10));
+	assert_ok!(Treasury::accept_curator(Origin::signed(4), 0));
+	assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 50);
+}
+
+#[test]
+fn add_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12".to_vec()),
+			Error::<Test>::ParentBountyNotActive
+		);
+
+		create_active_parent_bounty();
+
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(1), 0, 10, b"12".to_vec()),
+			Error::<Test>::RequireCurator
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 0, b"12".to_vec()),
+			Error::<Test>::InvalidValue
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, [0; 17_000].to_vec()),
+			Error::<Test>::ReasonTooBig
+		);
+		// The parent bounty account must be kept alive.
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 50, b"12".to_vec()),
+			Error::<Test>::InsufficientBountyBalance
+		);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12".to_vec()));
+
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap(), ChildBounty {
+			parent_bounty: 0,
+			value: 10,
+			fee: 0,
+			curator_deposit: 0,
+			status: ChildBountyStatus::Added,
+		});
+		assert_eq!(ChildBounties::child_bounty_descriptions(0).unwrap(), b"12".to_vec());
+		assert_eq!(ChildBounties::child_bounty_count(), 1);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 1);
+
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 10);
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 40);
+	});
+}
+
+#[test]
+fn add_child_bounty_respects_limit() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"1".to_vec()));
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"2".to_vec()));
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"3".to_vec()),
+			Error::<Test>::TooManyChildBounties
+		);
+
+		// Closing a child bounty makes room for a new one.
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 0));
+		assert_eq!(ChildBounties::parent_child_bounties(0), 1);
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"3".to_vec()));
+		assert_eq!(ChildBounties::parent_child_bounties(0), 2);
+	});
+}
+
+#[test]
+fn award_and_claim_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12".to_vec()));
+
+		assert_noop!(
+			ChildBounties::propose_curator(Origin::signed(1), 0, 0, 8, 2),
+			Error::<Test>::RequireCurator
+		);
+		assert_noop!(
+			ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 10),
+			Error::<Test>::InvalidFee
+		);
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+
+		assert_noop!(
+			ChildBounties::accept_curator(Origin::signed(1), 0, 0),
+			Error::<Test>::RequireCurator
+		);
+		Balances::make_free_balance_be(&8, 10);
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap(), ChildBounty {
+			parent_bounty: 0,
+			value: 10,
+			fee: 2,
+			curator_deposit: 1,
+			status: ChildBountyStatus::Active { curator: 8 },
+		});
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 1);
+
+		assert_noop!(
+			ChildBounties::award_child_bounty(Origin::signed(4), 0, 0, 9),
+			Error::<Test>::RequireCurator
+		);
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 9));
+
+		assert_eq!(
+			ChildBounties::child_bounties(0, 0).unwrap().status,
+			ChildBountyStatus::PendingPayout { curator: 8, beneficiary: 9, unlock_at: 5 },
+		);
+
+		assert_noop!(
+			ChildBounties::claim_child_bounty(Origin::signed(1), 0, 0),
+			Error::<Test>::Premature
+		);
+
+		System::set_block_number(5);
+		assert_ok!(ChildBounties::claim_child_bounty(Origin::signed(1), 0, 0));
+
+		assert_eq!(Balances::free_balance(8), 12); // initial 10 + fee 2
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(Balances::free_balance(9), 8);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 0);
+
+		assert_eq!(ChildBounties::child_bounties(0, 0), None);
+		assert_eq!(ChildBounties::child_bounty_descriptions(0), None);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+	});
+}
+
+#[test]
+fn unassign_child_curator_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12".to_vec()));
+
+		assert_noop!(
+			ChildBounties::unassign_curator(Origin::root(), 0, 0),
+			Error::<Test>::UnexpectedStatus
+		);
+
+		Balances::make_free_balance_be(&8, 10);
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 4));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+
+		// Anyone else cannot unassign the child curator.
+		assert_noop!(ChildBounties::unassign_curator(Origin::signed(1), 0, 0), BadOrigin);
+
+		// The child curator giving up the role gets the deposit back.
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(8), 0, 0));
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().status, ChildBountyStatus::Added);
+		assert_eq!(Balances::free_balance(8), 10);
+		assert_eq!(Balances::reserved_balance(8), 0);
+
+		// The parent curator slashes the child curator.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 4));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(4), 0, 0));
+		assert_eq!(Balances::free_balance(8), 8);
+		assert_eq!(Balances::reserved_balance(8), 0);
+
+		// The reject origin slashes the child curator, even with the payout pending.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 4));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 9));
+		assert_noop!(ChildBounties::unassign_curator(Origin::signed(8), 0, 0), BadOrigin);
+		assert_ok!(ChildBounties::unassign_curator(Origin::root(), 0, 0));
+
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap(), ChildBounty {
+			parent_bounty: 0,
+			value: 10,
+			fee: 4,
+			curator_deposit: 0,
+			status: ChildBountyStatus::Added,
+		});
+		assert_eq!(Balances::free_balance(8), 6);
+		assert_eq!(Balances::reserved_balance(8), 0);
+	});
+}
+
+#[test]
+fn close_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12".to_vec()));
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 40);
+
+		assert_noop!(ChildBounties::close_child_bounty(Origin::signed(1), 0, 0), BadOrigin);
+
+		Balances::make_free_balance_be(&8, 10);
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 4));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 9));
+
+		assert_noop!(
+			ChildBounties::close_child_bounty(Origin::root(), 0, 0),
+			Error::<Test>::PendingPayout
+		);
+
+		// Slash the child curator, then close.
+		assert_ok!(ChildBounties::unassign_curator(Origin::root(), 0, 0));
+		assert_ok!(ChildBounties::close_child_bounty(Origin::root(), 0, 0));
+
+		assert_eq!(ChildBounties::child_bounties(0, 0), None);
+		assert_eq!(ChildBounties::child_bounty_descriptions(0), None);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 0);
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 50);
+
+		// An active child curator gets the deposit back when the child bounty is closed.
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12".to_vec()));
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 1, 8, 4));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 1));
+		assert_eq!(Balances::reserved_balance(8), 2);
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 1));
+		assert_eq!(Balances::free_balance(8), 8);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 50);
+	});
+}
+
+#[test]
+fn parent_bounty_waits_for_child_bounties() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12".to_vec()));
+
+		assert_noop!(
+			Treasury::award_bounty(Origin::signed(4), 0, 3),
+			TreasuryError::<Test>::HasActiveChildBounty
+		);
+		assert_noop!(
+			Treasury::close_bounty(Origin::root(), 0),
+			TreasuryError::<Test>::HasActiveChildBounty
+		);
+
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 0));
+		assert_ok!(Treasury::award_bounty(Origin::signed(4), 0, 3));
+	});
+}
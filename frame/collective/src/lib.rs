@@ -367,6 +367,12 @@ decl_module! {
 	pub struct Module<T: Trait<I>, I: Instance=DefaultInstance> for enum Call where origin: <T as frame_system::Trait>::Origin {
 		type Error = Error<T, I>;
 
+		/// The time-out for council motions.
+		const MotionDuration: T::BlockNumber = T::MotionDuration::get();
+
+		/// Maximum number of proposals allowed to be active in parallel.
+		const MaxProposals: u32 = T::MaxProposals::get();
+
 		fn deposit_event() = default;
 
 		/// Set the collective's membership.
@@ -0,0 +1,24 @@
+(module
+	(import "env" "ext_deposit_event" (func $ext_deposit_event (param i32 i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $ext_deposit_event
+			(i32.const 0)  ;; The topics buffer
+			(i32.const 65) ;; The topics buffer's length
+			(i32.const 80) ;; The data buffer
+			(i32.const 4)  ;; The data buffer's length
+		)
+	)
+
+	;; Two topics encoded as `Vec<H256>`: `[1; 32]` and `[2; 32]`.
+	(data (i32.const 0) "\08"
+		"\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01"
+		"\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01"
+		"\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02"
+		"\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02"
+	)
+	(data (i32.const 80) "\01\02\03\04")
+)
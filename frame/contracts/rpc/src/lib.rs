@@ -19,18 +19,22 @@
 
 use std::sync::Arc;
 
-use codec::Codec;
-use jsonrpc_core::{Error, ErrorCode, Result};
+use codec::{Codec, Decode};
+use futures::{StreamExt as _, TryStreamExt as _};
+use jsonrpc_core::{Error, ErrorCode, Result, futures::{Future, Sink}};
 use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, manager::SubscriptionManager};
+use log::warn;
 use pallet_contracts_primitives::RentProjection;
+use sc_client_api::BlockchainEvents;
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_core::{Bytes, H256};
+use sp_core::{Bytes, H256, hashing::{blake2_128, twox_128}, storage::StorageKey};
 use sp_rpc::number;
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block as BlockT, Header as HeaderT},
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
 };
 use std::convert::TryInto;
 
@@ -120,6 +124,19 @@ impl From<ContractExecResult> for RpcContractExecResult {
 	}
 }
 
+/// A notification about events emitted by contracts in an imported block.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct ContractEventsNotification<BlockHash> {
+	/// The hash of the block the events were emitted in.
+	pub block: BlockHash,
+	/// The topic the events were emitted with.
+	pub topic: H256,
+	/// Indices of the matching records in `System::Events` of that block.
+	pub indices: Vec<u32>,
+}
+
 /// Contracts RPC methods.
 #[rpc]
 pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance> {
@@ -160,6 +177,36 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance> {
 	) -> Result<Option<BlockNumber>>;
 }
 
+/// Contracts events RPC methods.
+#[rpc]
+pub trait ContractsEventsApi<BlockHash> {
+	/// RPC metadata
+	type Metadata;
+
+	/// Subscribe to events emitted by contracts with any of the given `topics`.
+	///
+	/// A notification is sent for every imported block and topic that has at least one such
+	/// event. The events themselves can be fetched from `System::Events` of that block.
+	///
+	/// The subscription is served from `System::EventTopics` and therefore also picks up events
+	/// of other modules that deposit the same topics.
+	#[pubsub(subscription = "contracts_events", subscribe, name = "contracts_subscribeEvents")]
+	fn subscribe_events(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ContractEventsNotification<BlockHash>>,
+		topics: Vec<H256>,
+	);
+
+	/// Unsubscribe from contract events subscription.
+	#[pubsub(subscription = "contracts_events", unsubscribe, name = "contracts_unsubscribeEvents")]
+	fn unsubscribe_events(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+}
+
 /// An implementation of contract specific RPC methods.
 pub struct Contracts<C, B> {
 	client: Arc<C>,
@@ -280,6 +327,103 @@ where
 	}
 }
 
+/// An implementation of the contract events subscription.
+pub struct ContractsEvents<C, B, M> {
+	client: Arc<C>,
+	subscriptions: SubscriptionManager,
+	_marker: std::marker::PhantomData<(B, M)>,
+}
+
+impl<C, B, M> ContractsEvents<C, B, M> {
+	/// Create new `ContractsEvents` with the given reference to the client.
+	pub fn new(client: Arc<C>, subscriptions: SubscriptionManager) -> Self {
+		ContractsEvents {
+			client,
+			subscriptions,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block, M> ContractsEventsApi<<Block as BlockT>::Hash> for ContractsEvents<C, Block, M>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + BlockchainEvents<Block>,
+	M: jsonrpc_pubsub::PubSubMetadata + Send + Sync + 'static,
+{
+	type Metadata = M;
+
+	fn subscribe_events(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<ContractEventsNotification<<Block as BlockT>::Hash>>,
+		topics: Vec<H256>,
+	) {
+		let keys = topics.iter().map(|topic| event_topics_key(topic)).collect::<Vec<_>>();
+		let stream = match self.client.storage_changes_notification_stream(Some(&keys), None) {
+			Ok(stream) => stream,
+			Err(err) => {
+				let _ = subscriber.reject(Error {
+					code: ErrorCode::ServerError(RUNTIME_ERROR),
+					message: "Failed to subscribe to storage changes".into(),
+					data: Some(format!("{:?}", err).into()),
+				});
+				return;
+			},
+		};
+
+		self.subscriptions.add(subscriber, |sink| {
+			let stream = stream
+				.map(move |(block, changes)| {
+					changes.iter()
+						.filter_map(|(child, key, value)| {
+							if child.is_some() {
+								return None;
+							}
+							let topic = topics.iter().find(|t| event_topics_key(t) == *key)?;
+							// `EventTopics` is cleared at the start of every block, so whatever
+							// is stored there now was emitted in this block.
+							let entries = <Vec<(NumberFor<Block>, u32)>>::decode(&mut &value?.0[..])
+								.ok()?;
+							Some(ContractEventsNotification {
+								block,
+								topic: *topic,
+								indices: entries.into_iter().map(|(_, index)| index).collect(),
+							})
+						})
+						.collect::<Vec<_>>()
+				})
+				.map(futures::stream::iter)
+				.flatten()
+				.map(|notification| Ok::<_, ()>(Ok(notification)))
+				.compat();
+
+			sink
+				.sink_map_err(|e| warn!("Error sending notifications: {:?}", e))
+				.send_all(stream)
+				// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_events(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
+
+/// The storage key of `System::EventTopics` for the given `topic`.
+fn event_topics_key(topic: &H256) -> StorageKey {
+	let mut key = twox_128(b"System").to_vec();
+	key.extend_from_slice(&twox_128(b"EventTopics"));
+	key.extend_from_slice(&blake2_128(topic.as_bytes()));
+	key.extend_from_slice(topic.as_bytes());
+	StorageKey(key)
+}
+
 /// Converts a runtime trap into an RPC error.
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
 	Error {
@@ -319,4 +463,20 @@ mod tests {
 		test(r#"{"success":{"flags":5,"data":"0x1234","gas_consumed":5000}}"#);
 		test(r#"{"error":null}"#);
 	}
+
+	#[test]
+	fn events_notification_should_serialize_properly() {
+		let notification = ContractEventsNotification {
+			block: H256::repeat_byte(1),
+			topic: H256::repeat_byte(2),
+			indices: vec![3, 5],
+		};
+		let json = serde_json::to_string(&notification).unwrap();
+		assert_eq!(json, format!(
+			r#"{{"block":"0x{}","topic":"0x{}","indices":[3,5]}}"#,
+			"01".repeat(32),
+			"02".repeat(32),
+		));
+		assert_eq!(serde_json::from_str::<ContractEventsNotification<H256>>(&json).unwrap(), notification);
+	}
 }
@@ -21,7 +21,7 @@ use bitflags::bitflags;
 use sp_std::prelude::*;
 use sp_runtime::traits::{Bounded, Zero, Convert, Saturating};
 use frame_support::{
-	dispatch::DispatchError,
+	dispatch::{DispatchError, DispatchResultWithPostInfo, Dispatchable},
 	traits::{ExistenceRequirement, Currency, Time, Randomness},
 	weights::Weight,
 	ensure, StorageMap,
@@ -44,6 +44,20 @@ bitflags! {
 	}
 }
 
+bitflags! {
+	/// Flags used by a contract to customize the behaviour of a call to another contract.
+	pub struct CallFlags: u32 {
+		/// Forward the input of the current contract to the callee instead of reading the
+		/// input from the sandbox memory. The input can't be read by the caller afterwards.
+		const FORWARD_INPUT = 0x0000_0001;
+		/// Same as `FORWARD_INPUT` but the input stays readable by the caller.
+		const CLONE_INPUT = 0x0000_0002;
+		/// Allow the callee to re-enter a contract which is already on the call stack,
+		/// including the caller itself.
+		const ALLOW_REENTRY = 0x0000_0004;
+	}
+}
+
 /// Describes whether we deal with a contract or a plain account.
 pub enum TransactorKind {
 	/// Transaction was initiated from a plain account. That can be either be through a
@@ -123,14 +137,23 @@ pub trait Ext {
 	) -> Result<(), DispatchError>;
 
 	/// Call (possibly transferring some amount of funds) into the specified account.
+	///
+	/// Unless `allow_reentry` is set the call fails if `to` is a contract which is already
+	/// present on the call stack.
 	fn call(
 		&mut self,
 		to: &AccountIdOf<Self::T>,
 		value: BalanceOf<Self::T>,
 		gas_meter: &mut GasMeter<Self::T>,
 		input_data: Vec<u8>,
+		allow_reentry: bool,
 	) -> ExecResult;
 
+	/// Dispatch a runtime call with the current contract as signed origin.
+	///
+	/// All storage changes made by the call are reverted if it returns an error.
+	fn call_runtime(&mut self, call: <Self::T as Trait>::Call) -> DispatchResultWithPostInfo;
+
 	/// Restores the given destination contract sacrificing the current one.
 	///
 	/// Since this function removes the self contract eagerly, if succeeded, no further actions should
@@ -204,6 +227,14 @@ pub trait Loader<T: Trait> {
 	/// Load the main portion of the code specified by the `code_hash`. This executable
 	/// is called for each call to a contract.
 	fn load_main(&self, code_hash: &CodeHash<T>) -> Result<Self::Executable, &'static str>;
+	/// Record that a new contract uses the code specified by the `code_hash`.
+	///
+	/// Returns `Err` if there is no such code.
+	fn add_user(&self, code_hash: &CodeHash<T>) -> Result<(), &'static str>;
+	/// Record that a contract using the code specified by the `code_hash` was removed.
+	///
+	/// The code is removed once it has no users left.
+	fn remove_user(&self, code_hash: &CodeHash<T>);
 }
 
 /// A trait that represent a virtual machine.
@@ -403,6 +434,7 @@ where
 					.expect("the nested context always has to have self_trie_id"),
 				code_hash.clone()
 			)?;
+			nested.loader.add_user(&code_hash)?;
 
 			// Send funds unconditionally here. If the `endowment` is below existential_deposit
 			// then error will be returned here.
@@ -699,7 +731,11 @@ where
 				a contract has a trie id;\
 				this can't be None; qed",
 		);
+		let code_hash = storage::code_hash::<T>(&self_id).map_err(|_| {
+			DispatchError::Other("Cannot terminate a contract that is not alive")
+		})?;
 		storage::destroy_contract::<T>(&self_id, self_trie_id);
+		self.ctx.loader.remove_user(&code_hash);
 		Ok(())
 	}
 
@@ -709,10 +745,27 @@ where
 		value: BalanceOf<T>,
 		gas_meter: &mut GasMeter<T>,
 		input_data: Vec<u8>,
+		allow_reentry: bool,
 	) -> ExecResult {
+		if !allow_reentry && self.ctx.is_live(to) {
+			Err(Error::<T>::ReentranceDenied)?
+		}
 		self.ctx.call(to.clone(), value, gas_meter, input_data)
 	}
 
+	fn call_runtime(&mut self, call: <T as Trait>::Call) -> DispatchResultWithPostInfo {
+		use frame_support::storage::{with_transaction, TransactionOutcome::*};
+		let origin = frame_system::RawOrigin::Signed(self.ctx.self_account.clone()).into();
+		with_transaction(|| {
+			let result = call.dispatch(origin);
+			if result.is_ok() {
+				Commit(result)
+			} else {
+				Rollback(result)
+			}
+		})
+	}
+
 	fn restore_to(
 		&mut self,
 		dest: AccountIdOf<Self::T>,
@@ -784,7 +837,7 @@ where
 	fn deposit_event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
 		deposit_event::<Self::T>(
 			topics,
-			RawEvent::ContractExecution(self.ctx.self_account.clone(), data)
+			RawEvent::ContractEmitted(self.ctx.self_account.clone(), data)
 		);
 	}
 
@@ -926,6 +979,13 @@ mod tests {
 				.cloned()
 				.ok_or_else(|| "code not found")
 		}
+		fn add_user(&self, code_hash: &CodeHash<Test>) -> Result<(), &'static str> {
+			self.map
+				.get(code_hash)
+				.map(|_| ())
+				.ok_or_else(|| "code not found")
+		}
+		fn remove_user(&self, _code_hash: &CodeHash<Test>) {}
 	}
 
 	impl<'a> Vm<Test> for MockVm<'a> {
@@ -1326,7 +1386,7 @@ mod tests {
 		let mut loader = MockLoader::empty();
 		let recurse_ch = loader.insert(|ctx| {
 			// Try to call into yourself.
-			let r = ctx.ext.call(&BOB, 0, ctx.gas_meter, vec![]);
+			let r = ctx.ext.call(&BOB, 0, ctx.gas_meter, vec![], true);
 
 			let mut reached_bottom = reached_bottom.borrow_mut();
 			if !*reached_bottom {
@@ -1362,6 +1422,44 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn reentrance_requires_flag() {
+		let vm = MockVm::new();
+		let reentered = RefCell::new(false);
+
+		let mut loader = MockLoader::empty();
+		let recurse_ch = loader.insert(|ctx| {
+			if *reentered.borrow() {
+				return exec_success();
+			}
+			// Calling into yourself is denied unless reentry is allowed.
+			assert_matches!(
+				ctx.ext.call(&BOB, 0, ctx.gas_meter, vec![], false),
+				Err(DispatchError::Module { message: Some("ReentranceDenied"), .. })
+			);
+			*reentered.borrow_mut() = true;
+			assert_matches!(ctx.ext.call(&BOB, 0, ctx.gas_meter, vec![], true), Ok(_));
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let cfg = Config::preload();
+			let mut ctx = ExecutionContext::top_level(ALICE, &cfg, &vm, &loader);
+			set_balance(&BOB, 1);
+			place_contract(&BOB, recurse_ch);
+
+			let result = ctx.call(
+				BOB,
+				0,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				vec![],
+			);
+
+			assert_matches!(result, Ok(_));
+		});
+		assert!(*reentered.borrow());
+	}
+
 	#[test]
 	fn caller_returns_proper_values() {
 		let origin = ALICE;
@@ -1379,7 +1477,7 @@ mod tests {
 
 			// Call into CHARLIE contract.
 			assert_matches!(
-				ctx.ext.call(&CHARLIE, 0, ctx.gas_meter, vec![]),
+				ctx.ext.call(&CHARLIE, 0, ctx.gas_meter, vec![], true),
 				Ok(_)
 			);
 			exec_success()
@@ -1422,7 +1520,7 @@ mod tests {
 
 			// Call into charlie contract.
 			assert_matches!(
-				ctx.ext.call(&CHARLIE, 0, ctx.gas_meter, vec![]),
+				ctx.ext.call(&CHARLIE, 0, ctx.gas_meter, vec![], true),
 				Ok(_)
 			);
 			exec_success()
@@ -29,7 +29,9 @@
 //!
 //! The smart-contract code is stored once in a `code_cache`, and later retrievable via its `code_hash`.
 //! This means that multiple smart-contracts can be instantiated from the same `code_cache`, without replicating
-//! the code each time.
+//! the code each time. The code cache keeps a reference count of the contracts (including tombstones)
+//! using each code. When the last contract using a code is removed the code is deleted and the
+//! deposit paid upon upload is refunded to the uploader.
 //!
 //! When a smart-contract is called, its associated code is retrieved via the code hash and gets executed.
 //! This call can alter the storage entries of the smart-contract account, instantiate new smart-contracts,
@@ -58,7 +60,9 @@
 //!
 //! ### Dispatchable functions
 //!
-//! * `put_code` - Stores the given binary Wasm code into the chain's storage and returns its `code_hash`.
+//! * `upload_code` - Stores the given binary Wasm code into the chain's storage and returns its `code_hash`.
+//! A deposit proportional to the code size is reserved from the uploader.
+//! * `remove_code` - Removes code which is not used by any contract and refunds the deposit.
 //! * `instantiate` - Deploys a new contract from the given `code_hash`, optionally transferring some balance.
 //! This instantiates a new smart contract account and calls its contract deploy handler to
 //! initialize the contract.
@@ -109,8 +113,10 @@ use sp_runtime::{
 use frame_support::{
 	decl_module, decl_event, decl_storage, decl_error,
 	parameter_types, storage::child::ChildInfo,
-	dispatch::{DispatchResult, DispatchResultWithPostInfo},
-	traits::{OnUnbalanced, Currency, Get, Time, Randomness},
+	dispatch::{DispatchResult, DispatchResultWithPostInfo, Dispatchable},
+	traits::{OnUnbalanced, Currency, ReservableCurrency, Get, Time, Randomness, Filter},
+	weights::{GetDispatchInfo, PostDispatchInfo},
+	Parameter,
 };
 use frame_system::{ensure_signed, ensure_root};
 use pallet_contracts_primitives::{RentProjection, ContractAccessError};
@@ -245,6 +251,19 @@ where
 	}
 }
 
+pub type OwnerInfo<T> = RawOwnerInfo<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+
+/// Information about the uploader of a code and the contracts using it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RawOwnerInfo<AccountId, Balance> {
+	/// The account that uploaded the code and paid the deposit.
+	pub owner: AccountId,
+	/// The amount reserved from the owner for storing the code.
+	pub deposit: Balance,
+	/// The number of contracts (alive or tombstone) that use this code.
+	pub refcount: u64,
+}
+
 impl<T: Trait> From<AliveContractInfo<T>> for ContractInfo<T> {
 	fn from(alive_info: AliveContractInfo<T>) -> Self {
 		Self::Alive(alive_info)
@@ -316,11 +335,22 @@ pub trait Trait: frame_system::Trait {
 	type Randomness: Randomness<Self::Hash>;
 
 	/// The currency in which fees are paid and contract balances are held.
-	type Currency: Currency<Self::AccountId>;
+	type Currency: ReservableCurrency<Self::AccountId>;
 
 	/// The overarching event type.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
+	/// The overarching call type which contracts can dispatch through `ext_call_runtime`.
+	type Call: Parameter
+		+ Dispatchable<PostInfo = PostDispatchInfo, Origin = <Self as frame_system::Trait>::Origin>
+		+ GetDispatchInfo;
+
+	/// Decides which runtime calls contracts are allowed to dispatch.
+	///
+	/// A contract trying to dispatch a call which doesn't pass the filter traps. Use `()` to
+	/// allow every call.
+	type CallFilter: Filter<<Self as Trait>::Call>;
+
 	/// A function type to get the contract address given the instantiator.
 	type DetermineContractAddress: ContractAddressFor<CodeHash<Self>, Self::AccountId>;
 
@@ -368,6 +398,9 @@ pub trait Trait: frame_system::Trait {
 	/// The maximum size of a storage value in bytes.
 	type MaxValueSize: Get<u32>;
 
+	/// The amount of balance reserved from the uploader per byte of uploaded code.
+	type CodeDepositPerByte: Get<BalanceOf<Self>>;
+
 	/// Used to answer contracts's queries regarding the current weight price. This is **not**
 	/// used to calculate the actual fee and is only for informational purposes.
 	type WeightPrice: Convert<Weight, BalanceOf<Self>>;
@@ -420,6 +453,64 @@ decl_error! {
 		/// for a tombstone to be created. Use `ext_terminate` to remove a contract without
 		/// leaving a tombstone behind.
 		InsufficientBalance,
+		/// The code supplied to `upload_code` is already stored.
+		CodeAlreadyExists,
+		/// No code could be found under the supplied code hash.
+		CodeNotFound,
+		/// Only the account that uploaded the code is allowed to remove it.
+		NotCodeOwner,
+		/// The code is still used by at least one contract and cannot be removed.
+		CodeInUse,
+		/// The uploaded code couldn't be decoded or is not a valid wasm module.
+		CodeInvalid,
+		/// The uploaded code must export exactly the `call` and `deploy` functions, both
+		/// with the signature `() -> ()`.
+		CodeInvalidExports,
+		/// The uploaded code imports a function which is not provided by the runtime.
+		CodeUnknownImport,
+		/// The uploaded code imports a table, a global, imports from a namespace other than
+		/// `env` or imports a debug function which is disabled.
+		CodeForbiddenImport,
+		/// The uploaded code declares an internal memory instead of importing it.
+		CodeInternalMemory,
+		/// The memory imported by the uploaded code has no maximum or its initial size
+		/// exceeds the maximum.
+		CodeInvalidMemoryLimits,
+		/// The memory imported by the uploaded code exceeds `Schedule::max_memory_pages`.
+		CodeTooMuchMemory,
+		/// The table declared by the uploaded code exceeds `Schedule::max_table_size`.
+		CodeTableTooLarge,
+		/// The uploaded code uses floating point types or instructions.
+		CodeFloatingPoint,
+		/// Gas or stack height metering could not be injected into the uploaded code.
+		CodeInstrumentationFailed,
+		/// A contract tried to call into a contract which is already on the call stack
+		/// without setting the `ALLOW_REENTRY` flag.
+		ReentranceDenied,
+		/// The input of the current contract was already read or forwarded.
+		InputAlreadyRead,
+		/// Unknown flags were passed to `ext_call_with_flags`.
+		InvalidCallFlags,
+		/// The runtime call dispatched by a contract was refused by `Trait::CallFilter`.
+		CallFiltered,
+	}
+}
+
+impl<T: Trait> From<wasm::PrepareError> for Error<T> {
+	fn from(err: wasm::PrepareError) -> Self {
+		use wasm::PrepareError::*;
+		match err {
+			InvalidModule => Error::<T>::CodeInvalid,
+			InvalidExports => Error::<T>::CodeInvalidExports,
+			UnknownImport => Error::<T>::CodeUnknownImport,
+			ForbiddenImport => Error::<T>::CodeForbiddenImport,
+			InternalMemoryDeclared => Error::<T>::CodeInternalMemory,
+			InvalidMemoryLimits => Error::<T>::CodeInvalidMemoryLimits,
+			MemoryLimitExceeded => Error::<T>::CodeTooMuchMemory,
+			TableTooLarge => Error::<T>::CodeTableTooLarge,
+			FloatingPointUsed => Error::<T>::CodeFloatingPoint,
+			InstrumentationFailed => Error::<T>::CodeInstrumentationFailed,
+		}
 	}
 }
 
@@ -468,6 +559,9 @@ decl_module! {
 		/// The maximum size of a storage value in bytes. A reasonable default is 16 KiB.
 		const MaxValueSize: u32 = T::MaxValueSize::get();
 
+		/// The amount of balance reserved from the uploader per byte of uploaded code.
+		const CodeDepositPerByte: BalanceOf<T> = T::CodeDepositPerByte::get();
+
 		fn deposit_event() = default;
 
 		/// Updates the schedule for metering contracts.
@@ -488,18 +582,30 @@ decl_module! {
 
 		/// Stores the given binary Wasm code into the chain's storage and returns its `codehash`.
 		/// You can instantiate contracts only with stored code.
+		///
+		/// A deposit of `CodeDepositPerByte` for each byte of `code` is reserved from the origin.
+		/// It is refunded once the code is removed, either by `remove_code` or because the last
+		/// contract using it was removed.
 		#[weight = Module::<T>::calc_code_put_costs(&code)]
-		pub fn put_code(
+		pub fn upload_code(
 			origin,
 			code: Vec<u8>
 		) -> DispatchResult {
-			ensure_signed(origin)?;
+			let origin = ensure_signed(origin)?;
 			let schedule = <Module<T>>::current_schedule();
-			let result = wasm::save_code::<T>(code, &schedule);
-			if let Ok(code_hash) = result {
-				Self::deposit_event(RawEvent::CodeStored(code_hash));
-			}
-			result.map(|_| ()).map_err(Into::into)
+			let code_hash = wasm::save_code::<T>(code, origin, &schedule)?;
+			Self::deposit_event(RawEvent::CodeStored(code_hash));
+			Ok(())
+		}
+
+		/// Removes the code stored under `code_hash` and refunds the deposit to its owner.
+		///
+		/// Only the account that uploaded the code can remove it and only while no contract
+		/// uses it.
+		#[weight = T::DbWeight::get().reads_writes(2, 4)]
+		pub fn remove_code(origin, code_hash: CodeHash<T>) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			wasm::remove_code::<T>(&origin, code_hash)
 		}
 
 		/// Makes a call to an account, optionally transferring some balance.
@@ -527,7 +633,7 @@ decl_module! {
 			gas_meter.into_dispatch_result(result)
 		}
 
-		/// Instantiates a new contract from the `codehash` generated by `put_code`, optionally transferring some balance.
+		/// Instantiates a new contract from the `codehash` generated by `upload_code`, optionally transferring some balance.
 		///
 		/// Instantiation is executed as follows:
 		///
@@ -684,11 +790,17 @@ decl_event! {
 		/// Code with the specified hash has been stored.
 		CodeStored(Hash),
 
+		/// Code with the specified hash has been removed and its deposit refunded.
+		CodeRemoved(Hash),
+
 		/// Triggered when the current schedule is updated.
 		ScheduleUpdated(u32),
 
-		/// An event deposited upon execution of a contract from the account.
-		ContractExecution(AccountId, Vec<u8>),
+		/// A custom event emitted by the contract.
+		///
+		/// The topics the contract supplied along with the event are stored in the event
+		/// record and indexed in `System::EventTopics`.
+		ContractEmitted(AccountId, Vec<u8>),
 	}
 }
 
@@ -700,6 +812,8 @@ decl_storage! {
 		pub PristineCode: map hasher(identity) CodeHash<T> => Option<Vec<u8>>;
 		/// A mapping between an original code hash and instrumented wasm code, ready for execution.
 		pub CodeStorage: map hasher(identity) CodeHash<T> => Option<wasm::PrefabWasmModule>;
+		/// A mapping between an original code hash and its owner and reference count.
+		pub OwnerInfoOf: map hasher(identity) CodeHash<T> => Option<OwnerInfo<T>>;
 		/// The subtrie counter.
 		pub AccountCounter: u64 = 0;
 		/// The code associated with a given account.
@@ -230,6 +230,7 @@ fn enact_verdict<T: Trait>(
 			child::kill_storage(
 				&alive_contract_info.child_trie_info(),
 			);
+			crate::wasm::decrement_refcount::<T>(&alive_contract_info.code_hash);
 			<Module<T>>::deposit_event(RawEvent::Evicted(account.clone(), false));
 			None
 		}
@@ -466,6 +467,10 @@ pub fn restore_to<T: Trait>(
 		.map(|(_, value)| value.len() as u32)
 		.sum::<u32>();
 
+	// The tombstone keeps its reference to the code, so only the reference held by the
+	// origin contract is released here.
+	crate::wasm::decrement_refcount::<T>(&origin_contract.code_hash);
+
 	<ContractInfoOf<T>>::remove(&origin);
 	<ContractInfoOf<T>>::insert(&dest, ContractInfo::Alive(AliveContractInfo::<T> {
 		trie_id: origin_contract.trie_id,
@@ -17,7 +17,7 @@
 use crate::{
 	BalanceOf, ContractAddressFor, ContractInfo, ContractInfoOf, GenesisConfig, Module,
 	RawAliveContractInfo, RawEvent, Trait, TrieId, Schedule, TrieIdGenerator, gas::Gas,
-	Error,
+	Error, PristineCode, CodeStorage, OwnerInfoOf,
 };
 use assert_matches::assert_matches;
 use hex_literal::*;
@@ -28,7 +28,7 @@ use sp_runtime::{
 	testing::{Header, H256},
 };
 use frame_support::{
-	assert_ok, assert_err_ignore_postinfo, impl_outer_dispatch, impl_outer_event,
+	assert_ok, assert_err, assert_err_ignore_postinfo, impl_outer_dispatch, impl_outer_event,
 	impl_outer_origin, parameter_types, StorageMap, StorageValue,
 	traits::{Currency, Get},
 	weights::{Weight, PostDispatchInfo},
@@ -93,6 +93,7 @@ pub mod test_utils {
 
 thread_local! {
 	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(0);
+	static CODE_DEPOSIT_PER_BYTE: RefCell<u64> = RefCell::new(0);
 }
 
 pub struct ExistentialDeposit;
@@ -100,6 +101,11 @@ impl Get<u64> for ExistentialDeposit {
 	fn get() -> u64 { EXISTENTIAL_DEPOSIT.with(|v| *v.borrow()) }
 }
 
+pub struct CodeDepositPerByte;
+impl Get<u64> for CodeDepositPerByte {
+	fn get() -> u64 { CODE_DEPOSIT_PER_BYTE.with(|v| *v.borrow()) }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Test;
 parameter_types! {
@@ -142,6 +148,8 @@ impl pallet_balances::Trait for Test {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
 }
 parameter_types! {
 	pub const MinimumPeriod: u64 = 1;
@@ -179,6 +187,8 @@ impl Trait for Test {
 	type Currency = Balances;
 	type DetermineContractAddress = DummyContractAddressFor;
 	type Event = MetaEvent;
+	type Call = Call;
+	type CallFilter = ();
 	type TrieIdGenerator = DummyTrieIdGenerator;
 	type RentPayment = ();
 	type SignedClaimHandicap = SignedClaimHandicap;
@@ -189,6 +199,7 @@ impl Trait for Test {
 	type SurchargeReward = SurchargeReward;
 	type MaxDepth = MaxDepth;
 	type MaxValueSize = MaxValueSize;
+	type CodeDepositPerByte = CodeDepositPerByte;
 	type WeightPrice = Self;
 }
 
@@ -229,11 +240,13 @@ const GAS_LIMIT: Gas = 10_000_000_000;
 
 pub struct ExtBuilder {
 	existential_deposit: u64,
+	code_deposit_per_byte: u64,
 }
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			existential_deposit: 1,
+			code_deposit_per_byte: 0,
 		}
 	}
 }
@@ -242,8 +255,13 @@ impl ExtBuilder {
 		self.existential_deposit = existential_deposit;
 		self
 	}
+	pub fn code_deposit_per_byte(mut self, code_deposit_per_byte: u64) -> Self {
+		self.code_deposit_per_byte = code_deposit_per_byte;
+		self
+	}
 	pub fn set_associated_consts(&self) {
 		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = self.existential_deposit);
+		CODE_DEPOSIT_PER_BYTE.with(|v| *v.borrow_mut() = self.code_deposit_per_byte);
 	}
 	pub fn build(self) -> sp_io::TestExternalities {
 		self.set_associated_consts();
@@ -386,7 +404,7 @@ fn instantiate_and_call_and_deposit_event() {
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			// Check at the end to get hash on error easily
 			let creation = Contracts::instantiate(
@@ -434,7 +452,7 @@ fn instantiate_and_call_and_deposit_event() {
 				},
 				EventRecord {
 					phase: Phase::Initialization,
-					event: MetaEvent::contracts(RawEvent::ContractExecution(BOB, vec![1, 2, 3, 4])),
+					event: MetaEvent::contracts(RawEvent::ContractEmitted(BOB, vec![1, 2, 3, 4])),
 					topics: vec![],
 				},
 				EventRecord {
@@ -449,6 +467,42 @@ fn instantiate_and_call_and_deposit_event() {
 		});
 }
 
+#[test]
+fn deposit_event_stores_topics() {
+	let (wasm, code_hash) = compile_module::<Test>("event_topics").unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100,
+				GAS_LIMIT,
+				code_hash.into(),
+				vec![],
+			));
+			assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, vec![]));
+
+			let topics = vec![H256::repeat_byte(1), H256::repeat_byte(2)];
+			let events = System::events();
+			let index = events.len() as u32 - 1;
+			assert_eq!(
+				events.last().unwrap(),
+				&EventRecord {
+					phase: Phase::Initialization,
+					event: MetaEvent::contracts(RawEvent::ContractEmitted(BOB, vec![1, 2, 3, 4])),
+					topics: topics.clone(),
+				},
+			);
+			for topic in topics {
+				assert_eq!(System::event_topics(topic), vec![(System::block_number(), index)]);
+			}
+		});
+}
+
 #[test]
 fn run_out_of_gas() {
 	let (wasm, code_hash) = compile_module::<Test>("run_out_of_gas").unwrap();
@@ -459,7 +513,7 @@ fn run_out_of_gas() {
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
@@ -508,7 +562,7 @@ fn test_set_rent_code_and_hash() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			// If you ever need to update the wasm source this test will fail
 			// and will show you the actual hash.
@@ -543,7 +597,7 @@ fn storage_size() {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				30_000,
@@ -626,7 +680,7 @@ fn empty_kv_pairs() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				30_000,
@@ -674,7 +728,7 @@ fn deduct_blocks() {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				30_000,
@@ -771,7 +825,7 @@ fn claim_surcharge(blocks: u64, trigger_call: impl Fn() -> bool, removes: bool)
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				100,
@@ -808,7 +862,7 @@ fn removals(trigger_call: impl Fn() -> bool) {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm.clone()));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm.clone()));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				100,
@@ -847,7 +901,7 @@ fn removals(trigger_call: impl Fn() -> bool) {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm.clone()));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm.clone()));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				1_000,
@@ -901,7 +955,7 @@ fn removals(trigger_call: impl Fn() -> bool) {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 			let subsistence_threshold =
 				Balances::minimum_balance() + <Test as Trait>::TombstoneDeposit::get();
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm.clone()));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm.clone()));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				50 + subsistence_threshold,
@@ -972,7 +1026,7 @@ fn call_removed_contract() {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm.clone()));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm.clone()));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				100,
@@ -1018,7 +1072,7 @@ fn default_rent_allowance_on_instantiate() {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				30_000,
@@ -1072,8 +1126,8 @@ fn restoration(test_different_storage: bool, test_restore_to_with_dirty_storage:
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), restoration_wasm));
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), set_rent_wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), restoration_wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), set_rent_wasm));
 
 			// If you ever need to update the wasm source this test will fail
 			// and will show you the actual hash.
@@ -1280,7 +1334,7 @@ fn storage_max_value_limit() {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
 				30_000,
@@ -1327,8 +1381,8 @@ fn deploy_and_call_other_contract() {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), callee_wasm));
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), caller_wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), callee_wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), caller_wasm));
 
 			assert_ok!(Contracts::instantiate(
 				Origin::signed(ALICE),
@@ -1358,7 +1412,7 @@ fn cannot_self_destruct_through_draning() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			// Instantiate the BOB contract.
 			assert_ok!(Contracts::instantiate(
@@ -1398,7 +1452,7 @@ fn cannot_self_destruct_while_live() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			// Instantiate the BOB contract.
 			assert_ok!(Contracts::instantiate(
@@ -1444,7 +1498,7 @@ fn self_destruct_works() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			// Instantiate the BOB contract.
 			assert_ok!(Contracts::instantiate(
@@ -1481,6 +1535,171 @@ fn self_destruct_works() {
 		});
 }
 
+#[test]
+fn upload_code_reserves_deposit_and_remove_code_refunds_it() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
+	let deposit = 2 * wasm.len() as u64;
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.code_deposit_per_byte(2)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			let _ = Balances::deposit_creating(&BOB, 1_000_000);
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm.clone()));
+			assert_eq!(Balances::reserved_balance(ALICE), deposit);
+
+			// The same code cannot be stored twice.
+			assert_err!(
+				Contracts::upload_code(Origin::signed(BOB), wasm),
+				Error::<Test>::CodeAlreadyExists,
+			);
+
+			// Only the owner can remove the code.
+			assert_err!(
+				Contracts::remove_code(Origin::signed(BOB), code_hash.into()),
+				Error::<Test>::NotCodeOwner,
+			);
+			assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash.into()));
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+			assert!(!PristineCode::<Test>::contains_key(&code_hash));
+			assert!(!CodeStorage::<Test>::contains_key(&code_hash));
+			assert!(!OwnerInfoOf::<Test>::contains_key(&code_hash));
+
+			assert_err!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash.into()),
+				Error::<Test>::CodeNotFound,
+			);
+		});
+}
+
+#[test]
+fn upload_code_refuses_invalid_modules() {
+	let upload = |wat: &str| {
+		let wasm = wabt::Wat2Wasm::new().validate(false).convert(wat).unwrap();
+		Contracts::upload_code(Origin::signed(ALICE), wasm.as_ref().to_vec())
+	};
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		assert_err!(
+			upload(r#"
+				(module
+					(func (export "call") (drop (f32.add (f32.const 0) (f32.const 1))))
+					(func (export "deploy"))
+				)
+			"#),
+			Error::<Test>::CodeFloatingPoint,
+		);
+		assert_err!(
+			upload(r#"
+				(module
+					(import "env" "ext_does_not_exist" (func))
+					(func (export "call"))
+					(func (export "deploy"))
+				)
+			"#),
+			Error::<Test>::CodeUnknownImport,
+		);
+		assert_err!(
+			upload(r#"
+				(module
+					(import "env" "memory" (memory 1 17))
+					(func (export "call"))
+					(func (export "deploy"))
+				)
+			"#),
+			Error::<Test>::CodeTooMuchMemory,
+		);
+		assert_err!(
+			upload(r#"
+				(module
+					(func (export "call"))
+				)
+			"#),
+			Error::<Test>::CodeInvalidExports,
+		);
+
+		// Nothing was stored and no deposit was taken.
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn code_is_removed_when_last_contract_terminates() {
+	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
+	let deposit = 2 * wasm.len() as u64;
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.code_deposit_per_byte(2)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100_000,
+				GAS_LIMIT,
+				code_hash.into(),
+				vec![],
+			));
+			assert_eq!(OwnerInfoOf::<Test>::get(&code_hash).unwrap().refcount, 1);
+
+			// Code in use cannot be removed.
+			assert_err!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash.into()),
+				Error::<Test>::CodeInUse,
+			);
+			assert_eq!(Balances::reserved_balance(ALICE), deposit);
+
+			// Call BOB without input data which triggers termination.
+			assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, vec![]));
+
+			// The code went away together with its only user and the deposit is refunded.
+			assert!(ContractInfoOf::<Test>::get(BOB).is_none());
+			assert!(!OwnerInfoOf::<Test>::contains_key(&code_hash));
+			assert!(!PristineCode::<Test>::contains_key(&code_hash));
+			assert_eq!(Balances::reserved_balance(ALICE), 0);
+		});
+}
+
+#[test]
+fn code_without_owner_info_is_kept() {
+	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm.clone()));
+			// Code stored before owners were recorded has no owner info.
+			OwnerInfoOf::<Test>::remove(&code_hash);
+
+			assert_ok!(Contracts::instantiate(
+				Origin::signed(ALICE),
+				100_000,
+				GAS_LIMIT,
+				code_hash.into(),
+				vec![],
+			));
+			assert_ok!(Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, vec![]));
+			assert!(ContractInfoOf::<Test>::get(BOB).is_none());
+
+			// The code is neither removed nor claimed by a new upload.
+			assert!(PristineCode::<Test>::contains_key(&code_hash));
+			assert!(CodeStorage::<Test>::contains_key(&code_hash));
+			assert_err!(
+				Contracts::upload_code(Origin::signed(ALICE), wasm),
+				Error::<Test>::CodeAlreadyExists,
+			);
+			assert_err!(
+				Contracts::remove_code(Origin::signed(ALICE), code_hash.into()),
+				Error::<Test>::CodeNotFound,
+			);
+		});
+}
+
 // This tests that one contract cannot prevent another from self-destructing by sending it
 // additional funds after it has been drained.
 #[test]
@@ -1494,8 +1713,8 @@ fn destroy_contract_and_transfer_funds() {
 		.execute_with(|| {
 			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), callee_wasm));
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), caller_wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), callee_wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), caller_wasm));
 
 			// This deploys the BOB contract, which in turn deploys the CHARLIE contract during
 			// construction.
@@ -1535,7 +1754,7 @@ fn cannot_self_destruct_in_constructor() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			// Fail to instantiate the BOB because the call that is issued in the deploy
 			// function exhausts all balances which puts it below the existential deposit.
@@ -1561,7 +1780,7 @@ fn crypto_hashes() {
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::put_code(Origin::signed(ALICE), wasm));
+			assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm));
 
 			// Instantiate the CRYPTO_HASHES contract.
 			assert_ok!(Contracts::instantiate(
@@ -27,28 +27,93 @@
 //! Thus, before executing a contract it should be reinstrument with new schedule.
 
 use crate::wasm::{prepare, runtime::Env, PrefabWasmModule};
-use crate::{CodeHash, CodeStorage, PristineCode, Schedule, Trait};
+use crate::{
+	CodeHash, CodeStorage, PristineCode, OwnerInfoOf, OwnerInfo, Schedule, Trait, Error, Module,
+	RawEvent, BalanceOf,
+};
 use sp_std::prelude::*;
-use sp_runtime::traits::Hash;
-use frame_support::StorageMap;
+use sp_runtime::{traits::{Hash, Saturating}, DispatchError, DispatchResult};
+use frame_support::{StorageMap, ensure, traits::{Get, ReservableCurrency}};
 
 /// Put code in the storage. The hash of code is used as a key and is returned
 /// as a result of this function.
 ///
-/// This function instruments the given code and caches it in the storage.
+/// This function instruments the given code and caches it in the storage. A deposit
+/// proportional to the size of the original code is reserved from the `owner`. The code is
+/// removed and the deposit refunded once the last contract using it is removed.
 pub fn save<T: Trait>(
 	original_code: Vec<u8>,
+	owner: T::AccountId,
 	schedule: &Schedule,
-) -> Result<CodeHash<T>, &'static str> {
-	let prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)?;
+) -> Result<CodeHash<T>, DispatchError> {
+	let prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)
+		.map_err(Error::<T>::from)?;
 	let code_hash = T::Hashing::hash(&original_code);
+	ensure!(!<CodeStorage<T>>::contains_key(&code_hash), Error::<T>::CodeAlreadyExists);
+
+	let deposit = T::CodeDepositPerByte::get()
+		.saturating_mul(<BalanceOf<T>>::from(original_code.len() as u32));
+	T::Currency::reserve(&owner, deposit)?;
 
 	<CodeStorage<T>>::insert(code_hash, prefab_module);
 	<PristineCode<T>>::insert(code_hash, original_code);
+	<OwnerInfoOf<T>>::insert(code_hash, OwnerInfo::<T> { owner, deposit, refcount: 0 });
 
 	Ok(code_hash)
 }
 
+/// Remove the code stored under `code_hash` on behalf of `origin`.
+///
+/// Only the owner of the code can remove it and only if no contract uses it anymore.
+pub fn remove<T: Trait>(origin: &T::AccountId, code_hash: CodeHash<T>) -> DispatchResult {
+	let owner_info = <OwnerInfoOf<T>>::get(&code_hash).ok_or(Error::<T>::CodeNotFound)?;
+	ensure!(&owner_info.owner == origin, Error::<T>::NotCodeOwner);
+	ensure!(owner_info.refcount == 0, Error::<T>::CodeInUse);
+	remove_unchecked::<T>(code_hash, owner_info);
+	Ok(())
+}
+
+/// Record that a new contract uses the code stored under `code_hash`.
+///
+/// Code stored before owners were recorded has no owner info. Such code is not reference
+/// counted and is never removed, since the contracts already using it are not counted.
+pub fn increment_refcount<T: Trait>(code_hash: &CodeHash<T>) -> Result<(), &'static str> {
+	<OwnerInfoOf<T>>::mutate(code_hash, |maybe_owner_info| {
+		match maybe_owner_info.as_mut() {
+			Some(owner_info) => owner_info.refcount = owner_info.refcount.saturating_add(1),
+			None if <CodeStorage<T>>::contains_key(code_hash) => (),
+			None => return Err("code is not found"),
+		}
+		Ok(())
+	})
+}
+
+/// Record that a contract using the code stored under `code_hash` was removed.
+///
+/// If this was the last contract using the code, the code is removed and the deposit
+/// is refunded to the owner.
+pub fn decrement_refcount<T: Trait>(code_hash: &CodeHash<T>) {
+	let unused = <OwnerInfoOf<T>>::mutate(code_hash, |maybe_owner_info| {
+		maybe_owner_info.as_mut().map(|owner_info| {
+			owner_info.refcount = owner_info.refcount.saturating_sub(1);
+			owner_info.refcount == 0
+		})
+	});
+	if let Some(true) = unused {
+		if let Some(owner_info) = <OwnerInfoOf<T>>::get(code_hash) {
+			remove_unchecked::<T>(*code_hash, owner_info);
+		}
+	}
+}
+
+fn remove_unchecked<T: Trait>(code_hash: CodeHash<T>, owner_info: OwnerInfo<T>) {
+	T::Currency::unreserve(&owner_info.owner, owner_info.deposit);
+	<CodeStorage<T>>::remove(&code_hash);
+	<PristineCode<T>>::remove(&code_hash);
+	<OwnerInfoOf<T>>::remove(&code_hash);
+	<Module<T>>::deposit_event(RawEvent::CodeRemoved(code_hash));
+}
+
 /// Load code with the given code hash.
 ///
 /// If the module was instrumented with a lower version of schedule than
@@ -68,7 +133,8 @@ pub fn load<T: Trait>(
 		// We need to re-instrument the code with the latest schedule here.
 		let original_code =
 			<PristineCode<T>>::get(code_hash).ok_or_else(|| "pristine code is not found")?;
-		prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)?;
+		prefab_module = prepare::prepare_contract::<Env>(&original_code, schedule)
+			.map_err(|_| "code can't be prepared with the current schedule")?;
 		<CodeStorage<T>>::insert(&code_hash, &prefab_module);
 	}
 	Ok(prefab_module)
@@ -35,7 +35,10 @@ mod runtime;
 use self::runtime::{to_execution_result, Runtime};
 use self::code_cache::load as load_code;
 
-pub use self::code_cache::save as save_code;
+pub use self::prepare::PrepareError;
+pub use self::code_cache::{
+	save as save_code, remove as remove_code, increment_refcount, decrement_refcount,
+};
 
 /// A prepared wasm module ready for execution.
 #[derive(Clone, Encode, Decode)]
@@ -90,6 +93,12 @@ impl<'a, T: Trait> crate::exec::Loader<T> for WasmLoader<'a> {
 			prefab_module,
 		})
 	}
+	fn add_user(&self, code_hash: &CodeHash<T>) -> Result<(), &'static str> {
+		increment_refcount::<T>(code_hash)
+	}
+	fn remove_user(&self, code_hash: &CodeHash<T>) {
+		decrement_refcount::<T>(code_hash)
+	}
 }
 
 /// Implementation of `Vm` that takes `WasmExecutable` and executes it.
@@ -161,7 +170,7 @@ mod tests {
 	use hex_literal::hex;
 	use assert_matches::assert_matches;
 	use sp_runtime::DispatchError;
-	use frame_support::weights::Weight;
+	use frame_support::{weights::Weight, dispatch::DispatchResultWithPostInfo};
 
 	const GAS_LIMIT: Gas = 10_000_000_000;
 
@@ -206,6 +215,7 @@ mod tests {
 		terminations: Vec<TerminationEntry>,
 		transfers: Vec<TransferEntry>,
 		restores: Vec<RestoreEntry>,
+		runtime_calls: Vec<DispatchEntry>,
 		// (topics, data)
 		events: Vec<(Vec<H256>, Vec<u8>)>,
 		next_account_id: u64,
@@ -264,6 +274,7 @@ mod tests {
 			value: u64,
 			gas_meter: &mut GasMeter<Test>,
 			data: Vec<u8>,
+			_allow_reentry: bool,
 		) -> ExecResult {
 			self.transfers.push(TransferEntry {
 				to: *to,
@@ -275,6 +286,10 @@ mod tests {
 			// TODO: Add tests for different call outcomes.
 			Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Vec::new() })
 		}
+		fn call_runtime(&mut self, call: Call) -> DispatchResultWithPostInfo {
+			self.runtime_calls.push(DispatchEntry(call));
+			Ok(Default::default())
+		}
 		fn terminate(
 			&mut self,
 			beneficiary: &u64,
@@ -390,8 +405,12 @@ mod tests {
 			value: u64,
 			gas_meter: &mut GasMeter<Test>,
 			input_data: Vec<u8>,
+			allow_reentry: bool,
 		) -> ExecResult {
-			(**self).call(to, value, gas_meter, input_data)
+			(**self).call(to, value, gas_meter, input_data, allow_reentry)
+		}
+		fn call_runtime(&mut self, call: Call) -> DispatchResultWithPostInfo {
+			(**self).call_runtime(call)
 		}
 		fn restore_to(
 			&mut self,
@@ -746,6 +765,100 @@ mod tests {
 )
 "#;
 
+	const CODE_CALL_FORWARD_INPUT: &str = r#"
+(module
+	;; ext_call_with_flags(
+	;;    flags: u32,
+	;;    callee_ptr: u32,
+	;;    callee_len: u32,
+	;;    gas: u64,
+	;;    value_ptr: u32,
+	;;    value_len: u32,
+	;;    input_data_ptr: u32,
+	;;    input_data_len: u32,
+	;;    output_ptr: u32,
+	;;    output_len_ptr: u32
+	;;) -> u32
+	(import "env" "ext_call_with_flags"
+		(func $ext_call_with_flags (param i32 i32 i32 i64 i32 i32 i32 i32 i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	(func (export "call")
+		(drop
+			(call $ext_call_with_flags
+				(i32.const 1)  ;; FORWARD_INPUT
+				(i32.const 4)  ;; Pointer to "callee" address.
+				(i32.const 8)  ;; Length of "callee" address.
+				(i64.const 0)  ;; How much gas to devote for the execution. 0 = all.
+				(i32.const 12) ;; Pointer to the buffer with value to transfer
+				(i32.const 8)  ;; Length of the buffer with value to transfer.
+				(i32.const 0)  ;; Ignored because the input is forwarded.
+				(i32.const 0)  ;; Ignored because the input is forwarded.
+				(i32.const 4294967295) ;; u32 max value is the sentinel value: do not copy output
+				(i32.const 0) ;; Length is ignored in this case
+			)
+		)
+	)
+	(func (export "deploy"))
+
+	;; Destination AccountId to transfer the funds.
+	;; Represented by u64 (8 bytes long) in little endian.
+	(data (i32.const 4) "\09\00\00\00\00\00\00\00")
+	;; Amount of value to transfer.
+	;; Represented by u64 (8 bytes long) in little endian.
+	(data (i32.const 12) "\06\00\00\00\00\00\00\00")
+)
+"#;
+
+	#[test]
+	fn contract_call_forward_input() {
+		let mut mock_ext = MockExt::default();
+		let _ = execute(
+			CODE_CALL_FORWARD_INPUT,
+			vec![5, 6, 7],
+			&mut mock_ext,
+			&mut GasMeter::new(GAS_LIMIT),
+		).unwrap();
+
+		assert_eq!(mock_ext.transfers.len(), 1);
+		assert_eq!(mock_ext.transfers[0].to, 9);
+		assert_eq!(mock_ext.transfers[0].data, vec![5, 6, 7]);
+	}
+
+	const CODE_CALL_RUNTIME: &str = r#"
+(module
+	(import "env" "ext_call_runtime" (func $ext_call_runtime (param i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	(func (export "call")
+		(drop
+			(call $ext_call_runtime
+				(i32.const 4)  ;; Pointer to the encoded call.
+				(i32.const 11) ;; Length of the encoded call.
+			)
+		)
+	)
+	(func (export "deploy"))
+
+	;; Encoded `Call::Balances(pallet_balances::Call::transfer(3, 50))`.
+	(data (i32.const 4) "\00\00\03\00\00\00\00\00\00\00\C8")
+)
+"#;
+
+	#[test]
+	fn contract_call_runtime() {
+		let mut mock_ext = MockExt::default();
+		let _ = execute(
+			CODE_CALL_RUNTIME,
+			vec![],
+			&mut mock_ext,
+			&mut GasMeter::new(GAS_LIMIT),
+		).unwrap();
+
+		assert_eq!(
+			&mock_ext.runtime_calls,
+			&[DispatchEntry(Call::Balances(pallet_balances::Call::transfer(3, 50)))]
+		);
+	}
+
 	#[test]
 	fn contract_call_limited_gas() {
 		let mut mock_ext = MockExt::default();
@@ -26,7 +26,34 @@ use parity_wasm::elements::{self, Internal, External, MemoryType, Type, ValueTyp
 use pwasm_utils;
 use pwasm_utils::rules;
 use sp_std::prelude::*;
-use sp_runtime::traits::{SaturatedConversion};
+use sp_runtime::{traits::SaturatedConversion, RuntimeDebug};
+
+/// The reason why a wasm module was refused by `prepare_contract`.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PrepareError {
+	/// The code couldn't be decoded or is not a valid wasm module.
+	InvalidModule,
+	/// The module doesn't export exactly the `call` and `deploy` entry points with the expected
+	/// signatures.
+	InvalidExports,
+	/// The module imports a function which is not provided by the host environment.
+	UnknownImport,
+	/// The module imports something it isn't allowed to, e.g. a table, a global or a
+	/// debug function which is disabled by the current schedule.
+	ForbiddenImport,
+	/// The module declares an internal memory instead of importing it.
+	InternalMemoryDeclared,
+	/// The declared memory limits are inconsistent or the maximum is missing.
+	InvalidMemoryLimits,
+	/// The declared maximum memory exceeds `Schedule::max_memory_pages`.
+	MemoryLimitExceeded,
+	/// The declared table exceeds `Schedule::max_table_size`.
+	TableTooLarge,
+	/// The module uses floating point types or instructions.
+	FloatingPointUsed,
+	/// Injecting gas or stack height metering into the module failed.
+	InstrumentationFailed,
+}
 
 struct ContractModule<'a> {
 	/// A deserialized module. The module is valid (this is Guaranteed by `new` method).
@@ -42,14 +69,14 @@ impl<'a> ContractModule<'a> {
 	fn new(
 		original_code: &[u8],
 		schedule: &'a Schedule,
-	) -> Result<Self, &'static str> {
+	) -> Result<Self, PrepareError> {
 		use wasmi_validation::{validate_module, PlainValidator};
 
 		let module =
-			elements::deserialize_buffer(original_code).map_err(|_| "Can't decode wasm code")?;
+			elements::deserialize_buffer(original_code).map_err(|_| PrepareError::InvalidModule)?;
 
 		// Make sure that the module is valid.
-		validate_module::<PlainValidator>(&module).map_err(|_| "Module is not valid")?;
+		validate_module::<PlainValidator>(&module).map_err(|_| PrepareError::InvalidModule)?;
 
 		// Return a `ContractModule` instance with
 		// __valid__ module.
@@ -64,29 +91,29 @@ impl<'a> ContractModule<'a> {
 	/// In this runtime we only allow wasm module to import memory from the environment.
 	/// Memory section contains declarations of internal linear memories, so if we find one
 	/// we reject such a module.
-	fn ensure_no_internal_memory(&self) -> Result<(), &'static str> {
+	fn ensure_no_internal_memory(&self) -> Result<(), PrepareError> {
 		if self.module
 			.memory_section()
 			.map_or(false, |ms| ms.entries().len() > 0)
 		{
-			return Err("module declares internal memory");
+			return Err(PrepareError::InternalMemoryDeclared);
 		}
 		Ok(())
 	}
 
 	/// Ensures that tables declared in the module are not too big.
-	fn ensure_table_size_limit(&self, limit: u32) -> Result<(), &'static str> {
+	fn ensure_table_size_limit(&self, limit: u32) -> Result<(), PrepareError> {
 		if let Some(table_section) = self.module.table_section() {
 			// In Wasm MVP spec, there may be at most one table declared. Double check this
 			// explicitly just in case the Wasm version changes.
 			if table_section.entries().len() > 1 {
-				return Err("multiple tables declared");
+				return Err(PrepareError::InvalidModule);
 			}
 			if let Some(table_type) = table_section.entries().first() {
 				// Check the table's initial size as there is no instruction or environment function
 				// capable of growing the table.
 				if table_type.limits().initial() > limit {
-					return Err("table exceeds maximum size allowed")
+					return Err(PrepareError::TableTooLarge)
 				}
 			}
 		}
@@ -94,12 +121,12 @@ impl<'a> ContractModule<'a> {
 	}
 
 	/// Ensures that no floating point types are in use.
-	fn ensure_no_floating_types(&self) -> Result<(), &'static str> {
+	fn ensure_no_floating_types(&self) -> Result<(), PrepareError> {
 		if let Some(global_section) = self.module.global_section() {
 			for global in global_section.entries() {
 				match global.global_type().content_type() {
 					ValueType::F32 | ValueType::F64 =>
-						return Err("use of floating point type in globals is forbidden"),
+						return Err(PrepareError::FloatingPointUsed),
 					_ => {}
 				}
 			}
@@ -110,7 +137,7 @@ impl<'a> ContractModule<'a> {
 				for local in func_body.locals() {
 					match local.value_type() {
 						ValueType::F32 | ValueType::F64 =>
-							return Err("use of floating point type in locals is forbidden"),
+							return Err(PrepareError::FloatingPointUsed),
 						_ => {}
 					}
 				}
@@ -125,7 +152,7 @@ impl<'a> ContractModule<'a> {
 						for value_type in func_type.params().iter().chain(return_type.iter()) {
 							match value_type {
 								ValueType::F32 | ValueType::F64 =>
-									return Err("use of floating point type in function types is forbidden"),
+									return Err(PrepareError::FloatingPointUsed),
 								_ => {}
 							}
 						}
@@ -137,7 +164,32 @@ impl<'a> ContractModule<'a> {
 		Ok(())
 	}
 
-	fn inject_gas_metering(self) -> Result<Self, &'static str> {
+	/// Ensures that no floating point instructions are in use.
+	///
+	/// Gas metering would refuse such a module as well, but checking explicitly allows to
+	/// report a precise reason.
+	fn ensure_no_floating_instructions(&self) -> Result<(), PrepareError> {
+		use pwasm_utils::rules::InstructionType;
+
+		if let Some(code_section) = self.module.code_section() {
+			for func_body in code_section.bodies() {
+				for instruction in func_body.code().elements() {
+					match InstructionType::op(instruction) {
+						InstructionType::Float |
+						InstructionType::FloatConst |
+						InstructionType::FloatComparison |
+						InstructionType::FloatConversion =>
+							return Err(PrepareError::FloatingPointUsed),
+						_ => {}
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	fn inject_gas_metering(self) -> Result<Self, PrepareError> {
 		let gas_rules =
 			rules::Set::new(
 				self.schedule.regular_op_cost.clone().saturated_into(),
@@ -147,17 +199,17 @@ impl<'a> ContractModule<'a> {
 			.with_forbidden_floats();
 
 		let contract_module = pwasm_utils::inject_gas_counter(self.module, &gas_rules)
-			.map_err(|_| "gas instrumentation failed")?;
+			.map_err(|_| PrepareError::InstrumentationFailed)?;
 		Ok(ContractModule {
 			module: contract_module,
 			schedule: self.schedule,
 		})
 	}
 
-	fn inject_stack_height_metering(self) -> Result<Self, &'static str> {
+	fn inject_stack_height_metering(self) -> Result<Self, PrepareError> {
 		let contract_module =
 			pwasm_utils::stack_height::inject_limiter(self.module, self.schedule.max_stack_height)
-				.map_err(|_| "stack height instrumentation failed")?;
+				.map_err(|_| PrepareError::InstrumentationFailed)?;
 		Ok(ContractModule {
 			module: contract_module,
 			schedule: self.schedule,
@@ -171,7 +223,7 @@ impl<'a> ContractModule<'a> {
 	/// - 'deploy'
 	///
 	/// Any other exports are not allowed.
-	fn scan_exports(&self) -> Result<(), &'static str> {
+	fn scan_exports(&self) -> Result<(), PrepareError> {
 		let mut deploy_found = false;
 		let mut call_found = false;
 
@@ -207,14 +259,14 @@ impl<'a> ContractModule<'a> {
 			match export.field() {
 				"call" => call_found = true,
 				"deploy" => deploy_found = true,
-				_ => return Err("unknown export: expecting only deploy and call functions"),
+				_ => return Err(PrepareError::InvalidExports),
 			}
 
 			// Then check the export kind. "call" and "deploy" are
 			// functions.
 			let fn_idx = match export.internal() {
 				Internal::Function(ref fn_idx) => *fn_idx,
-				_ => return Err("expected a function"),
+				_ => return Err(PrepareError::InvalidExports),
 			};
 
 			// convert index from function index space to declared index space.
@@ -222,30 +274,30 @@ impl<'a> ContractModule<'a> {
 				Some(fn_idx) => fn_idx,
 				None => {
 					// Underflow here means fn_idx points to imported function which we don't allow!
-					return Err("entry point points to an imported function");
+					return Err(PrepareError::InvalidExports);
 				}
 			};
 
 			// Then check the signature.
 			// Both "call" and "deploy" has a () -> () function type.
 			let func_ty_idx = func_entries.get(fn_idx as usize)
-				.ok_or_else(|| "export refers to non-existent function")?
+				.ok_or_else(|| PrepareError::InvalidExports)?
 				.type_ref();
 			let Type::Function(ref func_ty) = types
 				.get(func_ty_idx as usize)
-				.ok_or_else(|| "function has a non-existent type")?;
+				.ok_or_else(|| PrepareError::InvalidExports)?;
 			if !func_ty.params().is_empty() ||
 				!(func_ty.return_type().is_none() ||
 					func_ty.return_type() == Some(ValueType::I32)) {
-				return Err("entry point has wrong signature");
+				return Err(PrepareError::InvalidExports);
 			}
 		}
 
 		if !deploy_found {
-			return Err("deploy function isn't exported");
+			return Err(PrepareError::InvalidExports);
 		}
 		if !call_found {
-			return Err("call function isn't exported");
+			return Err(PrepareError::InvalidExports);
 		}
 
 		Ok(())
@@ -258,7 +310,7 @@ impl<'a> ContractModule<'a> {
 	/// - checks any imported function against defined host functions set, incl.
 	///   their signatures.
 	/// - if there is a memory import, returns it's descriptor
-	fn scan_imports<C: ImportSatisfyCheck>(&self) -> Result<Option<&MemoryType>, &'static str> {
+	fn scan_imports<C: ImportSatisfyCheck>(&self) -> Result<Option<&MemoryType>, PrepareError> {
 		let module = &self.module;
 
 		let types = module.type_section().map(|ts| ts.types()).unwrap_or(&[]);
@@ -273,19 +325,19 @@ impl<'a> ContractModule<'a> {
 			if import.module() != "env" {
 				// This import tries to import something from non-"env" module,
 				// but all imports are located in "env" at the moment.
-				return Err("module has imports from a non-'env' namespace");
+				return Err(PrepareError::ForbiddenImport);
 			}
 
 			let type_idx = match import.external() {
-				&External::Table(_) => return Err("Cannot import tables"),
-				&External::Global(_) => return Err("Cannot import globals"),
+				&External::Table(_) => return Err(PrepareError::ForbiddenImport),
+				&External::Global(_) => return Err(PrepareError::ForbiddenImport),
 				&External::Function(ref type_idx) => type_idx,
 				&External::Memory(ref memory_type) => {
 					if import.field() != "memory" {
-						return Err("Memory import must have the field name 'memory'")
+						return Err(PrepareError::ForbiddenImport)
 					}
 					if imported_mem_type.is_some() {
-						return Err("Multiple memory imports defined")
+						return Err(PrepareError::ForbiddenImport)
 					}
 					imported_mem_type = Some(memory_type);
 					continue;
@@ -294,27 +346,27 @@ impl<'a> ContractModule<'a> {
 
 			let Type::Function(ref func_ty) = types
 				.get(*type_idx as usize)
-				.ok_or_else(|| "validation: import entry points to a non-existent type")?;
+				.ok_or_else(|| PrepareError::InvalidModule)?;
 
 			// We disallow importing `ext_println` unless debug features are enabled,
 			// which should only be allowed on a dev chain
 			if !self.schedule.enable_println && import.field().as_bytes() == b"ext_println" {
-				return Err("module imports `ext_println` but debug features disabled");
+				return Err(PrepareError::ForbiddenImport);
 			}
 
 			// We disallow importing `gas` function here since it is treated as implementation detail.
 			if import.field().as_bytes() == b"gas"
 				|| !C::can_satisfy(import.field().as_bytes(), func_ty)
 			{
-				return Err("module imports a non-existent function");
+				return Err(PrepareError::UnknownImport);
 			}
 		}
 		Ok(imported_mem_type)
 	}
 
-	fn into_wasm_code(self) -> Result<Vec<u8>, &'static str> {
+	fn into_wasm_code(self) -> Result<Vec<u8>, PrepareError> {
 		elements::serialize(self.module)
-			.map_err(|_| "error serializing instrumented module")
+			.map_err(|_| PrepareError::InstrumentationFailed)
 	}
 }
 
@@ -327,17 +379,22 @@ impl<'a> ContractModule<'a> {
 /// - the module doesn't define an internal memory instance,
 /// - imported memory (if any) doesn't reserve more memory than permitted by the `schedule`,
 /// - all imported functions from the external environment matches defined by `env` module,
+/// - neither floating point types nor floating point instructions are used.
+///
+/// These checks are performed when the code is uploaded so that a module which can never be
+/// executed is refused with a precise `PrepareError` instead of failing at call time.
 ///
 /// The preprocessing includes injecting code for gas metering and metering the height of stack.
 pub fn prepare_contract<C: ImportSatisfyCheck>(
 	original_code: &[u8],
 	schedule: &Schedule,
-) -> Result<PrefabWasmModule, &'static str> {
+) -> Result<PrefabWasmModule, PrepareError> {
 	let mut contract_module = ContractModule::new(original_code, schedule)?;
 	contract_module.scan_exports()?;
 	contract_module.ensure_no_internal_memory()?;
 	contract_module.ensure_table_size_limit(schedule.max_table_size)?;
 	contract_module.ensure_no_floating_types()?;
+	contract_module.ensure_no_floating_instructions()?;
 
 	struct MemoryDefinition {
 		initial: u32,
@@ -350,18 +407,18 @@ pub fn prepare_contract<C: ImportSatisfyCheck>(
 		match (limits.initial(), limits.maximum()) {
 			(initial, Some(maximum)) if initial > maximum => {
 				return Err(
-					"Requested initial number of pages should not exceed the requested maximum",
+					PrepareError::InvalidMemoryLimits,
 				);
 			}
 			(_, Some(maximum)) if maximum > schedule.max_memory_pages => {
-				return Err("Maximum number of pages should not exceed the configured maximum.");
+				return Err(PrepareError::MemoryLimitExceeded);
 			}
 			(initial, Some(maximum)) => MemoryDefinition { initial, maximum },
 			(_, None) => {
 				// Maximum number of pages should be always declared.
 				// This isn't a hard requirement and can be treated as a maximum set
 				// to configured maximum.
-				return Err("Maximum number of pages should be always declared.");
+				return Err(PrepareError::InvalidMemoryLimits);
 			}
 		}
 	} else {
@@ -438,7 +495,7 @@ mod tests {
 			)
 			(func (export "deploy"))
 		)"#,
-		Err("gas instrumentation failed")
+		Err(PrepareError::FloatingPointUsed)
 	);
 
 	mod memories {
@@ -471,7 +528,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("module declares internal memory")
+			Err(PrepareError::InternalMemoryDeclared)
 		);
 
 		prepare_test!(no_memory_import,
@@ -494,7 +551,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("Module is not valid")
+			Err(PrepareError::InvalidModule)
 		);
 
 		prepare_test!(no_maximum,
@@ -506,7 +563,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("Maximum number of pages should be always declared.")
+			Err(PrepareError::InvalidMemoryLimits)
 		);
 
 		prepare_test!(requested_maximum_exceeds_configured_maximum,
@@ -518,7 +575,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("Maximum number of pages should not exceed the configured maximum.")
+			Err(PrepareError::MemoryLimitExceeded)
 		);
 
 		prepare_test!(field_name_not_memory,
@@ -530,7 +587,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("Memory import must have the field name 'memory'")
+			Err(PrepareError::ForbiddenImport)
 		);
 
 		prepare_test!(multiple_memory_imports,
@@ -543,7 +600,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("Module is not valid")
+			Err(PrepareError::InvalidModule)
 		);
 
 		prepare_test!(table_import,
@@ -555,7 +612,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("Cannot import tables")
+			Err(PrepareError::ForbiddenImport)
 		);
 
 		prepare_test!(global_import,
@@ -566,7 +623,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("Cannot import globals")
+			Err(PrepareError::ForbiddenImport)
 		);
 	}
 
@@ -609,7 +666,7 @@ mod tests {
 				(func (export "call"))
 				(func (export "deploy"))
 			)"#,
-			Err("table exceeds maximum size allowed")
+			Err(PrepareError::TableTooLarge)
 		);
 	}
 
@@ -639,7 +696,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("module imports a non-existent function")
+			Err(PrepareError::UnknownImport)
 		);
 
 		// nothing can be imported from non-"env" module for now.
@@ -652,7 +709,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("module has imports from a non-'env' namespace")
+			Err(PrepareError::ForbiddenImport)
 		);
 
 		// wrong signature
@@ -665,7 +722,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("module imports a non-existent function")
+			Err(PrepareError::UnknownImport)
 		);
 
 		prepare_test!(unknown_func_name,
@@ -677,7 +734,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("module imports a non-existent function")
+			Err(PrepareError::UnknownImport)
 		);
 
 		prepare_test!(ext_println_debug_disabled,
@@ -689,7 +746,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("module imports `ext_println` but debug features disabled")
+			Err(PrepareError::ForbiddenImport)
 		);
 
 		#[test]
@@ -730,7 +787,7 @@ mod tests {
 				(func (export "call"))
 			)
 			"#,
-			Err("deploy function isn't exported")
+			Err(PrepareError::InvalidExports)
 		);
 
 		prepare_test!(omit_call,
@@ -739,7 +796,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("call function isn't exported")
+			Err(PrepareError::InvalidExports)
 		);
 
 		// Try to use imported function as an entry point.
@@ -753,7 +810,7 @@ mod tests {
 				(export "call" (func 0))
 			)
 			"#,
-			Err("entry point points to an imported function")
+			Err(PrepareError::InvalidExports)
 		);
 
 		// Try to use imported function as an entry point.
@@ -764,7 +821,7 @@ mod tests {
 				(global (export "call") i32 (i32.const 0))
 			)
 			"#,
-			Err("expected a function")
+			Err(PrepareError::InvalidExports)
 		);
 
 		prepare_test!(wrong_signature,
@@ -774,7 +831,7 @@ mod tests {
 				(func (export "call") (param i32))
 			)
 			"#,
-			Err("entry point has wrong signature")
+			Err(PrepareError::InvalidExports)
 		);
 
 		prepare_test!(unknown_exports,
@@ -785,7 +842,7 @@ mod tests {
 				(func (export "whatevs"))
 			)
 			"#,
-			Err("unknown export: expecting only deploy and call functions")
+			Err(PrepareError::InvalidExports)
 		);
 
 		prepare_test!(global_float,
@@ -796,7 +853,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("use of floating point type in globals is forbidden")
+			Err(PrepareError::FloatingPointUsed)
 		);
 
 		prepare_test!(local_float,
@@ -807,7 +864,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("use of floating point type in locals is forbidden")
+			Err(PrepareError::FloatingPointUsed)
 		);
 
 		prepare_test!(param_float,
@@ -818,7 +875,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("use of floating point type in function types is forbidden")
+			Err(PrepareError::FloatingPointUsed)
 		);
 
 		prepare_test!(result_float,
@@ -829,7 +886,7 @@ mod tests {
 				(func (export "deploy"))
 			)
 			"#,
-			Err("use of floating point type in function types is forbidden")
+			Err(PrepareError::FloatingPointUsed)
 		);
 	}
 }
@@ -18,14 +18,18 @@
 
 use crate::{Schedule, Trait, CodeHash, BalanceOf, Error};
 use crate::exec::{
-	Ext, ExecResult, ExecReturnValue, StorageKey, TopicOf, ReturnFlags,
+	Ext, ExecResult, ExecReturnValue, StorageKey, TopicOf, ReturnFlags, CallFlags,
 };
 use crate::gas::{Gas, GasMeter, Token, GasMeterResult};
 use crate::wasm::env_def::ConvertibleToWasm;
 use sp_sandbox;
 use parity_wasm::elements::ValueType;
 use frame_system;
-use frame_support::dispatch::DispatchError;
+use frame_support::{
+	dispatch::DispatchError,
+	traits::Filter,
+	weights::{GetDispatchInfo, Weight},
+};
 use sp_std::prelude::*;
 use codec::{Decode, Encode};
 use sp_runtime::traits::{Bounded, SaturatedConversion};
@@ -51,6 +55,9 @@ pub enum ReturnCode {
 	CalleeReverted = 2,
 	/// The passed key does not exist in storage.
 	KeyNotFound = 3,
+	/// The call dispatched by `ext_call_runtime` returned an error.
+	/// All changes made by the dispatched call are reverted.
+	CallRuntimeReturnedError = 4,
 }
 
 impl ConvertibleToWasm for ReturnCode {
@@ -198,6 +205,8 @@ pub enum RuntimeToken {
 	/// (topic_count, data_bytes): A buffer of the given size is posted as an event indexed with the
 	/// given number of topics.
 	DepositEvent(u32, u32),
+	/// A runtime call with the given weight is dispatched.
+	CallRuntime(Weight),
 }
 
 impl<T: Trait> Token<T> for RuntimeToken {
@@ -235,6 +244,7 @@ impl<T: Trait> Token<T> for RuntimeToken {
 						data_and_topics_cost.checked_add(metadata.event_base_cost)
 					)
 			},
+			CallRuntime(weight) => metadata.dispatch_base_cost.checked_add(weight),
 		};
 
 		value.unwrap_or_else(|| Bounded::max_value())
@@ -395,6 +405,67 @@ fn write_sandbox_output<E: Ext>(
 	Ok(())
 }
 
+/// The implementation shared by `ext_call` and `ext_call_with_flags`.
+fn call<E: Ext>(
+	ctx: &mut Runtime<E>,
+	flags: CallFlags,
+	callee_ptr: u32,
+	callee_len: u32,
+	gas: u64,
+	value_ptr: u32,
+	value_len: u32,
+	input_data_ptr: u32,
+	input_data_len: u32,
+	output_ptr: u32,
+	output_len_ptr: u32,
+) -> Result<ReturnCode, sp_sandbox::HostError> {
+	let callee: <<E as Ext>::T as frame_system::Trait>::AccountId =
+		read_sandbox_memory_as(ctx, callee_ptr, callee_len)?;
+	let value: BalanceOf<<E as Ext>::T> = read_sandbox_memory_as(ctx, value_ptr, value_len)?;
+	let input_data = if flags.contains(CallFlags::FORWARD_INPUT) {
+		ctx.input_data.take().ok_or_else(|| map_err(ctx, Error::<E::T>::InputAlreadyRead))?
+	} else if flags.contains(CallFlags::CLONE_INPUT) {
+		ctx.input_data.clone().ok_or_else(|| map_err(ctx, Error::<E::T>::InputAlreadyRead))?
+	} else {
+		read_sandbox_memory(ctx, input_data_ptr, input_data_len)?
+	};
+	let allow_reentry = flags.contains(CallFlags::ALLOW_REENTRY);
+
+	let nested_gas_limit = if gas == 0 {
+		ctx.gas_meter.gas_left()
+	} else {
+		gas.saturated_into()
+	};
+	let ext = &mut ctx.ext;
+	let call_outcome = ctx.gas_meter.with_nested(nested_gas_limit, |nested_meter| {
+		match nested_meter {
+			Some(nested_meter) => {
+				ext.call(
+					&callee,
+					value,
+					nested_meter,
+					input_data,
+					allow_reentry,
+				)
+			}
+			// there is not enough gas to allocate for the nested call.
+			None => Err(Error::<E::T>::OutOfGas.into()),
+		}
+	});
+
+	match call_outcome {
+		Ok(output) => {
+			write_sandbox_output(ctx, output_ptr, output_len_ptr, &output.data, true)?;
+			Ok(output.into())
+		},
+		Err(err) if err == DispatchError::from(Error::<E::T>::ReentranceDenied) =>
+			Err(map_err(ctx, err)),
+		Err(_) => {
+			Ok(ReturnCode::CalleeTrapped)
+		},
+	}
+}
+
 /// Stores a DispatchError returned from an Ext function into the trap_reason.
 ///
 /// This allows through supervisor generated errors to the caller.
@@ -574,41 +645,114 @@ define_env!(Env, <E: Ext>,
 		output_ptr: u32,
 		output_len_ptr: u32
 	) -> ReturnCode => {
-		let callee: <<E as Ext>::T as frame_system::Trait>::AccountId =
-			read_sandbox_memory_as(ctx, callee_ptr, callee_len)?;
-		let value: BalanceOf<<E as Ext>::T> = read_sandbox_memory_as(ctx, value_ptr, value_len)?;
-		let input_data = read_sandbox_memory(ctx, input_data_ptr, input_data_len)?;
+		call(
+			ctx,
+			CallFlags::ALLOW_REENTRY,
+			callee_ptr,
+			callee_len,
+			gas,
+			value_ptr,
+			value_len,
+			input_data_ptr,
+			input_data_len,
+			output_ptr,
+			output_len_ptr,
+		)
+	},
 
-		let nested_gas_limit = if gas == 0 {
-			ctx.gas_meter.gas_left()
-		} else {
-			gas.saturated_into()
+	// Make a call to another contract, customized by the supplied `flags`.
+	//
+	// Works like `ext_call` with the following differences:
+	//
+	// - Calling into a contract which is already on the call stack (including the caller
+	//   itself) traps unless `ALLOW_REENTRY` is set.
+	// - With `FORWARD_INPUT` the input of the caller is passed to the callee and the input
+	//   buffer parameters are ignored. The caller can't read its input afterwards.
+	// - With `CLONE_INPUT` the input of the caller is passed to the callee and the input
+	//   buffer parameters are ignored. The caller can still read its input.
+	//
+	// The flags argument is a bitfield:
+	// --- lsb ---
+	// bit 0      : FORWARD_INPUT
+	// bit 1      : CLONE_INPUT
+	// bit 2      : ALLOW_REENTRY
+	// bit [3, 31]: Reserved for future use.
+	// --- msb ---
+	//
+	// # Traps
+	//
+	// In addition to the traps of `ext_call`:
+	//
+	// - A reserved bit is set in `flags` or both `FORWARD_INPUT` and `CLONE_INPUT` are set.
+	// - The input was already read or forwarded and one of the input flags is set.
+	// - Reentrance is attempted without `ALLOW_REENTRY`.
+	ext_call_with_flags(
+		ctx,
+		flags: u32,
+		callee_ptr: u32,
+		callee_len: u32,
+		gas: u64,
+		value_ptr: u32,
+		value_len: u32,
+		input_data_ptr: u32,
+		input_data_len: u32,
+		output_ptr: u32,
+		output_len_ptr: u32
+	) -> ReturnCode => {
+		let flags = match CallFlags::from_bits(flags) {
+			Some(flags) if !flags.contains(CallFlags::FORWARD_INPUT | CallFlags::CLONE_INPUT) =>
+				flags,
+			_ => return Err(map_err(ctx, Error::<E::T>::InvalidCallFlags)),
 		};
-		let ext = &mut ctx.ext;
-		let call_outcome = ctx.gas_meter.with_nested(nested_gas_limit, |nested_meter| {
-			match nested_meter {
-				Some(nested_meter) => {
-					ext.call(
-						&callee,
-						value,
-						nested_meter,
-						input_data,
-					)
-					.map_err(|_| ())
-				}
-				// there is not enough gas to allocate for the nested call.
-				None => Err(()),
-			}
-		});
+		call(
+			ctx,
+			flags,
+			callee_ptr,
+			callee_len,
+			gas,
+			value_ptr,
+			value_len,
+			input_data_ptr,
+			input_data_len,
+			output_ptr,
+			output_len_ptr,
+		)
+	},
 
-		match call_outcome {
-			Ok(output) => {
-				write_sandbox_output(ctx, output_ptr, output_len_ptr, &output.data, true)?;
-				Ok(output.into())
-			},
-			Err(_) => {
-				Ok(ReturnCode::CalleeTrapped)
-			},
+	// Dispatch a call into the runtime with the current contract as signed origin.
+	//
+	// The weight declared by the call is charged upfront. Which calls are allowed is decided
+	// by the runtime through `Trait::CallFilter`.
+	//
+	// # Parameters
+	//
+	// - call_ptr: a pointer to the SCALE encoded call.
+	//   Should be decodable as a `T::Call`. Traps otherwise.
+	// - call_len: length of the call buffer.
+	//
+	// # Errors
+	//
+	// `ReturnCode::CallRuntimeReturnedError`: The dispatched call returned an error. All changes
+	//  made by it are reverted.
+	//
+	// # Traps
+	//
+	// - The call is refused by `Trait::CallFilter`.
+	// - Not enough gas to pay for the weight of the call.
+	ext_call_runtime(ctx, call_ptr: u32, call_len: u32) -> ReturnCode => {
+		let call: <<E as Ext>::T as Trait>::Call = read_sandbox_memory_as(ctx, call_ptr, call_len)?;
+		if !<<E as Ext>::T as Trait>::CallFilter::filter(&call) {
+			return Err(map_err(ctx, Error::<E::T>::CallFiltered));
+		}
+		charge_gas(
+			ctx.gas_meter,
+			ctx.schedule,
+			&mut ctx.trap_reason,
+			RuntimeToken::CallRuntime(call.get_dispatch_info().weight),
+		)?;
+		match ctx.ext.call_runtime(call) {
+			Ok(_) => Ok(ReturnCode::Success),
+			Err(_) => Ok(ReturnCode::CallRuntimeReturnedError),
 		}
 	},
 
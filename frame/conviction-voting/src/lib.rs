@@ -0,0 +1,396 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Conviction Voting Pallet
+//!
+//! - [`conviction_voting::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Conviction Voting pallet lets accounts vote on the polls of any pallet implementing the
+//! `Polling` trait of `frame_support`, e.g. the Referenda pallet. Votes are expressed with
+//! locked funds, and are tallied as they are placed.
+//!
+//! An account votes in one of three ways:
+//!
+//! - **Standard:** aye or nay with all of the given balance, multiplied by a _conviction_.
+//!   The higher the conviction, the longer the balance stays locked after the poll ended, if it
+//!   went the way of the vote.
+//! - **Split:** part of the balance aye and part nay, without conviction.
+//! - **Split abstain:** like a split vote, with a further part abstaining. Abstentions count
+//!   towards the support of a poll, but not its approval.
+//!
+//! Polls are grouped into classes, and the votes of an account in each class are locked
+//! independently: the funds locked in an account are the largest lock needed by any class.
+//!
+//! ### Locks
+//!
+//! A vote may be removed with `remove_vote`. A vote on an ongoing poll is removed from its tally.
+//! A winning standard vote on a completed poll stays locked until `VoteLockingPeriod` times the
+//! lock periods of its conviction has passed since the end of the poll. Once votes have been
+//! removed, `unlock` reduces the lock of an account to what its remaining votes require.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `vote` - Vote on an ongoing poll, or replace a previous vote.
+//! - `remove_vote` - Remove a vote, keeping its lock if it is still needed.
+//! - `unlock` - Remove the locks of an account which have expired.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use sp_runtime::{DispatchResult, traits::{Saturating, Zero}};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure,
+	weights::Weight,
+	traits::{
+		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
+		PollStatus, Polling,
+	},
+};
+use frame_system::{self as system, ensure_signed};
+
+mod conviction;
+mod types;
+mod vote;
+#[cfg(test)]
+mod tests;
+
+pub use conviction::Conviction;
+pub use types::{Delegations, Tally};
+pub use vote::{Vote, AccountVote, Voting, PriorLock};
+
+const CONVICTION_VOTING_ID: LockIdentifier = *b"pyconvot";
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+/// The tally of a poll voted on through this pallet.
+pub type TallyOf<T> = Tally<BalanceOf<T>, <T as Trait>::MaxTurnout>;
+/// The index of a poll.
+pub type PollIndexOf<T> = <<T as Trait>::Polls as Polling<TallyOf<T>>>::Index;
+/// The class of a poll.
+pub type ClassOf<T> = <<T as Trait>::Polls as Polling<TallyOf<T>>>::Class;
+/// The votes of an account in one class of polls.
+pub type VotingOf<T> = Voting<
+	BalanceOf<T>,
+	<T as frame_system::Trait>::BlockNumber,
+	PollIndexOf<T>,
+>;
+
+pub trait WeightInfo {
+	fn vote_new() -> Weight;
+	fn vote_existing() -> Weight;
+	fn remove_vote() -> Weight;
+	fn unlock() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn vote_new() -> Weight { 1_000_000_000 }
+	fn vote_existing() -> Weight { 1_000_000_000 }
+	fn remove_vote() -> Weight { 1_000_000_000 }
+	fn unlock() -> Weight { 1_000_000_000 }
+}
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Currency type with which voting happens.
+	type Currency: ReservableCurrency<Self::AccountId>
+		+ LockableCurrency<Self::AccountId, Moment=Self::BlockNumber>;
+
+	/// The polls which may be voted on.
+	type Polls: Polling<TallyOf<Self>, Moment = Self::BlockNumber>;
+
+	/// The maximum amount of funds which may vote on a poll, against which its support is
+	/// measured. Usually the total issuance.
+	type MaxTurnout: Get<BalanceOf<Self>>;
+
+	/// The maximum number of concurrent votes an account may have in one class of polls.
+	type MaxVotes: Get<u32>;
+
+	/// The minimum period of vote locking.
+	///
+	/// It should be no shorter than the enactment period of the polls, to ensure that in the
+	/// case of an approval, those successful voters are locked into the consequences that their
+	/// votes entail.
+	type VoteLockingPeriod: Get<Self::BlockNumber>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as ConvictionVoting {
+		/// All voting for a particular voter in a particular class of polls.
+		pub VotingFor get(fn voting_for):
+			double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) ClassOf<T>
+			=> VotingOf<T>;
+
+		/// The voting classes which have a non-zero lock requirement and the lock amounts which
+		/// they require. The actual amount locked on behalf of this pallet is the maximum of
+		/// these.
+		pub ClassLocksFor get(fn class_locks_for):
+			map hasher(twox_64_concat) T::AccountId => Vec<(ClassOf<T>, BalanceOf<T>)>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		PollIndex = PollIndexOf<T>,
+	{
+		/// An account has voted on a poll. \[who, poll_index\]
+		Voted(AccountId, PollIndex),
+		/// An account has removed its vote on a poll. \[who, poll_index\]
+		VoteRemoved(AccountId, PollIndex),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Poll is not ongoing.
+		NotOngoing,
+		/// The given account did not vote on the poll.
+		NotVoter,
+		/// The actor has no permission to conduct the action.
+		NoPermission,
+		/// Too high a balance was provided that the account cannot afford.
+		InsufficientFunds,
+		/// Maximum number of votes reached.
+		MaxVotesReached,
+		/// An unexpected integer overflow occurred.
+		Overflow,
+		/// An unexpected integer underflow occurred.
+		Underflow,
+		/// The class must be supplied since it is not easily determinable from the state.
+		ClassNeeded,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The maximum number of concurrent votes an account may have in one class of polls.
+		const MaxVotes: u32 = T::MaxVotes::get();
+
+		/// The minimum period of vote locking.
+		const VoteLockingPeriod: T::BlockNumber = T::VoteLockingPeriod::get();
+
+		fn deposit_event() = default;
+
+		/// Vote in a poll. If `vote.is_aye()`, the vote is to enact the proposal;
+		/// otherwise it is a vote to keep the status quo.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_index`: The index of the poll to vote for.
+		/// - `vote`: The vote configuration.
+		///
+		/// Weight: `O(R)` where R is the number of polls the voter has voted on in the class.
+		#[weight = T::WeightInfo::vote_new().max(T::WeightInfo::vote_existing())]
+		fn vote(origin, poll_index: PollIndexOf<T>, vote: AccountVote<BalanceOf<T>>) {
+			let who = ensure_signed(origin)?;
+			Self::try_vote(&who, poll_index, vote)?;
+			Self::deposit_event(RawEvent::Voted(who, poll_index));
+		}
+
+		/// Remove a vote for a poll.
+		///
+		/// If:
+		/// - the poll was cancelled, or
+		/// - the poll is ongoing, or
+		/// - the poll has ended such that
+		///   - the vote of the account was in opposition to the result; or
+		///   - there was no conviction to the account's vote; or
+		///   - the account made a split vote
+		/// ...then the vote is removed cleanly and a following call to `unlock` may result in more
+		/// funds being available.
+		///
+		/// If, however, the poll has ended and:
+		/// - it finished corresponding to the vote of the account, and
+		/// - the account made a standard vote with conviction, and
+		/// - the lock period of the conviction is not over
+		/// ...then the lock will be aggregated into the overall account's lock, which may involve
+		/// *overlocking* (where the two locks are combined into a single lock that is the maximum
+		/// of both the amount locked and the time is it locked for).
+		///
+		/// The dispatch origin of this call must be _Signed_, and the signer must have a vote
+		/// registered for poll `index`.
+		///
+		/// - `class`: Optional parameter, if given it indicates the class of the poll. For polls
+		///   which have finished or are cancelled, this must be `Some`.
+		/// - `index`: The index of poll of the vote to be removed.
+		///
+		/// Weight: `O(R + log R)` where R is the number of polls the voter has voted on in the
+		/// class.
+		#[weight = T::WeightInfo::remove_vote()]
+		fn remove_vote(origin, class: Option<ClassOf<T>>, index: PollIndexOf<T>) {
+			let who = ensure_signed(origin)?;
+			Self::try_remove_vote(&who, index, class)?;
+			Self::deposit_event(RawEvent::VoteRemoved(who, index));
+		}
+
+		/// Remove the lock caused by prior voting within a particular class.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `class`: The class of polls to remove the lock from.
+		/// - `target`: The account to remove the lock on.
+		///
+		/// Weight: `O(R)` with R number of votes of `target` in the class.
+		#[weight = T::WeightInfo::unlock()]
+		fn unlock(origin, class: ClassOf<T>, target: T::AccountId) {
+			ensure_signed(origin)?;
+			Self::update_lock(&class, &target);
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Actually enact a vote, if legit.
+	fn try_vote(
+		who: &T::AccountId,
+		poll_index: PollIndexOf<T>,
+		vote: AccountVote<BalanceOf<T>>,
+	) -> DispatchResult {
+		ensure!(vote.balance() <= T::Currency::free_balance(who), Error::<T>::InsufficientFunds);
+		T::Polls::try_access_poll(poll_index, |poll_status| {
+			let (tally, class) = poll_status.ensure_ongoing().ok_or(Error::<T>::NotOngoing)?;
+			VotingFor::<T>::try_mutate(who, &class, |voting| -> DispatchResult {
+				let votes = &mut voting.votes;
+				match votes.binary_search_by_key(&poll_index, |i| i.0) {
+					Ok(i) => {
+						// Shouldn't be possible to fail, but we handle it gracefully.
+						tally.remove(votes[i].1).ok_or(Error::<T>::Underflow)?;
+						votes[i].1 = vote;
+					}
+					Err(i) => {
+						ensure!(
+							(votes.len() as u32) < T::MaxVotes::get(),
+							Error::<T>::MaxVotesReached
+						);
+						votes.insert(i, (poll_index, vote));
+					}
+				}
+				// Shouldn't be possible to fail, but we handle it gracefully.
+				tally.add(vote).ok_or(Error::<T>::Overflow)?;
+				Ok(())
+			})?;
+			// Extend the lock to `balance` (rather than setting it) since we don't know what
+			// other votes are in place.
+			Self::extend_lock(who, &class, vote.balance());
+			Ok(())
+		})
+	}
+
+	/// Remove the account's vote for the given poll if possible. This is possible when:
+	/// - The poll has not finished.
+	/// - The poll has finished and the voter lost their direction.
+	/// - The poll has finished and the voter's lock period is up.
+	///
+	/// This will generally be combined with a call to `unlock`.
+	fn try_remove_vote(
+		who: &T::AccountId,
+		poll_index: PollIndexOf<T>,
+		class_hint: Option<ClassOf<T>>,
+	) -> DispatchResult {
+		let class = class_hint
+			.or_else(|| T::Polls::access_poll(poll_index, Self::ongoing_class))
+			.ok_or(Error::<T>::ClassNeeded)?;
+		VotingFor::<T>::try_mutate(who, class, |voting| -> DispatchResult {
+			let Voting { votes, prior } = voting;
+			let i = votes.binary_search_by_key(&poll_index, |i| i.0)
+				.map_err(|_| Error::<T>::NotVoter)?;
+			T::Polls::try_access_poll(poll_index, |poll_status| match poll_status {
+				PollStatus::Ongoing(tally, _) => {
+					// Shouldn't be possible to fail, but we handle it gracefully.
+					tally.remove(votes[i].1).ok_or(Error::<T>::Underflow)?;
+					Ok(())
+				}
+				PollStatus::Completed(end, approved) => {
+					if let Some((lock_periods, balance)) = votes[i].1.locked_if(approved) {
+						let unlock_at = end.saturating_add(
+							T::VoteLockingPeriod::get().saturating_mul(lock_periods.into())
+						);
+						let now = system::Module::<T>::block_number();
+						if now < unlock_at {
+							prior.accumulate(unlock_at, balance)
+						}
+					}
+					Ok(())
+				}
+				// Poll was cancelled.
+				PollStatus::None => Ok(()),
+			})?;
+			votes.remove(i);
+			Ok(())
+		})
+	}
+
+	/// The class of a poll, if it is ongoing.
+	fn ongoing_class(
+		poll_status: PollStatus<&mut TallyOf<T>, T::BlockNumber, ClassOf<T>>,
+	) -> Option<ClassOf<T>> {
+		poll_status.ensure_ongoing().map(|(_, class)| class)
+	}
+
+	/// Note that the account needs at least `amount` locked for its votes in `class`.
+	fn extend_lock(who: &T::AccountId, class: &ClassOf<T>, amount: BalanceOf<T>) {
+		ClassLocksFor::<T>::mutate(who, |locks| {
+			match locks.iter().position(|x| &x.0 == class) {
+				Some(i) => locks[i].1 = locks[i].1.max(amount),
+				None => locks.push((*class, amount)),
+			}
+		});
+		T::Currency::extend_lock(
+			CONVICTION_VOTING_ID,
+			who,
+			amount,
+			WithdrawReason::Transfer.into(),
+		);
+	}
+
+	/// Rejig the lock on an account. It will never get more stringent (since that would indicate
+	/// a security hole) but may be reduced from what they are currently.
+	fn update_lock(class: &ClassOf<T>, who: &T::AccountId) {
+		let class_lock_needed = VotingFor::<T>::mutate(who, class, |voting| {
+			voting.rejig(system::Module::<T>::block_number());
+			voting.locked_balance()
+		});
+		let lock_needed = ClassLocksFor::<T>::mutate(who, |locks| {
+			locks.retain(|x| &x.0 != class);
+			if !class_lock_needed.is_zero() {
+				locks.push((*class, class_lock_needed));
+			}
+			locks.iter().map(|x| x.1).max().unwrap_or_else(Zero::zero)
+		});
+		if lock_needed.is_zero() {
+			T::Currency::remove_lock(CONVICTION_VOTING_ID, who);
+		} else {
+			T::Currency::set_lock(
+				CONVICTION_VOTING_ID,
+				who,
+				lock_needed,
+				WithdrawReason::Transfer.into(),
+			);
+		}
+	}
+}
@@ -0,0 +1,375 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for Conviction Voting Pallet
+
+#![cfg(test)]
+
+use super::*;
+
+use std::{cell::RefCell, collections::BTreeMap};
+use frame_support::{
+	assert_ok, assert_noop, impl_outer_origin, parameter_types, traits::VoteTally,
+};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill, DispatchError, testing::Header, traits::{BlakeTwo256, IdentityLookup},
+};
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = ();
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TestPollState {
+	Ongoing(TallyOf<Test>, u8),
+	Completed(u64, bool),
+}
+use TestPollState::*;
+
+thread_local! {
+	static POLLS: RefCell<BTreeMap<u8, TestPollState>> = RefCell::new(vec![
+		(1, Ongoing(Tally::default(), 0)),
+		(2, Ongoing(Tally::default(), 0)),
+		(3, Ongoing(Tally::default(), 1)),
+	].into_iter().collect());
+}
+
+/// The polls voted on in the tests, with their classes.
+pub struct TestPolls;
+impl TestPolls {
+	fn set(index: u8, state: TestPollState) {
+		POLLS.with(|p| p.borrow_mut().insert(index, state));
+	}
+
+	fn tally(index: u8) -> TallyOf<Test> {
+		match POLLS.with(|p| p.borrow().get(&index).cloned()) {
+			Some(Ongoing(tally, _)) => tally,
+			other => panic!("poll {} is not ongoing: {:?}", index, other),
+		}
+	}
+}
+
+impl Polling<TallyOf<Test>> for TestPolls {
+	type Index = u8;
+	type Class = u8;
+	type Moment = u64;
+
+	fn access_poll<R>(
+		index: u8,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> R,
+	) -> R {
+		let mut polls = POLLS.with(|p| p.borrow().clone());
+		let r = match polls.get_mut(&index) {
+			Some(Ongoing(ref mut tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(Completed(end, approved)) => f(PollStatus::Completed(*end, *approved)),
+			None => f(PollStatus::None),
+		};
+		POLLS.with(|p| *p.borrow_mut() = polls);
+		r
+	}
+
+	fn try_access_poll<R>(
+		index: u8,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		let mut polls = POLLS.with(|p| p.borrow().clone());
+		let r = match polls.get_mut(&index) {
+			Some(Ongoing(ref mut tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(Completed(end, approved)) => f(PollStatus::Completed(*end, *approved)),
+			None => f(PollStatus::None),
+		}?;
+		POLLS.with(|p| *p.borrow_mut() = polls);
+		Ok(r)
+	}
+}
+
+pub struct TotalIssuance;
+impl Get<u64> for TotalIssuance {
+	fn get() -> u64 {
+		Balances::total_issuance()
+	}
+}
+
+parameter_types! {
+	pub const MaxVotes: u32 = 3;
+	pub const VoteLockingPeriod: u64 = 3;
+}
+impl Trait for Test {
+	type Event = ();
+	type Currency = Balances;
+	type Polls = TestPolls;
+	type MaxTurnout = TotalIssuance;
+	type MaxVotes = MaxVotes;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type WeightInfo = ();
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type ConvictionVoting = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn aye(amount: u64, conviction: Conviction) -> AccountVote<u64> {
+	let vote = Vote { aye: true, conviction };
+	AccountVote::Standard { vote, balance: amount }
+}
+
+fn nay(amount: u64, conviction: Conviction) -> AccountVote<u64> {
+	let vote = Vote { aye: false, conviction };
+	AccountVote::Standard { vote, balance: amount }
+}
+
+fn tally(ayes: u64, nays: u64, support: u64) -> TallyOf<Test> {
+	Tally::from_parts(ayes, nays, support)
+}
+
+#[test]
+fn standard_votes_are_tallied_with_conviction() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(10, Conviction::Locked1x)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(2), 1, nay(20, Conviction::Locked2x)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(3), 1, aye(30, Conviction::None)));
+		assert_eq!(TestPolls::tally(1), tally(13, 40, 40));
+		assert_eq!(Balances::usable_balance(1), 0);
+		assert_eq!(Balances::usable_balance(2), 0);
+		assert_eq!(Balances::usable_balance(3), 0);
+	});
+}
+
+#[test]
+fn split_votes_are_tallied_without_conviction() {
+	new_test_ext().execute_with(|| {
+		let split = AccountVote::Split { aye: 10, nay: 10 };
+		assert_ok!(ConvictionVoting::vote(Origin::signed(2), 1, split));
+		assert_eq!(TestPolls::tally(1), tally(1, 1, 10));
+		assert_eq!(Balances::usable_balance(2), 0);
+	});
+}
+
+#[test]
+fn split_abstain_votes_count_towards_support_only() {
+	new_test_ext().execute_with(|| {
+		let split_abstain = AccountVote::SplitAbstain { aye: 0, nay: 0, abstain: 30 };
+		assert_ok!(ConvictionVoting::vote(Origin::signed(3), 1, split_abstain));
+		assert_eq!(TestPolls::tally(1), tally(0, 0, 30));
+		assert_eq!(Balances::usable_balance(3), 0);
+
+		let split_abstain = AccountVote::SplitAbstain { aye: 10, nay: 10, abstain: 20 };
+		assert_ok!(ConvictionVoting::vote(Origin::signed(4), 1, split_abstain));
+		assert_eq!(TestPolls::tally(1), tally(1, 1, 60));
+
+		// 60 of the total issuance of 210 supports the poll.
+		let t = TestPolls::tally(1);
+		assert_eq!(VoteTally::<u8>::approval(&t), Perbill::from_percent(50));
+		assert_eq!(t.support(0u8), Perbill::from_rational_approximation(60u32, 210));
+	});
+}
+
+#[test]
+fn voting_again_replaces_the_vote() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(10, Conviction::Locked1x)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, nay(5, Conviction::Locked3x)));
+		assert_eq!(TestPolls::tally(1), tally(0, 15, 0));
+		assert_eq!(ConvictionVoting::voting_for(1, 0).votes.len(), 1);
+	});
+}
+
+#[test]
+fn vote_is_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ConvictionVoting::vote(Origin::signed(1), 1, aye(11, Conviction::None)),
+			Error::<Test>::InsufficientFunds,
+		);
+		TestPolls::set(2, Completed(1, true));
+		assert_noop!(
+			ConvictionVoting::vote(Origin::signed(1), 2, aye(10, Conviction::None)),
+			Error::<Test>::NotOngoing,
+		);
+		assert_noop!(
+			ConvictionVoting::vote(Origin::signed(1), 4, aye(10, Conviction::None)),
+			Error::<Test>::NotOngoing,
+		);
+	});
+}
+
+#[test]
+fn max_votes_per_class_is_respected() {
+	new_test_ext().execute_with(|| {
+		for i in 1..=4 {
+			TestPolls::set(i + 3, Ongoing(Tally::default(), 2));
+		}
+		for i in 4..=6 {
+			assert_ok!(ConvictionVoting::vote(Origin::signed(1), i, aye(1, Conviction::None)));
+		}
+		assert_noop!(
+			ConvictionVoting::vote(Origin::signed(1), 7, aye(1, Conviction::None)),
+			Error::<Test>::MaxVotesReached,
+		);
+		// Polls of another class are unaffected.
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(1, Conviction::None)));
+	});
+}
+
+#[test]
+fn removing_vote_on_ongoing_poll_unlocks_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(10, Conviction::Locked6x)));
+		assert_eq!(TestPolls::tally(1), tally(60, 0, 10));
+
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), None, 1));
+		assert_eq!(TestPolls::tally(1), tally(0, 0, 0));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(2), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+		assert!(ConvictionVoting::class_locks_for(1).is_empty());
+	});
+}
+
+#[test]
+fn remove_vote_is_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ConvictionVoting::remove_vote(Origin::signed(1), None, 1),
+			Error::<Test>::NotVoter,
+		);
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(10, Conviction::None)));
+		TestPolls::set(1, Completed(1, true));
+		assert_noop!(
+			ConvictionVoting::remove_vote(Origin::signed(1), None, 1),
+			Error::<Test>::ClassNeeded,
+		);
+		assert_noop!(
+			ConvictionVoting::remove_vote(Origin::signed(1), Some(1), 1),
+			Error::<Test>::NotVoter,
+		);
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), Some(0), 1));
+	});
+}
+
+#[test]
+fn winning_conviction_vote_stays_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(10, Conviction::Locked2x)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(2), 1, nay(20, Conviction::Locked2x)));
+		TestPolls::set(1, Completed(2, true));
+
+		// The losing vote is removed cleanly.
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(2), Some(0), 1));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::usable_balance(2), 20);
+
+		// The winning vote is locked until 2 lock periods after the end of the poll.
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), Some(0), 1));
+		System::set_block_number(7);
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 0);
+		System::set_block_number(8);
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn cancelled_poll_vote_is_removed_cleanly() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(10, Conviction::Locked6x)));
+		POLLS.with(|p| p.borrow_mut().remove(&1));
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), Some(0), 1));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn classes_are_locked_independently() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 1, aye(10, Conviction::None)));
+		assert_ok!(ConvictionVoting::vote(Origin::signed(1), 3, aye(5, Conviction::None)));
+		assert_eq!(ConvictionVoting::class_locks_for(1), vec![(0, 10), (1, 5)]);
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), None, 1));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(ConvictionVoting::class_locks_for(1), vec![(1, 5)]);
+		assert_eq!(Balances::usable_balance(1), 5);
+
+		assert_ok!(ConvictionVoting::remove_vote(Origin::signed(1), None, 3));
+		assert_ok!(ConvictionVoting::unlock(Origin::signed(1), 1, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
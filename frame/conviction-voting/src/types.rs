@@ -0,0 +1,224 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use sp_std::{fmt, marker::PhantomData};
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, Perbill};
+use sp_runtime::traits::{
+	Zero, Bounded, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv, Saturating, AtLeast32BitUnsigned,
+};
+use frame_support::traits::{Get, VoteTally};
+use crate::{AccountVote, Conviction};
+
+/// Info regarding an ongoing poll.
+///
+/// `Total` gives the amount of funds which could possibly be voting, against which the support of
+/// the poll is measured.
+#[derive(Encode, Decode)]
+pub struct Tally<Votes, Total> {
+	/// The number of aye votes, expressed in terms of post-conviction lock-vote.
+	pub ayes: Votes,
+	/// The number of nay votes, expressed in terms of post-conviction lock-vote.
+	pub nays: Votes,
+	/// The amount of funds currently supporting the poll, either by voting aye or by abstaining.
+	/// Pre-conviction.
+	pub support: Votes,
+	#[codec(skip)]
+	dummy: PhantomData<Total>,
+}
+
+// `Total` is only a marker for what the support is measured against, typically a `Get` type
+// which implements none of these traits. `derive` would bound them on `Total` all the same.
+
+impl<Votes: Clone, Total> Clone for Tally<Votes, Total> {
+	fn clone(&self) -> Self {
+		Self::from_parts(self.ayes.clone(), self.nays.clone(), self.support.clone())
+	}
+}
+
+impl<Votes: PartialEq, Total> PartialEq for Tally<Votes, Total> {
+	fn eq(&self, other: &Self) -> bool {
+		self.ayes == other.ayes && self.nays == other.nays && self.support == other.support
+	}
+}
+
+impl<Votes: Eq, Total> Eq for Tally<Votes, Total> {}
+
+impl<Votes: Default, Total> Default for Tally<Votes, Total> {
+	fn default() -> Self {
+		Self::from_parts(Default::default(), Default::default(), Default::default())
+	}
+}
+
+impl<Votes: fmt::Debug, Total> fmt::Debug for Tally<Votes, Total> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Tally")
+			.field("ayes", &self.ayes)
+			.field("nays", &self.nays)
+			.field("support", &self.support)
+			.finish()
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("<wasm:stripped>")
+	}
+}
+
+impl<Votes, Total> Tally<Votes, Total> {
+	/// Create a tally from its constituent parts.
+	pub fn from_parts(ayes: Votes, nays: Votes, support: Votes) -> Self {
+		Self { ayes, nays, support, dummy: PhantomData }
+	}
+}
+
+impl<
+	Votes: AtLeast32BitUnsigned + Copy,
+	Total: Get<Votes>,
+	Class,
+> VoteTally<Class> for Tally<Votes, Total> {
+	fn approval(&self) -> Perbill {
+		Perbill::from_rational_approximation(self.ayes, self.ayes.saturating_add(self.nays))
+	}
+
+	// All funds may vote on polls of any class.
+	fn support(&self, _class: Class) -> Perbill {
+		Perbill::from_rational_approximation(self.support, Total::get())
+	}
+}
+
+impl<
+	Votes: From<u8> + Zero + Copy + CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + Bounded +
+		Saturating,
+	Total,
+> Tally<Votes, Total> {
+	/// Add an account's vote into the tally.
+	pub fn add(
+		&mut self,
+		vote: AccountVote<Votes>,
+	) -> Option<()> {
+		match vote {
+			AccountVote::Standard { vote, balance } => {
+				let Delegations { votes, capital } = vote.conviction.votes(balance);
+				match vote.aye {
+					true => {
+						self.support = self.support.checked_add(&capital)?;
+						self.ayes = self.ayes.checked_add(&votes)?;
+					}
+					false => self.nays = self.nays.checked_add(&votes)?,
+				}
+			}
+			AccountVote::Split { aye, nay } => {
+				let aye = Conviction::None.votes(aye);
+				let nay = Conviction::None.votes(nay);
+				self.support = self.support.checked_add(&aye.capital)?;
+				self.ayes = self.ayes.checked_add(&aye.votes)?;
+				self.nays = self.nays.checked_add(&nay.votes)?;
+			}
+			AccountVote::SplitAbstain { aye, nay, abstain } => {
+				let aye = Conviction::None.votes(aye);
+				let nay = Conviction::None.votes(nay);
+				let abstain = Conviction::None.votes(abstain);
+				self.support = self.support
+					.checked_add(&aye.capital)?
+					.checked_add(&abstain.capital)?;
+				self.ayes = self.ayes.checked_add(&aye.votes)?;
+				self.nays = self.nays.checked_add(&nay.votes)?;
+			}
+		}
+		Some(())
+	}
+
+	/// Remove an account's vote from the tally.
+	pub fn remove(
+		&mut self,
+		vote: AccountVote<Votes>,
+	) -> Option<()> {
+		match vote {
+			AccountVote::Standard { vote, balance } => {
+				let Delegations { votes, capital } = vote.conviction.votes(balance);
+				match vote.aye {
+					true => {
+						self.support = self.support.checked_sub(&capital)?;
+						self.ayes = self.ayes.checked_sub(&votes)?;
+					}
+					false => self.nays = self.nays.checked_sub(&votes)?,
+				}
+			}
+			AccountVote::Split { aye, nay } => {
+				let aye = Conviction::None.votes(aye);
+				let nay = Conviction::None.votes(nay);
+				self.support = self.support.checked_sub(&aye.capital)?;
+				self.ayes = self.ayes.checked_sub(&aye.votes)?;
+				self.nays = self.nays.checked_sub(&nay.votes)?;
+			}
+			AccountVote::SplitAbstain { aye, nay, abstain } => {
+				let aye = Conviction::None.votes(aye);
+				let nay = Conviction::None.votes(nay);
+				let abstain = Conviction::None.votes(abstain);
+				self.support = self.support
+					.checked_sub(&aye.capital)?
+					.checked_sub(&abstain.capital)?;
+				self.ayes = self.ayes.checked_sub(&aye.votes)?;
+				self.nays = self.nays.checked_sub(&nay.votes)?;
+			}
+		}
+		Some(())
+	}
+}
+
+/// Amount of votes and capital placed in delegation for an account.
+#[derive(Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Delegations<Balance> {
+	/// The number of votes (this is post-conviction).
+	pub votes: Balance,
+	/// The amount of raw capital, used for the support.
+	pub capital: Balance,
+}
+
+impl<Balance: Saturating> Saturating for Delegations<Balance> {
+	fn saturating_add(self, o: Self) -> Self {
+		Self {
+			votes: self.votes.saturating_add(o.votes),
+			capital: self.capital.saturating_add(o.capital),
+		}
+	}
+
+	fn saturating_sub(self, o: Self) -> Self {
+		Self {
+			votes: self.votes.saturating_sub(o.votes),
+			capital: self.capital.saturating_sub(o.capital),
+		}
+	}
+
+	fn saturating_mul(self, o: Self) -> Self {
+		Self {
+			votes: self.votes.saturating_mul(o.votes),
+			capital: self.capital.saturating_mul(o.capital),
+		}
+	}
+
+	fn saturating_pow(self, exp: usize) -> Self {
+		Self {
+			votes: self.votes.saturating_pow(exp),
+			capital: self.capital.saturating_pow(exp),
+		}
+	}
+}
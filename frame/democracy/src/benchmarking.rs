@@ -321,7 +321,9 @@ benchmarks! {
 			add_referendum::<T>(i)?;
 		}
 		let referendum_index = add_referendum::<T>(r)?;
-	}: _(RawOrigin::Root, referendum_index)
+		let origin = T::CancelReferendumOrigin::successful_origin();
+		let call = Call::<T>::cancel_referendum(referendum_index);
+	}: { call.dispatch_bypass_filter(origin)? }
 
 	cancel_queued {
 		let r in 1 .. MAX_REFERENDUMS;
@@ -534,6 +536,38 @@ benchmarks! {
 
 	}: _(RawOrigin::Root)
 
+	blacklist {
+		let p in 1 .. MAX_PROPOSALS;
+
+		// Place our proposal last, both in the public queue and as a referendum.
+		for i in 0 .. p - 1 {
+			add_proposal::<T>(i)?;
+		}
+		let ref_index = add_referendum::<T>(p - 1)?;
+		let proposal_hash = T::Hashing::hash_of(&(p - 1));
+
+		let origin = T::BlacklistOrigin::successful_origin();
+		let call = Call::<T>::blacklist(proposal_hash, Some(ref_index));
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Blacklist::<T>::get(proposal_hash).is_some());
+		assert!(Democracy::<T>::referendum_status(ref_index).is_err());
+	}
+
+	cancel_proposal {
+		let p in 1 .. MAX_PROPOSALS;
+
+		for i in 0 .. p {
+			add_proposal::<T>(i)?;
+		}
+
+		let origin = T::CancelProposalOrigin::successful_origin();
+		let call = Call::<T>::cancel_proposal(p - 1);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Democracy::<T>::deposit_of(p - 1).is_none());
+	}
+
 	note_preimage {
 		// Num of bytes in encoded proposal
 		let b in 0 .. MAX_BYTES;
@@ -792,6 +826,8 @@ mod tests {
 			assert_ok!(test_benchmark_delegate::<Test>());
 			assert_ok!(test_benchmark_undelegate::<Test>());
 			assert_ok!(test_benchmark_clear_public_proposals::<Test>());
+			assert_ok!(test_benchmark_blacklist::<Test>());
+			assert_ok!(test_benchmark_cancel_proposal::<Test>());
 			assert_ok!(test_benchmark_note_preimage::<Test>());
 			assert_ok!(test_benchmark_note_imminent_preimage::<Test>());
 			assert_ok!(test_benchmark_reap_preimage::<Test>());
@@ -143,9 +143,27 @@
 //!
 //! - `veto_external` - Vetoes and blacklists the external proposal hash.
 //!
-//! #### Root
+//! #### Blacklist Origin
+//!
+//! This call can only be made by the `BlacklistOrigin`.
+//!
+//! - `blacklist` - Permanently blacklists a proposal hash, removing any proposal or referendum
+//!   for it.
+//!
+//! #### Cancel Proposal Origin
+//!
+//! This call can only be made by the `CancelProposalOrigin`.
+//!
+//! - `cancel_proposal` - Removes a public proposal, slashing the deposits of its backers.
+//!
+//! #### Cancel Referendum Origin
+//!
+//! This call can only be made by the `CancelReferendumOrigin`.
 //!
 //! - `cancel_referendum` - Removes a referendum.
+//!
+//! #### Root
+//!
 //! - `cancel_queued` - Cancels a proposal that is queued for enactment.
 //! - `clear_public_proposal` - Removes all public proposals.
 
@@ -155,7 +173,7 @@
 use sp_std::prelude::*;
 use sp_runtime::{
 	DispatchResult, DispatchError, RuntimeDebug,
-	traits::{Zero, Hash, Dispatchable, Saturating},
+	traits::{Zero, Hash, Dispatchable, Saturating, Bounded},
 };
 use codec::{Encode, Decode, Input};
 use frame_support::{
@@ -163,7 +181,8 @@ use frame_support::{
 	weights::{Weight, DispatchClass},
 	traits::{
 		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
-		OnUnbalanced, BalanceStatus, schedule::Named as ScheduleNamed, EnsureOrigin
+		OnUnbalanced, BalanceStatus, schedule::Named as ScheduleNamed, EnsureOrigin,
+		PreimageProvider,
 	},
 	dispatch::DispatchResultWithPostInfo,
 };
@@ -229,6 +248,8 @@ pub trait WeightInfo {
 	fn remove_other_vote(r: u32, ) -> Weight;
 	fn enact_proposal_execute(b: u32, ) -> Weight;
 	fn enact_proposal_slash(b: u32, ) -> Weight;
+	fn blacklist(p: u32, ) -> Weight;
+	fn cancel_proposal(p: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -259,6 +280,8 @@ impl WeightInfo for () {
 	fn remove_other_vote(_r: u32, ) -> Weight { 1_000_000_000 }
 	fn enact_proposal_execute(_b: u32, ) -> Weight { 1_000_000_000 }
 	fn enact_proposal_slash(_b: u32, ) -> Weight { 1_000_000_000 }
+	fn blacklist(_p: u32, ) -> Weight { 1_000_000_000 }
+	fn cancel_proposal(_p: u32, ) -> Weight { 1_000_000_000 }
 }
 
 pub trait Trait: frame_system::Trait + Sized {
@@ -318,6 +341,15 @@ pub trait Trait: frame_system::Trait + Sized {
 	/// Origin from which any referendum may be cancelled in an emergency.
 	type CancellationOrigin: EnsureOrigin<Self::Origin>;
 
+	/// Origin from which proposals may be blacklisted.
+	type BlacklistOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin from which a proposal may be cancelled and its backers slashed.
+	type CancelProposalOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin from which an ongoing referendum may be removed.
+	type CancelReferendumOrigin: EnsureOrigin<Self::Origin>;
+
 	/// Origin for anyone able to veto proposals.
 	///
 	/// # Warning
@@ -341,6 +373,11 @@ pub trait Trait: frame_system::Trait + Sized {
 	/// The Scheduler.
 	type Scheduler: ScheduleNamed<Self::BlockNumber, Self::Proposal, Self::PalletsOrigin>;
 
+	/// Where to look up the preimage of a proposal which was not noted in this module.
+	///
+	/// The preimage of an approved proposal is requested from it until the proposal is enacted.
+	type Preimages: PreimageProvider<Self::Hash>;
+
 	/// Overarching type of all pallets origins.
 	type PalletsOrigin: From<system::RawOrigin<Self::AccountId>>;
 
@@ -408,6 +445,11 @@ decl_storage! {
 			map hasher(identity) T::Hash
 			=> Option<PreimageStatus<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
 
+		/// The preimage hash requested from `T::Preimages` for each referendum queued for
+		/// enactment. The request is released once the enactment happens or is cancelled.
+		pub RequestedPreimages get(fn requested_preimage):
+			map hasher(twox_64_concat) ReferendumIndex => Option<T::Hash>;
+
 		/// The next free referendum index, aka the number of referenda started so far.
 		pub ReferendumCount get(fn referendum_count) build(|_| 0 as ReferendumIndex): ReferendumIndex;
 		/// The lowest referendum index representing an unbaked referendum. Equal to
@@ -501,6 +543,8 @@ decl_event! {
 		PreimageReaped(Hash, AccountId, Balance, AccountId),
 		/// An account has been unlocked successfully.
 		Unlocked(AccountId),
+		/// A proposal (given hash) has been blacklisted permanently.
+		Blacklisted(Hash),
 	}
 }
 
@@ -679,15 +723,16 @@ decl_module! {
 		///
 		/// # <weight>
 		/// - Complexity: `O(1)`
-		/// - Db reads: `PublicPropCount`, `PublicProps`
+		/// - Db reads: `PublicPropCount`, `PublicProps`, `Blacklist`
 		/// - Db writes: `PublicPropCount`, `PublicProps`, `DepositOf`
 		/// -------------------
 		/// Base Weight: 42.58 + .127 * P µs with `P` the number of proposals `PublicProps`
 		/// # </weight>
-		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(2, 3)]
+		#[weight = 50_000_000 + 110_000 * MAX_VETOERS + T::DbWeight::get().reads_writes(3, 3)]
 		fn propose(origin, proposal_hash: T::Hash, #[compact] value: BalanceOf<T>) {
 			let who = ensure_signed(origin)?;
 			ensure!(value >= T::MinimumDeposit::get(), Error::<T>::ValueLow);
+			Self::ensure_not_blacklisted(&proposal_hash)?;
 
 			T::Currency::reserve(&who, value)?;
 
@@ -804,12 +849,7 @@ decl_module! {
 		fn external_propose(origin, proposal_hash: T::Hash) {
 			T::ExternalOrigin::ensure_origin(origin)?;
 			ensure!(!<NextExternal<T>>::exists(), Error::<T>::DuplicateProposal);
-			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
-				ensure!(
-					<frame_system::Module<T>>::block_number() >= until,
-					Error::<T>::ProposalBlacklisted,
-				);
-			}
+			Self::ensure_not_blacklisted(&proposal_hash)?;
 			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityApprove));
 		}
 
@@ -952,7 +992,7 @@ decl_module! {
 
 		/// Remove a referendum.
 		///
-		/// The dispatch origin of this call must be _Root_.
+		/// The dispatch origin of this call must be the `CancelReferendumOrigin`.
 		///
 		/// - `ref_index`: The index of the referendum to cancel.
 		///
@@ -963,10 +1003,88 @@ decl_module! {
 		/// # </weight>
 		#[weight = (22_000_000 + T::DbWeight::get().writes(1), DispatchClass::Operational)]
 		fn cancel_referendum(origin, #[compact] ref_index: ReferendumIndex) {
-			ensure_root(origin)?;
+			T::CancelReferendumOrigin::ensure_origin(origin)?;
 			Self::internal_cancel_referendum(ref_index);
 		}
 
+		/// Permanently place a proposal into the blacklist. This prevents it from ever being
+		/// proposed again.
+		///
+		/// If called on a queued public or external proposal, then this will result in it being
+		/// removed. If the `ref_index` supplied is an active referendum with the proposal hash,
+		/// then it will be cancelled.
+		///
+		/// The dispatch origin of this call must be `BlacklistOrigin`.
+		///
+		/// - `proposal_hash`: The proposal hash to blacklist permanently.
+		/// - `maybe_ref_index`: An ongoing referendum whose hash is `proposal_hash`, which will be
+		///   cancelled.
+		///
+		/// Emits `Blacklisted`.
+		///
+		/// # <weight>
+		/// - Complexity: `O(P + V)` where P is the number of public proposals and V the number of
+		///   vetoers of the blacklisted hash.
+		/// - Db reads: `PublicProps`, `DepositOf`, `NextExternal`, `ReferendumInfoOf`
+		/// - Db writes: `Blacklist`, `PublicProps`, `DepositOf`, `NextExternal`,
+		///   `ReferendumInfoOf`, backers' balances
+		/// # </weight>
+		#[weight = (
+			60_000_000 + 130_000 * MAX_VETOERS + T::DbWeight::get().reads_writes(4, 5),
+			DispatchClass::Operational,
+		)]
+		fn blacklist(origin, proposal_hash: T::Hash, maybe_ref_index: Option<ReferendumIndex>) {
+			T::BlacklistOrigin::ensure_origin(origin)?;
+
+			// Insert the proposal into the blacklist.
+			let permanent = (T::BlockNumber::max_value(), Vec::<T::AccountId>::new());
+			<Blacklist<T>>::insert(&proposal_hash, permanent);
+
+			// Remove the queued proposal, if it's there.
+			let maybe_prop_index = <PublicProps<T>>::get().into_iter()
+				.find(|(_, hash, _)| *hash == proposal_hash)
+				.map(|(index, ..)| index);
+			if let Some(prop_index) = maybe_prop_index {
+				Self::internal_cancel_proposal(prop_index);
+			}
+
+			// Remove the external queued referendum, if it's there.
+			if matches!(<NextExternal<T>>::get(), Some((h, ..)) if h == proposal_hash) {
+				<NextExternal<T>>::kill();
+			}
+
+			// Remove the referendum, if it's there.
+			if let Some(ref_index) = maybe_ref_index {
+				if let Ok(status) = Self::referendum_status(ref_index) {
+					if status.proposal_hash == proposal_hash {
+						Self::internal_cancel_referendum(ref_index);
+					}
+				}
+			}
+
+			Self::deposit_event(RawEvent::Blacklisted(proposal_hash));
+		}
+
+		/// Remove a proposal, slashing the deposits of its proposer and seconders.
+		///
+		/// The dispatch origin of this call must be `CancelProposalOrigin`.
+		///
+		/// - `prop_index`: The index of the proposal to cancel.
+		///
+		/// # <weight>
+		/// - Complexity: `O(P + S)` where P is the number of public proposals and S the number of
+		///   seconds of the proposal.
+		/// - Db reads: `PublicProps`, `DepositOf`
+		/// - Db writes: `PublicProps`, `DepositOf`, backers' balances
+		/// # </weight>
+		#[weight = (50_000_000 + T::DbWeight::get().reads_writes(2, 2), DispatchClass::Operational)]
+		fn cancel_proposal(origin, #[compact] prop_index: PropIndex) {
+			T::CancelProposalOrigin::ensure_origin(origin)?;
+			ensure!(<DepositOf<T>>::contains_key(prop_index), Error::<T>::ProposalMissing);
+
+			Self::internal_cancel_proposal(prop_index);
+		}
+
 		/// Cancel a proposal queued for enactment.
 		///
 		/// The dispatch origin of this call must be _Root_.
@@ -975,15 +1093,18 @@ decl_module! {
 		///
 		/// # <weight>
 		/// - `O(D)` where `D` is the items in the dispatch queue. Weighted as `D = 10`.
-		/// - Db reads: `scheduler lookup`, scheduler agenda`
-		/// - Db writes: `scheduler lookup`, scheduler agenda`
+		/// - Db reads: `scheduler lookup`, scheduler agenda`, `RequestedPreimages`, preimage
+		///   request
+		/// - Db writes: `scheduler lookup`, scheduler agenda`, `RequestedPreimages`, preimage
+		///   request
 		/// - Base Weight: 36.78 + 3.277 * D µs
 		/// # </weight>
-		#[weight = (68_000_000 + T::DbWeight::get().reads_writes(2, 2), DispatchClass::Operational)]
+		#[weight = (68_000_000 + T::DbWeight::get().reads_writes(4, 4), DispatchClass::Operational)]
 		fn cancel_queued(origin, which: ReferendumIndex) {
 			ensure_root(origin)?;
 			T::Scheduler::cancel_named((DEMOCRACY_ID, which).encode())
 				.map_err(|_| Error::<T>::ProposalMissing)?;
+			Self::release_preimage(which);
 		}
 
 		/// Weight: see `begin_block`
@@ -1349,6 +1470,27 @@ impl<T: Trait> Module<T> {
 		Self::ensure_ongoing(info)
 	}
 
+	/// Ok if `proposal_hash` is not blacklisted, or its blacklisting has expired.
+	fn ensure_not_blacklisted(proposal_hash: &T::Hash) -> DispatchResult {
+		if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+			ensure!(
+				<frame_system::Module<T>>::block_number() >= until,
+				Error::<T>::ProposalBlacklisted,
+			);
+		}
+		Ok(())
+	}
+
+	/// Remove a public proposal and slash the deposits of its backers.
+	fn internal_cancel_proposal(prop_index: PropIndex) {
+		<PublicProps<T>>::mutate(|props| props.retain(|p| p.0 != prop_index));
+		if let Some((whos, amount)) = <DepositOf<T>>::take(prop_index) {
+			for who in whos.into_iter() {
+				T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
+			}
+		}
+	}
+
 	/// Actually enact a vote, if legit.
 	fn try_vote(who: &T::AccountId, ref_index: ReferendumIndex, vote: AccountVote<BalanceOf<T>>) -> DispatchResult {
 		let mut status = Self::referendum_status(ref_index)?;
@@ -1645,27 +1787,56 @@ impl<T: Trait> Module<T> {
 	}
 
 	fn do_enact_proposal(proposal_hash: T::Hash, index: ReferendumIndex) -> DispatchResult {
-		let preimage = <Preimages<T>>::take(&proposal_hash);
-		if let Some(PreimageStatus::Available { data, provider, deposit, .. }) = preimage {
+		let maybe_available = match <Preimages<T>>::take(&proposal_hash) {
+			Some(PreimageStatus::Available { data, provider, deposit, .. }) =>
+				Some((data, provider, deposit)),
+			_ => None,
+		};
+		let result = if let Some((data, provider, deposit)) = maybe_available {
 			if let Ok(proposal) = T::Proposal::decode(&mut &data[..]) {
 				let _ = T::Currency::unreserve(&provider, deposit);
 				Self::deposit_event(RawEvent::PreimageUsed(proposal_hash, provider, deposit));
-
-				let ok = proposal.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
-				Self::deposit_event(RawEvent::Executed(index, ok));
-
+				Self::dispatch_proposal(proposal, index);
 				Ok(())
 			} else {
 				T::Slash::on_unbalanced(T::Currency::slash_reserved(&provider, deposit).0);
 				Self::deposit_event(RawEvent::PreimageInvalid(proposal_hash, index));
 				Err(Error::<T>::PreimageInvalid.into())
 			}
+		} else if let Some(data) = T::Preimages::get_preimage(&proposal_hash) {
+			// The provider took care of any deposit, there is nobody to slash here.
+			if let Ok(proposal) = T::Proposal::decode(&mut &data[..]) {
+				Self::dispatch_proposal(proposal, index);
+				Ok(())
+			} else {
+				Self::deposit_event(RawEvent::PreimageInvalid(proposal_hash, index));
+				Err(Error::<T>::PreimageInvalid.into())
+			}
 		} else {
 			Self::deposit_event(RawEvent::PreimageMissing(proposal_hash, index));
 			Err(Error::<T>::PreimageMissing.into())
+		};
+
+		Self::release_preimage(index);
+		result
+	}
+
+	/// Release the preimage request taken for the enactment of referendum `index`, if any.
+	///
+	/// Only requests taken by `bake_referendum` are released, so a Root `enact_proposal` or a
+	/// request another pallet holds for the same hash is left alone.
+	fn release_preimage(index: ReferendumIndex) {
+		if let Some(proposal_hash) = <RequestedPreimages<T>>::take(index) {
+			T::Preimages::unrequest_preimage(&proposal_hash);
 		}
 	}
 
+	/// Dispatch an enacted `proposal` of referendum `index` as Root.
+	fn dispatch_proposal(proposal: T::Proposal, index: ReferendumIndex) {
+		let ok = proposal.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
+		Self::deposit_event(RawEvent::Executed(index, ok));
+	}
+
 	fn bake_referendum(
 		now: T::BlockNumber,
 		index: ReferendumIndex,
@@ -1695,6 +1866,10 @@ impl<T: Trait> Module<T> {
 					Call::enact_proposal(status.proposal_hash, index).into(),
 				).is_err() {
 					frame_support::print("LOGIC ERROR: bake_referendum/schedule_named failed");
+				} else {
+					// Keep the preimage around at the provider until the proposal is enacted.
+					T::Preimages::request_preimage(&status.proposal_hash);
+					<RequestedPreimages<T>>::insert(index, status.proposal_hash);
 				}
 			}
 		} else {
@@ -31,7 +31,7 @@ use sp_runtime::{
 	testing::Header, Perbill,
 };
 use pallet_balances::{BalanceLock, Error as BalancesError};
-use frame_system::{EnsureSignedBy, EnsureRoot};
+use frame_system::{EnsureSignedBy, EnsureRoot, EnsureOneOf};
 
 mod cancellation;
 mod delegation;
@@ -140,6 +140,8 @@ impl pallet_balances::Trait for Test {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
 }
 parameter_types! {
 	pub const LaunchPeriod: u64 = 2;
@@ -169,6 +171,8 @@ impl Contains<u64> for OneToFive {
 thread_local! {
 	static PREIMAGE_BYTE_DEPOSIT: RefCell<u64> = RefCell::new(0);
 	static INSTANT_ALLOWED: RefCell<bool> = RefCell::new(false);
+	static PROVIDED_PREIMAGES: RefCell<Vec<(H256, Vec<u8>)>> = RefCell::new(vec![]);
+	static PREIMAGE_REQUESTS: RefCell<Vec<H256>> = RefCell::new(vec![]);
 }
 pub struct PreimageByteDeposit;
 impl Get<u64> for PreimageByteDeposit {
@@ -178,6 +182,41 @@ pub struct InstantAllowed;
 impl Get<bool> for InstantAllowed {
 	fn get() -> bool { INSTANT_ALLOWED.with(|v| *v.borrow()) }
 }
+/// A preimage provider which keeps its preimages and requests in thread local storage.
+pub struct TestPreimages;
+impl TestPreimages {
+	fn note(bytes: Vec<u8>) -> H256 {
+		let hash = BlakeTwo256::hash(&bytes[..]);
+		PROVIDED_PREIMAGES.with(|p| p.borrow_mut().push((hash, bytes)));
+		hash
+	}
+	fn requests(hash: &H256) -> usize {
+		PREIMAGE_REQUESTS.with(|r| r.borrow().iter().filter(|h| *h == hash).count())
+	}
+}
+impl PreimageProvider<H256> for TestPreimages {
+	fn have_preimage(hash: &H256) -> bool {
+		Self::get_preimage(hash).is_some()
+	}
+	fn get_preimage(hash: &H256) -> Option<Vec<u8>> {
+		PROVIDED_PREIMAGES.with(|p| {
+			p.borrow().iter().find(|(h, _)| h == hash).map(|(_, bytes)| bytes.clone())
+		})
+	}
+	fn preimage_requested(hash: &H256) -> bool {
+		Self::requests(hash) > 0
+	}
+	fn request_preimage(hash: &H256) {
+		PREIMAGE_REQUESTS.with(|r| r.borrow_mut().push(*hash));
+	}
+	fn unrequest_preimage(hash: &H256) {
+		PREIMAGE_REQUESTS.with(|r| {
+			let mut requests = r.borrow_mut();
+			let position = requests.iter().position(|h| h == hash).expect("was requested");
+			requests.remove(position);
+		});
+	}
+}
 impl super::Trait for Test {
 	type Proposal = Call;
 	type Event = Event;
@@ -192,6 +231,9 @@ impl super::Trait for Test {
 	type ExternalDefaultOrigin = EnsureSignedBy<One, u64>;
 	type FastTrackOrigin = EnsureSignedBy<Five, u64>;
 	type CancellationOrigin = EnsureSignedBy<Four, u64>;
+	type BlacklistOrigin = EnsureRoot<u64>;
+	type CancelProposalOrigin = EnsureRoot<u64>;
+	type CancelReferendumOrigin = EnsureOneOf<u64, EnsureRoot<u64>, EnsureSignedBy<Four, u64>>;
 	type VetoOrigin = EnsureSignedBy<OneToFive, u64>;
 	type CooloffPeriod = CooloffPeriod;
 	type PreimageByteDeposit = PreimageByteDeposit;
@@ -199,6 +241,7 @@ impl super::Trait for Test {
 	type InstantOrigin = EnsureSignedBy<Six, u64>;
 	type InstantAllowed = InstantAllowed;
 	type Scheduler = Scheduler;
+	type Preimages = TestPreimages;
 	type MaxVotes = MaxVotes;
 	type OperationalPreimageOrigin = EnsureSignedBy<Six, u64>;
 	type PalletsOrigin = OriginCaller;
@@ -38,6 +38,22 @@ fn cancel_referendum_should_work() {
 	});
 }
 
+#[test]
+fn cancel_referendum_should_require_the_origin() {
+	new_test_ext().execute_with(|| {
+		let r = Democracy::inject_referendum(
+			2,
+			set_balance_proposal_hash_and_note(2),
+			VoteThreshold::SuperMajorityApprove,
+			0
+		);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_noop!(Democracy::cancel_referendum(Origin::signed(1), r.into()), BadOrigin);
+		assert_ok!(Democracy::cancel_referendum(Origin::signed(4), r.into()));
+		assert_eq!(Democracy::referendum_info(r), None);
+	});
+}
+
 #[test]
 fn cancel_queued_should_work() {
 	new_test_ext().execute_with(|| {
@@ -90,3 +106,65 @@ fn emergency_cancel_should_work() {
 		);
 	});
 }
+
+#[test]
+fn cancel_proposal_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		assert_ok!(propose_set_balance_and_note(1, 2, 2));
+		assert_ok!(propose_set_balance_and_note(1, 4, 4));
+		assert_noop!(Democracy::cancel_proposal(Origin::signed(1), 0), BadOrigin);
+		assert_ok!(Democracy::cancel_proposal(Origin::root(), 0));
+		assert_noop!(Democracy::cancel_proposal(Origin::root(), 0), Error::<Test>::ProposalMissing);
+		assert_eq!(Democracy::backing_for(0), None);
+		assert_eq!(Democracy::backing_for(1), Some(4));
+		// the deposit of the cancelled proposal is slashed.
+		assert_eq!(Balances::free_balance(1), 4);
+		assert_eq!(Balances::total_balance(&1), 8);
+	});
+}
+
+#[test]
+fn blacklisting_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		let hash = set_balance_proposal_hash(2);
+
+		assert_ok!(propose_set_balance_and_note(1, 2, 2));
+		assert_ok!(propose_set_balance_and_note(1, 4, 4));
+
+		assert_noop!(Democracy::blacklist(Origin::signed(1), hash.clone(), None), BadOrigin);
+		assert_ok!(Democracy::blacklist(Origin::root(), hash, None));
+
+		assert_eq!(Democracy::backing_for(0), None);
+		assert_eq!(Democracy::backing_for(1), Some(4));
+
+		assert_noop!(propose_set_balance(1, 2, 2), Error::<Test>::ProposalBlacklisted);
+
+		fast_forward_to(2);
+
+		let hash = set_balance_proposal_hash(4);
+		assert!(Democracy::referendum_status(0).is_ok());
+		assert_ok!(Democracy::blacklist(Origin::root(), hash, Some(0)));
+		assert!(Democracy::referendum_info(0).is_none());
+	});
+}
+
+#[test]
+fn blacklisting_removes_external_proposal() {
+	new_test_ext().execute_with(|| {
+		let hash = set_balance_proposal_hash_and_note(2);
+		assert_ok!(Democracy::external_propose(Origin::signed(2), hash.clone()));
+		assert!(<NextExternal<Test>>::exists());
+
+		assert_ok!(Democracy::blacklist(Origin::root(), hash.clone(), None));
+		assert!(!<NextExternal<Test>>::exists());
+
+		// blacklisting is permanent, unlike a veto.
+		fast_forward_to(100);
+		assert_noop!(
+			Democracy::external_propose(Origin::signed(2), hash),
+			Error::<Test>::ProposalBlacklisted,
+		);
+	});
+}
@@ -164,3 +164,84 @@ fn reaping_imminent_preimage_should_fail() {
 		assert_noop!(Democracy::reap_preimage(Origin::signed(6), h, u32::max_value()), Error::<Test>::Imminent);
 	});
 }
+
+#[test]
+fn preimage_from_provider_should_be_requested_and_enacted() {
+	new_test_ext().execute_with(|| {
+		let h = TestPreimages::note(set_balance_proposal(2));
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 1);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+
+		next_block();
+		// Approved, but not enacted yet: the preimage is requested meanwhile.
+		assert_eq!(TestPreimages::requests(&h), 1);
+		assert_eq!(Balances::free_balance(42), 0);
+
+		next_block();
+		assert_eq!(TestPreimages::requests(&h), 0);
+		assert_eq!(Balances::free_balance(42), 2);
+	});
+}
+
+#[test]
+fn cancelling_queued_proposal_should_release_preimage_request() {
+	new_test_ext().execute_with(|| {
+		let h = TestPreimages::note(set_balance_proposal(2));
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 1);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+
+		next_block();
+		assert_eq!(TestPreimages::requests(&h), 1);
+		assert_eq!(Democracy::requested_preimage(r), Some(h));
+
+		assert_ok!(Democracy::cancel_queued(Origin::root(), r));
+		assert_eq!(TestPreimages::requests(&h), 0);
+		assert_eq!(Democracy::requested_preimage(r), None);
+	});
+}
+
+#[test]
+fn failing_to_schedule_enactment_should_not_request_preimage() {
+	new_test_ext().execute_with(|| {
+		let h = TestPreimages::note(set_balance_proposal(2));
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 1);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+
+		// Take the name the enactment would be scheduled under.
+		assert!(<Scheduler as ScheduleNamed<u64, Call, OriginCaller>>::schedule_named(
+			(DEMOCRACY_ID, r).encode(),
+			10,
+			None,
+			63,
+			frame_system::RawOrigin::Root.into(),
+			Call::System(frame_system::Call::remark(vec![])),
+		).is_ok());
+
+		next_block();
+		assert_eq!(TestPreimages::requests(&h), 0);
+		assert_eq!(Democracy::requested_preimage(r), None);
+	});
+}
+
+#[test]
+fn enactment_should_only_release_its_own_preimage_request() {
+	new_test_ext().execute_with(|| {
+		let h = TestPreimages::note(set_balance_proposal(2));
+		// Some other pallet wants the preimage kept around as well.
+		TestPreimages::request_preimage(&h);
+
+		// Root enacting the proposal directly took no request.
+		assert_ok!(Democracy::enact_proposal(Origin::root(), h, 0));
+		assert_eq!(Balances::free_balance(42), 2);
+		assert_eq!(TestPreimages::requests(&h), 1);
+
+		let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 1);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+
+		next_block();
+		assert_eq!(TestPreimages::requests(&h), 2);
+
+		next_block();
+		assert_eq!(TestPreimages::requests(&h), 1);
+	});
+}
@@ -35,9 +35,10 @@
 //!
 //! Voters can vote for any set of the candidates by providing a list of account ids. Invalid votes
 //! (voting for non-candidates) are ignored during election. Yet, a voter _might_ vote for a future
-//! candidate. Voters reserve a bond as they vote. Each vote defines a `value`. This amount is
-//! locked from the account of the voter and indicates the weight of the vote. Voters can update
-//! their votes at any time by calling `vote()` again. This keeps the bond untouched but can
+//! candidate. Voters reserve a bond as they vote, made of `VotingBondBase` plus `VotingBondFactor`
+//! for each candidate they vote for. Each vote defines a `value`. This amount is locked from the
+//! account of the voter and indicates the weight of the vote. Voters can update their votes at any
+//! time by calling `vote()` again. This adjusts the bond to the new number of votes and can
 //! optionally change the locked `value`. After a round, votes are kept and might still be valid for
 //! further rounds. A voter is responsible for calling `remove_voter` once they are done to have
 //! their bond back and remove the lock.
@@ -62,7 +63,8 @@
 //!     election.
 //!   - **Loser**: Any of the candidate who are not a winner are left as losers. A loser might be an
 //!     _outgoing member or runner_, meaning that they are an active member who failed to keep their
-//!     spot. An outgoing will always lose their bond.
+//!     spot. A loser forfeits their bond, unless the total stake of the voters approving them
+//!     reached `LoserCandidateRefundThreshold`, in which case the bond is returned.
 //!
 //! ##### Renouncing candidacy.
 //!
@@ -87,7 +89,7 @@ use codec::{Encode, Decode};
 use sp_std::prelude::*;
 use sp_runtime::{
 	DispatchError, RuntimeDebug, Perbill,
-	traits::{Zero, StaticLookup, Convert},
+	traits::{Zero, StaticLookup, Convert, Saturating},
 };
 use frame_support::{
 	decl_storage, decl_event, ensure, decl_module, decl_error,
@@ -97,7 +99,8 @@ use frame_support::{
 	traits::{
 		Currency, Get, LockableCurrency, LockIdentifier, ReservableCurrency, WithdrawReasons,
 		ChangeMembers, OnUnbalanced, WithdrawReason, Contains, BalanceStatus, InitializeMembers,
-		ContainsLengthBound,
+		ContainsLengthBound, OnRuntimeUpgrade, StorageVersion, GetStorageVersion,
+		VersionedMigration,
 	}
 };
 use sp_npos_elections::{build_support_map, ExtendedBalance, VoteWeight, ElectionResult};
@@ -108,6 +111,14 @@ mod benchmarking;
 /// The maximum votes allowed per voter.
 pub const MAXIMUM_VOTE: usize = 16;
 
+/// The current storage version of the Elections Phragmen pallet.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+frame_support::parameter_types! {
+	pub const V0: u16 = 0;
+	pub const V1: u16 = 1;
+}
+
 type BalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> =
@@ -124,6 +135,18 @@ pub enum Renouncing {
 	Candidate(#[codec(compact)] u32),
 }
 
+/// The votes of a voter, together with their locked stake and reserved deposit.
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct Voter<AccountId, Balance> {
+	/// The candidates being voted for.
+	pub votes: Vec<AccountId>,
+	/// The amount of stake locked for the votes.
+	pub stake: Balance,
+	/// The bond reserved when the votes were last submitted. This is what is returned, or
+	/// slashed, when the voter is removed.
+	pub deposit: Balance,
+}
+
 /// Information needed to prove the defunct-ness of a voter.
 #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
 pub struct DefunctVoter<AccountId> {
@@ -194,12 +217,19 @@ pub trait Trait: frame_system::Trait {
 	/// How much should be locked up in order to submit one's candidacy.
 	type CandidacyBond: Get<BalanceOf<Self>>;
 
-	/// How much should be locked up in order to be able to submit votes.
-	type VotingBond: Get<BalanceOf<Self>>;
+	/// Base deposit associated with voting.
+	type VotingBondBase: Get<BalanceOf<Self>>;
+
+	/// The amount of bond that needs to be locked for each vote.
+	type VotingBondFactor: Get<BalanceOf<Self>>;
 
 	/// Handler for the unbalanced reduction when a candidate has lost (and is not a runner-up)
 	type LoserCandidate: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+	/// The approval stake at or above which a losing candidate has their bond returned rather
+	/// than slashed.
+	type LoserCandidateRefundThreshold: Get<BalanceOf<Self>>;
+
 	/// Handler for the unbalanced reduction when a reporter has submitted a bad defunct report.
 	type BadReport: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
@@ -231,10 +261,11 @@ decl_storage! {
 		/// The total number of vote rounds that have happened, excluding the upcoming one.
 		pub ElectionRounds get(fn election_rounds): u32 = Zero::zero();
 
-		/// Votes and locked stake of a particular voter.
+		/// Votes, locked stake and reserved deposit of a particular voter.
 		///
 		/// TWOX-NOTE: SAFE as `AccountId` is a crypto hash
-		pub Voting get(fn voting): map hasher(twox_64_concat) T::AccountId => (BalanceOf<T>, Vec<T::AccountId>);
+		pub Voting get(fn voting):
+			map hasher(twox_64_concat) T::AccountId => Voter<T::AccountId, BalanceOf<T>>;
 
 		/// The present candidate list. Sorted based on account-id. A current member or runner-up
 		/// can never enter this vector and is always implicitly assumed to be a candidate.
@@ -242,6 +273,9 @@ decl_storage! {
 	} add_extra_genesis {
 		config(members): Vec<(T::AccountId, BalanceOf<T>)>;
 		build(|config: &GenesisConfig<T>| {
+			// New networks start with the current storage version.
+			STORAGE_VERSION.put::<Module<T>>();
+
 			let members = config.members.iter().map(|(ref member, ref stake)| {
 				// make sure they have enough stake
 				assert!(
@@ -325,7 +359,9 @@ decl_module! {
 		fn deposit_event() = default;
 
 		const CandidacyBond: BalanceOf<T> = T::CandidacyBond::get();
-		const VotingBond: BalanceOf<T> = T::VotingBond::get();
+		const VotingBondBase: BalanceOf<T> = T::VotingBondBase::get();
+		const VotingBondFactor: BalanceOf<T> = T::VotingBondFactor::get();
+		const LoserCandidateRefundThreshold: BalanceOf<T> = T::LoserCandidateRefundThreshold::get();
 		const DesiredMembers: u32 = T::DesiredMembers::get();
 		const DesiredRunnersUp: u32 = T::DesiredRunnersUp::get();
 		const TermDuration: T::BlockNumber = T::TermDuration::get();
@@ -335,7 +371,9 @@ decl_module! {
 		/// set the initial votes, or update already existing votes.
 		///
 		/// Upon initial voting, `value` units of `who`'s balance is locked and a bond amount is
-		/// reserved.
+		/// reserved. The bond is `VotingBondBase` plus `VotingBondFactor` for each vote, and is
+		/// adjusted whenever the votes are updated. The reserved bond is recorded with the votes,
+		/// so that changes to the bond only apply to votes submitted afterwards.
 		///
 		/// The `votes` should:
 		///   - not be empty.
@@ -379,10 +417,15 @@ decl_module! {
 
 			ensure!(value > T::Currency::minimum_balance(), Error::<T>::LowBalance);
 
-			// first time voter. Reserve bond.
-			if !Self::is_voter(&who) {
-				T::Currency::reserve(&who, T::VotingBond::get())
+			// reserve or return the difference between the old and the new bond. Voters who are
+			// new have no deposit yet.
+			let new_deposit = Self::voting_bond(votes.len());
+			let old_deposit = Voting::<T>::get(&who).deposit;
+			if new_deposit > old_deposit {
+				T::Currency::reserve(&who, new_deposit - old_deposit)
 					.map_err(|_| Error::<T>::UnableToPayBond)?;
+			} else if new_deposit < old_deposit {
+				T::Currency::unreserve(&who, old_deposit - new_deposit);
 			}
 
 			// Amount to be locked up.
@@ -396,7 +439,7 @@ decl_module! {
 				WithdrawReasons::except(WithdrawReason::TransactionPayment),
 			);
 
-			Voting::<T>::insert(&who, (locked_balance, votes));
+			Voting::<T>::insert(&who, Voter { votes, stake: locked_balance, deposit: new_deposit });
 		}
 
 		/// Remove `origin` as a voter. This removes the lock and returns the bond.
@@ -469,7 +512,7 @@ decl_module! {
 				Error::<T>::InvalidCandidateCount,
 			);
 
-			let (_, votes) = <Voting<T>>::get(&target);
+			let Voter { votes, deposit, .. } = <Voting<T>>::get(&target);
 			// indirect way to ensure target is a voter. We could call into `::contains()`, but it
 			// would have the same effect with one extra db access. Note that votes cannot be
 			// submitted with length 0. Hence, a non-zero length means that the target is a voter.
@@ -484,12 +527,13 @@ decl_module! {
 			let valid = Self::is_defunct_voter(&votes);
 			if valid {
 				// reporter will get the voting bond of the target
-				T::Currency::repatriate_reserved(&target, &reporter, T::VotingBond::get(), BalanceStatus::Free)?;
+				T::Currency::repatriate_reserved(&target, &reporter, deposit, BalanceStatus::Free)?;
 				// remove the target. They are defunct.
 				Self::do_remove_voter(&target, false);
 			} else {
 				// slash the bond of the reporter.
-				let imbalance = T::Currency::slash_reserved(&reporter, T::VotingBond::get()).0;
+				let bond = Voting::<T>::get(&reporter).deposit;
+				let imbalance = T::Currency::slash_reserved(&reporter, bond).0;
 				T::BadReport::on_unbalanced(imbalance);
 				// remove the reporter.
 				Self::do_remove_voter(&reporter, false);
@@ -845,17 +889,23 @@ impl<T: Trait> Module<T> {
 	/// DB access: Voting and Lock are always written to, if unreserve, then 1 read and write added.
 	fn do_remove_voter(who: &T::AccountId, unreserve: bool) {
 		// remove storage and lock.
-		Voting::<T>::remove(who);
+		let Voter { deposit, .. } = Voting::<T>::take(who);
 		T::Currency::remove_lock(T::ModuleId::get(), who);
 
 		if unreserve {
-			T::Currency::unreserve(who, T::VotingBond::get());
+			T::Currency::unreserve(who, deposit);
 		}
 	}
 
 	/// The locked stake of a voter.
 	fn locked_stake_of(who: &T::AccountId) -> BalanceOf<T> {
-		Voting::<T>::get(who).0
+		Voting::<T>::get(who).stake
+	}
+
+	/// The bond reserved by a voter who voted for `count` candidates.
+	fn voting_bond(count: usize) -> BalanceOf<T> {
+		let factor = T::VotingBondFactor::get().saturating_mul((count as u32).into());
+		T::VotingBondBase::get().saturating_add(factor)
 	}
 
 	/// Check there's nothing to do this block.
@@ -905,7 +955,7 @@ impl<T: Trait> Module<T> {
 		};
 
 		let voters_and_votes = Voting::<T>::iter()
-			.map(|(voter, (stake, targets))| { (voter, to_votes(stake), targets) })
+			.map(|(voter, Voter { stake, votes, .. })| { (voter, to_votes(stake), votes) })
 			.collect::<Vec<_>>();
 		let maybe_phragmen_result = sp_npos_elections::seq_phragmen::<T::AccountId, Perbill>(
 			num_to_elect,
@@ -966,7 +1016,7 @@ impl<T: Trait> Module<T> {
 			new_members.sort_by(|i, j| i.0.cmp(&j.0));
 
 			let mut prime_votes: Vec<_> = new_members.iter().map(|c| (&c.0, VoteWeight::zero())).collect();
-			for (_, stake, targets) in voters_and_votes.into_iter() {
+			for (_, stake, targets) in voters_and_votes.iter() {
 				for (votes, who) in targets.iter()
 					.enumerate()
 					.map(|(votes, who)| ((MAXIMUM_VOTE - votes) as u32, who))
@@ -1007,7 +1057,26 @@ impl<T: Trait> Module<T> {
 			);
 			T::ChangeMembers::set_prime(prime);
 
-			// outgoing candidates lose their bond.
+			// the total stake of the voters approving a candidate. Well backed losers are spared
+			// their bond.
+			let approval_stake_of = |who: &T::AccountId| -> BalanceOf<T> {
+				let approval = voters_and_votes.iter()
+					.filter(|(_, _, targets)| targets.contains(who))
+					.fold(0 as ExtendedBalance, |acc, (_, stake, _)| {
+						acc.saturating_add(ExtendedBalance::from(*stake))
+					});
+				to_balance(approval)
+			};
+			let settle_loser_bond = |loser: &T::AccountId| {
+				if approval_stake_of(loser) >= T::LoserCandidateRefundThreshold::get() {
+					T::Currency::unreserve(loser, T::CandidacyBond::get());
+				} else {
+					let (imbalance, _) = T::Currency::slash_reserved(loser, T::CandidacyBond::get());
+					T::LoserCandidate::on_unbalanced(imbalance);
+				}
+			};
+
+			// outgoing candidates lose their bond, unless they are well backed.
 			let mut to_burn_bond = outgoing.to_vec();
 
 			// compute the outgoing of runners up as well and append them to the `to_burn_bond`
@@ -1019,7 +1088,7 @@ impl<T: Trait> Module<T> {
 				to_burn_bond.extend(outgoing);
 			}
 
-			// Burn loser bond. members list is sorted. O(NLogM) (N candidates, M members)
+			// Settle loser bond. members list is sorted. O(NLogM) (N candidates, M members)
 			// runner up list is not sorted. O(K*N) given K runner ups. Overall: O(NLogM + N*K)
 			// both the member and runner counts are bounded.
 			exposed_candidates.into_iter().for_each(|c| {
@@ -1027,16 +1096,12 @@ impl<T: Trait> Module<T> {
 				if new_members.binary_search_by_key(&c, |(m, _)| m.clone()).is_err()
 					&& !new_runners_up_ids.contains(&c)
 				{
-					let (imbalance, _) = T::Currency::slash_reserved(&c, T::CandidacyBond::get());
-					T::LoserCandidate::on_unbalanced(imbalance);
+					settle_loser_bond(&c);
 				}
 			});
 
-			// Burn outgoing bonds
-			to_burn_bond.into_iter().for_each(|x| {
-				let (imbalance, _) = T::Currency::slash_reserved(&x, T::CandidacyBond::get());
-				T::LoserCandidate::on_unbalanced(imbalance);
-			});
+			// Settle outgoing bonds
+			to_burn_bond.iter().for_each(settle_loser_bond);
 
 			<Members<T>>::put(&new_members);
 			<RunnersUp<T>>::put(new_runners_up);
@@ -1081,6 +1146,44 @@ impl<T: Trait> ContainsLengthBound for Module<T> {
 	}
 }
 
+impl<T: Trait> GetStorageVersion for Module<T> {
+	fn storage_prefix() -> &'static [u8] {
+		b"PhragmenElection"
+	}
+
+	fn current_storage_version() -> StorageVersion {
+		STORAGE_VERSION
+	}
+}
+
+/// Migrate every voter from a `(stake, votes)` pair to a `Voter`, recording `OldVotingBond` as
+/// its deposit: that is the flat bond which voters reserved before deposits depended on the
+/// number of votes.
+///
+/// The pallet does not know the old bond, so the runtime has to run this migration itself, see
+/// `MigrateToV1`.
+pub struct MigrateToVoterDeposits<T, OldVotingBond>(
+	sp_std::marker::PhantomData<(T, OldVotingBond)>
+);
+
+impl<T: Trait, OldVotingBond: Get<BalanceOf<T>>> OnRuntimeUpgrade
+	for MigrateToVoterDeposits<T, OldVotingBond>
+{
+	fn on_runtime_upgrade() -> Weight {
+		let mut translated: Weight = 0;
+		Voting::<T>::translate::<(BalanceOf<T>, Vec<T::AccountId>), _>(|_, (stake, votes)| {
+			translated += 1;
+			Some(Voter { votes, stake, deposit: OldVotingBond::get() })
+		});
+		T::DbWeight::get().reads_writes(translated, translated)
+	}
+}
+
+/// Take the pallet from storage version `0` to `1`, see `MigrateToVoterDeposits`.
+pub type MigrateToV1<T, OldVotingBond> = VersionedMigration<
+	V0, V1, MigrateToVoterDeposits<T, OldVotingBond>, Module<T>, <T as frame_system::Trait>::DbWeight
+>;
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1142,6 +1245,8 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = frame_system::Module<Test>;
 		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
 	}
 
 	parameter_types! {
@@ -1149,15 +1254,27 @@ mod tests {
 	}
 
 	thread_local! {
-		static VOTING_BOND: RefCell<u64> = RefCell::new(2);
+		static VOTING_BOND_BASE: RefCell<u64> = RefCell::new(2);
+		static VOTING_BOND_FACTOR: RefCell<u64> = RefCell::new(0);
+		static LOSER_REFUND_THRESHOLD: RefCell<u64> = RefCell::new(u64::max_value());
 		static DESIRED_MEMBERS: RefCell<u32> = RefCell::new(2);
 		static DESIRED_RUNNERS_UP: RefCell<u32> = RefCell::new(2);
 		static TERM_DURATION: RefCell<u64> = RefCell::new(5);
 	}
 
-	pub struct VotingBond;
-	impl Get<u64> for VotingBond {
-		fn get() -> u64 { VOTING_BOND.with(|v| *v.borrow()) }
+	pub struct VotingBondBase;
+	impl Get<u64> for VotingBondBase {
+		fn get() -> u64 { VOTING_BOND_BASE.with(|v| *v.borrow()) }
+	}
+
+	pub struct VotingBondFactor;
+	impl Get<u64> for VotingBondFactor {
+		fn get() -> u64 { VOTING_BOND_FACTOR.with(|v| *v.borrow()) }
+	}
+
+	pub struct LoserCandidateRefundThreshold;
+	impl Get<u64> for LoserCandidateRefundThreshold {
+		fn get() -> u64 { LOSER_REFUND_THRESHOLD.with(|v| *v.borrow()) }
 	}
 
 	pub struct DesiredMembers;
@@ -1243,11 +1360,13 @@ mod tests {
 		type ChangeMembers = TestChangeMembers;
 		type InitializeMembers = ();
 		type CandidacyBond = CandidacyBond;
-		type VotingBond = VotingBond;
+		type VotingBondBase = VotingBondBase;
+		type VotingBondFactor = VotingBondFactor;
 		type TermDuration = TermDuration;
 		type DesiredMembers = DesiredMembers;
 		type DesiredRunnersUp = DesiredRunnersUp;
 		type LoserCandidate = ();
+		type LoserCandidateRefundThreshold = LoserCandidateRefundThreshold;
 		type KickedMember = ();
 		type BadReport = ();
 		type WeightInfo = ();
@@ -1272,6 +1391,8 @@ mod tests {
 		genesis_members: Vec<(u64, u64)>,
 		balance_factor: u64,
 		voter_bond: u64,
+		voter_bond_factor: u64,
+		loser_refund_threshold: u64,
 		term_duration: u64,
 		desired_runners_up: u32,
 		desired_members: u32,
@@ -1283,6 +1404,8 @@ mod tests {
 				genesis_members: vec![],
 				balance_factor: 1,
 				voter_bond: 2,
+				voter_bond_factor: 0,
+				loser_refund_threshold: u64::max_value(),
 				term_duration: 5,
 				desired_runners_up: 0,
 				desired_members: 2,
@@ -1295,6 +1418,14 @@ mod tests {
 			self.voter_bond = fee;
 			self
 		}
+		pub fn voter_bond_factor(mut self, fee: u64) -> Self {
+			self.voter_bond_factor = fee;
+			self
+		}
+		pub fn loser_refund_threshold(mut self, threshold: u64) -> Self {
+			self.loser_refund_threshold = threshold;
+			self
+		}
 		pub fn desired_runners_up(mut self, count: u32) -> Self {
 			self.desired_runners_up = count;
 			self
@@ -1317,7 +1448,9 @@ mod tests {
 			self
 		}
 		fn set_constants(&self) {
-			VOTING_BOND.with(|v| *v.borrow_mut() = self.voter_bond);
+			VOTING_BOND_BASE.with(|v| *v.borrow_mut() = self.voter_bond);
+			VOTING_BOND_FACTOR.with(|v| *v.borrow_mut() = self.voter_bond_factor);
+			LOSER_REFUND_THRESHOLD.with(|v| *v.borrow_mut() = self.loser_refund_threshold);
 			TERM_DURATION.with(|v| *v.borrow_mut() = self.term_duration);
 			DESIRED_RUNNERS_UP.with(|v| *v.borrow_mut() = self.desired_runners_up);
 			DESIRED_MEMBERS.with(|m| *m.borrow_mut() = self.desired_members);
@@ -1419,7 +1552,7 @@ mod tests {
 	}
 
 	fn votes_of(who: &u64) -> Vec<u64> {
-		Voting::<Test>::get(who).1
+		Voting::<Test>::get(who).votes
 	}
 
 	fn defunct_for(who: u64) -> DefunctVoter<u64> {
@@ -1455,8 +1588,8 @@ mod tests {
 			System::set_block_number(1);
 			assert_eq!(Elections::members(), vec![(1, 10), (2, 20)]);
 
-			assert_eq!(Elections::voting(1), (10, vec![1]));
-			assert_eq!(Elections::voting(2), (20, vec![2]));
+			assert_eq!(Elections::voting(1), Voter { votes: vec![1], stake: 10, deposit: 2 });
+			assert_eq!(Elections::voting(2), Voter { votes: vec![2], stake: 20, deposit: 2 });
 
 			// they will persist since they have self vote.
 			System::set_block_number(5);
@@ -1472,8 +1605,8 @@ mod tests {
 			System::set_block_number(1);
 			assert_eq!(Elections::members(), vec![(1, 10), (2, 20)]);
 
-			assert_eq!(Elections::voting(1), (10, vec![1]));
-			assert_eq!(Elections::voting(2), (20, vec![2]));
+			assert_eq!(Elections::voting(1), Voter { votes: vec![1], stake: 10, deposit: 2 });
+			assert_eq!(Elections::voting(2), Voter { votes: vec![2], stake: 20, deposit: 2 });
 
 			// they will persist since they have self vote.
 			System::set_block_number(5);
@@ -2306,6 +2439,102 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn well_backed_losers_keep_the_bond() {
+		ExtBuilder::default().loser_refund_threshold(30).build_and_execute(|| {
+			assert_ok!(submit_candidacy(Origin::signed(5)));
+			assert_ok!(submit_candidacy(Origin::signed(4)));
+			assert_ok!(submit_candidacy(Origin::signed(3)));
+			assert_ok!(submit_candidacy(Origin::signed(2)));
+
+			assert_ok!(vote(Origin::signed(5), vec![5], 50));
+			assert_ok!(vote(Origin::signed(4), vec![4], 40));
+			assert_ok!(vote(Origin::signed(3), vec![3], 30));
+			assert_ok!(vote(Origin::signed(2), vec![2], 20));
+
+			System::set_block_number(5);
+			Elections::end_block(System::block_number());
+
+			assert_eq!(Elections::members_ids(), vec![4, 5]);
+
+			// loser backed by enough stake gets the bond back.
+			assert_eq!(balances(&3), (28, 2));
+			// loser below the threshold is slashed.
+			assert_eq!(balances(&2), (15, 2));
+		});
+	}
+
+	#[test]
+	fn voting_bond_scales_with_votes() {
+		ExtBuilder::default().voter_bond_factor(1).build_and_execute(|| {
+			assert_ok!(submit_candidacy(Origin::signed(5)));
+			assert_ok!(submit_candidacy(Origin::signed(4)));
+			assert_ok!(submit_candidacy(Origin::signed(3)));
+
+			assert_ok!(vote(Origin::signed(2), vec![5], 10));
+			assert_eq!(balances(&2), (17, 3));
+
+			assert_ok!(vote(Origin::signed(2), vec![5, 4, 3], 10));
+			assert_eq!(balances(&2), (15, 5));
+
+			assert_ok!(vote(Origin::signed(2), vec![4], 10));
+			assert_eq!(balances(&2), (17, 3));
+
+			assert_ok!(Elections::remove_voter(Origin::signed(2)));
+			assert_eq!(balances(&2), (20, 0));
+		});
+	}
+
+	#[test]
+	fn stored_deposit_is_returned_after_bond_change() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(submit_candidacy(Origin::signed(5)));
+			assert_ok!(vote(Origin::signed(2), vec![5], 10));
+			assert_eq!(Elections::voting(2).deposit, 2);
+			assert_eq!(balances(&2), (18, 2));
+
+			// the bond changes, the voter keeps the deposit they reserved.
+			VOTING_BOND_BASE.with(|v| *v.borrow_mut() = 5);
+			assert_ok!(vote(Origin::signed(3), vec![5], 10));
+			assert_eq!(balances(&3), (25, 5));
+			assert_ok!(Elections::remove_voter(Origin::signed(2)));
+			assert_eq!(balances(&2), (20, 0));
+
+			// updating the votes reserves the difference to the new bond.
+			VOTING_BOND_BASE.with(|v| *v.borrow_mut() = 3);
+			assert_ok!(vote(Origin::signed(3), vec![5], 10));
+			assert_eq!(Elections::voting(3).deposit, 3);
+			assert_eq!(balances(&3), (27, 3));
+		});
+	}
+
+	parameter_types! {
+		pub const OldVotingBond: u64 = 3;
+	}
+
+	#[test]
+	fn migration_records_old_voting_bond() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(submit_candidacy(Origin::signed(5)));
+			// a voter from before deposits were stored, who reserved the old flat bond.
+			let key = Voting::<Test>::hashed_key_for(&2);
+			frame_support::storage::unhashed::put(&key, &(10u64, vec![5u64]));
+			assert_ok!(Balances::reserve(&2, 3));
+			StorageVersion::new(0).put::<Elections>();
+
+			MigrateToV1::<Test, OldVotingBond>::on_runtime_upgrade();
+			assert_eq!(Elections::voting(2), Voter { votes: vec![5], stake: 10, deposit: 3 });
+			assert_eq!(Elections::on_chain_storage_version(), 1);
+
+			// running it again does nothing.
+			MigrateToV1::<Test, OldVotingBond>::on_runtime_upgrade();
+			assert_eq!(Elections::voting(2), Voter { votes: vec![5], stake: 10, deposit: 3 });
+
+			assert_ok!(Elections::remove_voter(Origin::signed(2)));
+			assert_eq!(balances(&2), (20, 0));
+		});
+	}
+
 	#[test]
 	fn current_members_are_always_next_candidate() {
 		ExtBuilder::default().build_and_execute(|| {
@@ -780,6 +780,8 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
 	}
 	impl Trait for Test {
 		type Event = ();
@@ -118,7 +118,7 @@
 use sp_std::{prelude::*, marker::PhantomData};
 use frame_support::{
 	storage::StorageValue, weights::{GetDispatchInfo, DispatchInfo, DispatchClass},
-	traits::{OnInitialize, OnFinalize, OnRuntimeUpgrade, OffchainWorker},
+	traits::{OnInitialize, OnIdle, OnFinalize, OnRuntimeUpgrade, OffchainWorker},
 	dispatch::PostDispatchInfo,
 };
 use sp_runtime::{
@@ -165,6 +165,7 @@ impl<
 	AllModules:
 		OnRuntimeUpgrade +
 		OnInitialize<System::BlockNumber> +
+		OnIdle<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
 		OffchainWorker<System::BlockNumber>,
 	COnRuntimeUpgrade: OnRuntimeUpgrade,
@@ -192,6 +193,7 @@ impl<
 	AllModules:
 		OnRuntimeUpgrade +
 		OnInitialize<System::BlockNumber> +
+		OnIdle<System::BlockNumber> +
 		OnFinalize<System::BlockNumber> +
 		OffchainWorker<System::BlockNumber>,
 	COnRuntimeUpgrade: OnRuntimeUpgrade,
@@ -313,6 +315,26 @@ where
 
 		// post-extrinsics book-keeping
 		<frame_system::Module<System>>::note_finished_extrinsics();
+		Self::idle_and_finalize_hook(block_number);
+	}
+
+	/// Call `on_idle` with the weight left in the block, followed by `on_finalize`.
+	fn idle_and_finalize_hook(block_number: NumberFor<Block>) {
+		let weight = <frame_system::Module<System>>::block_weight();
+		let max_weight = <System::MaximumBlockWeight as frame_support::traits::Get<_>>::get();
+		let remaining_weight = max_weight.saturating_sub(weight.total());
+
+		if remaining_weight > 0 {
+			let used_weight = <AllModules as OnIdle<System::BlockNumber>>::on_idle(
+				block_number,
+				remaining_weight,
+			);
+			<frame_system::Module<System>>::register_extra_weight_unchecked(
+				used_weight,
+				DispatchClass::Mandatory,
+			);
+		}
+
 		<frame_system::Module<System> as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
 		<AllModules as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
 	}
@@ -322,8 +344,7 @@ where
 	pub fn finalize_block() -> System::Header {
 		<frame_system::Module<System>>::note_finished_extrinsics();
 		let block_number = <frame_system::Module<System>>::block_number();
-		<frame_system::Module<System> as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
-		<AllModules as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
+		Self::idle_and_finalize_hook(block_number);
 
 		// set up extrinsics
 		<frame_system::Module<System>>::derive_extrinsics();
@@ -585,6 +606,8 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
 	}
 
 	parameter_types! {
@@ -713,7 +736,7 @@ mod tests {
 				header: Header {
 					parent_hash: [69u8; 32].into(),
 					number: 1,
-					state_root: hex!("e8ff7b3dd4375f6f3a76e24a1999e2a7be2d15b353e49ac94ace1eae3e80eb87").into(),
+					state_root: hex!("4b1d7e54bcc199b6e69139f5d78f110585446c2ced0fec3e01d48ea598e6dc38").into(),
 					extrinsics_root: hex!("03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314").into(),
 					digest: Digest { logs: vec![], },
 				},
@@ -781,8 +804,9 @@ mod tests {
 		let xt = TestXt::new(Call::Balances(BalancesCall::transfer(33, 0)), sign_extra(1, 0, 0));
 		let encoded = xt.encode();
 		let encoded_len = encoded.len() as Weight;
-		// Block execution weight + on_initialize weight
-		let base_block_weight = 175 + <Runtime as frame_system::Trait>::BlockExecutionWeight::get();
+		// Block execution weight + on_initialize weight, including the balances migration check
+		let base_block_weight = 175 + <Runtime as frame_system::Trait>::BlockExecutionWeight::get()
+			+ DbWeight::get().reads(1);
 		let limit = AvailableBlockRatio::get() * MaximumBlockWeight::get() - base_block_weight;
 		let num_to_exhaust_block = limit / (encoded_len + 5);
 		t.execute_with(|| {
@@ -793,7 +817,8 @@ mod tests {
 				[69u8; 32].into(),
 				Digest::default(),
 			));
-			// Base block execution weight + `on_initialize` weight from the custom module.
+			// Base block execution weight + `on_initialize` weight from the custom and balances
+			// modules.
 			assert_eq!(<frame_system::Module<Runtime>>::block_weight().total(), base_block_weight);
 
 			for nonce in 0..=num_to_exhaust_block {
@@ -824,8 +849,10 @@ mod tests {
 		let len = xt.clone().encode().len() as u32;
 		let mut t = new_test_ext(1);
 		t.execute_with(|| {
-			// Block execution weight + on_initialize weight from custom module
-			let base_block_weight = 175 + <Runtime as frame_system::Trait>::BlockExecutionWeight::get();
+			// Block execution weight + on_initialize weight from the custom and balances modules
+			let base_block_weight = 175
+				+ <Runtime as frame_system::Trait>::BlockExecutionWeight::get()
+				+ DbWeight::get().reads(1);
 
 			Executive::initialize_block(&Header::new(
 				1,
@@ -934,9 +961,10 @@ mod tests {
 
 			Executive::initialize_block(&Header::new_from_number(1));
 			// NOTE: might need updates over time if new weights are introduced.
-			// For now it only accounts for the base block execution weight and
-			// the `on_initialize` weight defined in the custom test module.
-			assert_eq!(<frame_system::Module<Runtime>>::block_weight().total(), 175 + 10);
+			// For now it only accounts for the base block execution weight, the
+			// `on_initialize` weight defined in the custom test module and the read
+			// of the existential deposit migration in balances.
+			assert_eq!(<frame_system::Module<Runtime>>::block_weight().total(), 175 + 10 + 10);
 		})
 	}
 
@@ -172,6 +172,39 @@ benchmarks! {
 
 	}: _(RawOrigin::Signed(caller), subs)
 
+	add_sub {
+		let caller = account::<T>("caller", 0);
+
+		// Give them s many sub accounts.
+		let s in 1 .. T::MaxSubAccounts::get() - 1 => ();
+		let _ = add_sub_accounts::<T>(&caller, s)?;
+
+		let sub = T::Lookup::unlookup(account::<T>("new_sub", 0));
+		let data = Data::Raw(vec![0; 32]);
+	}: _(RawOrigin::Signed(caller), sub, data)
+
+	rename_sub {
+		let caller = account::<T>("caller", 0);
+
+		let s in 1 .. T::MaxSubAccounts::get() => ();
+		let (sub, _) = add_sub_accounts::<T>(&caller, s)?.remove(0);
+		let data = Data::Raw(vec![1; 32]);
+	}: _(RawOrigin::Signed(caller), T::Lookup::unlookup(sub), data)
+
+	remove_sub {
+		let caller = account::<T>("caller", 0);
+
+		let s in 1 .. T::MaxSubAccounts::get() => ();
+		let (sub, _) = add_sub_accounts::<T>(&caller, s)?.remove(0);
+	}: _(RawOrigin::Signed(caller), T::Lookup::unlookup(sub))
+
+	quit_sub {
+		let caller = account::<T>("caller", 0);
+
+		let s in 1 .. T::MaxSubAccounts::get() => ();
+		let (sub, _) = add_sub_accounts::<T>(&caller, s)?.remove(0);
+	}: _(RawOrigin::Signed(sub))
+
 	clear_identity {
 		let caller = account::<T>("caller", 0);
 		let caller_origin = <T as frame_system::Trait>::Origin::from(RawOrigin::Signed(caller.clone()));
@@ -299,6 +332,10 @@ mod tests {
 			assert_ok!(test_benchmark_add_registrar::<Test>());
 			assert_ok!(test_benchmark_set_identity::<Test>());
 			assert_ok!(test_benchmark_set_subs::<Test>());
+			assert_ok!(test_benchmark_add_sub::<Test>());
+			assert_ok!(test_benchmark_rename_sub::<Test>());
+			assert_ok!(test_benchmark_remove_sub::<Test>());
+			assert_ok!(test_benchmark_quit_sub::<Test>());
 			assert_ok!(test_benchmark_clear_identity::<Test>());
 			assert_ok!(test_benchmark_request_judgement::<Test>());
 			assert_ok!(test_benchmark_cancel_request::<Test>());
@@ -48,6 +48,10 @@
 //! * `set_identity` - Set the associated identity of an account; a small deposit is reserved if not
 //!   already taken.
 //! * `set_subs` - Set the sub-accounts of an identity.
+//! * `add_sub` - Add a sub-identity to an identity.
+//! * `rename_sub` - Rename a sub-identity.
+//! * `remove_sub` - Remove a sub-identity of an identity.
+//! * `quit_sub` - Remove an account as a sub-identity of its super-identity, taking its deposit.
 //! * `clear_identity` - Remove an account's associated identity; the deposit is returned.
 //! * `request_judgement` - Request a judgement from a registrar, paying a fee.
 //! * `cancel_request` - Cancel the previous request for a judgement.
@@ -71,7 +75,7 @@ use sp_std::{fmt::Debug, ops::Add, iter::once};
 use enumflags2::BitFlags;
 use codec::{Encode, Decode};
 use sp_runtime::{DispatchError, RuntimeDebug};
-use sp_runtime::traits::{StaticLookup, Zero, AppendZerosInput};
+use sp_runtime::traits::{StaticLookup, Zero, AppendZerosInput, Saturating};
 use frame_support::{
 	decl_module, decl_event, decl_storage, ensure, decl_error,
 	dispatch::DispatchResultWithPostInfo,
@@ -97,6 +101,10 @@ pub trait WeightInfo {
 	fn set_fields(r: u32, ) -> Weight;
 	fn provide_judgement(r: u32, x: u32, ) -> Weight;
 	fn kill_identity(r: u32, s: u32, x: u32, ) -> Weight;
+	fn add_sub(s: u32, ) -> Weight;
+	fn rename_sub(s: u32, ) -> Weight;
+	fn remove_sub(s: u32, ) -> Weight;
+	fn quit_sub(s: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -111,6 +119,10 @@ impl WeightInfo for () {
 	fn set_fields(_r: u32, ) -> Weight { 1_000_000_000 }
 	fn provide_judgement(_r: u32, _x: u32, ) -> Weight { 1_000_000_000 }
 	fn kill_identity(_r: u32, _s: u32, _x: u32, ) -> Weight { 1_000_000_000 }
+	fn add_sub(_s: u32, ) -> Weight { 1_000_000_000 }
+	fn rename_sub(_s: u32, ) -> Weight { 1_000_000_000 }
+	fn remove_sub(_s: u32, ) -> Weight { 1_000_000_000 }
+	fn quit_sub(_s: u32, ) -> Weight { 1_000_000_000 }
 }
 
 pub trait Trait: frame_system::Trait {
@@ -462,6 +474,13 @@ decl_event!(
 		JudgementGiven(AccountId, RegistrarIndex),
 		/// A registrar was added.
 		RegistrarAdded(RegistrarIndex),
+		/// A sub-identity (first) was added to an identity (second) and the deposit paid.
+		SubIdentityAdded(AccountId, AccountId, Balance),
+		/// A sub-identity (first) was removed from an identity (second) and the deposit freed.
+		SubIdentityRemoved(AccountId, AccountId, Balance),
+		/// A sub-identity (first) was cleared, and the given deposit repatriated from the
+		/// main identity account (second) to the sub-identity account.
+		SubIdentityRevoked(AccountId, AccountId, Balance),
 	}
 );
 
@@ -494,6 +513,12 @@ decl_error! {
 		TooManyFields,
 		/// Maximum amount of registrars reached. Cannot add any more.
 		TooManyRegistrars,
+		/// Account ID is already named as a sub-identity.
+		AlreadyClaimed,
+		/// Sender is not a sub-account.
+		NotSub,
+		/// Sub-account isn't owned by sender.
+		NotOwned,
 }
 }
 
@@ -545,6 +570,27 @@ mod weight_for {
 			.saturating_add(subs.saturating_mul(3_700_000)) // S
 	}
 
+	/// Weight calculation for `add_sub`, given the number of existing sub-accounts.
+	pub(crate) fn add_sub<T: Trait>(subs: Weight) -> Weight {
+		T::DbWeight::get().reads_writes(3, 2) // `IdentityOf`, `SuperOf`, `SubsOf` + balance op
+			+ 42_000_000 // constant
+			+ 400_000 * subs // S
+	}
+
+	/// Weight calculation for `rename_sub`.
+	pub(crate) fn rename_sub<T: Trait>() -> Weight {
+		T::DbWeight::get().reads_writes(2, 1) // `IdentityOf`, `SuperOf`
+			+ 15_000_000 // constant
+	}
+
+	/// Weight calculation for `remove_sub` and `quit_sub`, given the number of existing
+	/// sub-accounts.
+	pub(crate) fn remove_sub<T: Trait>(subs: Weight) -> Weight {
+		T::DbWeight::get().reads_writes(3, 3) // `SuperOf`, `SubsOf` + balance ops
+			+ 42_000_000 // constant
+			+ 400_000 * subs // S
+	}
+
 	/// Weight calculation for `clear_identity`.
 	///
 	/// Based on benchmark:
@@ -803,6 +849,145 @@ decl_module! {
 			)).into())
 		}
 
+		/// Add the given account to the sender's subs.
+		///
+		/// Payment: An amount `SubAccountDeposit` will be reserved for the new sub.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity. The sub-account must not already be a sub-identity of any account.
+		///
+		/// - `sub`: The account to add as a sub-identity.
+		/// - `data`: The name of the sub-identity, within the context of the sender's identity.
+		///
+		/// Emits `SubIdentityAdded` if successful.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` subs-count (hard- and deposit-bounded).
+		/// - One balance-reserve operation.
+		/// - Two storage mutations and two storage reads.
+		/// - One event.
+		/// # </weight>
+		#[weight = weight_for::add_sub::<T>(T::MaxSubAccounts::get().into())]
+		fn add_sub(origin, sub: <T::Lookup as StaticLookup>::Source, data: Data) {
+			let sender = ensure_signed(origin)?;
+			let sub = T::Lookup::lookup(sub)?;
+			ensure!(<IdentityOf<T>>::contains_key(&sender), Error::<T>::NoIdentity);
+			ensure!(!<SuperOf<T>>::contains_key(&sub), Error::<T>::AlreadyClaimed);
+
+			<SubsOf<T>>::try_mutate(&sender, |(ref mut subs_deposit, ref mut sub_ids)| {
+				ensure!(
+					sub_ids.len() < T::MaxSubAccounts::get() as usize,
+					Error::<T>::TooManySubAccounts
+				);
+				let deposit = T::SubAccountDeposit::get();
+				T::Currency::reserve(&sender, deposit)?;
+
+				<SuperOf<T>>::insert(&sub, (sender.clone(), data));
+				sub_ids.push(sub.clone());
+				*subs_deposit = subs_deposit.saturating_add(deposit);
+
+				Self::deposit_event(RawEvent::SubIdentityAdded(sub, sender.clone(), deposit));
+				Ok::<_, DispatchError>(())
+			})?;
+		}
+
+		/// Alter the associated name of the given sub-account.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity and own `sub`.
+		///
+		/// - `sub`: The sub-identity to rename.
+		/// - `data`: Its new name.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One storage mutation and two storage reads.
+		/// # </weight>
+		#[weight = weight_for::rename_sub::<T>()]
+		fn rename_sub(origin, sub: <T::Lookup as StaticLookup>::Source, data: Data) {
+			let sender = ensure_signed(origin)?;
+			let sub = T::Lookup::lookup(sub)?;
+			ensure!(<IdentityOf<T>>::contains_key(&sender), Error::<T>::NoIdentity);
+			ensure!(
+				<SuperOf<T>>::get(&sub).map_or(false, |x| x.0 == sender),
+				Error::<T>::NotOwned
+			);
+			<SuperOf<T>>::insert(&sub, (sender, data));
+		}
+
+		/// Remove the given account from the sender's subs.
+		///
+		/// Payment: Balance reserved by a previous `set_subs` or `add_sub` call for one sub will
+		/// be returned to the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity and own `sub`.
+		///
+		/// - `sub`: The sub-identity to remove.
+		///
+		/// Emits `SubIdentityRemoved` if successful.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` subs-count (hard- and deposit-bounded).
+		/// - One balance-unreserve operation.
+		/// - Two storage mutations and two storage reads.
+		/// - One event.
+		/// # </weight>
+		#[weight = weight_for::remove_sub::<T>(T::MaxSubAccounts::get().into())]
+		fn remove_sub(origin, sub: <T::Lookup as StaticLookup>::Source) {
+			let sender = ensure_signed(origin)?;
+			ensure!(<IdentityOf<T>>::contains_key(&sender), Error::<T>::NoIdentity);
+			let sub = T::Lookup::lookup(sub)?;
+			let (sup, _) = <SuperOf<T>>::get(&sub).ok_or(Error::<T>::NotSub)?;
+			ensure!(sup == sender, Error::<T>::NotOwned);
+
+			<SuperOf<T>>::remove(&sub);
+			<SubsOf<T>>::mutate(&sup, |(ref mut subs_deposit, ref mut sub_ids)| {
+				sub_ids.retain(|x| x != &sub);
+				let deposit = T::SubAccountDeposit::get().min(*subs_deposit);
+				*subs_deposit -= deposit;
+				let _ = T::Currency::unreserve(&sender, deposit);
+				Self::deposit_event(RawEvent::SubIdentityRemoved(sub, sender, deposit));
+			});
+		}
+
+		/// Remove the sender as a sub-account.
+		///
+		/// Payment: Balance reserved by a previous `set_subs` or `add_sub` call for one sub will
+		/// be repatriated to the sender (*not* the original depositor).
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must be a sub-account
+		/// of some identity.
+		///
+		/// NOTE: This should not normally be used, but is provided in the case that the
+		/// non-controller of an account is maliciously registered as a sub-account.
+		///
+		/// Emits `SubIdentityRevoked` if successful.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` subs-count (hard- and deposit-bounded).
+		/// - One balance-repatriate operation.
+		/// - Two storage mutations and two storage reads.
+		/// - One event.
+		/// # </weight>
+		#[weight = weight_for::remove_sub::<T>(T::MaxSubAccounts::get().into())]
+		fn quit_sub(origin) {
+			let sender = ensure_signed(origin)?;
+			let (sup, _) = <SuperOf<T>>::take(&sender).ok_or(Error::<T>::NotSub)?;
+			<SubsOf<T>>::mutate(&sup, |(ref mut subs_deposit, ref mut sub_ids)| {
+				sub_ids.retain(|x| x != &sender);
+				let deposit = T::SubAccountDeposit::get().min(*subs_deposit);
+				*subs_deposit -= deposit;
+				let _ = T::Currency::repatriate_reserved(
+					&sup,
+					&sender,
+					deposit,
+					BalanceStatus::Free,
+				);
+				Self::deposit_event(RawEvent::SubIdentityRevoked(sender, sup.clone(), deposit));
+			});
+		}
+
 		/// Clear an account's identity info and all sub-accounts and return all deposits.
 		///
 		/// Payment: All reserved balances on the account are returned.
@@ -1236,6 +1421,8 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
 	}
 	parameter_types! {
 		pub const BasicDeposit: u64 = 10;
@@ -1474,6 +1661,68 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn adding_and_removing_subaccounts_should_work() {
+		new_test_ext().execute_with(|| {
+			let data = |x| Data::Raw(vec![x; 1]);
+			assert_noop!(
+				Identity::add_sub(Origin::signed(10), 20, data(1)),
+				Error::<Test>::NoIdentity
+			);
+
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_ok!(Identity::add_sub(Origin::signed(10), 20, data(1)));
+			assert_eq!(Balances::free_balance(10), 80);
+			assert_eq!(Identity::subs_of(10), (10, vec![20]));
+			assert_eq!(Identity::super_of(20), Some((10, data(1))));
+
+			// a sub-identity can't be claimed twice.
+			assert_ok!(Identity::set_identity(Origin::signed(30), ten()));
+			assert_noop!(
+				Identity::add_sub(Origin::signed(30), 20, data(2)),
+				Error::<Test>::AlreadyClaimed
+			);
+
+			assert_ok!(Identity::add_sub(Origin::signed(10), 1, data(2)));
+			assert_eq!(Balances::free_balance(10), 70);
+			assert_noop!(
+				Identity::add_sub(Origin::signed(10), 2, data(3)),
+				Error::<Test>::TooManySubAccounts
+			);
+
+			assert_noop!(
+				Identity::rename_sub(Origin::signed(30), 20, data(4)),
+				Error::<Test>::NotOwned
+			);
+			assert_ok!(Identity::rename_sub(Origin::signed(10), 20, data(4)));
+			assert_eq!(Identity::super_of(20), Some((10, data(4))));
+
+			assert_noop!(Identity::remove_sub(Origin::signed(30), 20), Error::<Test>::NotOwned);
+			assert_noop!(Identity::remove_sub(Origin::signed(10), 3), Error::<Test>::NotSub);
+			assert_ok!(Identity::remove_sub(Origin::signed(10), 20));
+			assert_eq!(Balances::free_balance(10), 80);
+			assert_eq!(Identity::subs_of(10), (10, vec![1]));
+			assert_eq!(Identity::super_of(20), None);
+			assert_eq!(Identity::super_of(1), Some((10, data(2))));
+		});
+	}
+
+	#[test]
+	fn quitting_subaccount_should_repatriate_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_ok!(Identity::add_sub(Origin::signed(10), 20, Data::Raw(vec![40; 1])));
+			assert_eq!(Balances::reserved_balance(10), 20);
+
+			assert_noop!(Identity::quit_sub(Origin::signed(30)), Error::<Test>::NotSub);
+			assert_ok!(Identity::quit_sub(Origin::signed(20)));
+			assert_eq!(Balances::free_balance(20), 110);
+			assert_eq!(Balances::reserved_balance(10), 10);
+			assert_eq!(Identity::subs_of(10), (0, vec![]));
+			assert_eq!(Identity::super_of(20), None);
+		});
+	}
+
 	#[test]
 	fn cancelling_requested_judgement_should_work() {
 		new_test_ext().execute_with(|| {
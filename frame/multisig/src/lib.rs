@@ -263,6 +263,16 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
 
+		/// The base amount of currency needed to reserve for creating a multisig execution or to
+		/// store a dispatch call for later.
+		const DepositBase: BalanceOf<T> = T::DepositBase::get();
+
+		/// The amount of currency needed per unit threshold when creating a multisig execution.
+		const DepositFactor: BalanceOf<T> = T::DepositFactor::get();
+
+		/// The maximum amount of signatories allowed in the multisig.
+		const MaxSignatories: u16 = T::MaxSignatories::get();
+
 		/// Deposit one of this module's events by using the default implementation.
 		fn deposit_event() = default;
 
@@ -297,6 +297,8 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
 	}
 	parameter_types! {
 		pub const ReservationFee: u64 = 2;
@@ -0,0 +1,108 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Benchmarks for Preimage Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::UnfilteredDispatchable;
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Preimage;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn sized_preimage_and_hash<T: Trait>(size: u32) -> (Vec<u8>, T::Hash) {
+	let preimage = vec![0; size as usize];
+	let hash = T::Hashing::hash(&preimage[..]);
+	(preimage, hash)
+}
+
+benchmarks! {
+	_ { }
+
+	note_preimage {
+		let s in 0 .. T::MaxSize::get();
+		let caller = funded_account::<T>("caller", 0);
+		let (preimage, hash) = sized_preimage_and_hash::<T>(s);
+	}: _(RawOrigin::Signed(caller), preimage)
+	verify {
+		assert!(Preimage::<T>::have_preimage(&hash));
+	}
+
+	unnote_preimage {
+		let s in 0 .. T::MaxSize::get();
+		let caller = funded_account::<T>("caller", 0);
+		let (preimage, hash) = sized_preimage_and_hash::<T>(s);
+		Preimage::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), preimage)?;
+	}: _(RawOrigin::Signed(caller), hash)
+	verify {
+		assert!(!Preimage::<T>::have_preimage(&hash));
+	}
+
+	// The worst case returns the deposit of a preimage noted by a user.
+	request_preimage {
+		let s in 0 .. T::MaxSize::get();
+		let noter = funded_account::<T>("noter", 0);
+		let (preimage, hash) = sized_preimage_and_hash::<T>(s);
+		Preimage::<T>::note_preimage(RawOrigin::Signed(noter).into(), preimage)?;
+		let origin = T::ManagerOrigin::successful_origin();
+		let call = Call::<T>::request_preimage(hash);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Preimage::<T>::preimage_requested(&hash));
+	}
+
+	// The worst case withdraws the last request, removing the preimage.
+	unrequest_preimage {
+		let s in 0 .. T::MaxSize::get();
+		let (preimage, hash) = sized_preimage_and_hash::<T>(s);
+		let origin = T::ManagerOrigin::successful_origin();
+		Call::<T>::request_preimage(hash).dispatch_bypass_filter(origin.clone())?;
+		Call::<T>::note_preimage(preimage).dispatch_bypass_filter(origin.clone())?;
+		let call = Call::<T>::unrequest_preimage(hash);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!Preimage::<T>::have_preimage(&hash));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_note_preimage::<Test>());
+			assert_ok!(test_benchmark_unnote_preimage::<Test>());
+			assert_ok!(test_benchmark_request_preimage::<Test>());
+			assert_ok!(test_benchmark_unrequest_preimage::<Test>());
+		});
+	}
+}
@@ -0,0 +1,390 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Preimage Module
+//!
+//! - [`preimage::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//! - [`Module`](./struct.Module.html)
+//!
+//! ## Overview
+//!
+//! The Preimage module allows users and the runtime to store the preimage of a hash on chain, so
+//! that other modules, e.g. democracy, only need to keep track of the hash of some large data and
+//! can fetch it when needed.
+//!
+//! Anyone may note a preimage against a deposit, proportional to its size, which is returned when
+//! they unnote it again. Modules request the preimages they are interested in through the
+//! [`PreimageProvider`] trait: a requested preimage can be noted without a deposit, any deposit
+//! already held for it is returned, and it cannot be unnoted until every request was withdrawn.
+//! Once the last request is withdrawn the preimage is removed.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * `note_preimage` - Note a preimage, taking a deposit unless it is requested or the origin is
+//!   the `ManagerOrigin`.
+//! * `unnote_preimage` - Remove an unrequested preimage; the deposit is returned.
+//! * `request_preimage` - Request a preimage be noted, called by the `ManagerOrigin`.
+//! * `unrequest_preimage` - Withdraw a request, called by the `ManagerOrigin`.
+//!
+//! ### Implementations
+//!
+//! - [`PreimageProvider`](../frame_support/traits/trait.PreimageProvider.html): Looking up and
+//! requesting preimages.
+//! - [`PreimageRecipient`](../frame_support/traits/trait.PreimageRecipient.html): Noting and
+//! unnoting preimages without any deposit.
+//!
+//! [`Call`]: ./enum.Call.html
+//! [`Trait`]: ./trait.Trait.html
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, DispatchError, DispatchResult, traits::{Hash, Saturating}};
+use frame_support::{
+	decl_module, decl_event, decl_storage, decl_error, ensure,
+	weights::Weight,
+	traits::{
+		Currency, ReservableCurrency, EnsureOrigin, Get, PreimageProvider, PreimageRecipient,
+	},
+};
+use frame_system::ensure_signed;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub trait WeightInfo {
+	fn note_preimage(s: u32, ) -> Weight;
+	fn unnote_preimage() -> Weight;
+	fn request_preimage() -> Weight;
+	fn unrequest_preimage() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn note_preimage(_s: u32, ) -> Weight { 1_000_000_000 }
+	fn unnote_preimage() -> Weight { 1_000_000_000 }
+	fn request_preimage() -> Weight { 1_000_000_000 }
+	fn unrequest_preimage() -> Weight { 1_000_000_000 }
+}
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Currency type for this module.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// An origin that can request a preimage be placed on-chain without a deposit, or manage
+	/// existing preimages.
+	type ManagerOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Max size allowed for a preimage.
+	type MaxSize: Get<u32>;
+
+	/// The base deposit for placing a preimage on chain.
+	type BaseDeposit: Get<BalanceOf<Self>>;
+
+	/// The per-byte deposit for placing a preimage on chain.
+	type ByteDeposit: Get<BalanceOf<Self>>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+/// A type to note whether a preimage is owned by a user or the system.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum RequestStatus<AccountId, Balance> {
+	/// The associated preimage has not yet been requested by the system. The given deposit (if
+	/// some) is being held until either it becomes requested or the user retracts the preimage.
+	Unrequested(Option<(AccountId, Balance)>),
+	/// There are a non-zero number of outstanding requests for this hash by this chain. If there
+	/// is a preimage registered, then it may be removed iff this counter becomes zero.
+	Requested(u32),
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Preimage {
+		/// The request status of a given hash.
+		pub StatusFor get(fn status_for):
+			map hasher(identity) T::Hash => Option<RequestStatus<T::AccountId, BalanceOf<T>>>;
+
+		/// The preimages stored by this module.
+		pub PreimageFor get(fn preimage_for): map hasher(identity) T::Hash => Option<Vec<u8>>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as frame_system::Trait>::Hash {
+		/// A preimage has been noted.
+		Noted(Hash),
+		/// A preimage has been requested.
+		Requested(Hash),
+		/// A preimage has been cleared.
+		Cleared(Hash),
+	}
+);
+
+decl_error! {
+	/// Error for the preimage module.
+	pub enum Error for Module<T: Trait> {
+		/// Preimage is too large to store on-chain.
+		TooLarge,
+		/// Preimage has already been noted on-chain.
+		AlreadyNoted,
+		/// The user is not authorized to perform this action.
+		NotAuthorized,
+		/// The preimage cannot be removed since it has not yet been noted.
+		NotNoted,
+		/// A preimage may not be removed when there are outstanding requests.
+		Requested,
+		/// The preimage request cannot be removed since no outstanding requests exist.
+		NotRequested,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The maximum size of a preimage.
+		const MaxSize: u32 = T::MaxSize::get();
+
+		/// The base deposit for placing a preimage on chain.
+		const BaseDeposit: BalanceOf<T> = T::BaseDeposit::get();
+
+		/// The per-byte deposit for placing a preimage on chain.
+		const ByteDeposit: BalanceOf<T> = T::ByteDeposit::get();
+
+		fn deposit_event() = default;
+
+		/// Register a preimage on-chain.
+		///
+		/// If the preimage was previously requested, no deposit is taken. The `ManagerOrigin`
+		/// never pays a deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ or the `ManagerOrigin`.
+		///
+		/// - `bytes`: The preimage, at most `MaxSize` bytes long.
+		///
+		/// Emits `Noted`.
+		///
+		/// # <weight>
+		/// - `O(B)` where `B` is the length of `bytes`.
+		/// - One storage read and two storage writes, plus a balance reservation.
+		/// # </weight>
+		#[weight = T::WeightInfo::note_preimage(bytes.len() as u32)]
+		fn note_preimage(origin, bytes: Vec<u8>) {
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			Self::note_bytes(bytes, maybe_sender.as_ref())?;
+		}
+
+		/// Clear an unrequested preimage from the runtime storage, returning its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account which noted the
+		/// preimage, or the `ManagerOrigin`.
+		///
+		/// - `hash`: The hash of the preimage to be removed from the store.
+		///
+		/// Emits `Cleared`.
+		#[weight = T::WeightInfo::unnote_preimage()]
+		fn unnote_preimage(origin, hash: T::Hash) {
+			let maybe_sender = Self::ensure_signed_or_manager(origin)?;
+			Self::do_unnote_preimage(&hash, maybe_sender)?;
+		}
+
+		/// Request a preimage be uploaded to the chain without paying any deposit. Any deposit
+		/// already held for the preimage is returned.
+		///
+		/// The dispatch origin for this call must be the `ManagerOrigin`.
+		///
+		/// Emits `Requested` if the preimage was not requested before.
+		#[weight = T::WeightInfo::request_preimage()]
+		fn request_preimage(origin, hash: T::Hash) {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_request_preimage(&hash);
+		}
+
+		/// Clear a previously made request for a preimage. The preimage is removed once no
+		/// request is left.
+		///
+		/// The dispatch origin for this call must be the `ManagerOrigin`.
+		///
+		/// Emits `Cleared` if the last request was withdrawn.
+		#[weight = T::WeightInfo::unrequest_preimage()]
+		fn unrequest_preimage(origin, hash: T::Hash) {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			Self::do_unrequest_preimage(&hash)?;
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Ensure that the origin is either the `ManagerOrigin` or a signed origin.
+	///
+	/// Returns the signer, or `None` for the `ManagerOrigin`.
+	fn ensure_signed_or_manager(origin: T::Origin) -> Result<Option<T::AccountId>, DispatchError> {
+		match T::ManagerOrigin::try_origin(origin) {
+			Ok(_) => Ok(None),
+			Err(origin) => Ok(Some(ensure_signed(origin)?)),
+		}
+	}
+
+	/// Store some preimage on chain.
+	///
+	/// We verify that the preimage is within the bounds of what the module supports.
+	///
+	/// If the preimage was requested to be uploaded, then the user pays no deposit. The
+	/// transaction fee is paid as usual.
+	fn note_bytes(
+		preimage: Vec<u8>,
+		maybe_depositor: Option<&T::AccountId>,
+	) -> Result<T::Hash, DispatchError> {
+		ensure!(preimage.len() as u32 <= T::MaxSize::get(), Error::<T>::TooLarge);
+		let hash = T::Hashing::hash(&preimage);
+		ensure!(!PreimageFor::<T>::contains_key(hash), Error::<T>::AlreadyNoted);
+
+		// We take a deposit only if there is a provided depositor, and the preimage was not
+		// previously requested.
+		match (StatusFor::<T>::get(hash), maybe_depositor) {
+			(Some(RequestStatus::Requested(_)), _) => (),
+			(Some(RequestStatus::Unrequested(_)), _) => Err(Error::<T>::AlreadyNoted)?,
+			(None, None) => StatusFor::<T>::insert(hash, RequestStatus::Unrequested(None)),
+			(None, Some(depositor)) => {
+				let length = preimage.len() as u32;
+				let deposit = T::BaseDeposit::get()
+					.saturating_add(T::ByteDeposit::get().saturating_mul(length.into()));
+				T::Currency::reserve(depositor, deposit)?;
+				let status = RequestStatus::Unrequested(Some((depositor.clone(), deposit)));
+				StatusFor::<T>::insert(hash, status);
+			},
+		}
+
+		PreimageFor::<T>::insert(hash, preimage);
+		Self::deposit_event(RawEvent::Noted(hash));
+		Ok(hash)
+	}
+
+	/// Clear a preimage from the storage of the chain, returning any deposit that may be
+	/// reserved.
+	///
+	/// If `maybe_check_owner` is provided, we verify that it is the correct owner before
+	/// clearing the data.
+	fn do_unnote_preimage(
+		hash: &T::Hash,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> DispatchResult {
+		match StatusFor::<T>::get(hash).ok_or(Error::<T>::NotNoted)? {
+			RequestStatus::Unrequested(Some((owner, deposit))) => {
+				ensure!(
+					maybe_check_owner.map_or(true, |c| c == owner),
+					Error::<T>::NotAuthorized,
+				);
+				T::Currency::unreserve(&owner, deposit);
+			},
+			RequestStatus::Unrequested(None) => {
+				ensure!(maybe_check_owner.is_none(), Error::<T>::NotAuthorized);
+			},
+			RequestStatus::Requested(_) => Err(Error::<T>::Requested)?,
+		}
+		StatusFor::<T>::remove(hash);
+		PreimageFor::<T>::remove(hash);
+		Self::deposit_event(RawEvent::Cleared(*hash));
+		Ok(())
+	}
+
+	/// Request a preimage, returning any deposit held for it.
+	fn do_request_preimage(hash: &T::Hash) {
+		let count = match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Requested(count)) => count.saturating_add(1),
+			Some(RequestStatus::Unrequested(None)) | None => 1,
+			Some(RequestStatus::Unrequested(Some((owner, deposit)))) => {
+				// Return the deposit - the preimage now has outstanding requests.
+				T::Currency::unreserve(&owner, deposit);
+				1
+			},
+		};
+		StatusFor::<T>::insert(hash, RequestStatus::Requested(count));
+		if count == 1 {
+			Self::deposit_event(RawEvent::Requested(*hash));
+		}
+	}
+
+	/// Withdraw a request for a preimage, removing the preimage with the last request.
+	fn do_unrequest_preimage(hash: &T::Hash) -> DispatchResult {
+		match StatusFor::<T>::get(hash).ok_or(Error::<T>::NotRequested)? {
+			RequestStatus::Requested(count) if count > 1 => {
+				StatusFor::<T>::insert(hash, RequestStatus::Requested(count - 1));
+			},
+			RequestStatus::Requested(count) => {
+				debug_assert!(count == 1, "preimage request counter at zero?");
+				PreimageFor::<T>::remove(hash);
+				StatusFor::<T>::remove(hash);
+				Self::deposit_event(RawEvent::Cleared(*hash));
+			},
+			RequestStatus::Unrequested(_) => Err(Error::<T>::NotRequested)?,
+		}
+		Ok(())
+	}
+}
+
+impl<T: Trait> PreimageProvider<T::Hash> for Module<T> {
+	fn have_preimage(hash: &T::Hash) -> bool {
+		PreimageFor::<T>::contains_key(hash)
+	}
+
+	fn get_preimage(hash: &T::Hash) -> Option<Vec<u8>> {
+		PreimageFor::<T>::get(hash)
+	}
+
+	fn preimage_requested(hash: &T::Hash) -> bool {
+		match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Requested(_)) => true,
+			_ => false,
+		}
+	}
+
+	fn request_preimage(hash: &T::Hash) {
+		Self::do_request_preimage(hash)
+	}
+
+	fn unrequest_preimage(hash: &T::Hash) {
+		let res = Self::do_unrequest_preimage(hash);
+		debug_assert!(res.is_ok(), "do_unrequest_preimage failed - counter underflow?");
+	}
+}
+
+impl<T: Trait> PreimageRecipient<T::Hash> for Module<T> {
+	type MaxSize = T::MaxSize;
+
+	fn note_preimage(bytes: Vec<u8>) {
+		// Note will error if the preimage is too large or already noted, there is nothing to do
+		// about it here.
+		let _ = Self::note_bytes(bytes, None);
+	}
+
+	fn unnote_preimage(hash: &T::Hash) {
+		// Unnoting fails if the preimage is requested or unknown, which is fine as this is only
+		// a hint.
+		let _ = Self::do_unnote_preimage(hash, None);
+	}
+}
@@ -24,10 +24,27 @@
 //!
 //! ## Overview
 //!
+//! Each proxy is registered with a type, restricting the calls it may make through the proxy
+//! type's `InstanceFilter`, and a delay. A proxy with a non-zero delay may not make calls
+//! directly; it must first `announce` the hash of the call it intends to make, and may only
+//! dispatch it with `proxy_announced` once the delay has elapsed. In the meantime, the real
+//! account may `reject_announcement` to prevent the call from ever being made.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
+//! * `proxy` - Dispatch a call from an account for which the sender is a proxy without delay.
+//! * `add_proxy` - Register a proxy account for the sender.
+//! * `remove_proxy` - Unregister a proxy account for the sender.
+//! * `remove_proxies` - Unregister all proxy accounts for the sender.
+//! * `anonymous` - Spawn a fresh account which may only be accessed through a proxy.
+//! * `kill_anonymous` - Remove a previously spawned anonymous proxy.
+//! * `announce` - Publish the hash of a call which a time-delayed proxy intends to make.
+//! * `remove_announcement` - Remove an announcement made by the sender.
+//! * `reject_announcement` - Remove an announcement made by a proxy of the sender.
+//! * `proxy_announced` - Dispatch a previously announced call once its delay has elapsed.
+//!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
 
@@ -37,12 +54,12 @@
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
 use sp_io::hashing::blake2_256;
-use sp_runtime::{DispatchResult, traits::{Dispatchable, Zero}};
-use sp_runtime::traits::Member;
+use sp_runtime::{DispatchResult, DispatchError, RuntimeDebug};
+use sp_runtime::traits::{Dispatchable, Zero, Hash, Member, Saturating};
 use frame_support::{
 	decl_module, decl_event, decl_error, decl_storage, Parameter, ensure, traits::{
-		Get, ReservableCurrency, Currency, InstanceFilter,
-		OriginTrait, IsType,
+		Get, ReservableCurrency, Currency, InstanceFilter, OriginTrait, IsType, OnRuntimeUpgrade,
+		StorageVersion, GetStorageVersion, VersionedMigration,
 	}, weights::{Weight, GetDispatchInfo, constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS}},
 	dispatch::{PostDispatchInfo, IsSubType},
 };
@@ -53,6 +70,8 @@ mod benchmarking;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
+type CallHashOf<T> = <<T as Trait>::CallHasher as Hash>::Output;
+
 pub trait WeightInfo {
 	fn proxy(p: u32, ) -> Weight;
 	fn add_proxy(p: u32, ) -> Weight;
@@ -60,6 +79,10 @@ pub trait WeightInfo {
 	fn remove_proxies(p: u32, ) -> Weight;
 	fn anonymous(p: u32, ) -> Weight;
 	fn kill_anonymous(p: u32, ) -> Weight;
+	fn announce(a: u32, p: u32, ) -> Weight;
+	fn remove_announcement(a: u32, p: u32, ) -> Weight;
+	fn reject_announcement(a: u32, p: u32, ) -> Weight;
+	fn proxy_announced(a: u32, p: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -69,6 +92,10 @@ impl WeightInfo for () {
 	fn remove_proxies(_p: u32, ) -> Weight { 1_000_000_000 }
 	fn anonymous(_p: u32, ) -> Weight { 1_000_000_000 }
 	fn kill_anonymous(_p: u32, ) -> Weight { 1_000_000_000 }
+	fn announce(_a: u32, _p: u32, ) -> Weight { 1_000_000_000 }
+	fn remove_announcement(_a: u32, _p: u32, ) -> Weight { 1_000_000_000 }
+	fn reject_announcement(_a: u32, _p: u32, ) -> Weight { 1_000_000_000 }
+	fn proxy_announced(_a: u32, _p: u32, ) -> Weight { 1_000_000_000 }
 }
 
 /// Configuration trait.
@@ -108,13 +135,71 @@ pub trait Trait: frame_system::Trait {
 
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
+
+	/// The maximum amount of time-delayed announcements that are allowed to be pending.
+	type MaxPending: Get<u32>;
+
+	/// The type of hash used for hashing the call.
+	type CallHasher: Hash;
+
+	/// The base amount of currency needed to reserve for creating an announcement.
+	///
+	/// This is held when a new storage item holding a `Balance` is created (typically 16 bytes).
+	type AnnouncementDepositBase: Get<BalanceOf<Self>>;
+
+	/// The amount of currency needed per announcement made.
+	///
+	/// This is held for adding an `AccountId`, `Hash` and `BlockNumber` (typically 68 bytes)
+	/// into a pre-existing storage value.
+	type AnnouncementDepositFactor: Get<BalanceOf<Self>>;
+}
+
+/// The parameters under which a particular account has a proxy relationship with some other
+/// account.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+pub struct ProxyDefinition<AccountId, ProxyType, BlockNumber> {
+	/// The account which may act on behalf of another.
+	pub delegate: AccountId,
+	/// A value defining the subset of calls that it is allowed to make.
+	pub proxy_type: ProxyType,
+	/// The number of blocks that an announcement must be in place for before the corresponding
+	/// call may be dispatched. If zero, then no announcement is needed.
+	pub delay: BlockNumber,
+}
+
+/// Details surrounding a specific instance of an announcement to make a call.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub struct Announcement<AccountId, Hash, BlockNumber> {
+	/// The account which made the announcement.
+	real: AccountId,
+	/// The hash of the call to be made.
+	call_hash: Hash,
+	/// The height at which the announcement was made.
+	height: BlockNumber,
+}
+
+/// The current storage version of the Proxy pallet.
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+frame_support::parameter_types! {
+	const V0: u16 = 0;
+	const V1: u16 = 1;
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Proxy {
 		/// The set of account proxies. Maps the account which has delegated to the accounts
 		/// which are being delegated to, together with the amount held on deposit.
-		pub Proxies: map hasher(twox_64_concat) T::AccountId => (Vec<(T::AccountId, T::ProxyType)>, BalanceOf<T>);
+		pub Proxies: map hasher(twox_64_concat) T::AccountId
+			=> (Vec<ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>>, BalanceOf<T>);
+
+		/// The announcements made by the proxy (key).
+		pub Announcements: map hasher(twox_64_concat) T::AccountId
+			=> (Vec<Announcement<T::AccountId, CallHashOf<T>, T::BlockNumber>>, BalanceOf<T>);
+	}
+	add_extra_genesis {
+		// New networks start with the current storage version.
+		build(|_| STORAGE_VERSION.put::<Module<T>>());
 	}
 }
 
@@ -132,6 +217,8 @@ decl_error! {
 		Duplicate,
 		/// Call may not be made by proxy because it may escalate its privileges.
 		NoPermission,
+		/// Announcement, if made at all, was made too recently.
+		Unannounced,
 	}
 }
 
@@ -139,16 +226,21 @@ decl_event! {
 	/// Events type.
 	pub enum Event<T> where
 		AccountId = <T as frame_system::Trait>::AccountId,
-		ProxyType = <T as Trait>::ProxyType
+		ProxyType = <T as Trait>::ProxyType,
+		Hash = CallHashOf<T>,
 	{
 		/// A proxy was executed correctly, with the given result.
 		ProxyExecuted(DispatchResult),
 		/// Anonymous account (first parameter) has been created by new proxy (second) with given
 		/// disambiguation index and proxy type.
 		AnonymousCreated(AccountId, AccountId, ProxyType, u16),
+		/// An announcement was placed by a proxy (second) to make a call in the future on behalf
+		/// of the real account (first), with the given call hash.
+		Announced(AccountId, AccountId, Hash),
 	}
 }
 
+
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
@@ -165,9 +257,26 @@ decl_module! {
 		/// The maximum amount of proxies allowed for a single account.
 		const MaxProxies: u16 = T::MaxProxies::get();
 
+		/// The maximum amount of time-delayed announcements that are allowed to be pending.
+		const MaxPending: u32 = T::MaxPending::get();
+
+		/// The base amount of currency needed to reserve for creating an announcement.
+		const AnnouncementDepositBase: BalanceOf<T> = T::AnnouncementDepositBase::get();
+
+		/// The amount of currency needed per announcement made.
+		const AnnouncementDepositFactor: BalanceOf<T> = T::AnnouncementDepositFactor::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			VersionedMigration::<
+				V0, V1, MigrateToTimeDelayedProxies<T>, Self, T::DbWeight
+			>::on_runtime_upgrade()
+		}
+
 		/// Dispatch the given `call` from an account that the sender is authorised for through
 		/// `add_proxy`.
 		///
+		/// Removes any corresponding announcement(s).
+		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// Parameters:
@@ -175,6 +284,9 @@ decl_module! {
 		/// - `force_proxy_type`: Specify the exact proxy type to be used and checked for this call.
 		/// - `call`: The call to be made by the `real` account.
 		///
+		/// Fails with `Unannounced` if the proxy was registered with a non-zero delay; such
+		/// proxies must use `announce` and `proxy_announced` instead.
+		///
 		/// # <weight>
 		/// P is the number of proxies the user has
 		/// - Base weight: 19.87 + .141 * P µs
@@ -195,24 +307,10 @@ decl_module! {
 			call: Box<<T as Trait>::Call>
 		) {
 			let who = ensure_signed(origin)?;
-			let (_, proxy_type) = Proxies::<T>::get(&real).0.into_iter()
-				.find(|x| &x.0 == &who && force_proxy_type.as_ref().map_or(true, |y| &x.1 == y))
-				.ok_or(Error::<T>::NotProxy)?;
+			let def = Self::find_proxy(&real, &who, force_proxy_type)?;
+			ensure!(def.delay.is_zero(), Error::<T>::Unannounced);
 
-			// This is a freshly authenticated new account, the origin restrictions doesn't apply.
-			let mut origin: T::Origin = frame_system::RawOrigin::Signed(real).into();
-			origin.add_filter(move |c: &<T as frame_system::Trait>::Call| {
-				let c = <T as Trait>::Call::from_ref(c);
-				match c.is_sub_type() {
-					Some(Call::add_proxy(_, ref pt)) | Some(Call::remove_proxy(_, ref pt))
-						if !proxy_type.is_superset(&pt) => false,
-					Some(Call::remove_proxies(..)) | Some(Call::kill_anonymous(..))
-					    if proxy_type != T::ProxyType::default() => false,
-					_ => proxy_type.filter(c)
-				}
-			});
-			let e = call.dispatch(origin);
-			Self::deposit_event(RawEvent::ProxyExecuted(e.map(|_| ()).map_err(|e| e.error)));
+			Self::do_proxy(def, real, *call);
 		}
 
 		/// Register a proxy account for the sender that is able to make calls on its behalf.
@@ -222,6 +320,8 @@ decl_module! {
 		/// Parameters:
 		/// - `proxy`: The account that the `caller` would like to make a proxy.
 		/// - `proxy_type`: The permissions allowed for this proxy account.
+		/// - `delay`: The announcement period required of the initial proxy. Will generally be
+		/// zero.
 		///
 		/// # <weight>
 		/// P is the number of proxies the user has
@@ -232,15 +332,18 @@ decl_module! {
 			.saturating_add(18 * WEIGHT_PER_MICROS)
 			.saturating_add((200 * WEIGHT_PER_NANOS).saturating_mul(T::MaxProxies::get().into()))
 		]
-		fn add_proxy(origin, proxy: T::AccountId, proxy_type: T::ProxyType) -> DispatchResult {
+		fn add_proxy(origin,
+			delegate: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: T::BlockNumber,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Proxies::<T>::try_mutate(&who, |(ref mut proxies, ref mut deposit)| {
 				ensure!(proxies.len() < T::MaxProxies::get() as usize, Error::<T>::TooMany);
-				let typed_proxy = (proxy, proxy_type);
-				let i = proxies.binary_search(&typed_proxy).err().ok_or(Error::<T>::Duplicate)?;
-				proxies.insert(i, typed_proxy);
-				let new_deposit = T::ProxyDepositBase::get()
-					+ T::ProxyDepositFactor::get() * (proxies.len() as u32).into();
+				let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
+				let i = proxies.binary_search(&proxy_def).err().ok_or(Error::<T>::Duplicate)?;
+				proxies.insert(i, proxy_def);
+				let new_deposit = Self::deposit(proxies.len() as u32);
 				if new_deposit > *deposit {
 					T::Currency::reserve(&who, new_deposit - *deposit)?;
 				} else if new_deposit < *deposit {
@@ -258,6 +361,7 @@ decl_module! {
 		/// Parameters:
 		/// - `proxy`: The account that the `caller` would like to remove as a proxy.
 		/// - `proxy_type`: The permissions currently enabled for the removed proxy account.
+		/// - `delay`: The announcement period currently required of the removed proxy account.
 		///
 		/// # <weight>
 		/// P is the number of proxies the user has
@@ -268,17 +372,21 @@ decl_module! {
 			.saturating_add(14 * WEIGHT_PER_MICROS)
 			.saturating_add((160 * WEIGHT_PER_NANOS).saturating_mul(T::MaxProxies::get().into()))
 		]
-		fn remove_proxy(origin, proxy: T::AccountId, proxy_type: T::ProxyType) -> DispatchResult {
+		fn remove_proxy(origin,
+			delegate: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: T::BlockNumber,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Proxies::<T>::try_mutate_exists(&who, |x| {
 				let (mut proxies, old_deposit) = x.take().ok_or(Error::<T>::NotFound)?;
-				let typed_proxy = (proxy, proxy_type);
-				let i = proxies.binary_search(&typed_proxy).ok().ok_or(Error::<T>::NotFound)?;
+				let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
+				let i = proxies.binary_search(&proxy_def).ok().ok_or(Error::<T>::NotFound)?;
 				proxies.remove(i);
 				let new_deposit = if proxies.is_empty() {
 					BalanceOf::<T>::zero()
 				} else {
-					T::ProxyDepositBase::get() + T::ProxyDepositFactor::get() * (proxies.len() as u32).into()
+					Self::deposit(proxies.len() as u32)
 				};
 				if new_deposit > old_deposit {
 					T::Currency::reserve(&who, new_deposit - old_deposit)?;
@@ -322,6 +430,8 @@ decl_module! {
 		/// - `proxy_type`: The type of the proxy that the sender will be registered as over the
 		/// new account. This will almost always be the most permissive `ProxyType` possible to
 		/// allow for maximum flexibility.
+		/// - `delay`: The announcement period required of the initial proxy. Will generally be
+		/// zero.
 		/// - `index`: A disambiguation index, in case this is called multiple times in the same
 		/// transaction (e.g. with `utility::batch`). Unless you're using `batch` you probably just
 		/// want to use `0`.
@@ -340,14 +450,19 @@ decl_module! {
 			.saturating_add(36 * WEIGHT_PER_MICROS)
 			.saturating_add((40 * WEIGHT_PER_NANOS).saturating_mul(T::MaxProxies::get().into()))
 		]
-		fn anonymous(origin, proxy_type: T::ProxyType, index: u16) {
+		fn anonymous(origin, proxy_type: T::ProxyType, delay: T::BlockNumber, index: u16) {
 			let who = ensure_signed(origin)?;
 
 			let anonymous = Self::anonymous_account(&who, &proxy_type, index, None);
 			ensure!(!Proxies::<T>::contains_key(&anonymous), Error::<T>::Duplicate);
-			let deposit = T::ProxyDepositBase::get() + T::ProxyDepositFactor::get();
+			let deposit = Self::deposit(1);
 			T::Currency::reserve(&who, deposit)?;
-			Proxies::<T>::insert(&anonymous, (vec![(who.clone(), proxy_type.clone())], deposit));
+			let proxy_def = ProxyDefinition {
+				delegate: who.clone(),
+				proxy_type: proxy_type.clone(),
+				delay,
+			};
+			Proxies::<T>::insert(&anonymous, (vec![proxy_def], deposit));
 			Self::deposit_event(RawEvent::AnonymousCreated(anonymous, who, proxy_type, index));
 		}
 
@@ -393,6 +508,156 @@ decl_module! {
 			let (_, deposit) = Proxies::<T>::take(&who);
 			T::Currency::unreserve(&spawner, deposit);
 		}
+
+		/// Publish the hash of a proxy-call that will be made in the future.
+		///
+		/// This must be called some number of blocks before the corresponding `proxy_announced`
+		/// is attempted if the delay associated with the proxy relationship is greater than zero.
+		///
+		/// No more than `MaxPending` announcements may be made at any one time.
+		///
+		/// This will take a deposit of `AnnouncementDepositFactor` as well as
+		/// `AnnouncementDepositBase` if there are no other pending announcements.
+		///
+		/// The dispatch origin for this call must be _Signed_ and a proxy of `real`.
+		///
+		/// Parameters:
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `call_hash`: The hash of the call to be made by the `real` account.
+		///
+		/// # <weight>
+		/// A is the number of announcements the proxy has, P is the number of proxies of `real`.
+		/// - Base weight: 35 + .15 * A + .15 * P µs
+		/// - DB weight: 2 storage reads and 1 storage write.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(2, 1)
+			.saturating_add(35 * WEIGHT_PER_MICROS)
+			.saturating_add((150 * WEIGHT_PER_NANOS).saturating_mul(T::MaxPending::get().into()))
+			.saturating_add((150 * WEIGHT_PER_NANOS).saturating_mul(T::MaxProxies::get().into()))
+		]
+		fn announce(origin, real: T::AccountId, call_hash: CallHashOf<T>) {
+			let who = ensure_signed(origin)?;
+			Proxies::<T>::get(&real).0.into_iter()
+				.find(|x| x.delegate == who)
+				.ok_or(Error::<T>::NotProxy)?;
+
+			let announcement = Announcement {
+				real: real.clone(),
+				call_hash,
+				height: system::Module::<T>::block_number(),
+			};
+
+			Announcements::<T>::try_mutate(&who, |(ref mut pending, ref mut deposit)| {
+				ensure!(pending.len() < T::MaxPending::get() as usize, Error::<T>::TooMany);
+				pending.push(announcement);
+				*deposit = Self::rejig_announcement_deposit(&who, *deposit, pending.len())?;
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(RawEvent::Announced(real, who, call_hash));
+		}
+
+		/// Remove a given announcement.
+		///
+		/// May be called by a proxy account to remove a call they previously announced and return
+		/// the deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `call_hash`: The hash of the call to be made by the `real` account.
+		///
+		/// # <weight>
+		/// A is the number of announcements the proxy has.
+		/// - Base weight: 25 + .15 * A µs
+		/// - DB weight: 1 storage read and write.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)
+			.saturating_add(25 * WEIGHT_PER_MICROS)
+			.saturating_add((150 * WEIGHT_PER_NANOS).saturating_mul(T::MaxPending::get().into()))
+		]
+		fn remove_announcement(origin, real: T::AccountId, call_hash: CallHashOf<T>) {
+			let who = ensure_signed(origin)?;
+			Self::edit_announcements(&who, |ann| ann.real != real || ann.call_hash != call_hash)?;
+		}
+
+		/// Remove the given announcement of a delegate.
+		///
+		/// May be called by a target (proxied) account to remove a call that one of their
+		/// delegates (`delegate`) has announced they want to execute. The deposit is returned.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `delegate`: The account that previously announced the call.
+		/// - `call_hash`: The hash of the call to be made.
+		///
+		/// # <weight>
+		/// A is the number of announcements the delegate has.
+		/// - Base weight: 25 + .15 * A µs
+		/// - DB weight: 1 storage read and write.
+		/// # </weight>
+		#[weight = T::DbWeight::get().reads_writes(1, 1)
+			.saturating_add(25 * WEIGHT_PER_MICROS)
+			.saturating_add((150 * WEIGHT_PER_NANOS).saturating_mul(T::MaxPending::get().into()))
+		]
+		fn reject_announcement(origin, delegate: T::AccountId, call_hash: CallHashOf<T>) {
+			let who = ensure_signed(origin)?;
+			Self::edit_announcements(&delegate, |ann|
+				ann.real != who || ann.call_hash != call_hash
+			)?;
+		}
+
+		/// Dispatch the given `call` from an account that the sender is authorised for through
+		/// `add_proxy`.
+		///
+		/// Removes any corresponding announcement(s).
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `delegate`: The account that previously announced the call.
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `force_proxy_type`: Specify the exact proxy type to be used and checked for this call.
+		/// - `call`: The call to be made by the `real` account.
+		///
+		/// Fails with `Unannounced` if there is no announcement of `call` by `delegate` on behalf
+		/// of `real` which is at least as old as the delay of the proxy.
+		///
+		/// # <weight>
+		/// A is the number of announcements the delegate has, P is the number of proxies of
+		/// `real`.
+		/// - Base weight: 40 + .15 * A + .15 * P µs
+		/// - DB weight: 2 storage reads and 1 storage write.
+		/// - Plus the weight of the `call`
+		/// # </weight>
+		#[weight = {
+			let di = call.get_dispatch_info();
+			(T::DbWeight::get().reads_writes(2, 1)
+				.saturating_add(di.weight)
+				.saturating_add(40 * WEIGHT_PER_MICROS)
+				.saturating_add((150 * WEIGHT_PER_NANOS).saturating_mul(T::MaxPending::get().into()))
+				.saturating_add((150 * WEIGHT_PER_NANOS).saturating_mul(T::MaxProxies::get().into())),
+			di.class)
+		}]
+		fn proxy_announced(origin,
+			delegate: T::AccountId,
+			real: T::AccountId,
+			force_proxy_type: Option<T::ProxyType>,
+			call: Box<<T as Trait>::Call>,
+		) {
+			ensure_signed(origin)?;
+			let def = Self::find_proxy(&real, &delegate, force_proxy_type)?;
+
+			let call_hash = T::CallHasher::hash_of(&call);
+			let now = system::Module::<T>::block_number();
+			Self::edit_announcements(&delegate, |ann|
+				ann.real != real || ann.call_hash != call_hash
+					|| now.saturating_sub(ann.height) < def.delay
+			).map_err(|_| Error::<T>::Unannounced)?;
+
+			Self::do_proxy(def, real, *call);
+		}
 	}
 }
 
@@ -411,4 +676,117 @@ impl<T: Trait> Module<T> {
 			.using_encoded(blake2_256);
 		T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
 	}
+
+	/// The deposit held for `count` proxies.
+	fn deposit(count: u32) -> BalanceOf<T> {
+		T::ProxyDepositBase::get() + T::ProxyDepositFactor::get() * count.into()
+	}
+
+	/// Reserve or unreserve the difference between `old_deposit` and the deposit needed for
+	/// `len` announcements of `who`, returning the new deposit.
+	fn rejig_announcement_deposit(
+		who: &T::AccountId,
+		old_deposit: BalanceOf<T>,
+		len: usize,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let new_deposit = if len == 0 {
+			BalanceOf::<T>::zero()
+		} else {
+			T::AnnouncementDepositBase::get()
+				+ T::AnnouncementDepositFactor::get() * (len as u32).into()
+		};
+		if new_deposit > old_deposit {
+			T::Currency::reserve(&who, new_deposit - old_deposit)?;
+		} else if new_deposit < old_deposit {
+			T::Currency::unreserve(&who, old_deposit - new_deposit);
+		}
+		Ok(new_deposit)
+	}
+
+	/// Retain only the announcements of `delegate` for which `f` returns `true`, adjusting its
+	/// deposit accordingly.
+	///
+	/// Fails with `NotFound` if no announcement was removed.
+	fn edit_announcements<
+		F: FnMut(&Announcement<T::AccountId, CallHashOf<T>, T::BlockNumber>) -> bool
+	>(delegate: &T::AccountId, f: F) -> DispatchResult {
+		Announcements::<T>::try_mutate_exists(delegate, |x| {
+			let (mut pending, old_deposit) = x.take().ok_or(Error::<T>::NotFound)?;
+			let orig_pending_len = pending.len();
+			pending.retain(f);
+			ensure!(orig_pending_len > pending.len(), Error::<T>::NotFound);
+			let len = pending.len();
+			let new_deposit = Self::rejig_announcement_deposit(delegate, old_deposit, len)?;
+			if !pending.is_empty() {
+				*x = Some((pending, new_deposit));
+			}
+			Ok(())
+		})
+	}
+
+	fn find_proxy(
+		real: &T::AccountId,
+		delegate: &T::AccountId,
+		force_proxy_type: Option<T::ProxyType>,
+	) -> Result<ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>, DispatchError> {
+		let f = |x: &ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>| -> bool {
+			&x.delegate == delegate && force_proxy_type.as_ref().map_or(true, |y| &x.proxy_type == y)
+		};
+		Ok(Proxies::<T>::get(real).0.into_iter().find(f).ok_or(Error::<T>::NotProxy)?)
+	}
+
+	fn do_proxy(
+		def: ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>,
+		real: T::AccountId,
+		call: <T as Trait>::Call,
+	) {
+		// This is a freshly authenticated new account, the origin restrictions doesn't apply.
+		let mut origin: T::Origin = frame_system::RawOrigin::Signed(real).into();
+		origin.add_filter(move |c: &<T as frame_system::Trait>::Call| {
+			let c = <T as Trait>::Call::from_ref(c);
+			match c.is_sub_type() {
+				Some(Call::add_proxy(_, ref pt, _)) | Some(Call::remove_proxy(_, ref pt, _))
+					if !def.proxy_type.is_superset(&pt) => false,
+				Some(Call::remove_proxies(..)) | Some(Call::kill_anonymous(..))
+					if def.proxy_type != T::ProxyType::default() => false,
+				_ => def.proxy_type.filter(c)
+			}
+		});
+		let e = call.dispatch(origin);
+		Self::deposit_event(RawEvent::ProxyExecuted(e.map(|_| ()).map_err(|e| e.error)));
+	}
+}
+
+impl<T: Trait> GetStorageVersion for Module<T> {
+	fn storage_prefix() -> &'static [u8] {
+		b"Proxy"
+	}
+
+	fn current_storage_version() -> StorageVersion {
+		STORAGE_VERSION
+	}
+}
+
+/// Migrate the proxies of every account from `(delegate, proxy_type)` pairs to
+/// `ProxyDefinition`s with a zero delay.
+///
+/// This takes the pallet from storage version `0` to `1`.
+pub struct MigrateToTimeDelayedProxies<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> OnRuntimeUpgrade for MigrateToTimeDelayedProxies<T> {
+	fn on_runtime_upgrade() -> Weight {
+		Proxies::<T>::translate::<(Vec<(T::AccountId, T::ProxyType)>, BalanceOf<T>), _>(
+			|_, (targets, deposit)| Some((
+				targets.into_iter()
+					.map(|(delegate, proxy_type)| ProxyDefinition {
+						delegate,
+						proxy_type,
+						delay: Zero::zero(),
+					})
+					.collect::<Vec<_>>(),
+				deposit,
+			))
+		);
+		T::MaximumBlockWeight::get()
+	}
 }
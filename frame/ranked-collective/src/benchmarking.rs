@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Benchmarks for Ranked Collective Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::UnfilteredDispatchable;
+
+use crate::Module as RankedCollective;
+
+const SEED: u32 = 0;
+
+fn make_member<T: Trait>(rank: Rank) -> Result<T::AccountId, DispatchError> {
+	let who = account::<T::AccountId>("member", MemberCount::get(0), SEED);
+	RankedCollective::<T>::add_member(T::PromoteOrigin::successful_origin(), who.clone())?;
+	for _ in 0..rank {
+		RankedCollective::<T>::promote_member(T::PromoteOrigin::successful_origin(), who.clone())?;
+	}
+	Ok(who)
+}
+
+benchmarks! {
+	_ { }
+
+	add_member {
+		let count = MemberCount::get(0);
+		let who = account::<T::AccountId>("member", 0, SEED);
+		let origin = T::PromoteOrigin::successful_origin();
+		let call = Call::<T>::add_member(who.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(MemberCount::get(0), count + 1);
+		assert_eq!(RankedCollective::<T>::members(&who), Some(MemberRecord { rank: 0 }));
+	}
+
+	// The weight does not depend on the rank, which is only varied to confirm that.
+	promote_member {
+		let r in 0 .. 10;
+		let who = make_member::<T>(r as Rank)?;
+		let origin = T::PromoteOrigin::successful_origin();
+		let call = Call::<T>::promote_member(who.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		let rank = r as Rank + 1;
+		assert_eq!(RankedCollective::<T>::members(&who), Some(MemberRecord { rank }));
+	}
+
+	// The worst case demotes the member from the end of the rank, while another member has to
+	// be moved.
+	demote_member {
+		let r in 1 .. 10;
+		let rank = r as Rank;
+		make_member::<T>(rank)?;
+		let who = make_member::<T>(rank)?;
+		let count = MemberCount::get(rank);
+		let origin = T::DemoteOrigin::successful_origin();
+		let call = Call::<T>::demote_member(who.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(RankedCollective::<T>::members(&who), Some(MemberRecord { rank: rank - 1 }));
+		assert_eq!(MemberCount::get(rank), count - 1);
+	}
+
+	remove_member {
+		let r in 0 .. 10;
+		make_member::<T>(r as Rank)?;
+		let who = make_member::<T>(r as Rank)?;
+		let count = MemberCount::get(0);
+		let origin = T::DemoteOrigin::successful_origin();
+		let call = Call::<T>::remove_member(who.clone(), r as Rank);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(RankedCollective::<T>::members(&who), None);
+		assert_eq!(MemberCount::get(0), count - 1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_member::<Test>());
+			assert_ok!(test_benchmark_promote_member::<Test>());
+			assert_ok!(test_benchmark_demote_member::<Test>());
+			assert_ok!(test_benchmark_remove_member::<Test>());
+		});
+	}
+}
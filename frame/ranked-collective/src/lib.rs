@@ -0,0 +1,512 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Ranked Collective Pallet
+//!
+//! - [`ranked_collective::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Ranked Collective pallet keeps a set of members, each of which holds an integer rank,
+//! starting at zero. Members vote on the polls of a pallet implementing the `Polling` trait of
+//! `frame_support`, whose class is the minimum rank needed to vote on a poll. The vote of a
+//! member counts `rank - min_rank + 1` times, so higher ranked members carry more weight.
+//!
+//! Membership is managed by origins which carry a rank themselves, e.g. [`EnsureRootOrRanked`]:
+//! an origin may only add members, or promote them, to ranks strictly below its own, and may only
+//! demote or remove members ranked strictly below its own.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `add_member` - Add a member at rank zero, called by the `PromoteOrigin`.
+//! - `promote_member` - Increment the rank of a member, called by the `PromoteOrigin`.
+//! - `demote_member` - Decrement the rank of a member, removing it at rank zero, called by the
+//!   `DemoteOrigin`.
+//! - `remove_member` - Remove a member entirely, called by the `DemoteOrigin`.
+//! - `vote` - Vote on an ongoing poll as a member of sufficient rank.
+//! - `cleanup_poll` - Remove the votes of a poll which is no longer ongoing.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::{prelude::*, fmt, marker::PhantomData};
+use codec::{Encode, Decode};
+use sp_runtime::{Perbill, RuntimeDebug, DispatchError};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure,
+	weights::Weight,
+	traits::{EnsureOrigin, Get, VoteTally, PollStatus, Polling},
+};
+use frame_system::{ensure_signed, RawOrigin};
+
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+
+/// A number of members.
+pub type MemberIndex = u32;
+
+/// Member rank.
+pub type Rank = u16;
+
+/// Votes.
+pub type Votes = u32;
+
+/// The tally of a poll in a ranked collective.
+///
+/// `M` gives the number of members against which the support of the poll is measured, depending
+/// on the minimum rank of the poll.
+#[derive(Encode, Decode)]
+pub struct Tally<M> {
+	/// The number of members voting aye, irrespective of their rank.
+	pub bare_ayes: MemberIndex,
+	/// The number of aye votes, weighted by rank.
+	pub ayes: Votes,
+	/// The number of nay votes, weighted by rank.
+	pub nays: Votes,
+	#[codec(skip)]
+	dummy: PhantomData<M>,
+}
+
+// The fields are plain numbers, so a tally can be copied whichever member count `M` it is measured
+// against; deriving would also ask `M` to be `Copy`, `Debug` and so on.
+
+impl<M> Copy for Tally<M> {}
+
+impl<M> Clone for Tally<M> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<M> PartialEq for Tally<M> {
+	fn eq(&self, other: &Self) -> bool {
+		self.bare_ayes == other.bare_ayes && self.ayes == other.ayes && self.nays == other.nays
+	}
+}
+
+impl<M> Eq for Tally<M> {}
+
+impl<M> Default for Tally<M> {
+	fn default() -> Self {
+		Self::from_parts(0, 0, 0)
+	}
+}
+
+impl<M> fmt::Debug for Tally<M> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Tally")
+			.field("bare_ayes", &self.bare_ayes)
+			.field("ayes", &self.ayes)
+			.field("nays", &self.nays)
+			.finish()
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("<wasm:stripped>")
+	}
+}
+
+impl<M> Tally<M> {
+	/// Create a tally from its constituent parts.
+	pub fn from_parts(bare_ayes: MemberIndex, ayes: Votes, nays: Votes) -> Self {
+		Tally { bare_ayes, ayes, nays, dummy: PhantomData }
+	}
+}
+
+impl<M: GetMaxVoters> VoteTally<Rank> for Tally<M> {
+	fn approval(&self) -> Perbill {
+		Perbill::from_rational_approximation(self.ayes, self.ayes.saturating_add(self.nays))
+	}
+
+	fn support(&self, min_rank: Rank) -> Perbill {
+		Perbill::from_rational_approximation(self.bare_ayes, M::get_max_voters(min_rank))
+	}
+}
+
+/// The number of members which may vote on the polls of a given minimum rank.
+pub trait GetMaxVoters {
+	/// Return the number of members of at least `min_rank`.
+	fn get_max_voters(min_rank: Rank) -> MemberIndex;
+}
+
+/// The number of members of a ranked collective of at least a given rank.
+pub struct MemberCountOf<T>(PhantomData<T>);
+impl<T: Trait> GetMaxVoters for MemberCountOf<T> {
+	fn get_max_voters(min_rank: Rank) -> MemberIndex {
+		MemberCount::get(min_rank)
+	}
+}
+
+/// The tally of a poll voted on by the members of the collective.
+pub type TallyOf<T> = Tally<MemberCountOf<T>>;
+/// The index of a poll.
+pub type PollIndexOf<T> = <<T as Trait>::Polls as Polling<TallyOf<T>>>::Index;
+
+/// Record needed for every member.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MemberRecord {
+	/// The rank of the member.
+	pub rank: Rank,
+}
+
+/// Record needed for every vote.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum VoteRecord {
+	/// Vote was an aye with given vote weight.
+	Aye(Votes),
+	/// Vote was a nay with given vote weight.
+	Nay(Votes),
+}
+
+impl From<(bool, Votes)> for VoteRecord {
+	fn from((aye, votes): (bool, Votes)) -> Self {
+		match aye {
+			true => VoteRecord::Aye(votes),
+			false => VoteRecord::Nay(votes),
+		}
+	}
+}
+
+/// Ensures that the origin is _Root_, which may act on any rank, or a signed member of at least
+/// `MinRank`, which may act on the ranks below its own. Gives the rank of the origin.
+pub struct EnsureRootOrRanked<T, MinRank>(PhantomData<(T, MinRank)>);
+impl<T: Trait, MinRank: Get<Rank>> EnsureOrigin<T::Origin> for EnsureRootOrRanked<T, MinRank> {
+	type Success = Rank;
+
+	fn try_origin(o: T::Origin) -> Result<Rank, T::Origin> {
+		o.into().and_then(|o| {
+			let rank = match &o {
+				RawOrigin::Root => Some(Rank::max_value()),
+				RawOrigin::Signed(who) => Members::<T>::get(who)
+					.map(|record| record.rank)
+					.filter(|rank| *rank >= MinRank::get()),
+				_ => None,
+			};
+			rank.ok_or_else(|| T::Origin::from(o))
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> T::Origin {
+		T::Origin::from(RawOrigin::Root)
+	}
+}
+
+pub trait WeightInfo {
+	fn add_member() -> Weight;
+	fn promote_member() -> Weight;
+	fn demote_member() -> Weight;
+	fn remove_member(r: u32, ) -> Weight;
+	fn vote() -> Weight;
+	fn cleanup_poll() -> Weight;
+}
+
+impl WeightInfo for () {
+	fn add_member() -> Weight { 1_000_000_000 }
+	fn promote_member() -> Weight { 1_000_000_000 }
+	fn demote_member() -> Weight { 1_000_000_000 }
+	fn remove_member(_r: u32, ) -> Weight { 1_000_000_000 }
+	fn vote() -> Weight { 1_000_000_000 }
+	fn cleanup_poll() -> Weight { 1_000_000_000 }
+}
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// Origin which may add members and promote them, giving the rank of the origin. Members
+	/// may only be added or promoted to ranks below it.
+	type PromoteOrigin: EnsureOrigin<Self::Origin, Success = Rank>;
+
+	/// Origin which may demote and remove members, giving the rank of the origin. Only members
+	/// ranked below it may be demoted or removed.
+	type DemoteOrigin: EnsureOrigin<Self::Origin, Success = Rank>;
+
+	/// The polls which the members may vote on. The class of a poll is the minimum rank needed
+	/// to vote on it.
+	type Polls: Polling<TallyOf<Self>, Class = Rank, Moment = Self::BlockNumber>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as RankedCollective {
+		/// The number of members in the collective who have at least the rank according to the
+		/// index.
+		pub MemberCount get(fn member_count): map hasher(twox_64_concat) Rank => MemberIndex;
+
+		/// The current members of the collective.
+		pub Members get(fn members):
+			map hasher(twox_64_concat) T::AccountId => Option<MemberRecord>;
+
+		/// The index of each ranks's member into the group of members who have at least that
+		/// rank.
+		pub IdToIndex get(fn id_to_index):
+			double_map hasher(twox_64_concat) Rank, hasher(twox_64_concat) T::AccountId
+			=> Option<MemberIndex>;
+
+		/// The members in the collective by index. All indices in the range `0..MemberCount`
+		/// will return `Some`, however a member's index is not guaranteed to remain unchanged
+		/// over time.
+		pub IndexToId get(fn index_to_id):
+			double_map hasher(twox_64_concat) Rank, hasher(twox_64_concat) MemberIndex
+			=> Option<T::AccountId>;
+
+		/// Votes on a given poll, if it is ongoing.
+		pub Voting get(fn voting):
+			double_map hasher(twox_64_concat) PollIndexOf<T>, hasher(twox_64_concat) T::AccountId
+			=> Option<VoteRecord>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		PollIndex = PollIndexOf<T>,
+	{
+		/// A member has been added at rank zero. \[who\]
+		MemberAdded(AccountId),
+		/// The rank of a member has changed. \[who, rank\]
+		RankChanged(AccountId, Rank),
+		/// A member has been removed, it had the given rank. \[who, rank\]
+		MemberRemoved(AccountId, Rank),
+		/// A member has voted on a poll. \[who, poll, vote\]
+		Voted(AccountId, PollIndex, VoteRecord),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Account is already a member.
+		AlreadyMember,
+		/// Account is not a member.
+		NotMember,
+		/// The given poll index is unknown or has closed.
+		NotPolling,
+		/// The given poll is still ongoing.
+		Ongoing,
+		/// The member's rank is too low to vote.
+		RankTooLow,
+		/// The information provided is incorrect.
+		InvalidWitness,
+		/// The origin is not sufficiently privileged to do the operation.
+		NoPermission,
+		/// The rank cannot be increased any further.
+		RankOverflow,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Introduce a new member at rank zero.
+		///
+		/// The dispatch origin of this call must be the `PromoteOrigin`, of a rank above zero.
+		///
+		/// - `who`: Account of non-member which will become a member.
+		#[weight = T::WeightInfo::add_member()]
+		fn add_member(origin, who: T::AccountId) {
+			let max_rank = T::PromoteOrigin::ensure_origin(origin)?;
+			ensure!(max_rank > 0, Error::<T>::NoPermission);
+			ensure!(!Members::<T>::contains_key(&who), Error::<T>::AlreadyMember);
+
+			Self::add_to_rank(&who, 0);
+			Members::<T>::insert(&who, MemberRecord { rank: 0 });
+
+			Self::deposit_event(RawEvent::MemberAdded(who));
+		}
+
+		/// Increment the rank of an existing member by one.
+		///
+		/// The dispatch origin of this call must be the `PromoteOrigin`, of a rank above the new
+		/// rank of the member.
+		///
+		/// - `who`: Account of existing member.
+		///
+		/// Weight: `O(1)`, only the new rank of the member is touched.
+		#[weight = T::WeightInfo::promote_member()]
+		fn promote_member(origin, who: T::AccountId) {
+			let max_rank = T::PromoteOrigin::ensure_origin(origin)?;
+			let record = Self::ensure_member(&who)?;
+			let rank = record.rank.checked_add(1).ok_or(Error::<T>::RankOverflow)?;
+			ensure!(rank < max_rank, Error::<T>::NoPermission);
+
+			Self::add_to_rank(&who, rank);
+			Members::<T>::insert(&who, MemberRecord { rank });
+
+			Self::deposit_event(RawEvent::RankChanged(who, rank));
+		}
+
+		/// Decrement the rank of an existing member by one. If the member is already at rank
+		/// zero, then they are removed entirely.
+		///
+		/// The dispatch origin of this call must be the `DemoteOrigin`, of a rank above the
+		/// current rank of the member.
+		///
+		/// - `who`: Account of existing member.
+		///
+		/// Weight: `O(1)`, only the current rank of the member is touched.
+		#[weight = T::WeightInfo::demote_member()]
+		fn demote_member(origin, who: T::AccountId) {
+			let max_rank = T::DemoteOrigin::ensure_origin(origin)?;
+			let record = Self::ensure_member(&who)?;
+			let rank = record.rank;
+			ensure!(rank < max_rank, Error::<T>::NoPermission);
+
+			Self::remove_from_rank(&who, rank);
+			match rank.checked_sub(1) {
+				Some(rank) => {
+					Members::<T>::insert(&who, MemberRecord { rank });
+					Self::deposit_event(RawEvent::RankChanged(who, rank));
+				}
+				None => {
+					Members::<T>::remove(&who);
+					Self::deposit_event(RawEvent::MemberRemoved(who, 0));
+				}
+			}
+		}
+
+		/// Remove the member entirely.
+		///
+		/// The dispatch origin of this call must be the `DemoteOrigin`, of a rank above the
+		/// current rank of the member.
+		///
+		/// - `who`: Account of existing member, of any rank.
+		/// - `min_rank`: The rank of the member or greater.
+		///
+		/// Weight: `O(min_rank)`.
+		#[weight = T::WeightInfo::remove_member(*min_rank as u32)]
+		fn remove_member(origin, who: T::AccountId, min_rank: Rank) {
+			let max_rank = T::DemoteOrigin::ensure_origin(origin)?;
+			let MemberRecord { rank } = Self::ensure_member(&who)?;
+			ensure!(min_rank >= rank, Error::<T>::InvalidWitness);
+			ensure!(rank < max_rank, Error::<T>::NoPermission);
+
+			for r in 0..=rank {
+				Self::remove_from_rank(&who, r);
+			}
+			Members::<T>::remove(&who);
+
+			Self::deposit_event(RawEvent::MemberRemoved(who, rank));
+		}
+
+		/// Add an aye or nay vote for the sender to the given proposal.
+		///
+		/// The dispatch origin of this call must be _Signed_ by a member account of at least the
+		/// minimum rank of the poll. Voting again replaces the previous vote.
+		///
+		/// - `poll`: Index of a poll which is ongoing.
+		/// - `aye`: `true` if the vote is to approve the proposal, `false` otherwise.
+		#[weight = T::WeightInfo::vote()]
+		fn vote(origin, poll: PollIndexOf<T>, aye: bool) {
+			let who = ensure_signed(origin)?;
+			let record = Self::ensure_member(&who)?;
+			let vote = T::Polls::try_access_poll(poll, |status| -> Result<_, DispatchError> {
+				match status {
+					PollStatus::None | PollStatus::Completed(..) =>
+						Err(Error::<T>::NotPolling.into()),
+					PollStatus::Ongoing(tally, min_rank) => {
+						match Voting::<T>::get(&poll, &who) {
+							Some(VoteRecord::Aye(votes)) => {
+								tally.bare_ayes = tally.bare_ayes.saturating_sub(1);
+								tally.ayes = tally.ayes.saturating_sub(votes);
+							}
+							Some(VoteRecord::Nay(votes)) => {
+								tally.nays = tally.nays.saturating_sub(votes);
+							}
+							None => {}
+						}
+						let votes = Self::rank_to_votes(record.rank, min_rank)?;
+						match aye {
+							true => {
+								tally.bare_ayes = tally.bare_ayes.saturating_add(1);
+								tally.ayes = tally.ayes.saturating_add(votes);
+							}
+							false => tally.nays = tally.nays.saturating_add(votes),
+						}
+						let vote = VoteRecord::from((aye, votes));
+						Voting::<T>::insert(&poll, &who, vote);
+						Ok(vote)
+					}
+				}
+			})?;
+			Self::deposit_event(RawEvent::Voted(who, poll, vote));
+		}
+
+		/// Remove the votes of a poll which is no longer ongoing.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `poll_index`: Index of a poll which is completed and for which votes continue to
+		///   exist.
+		#[weight = T::WeightInfo::cleanup_poll()]
+		fn cleanup_poll(origin, poll_index: PollIndexOf<T>) {
+			ensure_signed(origin)?;
+			ensure!(
+				T::Polls::access_poll(poll_index, |status| status.ensure_ongoing().is_none()),
+				Error::<T>::Ongoing
+			);
+			Voting::<T>::remove_prefix(&poll_index);
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The record of a member.
+	fn ensure_member(who: &T::AccountId) -> Result<MemberRecord, DispatchError> {
+		Members::<T>::get(who).ok_or_else(|| Error::<T>::NotMember.into())
+	}
+
+	/// The votes of a member of `rank` on a poll of `min_rank`.
+	fn rank_to_votes(rank: Rank, min_rank: Rank) -> Result<Votes, DispatchError> {
+		let excess = rank.checked_sub(min_rank).ok_or(Error::<T>::RankTooLow)?;
+		Ok(Votes::from(excess) + 1)
+	}
+
+	/// Add `who` to the members of at least `rank`.
+	fn add_to_rank(who: &T::AccountId, rank: Rank) {
+		let index = MemberCount::get(rank);
+		IdToIndex::<T>::insert(rank, who, index);
+		IndexToId::<T>::insert(rank, index, who);
+		MemberCount::insert(rank, index + 1);
+	}
+
+	/// Remove `who` from the members of at least `rank`, moving the last of those members into
+	/// its index.
+	fn remove_from_rank(who: &T::AccountId, rank: Rank) {
+		let last_index = MemberCount::get(rank).saturating_sub(1);
+		if let Some(index) = IdToIndex::<T>::take(rank, who) {
+			if index != last_index {
+				if let Some(last) = IndexToId::<T>::get(rank, last_index) {
+					IdToIndex::<T>::insert(rank, &last, index);
+					IndexToId::<T>::insert(rank, index, &last);
+				}
+			}
+			IndexToId::<T>::remove(rank, last_index);
+			MemberCount::insert(rank, last_index);
+		}
+	}
+}
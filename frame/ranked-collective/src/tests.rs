@@ -0,0 +1,302 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for Ranked Collective Pallet
+
+#![cfg(test)]
+
+use super::*;
+
+use std::{cell::RefCell, collections::BTreeMap};
+use frame_support::{assert_ok, assert_noop, impl_outer_origin, parameter_types};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::{BlakeTwo256, IdentityLookup, BadOrigin}};
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = ();
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TestPollState {
+	Ongoing(TallyOf<Test>, Rank),
+	Completed(u64, bool),
+}
+use TestPollState::*;
+
+thread_local! {
+	static POLLS: RefCell<BTreeMap<u8, TestPollState>> = RefCell::new(vec![
+		(1, Ongoing(Tally::default(), 1)),
+		(2, Ongoing(Tally::default(), 2)),
+		(3, Completed(1, true)),
+	].into_iter().collect());
+}
+
+/// The polls voted on in the tests, with the minimum rank of their voters.
+pub struct TestPolls;
+impl TestPolls {
+	fn set(index: u8, state: TestPollState) {
+		POLLS.with(|p| p.borrow_mut().insert(index, state));
+	}
+
+	fn tally(index: u8) -> TallyOf<Test> {
+		match POLLS.with(|p| p.borrow().get(&index).cloned()) {
+			Some(Ongoing(tally, _)) => tally,
+			other => panic!("poll {} is not ongoing: {:?}", index, other),
+		}
+	}
+}
+
+impl Polling<TallyOf<Test>> for TestPolls {
+	type Index = u8;
+	type Class = Rank;
+	type Moment = u64;
+
+	fn access_poll<R>(
+		index: u8,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, Rank>) -> R,
+	) -> R {
+		let mut polls = POLLS.with(|p| p.borrow().clone());
+		let r = match polls.get_mut(&index) {
+			Some(Ongoing(ref mut tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(Completed(end, approved)) => f(PollStatus::Completed(*end, *approved)),
+			None => f(PollStatus::None),
+		};
+		POLLS.with(|p| *p.borrow_mut() = polls);
+		r
+	}
+
+	fn try_access_poll<R>(
+		index: u8,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, Rank>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		let mut polls = POLLS.with(|p| p.borrow().clone());
+		let r = match polls.get_mut(&index) {
+			Some(Ongoing(ref mut tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(Completed(end, approved)) => f(PollStatus::Completed(*end, *approved)),
+			None => f(PollStatus::None),
+		}?;
+		POLLS.with(|p| *p.borrow_mut() = polls);
+		Ok(r)
+	}
+}
+
+parameter_types! {
+	pub const MinPromoterRank: Rank = 1;
+}
+impl Trait for Test {
+	type Event = ();
+	type PromoteOrigin = EnsureRootOrRanked<Test, MinPromoterRank>;
+	type DemoteOrigin = EnsureRootOrRanked<Test, MinPromoterRank>;
+	type Polls = TestPolls;
+	type WeightInfo = ();
+}
+type Club = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| frame_system::Module::<Test>::set_block_number(1));
+	ext
+}
+
+fn add_member_of_rank(who: u64, rank: Rank) {
+	assert_ok!(Club::add_member(Origin::root(), who));
+	for _ in 0..rank {
+		assert_ok!(Club::promote_member(Origin::root(), who));
+	}
+}
+
+fn member_count(rank: Rank) -> MemberIndex {
+	Club::member_count(rank)
+}
+
+fn tally(bare_ayes: MemberIndex, ayes: Votes, nays: Votes) -> TallyOf<Test> {
+	Tally::from_parts(bare_ayes, ayes, nays)
+}
+
+#[test]
+fn basic_membership_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Club::add_member(Origin::signed(1), 1), BadOrigin);
+		assert_ok!(Club::add_member(Origin::root(), 1));
+		assert_eq!(Club::members(1), Some(MemberRecord { rank: 0 }));
+		assert_noop!(Club::add_member(Origin::root(), 1), Error::<Test>::AlreadyMember);
+		assert_ok!(Club::promote_member(Origin::root(), 1));
+		assert_eq!(Club::members(1), Some(MemberRecord { rank: 1 }));
+		assert_eq!(member_count(0), 1);
+		assert_eq!(member_count(1), 1);
+		assert_eq!(member_count(2), 0);
+
+		assert_ok!(Club::demote_member(Origin::root(), 1));
+		assert_eq!(Club::members(1), Some(MemberRecord { rank: 0 }));
+		assert_eq!(member_count(1), 0);
+		assert_ok!(Club::demote_member(Origin::root(), 1));
+		assert_eq!(Club::members(1), None);
+		assert_eq!(member_count(0), 0);
+		assert_noop!(Club::demote_member(Origin::root(), 1), Error::<Test>::NotMember);
+	});
+}
+
+#[test]
+fn members_may_only_act_below_their_rank() {
+	new_test_ext().execute_with(|| {
+		add_member_of_rank(1, 0);
+		add_member_of_rank(2, 2);
+
+		// Rank zero is below the minimum rank of the origins.
+		assert_noop!(Club::add_member(Origin::signed(1), 3), BadOrigin);
+
+		assert_ok!(Club::add_member(Origin::signed(2), 3));
+		assert_ok!(Club::promote_member(Origin::signed(2), 3));
+		assert_eq!(Club::members(3), Some(MemberRecord { rank: 1 }));
+		assert_noop!(Club::promote_member(Origin::signed(2), 3), Error::<Test>::NoPermission);
+
+		// Member 3 has rank 1 now and may act on rank 0.
+		assert_ok!(Club::add_member(Origin::signed(3), 4));
+		assert_noop!(Club::promote_member(Origin::signed(3), 4), Error::<Test>::NoPermission);
+		assert_noop!(Club::demote_member(Origin::signed(3), 2), Error::<Test>::NoPermission);
+		assert_ok!(Club::demote_member(Origin::signed(3), 4));
+		assert_eq!(Club::members(4), None);
+
+		assert_noop!(Club::remove_member(Origin::signed(2), 2, 2), Error::<Test>::NoPermission);
+		assert_ok!(Club::demote_member(Origin::signed(2), 3));
+	});
+}
+
+#[test]
+fn remove_member_works() {
+	new_test_ext().execute_with(|| {
+		add_member_of_rank(1, 2);
+		add_member_of_rank(2, 1);
+		add_member_of_rank(3, 2);
+		assert_eq!(member_count(0), 3);
+		assert_eq!(member_count(2), 2);
+
+		assert_noop!(
+			Club::remove_member(Origin::root(), 1, 1),
+			Error::<Test>::InvalidWitness,
+		);
+		assert_ok!(Club::remove_member(Origin::root(), 1, 2));
+		assert_eq!(Club::members(1), None);
+		assert_eq!(member_count(0), 2);
+		assert_eq!(member_count(1), 2);
+		assert_eq!(member_count(2), 1);
+
+		// The remaining members were moved into the freed indices.
+		assert_eq!(Club::index_to_id(2, 0), Some(3));
+		assert_eq!(Club::id_to_index(2, 3), Some(0));
+		assert_eq!(Club::index_to_id(2, 1), None);
+		let mut rank_zero = vec![Club::index_to_id(0, 0), Club::index_to_id(0, 1)];
+		rank_zero.sort();
+		assert_eq!(rank_zero, vec![Some(2), Some(3)]);
+	});
+}
+
+#[test]
+fn votes_are_weighted_by_rank() {
+	new_test_ext().execute_with(|| {
+		add_member_of_rank(1, 0);
+		add_member_of_rank(2, 1);
+		add_member_of_rank(3, 2);
+		add_member_of_rank(4, 3);
+
+		assert_noop!(Club::vote(Origin::signed(1), 1, true), Error::<Test>::RankTooLow);
+		assert_noop!(Club::vote(Origin::signed(5), 1, true), Error::<Test>::NotMember);
+
+		assert_ok!(Club::vote(Origin::signed(2), 1, true));
+		assert_ok!(Club::vote(Origin::signed(3), 1, false));
+		assert_ok!(Club::vote(Origin::signed(4), 1, true));
+		assert_eq!(TestPolls::tally(1), tally(2, 1 + 3, 2));
+		assert_eq!(Club::voting(1, 4), Some(VoteRecord::Aye(3)));
+
+		assert_noop!(Club::vote(Origin::signed(2), 2, true), Error::<Test>::RankTooLow);
+		assert_ok!(Club::vote(Origin::signed(3), 2, true));
+		assert_ok!(Club::vote(Origin::signed(4), 2, true));
+		assert_eq!(TestPolls::tally(2), tally(2, 1 + 2, 0));
+
+		// Both members of at least rank two support the poll, half of all members do.
+		let t = TestPolls::tally(2);
+		assert_eq!(t.support(2), Perbill::one());
+		assert_eq!(t.support(0), Perbill::from_percent(50));
+		assert_eq!(t.approval(), Perbill::one());
+	});
+}
+
+#[test]
+fn voting_again_replaces_the_vote() {
+	new_test_ext().execute_with(|| {
+		add_member_of_rank(1, 2);
+		assert_ok!(Club::vote(Origin::signed(1), 1, true));
+		assert_eq!(TestPolls::tally(1), tally(1, 2, 0));
+		assert_ok!(Club::vote(Origin::signed(1), 1, false));
+		assert_eq!(TestPolls::tally(1), tally(0, 0, 2));
+		assert_eq!(Club::voting(1, 1), Some(VoteRecord::Nay(2)));
+	});
+}
+
+#[test]
+fn cleanup_poll_works() {
+	new_test_ext().execute_with(|| {
+		add_member_of_rank(1, 1);
+		add_member_of_rank(2, 1);
+		assert_noop!(Club::vote(Origin::signed(1), 3, true), Error::<Test>::NotPolling);
+		assert_ok!(Club::vote(Origin::signed(1), 1, true));
+		assert_ok!(Club::vote(Origin::signed(2), 1, false));
+
+		assert_noop!(Club::cleanup_poll(Origin::signed(3), 1), Error::<Test>::Ongoing);
+		TestPolls::set(1, Completed(2, true));
+		assert_ok!(Club::cleanup_poll(Origin::signed(3), 1));
+		assert_eq!(Club::voting(1, 1), None);
+		assert_eq!(Club::voting(1, 2), None);
+	});
+}
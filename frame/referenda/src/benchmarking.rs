@@ -0,0 +1,147 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Benchmarks for Referenda Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::{OnInitialize, UnfilteredDispatchable};
+use sp_runtime::traits::{Bounded, Hash};
+
+use crate::Module as Referenda;
+
+const SEED: u32 = 0;
+
+// The benchmarks submit their proposals with the root origin, which must have a track.
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn root_origin<T: Trait>() -> T::PalletsOrigin where
+	T::PalletsOrigin: From<RawOrigin<T::AccountId>>,
+{
+	RawOrigin::Root.into()
+}
+
+fn root_enactment_delay<T: Trait>() -> T::BlockNumber where
+	T::PalletsOrigin: From<RawOrigin<T::AccountId>>,
+{
+	T::Tracks::track_for(&root_origin::<T>()).ok()
+		.and_then(|track| T::Tracks::info(track))
+		.map(|info| info.min_enactment_period)
+		.unwrap_or_default()
+}
+
+fn submit_root<T: Trait>(who: T::AccountId, i: u32) -> Result<ReferendumIndex, DispatchError> where
+	T::PalletsOrigin: From<RawOrigin<T::AccountId>>,
+{
+	let origin = RawOrigin::Signed(who).into();
+	let hash = T::Hashing::hash_of(&i);
+	let delay = root_enactment_delay::<T>();
+	Referenda::<T>::submit(origin, Box::new(root_origin::<T>()), hash, delay)?;
+	Ok(Referenda::<T>::referendum_count() - 1)
+}
+
+benchmarks! {
+	where_clause { where T::PalletsOrigin: From<RawOrigin<T::AccountId>> }
+
+	_ { }
+
+	submit {
+		let caller = funded_account::<T>("caller", 0);
+		let hash = T::Hashing::hash_of(&0u32);
+		let delay = root_enactment_delay::<T>();
+	}: _(RawOrigin::Signed(caller), Box::new(root_origin::<T>()), hash, delay)
+	verify {
+		assert!(Referenda::<T>::referendum_info(0).is_some());
+	}
+
+	place_decision_deposit {
+		let u in 0 .. 1000;
+		let caller = funded_account::<T>("caller", u);
+		let index = submit_root::<T>(caller.clone(), u)?;
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		match Referenda::<T>::referendum_info(index) {
+			Some(ReferendumInfo::Ongoing(status)) => assert!(status.decision_deposit.is_some()),
+			_ => panic!("referendum should be ongoing"),
+		}
+	}
+
+	cancel {
+		let u in 0 .. 1000;
+		let caller = funded_account::<T>("caller", u);
+		let index = submit_root::<T>(caller.clone(), u)?;
+		Referenda::<T>::place_decision_deposit(RawOrigin::Signed(caller).into(), index)?;
+		let origin = T::CancelOrigin::successful_origin();
+		let call = Call::<T>::cancel(index);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		let info = Referenda::<T>::referendum_info(index);
+		assert!(matches!(info, Some(ReferendumInfo::Cancelled(_))));
+	}
+
+	kill {
+		let u in 0 .. 1000;
+		let caller = funded_account::<T>("caller", u);
+		let index = submit_root::<T>(caller.clone(), u)?;
+		Referenda::<T>::place_decision_deposit(RawOrigin::Signed(caller).into(), index)?;
+		let origin = T::KillOrigin::successful_origin();
+		let call = Call::<T>::kill(index);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		let info = Referenda::<T>::referendum_info(index);
+		assert!(matches!(info, Some(ReferendumInfo::Killed(_))));
+	}
+
+	// The worst case times out `r` referenda at once.
+	on_initialize {
+		let r in 0 .. 100;
+		for i in 0 .. r {
+			let caller = funded_account::<T>("caller", i);
+			submit_root::<T>(caller, i)?;
+		}
+		let when = frame_system::Module::<T>::block_number() + T::UndecidingTimeout::get();
+	}: { Referenda::<T>::on_initialize(when); }
+	verify {
+		assert_eq!(Referenda::<T>::alarms(when).len(), 0);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_submit::<Test>());
+			assert_ok!(test_benchmark_place_decision_deposit::<Test>());
+			assert_ok!(test_benchmark_cancel::<Test>());
+			assert_ok!(test_benchmark_kill::<Test>());
+			assert_ok!(test_benchmark_on_initialize::<Test>());
+		});
+	}
+}
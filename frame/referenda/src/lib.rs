@@ -0,0 +1,660 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Referenda Pallet
+//!
+//! - [`referenda::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Referenda pallet runs any number of referenda at the same time. Each referendum is
+//! submitted with the origin its proposal should be dispatched with, and the origin determines
+//! the _track_ it is decided on. Every track has its own parameters, set by the runtime through
+//! [`TracksInfo`], so that e.g. a root referendum can require more support and a longer decision
+//! than a referendum spending a small amount of funds.
+//!
+//! Votes are not collected by this pallet: it implements the `Polling` trait of
+//! `frame_support`, through which a voting pallet updates the tally of each referendum.
+//!
+//! ### Lifecycle
+//!
+//! - **Submission:** Any account may submit a proposal hash, reserving the `SubmissionDeposit`.
+//!   The preimage of the proposal is requested from `Preimages`, so it may be noted for free.
+//! - **Preparation:** A referendum may start deciding once the track's `prepare_period` has
+//!   passed and someone reserved the track's `decision_deposit` for it. A referendum without a
+//!   decision deposit times out after `UndecidingTimeout`.
+//! - **Decision:** At most `max_deciding` referenda of a track are deciding at once, the others
+//!   wait in the queue of the track. During the `decision_period` the approval and support of
+//!   the tally must reach the track's `min_approval` and `min_support` curves, which are
+//!   evaluated at the proportion of the period that has passed.
+//! - **Confirmation:** A passing referendum must keep passing for the `confirm_period` to be
+//!   approved. It is rejected if it is not passing at the end of the decision period.
+//! - **Enactment:** An approved proposal is scheduled for dispatch with its origin once the
+//!   requested enactment delay has passed.
+//!
+//! Deposits are returned once a referendum is approved, rejected or cancelled. The submission
+//! deposit of a referendum which timed out, and all deposits of a killed referendum, are
+//! slashed.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `submit` - Submit a proposal to be decided on the track of its origin.
+//! - `place_decision_deposit` - Reserve the decision deposit of a referendum.
+//! - `cancel` - Cancel a referendum, returning its deposits. Called by the `CancelOrigin`.
+//! - `kill` - Cancel a referendum, slashing its deposits. Called by the `KillOrigin`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::Decode;
+use sp_runtime::{Perbill, DispatchError, traits::{One, Saturating}};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
+	weights::Weight,
+	traits::{
+		Currency, ReservableCurrency, EnsureOrigin, Get, OnUnbalanced, PreimageProvider,
+		VoteTally, PollStatus, Polling, schedule::{Anon as ScheduleAnon, HARD_DEADLINE},
+	},
+};
+use frame_system::ensure_signed;
+
+mod types;
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+
+pub use types::{
+	ReferendumIndex, Deposit, Curve, TrackInfo, TracksInfo, DecidingStatus, ReferendumStatus,
+	ReferendumInfo,
+};
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+/// The identifier of a track.
+pub type TrackIdOf<T> =
+	<<T as Trait>::Tracks as TracksInfo<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>>::Id;
+/// Info regarding an ongoing referendum.
+pub type ReferendumStatusOf<T> = ReferendumStatus<
+	TrackIdOf<T>,
+	<T as Trait>::PalletsOrigin,
+	<T as frame_system::Trait>::BlockNumber,
+	<T as frame_system::Trait>::Hash,
+	<T as frame_system::Trait>::AccountId,
+	BalanceOf<T>,
+	<T as Trait>::Tally,
+>;
+/// Info regarding a referendum, present or past.
+pub type ReferendumInfoOf<T> = ReferendumInfo<
+	TrackIdOf<T>,
+	<T as Trait>::PalletsOrigin,
+	<T as frame_system::Trait>::BlockNumber,
+	<T as frame_system::Trait>::Hash,
+	<T as frame_system::Trait>::AccountId,
+	BalanceOf<T>,
+	<T as Trait>::Tally,
+>;
+
+pub trait WeightInfo {
+	fn submit() -> Weight;
+	fn place_decision_deposit() -> Weight;
+	fn cancel() -> Weight;
+	fn kill() -> Weight;
+	fn on_initialize(r: u32, ) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn submit() -> Weight { 1_000_000_000 }
+	fn place_decision_deposit() -> Weight { 1_000_000_000 }
+	fn cancel() -> Weight { 1_000_000_000 }
+	fn kill() -> Weight { 1_000_000_000 }
+	fn on_initialize(_r: u32, ) -> Weight { 1_000_000_000 }
+}
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The call type of the proposals.
+	type Call: Parameter;
+
+	/// The caller origin, overarching type of all pallets origins, with which proposals are
+	/// dispatched.
+	type PalletsOrigin: Parameter;
+
+	/// The scheduler used to enact approved proposals.
+	type Scheduler: ScheduleAnon<Self::BlockNumber, <Self as Trait>::Call, Self::PalletsOrigin>;
+
+	/// The provider of the preimages of proposals.
+	type Preimages: PreimageProvider<Self::Hash>;
+
+	/// Currency type for this module.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// Origin from which any referendum may be cancelled, returning its deposits.
+	type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin from which any referendum may be killed, slashing its deposits.
+	type KillOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Handler for the unbalanced reduction when slashing a deposit.
+	type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+	/// The tally of the votes on a referendum, as updated by the voting system.
+	type Tally: VoteTally<TrackIdOf<Self>> + Parameter + Default;
+
+	/// The deposit reserved when submitting a referendum.
+	type SubmissionDeposit: Get<BalanceOf<Self>>;
+
+	/// The maximum number of referenda which may wait in the queue of a track.
+	type MaxQueued: Get<u32>;
+
+	/// The amount of time after which a referendum without decision deposit times out.
+	type UndecidingTimeout: Get<Self::BlockNumber>;
+
+	/// The interval in which deciding referenda are checked against the curves of their track.
+	/// Must be non-zero.
+	type AlarmInterval: Get<Self::BlockNumber>;
+
+	/// The tracks referenda are decided on.
+	type Tracks: TracksInfo<BalanceOf<Self>, Self::BlockNumber, Origin = Self::PalletsOrigin>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Referenda {
+		/// The number of referenda submitted so far, i.e. the next free referendum index.
+		pub ReferendumCount get(fn referendum_count): ReferendumIndex;
+
+		/// Information concerning any given referendum.
+		pub ReferendumInfoFor get(fn referendum_info):
+			map hasher(twox_64_concat) ReferendumIndex => Option<ReferendumInfoOf<T>>;
+
+		/// The referenda waiting for a decision slot on a track, oldest first.
+		pub TrackQueue get(fn track_queue):
+			map hasher(twox_64_concat) TrackIdOf<T> => Vec<ReferendumIndex>;
+
+		/// The number of referenda deciding on a track.
+		pub DecidingCount get(fn deciding_count): map hasher(twox_64_concat) TrackIdOf<T> => u32;
+
+		/// The referenda to be serviced in a block.
+		///
+		/// An entry is stale, and ignored, if the alarm of the referendum has been moved since.
+		pub Alarms get(fn alarms):
+			map hasher(twox_64_concat) T::BlockNumber => Vec<ReferendumIndex>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		<T as frame_system::Trait>::AccountId,
+		<T as frame_system::Trait>::Hash,
+		Balance = BalanceOf<T>,
+		TrackId = TrackIdOf<T>,
+	{
+		/// A referendum has been submitted on a track. \[index, track, proposal_hash\]
+		Submitted(ReferendumIndex, TrackId, Hash),
+		/// The decision deposit has been placed. \[index, who, amount\]
+		DecisionDepositPlaced(ReferendumIndex, AccountId, Balance),
+		/// A referendum has started deciding. \[index, track\]
+		DecisionStarted(ReferendumIndex, TrackId),
+		/// A referendum is passing and started confirming. \[index\]
+		ConfirmStarted(ReferendumIndex),
+		/// A confirming referendum stopped passing. \[index\]
+		ConfirmAborted(ReferendumIndex),
+		/// A referendum has been approved and its proposal scheduled. \[index\]
+		Approved(ReferendumIndex),
+		/// A referendum has been rejected. \[index\]
+		Rejected(ReferendumIndex),
+		/// A referendum has been cancelled. \[index\]
+		Cancelled(ReferendumIndex),
+		/// A referendum has timed out without decision deposit. \[index\]
+		TimedOut(ReferendumIndex),
+		/// A referendum has been killed. \[index\]
+		Killed(ReferendumIndex),
+		/// The preimage of an approved proposal is missing. \[proposal_hash, index\]
+		PreimageMissing(Hash, ReferendumIndex),
+		/// The preimage of an approved proposal is not a valid call. \[proposal_hash, index\]
+		PreimageInvalid(Hash, ReferendumIndex),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Referendum is not ongoing.
+		NotOngoing,
+		/// Referendum already has a decision deposit.
+		HasDeposit,
+		/// There is no track for the origin of the proposal.
+		NoTrack,
+		/// The enactment delay is shorter than the minimum of the track.
+		EnactmentTooSoon,
+	}
+}
+
+/// The ways in which a referendum ends.
+enum Conclusion {
+	Approved,
+	Rejected,
+	Cancelled,
+	TimedOut,
+	Killed,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The deposit reserved when submitting a referendum.
+		const SubmissionDeposit: BalanceOf<T> = T::SubmissionDeposit::get();
+
+		/// The maximum number of referenda which may wait in the queue of a track.
+		const MaxQueued: u32 = T::MaxQueued::get();
+
+		/// The amount of time after which a referendum without decision deposit times out.
+		const UndecidingTimeout: T::BlockNumber = T::UndecidingTimeout::get();
+
+		/// The interval in which deciding referenda are checked against their track.
+		const AlarmInterval: T::BlockNumber = T::AlarmInterval::get();
+
+		fn deposit_event() = default;
+
+		/// Submit a proposal to be decided on the track of `proposal_origin`.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must have funds to
+		/// cover the `SubmissionDeposit`.
+		///
+		/// - `proposal_origin`: The origin with which the proposal is dispatched.
+		/// - `proposal_hash`: The hash of the proposal preimage.
+		/// - `enactment_delay`: The delay between approval and dispatch. Must be at least the
+		///   `min_enactment_period` of the track.
+		///
+		/// Emits `Submitted`.
+		#[weight = T::WeightInfo::submit()]
+		fn submit(origin,
+			proposal_origin: Box<T::PalletsOrigin>,
+			proposal_hash: T::Hash,
+			enactment_delay: T::BlockNumber,
+		) {
+			let who = ensure_signed(origin)?;
+			let track = T::Tracks::track_for(&proposal_origin).map_err(|_| Error::<T>::NoTrack)?;
+			let info = T::Tracks::info(track).ok_or(Error::<T>::NoTrack)?;
+			ensure!(enactment_delay >= info.min_enactment_period, Error::<T>::EnactmentTooSoon);
+
+			let amount = T::SubmissionDeposit::get();
+			T::Currency::reserve(&who, amount)?;
+
+			let index = ReferendumCount::mutate(|count| {
+				let index = *count;
+				*count += 1;
+				index
+			});
+			T::Preimages::request_preimage(&proposal_hash);
+
+			let now = frame_system::Module::<T>::block_number();
+			let mut status = ReferendumStatus {
+				track,
+				origin: *proposal_origin,
+				proposal_hash,
+				enactment_delay,
+				submitted: now,
+				submission_deposit: Deposit { who, amount },
+				decision_deposit: None,
+				deciding: None,
+				tally: Default::default(),
+				in_queue: false,
+				alarm: None,
+			};
+			Self::set_alarm(index, &mut status, now.saturating_add(T::UndecidingTimeout::get()));
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+
+			Self::deposit_event(RawEvent::Submitted(index, track, proposal_hash));
+		}
+
+		/// Reserve the decision deposit of the track for a referendum, allowing it to start
+		/// deciding once it is prepared.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must have funds to
+		/// cover the `decision_deposit` of the track.
+		///
+		/// - `index`: The index of the ongoing referendum without decision deposit.
+		///
+		/// Emits `DecisionDepositPlaced`.
+		#[weight = T::WeightInfo::place_decision_deposit()]
+		fn place_decision_deposit(origin, index: ReferendumIndex) {
+			let who = ensure_signed(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			ensure!(status.decision_deposit.is_none(), Error::<T>::HasDeposit);
+			let info = T::Tracks::info(status.track).ok_or(Error::<T>::NoTrack)?;
+
+			let amount = info.decision_deposit;
+			T::Currency::reserve(&who, amount)?;
+			status.decision_deposit = Some(Deposit { who: who.clone(), amount });
+			Self::deposit_event(RawEvent::DecisionDepositPlaced(index, who, amount));
+
+			let now = frame_system::Module::<T>::block_number();
+			Self::service_referendum(now, index, status);
+		}
+
+		/// Cancel an ongoing referendum, returning its deposits.
+		///
+		/// The dispatch origin of this call must be the `CancelOrigin`.
+		///
+		/// Emits `Cancelled`.
+		#[weight = T::WeightInfo::cancel()]
+		fn cancel(origin, index: ReferendumIndex) {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let status = Self::ensure_ongoing(index)?;
+			let now = frame_system::Module::<T>::block_number();
+			Self::conclude(now, index, status, Conclusion::Cancelled);
+		}
+
+		/// Cancel an ongoing referendum, slashing its deposits.
+		///
+		/// The dispatch origin of this call must be the `KillOrigin`.
+		///
+		/// Emits `Killed`.
+		#[weight = T::WeightInfo::kill()]
+		fn kill(origin, index: ReferendumIndex) {
+			T::KillOrigin::ensure_origin(origin)?;
+			let status = Self::ensure_ongoing(index)?;
+			let now = frame_system::Module::<T>::block_number();
+			Self::conclude(now, index, status, Conclusion::Killed);
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let due = Alarms::<T>::take(now);
+			let weight = T::WeightInfo::on_initialize(due.len() as u32);
+			for index in due {
+				match ReferendumInfoFor::<T>::get(index) {
+					Some(ReferendumInfo::Ongoing(status)) if status.alarm == Some(now) =>
+						Self::service_referendum(now, index, status),
+					_ => {},
+				}
+			}
+			weight
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Returns the status of the ongoing referendum `index`.
+	fn ensure_ongoing(index: ReferendumIndex) -> Result<ReferendumStatusOf<T>, DispatchError> {
+		match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(status)) => Ok(status),
+			_ => Err(Error::<T>::NotOngoing.into()),
+		}
+	}
+
+	/// Service the referendum `index` at `when`, replacing any earlier alarm.
+	fn set_alarm(index: ReferendumIndex, status: &mut ReferendumStatusOf<T>, when: T::BlockNumber) {
+		if status.alarm != Some(when) {
+			Alarms::<T>::append(when, index);
+			status.alarm = Some(when);
+		}
+	}
+
+	/// Advance the referendum `index` as far as possible at `now`, and store it.
+	fn service_referendum(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		mut status: ReferendumStatusOf<T>,
+	) {
+		let info = match T::Tracks::info(status.track) {
+			Some(info) => info,
+			// The track has been removed, the referendum can never be decided.
+			None => return Self::conclude(now, index, status, Conclusion::Cancelled),
+		};
+
+		if status.deciding.is_none() {
+			if status.decision_deposit.is_none() {
+				let timeout = status.submitted.saturating_add(T::UndecidingTimeout::get());
+				if now >= timeout {
+					return Self::conclude(now, index, status, Conclusion::TimedOut)
+				}
+				Self::set_alarm(index, &mut status, timeout);
+			} else if status.in_queue {
+				// Queued referenda are serviced again once a decision slot is free.
+				status.alarm = None;
+			} else {
+				let prepared = status.submitted.saturating_add(info.prepare_period);
+				if now < prepared {
+					Self::set_alarm(index, &mut status, prepared);
+				} else if DecidingCount::<T>::get(status.track) < info.max_deciding {
+					DecidingCount::<T>::mutate(status.track, |count| *count += 1);
+					status.deciding = Some(DecidingStatus { since: now, confirming: None });
+					Self::deposit_event(RawEvent::DecisionStarted(index, status.track));
+				} else if Self::enqueue(status.track, index) {
+					status.in_queue = true;
+					status.alarm = None;
+				} else {
+					// The queue is full as well, try again later.
+					let retry = now.saturating_add(T::AlarmInterval::get());
+					Self::set_alarm(index, &mut status, retry);
+				}
+			}
+		}
+
+		let mut conclusion = None;
+		let mut next_alarm = now.saturating_add(T::AlarmInterval::get());
+		if let Some(deciding) = status.deciding.as_mut() {
+			let decision_end = deciding.since.saturating_add(info.decision_period);
+			let elapsed = now.saturating_sub(deciding.since);
+			let x = Perbill::from_rational_approximation(elapsed, info.decision_period);
+			let passing = info.min_approval.passing(x, status.tally.approval())
+				&& info.min_support.passing(x, status.tally.support(status.track));
+
+			if passing {
+				let confirm_end = *deciding.confirming.get_or_insert_with(|| {
+					Self::deposit_event(RawEvent::ConfirmStarted(index));
+					now.saturating_add(info.confirm_period)
+				});
+				if now >= confirm_end {
+					conclusion = Some(Conclusion::Approved);
+				}
+				next_alarm = next_alarm.min(confirm_end);
+			} else {
+				if deciding.confirming.take().is_some() {
+					Self::deposit_event(RawEvent::ConfirmAborted(index));
+				}
+				if now >= decision_end {
+					conclusion = Some(Conclusion::Rejected);
+				}
+			}
+			if now < decision_end {
+				next_alarm = next_alarm.min(decision_end);
+			}
+		}
+
+		match conclusion {
+			Some(conclusion) => Self::conclude(now, index, status, conclusion),
+			None => {
+				if status.deciding.is_some() {
+					Self::set_alarm(index, &mut status, next_alarm);
+				}
+				ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+			},
+		}
+	}
+
+	/// Append `index` to the queue of `track`. Returns `false` if the queue is full.
+	fn enqueue(track: TrackIdOf<T>, index: ReferendumIndex) -> bool {
+		TrackQueue::<T>::mutate(track, |queue| {
+			if (queue.len() as u32) < T::MaxQueued::get() {
+				queue.push(index);
+				true
+			} else {
+				false
+			}
+		})
+	}
+
+	/// End the referendum `index`, dealing with its deposits and enacting it if approved.
+	fn conclude(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		status: ReferendumStatusOf<T>,
+		conclusion: Conclusion,
+	) {
+		let submission = &status.submission_deposit;
+		let decision = status.decision_deposit.as_ref();
+		let info = match conclusion {
+			Conclusion::Approved => {
+				Self::refund_deposits(Some(submission).into_iter().chain(decision));
+				Self::enact(now, index, &status);
+				Self::deposit_event(RawEvent::Approved(index));
+				ReferendumInfo::Approved(now)
+			},
+			Conclusion::Rejected => {
+				Self::refund_deposits(Some(submission).into_iter().chain(decision));
+				Self::deposit_event(RawEvent::Rejected(index));
+				ReferendumInfo::Rejected(now)
+			},
+			Conclusion::Cancelled => {
+				Self::refund_deposits(Some(submission).into_iter().chain(decision));
+				Self::deposit_event(RawEvent::Cancelled(index));
+				ReferendumInfo::Cancelled(now)
+			},
+			Conclusion::TimedOut => {
+				Self::slash_deposits(Some(submission));
+				Self::refund_deposits(decision);
+				Self::deposit_event(RawEvent::TimedOut(index));
+				ReferendumInfo::TimedOut(now)
+			},
+			Conclusion::Killed => {
+				Self::slash_deposits(Some(submission).into_iter().chain(decision));
+				Self::deposit_event(RawEvent::Killed(index));
+				ReferendumInfo::Killed(now)
+			},
+		};
+		T::Preimages::unrequest_preimage(&status.proposal_hash);
+		ReferendumInfoFor::<T>::insert(index, info);
+
+		if status.in_queue {
+			TrackQueue::<T>::mutate(status.track, |queue| queue.retain(|i| *i != index));
+		}
+		if status.deciding.is_some() {
+			DecidingCount::<T>::mutate(status.track, |count| *count = count.saturating_sub(1));
+			Self::decide_next_queued(now, status.track);
+		}
+	}
+
+	/// Return the given deposits to their owners.
+	fn refund_deposits<'a>(
+		deposits: impl IntoIterator<Item = &'a Deposit<T::AccountId, BalanceOf<T>>>,
+	) {
+		for deposit in deposits {
+			T::Currency::unreserve(&deposit.who, deposit.amount);
+		}
+	}
+
+	/// Slash the given deposits, handing the funds to `Slash`.
+	fn slash_deposits<'a>(
+		deposits: impl IntoIterator<Item = &'a Deposit<T::AccountId, BalanceOf<T>>>,
+	) {
+		for deposit in deposits {
+			T::Slash::on_unbalanced(T::Currency::slash_reserved(&deposit.who, deposit.amount).0);
+		}
+	}
+
+	/// Start deciding the oldest referendum in the queue of `track`, if any.
+	fn decide_next_queued(now: T::BlockNumber, track: TrackIdOf<T>) {
+		let next = TrackQueue::<T>::mutate(track, |queue| {
+			if queue.is_empty() { None } else { Some(queue.remove(0)) }
+		});
+		if let Some(index) = next {
+			if let Some(ReferendumInfo::Ongoing(mut status)) = ReferendumInfoFor::<T>::get(index) {
+				status.in_queue = false;
+				Self::service_referendum(now, index, status);
+			}
+		}
+	}
+
+	/// Schedule the proposal of the approved referendum `index` for dispatch.
+	fn enact(now: T::BlockNumber, index: ReferendumIndex, status: &ReferendumStatusOf<T>) {
+		let hash = status.proposal_hash;
+		let preimage = match T::Preimages::get_preimage(&hash) {
+			Some(preimage) => preimage,
+			None => return Self::deposit_event(RawEvent::PreimageMissing(hash, index)),
+		};
+		let call = match <T as Trait>::Call::decode(&mut &preimage[..]) {
+			Ok(call) => call,
+			Err(_) => return Self::deposit_event(RawEvent::PreimageInvalid(hash, index)),
+		};
+		// The scheduler only accepts blocks in the future.
+		let when = now.saturating_add(status.enactment_delay).max(now.saturating_add(One::one()));
+		let result = T::Scheduler::schedule(when, None, HARD_DEADLINE, status.origin.clone(), call);
+		debug_assert!(result.is_ok(), "enactment is always scheduled in the future; qed");
+	}
+}
+
+impl<T: Trait> Polling<T::Tally> for Module<T> {
+	type Index = ReferendumIndex;
+	type Class = TrackIdOf<T>;
+	type Moment = T::BlockNumber;
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut T::Tally, T::BlockNumber, TrackIdOf<T>>) -> R,
+	) -> R {
+		match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track));
+				Self::note_tally_changed(index, status);
+				result
+			},
+			Some(ReferendumInfo::Approved(end)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(
+			PollStatus<&mut T::Tally, T::BlockNumber, TrackIdOf<T>>
+		) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track))?;
+				Self::note_tally_changed(index, status);
+				Ok(result)
+			},
+			Some(ReferendumInfo::Approved(end)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Store the altered tally of the ongoing referendum `index`, making sure it is serviced in
+	/// the next block.
+	fn note_tally_changed(index: ReferendumIndex, mut status: ReferendumStatusOf<T>) {
+		let next = frame_system::Module::<T>::block_number().saturating_add(One::one());
+		if status.deciding.is_some() && status.alarm.map_or(true, |alarm| alarm > next) {
+			Self::set_alarm(index, &mut status, next);
+		}
+		ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+	}
+}
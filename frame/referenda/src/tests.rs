@@ -0,0 +1,527 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for Referenda Pallet
+
+#![cfg(test)]
+
+use super::*;
+
+use codec::Encode;
+use frame_support::{
+	assert_ok, assert_noop, impl_outer_origin, impl_outer_dispatch, parameter_types,
+	traits::OnInitialize,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	RuntimeDebug, testing::Header, traits::{BlakeTwo256, IdentityLookup, Hash, BadOrigin},
+};
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		pallet_balances::Balances,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 2_000_000_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
+}
+impl pallet_scheduler::Trait for Test {
+	type Event = ();
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096;
+	pub const PreimageBaseDeposit: u64 = 2;
+	pub const PreimageByteDeposit: u64 = 1;
+}
+impl pallet_preimage::Trait for Test {
+	type Event = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+	type WeightInfo = ();
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Tally {
+	pub ayes: u32,
+	pub nays: u32,
+}
+
+impl VoteTally<u8> for Tally {
+	fn approval(&self) -> Perbill {
+		Perbill::from_rational_approximation(self.ayes, self.ayes + self.nays)
+	}
+
+	fn support(&self, _class: u8) -> Perbill {
+		Perbill::from_percent(self.ayes.min(100))
+	}
+}
+
+pub struct TestTracksInfo;
+impl TracksInfo<u64, u64> for TestTracksInfo {
+	type Id = u8;
+	type Origin = OriginCaller;
+
+	fn tracks() -> &'static [(u8, TrackInfo<u64, u64>)] {
+		static DATA: [(u8, TrackInfo<u64, u64>); 2] = [
+			(0u8, TrackInfo {
+				name: "root",
+				max_deciding: 1,
+				decision_deposit: 10,
+				prepare_period: 4,
+				decision_period: 4,
+				confirm_period: 2,
+				min_enactment_period: 4,
+				min_approval: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(100),
+					end: Perbill::from_percent(50),
+				},
+				min_support: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(50),
+					end: Perbill::from_percent(0),
+				},
+			}),
+			(1u8, TrackInfo {
+				name: "none",
+				max_deciding: 3,
+				decision_deposit: 1,
+				prepare_period: 2,
+				decision_period: 2,
+				confirm_period: 1,
+				min_enactment_period: 2,
+				min_approval: Curve::SteppedDecreasing {
+					begin: Perbill::from_percent(80),
+					end: Perbill::from_percent(50),
+					step: Perbill::from_percent(10),
+					period: Perbill::from_percent(25),
+				},
+				min_support: Curve::LinearDecreasing {
+					begin: Perbill::from_percent(20),
+					end: Perbill::from_percent(0),
+				},
+			}),
+		];
+		&DATA[..]
+	}
+
+	fn track_for(origin: &OriginCaller) -> Result<u8, ()> {
+		match origin {
+			OriginCaller::system(frame_system::RawOrigin::Root) => Ok(0),
+			OriginCaller::system(frame_system::RawOrigin::None) => Ok(1),
+			_ => Err(()),
+		}
+	}
+}
+
+parameter_types! {
+	pub const SubmissionDeposit: u64 = 2;
+	pub const MaxQueued: u32 = 3;
+	pub const UndecidingTimeout: u64 = 20;
+	pub const AlarmInterval: u64 = 1;
+}
+impl Trait for Test {
+	type Event = ();
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type Preimages = Preimage;
+	type Currency = Balances;
+	type CancelOrigin = EnsureRoot<u64>;
+	type KillOrigin = EnsureRoot<u64>;
+	type Slash = ();
+	type Tally = Tally;
+	type SubmissionDeposit = SubmissionDeposit;
+	type MaxQueued = MaxQueued;
+	type UndecidingTimeout = UndecidingTimeout;
+	type AlarmInterval = AlarmInterval;
+	type Tracks = TestTracksInfo;
+	type WeightInfo = ();
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Scheduler = pallet_scheduler::Module<Test>;
+type Preimage = pallet_preimage::Module<Test>;
+type Referenda = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100), (6, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn run_to(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Scheduler::on_initialize(next);
+		Referenda::on_initialize(next);
+	}
+}
+
+fn root() -> Box<OriginCaller> {
+	Box::new(frame_system::RawOrigin::Root.into())
+}
+
+fn set_balance_proposal(value: u64) -> Vec<u8> {
+	Call::Balances(pallet_balances::Call::set_balance(42, value, 0)).encode()
+}
+
+fn set_balance_proposal_hash(value: u64) -> H256 {
+	BlakeTwo256::hash(&set_balance_proposal(value)[..])
+}
+
+fn set_tally(index: ReferendumIndex, ayes: u32, nays: u32) {
+	<Referenda as Polling<Tally>>::access_poll(index, |status| {
+		let (tally, _) = status.ensure_ongoing().expect("referendum is ongoing");
+		*tally = Tally { ayes, nays };
+	});
+}
+
+fn ongoing(index: ReferendumIndex) -> ReferendumStatusOf<Test> {
+	match Referenda::referendum_info(index) {
+		Some(ReferendumInfo::Ongoing(status)) => status,
+		other => panic!("referendum {} is not ongoing: {:?}", index, other),
+	}
+}
+
+fn deciding(index: ReferendumIndex) -> Option<DecidingStatus<u64>> {
+	ongoing(index).deciding
+}
+
+#[test]
+fn basic_happy_path_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(1), set_balance_proposal(20)));
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_eq!(Referenda::referendum_count(), 1);
+		assert_eq!(Balances::reserved_balance(1), 2);
+		assert!(Preimage::preimage_requested(&set_balance_proposal_hash(20)));
+
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(2), 10);
+		set_tally(0, 100, 0);
+
+		// Preparing.
+		run_to(4);
+		assert_eq!(deciding(0), None);
+
+		// Deciding and passing, hence confirming.
+		run_to(5);
+		assert_eq!(deciding(0), Some(DecidingStatus { since: 5, confirming: Some(7) }));
+		assert_eq!(Referenda::deciding_count(0), 1);
+
+		run_to(7);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Approved(7)));
+		assert_eq!(Referenda::deciding_count(0), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!Preimage::preimage_requested(&set_balance_proposal_hash(20)));
+
+		// Enacted after the enactment delay.
+		run_to(10);
+		assert_eq!(Balances::free_balance(42), 0);
+		run_to(11);
+		assert_eq!(Balances::free_balance(42), 20);
+	});
+}
+
+#[test]
+fn submit_checks_origin_track_and_enactment() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(20);
+		let signed = Box::new(frame_system::RawOrigin::Signed(1).into());
+		assert_noop!(
+			Referenda::submit(Origin::signed(1), signed, h, 4),
+			Error::<Test>::NoTrack
+		);
+		assert_noop!(
+			Referenda::submit(Origin::signed(1), root(), h, 3),
+			Error::<Test>::EnactmentTooSoon
+		);
+		assert_noop!(Referenda::submit(Origin::none(), root(), h, 4), BadOrigin);
+		assert_ok!(Referenda::submit(
+			Origin::signed(1),
+			Box::new(frame_system::RawOrigin::None.into()),
+			h,
+			2,
+		));
+		assert_eq!(ongoing(0).track, 1);
+	});
+}
+
+#[test]
+fn place_decision_deposit_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Referenda::place_decision_deposit(Origin::signed(2), 0),
+			Error::<Test>::NotOngoing
+		);
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		assert_eq!(ongoing(0).decision_deposit, Some(Deposit { who: 2, amount: 10 }));
+		assert_noop!(
+			Referenda::place_decision_deposit(Origin::signed(3), 0),
+			Error::<Test>::HasDeposit
+		);
+	});
+}
+
+#[test]
+fn late_decision_deposit_starts_deciding_immediately() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		run_to(8);
+		assert_eq!(deciding(0), None);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		assert_eq!(deciding(0), Some(DecidingStatus { since: 8, confirming: None }));
+	});
+}
+
+#[test]
+fn failing_referendum_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		set_tally(0, 1, 9);
+
+		run_to(8);
+		assert!(deciding(0).is_some());
+		run_to(9);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Rejected(9)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert!(!Preimage::preimage_requested(&set_balance_proposal_hash(20)));
+	});
+}
+
+#[test]
+fn support_curve_is_followed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		// Full approval, but only enough support once half the decision period has passed.
+		set_tally(0, 25, 0);
+
+		run_to(6);
+		assert_eq!(deciding(0), Some(DecidingStatus { since: 5, confirming: None }));
+		run_to(7);
+		assert_eq!(deciding(0), Some(DecidingStatus { since: 5, confirming: Some(9) }));
+		run_to(9);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Approved(9)));
+	});
+}
+
+#[test]
+fn confirmation_is_aborted_when_no_longer_passing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		set_tally(0, 100, 0);
+
+		run_to(6);
+		assert_eq!(deciding(0), Some(DecidingStatus { since: 5, confirming: Some(7) }));
+		set_tally(0, 0, 100);
+		run_to(7);
+		assert_eq!(deciding(0), Some(DecidingStatus { since: 5, confirming: None }));
+		run_to(9);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Rejected(9)));
+	});
+}
+
+#[test]
+fn confirmation_may_outlast_decision_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+
+		run_to(8);
+		set_tally(0, 100, 0);
+		run_to(9);
+		assert_eq!(deciding(0), Some(DecidingStatus { since: 5, confirming: Some(11) }));
+		run_to(11);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Approved(11)));
+	});
+}
+
+#[test]
+fn deciding_is_limited_per_track() {
+	new_test_ext().execute_with(|| {
+		for i in 0..3 {
+			let h = set_balance_proposal_hash(i);
+			assert_ok!(Referenda::submit(Origin::signed(1), root(), h, 4));
+			assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i as u32));
+		}
+		// A referendum on another track is not held up.
+		let none = Box::new(frame_system::RawOrigin::None.into());
+		assert_ok!(Referenda::submit(Origin::signed(1), none, set_balance_proposal_hash(3), 2));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 3));
+
+		run_to(5);
+		assert!(deciding(0).is_some());
+		assert_eq!(deciding(1), None);
+		assert_eq!(deciding(2), None);
+		assert_eq!(Referenda::referendum_info(3), Some(ReferendumInfo::Rejected(5)));
+		assert_eq!(Referenda::track_queue(0), vec![1, 2]);
+		assert_eq!(Referenda::deciding_count(0), 1);
+
+		// Cancelling a deciding referendum lets the oldest queued one decide.
+		assert_ok!(Referenda::cancel(Origin::root(), 0));
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Cancelled(5)));
+		assert_eq!(deciding(1), Some(DecidingStatus { since: 5, confirming: None }));
+		assert_eq!(Referenda::track_queue(0), vec![2]);
+		assert_eq!(Referenda::deciding_count(0), 1);
+
+		// Cancelling a queued referendum removes it from the queue.
+		assert_ok!(Referenda::cancel(Origin::root(), 2));
+		assert_eq!(Referenda::track_queue(0), Vec::<ReferendumIndex>::new());
+		assert_eq!(Referenda::deciding_count(0), 1);
+
+		// The queued referendum was decided after the other one.
+		run_to(9);
+		assert_eq!(Referenda::referendum_info(1), Some(ReferendumInfo::Rejected(9)));
+		assert_eq!(Referenda::deciding_count(0), 0);
+	});
+}
+
+#[test]
+fn referendum_without_decision_deposit_times_out() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		run_to(20);
+		assert_eq!(deciding(0), None);
+		run_to(21);
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::TimedOut(21)));
+		// The submission deposit is slashed.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 98);
+	});
+}
+
+#[test]
+fn cancel_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		assert_noop!(Referenda::cancel(Origin::signed(1), 0), BadOrigin);
+
+		assert_ok!(Referenda::cancel(Origin::root(), 0));
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Cancelled(1)));
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_noop!(Referenda::cancel(Origin::root(), 0), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn kill_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		assert_noop!(Referenda::kill(Origin::signed(1), 0), BadOrigin);
+
+		assert_ok!(Referenda::kill(Origin::root(), 0));
+		assert_eq!(Referenda::referendum_info(0), Some(ReferendumInfo::Killed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 98);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 90);
+	});
+}
+
+#[test]
+fn polling_reports_completed_referenda() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(Origin::signed(1), root(), set_balance_proposal_hash(20), 4));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), 0));
+		set_tally(0, 1, 9);
+		run_to(9);
+
+		<Referenda as Polling<Tally>>::access_poll(0, |status| {
+			assert!(matches!(status, PollStatus::Completed(9, false)));
+		});
+		<Referenda as Polling<Tally>>::access_poll(1, |status| {
+			assert!(matches!(status, PollStatus::None));
+		});
+	});
+}
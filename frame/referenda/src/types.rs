@@ -0,0 +1,203 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, Perbill, traits::{Member, Saturating}};
+use frame_support::Parameter;
+
+/// A referendum index.
+pub type ReferendumIndex = u32;
+
+/// A funds reservation made by an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Deposit<AccountId, Balance> {
+	/// The account which made the deposit.
+	pub who: AccountId,
+	/// The amount reserved.
+	pub amount: Balance,
+}
+
+/// A curve giving a threshold in terms of the progress through a decision period.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Curve {
+	/// Linearly falls from `begin`, at the start of the period, to `end`, at its end.
+	LinearDecreasing {
+		begin: Perbill,
+		end: Perbill,
+	},
+	/// Starts at `begin` and falls by `step` every `period`, never falling below `end`.
+	SteppedDecreasing {
+		begin: Perbill,
+		end: Perbill,
+		step: Perbill,
+		period: Perbill,
+	},
+}
+
+impl Curve {
+	/// The threshold once a proportion `x` of the decision period has passed.
+	pub fn threshold(&self, x: Perbill) -> Perbill {
+		match *self {
+			Curve::LinearDecreasing { begin, end } =>
+				begin.saturating_sub(x.saturating_mul(begin.saturating_sub(end))),
+			Curve::SteppedDecreasing { begin, end, step, period } => {
+				let steps = x.deconstruct().checked_div(period.deconstruct()).unwrap_or(0);
+				let fall = Perbill::from_parts(step.deconstruct().saturating_mul(steps));
+				begin.saturating_sub(fall).max(end)
+			},
+		}
+	}
+
+	/// Whether `y` reaches the threshold once a proportion `x` of the decision period has
+	/// passed.
+	pub fn passing(&self, x: Perbill, y: Perbill) -> bool {
+		y >= self.threshold(x)
+	}
+}
+
+/// The parameters of a referendum track.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TrackInfo<Balance, Moment> {
+	/// Name of this track.
+	pub name: &'static str,
+	/// The maximum number of referenda which may be deciding on this track at once.
+	pub max_deciding: u32,
+	/// The amount which must be placed on deposit before a referendum may start deciding.
+	pub decision_deposit: Balance,
+	/// The minimum amount of time a referendum must wait after submission before it may start
+	/// deciding.
+	pub prepare_period: Moment,
+	/// The amount of time a referendum may be deciding before it is rejected.
+	pub decision_period: Moment,
+	/// The amount of time a referendum must be passing continuously before it is approved.
+	pub confirm_period: Moment,
+	/// The minimum amount of time between approval and enactment.
+	pub min_enactment_period: Moment,
+	/// The minimum approval required over the decision period.
+	pub min_approval: Curve,
+	/// The minimum support required over the decision period.
+	pub min_support: Curve,
+}
+
+/// Information on the tracks which referenda may be submitted to.
+pub trait TracksInfo<Balance, Moment> {
+	/// The identifier of a track.
+	type Id: Parameter + Member + Ord + Copy;
+
+	/// The origin type whose referenda are placed on a track.
+	type Origin;
+
+	/// All tracks, with their identifiers.
+	fn tracks() -> &'static [(Self::Id, TrackInfo<Balance, Moment>)];
+
+	/// The track on which referenda dispatching with `origin` are decided.
+	fn track_for(origin: &Self::Origin) -> Result<Self::Id, ()>;
+
+	/// The parameters of the track `id`.
+	fn info(id: Self::Id) -> Option<&'static TrackInfo<Balance, Moment>> {
+		Self::tracks().iter().find(|(track, _)| *track == id).map(|(_, info)| info)
+	}
+}
+
+/// The decision phase of a referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DecidingStatus<BlockNumber> {
+	/// When the referendum started deciding.
+	pub since: BlockNumber,
+	/// When the referendum will be approved if it keeps passing, if it is passing at all.
+	pub confirming: Option<BlockNumber>,
+}
+
+/// Info regarding an ongoing referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReferendumStatus<TrackId, Origin, BlockNumber, Hash, AccountId, Balance, Tally> {
+	/// The track the referendum is decided on.
+	pub track: TrackId,
+	/// The origin with which the proposal is dispatched once approved.
+	pub origin: Origin,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The delay between approval and enactment.
+	pub enactment_delay: BlockNumber,
+	/// When the referendum was submitted.
+	pub submitted: BlockNumber,
+	/// The deposit reserved for the submission.
+	pub submission_deposit: Deposit<AccountId, Balance>,
+	/// The deposit reserved for the decision, once placed.
+	pub decision_deposit: Option<Deposit<AccountId, Balance>>,
+	/// The decision phase, once started.
+	pub deciding: Option<DecidingStatus<BlockNumber>>,
+	/// The current tally of votes.
+	pub tally: Tally,
+	/// Whether the referendum is waiting in the queue of its track for a decision slot.
+	pub in_queue: bool,
+	/// When the referendum is next serviced.
+	pub alarm: Option<BlockNumber>,
+}
+
+/// Info regarding a referendum, present or past.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ReferendumInfo<TrackId, Origin, BlockNumber, Hash, AccountId, Balance, Tally> {
+	/// Referendum is happening.
+	Ongoing(ReferendumStatus<TrackId, Origin, BlockNumber, Hash, AccountId, Balance, Tally>),
+	/// Referendum was approved at the given block.
+	Approved(BlockNumber),
+	/// Referendum was rejected at the given block.
+	Rejected(BlockNumber),
+	/// Referendum was cancelled at the given block.
+	Cancelled(BlockNumber),
+	/// Referendum timed out without a decision deposit at the given block.
+	TimedOut(BlockNumber),
+	/// Referendum was killed at the given block.
+	Killed(BlockNumber),
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn linear_curve_works() {
+		let c = Curve::LinearDecreasing {
+			begin: Perbill::from_percent(100),
+			end: Perbill::from_percent(50),
+		};
+		assert_eq!(c.threshold(Perbill::zero()), Perbill::from_percent(100));
+		assert_eq!(c.threshold(Perbill::from_percent(50)), Perbill::from_percent(75));
+		assert_eq!(c.threshold(Perbill::one()), Perbill::from_percent(50));
+		assert!(c.passing(Perbill::from_percent(50), Perbill::from_percent(75)));
+		assert!(!c.passing(Perbill::from_percent(50), Perbill::from_percent(74)));
+	}
+
+	#[test]
+	fn stepped_curve_works() {
+		let c = Curve::SteppedDecreasing {
+			begin: Perbill::from_percent(80),
+			end: Perbill::from_percent(30),
+			step: Perbill::from_percent(20),
+			period: Perbill::from_percent(25),
+		};
+		assert_eq!(c.threshold(Perbill::zero()), Perbill::from_percent(80));
+		assert_eq!(c.threshold(Perbill::from_percent(24)), Perbill::from_percent(80));
+		assert_eq!(c.threshold(Perbill::from_percent(25)), Perbill::from_percent(60));
+		assert_eq!(c.threshold(Perbill::from_percent(50)), Perbill::from_percent(40));
+		assert_eq!(c.threshold(Perbill::from_percent(75)), Perbill::from_percent(30));
+		assert_eq!(c.threshold(Perbill::one()), Perbill::from_percent(30));
+	}
+}
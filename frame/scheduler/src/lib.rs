@@ -198,6 +198,11 @@ decl_module! {
 	/// Scheduler module declaration.
 	pub struct Module<T: Trait> for enum Call where origin: <T as system::Trait>::Origin {
 		type Error = Error<T>;
+
+		/// The maximum weight that may be scheduled per block for any dispatchables of less
+		/// priority than `schedule::HARD_DEADLINE`.
+		const MaximumWeight: Weight = T::MaximumWeight::get();
+
 		fn deposit_event() = default;
 
 		/// Anonymously schedule a task.
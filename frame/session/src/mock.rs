@@ -24,7 +24,7 @@ use sp_core::{crypto::key_types::DUMMY, H256};
 use sp_runtime::{
 	Perbill, impl_opaque_keys,
 	traits::{BlakeTwo256, IdentityLookup, ConvertInto},
-	testing::{Header, UintAuthorityId},
+	testing::{Header, UintAuthorityId, TestSignature, TestXt},
 };
 use sp_staking::SessionIndex;
 
@@ -56,6 +56,8 @@ thread_local! {
 	pub static DISABLED: RefCell<bool> = RefCell::new(false);
 	// Stores if `on_before_session_end` was called
 	pub static BEFORE_SESSION_END_CALLED: RefCell<bool> = RefCell::new(false);
+	// The last key generated by a rotation.
+	pub static ROTATED_KEY: RefCell<u64> = RefCell::new(100);
 }
 
 pub struct TestShouldEndSession;
@@ -123,6 +125,24 @@ impl crate::historical::SessionManager<u64, u64> for TestSessionManager {
 	}
 }
 
+pub struct TestKeyGenerator;
+impl rotation::GenerateSessionKeys<MockSessionKeys> for TestKeyGenerator {
+	fn generate() -> Option<MockSessionKeys> {
+		let key = ROTATED_KEY.with(|k| {
+			*k.borrow_mut() += 1;
+			*k.borrow()
+		});
+		Some(UintAuthorityId(key).into())
+	}
+}
+
+pub struct TestControllerId;
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestControllerId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
 pub fn authorities() -> Vec<UintAuthorityId> {
 	AUTHORITIES.with(|l| l.borrow().to_vec())
 }
@@ -233,5 +253,34 @@ impl crate::historical::Trait for Test {
 	type FullIdentificationOf = sp_runtime::traits::ConvertInto;
 }
 
+pub type Extrinsic = TestXt<Call<Test>, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl frame_system::offchain::SendTransactionTypes<Call<Test>> for Test {
+	type OverarchingCall = Call<Test>;
+	type Extrinsic = Extrinsic;
+}
+
+impl frame_system::offchain::CreateSignedTransaction<Call<Test>> for Test {
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call<Test>,
+		_public: UintAuthorityId,
+		account: u64,
+		_nonce: u64,
+	) -> Option<(Call<Test>, (u64, ()))> {
+		Some((call, (account, ())))
+	}
+}
+
+impl rotation::Trait for Test {
+	type ControllerId = TestControllerId;
+	type KeyGenerator = TestKeyGenerator;
+}
+
 pub type System = frame_system::Module<Test>;
 pub type Session = Module<Test>;
+pub type Rotation = rotation::Module<Test>;
@@ -0,0 +1,354 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Automated rotation of session keys.
+//!
+//! The runtime side of the opt-in session key rotation. A node that enabled the rotation calls
+//! [`Module::rotate_session_keys`] through the `SessionKeysRotation` runtime api after every
+//! block it imports. This generates new session keys in the local keystore at most once per
+//! rotation period, and registers them with `set_keys`, signed by a controller account whose key
+//! is in the keystore as well.
+//!
+//! A rotation is only started if the keys registered by the previous one have been queued, so
+//! keys are never replaced faster than they can activate. The keys of earlier rotations are kept
+//! until a later rotation's keys became active, and are then handed back to the node for
+//! removal from its keystore.
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	RuntimeAppPublic,
+	offchain::{storage::StorageValueRef, storage_lock::{StorageLock, Time}},
+	traits::{Convert, IdentifyAccount, OpaqueKeys, Saturating},
+};
+use sp_staking::SessionIndex;
+use frame_support::{debug, decl_module};
+use frame_system::offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer};
+use super::{Module as SessionModule, Call as SessionCall};
+
+/// Key of the local storage entry holding the block of the last rotation.
+const LAST_ROTATION_KEY: &[u8] = b"session::last_key_rotation";
+
+/// Key of the local storage entry holding the keys registered by earlier rotations.
+const ROTATIONS_KEY: &[u8] = b"session::key_rotations";
+
+/// Key of the local storage lock serializing the rotations.
+const ROTATION_LOCK_KEY: &[u8] = b"session::key_rotation_lock";
+
+/// Something that can generate a new set of session keys in the keystore.
+pub trait GenerateSessionKeys<Keys> {
+	/// Generate new keys, storing the key pairs in the keystore.
+	///
+	/// Returns `None` if the keys could not be generated.
+	fn generate() -> Option<Keys>;
+}
+
+/// Trait necessary for the rotation module.
+pub trait Trait: super::Trait + CreateSignedTransaction<SessionCall<Self>> {
+	/// The crypto of the controller keys used to sign `set_keys`.
+	type ControllerId: AppCrypto<Self::Public, Self::Signature>;
+
+	/// Generates the new session keys.
+	type KeyGenerator: GenerateSessionKeys<Self::Keys>;
+}
+
+/// The keys registered by a rotation.
+#[derive(Encode, Decode, Clone)]
+struct Rotation<ValidatorId, Keys> {
+	/// The validator whose keys were rotated.
+	validator: ValidatorId,
+	/// The registered keys.
+	keys: Keys,
+	/// The session in which the keys were first seen queued, if they were yet.
+	queued_in: Option<SessionIndex>,
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+}
+
+impl<T: Trait> Module<T> {
+	/// Rotate the session keys of a local validator at block `now`, unless they were already
+	/// rotated in the last `period` blocks. Must be called in an offchain context.
+	///
+	/// Returns the public keys of earlier rotations which are neither active nor queued anymore.
+	/// They are forgotten by the runtime, so the caller should remove them from the keystore.
+	pub fn rotate_session_keys(
+		now: T::BlockNumber,
+		period: T::BlockNumber,
+	) -> Vec<(Vec<u8>, KeyTypeId)> {
+		let mut lock = StorageLock::<Time>::new(ROTATION_LOCK_KEY);
+		let _guard = match lock.try_lock() {
+			Ok(guard) => guard,
+			Err(_) => {
+				debug::warn!("Skipping session key rotation: concurrent rotation in progress");
+				return Vec::new()
+			},
+		};
+
+		let stale = Self::take_stale_keys();
+		if let Err(e) = Self::rotate_keys(now, period) {
+			debug::warn!("Skipping session key rotation: {}", e);
+		}
+		stale.iter()
+			.flat_map(|keys| T::Keys::key_ids()
+				.iter()
+				.map(move |id| (keys.get_raw(*id).to_vec(), *id))
+			)
+			.collect()
+	}
+
+	fn rotate_keys(now: T::BlockNumber, period: T::BlockNumber) -> Result<(), &'static str> {
+		let last_rotation = StorageValueRef::persistent(LAST_ROTATION_KEY);
+		if let Some(Some(last)) = last_rotation.get::<T::BlockNumber>() {
+			if now < last.saturating_add(period) {
+				return Ok(())
+			}
+		}
+
+		let (controller, validator) = Self::local_validators()
+			.find(|(_, v)| can_rotate::<T>(v))
+			.ok_or("no local controller can rotate its keys")?;
+
+		let keys = T::KeyGenerator::generate().ok_or("failed to generate session keys")?;
+		let signer = Signer::<T, T::ControllerId>::any_account().with_filter(vec![controller]);
+		match signer.send_signed_transaction(|_| SessionCall::set_keys(keys.clone(), Vec::new())) {
+			Some((_, Ok(()))) => (),
+			_ => return Err("failed to submit set_keys"),
+		}
+
+		// Only claim the period once the keys are submitted, so a failed rotation is retried on
+		// the next block.
+		last_rotation.set(&now);
+		let rotations = StorageValueRef::persistent(ROTATIONS_KEY);
+		let mut registered = Self::rotations(&rotations);
+		registered.push(Rotation { validator, keys, queued_in: None });
+		rotations.set(&registered);
+		Ok(())
+	}
+
+	/// Forget and return the keys of the rotations which are neither active nor queued anymore.
+	///
+	/// Queued keys become active with the session after the one they were queued in. Once the
+	/// keys of a rotation are active, those registered by the validator before are unused.
+	fn take_stale_keys() -> Vec<T::Keys> {
+		let rotations = StorageValueRef::persistent(ROTATIONS_KEY);
+		let mut registered = Self::rotations(&rotations);
+		if registered.is_empty() {
+			return Vec::new()
+		}
+
+		let session = SessionModule::<T>::current_index();
+		let queued = SessionModule::<T>::queued_keys();
+		for rotation in registered.iter_mut().filter(|r| r.queued_in.is_none()) {
+			if queued.iter().any(|(v, keys)| v == &rotation.validator && keys == &rotation.keys) {
+				rotation.queued_in = Some(session);
+			}
+		}
+
+		let is_active = |r: &Rotation<_, _>| r.queued_in.map_or(false, |s| s < session);
+		let mut stale = Vec::new();
+		let mut kept = Vec::new();
+		for (i, rotation) in registered.iter().enumerate() {
+			let superseded = registered[i + 1..].iter()
+				.any(|later| later.validator == rotation.validator && is_active(later));
+			if superseded {
+				stale.push(rotation.keys.clone());
+			} else {
+				kept.push(rotation.clone());
+			}
+		}
+		rotations.set(&kept);
+		stale
+	}
+
+	/// The rotations registered so far, oldest first.
+	fn rotations(
+		rotations: &StorageValueRef,
+	) -> Vec<Rotation<T::ValidatorId, T::Keys>> {
+		rotations.get().flatten().unwrap_or_default()
+	}
+
+	/// The controller keys in the keystore, with the validators they control.
+	fn local_validators() -> impl Iterator<Item = (T::Public, T::ValidatorId)> {
+		T::ControllerId::RuntimeAppPublic::all()
+			.into_iter()
+			.filter_map(|key| {
+				let public: T::Public = T::ControllerId::GenericPublic::from(key).into();
+				let validator = T::ValidatorIdOf::convert(public.clone().into_account())?;
+				Some((public, validator))
+			})
+	}
+}
+
+/// Whether the keys of validator `v` can be rotated.
+///
+/// This requires the validator to have registered keys, and the keys registered by a previous
+/// rotation to be queued already.
+fn can_rotate<T: super::Trait>(v: &T::ValidatorId) -> bool {
+	let next = match SessionModule::<T>::load_keys(v) {
+		Some(next) => next,
+		None => return false,
+	};
+	SessionModule::<T>::queued_keys()
+		.into_iter()
+		.find(|(queued, _)| queued == v)
+		.map_or(true, |(_, keys)| keys == next)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Decode;
+	use frame_support::{assert_ok, traits::OnInitialize};
+	use sp_core::{
+		crypto::key_types::DUMMY,
+		offchain::{
+			OffchainExt, TransactionPoolExt,
+			testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+		},
+	};
+	use sp_runtime::testing::UintAuthorityId;
+	use crate::mock::{Extrinsic, Origin, System, Session, Rotation, Test, new_test_ext};
+
+	fn initialize_block(block: u64) {
+		System::set_block_number(block);
+		Session::on_initialize(block);
+	}
+
+	fn offchain_test_ext() -> (sp_io::TestExternalities, impl Fn() -> Vec<Extrinsic>) {
+		let (offchain, _) = TestOffchainExt::new();
+		let (pool, pool_state) = TestTransactionPoolExt::new();
+		let mut ext = new_test_ext();
+		ext.register_extension(OffchainExt::new(offchain));
+		ext.register_extension(TransactionPoolExt::new(pool));
+		(ext, move || decode_transactions(&pool_state.read()))
+	}
+
+	fn decode_transactions(pool: &PoolState) -> Vec<Extrinsic> {
+		pool.transactions.iter().map(|tx| Extrinsic::decode(&mut &tx[..]).unwrap()).collect()
+	}
+
+	fn set_keys(keys: u64) -> SessionCall<Test> {
+		SessionCall::set_keys(UintAuthorityId(keys).into(), vec![])
+	}
+
+	#[test]
+	fn rotation_waits_for_queued_keys() {
+		new_test_ext().execute_with(|| {
+			initialize_block(1);
+			assert!(can_rotate::<Test>(&1));
+			assert!(!can_rotate::<Test>(&4));
+
+			assert_ok!(Session::set_keys(Origin::signed(1), UintAuthorityId(10).into(), vec![]));
+			assert!(!can_rotate::<Test>(&1));
+
+			// The new keys are queued on the next session rollover.
+			initialize_block(2);
+			assert!(can_rotate::<Test>(&1));
+		});
+	}
+
+	#[test]
+	fn rotation_submits_signed_set_keys() {
+		let (mut ext, submitted) = offchain_test_ext();
+		ext.execute_with(|| {
+			UintAuthorityId::set_all_keys(vec![1]);
+			initialize_block(1);
+
+			assert!(Rotation::rotate_session_keys(1, 10).is_empty());
+
+			let xts = submitted();
+			assert_eq!(xts.len(), 1);
+			assert_eq!(xts[0].signature, Some((1, ())));
+			assert_eq!(xts[0].call, set_keys(101));
+		});
+	}
+
+	#[test]
+	fn rotation_waits_for_the_period() {
+		let (mut ext, submitted) = offchain_test_ext();
+		ext.execute_with(|| {
+			UintAuthorityId::set_all_keys(vec![1]);
+			initialize_block(1);
+			Rotation::rotate_session_keys(1, 10);
+			assert_ok!(Session::set_keys(Origin::signed(1), UintAuthorityId(101).into(), vec![]));
+			initialize_block(2);
+
+			// The rotated keys are queued, but the period is not over yet.
+			assert!(can_rotate::<Test>(&1));
+			Rotation::rotate_session_keys(10, 10);
+			assert_eq!(submitted().len(), 1);
+
+			Rotation::rotate_session_keys(11, 10);
+			assert_eq!(submitted().len(), 2);
+			assert_eq!(submitted()[1].call, set_keys(102));
+		});
+	}
+
+	#[test]
+	fn failed_rotation_does_not_claim_the_period() {
+		let (mut ext, submitted) = offchain_test_ext();
+		ext.execute_with(|| {
+			initialize_block(1);
+
+			// No controller key in the keystore.
+			Rotation::rotate_session_keys(1, 10);
+			assert!(submitted().is_empty());
+
+			// The keys registered last are not queued yet.
+			UintAuthorityId::set_all_keys(vec![1]);
+			assert_ok!(Session::set_keys(Origin::signed(1), UintAuthorityId(10).into(), vec![]));
+			Rotation::rotate_session_keys(2, 10);
+			assert!(submitted().is_empty());
+
+			initialize_block(2);
+			Rotation::rotate_session_keys(3, 10);
+			assert_eq!(submitted().len(), 1);
+			assert_eq!(submitted()[0].call, set_keys(101));
+		});
+	}
+
+	#[test]
+	fn rotated_keys_are_returned_once_superseded_keys_are_active() {
+		let (mut ext, _) = offchain_test_ext();
+		ext.execute_with(|| {
+			UintAuthorityId::set_all_keys(vec![1]);
+			initialize_block(1);
+			Rotation::rotate_session_keys(1, 1);
+			assert_ok!(Session::set_keys(Origin::signed(1), UintAuthorityId(101).into(), vec![]));
+
+			// The first keys are queued, and rotated again.
+			initialize_block(2);
+			assert!(Rotation::rotate_session_keys(2, 1).is_empty());
+			assert_ok!(Session::set_keys(Origin::signed(1), UintAuthorityId(102).into(), vec![]));
+
+			// The first keys are active and the second ones queued.
+			initialize_block(4);
+			assert!(Rotation::rotate_session_keys(4, 100).is_empty());
+
+			// The second keys are active, so the first ones are not used anymore.
+			initialize_block(6);
+			assert_eq!(
+				Rotation::rotate_session_keys(6, 100),
+				vec![(UintAuthorityId(101).to_raw_vec(), DUMMY)],
+			);
+			assert!(Rotation::rotate_session_keys(7, 100).is_empty());
+		});
+	}
+}
@@ -1457,6 +1457,16 @@ decl_module! {
 			// `on_finalize` weight is tracked in `on_initialize`
 		}
 
+		fn integrity_test() {
+			assert!(T::SessionsPerEra::get() > 0, "An era must span at least one session");
+			// Deferred slashes must be applied while the slashed funds are still bonded.
+			assert!(
+				T::SlashDeferDuration::get() == 0 ||
+				T::SlashDeferDuration::get() < T::BondingDuration::get(),
+				"SlashDeferDuration must be less than BondingDuration",
+			);
+		}
+
 		/// Take the origin account as a stash and lock up `value` of its balance. `controller` will
 		/// be the account that controls it.
 		///
@@ -4531,3 +4531,16 @@ fn payout_creates_controller() {
 		assert!(Balances::free_balance(1337) > 0);
 	})
 }
+
+#[test]
+fn integrity_test_checks_slash_defer_duration() {
+	use frame_support::traits::IntegrityTest;
+
+	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
+		Staking::integrity_test();
+	});
+
+	ExtBuilder::default().slash_defer_duration(3).build_and_execute(|| {
+		assert!(std::panic::catch_unwind(|| Staking::integrity_test()).is_err());
+	});
+}
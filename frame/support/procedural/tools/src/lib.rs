@@ -46,6 +46,25 @@ pub fn generate_crate_access(unique_id: &str, def_crate: &str) -> TokenStream {
 	}
 }
 
+/// Generates the access to the `def_crate` crate using the 2018 edition syntax.
+///
+/// For `frame-support` the output will be `frame_support`.
+pub fn generate_crate_access_2018(def_crate: &str) -> Result<TokenStream, Error> {
+	if std::env::var("CARGO_PKG_NAME").unwrap() == def_crate {
+		let name = def_crate.replace("-", "_");
+		let name = Ident::new(&name, Span::call_site());
+		Ok(quote!( #name ))
+	} else {
+		match crate_name(def_crate) {
+			Ok(name) => {
+				let name = Ident::new(&name, Span::call_site());
+				Ok(quote!( #name ))
+			},
+			Err(e) => Err(Error::new(Span::call_site(), &e)),
+		}
+	}
+}
+
 /// Generates the hidden includes that are required to make the macro independent from its scope.
 pub fn generate_hidden_includes(unique_id: &str, def_crate: &str) -> TokenStream {
 	if std::env::var("CARGO_PKG_NAME").unwrap() == def_crate {
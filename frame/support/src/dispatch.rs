@@ -189,6 +189,31 @@ impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
 /// # fn main() {}
 /// ```
 ///
+/// ### Transactional Function Example
+///
+/// Transactional function discards all changes to storage if it returns `Err`, or commits if
+/// `Ok`, via the `#[transactional]` attribute. It has to be placed after the `#[weight]`
+/// attribute.
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate frame_support;
+/// # use frame_support::{dispatch, transactional};
+/// # use frame_system::{Trait, ensure_signed};
+/// decl_module! {
+/// 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+/// 		#[weight = 0]
+/// 		#[transactional]
+/// 		fn my_transactional_function(origin) -> dispatch::DispatchResult {
+/// 			let _sender = ensure_signed(origin)?;
+/// 			// Your implementation
+/// 			Ok(())
+/// 		}
+/// 	}
+/// }
+/// # fn main() {}
+/// ```
+///
 /// ## Multiple Module Instances Example
 ///
 /// A Substrate module can be built such that multiple instances of the same module can be used within a single
@@ -268,6 +293,12 @@ impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
 ///   * `fn on_finalize(n: BlockNumber) -> frame_support::weights::Weight` or
 ///   * `fn on_finalize() -> frame_support::weights::Weight`
 ///
+/// * `on_idle`: Executes at the end of a block, before `on_finalize`, if there is weight left
+/// in the block. Using this function will implement the [`OnIdle`](./traits/trait.OnIdle.html)
+/// trait. Function signature must be
+/// `fn on_idle(n: BlockNumber, remaining_weight: Weight) -> frame_support::weights::Weight`
+/// where the returned weight must not exceed `remaining_weight`.
+///
 /// * `offchain_worker`: Executes at the beginning of a block and produces extrinsics for a future block
 ///   upon completion. Using this function will implement the
 ///   [`OffchainWorker`](./traits/trait.OffchainWorker.html) trait.
@@ -302,6 +333,7 @@ macro_rules! decl_module {
 			{}
 			{}
 			{}
+			{}
 			[]
 			$($t)*
 		);
@@ -337,6 +369,7 @@ macro_rules! decl_module {
 			{}
 			{}
 			{}
+			{}
 			[]
 			$($t)*
 		);
@@ -356,6 +389,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$vis:vis fn deposit_event() = default;
@@ -374,6 +408,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -391,6 +426,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$vis:vis fn deposit_event
@@ -416,6 +452,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$vis:vis fn deposit_event() = default;
@@ -437,6 +474,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_finalize( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -457,6 +495,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -475,6 +514,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -502,6 +542,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -526,6 +567,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_runtime_upgrade( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -551,6 +593,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -578,6 +621,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_runtime_upgrade( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -598,6 +642,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -618,6 +663,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_runtime_upgrade( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -641,6 +687,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{}
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn integrity_test() { $( $impl:tt )* }
@@ -662,6 +709,7 @@ macro_rules! decl_module {
 				$(#[doc = $doc_attr])*
 				fn integrity_test() { $( $impl)* }
 			}
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -682,6 +730,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )+ }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn integrity_test() { $( $impl:tt )* }
@@ -689,6 +738,72 @@ macro_rules! decl_module {
 	) => {
 		compile_error!("`integrity_test` can only be passed once as input.");
 	};
+	// Add on_idle
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{ $( $on_runtime_upgrade:tt )* }
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{}
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn on_idle( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		$crate::decl_module!(@normalize
+			$(#[$attr])*
+			pub struct $mod_type<$trait_instance: $trait_name$(<I>, I: $instantiable $(= $module_default_instance)?)?>
+			for enum $call_type where origin: $origin_type, system = $system
+			{ $( $other_where_bounds )* }
+			{ $( $deposit_event )* }
+			{ $( $on_initialize )* }
+			{ $( $on_runtime_upgrade )* }
+			{ $( $on_finalize )* }
+			{ $( $offchain )* }
+			{ $( $constants )* }
+			{ $( $error_type )* }
+			{ $( $integrity_test )* }
+			{
+				fn on_idle( $( $param_name : $param ),* ) -> $return { $( $impl )* }
+			}
+			[ $( $dispatchables )* ]
+			$($rest)*
+		);
+	};
+	// Compile error on `on_idle` being added a second time.
+	(@normalize
+		$(#[$attr:meta])*
+		pub struct $mod_type:ident<
+			$trait_instance:ident: $trait_name:ident$(<I>, I: $instantiable:path $(= $module_default_instance:path)?)?
+		>
+		for enum $call_type:ident where origin: $origin_type:ty, system = $system:ident
+		{ $( $other_where_bounds:tt )* }
+		{ $( $deposit_event:tt )* }
+		{ $( $on_initialize:tt )* }
+		{ $( $on_runtime_upgrade:tt )* }
+		{ $( $on_finalize:tt )* }
+		{ $( $offchain:tt )* }
+		{ $( $constants:tt )* }
+		{ $( $error_type:tt )* }
+		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )+ }
+		[ $( $dispatchables:tt )* ]
+		$(#[doc = $doc_attr:tt])*
+		fn on_idle( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
+		$($rest:tt)*
+	) => {
+		compile_error!("`on_idle` can only be passed once as input.");
+	};
 	// compile_error on_initialize, without a given weight removed syntax.
 	(@normalize
 		$(#[$attr:meta])*
@@ -705,6 +820,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_initialize( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -730,6 +846,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
@@ -757,6 +874,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_initialize( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -777,6 +895,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -797,6 +916,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn on_initialize( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -820,6 +940,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn offchain_worker( $( $param_name:ident : $param:ty ),* $(,)? ) { $( $impl:tt )* }
@@ -840,6 +961,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -860,6 +982,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		fn offchain_worker( $( $param_name:ident : $param:ty ),* $(,)? ) -> $return:ty { $( $impl:tt )* }
@@ -884,6 +1007,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$( #[doc = $doc_attr:tt] )*
 		const $name:ident: $ty:ty = $value:expr;
@@ -909,6 +1033,7 @@ macro_rules! decl_module {
 			}
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -931,6 +1056,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		type Error = $error_type:ty;
@@ -951,6 +1077,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $error_type }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $( $dispatchables )* ]
 			$($rest)*
 		);
@@ -972,6 +1099,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $($t:tt)* ]
 		$($rest:tt)*
 	) => {
@@ -990,6 +1118,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ &'static str }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[ $($t)* ]
 			$($rest)*
 		);
@@ -1012,9 +1141,11 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $error_type:ty }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		#[weight = $weight:expr]
+		$(#[$fn_attr:meta])*
 		$fn_vis:vis fn $fn_name:ident(
 			$origin:ident $( , $(#[$codec_attr:ident])* $param_name:ident : $param:ty )* $(,)?
 		) $( -> $result:ty )* { $( $impl:tt )* }
@@ -1035,10 +1166,12 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $error_type }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 			[
 				$( $dispatchables )*
 				$(#[doc = $doc_attr])*
 				#[weight = $weight]
+				$(#[$fn_attr])*
 				$fn_vis fn $fn_name(
 					$origin $( , $(#[$codec_attr])* $param_name : $param )*
 				) $( -> $result )* { $( $impl )* }
@@ -1064,6 +1197,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$fn_vis:vis fn $fn_name:ident(
@@ -1091,6 +1225,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$(#[weight = $weight:expr])?
@@ -1118,6 +1253,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$(#[weight = $weight:expr])?
@@ -1145,6 +1281,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 		$(#[doc = $doc_attr:tt])*
 		$(#[weight = $weight:expr])?
@@ -1173,6 +1310,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $( $error_type:tt )* }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 		[ $( $dispatchables:tt )* ]
 	) => {
 		$crate::decl_module!(@imp
@@ -1190,6 +1328,7 @@ macro_rules! decl_module {
 			{ $( $constants )* }
 			{ $( $error_type )* }
 			{ $( $integrity_test)* }
+			{ $( $on_idle )* }
 		);
 	};
 
@@ -1296,6 +1435,34 @@ macro_rules! decl_module {
 		{}
 	};
 
+	(@impl_on_idle
+		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		{ $( $other_where_bounds:tt )* }
+		fn on_idle($param1:ident : $param1_ty:ty, $param2:ident: $param2_ty:ty) -> $return:ty {
+			$( $impl:tt )*
+		}
+	) => {
+		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::OnIdle<$trait_instance::BlockNumber>
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn on_idle($param1: $param1_ty, $param2: $param2_ty) -> $return {
+				$crate::sp_tracing::enter_span!("on_idle");
+				{ $( $impl )* }
+			}
+		}
+	};
+
+	(@impl_on_idle
+		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
+		{ $( $other_where_bounds:tt )* }
+	) => {
+		impl<$trait_instance: $trait_name$(<I>, $instance: $instantiable)?>
+			$crate::traits::OnIdle<$trait_instance::BlockNumber>
+			for $module<$trait_instance$(, $instance)?> where $( $other_where_bounds )*
+		{}
+	};
+
 	(@impl_integrity_test
 		$module:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>;
 		{ $( $other_where_bounds:tt )* }
@@ -1408,13 +1575,13 @@ macro_rules! decl_module {
 		$origin_ty:ty;
 		$error_type:ty;
 		$ignore:ident;
-		$(#[doc = $doc_attr:tt])*
+		$(#[$fn_attr:meta])*
 		$vis:vis fn $name:ident (
 			$origin:ident $(, $param:ident : $param_ty:ty )*
 		) { $( $impl:tt )* }
 	) => {
-		$(#[doc = $doc_attr])*
 		#[allow(unreachable_code)]
+		$(#[$fn_attr])*
 		$vis fn $name(
 			$origin: $origin_ty $(, $param: $param_ty )*
 		) -> $crate::dispatch::DispatchResult {
@@ -1430,12 +1597,12 @@ macro_rules! decl_module {
 		$origin_ty:ty;
 		$error_type:ty;
 		$ignore:ident;
-		$(#[doc = $doc_attr:tt])*
+		$(#[$fn_attr:meta])*
 		$vis:vis fn $name:ident (
 			$origin:ident $(, $param:ident : $param_ty:ty )*
 		) -> $result:ty { $( $impl:tt )* }
 	) => {
-		$(#[doc = $doc_attr])*
+		$(#[$fn_attr])*
 		$vis fn $name($origin: $origin_ty $(, $param: $param_ty )* ) -> $result {
 			$crate::sp_tracing::enter_span!(stringify!($name));
 			$( $impl )*
@@ -1567,6 +1734,7 @@ macro_rules! decl_module {
 			$(
 				$(#[doc = $doc_attr:tt])*
 				#[weight = $weight:expr]
+				$(#[$fn_attr:meta])*
 				$fn_vis:vis fn $fn_name:ident(
 					$from:ident $( , $(#[$codec_attr:ident])* $param_name:ident : $param:ty)*
 				) $( -> $result:ty )* { $( $impl:tt )* }
@@ -1582,6 +1750,7 @@ macro_rules! decl_module {
 		{ $( $constants:tt )* }
 		{ $error_type:ty }
 		{ $( $integrity_test:tt )* }
+		{ $( $on_idle:tt )* }
 	) => {
 		$crate::__check_reserved_fn_name! { $( $fn_name )* }
 
@@ -1621,6 +1790,13 @@ macro_rules! decl_module {
 			{ $( $other_where_bounds )* }
 			$( $offchain )*
 		}
+
+		$crate::decl_module! {
+			@impl_on_idle
+			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
+			{ $( $other_where_bounds )* }
+			$( $on_idle )*
+		}
 		$crate::decl_module! {
 			@impl_deposit_event
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
@@ -1652,6 +1828,7 @@ macro_rules! decl_module {
 					$(#[doc = $doc_attr])*
 					///
 					/// NOTE: Calling this function will bypass origin filters.
+					$(#[$fn_attr])*
 					$fn_vis fn $fn_name (
 						$from $(, $param_name : $param )*
 					) $( -> $result )* { $( $impl )* }
@@ -2266,6 +2443,9 @@ macro_rules! __check_reserved_fn_name {
 	(integrity_test $( $rest:ident )*) => {
 		$crate::__check_reserved_fn_name!(@compile_error integrity_test);
 	};
+	(on_idle $( $rest:ident )*) => {
+		$crate::__check_reserved_fn_name!(@compile_error on_idle);
+	};
 	($t:ident $( $rest:ident )*) => {
 		$crate::__check_reserved_fn_name!($( $rest )*);
 	};
@@ -2302,7 +2482,7 @@ mod tests {
 	use crate::weights::{DispatchInfo, DispatchClass, Pays};
 	use crate::traits::{
 		CallMetadata, GetCallMetadata, GetCallName, OnInitialize, OnFinalize, OnRuntimeUpgrade,
-		IntegrityTest,
+		IntegrityTest, OnIdle,
 	};
 
 	pub trait Trait: system::Trait + Sized where Self::AccountId: From<u32> {
@@ -2365,6 +2545,10 @@ mod tests {
 			fn on_initialize(n: T::BlockNumber,) -> Weight { if n.into() == 42 { panic!("on_initialize") } 7 }
 			fn on_finalize(n: T::BlockNumber,) { if n.into() == 42 { panic!("on_finalize") } }
 			fn on_runtime_upgrade() -> Weight { 10 }
+			fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+				if n.into() == 42 { panic!("on_idle") }
+				remaining_weight / 2
+			}
 			fn offchain_worker() {}
 			/// Some doc
 			fn integrity_test() { panic!("integrity_test") }
@@ -2515,6 +2699,13 @@ mod tests {
 		<Module<TraitImpl> as OnFinalize<u32>>::on_finalize(42);
 	}
 
+	#[test]
+	#[should_panic(expected = "on_idle")]
+	fn on_idle_should_work() {
+		assert_eq!(<Module<TraitImpl> as OnIdle<u32>>::on_idle(1, 10), 5);
+		<Module<TraitImpl> as OnIdle<u32>>::on_idle(42, 10);
+	}
+
 	#[test]
 	fn on_runtime_upgrade_should_work() {
 		assert_eq!(<Module<TraitImpl> as OnRuntimeUpgrade>::on_runtime_upgrade(), 10);
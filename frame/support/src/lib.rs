@@ -75,7 +75,8 @@ pub use self::hash::{
 };
 pub use self::storage::{
 	StorageValue, StorageMap, StorageDoubleMap, StoragePrefixedMap, IterableStorageMap,
-	IterableStorageDoubleMap, migration
+	IterableStorageDoubleMap, migration, BoundedVec, BoundedBTreeMap, CountedStorageMap,
+	bounded_vec::{TryAppendValue, TryAppendMap, TryAppendDoubleMap},
 };
 pub use self::dispatch::{Parameter, Callable, IsSubType};
 pub use sp_runtime::{self, ConsensusEngineId, print, traits::Printable};
@@ -267,7 +268,7 @@ macro_rules! ord_parameter_types {
 }
 
 #[doc(inline)]
-pub use frame_support_procedural::{decl_storage, construct_runtime};
+pub use frame_support_procedural::{decl_storage, construct_runtime, transactional};
 
 /// Return Err of the expression: `return Err($expression);`.
 ///
@@ -612,6 +613,17 @@ mod tests {
 			assert_eq!(DoubleMap::get(&(key1 + 1), &key2), 4u64);
 			assert_eq!(DoubleMap::get(&(key1 + 1), &(key2 + 1)), 4u64);
 
+			// clear prefix in steps
+			DoubleMap::insert(&key1, &key2, &4u64);
+			DoubleMap::insert(&key1, &(key2 + 1), &4u64);
+			let outcome = DoubleMap::clear_prefix(&key1, 1, None);
+			assert_eq!(outcome.removed, 1);
+			assert!(outcome.cursor.is_some());
+			let outcome = DoubleMap::clear_prefix(&key1, 1, outcome.cursor.as_deref());
+			assert_eq!(outcome, crate::storage::ClearPrefixOutcome { removed: 1, cursor: None });
+			assert_eq!(DoubleMap::get(&key1, &key2), 0u64);
+			assert_eq!(DoubleMap::get(&key1, &(key2 + 1)), 0u64);
+			assert_eq!(DoubleMap::get(&(key1 + 1), &key2), 4u64);
 		});
 	}
 
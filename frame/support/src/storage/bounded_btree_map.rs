@@ -0,0 +1,336 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits, types and structs to support a bounded BTreeMap.
+
+use sp_std::{
+	borrow::Borrow, collections::btree_map::BTreeMap, convert::TryFrom, fmt, marker::PhantomData,
+	ops::Deref,
+};
+use codec::{Encode, EncodeLike, Decode, Input};
+use crate::{traits::Get, storage::StorageDecodeLength};
+
+/// A bounded map based on a B-Tree.
+///
+/// B-Trees represent a fundamental compromise between cache-efficiency and actually minimizing
+/// the amount of work performed in a search. See [`BTreeMap`] for more details.
+///
+/// Unlike a standard `BTreeMap`, there is an enforced upper limit to the number of items in the
+/// map. All internal operations ensure this bound is respected, and decoding fails for a map
+/// which exceeds it.
+pub struct BoundedBTreeMap<K, V, S>(BTreeMap<K, V>, PhantomData<S>);
+
+impl<K: Ord, V, S: Get<u32>> BoundedBTreeMap<K, V, S> {
+	/// Create `Self` from `t` without any checks.
+	fn unchecked_from(t: BTreeMap<K, V>) -> Self {
+		Self(t, Default::default())
+	}
+
+	/// Get the bound of the type in `usize`.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Create a new `BoundedBTreeMap`.
+	///
+	/// Does not allocate.
+	pub fn new() -> Self {
+		Self::unchecked_from(BTreeMap::new())
+	}
+
+	/// Consume self, and return the inner `BTreeMap`.
+	///
+	/// This is useful when a mutating API of the inner type is desired, and closure-based mutation
+	/// such as provided by [`try_mutate`][Self::try_mutate] is inconvenient.
+	pub fn into_inner(self) -> BTreeMap<K, V> {
+		debug_assert!(self.0.len() <= Self::bound());
+		self.0
+	}
+
+	/// Consumes self and mutates self via the given `mutate` function.
+	///
+	/// If the outcome of mutation is within bounds, `Some(Self)` is returned. Else, `None` is
+	/// returned.
+	///
+	/// This is essentially a *consuming* shorthand [`Self::into_inner`] -> `...` ->
+	/// [`Self::try_from`].
+	pub fn try_mutate(mut self, mut mutate: impl FnMut(&mut BTreeMap<K, V>)) -> Option<Self> {
+		mutate(&mut self.0);
+		if self.0.len() <= Self::bound() {
+			Some(self)
+		} else {
+			None
+		}
+	}
+
+	/// Exactly the same semantics as [`BTreeMap::insert`], but returns an `Err` (and is a noop)
+	/// if the new length of the map exceeds `S`.
+	///
+	/// In the `Err` case, returns the inserted pair so it can be further used without cloning.
+	pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+		if self.len() < Self::bound() || self.0.contains_key(&key) {
+			Ok(self.0.insert(key, value))
+		} else {
+			Err((key, value))
+		}
+	}
+
+	/// Remove a key from the map, returning the value at the key if the key was previously in the
+	/// map.
+	///
+	/// The key may be any borrowed form of the map's key type, but the ordering on the borrowed
+	/// form _must_ match the ordering on the key type.
+	pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+	where
+		K: Borrow<Q>,
+		Q: Ord + ?Sized,
+	{
+		self.0.remove(key)
+	}
+
+	/// Gets a mutable reference to the value in the map at the given key, if there is one.
+	///
+	/// The key may be any borrowed form of the map's key type, but the ordering on the borrowed
+	/// form _must_ match the ordering on the key type.
+	pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+	where
+		K: Borrow<Q>,
+		Q: Ord + ?Sized,
+	{
+		self.0.get_mut(key)
+	}
+}
+
+impl<K: Ord, V, S: Get<u32>> Default for BoundedBTreeMap<K, V, S> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K: Clone, V: Clone, S> Clone for BoundedBTreeMap<K, V, S> {
+	fn clone(&self) -> Self {
+		// bound is retained
+		Self(self.0.clone(), Default::default())
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S: Get<u32>> fmt::Debug for BoundedBTreeMap<K, V, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("BoundedBTreeMap").field(&self.0).field(&S::get()).finish()
+	}
+}
+
+impl<K: PartialEq, V: PartialEq, S> PartialEq for BoundedBTreeMap<K, V, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<K: PartialEq, V: PartialEq, S> PartialEq<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	fn eq(&self, other: &BTreeMap<K, V>) -> bool {
+		&self.0 == other
+	}
+}
+
+impl<K: Eq, V: Eq, S> Eq for BoundedBTreeMap<K, V, S> {}
+
+impl<K: Encode, V: Encode, S> Encode for BoundedBTreeMap<K, V, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<K: Encode, V: Encode, S> EncodeLike for BoundedBTreeMap<K, V, S> {}
+
+impl<K: Decode + Ord, V: Decode, S: Get<u32>> Decode for BoundedBTreeMap<K, V, S> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let inner = BTreeMap::<K, V>::decode(input)?;
+		if inner.len() > Self::bound() {
+			return Err("BoundedBTreeMap exceeds its limit".into());
+		}
+		Ok(Self::unchecked_from(inner))
+	}
+}
+
+impl<K: Ord, V, S: Get<u32>> TryFrom<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	type Error = ();
+
+	fn try_from(value: BTreeMap<K, V>) -> Result<Self, Self::Error> {
+		if value.len() <= Self::bound() {
+			Ok(Self::unchecked_from(value))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<K, V, S> From<BoundedBTreeMap<K, V, S>> for BTreeMap<K, V> {
+	fn from(map: BoundedBTreeMap<K, V, S>) -> Self {
+		map.0
+	}
+}
+
+impl<K, V, S> AsRef<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	fn as_ref(&self) -> &BTreeMap<K, V> {
+		&self.0
+	}
+}
+
+// Allows for calling all of `BTreeMap`'s immutable methods on `BoundedBTreeMap`. A `DerefMut` is
+// deliberately not provided, as it would allow the bound to be broken.
+impl<K, V, S> Deref for BoundedBTreeMap<K, V, S> {
+	type Target = BTreeMap<K, V>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<K, V, S> IntoIterator for BoundedBTreeMap<K, V, S> {
+	type Item = (K, V);
+	type IntoIter = sp_std::collections::btree_map::IntoIter<K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<'a, K, V, S> IntoIterator for &'a BoundedBTreeMap<K, V, S> {
+	type Item = (&'a K, &'a V);
+	type IntoIter = sp_std::collections::btree_map::Iter<'a, K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.iter()
+	}
+}
+
+impl<K, V, S> codec::DecodeLength for BoundedBTreeMap<K, V, S> {
+	fn len(self_encoded: &[u8]) -> Result<usize, codec::Error> {
+		// `BoundedBTreeMap<K, V, S>` is stored just a `BTreeMap<K, V>`, which is stored as a
+		// `Compact<u32>` with its length followed by an iteration of its items. We can just use
+		// the underlying implementation.
+		<BTreeMap<K, V> as codec::DecodeLength>::len(self_encoded)
+	}
+}
+
+impl<K: Encode, V: Encode, S> StorageDecodeLength for BoundedBTreeMap<K, V, S> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_std::convert::TryInto;
+	use sp_io::TestExternalities;
+	use crate::{parameter_types, storage::{generator, StorageValue}};
+
+	parameter_types! {
+		pub const Seven: u32 = 7;
+		pub const Four: u32 = 4;
+	}
+
+	struct Foo;
+	impl generator::StorageValue<BoundedBTreeMap<u32, (), Seven>> for Foo {
+		type Query = BoundedBTreeMap<u32, (), Seven>;
+
+		fn module_prefix() -> &'static [u8] {
+			b"MyModule"
+		}
+
+		fn storage_prefix() -> &'static [u8] {
+			b"Foo"
+		}
+
+		fn from_optional_value_to_query(v: Option<BoundedBTreeMap<u32, (), Seven>>) -> Self::Query {
+			v.unwrap_or_default()
+		}
+
+		fn from_query_to_optional_value(v: Self::Query) -> Option<BoundedBTreeMap<u32, (), Seven>> {
+			Some(v)
+		}
+	}
+
+	fn map_from_keys<K: Ord + Copy>(keys: &[K]) -> BTreeMap<K, ()> {
+		keys.iter().copied().zip(sp_std::iter::repeat(())).collect()
+	}
+
+	fn boundedmap_from_keys<K: Ord + Copy, S: Get<u32>>(keys: &[K]) -> BoundedBTreeMap<K, (), S> {
+		map_from_keys(keys).try_into().unwrap()
+	}
+
+	#[test]
+	fn decode_len_works() {
+		TestExternalities::default().execute_with(|| {
+			let bounded = boundedmap_from_keys::<u32, Seven>(&[1, 2, 3]);
+			Foo::put(bounded);
+			assert_eq!(Foo::decode_len().unwrap(), 3);
+		});
+	}
+
+	#[test]
+	fn try_insert_works() {
+		let mut bounded = boundedmap_from_keys::<u32, Four>(&[1, 2, 3]);
+		bounded.try_insert(0, ()).unwrap();
+		assert_eq!(*bounded, map_from_keys(&[1, 0, 2, 3]));
+
+		assert!(bounded.try_insert(9, ()).is_err());
+		assert_eq!(*bounded, map_from_keys(&[1, 0, 2, 3]));
+
+		// Replacing the value of an existing key does not change the length.
+		assert_eq!(bounded.try_insert(1, ()), Ok(Some(())));
+	}
+
+	#[test]
+	fn deref_coercion_works() {
+		let bounded = boundedmap_from_keys::<u32, Seven>(&[1, 2, 3]);
+		// these methods come from deref-ed map.
+		assert_eq!(bounded.len(), 3);
+		assert!(bounded.iter().next().is_some());
+		assert!(!bounded.is_empty());
+	}
+
+	#[test]
+	fn try_mutate_works() {
+		let bounded = boundedmap_from_keys::<u32, Seven>(&[1, 2, 3, 4, 5, 6]);
+		let bounded = bounded
+			.try_mutate(|v| {
+				v.insert(7, ());
+			})
+			.unwrap();
+		assert_eq!(bounded.len(), 7);
+		assert!(bounded
+			.try_mutate(|v| {
+				v.insert(8, ());
+			})
+			.is_none());
+	}
+
+	#[test]
+	fn try_from_and_decode_respect_bound() {
+		let five = map_from_keys(&[1u32, 2, 3, 4, 5]);
+		assert!(BoundedBTreeMap::<u32, (), Four>::try_from(five.clone()).is_err());
+
+		let encoded = five.encode();
+		assert!(BoundedBTreeMap::<u32, (), Four>::decode(&mut &encoded[..]).is_err());
+		let decoded = BoundedBTreeMap::<u32, (), Seven>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded, five);
+		// The encoding is the same as that of the inner `BTreeMap`.
+		assert_eq!(decoded.encode(), encoded);
+	}
+}
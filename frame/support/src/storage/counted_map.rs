@@ -0,0 +1,295 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A wrapper around a storage map which maintains a counter of its items.
+
+use sp_std::marker::PhantomData;
+use codec::{FullCodec, FullEncode, EncodeLike};
+use crate::storage::{
+	StorageMap, StorageValue, StoragePrefixedMap, StorageDecodeLength, generator,
+};
+
+type QueryOf<K, V, Map> = <Map as generator::StorageMap<K, V>>::Query;
+
+/// A storage map which keeps count of the number of items it holds.
+///
+/// `Map` is the underlying map and `Counter` the storage value holding the number of items in
+/// it, both as declared with `decl_storage`:
+///
+/// ```nocompile
+/// decl_storage! {
+/// 	trait Store for Module<T: Trait> as Example {
+/// 		Bar: map hasher(twox_64_concat) u32 => u64;
+/// 		BarCount: u32;
+/// 	}
+/// }
+///
+/// type CountedBar = CountedStorageMap<u32, u64, Bar, BarCount>;
+/// ```
+///
+/// As long as `Map` is only ever modified through this type, `count` returns the number of
+/// items in it without iterating the map.
+pub struct CountedStorageMap<K, V, Map, Counter>(PhantomData<(K, V, Map, Counter)>);
+
+impl<K, V, Map, Counter> CountedStorageMap<K, V, Map, Counter>
+where
+	K: FullEncode,
+	V: FullCodec,
+	Map: generator::StorageMap<K, V>,
+	Counter: StorageValue<u32, Query = u32>,
+{
+	/// The number of items in the map.
+	pub fn count() -> u32 {
+		Counter::get()
+	}
+
+	/// Does the value (explicitly) exist in storage?
+	pub fn contains_key<KeyArg: EncodeLike<K>>(key: KeyArg) -> bool {
+		<Map as StorageMap<K, V>>::contains_key(key)
+	}
+
+	/// Load the value associated with the given key from the map.
+	pub fn get<KeyArg: EncodeLike<K>>(key: KeyArg) -> QueryOf<K, V, Map> {
+		<Map as StorageMap<K, V>>::get(key)
+	}
+
+	/// Store a value to be associated with the given key from the map.
+	pub fn insert<KeyArg, ValArg>(key: KeyArg, val: ValArg)
+	where
+		KeyArg: EncodeLike<K> + Clone,
+		ValArg: EncodeLike<V>,
+	{
+		if !Self::contains_key(key.clone()) {
+			Counter::mutate(|c| *c = c.saturating_add(1));
+		}
+		<Map as StorageMap<K, V>>::insert(key, val)
+	}
+
+	/// Remove the value under a key.
+	pub fn remove<KeyArg: EncodeLike<K> + Clone>(key: KeyArg) {
+		if Self::contains_key(key.clone()) {
+			Counter::mutate(|c| *c = c.saturating_sub(1));
+		}
+		<Map as StorageMap<K, V>>::remove(key)
+	}
+
+	/// Take the value under a key.
+	pub fn take<KeyArg: EncodeLike<K>>(key: KeyArg) -> QueryOf<K, V, Map> {
+		let removed = Self::mutate_exists(key, |value| value.take());
+		Map::from_optional_value_to_query(removed)
+	}
+
+	/// Mutate the value under a key.
+	pub fn mutate<KeyArg: EncodeLike<K>, R, F: FnOnce(&mut QueryOf<K, V, Map>) -> R>(
+		key: KeyArg,
+		f: F,
+	) -> R {
+		Self::mutate_exists(key, |option| {
+			let mut query = Map::from_optional_value_to_query(option.take());
+			let res = f(&mut query);
+			*option = Map::from_query_to_optional_value(query);
+			res
+		})
+	}
+
+	/// Mutate the item, only if an `Ok` value is returned.
+	pub fn try_mutate<KeyArg, R, E, F>(key: KeyArg, f: F) -> Result<R, E>
+	where
+		KeyArg: EncodeLike<K>,
+		F: FnOnce(&mut QueryOf<K, V, Map>) -> Result<R, E>,
+	{
+		Self::try_mutate_exists(key, |option| {
+			let mut query = Map::from_optional_value_to_query(option.take());
+			let res = f(&mut query);
+			*option = Map::from_query_to_optional_value(query);
+			res
+		})
+	}
+
+	/// Mutate the value under a key. Deletes the item if mutated to a `None`.
+	pub fn mutate_exists<KeyArg: EncodeLike<K>, R, F: FnOnce(&mut Option<V>) -> R>(
+		key: KeyArg,
+		f: F,
+	) -> R {
+		<Map as StorageMap<K, V>>::mutate_exists(key, |option| {
+			let existed = option.is_some();
+			let res = f(option);
+			Self::update_counter(existed, option.is_some());
+			res
+		})
+	}
+
+	/// Mutate the item, only if an `Ok` value is returned. Deletes the item if mutated to a
+	/// `None`.
+	pub fn try_mutate_exists<KeyArg, R, E, F>(key: KeyArg, f: F) -> Result<R, E>
+	where
+		KeyArg: EncodeLike<K>,
+		F: FnOnce(&mut Option<V>) -> Result<R, E>,
+	{
+		<Map as StorageMap<K, V>>::try_mutate_exists(key, |option| {
+			let existed = option.is_some();
+			let res = f(option)?;
+			Self::update_counter(existed, option.is_some());
+			Ok(res)
+		})
+	}
+
+	/// Read the length of the storage value without decoding the entire value under the given
+	/// `key`.
+	///
+	/// See [`StorageMap::decode_len`].
+	pub fn decode_len<KeyArg: EncodeLike<K>>(key: KeyArg) -> Option<usize>
+	where
+		V: StorageDecodeLength,
+	{
+		<Map as StorageMap<K, V>>::decode_len(key)
+	}
+
+	/// Remove all values of the map and reset the counter.
+	pub fn remove_all()
+	where
+		Map: StoragePrefixedMap<V>,
+	{
+		Counter::kill();
+		<Map as StoragePrefixedMap<V>>::remove_all()
+	}
+
+	/// Initialize the counter with the actual number of items in the map, returning it.
+	///
+	/// This only iterates over the keys of the map and never decodes a value. It is meant to be
+	/// used in a migration when a counter is introduced for an existing map, or when the map was
+	/// modified without going through this type.
+	pub fn initialize_counter() -> u32
+	where
+		Map: StoragePrefixedMap<V>,
+	{
+		let prefix = Map::final_prefix();
+		let mut count = 0u32;
+		let mut previous_key = prefix.to_vec();
+		while let Some(next) = sp_io::storage::next_key(&previous_key)
+			.filter(|n| n.starts_with(&prefix))
+		{
+			count = count.saturating_add(1);
+			previous_key = next;
+		}
+		Counter::put(count);
+		count
+	}
+
+	fn update_counter(existed: bool, exists: bool) {
+		match (existed, exists) {
+			(false, true) => Counter::mutate(|c| *c = c.saturating_add(1)),
+			(true, false) => Counter::mutate(|c| *c = c.saturating_sub(1)),
+			_ => (),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_io::TestExternalities;
+
+	pub trait Trait {
+		type Origin;
+		type BlockNumber;
+	}
+
+	crate::decl_module! {
+		pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	}
+
+	crate::decl_storage! {
+		trait Store for Module<T: Trait> as Runtime {
+			Values: map hasher(twox_64_concat) u32 => u64;
+			ValuesCount: u32;
+			Lists: map hasher(twox_64_concat) u32 => Option<Vec<u32>>;
+			ListsCount: u32;
+		}
+	}
+
+	type CountedValues = CountedStorageMap<u32, u64, Values, ValuesCount>;
+	type CountedLists = CountedStorageMap<u32, Vec<u32>, Lists, ListsCount>;
+
+	#[test]
+	fn insert_and_remove_maintain_count() {
+		TestExternalities::default().execute_with(|| {
+			assert_eq!(CountedValues::count(), 0);
+
+			CountedValues::insert(1, 10);
+			CountedValues::insert(2, 20);
+			assert_eq!(CountedValues::count(), 2);
+
+			// Overwriting does not change the count.
+			CountedValues::insert(1, 11);
+			assert_eq!(CountedValues::count(), 2);
+			assert_eq!(CountedValues::get(1), 11);
+
+			CountedValues::remove(1);
+			assert_eq!(CountedValues::count(), 1);
+
+			// Removing a missing key does not change the count.
+			CountedValues::remove(1);
+			assert_eq!(CountedValues::count(), 1);
+
+			assert_eq!(CountedValues::take(2), 20);
+			assert_eq!(CountedValues::take(2), 0);
+			assert_eq!(CountedValues::count(), 0);
+		});
+	}
+
+	#[test]
+	fn mutate_maintains_count() {
+		TestExternalities::default().execute_with(|| {
+			CountedLists::mutate(1, |v| *v = Some(vec![1]));
+			assert_eq!(CountedLists::count(), 1);
+
+			CountedLists::mutate(1, |v| v.as_mut().unwrap().push(2));
+			assert_eq!(CountedLists::count(), 1);
+			assert_eq!(CountedLists::decode_len(1), Some(2));
+
+			assert_eq!(
+				CountedLists::try_mutate(2, |v| { *v = Some(vec![]); Err(()) }),
+				Err::<(), _>(()),
+			);
+			assert_eq!(CountedLists::count(), 1);
+
+			CountedLists::mutate_exists(1, |v| *v = None);
+			assert_eq!(CountedLists::count(), 0);
+			assert!(!CountedLists::contains_key(1));
+		});
+	}
+
+	#[test]
+	fn initialize_counter_and_remove_all_work() {
+		TestExternalities::default().execute_with(|| {
+			Values::insert(1, 10);
+			Values::insert(2, 20);
+			Values::insert(3, 30);
+			Lists::insert(1, vec![1]);
+			assert_eq!(CountedValues::count(), 0);
+
+			assert_eq!(CountedValues::initialize_counter(), 3);
+			assert_eq!(CountedValues::count(), 3);
+
+			CountedValues::remove_all();
+			assert_eq!(CountedValues::count(), 0);
+			assert!(!Values::contains_key(1));
+			assert_eq!(CountedLists::initialize_counter(), 1);
+		});
+	}
+}
@@ -206,7 +206,15 @@ impl<K1, K2, V, G> storage::StorageDoubleMap<K1, K2, V> for G where
 	}
 
 	fn remove_prefix<KArg1>(k1: KArg1) where KArg1: EncodeLike<K1> {
-		unhashed::kill_prefix(Self::storage_double_map_final_key1(k1).as_ref())
+		sp_io::storage::clear_prefix(Self::storage_double_map_final_key1(k1).as_ref())
+	}
+
+	fn clear_prefix<KArg1>(
+		k1: KArg1,
+		limit: u32,
+		cursor: Option<&[u8]>,
+	) -> storage::ClearPrefixOutcome where KArg1: EncodeLike<K1> {
+		unhashed::clear_prefix(Self::storage_double_map_final_key1(k1).as_ref(), limit, cursor)
 	}
 
 	fn iter_prefix_values<KArg1>(k1: KArg1) -> storage::PrefixIterator<V> where
@@ -22,6 +22,7 @@ use codec::{FullCodec, FullEncode, Encode, EncodeLike, Decode};
 use crate::hash::{Twox128, StorageHasher};
 use sp_runtime::generic::{Digest, DigestItem};
 pub use sp_runtime::TransactionOutcome;
+pub use sp_io::ClearPrefixOutcome;
 
 pub mod unhashed;
 pub mod hashed;
@@ -29,6 +30,13 @@ pub mod child;
 #[doc(hidden)]
 pub mod generator;
 pub mod migration;
+pub mod bounded_vec;
+pub mod bounded_btree_map;
+pub mod counted_map;
+
+pub use bounded_vec::BoundedVec;
+pub use bounded_btree_map::BoundedBTreeMap;
+pub use counted_map::CountedStorageMap;
 
 /// Execute the supplied function in a new storage transaction.
 ///
@@ -49,6 +57,24 @@ pub fn with_transaction<R>(f: impl FnOnce() -> TransactionOutcome<R>) -> R {
 	}
 }
 
+/// Execute the supplied function in a new storage layer.
+///
+/// All changes to storage performed by the supplied function are discarded if it returns `Err`,
+/// and committed to the parent layer if it returns `Ok`.
+///
+/// Storage layers can be nested to any depth. This is what the `#[transactional]` attribute
+/// expands to.
+pub fn with_storage_layer<R, E>(f: impl FnOnce() -> Result<R, E>) -> Result<R, E> {
+	with_transaction(|| {
+		let r = f();
+		if r.is_ok() {
+			TransactionOutcome::Commit(r)
+		} else {
+			TransactionOutcome::Rollback(r)
+		}
+	})
+}
+
 /// A trait for working with macro-generated storage values under the substrate storage API.
 ///
 /// Details on implementation can be found at
@@ -348,6 +374,13 @@ pub trait StorageDoubleMap<K1: FullEncode, K2: FullEncode, V: FullCodec> {
 	/// Remove all values under the first key.
 	fn remove_prefix<KArg1>(k1: KArg1) where KArg1: ?Sized + EncodeLike<K1>;
 
+	/// Remove at most `limit` values under the first key, continuing after `cursor` if it is
+	/// given.
+	///
+	/// See [`unhashed::clear_prefix`] for how to continue the removal.
+	fn clear_prefix<KArg1>(k1: KArg1, limit: u32, cursor: Option<&[u8]>) -> ClearPrefixOutcome
+		where KArg1: ?Sized + EncodeLike<K1>;
+
 	/// Iterate over values that share the first key.
 	fn iter_prefix_values<KArg1>(k1: KArg1) -> PrefixIterator<V>
 		where KArg1: ?Sized + EncodeLike<K1>;
@@ -478,6 +511,13 @@ pub trait StoragePrefixedMap<Value: FullCodec> {
 		sp_io::storage::clear_prefix(&Self::final_prefix())
 	}
 
+	/// Remove at most `limit` values of the storage, continuing after `cursor` if it is given.
+	///
+	/// See [`unhashed::clear_prefix`] for how to continue the removal.
+	fn clear(limit: u32, cursor: Option<&[u8]>) -> ClearPrefixOutcome {
+		unhashed::clear_prefix(&Self::final_prefix(), limit, cursor)
+	}
+
 	/// Iter over all value of the storage.
 	fn iter_values() -> PrefixIterator<Value> {
 		let prefix = Self::final_prefix();
@@ -569,6 +609,8 @@ mod private {
 
 	impl<T: Encode> Sealed for Vec<T> {}
 	impl<Hash: Encode> Sealed for Digest<Hash> {}
+	impl<T, S> Sealed for BoundedVec<T, S> {}
+	impl<K, V, S> Sealed for BoundedBTreeMap<K, V, S> {}
 }
 
 impl<T: Encode> StorageAppend<T> for Vec<T> {}
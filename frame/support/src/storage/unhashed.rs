@@ -19,6 +19,7 @@
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
+use crate::storage::ClearPrefixOutcome;
 
 /// Return the value of the item in storage under `key`, or `None` if there is no explicit entry.
 pub fn get<T: Decode + Sized>(key: &[u8]) -> Option<T> {
@@ -92,10 +93,22 @@ pub fn kill(key: &[u8]) {
 }
 
 /// Ensure keys with the given `prefix` have no entries in storage.
+///
+/// This removes an unbounded number of entries, use [`clear_prefix`] instead.
+#[deprecated = "Use `clear_prefix` instead, which removes a bounded number of entries"]
 pub fn kill_prefix(prefix: &[u8]) {
 	sp_io::storage::clear_prefix(prefix);
 }
 
+/// Remove at most `limit` entries with keys starting with the given `prefix` from storage,
+/// continuing after `cursor` if it is given.
+///
+/// The returned outcome holds a cursor to continue the removal, for example in a later block, if
+/// not all entries could be removed.
+pub fn clear_prefix(prefix: &[u8], limit: u32, cursor: Option<&[u8]>) -> ClearPrefixOutcome {
+	sp_io::storage::clear_prefix_limited(prefix, limit, cursor.map(|c| c.to_vec()))
+}
+
 /// Get a Vec of bytes from storage.
 pub fn get_raw(key: &[u8]) -> Option<Vec<u8>> {
 	sp_io::storage::get(key)
@@ -23,7 +23,7 @@ use sp_std::{prelude::*, result, marker::PhantomData, ops::Div, fmt::Debug};
 use codec::{FullCodec, Codec, Encode, Decode, EncodeLike};
 use sp_core::u32_trait::Value as U32;
 use sp_runtime::{
-	RuntimeDebug, ConsensusEngineId, DispatchResult, DispatchError, traits::{
+	RuntimeDebug, ConsensusEngineId, DispatchResult, DispatchError, Perbill, traits::{
 		MaybeSerializeDeserialize, AtLeast32Bit, Saturating, TrailingZeroInput, Bounded, Zero,
 		BadOrigin, AtLeast32BitUnsigned
 	},
@@ -1093,6 +1093,66 @@ pub trait ReservableCurrency<AccountId>: Currency<AccountId> {
 	) -> result::Result<Self::Balance, DispatchError>;
 }
 
+/// A currency where funds can be reserved from the user under a named identifier, so that
+/// independent subsystems reserving funds of the same account do not interfere with each other.
+///
+/// The named reserves are a subset of the reserved balance of `ReservableCurrency`.
+pub trait NamedReservableCurrency<AccountId>: ReservableCurrency<AccountId> {
+	/// An identifier for a reserve. Used for disambiguating different reserves so that
+	/// they can be individually managed.
+	type ReserveIdentifier;
+
+	/// Deducts up to `value` from the reserved balance of `who` held under `id`. This function
+	/// cannot fail.
+	///
+	/// As much funds up to `value` will be deducted as possible. If the named reserve of `who`
+	/// is less than `value`, then a non-zero second item will be returned.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		who: &AccountId,
+		value: Self::Balance
+	) -> (Self::NegativeImbalance, Self::Balance);
+
+	/// The amount of the balance of a given account that is reserved under `id`.
+	fn reserved_balance_named(id: &Self::ReserveIdentifier, who: &AccountId) -> Self::Balance;
+
+	/// Moves `value` from balance to the reserved balance held under `id`.
+	///
+	/// If the free balance is lower than `value`, then no funds will be moved and an `Err` will
+	/// be returned to notify of this. This is different behavior than `unreserve_named`.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		who: &AccountId,
+		value: Self::Balance
+	) -> DispatchResult;
+
+	/// Moves up to `value` from the reserved balance held under `id` to free balance. This
+	/// function cannot fail.
+	///
+	/// As much funds up to `value` will be moved as possible. If the named reserve of `who`
+	/// is less than `value`, then the remaining amount will be returned.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		who: &AccountId,
+		value: Self::Balance
+	) -> Self::Balance;
+
+	/// Moves up to `value` from the reserved balance held under `id` of account `slashed` to
+	/// balance of account `beneficiary`. `beneficiary` must exist for this to succeed. If it does
+	/// not, `Err` will be returned. Funds will be placed in either the `free` balance or the
+	/// reserved balance held under the same `id`, depending on the `status`.
+	///
+	/// As much funds up to `value` will be deducted as possible. If this is less than `value`,
+	/// then `Ok(non_zero)` will be returned.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError>;
+}
+
 /// An identifier for a lock. Used for disambiguating different locks so that
 /// they can be individually replaced or removed.
 pub type LockIdentifier = [u8; 8];
@@ -1152,7 +1212,7 @@ pub trait VestingSchedule<AccountId> {
 
 	/// Adds a vesting schedule to a given account.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
+	/// If the account already has the maximum number of vesting schedules, an `Err` is returned
 	/// and nothing is updated.
 	///
 	/// Is a no-op if the amount to be vested is zero.
@@ -1165,10 +1225,10 @@ pub trait VestingSchedule<AccountId> {
 		starting_block: Self::Moment,
 	) -> DispatchResult;
 
-	/// Remove a vesting schedule for a given account.
+	/// Remove the vesting schedule at `schedule_index` for a given account.
 	///
 	/// NOTE: This doesn't alter the free balance of the account.
-	fn remove_vesting_schedule(who: &AccountId);
+	fn remove_vesting_schedule(who: &AccountId, schedule_index: u32) -> DispatchResult;
 }
 
 bitmask! {
@@ -1431,6 +1491,50 @@ impl<BlockNumber: Clone> OnInitialize<BlockNumber> for Tuple {
 	}
 }
 
+/// The block's on idle trait. Implementing this lets you express what should happen
+/// for your module when the block is ending, if there is weight left in the block.
+pub trait OnIdle<BlockNumber> {
+	/// The block is being finalized and `remaining_weight` is left unused in it.
+	///
+	/// Implement to have something happen, using at most `remaining_weight`. Return the weight
+	/// actually consumed, which is added to the block's weight.
+	fn on_idle(
+		_n: BlockNumber,
+		_remaining_weight: crate::weights::Weight,
+	) -> crate::weights::Weight {
+		0
+	}
+}
+
+#[impl_for_tuples(30)]
+impl<BlockNumber: Copy + AtLeast32BitUnsigned> OnIdle<BlockNumber> for Tuple {
+	/// Call `on_idle` of every element, each with the weight still left after the elements
+	/// before it.
+	///
+	/// The element which is called first rotates with the block number, so that no element is
+	/// consistently starved of weight by the ones before it.
+	fn on_idle(n: BlockNumber, remaining_weight: crate::weights::Weight) -> crate::weights::Weight {
+		use sp_std::convert::TryInto;
+
+		let on_idle_functions: &[fn(BlockNumber, crate::weights::Weight) -> crate::weights::Weight] =
+			&[for_tuples!( #( Tuple::on_idle ),* )];
+		let len = on_idle_functions.len();
+		if len == 0 {
+			return 0;
+		}
+
+		let start_index = n % (len as u32).into();
+		let start_index: usize = start_index.try_into().ok()
+			.expect("`start_index % len` always fits into `usize`, because `len` is a `usize`; qed");
+		let mut weight: crate::weights::Weight = 0;
+		for on_idle in on_idle_functions.iter().cycle().skip(start_index).take(len) {
+			let adjusted_remaining_weight = remaining_weight.saturating_sub(weight);
+			weight = weight.saturating_add(on_idle(n, adjusted_remaining_weight));
+		}
+		weight
+	}
+}
+
 /// The runtime upgrade trait.
 ///
 /// Implementing this lets you express what should happen when the runtime upgrades,
@@ -1457,6 +1561,117 @@ impl OnRuntimeUpgrade for Tuple {
 	}
 }
 
+/// The storage key postfix that is used to store the [`StorageVersion`] per pallet.
+///
+/// The full storage key is built by using:
+/// `Twox128(storage_prefix) ++ Twox128(STORAGE_VERSION_STORAGE_KEY_POSTFIX)`
+pub const STORAGE_VERSION_STORAGE_KEY_POSTFIX: &[u8] = b":__STORAGE_VERSION__:";
+
+/// The storage version of a pallet.
+///
+/// Each storage version of a pallet is stored in the state under a fixed key. See
+/// [`STORAGE_VERSION_STORAGE_KEY_POSTFIX`] for how this key is built. A pallet which has never
+/// stored its version is at version `0`.
+#[derive(RuntimeDebug, Eq, PartialEq, Encode, Decode, Ord, Clone, Copy, PartialOrd, Default)]
+pub struct StorageVersion(u16);
+
+impl StorageVersion {
+	/// Creates a new instance of `Self`.
+	pub const fn new(version: u16) -> Self {
+		Self(version)
+	}
+
+	/// Returns the storage key for a storage version of the pallet with the given storage prefix.
+	pub fn storage_key(pallet_prefix: &[u8]) -> [u8; 32] {
+		let mut final_key = [0u8; 32];
+		final_key[..16].copy_from_slice(&sp_io::hashing::twox_128(pallet_prefix));
+		final_key[16..].copy_from_slice(
+			&sp_io::hashing::twox_128(STORAGE_VERSION_STORAGE_KEY_POSTFIX)
+		);
+		final_key
+	}
+
+	/// Put this storage version for the given pallet into the storage.
+	pub fn put<P: GetStorageVersion + ?Sized>(&self) {
+		crate::storage::unhashed::put(&Self::storage_key(P::storage_prefix()), self);
+	}
+
+	/// Get the storage version of the given pallet from the storage.
+	///
+	/// It will return `StorageVersion(0)` if there is no version stored in the state.
+	pub fn get<P: GetStorageVersion + ?Sized>() -> Self {
+		crate::storage::unhashed::get_or_default(&Self::storage_key(P::storage_prefix()))
+	}
+}
+
+impl PartialEq<u16> for StorageVersion {
+	fn eq(&self, other: &u16) -> bool {
+		self.0 == *other
+	}
+}
+
+impl PartialOrd<u16> for StorageVersion {
+	fn partial_cmp(&self, other: &u16) -> Option<sp_std::cmp::Ordering> {
+		Some(self.0.cmp(other))
+	}
+}
+
+/// Provides information about the storage version of a pallet.
+///
+/// It differentiates between the current storage version, which is the version the pallet's
+/// code is written against, and the on-chain storage version, which is the version of the
+/// pallet's data in the state. A migration is due whenever these two differ.
+pub trait GetStorageVersion {
+	/// The prefix of the pallet's storage, as passed to `decl_storage`.
+	fn storage_prefix() -> &'static [u8];
+
+	/// Returns the current storage version as supported by the pallet.
+	fn current_storage_version() -> StorageVersion;
+
+	/// Returns the on-chain storage version of the pallet as stored in the storage.
+	fn on_chain_storage_version() -> StorageVersion {
+		StorageVersion::get::<Self>()
+	}
+}
+
+/// An `OnRuntimeUpgrade` which only runs the `Inner` migration of `Pallet` if its on-chain
+/// storage version is `From`, setting it to `To` afterwards.
+///
+/// This makes migrations idempotent: a migration which was already applied, or which does not
+/// apply to the pallet's data, is skipped. The weight of checking and updating the version is
+/// accounted for using `DbWeight`.
+pub struct VersionedMigration<From, To, Inner, Pallet, DbWeight>(
+	PhantomData<(From, To, Inner, Pallet, DbWeight)>
+);
+
+impl<From, To, Inner, Pallet, DbWeight> OnRuntimeUpgrade
+	for VersionedMigration<From, To, Inner, Pallet, DbWeight>
+where
+	From: Get<u16>,
+	To: Get<u16>,
+	Inner: OnRuntimeUpgrade,
+	Pallet: GetStorageVersion,
+	DbWeight: Get<crate::weights::RuntimeDbWeight>,
+{
+	fn on_runtime_upgrade() -> crate::weights::Weight {
+		let on_chain_version = Pallet::on_chain_storage_version();
+		if on_chain_version == From::get() {
+			let weight = Inner::on_runtime_upgrade();
+			StorageVersion::new(To::get()).put::<Pallet>();
+			weight.saturating_add(DbWeight::get().reads_writes(1, 1))
+		} else {
+			crate::debug::warn!(
+				"Skipping migration of {:?} from version {} to {}: on-chain version is {:?}",
+				sp_std::str::from_utf8(Pallet::storage_prefix()),
+				From::get(),
+				To::get(),
+				on_chain_version,
+			);
+			DbWeight::get().reads(1)
+		}
+	}
+}
+
 /// Off-chain computation trait.
 ///
 /// Implementing this trait on a module allows you to perform long-running tasks
@@ -1576,6 +1791,93 @@ pub trait EnsureOrigin<OuterOrigin> {
 	fn successful_origin() -> OuterOrigin;
 }
 
+/// Something which can compute a result from an input.
+pub trait Morph<A> {
+	/// The type into which `A` is mutated.
+	type Outcome;
+
+	/// Make conversion.
+	fn morph(a: A) -> Self::Outcome;
+}
+
+impl<T> Morph<T> for sp_runtime::traits::Identity {
+	type Outcome = T;
+	fn morph(a: T) -> T { a }
+}
+
+/// Something which may compute a result from an input, or fail.
+pub trait TryMorph<A> {
+	/// The type into which `A` is mutated.
+	type Outcome;
+
+	/// Make conversion.
+	fn try_morph(a: A) -> result::Result<Self::Outcome, ()>;
+}
+
+impl<A, M: Morph<A>> TryMorph<A> for M {
+	type Outcome = M::Outcome;
+	fn try_morph(a: A) -> result::Result<M::Outcome, ()> { Ok(M::morph(a)) }
+}
+
+/// "OR gate" implementation of `EnsureOrigin` for two origin checks with the same success type.
+///
+/// Origin check will pass if `L` or `R` origin check passes. `L` is tested first.
+pub struct EitherOf<L, R>(PhantomData<(L, R)>);
+impl<
+	OuterOrigin,
+	L: EnsureOrigin<OuterOrigin>,
+	R: EnsureOrigin<OuterOrigin, Success = L::Success>,
+> EnsureOrigin<OuterOrigin> for EitherOf<L, R> {
+	type Success = L::Success;
+	fn try_origin(o: OuterOrigin) -> result::Result<Self::Success, OuterOrigin> {
+		L::try_origin(o).or_else(R::try_origin)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> OuterOrigin {
+		L::successful_origin()
+	}
+}
+
+/// `EnsureOrigin` implementation which passes when `Original` does, mapping its success value
+/// with `Mutator`.
+pub struct MapSuccess<Original, Mutator>(PhantomData<(Original, Mutator)>);
+impl<
+	OuterOrigin,
+	Original: EnsureOrigin<OuterOrigin>,
+	Mutator: Morph<Original::Success>,
+> EnsureOrigin<OuterOrigin> for MapSuccess<Original, Mutator> {
+	type Success = Mutator::Outcome;
+	fn try_origin(o: OuterOrigin) -> result::Result<Self::Success, OuterOrigin> {
+		Original::try_origin(o).map(Mutator::morph)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> OuterOrigin {
+		Original::successful_origin()
+	}
+}
+
+/// `EnsureOrigin` implementation which passes when `Original` does and `Mutator` manages to map
+/// its success value. The origin check fails if the mapping fails.
+pub struct TryMapSuccess<Original, Mutator>(PhantomData<(Original, Mutator)>);
+impl<
+	OuterOrigin: Clone,
+	Original: EnsureOrigin<OuterOrigin>,
+	Mutator: TryMorph<Original::Success>,
+> EnsureOrigin<OuterOrigin> for TryMapSuccess<Original, Mutator> {
+	type Success = Mutator::Outcome;
+	fn try_origin(o: OuterOrigin) -> result::Result<Self::Success, OuterOrigin> {
+		let orig = o.clone();
+		Original::try_origin(o).and_then(|s| Mutator::try_morph(s).map_err(|()| orig))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> OuterOrigin {
+		Original::successful_origin()
+	}
+}
+
 /// Type that can be dispatched with an origin but without checking the origin filter.
 ///
 /// Implemented for pallet dispatchable type by `decl_module` and for runtime dispatchable by
@@ -1629,6 +1931,627 @@ pub trait IsType<T>: Into<T> + From<T> {
 	fn into_mut(&mut self) -> &mut T;
 }
 
+/// An interface for looking up preimages from their hash on chain.
+pub trait PreimageProvider<Hash> {
+	/// Returns whether a preimage exists for a given hash.
+	///
+	/// A value of `true` implies that `get_preimage` is `Some`.
+	fn have_preimage(hash: &Hash) -> bool;
+
+	/// Returns the preimage for a given hash.
+	fn get_preimage(hash: &Hash) -> Option<Vec<u8>>;
+
+	/// Returns whether a preimage request exists for a given hash.
+	fn preimage_requested(hash: &Hash) -> bool;
+
+	/// Request that someone report a preimage. Providers use this to optimise the economics for
+	/// preimage reporting.
+	fn request_preimage(hash: &Hash);
+
+	/// Cancel a previous preimage request.
+	fn unrequest_preimage(hash: &Hash);
+}
+
+impl<Hash> PreimageProvider<Hash> for () {
+	fn have_preimage(_: &Hash) -> bool { false }
+	fn get_preimage(_: &Hash) -> Option<Vec<u8>> { None }
+	fn preimage_requested(_: &Hash) -> bool { false }
+	fn request_preimage(_: &Hash) {}
+	fn unrequest_preimage(_: &Hash) {}
+}
+
+/// An interface for managing preimages to hashes on chain.
+///
+/// Note that this API does not assume any underlying user is calling, and thus
+/// does not handle any preimage ownership or fees. Other system level logic that
+/// uses this API should implement that on their own side.
+pub trait PreimageRecipient<Hash>: PreimageProvider<Hash> {
+	/// Maximum size of a preimage.
+	type MaxSize: Get<u32>;
+
+	/// Store the bytes of a preimage on chain. Preimages longer than `MaxSize` are ignored.
+	fn note_preimage(bytes: Vec<u8>);
+
+	/// Clear a previously noted preimage. This is infallible and should be treated more like a
+	/// hint - if it was not previously noted or if it is now requested, then this will not do
+	/// anything.
+	fn unnote_preimage(hash: &Hash);
+}
+
+impl<Hash> PreimageRecipient<Hash> for () {
+	type MaxSize = ();
+	fn note_preimage(_: Vec<u8>) {}
+	fn unnote_preimage(_: &Hash) {}
+}
+
+/// The tally of the votes on a poll of some `Class`, as far as the poll itself is concerned.
+pub trait VoteTally<Class> {
+	/// The proportion of the votes cast which are in favour.
+	fn approval(&self) -> Perbill;
+
+	/// The proportion of the electorate of polls of `class` which voted in favour.
+	fn support(&self, class: Class) -> Perbill;
+}
+
+/// The status of a poll, as seen by the voting system.
+#[derive(RuntimeDebug)]
+pub enum PollStatus<Tally, Moment, Class> {
+	/// There is no such poll.
+	None,
+	/// The poll is ongoing, with the given tally, in the given class.
+	Ongoing(Tally, Class),
+	/// The poll ended at the given moment, it was approved if the flag is set.
+	Completed(Moment, bool),
+}
+
+impl<Tally, Moment, Class> PollStatus<Tally, Moment, Class> {
+	/// Returns the tally and class of the poll, if it is ongoing.
+	pub fn ensure_ongoing(self) -> Option<(Tally, Class)> {
+		match self {
+			PollStatus::Ongoing(tally, class) => Some((tally, class)),
+			_ => None,
+		}
+	}
+}
+
+/// Something which holds polls that a voting system may vote on, e.g. referenda.
+pub trait Polling<Tally> {
+	/// The index by which a poll is identified.
+	type Index: Parameter + Copy + Ord;
+
+	/// The class of a poll. Votes on polls of different classes are locked independently.
+	type Class: Parameter + Copy + Ord;
+
+	/// The type used to express when a poll ended.
+	type Moment;
+
+	/// Access the status of the poll `index`, with the possibility of altering its tally.
+	///
+	/// Changes to the tally are stored once `f` returns.
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally, Self::Moment, Self::Class>) -> R,
+	) -> R;
+
+	/// Access the status of the poll `index`, with the possibility of altering its tally.
+	///
+	/// Changes to the tally are only stored if `f` returns `Ok`.
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(
+			PollStatus<&mut Tally, Self::Moment, Self::Class>
+		) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError>;
+}
+
+/// Traits for dealing with a single fungible token class and any associated types, written
+/// uniformly so that modules can be generic over where the funds are kept.
+pub mod fungible {
+	use super::*;
+
+	/// One of a number of consequences of depositing a fungible asset into an account.
+	#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+	pub enum DepositConsequence {
+		/// Deposit couldn't happen due to the amount being too low. This is usually because the
+		/// account doesn't yet exist and the deposit wouldn't bring it to at least the minimum
+		/// needed for existence.
+		BelowMinimum,
+		/// Deposit cannot happen since the account cannot be created (usually because it's a
+		/// consumer and there exists no provider reference).
+		CannotCreate,
+		/// The asset is unknown. Usually because an `AssetId` has been presented which doesn't
+		/// exist on the system.
+		UnknownAsset,
+		/// An overflow would occur. This is practically unexpected, but could happen in test
+		/// systems with extremely small balance types or balances that approach the max value of
+		/// the balance type.
+		Overflow,
+		/// Account continued in existence.
+		Success,
+	}
+
+	/// One of a number of consequences of withdrawing a fungible asset from an account.
+	#[derive(Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+	pub enum WithdrawConsequence<Balance> {
+		/// Withdraw could not happen since the amount to be withdrawn is less than the total
+		/// funds in the account.
+		NoFunds,
+		/// The withdraw would mean the account dying when it needs to exist (usually because it
+		/// is a provider and there are consumer references on it).
+		WouldDie,
+		/// The asset is unknown. Usually because an `AssetId` has been presented which doesn't
+		/// exist on the system.
+		UnknownAsset,
+		/// There has been an underflow in the system. This is indicative of a corrupt state and
+		/// likely unrecoverable.
+		Underflow,
+		/// Not enough of the funds in the account are available for withdrawal.
+		Frozen,
+		/// Account balance would reduce to zero, potentially destroying it. The parameter is the
+		/// amount of balance which is destroyed.
+		ReducedToZero(Balance),
+		/// Account continued in existence.
+		Success,
+	}
+
+	/// Trait for providing balance-inspection access to a fungible asset.
+	pub trait Inspect<AccountId> {
+		/// Scalar type for representing balance of an account.
+		type Balance: AtLeast32BitUnsigned + FullCodec + Copy + Default + Debug;
+
+		/// The total amount of issuance in the system.
+		fn total_issuance() -> Self::Balance;
+
+		/// The minimum balance any single account may have.
+		fn minimum_balance() -> Self::Balance;
+
+		/// Get the balance of `who`.
+		fn balance(who: &AccountId) -> Self::Balance;
+
+		/// Get the maximum amount that `who` can withdraw/transfer successfully. If `keep_alive`
+		/// is `true`, then the account will not be reduced below the minimum balance.
+		fn reducible_balance(who: &AccountId, keep_alive: bool) -> Self::Balance;
+
+		/// Returns `Success` if the balance of `who` may be increased by `amount`, otherwise
+		/// the consequence.
+		fn can_deposit(who: &AccountId, amount: Self::Balance) -> DepositConsequence;
+
+		/// Returns `Success` if the balance of `who` may be decreased by `amount`, otherwise
+		/// the consequence.
+		fn can_withdraw(
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> WithdrawConsequence<Self::Balance>;
+	}
+
+	/// Trait for providing an asset which can be minted and burned.
+	pub trait Mutate<AccountId>: Inspect<AccountId> {
+		/// Increase the balance of `who` by exactly `amount`, minting new tokens. If that isn't
+		/// possible then an `Err` is returned and nothing is changed.
+		fn mint_into(who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Decrease the balance of `who` by exactly `amount`, burning the tokens. If that isn't
+		/// possible then an `Err` is returned and nothing is changed. On success, the amount
+		/// actually removed from the account is returned, which may exceed `amount` if the
+		/// account is reaped as a result.
+		fn burn_from(
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> Result<Self::Balance, DispatchError>;
+	}
+
+	/// Trait for providing a fungible asset which can only be transferred.
+	pub trait Transfer<AccountId>: Inspect<AccountId> {
+		/// Transfer exactly `amount` of funds from `source` to `dest`. If `keep_alive` is `true`
+		/// then `source` will not be reduced below the minimum balance.
+		fn transfer(
+			source: &AccountId,
+			dest: &AccountId,
+			amount: Self::Balance,
+			keep_alive: bool,
+		) -> Result<Self::Balance, DispatchError>;
+	}
+
+	/// Trait for inspecting a fungible asset which can be placed on hold.
+	pub trait InspectHold<AccountId>: Inspect<AccountId> {
+		/// Amount of funds held in reserve by `who`.
+		fn balance_on_hold(who: &AccountId) -> Self::Balance;
+
+		/// Check to see if some `amount` of funds of `who` may be placed on hold.
+		fn can_hold(who: &AccountId, amount: Self::Balance) -> bool;
+	}
+
+	/// Trait for mutating a fungible asset which can be placed on hold.
+	pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
+		/// Hold some funds in an account.
+		fn hold(who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Release up to `amount` held funds in an account.
+		///
+		/// The actual amount released is returned with `Ok`. If `best_effort` is `false`, then
+		/// the amount released is exactly `amount` or an `Err` is returned and nothing changes.
+		fn release(
+			who: &AccountId,
+			amount: Self::Balance,
+			best_effort: bool,
+		) -> Result<Self::Balance, DispatchError>;
+
+		/// Transfer held funds of `source` into the account of `dest`. If `on_hold` is `true`,
+		/// then the funds remain on hold in `dest`, otherwise they become free.
+		///
+		/// The actual amount transferred is returned with `Ok`. If `best_effort` is `false`, then
+		/// the amount transferred is exactly `amount` or an `Err` is returned and nothing changes.
+		fn transfer_held(
+			source: &AccountId,
+			dest: &AccountId,
+			amount: Self::Balance,
+			best_effort: bool,
+			on_hold: bool,
+		) -> Result<Self::Balance, DispatchError>;
+	}
+
+	/// Convert a `fungibles` trait implementation into a `fungible` trait implementation by
+	/// identifying a single item.
+	pub struct ItemOf<F, A, AccountId>(PhantomData<(F, A, AccountId)>);
+
+	impl<
+		F: fungibles::Inspect<AccountId>,
+		A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> Inspect<AccountId> for ItemOf<F, A, AccountId> {
+		type Balance = <F as fungibles::Inspect<AccountId>>::Balance;
+		fn total_issuance() -> Self::Balance {
+			<F as fungibles::Inspect<AccountId>>::total_issuance(A::get())
+		}
+		fn minimum_balance() -> Self::Balance {
+			<F as fungibles::Inspect<AccountId>>::minimum_balance(A::get())
+		}
+		fn balance(who: &AccountId) -> Self::Balance {
+			<F as fungibles::Inspect<AccountId>>::balance(A::get(), who)
+		}
+		fn reducible_balance(who: &AccountId, keep_alive: bool) -> Self::Balance {
+			<F as fungibles::Inspect<AccountId>>::reducible_balance(A::get(), who, keep_alive)
+		}
+		fn can_deposit(who: &AccountId, amount: Self::Balance) -> DepositConsequence {
+			<F as fungibles::Inspect<AccountId>>::can_deposit(A::get(), who, amount)
+		}
+		fn can_withdraw(
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> WithdrawConsequence<Self::Balance> {
+			<F as fungibles::Inspect<AccountId>>::can_withdraw(A::get(), who, amount)
+		}
+	}
+
+	impl<
+		F: fungibles::Mutate<AccountId>,
+		A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> Mutate<AccountId> for ItemOf<F, A, AccountId> {
+		fn mint_into(who: &AccountId, amount: Self::Balance) -> DispatchResult {
+			<F as fungibles::Mutate<AccountId>>::mint_into(A::get(), who, amount)
+		}
+		fn burn_from(
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> Result<Self::Balance, DispatchError> {
+			<F as fungibles::Mutate<AccountId>>::burn_from(A::get(), who, amount)
+		}
+	}
+
+	impl<
+		F: fungibles::Transfer<AccountId>,
+		A: Get<<F as fungibles::Inspect<AccountId>>::AssetId>,
+		AccountId,
+	> Transfer<AccountId> for ItemOf<F, A, AccountId> {
+		fn transfer(
+			source: &AccountId,
+			dest: &AccountId,
+			amount: Self::Balance,
+			keep_alive: bool,
+		) -> Result<Self::Balance, DispatchError> {
+			<F as fungibles::Transfer<AccountId>>::transfer(
+				A::get(),
+				source,
+				dest,
+				amount,
+				keep_alive,
+			)
+		}
+	}
+}
+
+/// Traits for dealing with multiple collections of fungible assets, each identified by an
+/// `AssetId`. These are the keyed counterparts of the traits in `fungible`.
+pub mod fungibles {
+	use super::*;
+	pub use super::fungible::{DepositConsequence, WithdrawConsequence};
+
+	/// Trait for providing balance-inspection access to a set of named fungible assets.
+	pub trait Inspect<AccountId> {
+		/// Means of identifying one asset class from another.
+		type AssetId: Copy;
+
+		/// Scalar type for representing balance of an account.
+		type Balance: AtLeast32BitUnsigned + FullCodec + Copy + Default + Debug;
+
+		/// The total amount of issuance in the system.
+		fn total_issuance(asset: Self::AssetId) -> Self::Balance;
+
+		/// The minimum balance any single account may have.
+		fn minimum_balance(asset: Self::AssetId) -> Self::Balance;
+
+		/// Get the `asset` balance of `who`.
+		fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+		/// Get the maximum amount of `asset` that `who` can withdraw/transfer successfully. If
+		/// `keep_alive` is `true`, then the account will not be reduced below the minimum balance.
+		fn reducible_balance(
+			asset: Self::AssetId,
+			who: &AccountId,
+			keep_alive: bool,
+		) -> Self::Balance;
+
+		/// Returns `Success` if the `asset` balance of `who` may be increased by `amount`,
+		/// otherwise the consequence.
+		fn can_deposit(
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> DepositConsequence;
+
+		/// Returns `Success` if the `asset` balance of `who` may be decreased by `amount`,
+		/// otherwise the consequence.
+		fn can_withdraw(
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> WithdrawConsequence<Self::Balance>;
+	}
+
+	/// Trait for providing a set of named fungible assets which can be created and destroyed.
+	pub trait Mutate<AccountId>: Inspect<AccountId> {
+		/// Increase the `asset` balance of `who` by exactly `amount`, minting new tokens. If that
+		/// isn't possible then an `Err` is returned and nothing is changed.
+		fn mint_into(
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> DispatchResult;
+
+		/// Decrease the `asset` balance of `who` by exactly `amount`, burning the tokens. If that
+		/// isn't possible then an `Err` is returned and nothing is changed. On success, the
+		/// amount actually removed from the account is returned.
+		fn burn_from(
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> Result<Self::Balance, DispatchError>;
+	}
+
+	/// Trait for providing a set of named fungible assets which can only be transferred.
+	pub trait Transfer<AccountId>: Inspect<AccountId> {
+		/// Transfer exactly `amount` of `asset` from `source` to `dest`. If `keep_alive` is
+		/// `true` then `source` will not be reduced below the minimum balance.
+		fn transfer(
+			asset: Self::AssetId,
+			source: &AccountId,
+			dest: &AccountId,
+			amount: Self::Balance,
+			keep_alive: bool,
+		) -> Result<Self::Balance, DispatchError>;
+	}
+
+	/// Trait for inspecting a set of named fungible assets which can be placed on hold.
+	pub trait InspectHold<AccountId>: Inspect<AccountId> {
+		/// Amount of funds of `asset` held in reserve by `who`.
+		fn balance_on_hold(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+		/// Check to see if some `amount` of `asset` of `who` may be placed on hold.
+		fn can_hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> bool;
+	}
+
+	/// Trait for mutating a set of named fungible assets which can be placed on hold.
+	pub trait MutateHold<AccountId>: InspectHold<AccountId> + Transfer<AccountId> {
+		/// Hold some funds of `asset` in an account.
+		fn hold(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Release up to `amount` held funds of `asset` in an account.
+		///
+		/// The actual amount released is returned with `Ok`. If `best_effort` is `false`, then
+		/// the amount released is exactly `amount` or an `Err` is returned and nothing changes.
+		fn release(
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+			best_effort: bool,
+		) -> Result<Self::Balance, DispatchError>;
+
+		/// Transfer held funds of `asset` of `source` into the account of `dest`. If `on_hold`
+		/// is `true`, then the funds remain on hold in `dest`, otherwise they become free.
+		///
+		/// The actual amount transferred is returned with `Ok`. If `best_effort` is `false`, then
+		/// the amount transferred is exactly `amount` or an `Err` is returned and nothing changes.
+		fn transfer_held(
+			asset: Self::AssetId,
+			source: &AccountId,
+			dest: &AccountId,
+			amount: Self::Balance,
+			best_effort: bool,
+			on_hold: bool,
+		) -> Result<Self::Balance, DispatchError>;
+	}
+}
+
+/// The status of a payment made through `Pay::pay`.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, RuntimeDebug)]
+pub enum PaymentStatus {
+	/// The payment is in progress; its outcome is not yet known.
+	InProgress,
+	/// The payment has been made successfully.
+	Success,
+	/// The payment has failed. It may be retried.
+	Failure,
+	/// The payment is not known, e.g. because it has already been concluded and forgotten.
+	Unknown,
+}
+
+/// Means of paying some amount of an asset kind to a beneficiary. The payment may be carried out
+/// asynchronously, in which case its outcome can be queried later with `check_payment`.
+pub trait Pay {
+	/// The type by which we measure units of the asset.
+	type Balance: AtLeast32BitUnsigned + FullCodec + Copy + Default + Debug;
+	/// The type by which we identify the beneficiaries to whom a payment may be made.
+	type Beneficiary;
+	/// The type for the kinds of asset that are going to be paid.
+	type AssetKind;
+	/// An identifier given to an individual payment.
+	type Id: FullCodec + Clone + Eq + Debug;
+	/// An error which could be returned by `pay`.
+	type Error: Debug;
+
+	/// Make a payment of `amount` of `asset_kind` to `who` and return an identifier by which its
+	/// status may later be checked.
+	fn pay(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> result::Result<Self::Id, Self::Error>;
+
+	/// Check how a payment has proceeded.
+	fn check_payment(id: Self::Id) -> PaymentStatus;
+
+	/// Ensure that a call to `pay` with the given parameters will be successful if done
+	/// immediately after this call.
+	///
+	/// ** Should be used for benchmarking only!!! **
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	);
+
+	/// Ensure that a call to `check_payment` with the given parameters will return either
+	/// `Success` or `Failure`.
+	///
+	/// ** Should be used for benchmarking only!!! **
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(id: Self::Id);
+}
+
+/// `Pay` implementation which synchronously transfers the `fungible` asset `F` from the account
+/// given by `A`.
+pub struct PayFromAccount<F, A, AccountId>(PhantomData<(F, A, AccountId)>);
+impl<
+	F: fungible::Transfer<AccountId> + fungible::Mutate<AccountId>,
+	A: Get<AccountId>,
+	AccountId,
+> Pay for PayFromAccount<F, A, AccountId> {
+	type Balance = <F as fungible::Inspect<AccountId>>::Balance;
+	type Beneficiary = AccountId;
+	type AssetKind = ();
+	type Id = ();
+	type Error = DispatchError;
+
+	fn pay(
+		who: &Self::Beneficiary,
+		_asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> result::Result<Self::Id, Self::Error> {
+		<F as fungible::Transfer<AccountId>>::transfer(&A::get(), who, amount, false).map(|_| ())
+	}
+
+	fn check_payment(_id: Self::Id) -> PaymentStatus {
+		PaymentStatus::Success
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(_: &Self::Beneficiary, _: Self::AssetKind, amount: Self::Balance) {
+		let minimum = <F as fungible::Inspect<AccountId>>::minimum_balance();
+		let _ = <F as fungible::Mutate<AccountId>>::mint_into(
+			&A::get(),
+			amount.saturating_add(minimum),
+		);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(_: Self::Id) {}
+}
+
+/// `Pay` implementation which synchronously transfers any asset of the `fungibles` set `F` from
+/// the account given by `A`.
+pub struct PayAssetFromAccount<F, A, AccountId>(PhantomData<(F, A, AccountId)>);
+impl<
+	F: fungibles::Transfer<AccountId> + fungibles::Mutate<AccountId>,
+	A: Get<AccountId>,
+	AccountId,
+> Pay for PayAssetFromAccount<F, A, AccountId> {
+	type Balance = <F as fungibles::Inspect<AccountId>>::Balance;
+	type Beneficiary = AccountId;
+	type AssetKind = <F as fungibles::Inspect<AccountId>>::AssetId;
+	type Id = ();
+	type Error = DispatchError;
+
+	fn pay(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> result::Result<Self::Id, Self::Error> {
+		<F as fungibles::Transfer<AccountId>>::transfer(asset_kind, &A::get(), who, amount, false)
+			.map(|_| ())
+	}
+
+	fn check_payment(_id: Self::Id) -> PaymentStatus {
+		PaymentStatus::Success
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(
+		_: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) {
+		let minimum = <F as fungibles::Inspect<AccountId>>::minimum_balance(asset_kind);
+		let _ = <F as fungibles::Mutate<AccountId>>::mint_into(
+			asset_kind,
+			&A::get(),
+			amount.saturating_add(minimum),
+		);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(_: Self::Id) {}
+}
+
+/// Converts a balance of some asset kind into a balance of another asset, typically the native
+/// one, e.g. to measure a spend against a limit expressed in the native asset.
+pub trait ConversionFromAssetBalance<AssetBalance, AssetKind, OutBalance> {
+	/// An error which could be returned by `from_asset_balance`.
+	type Error;
+
+	/// Convert `balance` of `asset_kind` into the output balance.
+	fn from_asset_balance(
+		balance: AssetBalance,
+		asset_kind: AssetKind,
+	) -> result::Result<OutBalance, Self::Error>;
+}
+
+/// `ConversionFromAssetBalance` implementation which treats one unit of any asset kind as worth
+/// exactly one unit of the output balance.
+pub struct UnityAssetBalanceConversion;
+impl<AssetBalance: Into<OutBalance>, AssetKind, OutBalance>
+	ConversionFromAssetBalance<AssetBalance, AssetKind, OutBalance> for UnityAssetBalanceConversion
+{
+	type Error = ();
+	fn from_asset_balance(
+		balance: AssetBalance,
+		_: AssetKind,
+	) -> result::Result<OutBalance, Self::Error> {
+		Ok(balance.into())
+	}
+}
+
 impl<T> IsType<T> for T {
 	fn from_ref(t: &T) -> &Self { t }
 	fn into_ref(&self) -> &T { self }
@@ -1640,6 +2563,88 @@ impl<T> IsType<T> for T {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn versioned_migration_works() {
+		use sp_std::cell::RefCell;
+
+		thread_local! {
+			static MIGRATED: RefCell<u32> = RefCell::new(0);
+		}
+
+		struct Pallet;
+		impl GetStorageVersion for Pallet {
+			fn storage_prefix() -> &'static [u8] { b"Pallet" }
+			fn current_storage_version() -> StorageVersion { StorageVersion::new(2) }
+		}
+
+		struct Migration;
+		impl OnRuntimeUpgrade for Migration {
+			fn on_runtime_upgrade() -> crate::weights::Weight {
+				MIGRATED.with(|m| *m.borrow_mut() += 1);
+				10
+			}
+		}
+
+		crate::parameter_types! {
+			const V0: u16 = 0;
+			const V1: u16 = 1;
+			const V2: u16 = 2;
+			const DbWeight: crate::weights::RuntimeDbWeight = crate::weights::RuntimeDbWeight {
+				read: 1,
+				write: 100,
+			};
+		}
+		type ZeroToOne = VersionedMigration<V0, V1, Migration, Pallet, DbWeight>;
+		type OneToTwo = VersionedMigration<V1, V2, Migration, Pallet, DbWeight>;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			assert_eq!(Pallet::on_chain_storage_version(), StorageVersion::new(0));
+
+			// Out of order migrations are skipped.
+			assert_eq!(OneToTwo::on_runtime_upgrade(), 1);
+			assert_eq!(MIGRATED.with(|m| *m.borrow()), 0);
+
+			assert_eq!(<(ZeroToOne, OneToTwo)>::on_runtime_upgrade(), 2 * (10 + 101));
+			assert_eq!(MIGRATED.with(|m| *m.borrow()), 2);
+			assert_eq!(Pallet::on_chain_storage_version(), Pallet::current_storage_version());
+
+			// Running them again is a noop.
+			assert_eq!(<(ZeroToOne, OneToTwo)>::on_runtime_upgrade(), 2);
+			assert_eq!(MIGRATED.with(|m| *m.borrow()), 2);
+		});
+	}
+
+	#[test]
+	fn on_idle_rotates_and_passes_remaining_weight() {
+		use sp_std::cell::RefCell;
+
+		thread_local! {
+			static CALLS: RefCell<Vec<(u32, crate::weights::Weight)>> = RefCell::new(vec![]);
+		}
+
+		struct Test<N>(PhantomData<N>);
+		impl<N: Get<u32>> OnIdle<u32> for Test<N> {
+			fn on_idle(_n: u32, remaining_weight: crate::weights::Weight) -> crate::weights::Weight {
+				CALLS.with(|c| c.borrow_mut().push((N::get(), remaining_weight)));
+				remaining_weight.min(10)
+			}
+		}
+
+		crate::parameter_types! {
+			const One: u32 = 1;
+			const Two: u32 = 2;
+		}
+		type Hooks = (Test<One>, Test<Two>);
+
+		assert_eq!(<Hooks as OnIdle<u32>>::on_idle(0, 15), 15);
+		assert_eq!(CALLS.with(|c| c.replace(vec![])), vec![(1, 15), (2, 5)]);
+
+		assert_eq!(<Hooks as OnIdle<u32>>::on_idle(1, 15), 15);
+		assert_eq!(CALLS.with(|c| c.replace(vec![])), vec![(2, 15), (1, 5)]);
+
+		assert_eq!(<() as OnIdle<u32>>::on_idle(1, 15), 0);
+	}
+
 	#[test]
 	fn on_initialize_and_on_runtime_upgrade_weight_merge_works() {
 		struct Test;
@@ -1657,4 +2662,53 @@ mod tests {
 		assert_eq!(<(Test, Test)>::on_initialize(0), 20);
 		assert_eq!(<(Test, Test)>::on_runtime_upgrade(), 40);
 	}
+
+	struct EnsureExactly<V>(PhantomData<V>);
+	impl<V: Get<u64>> EnsureOrigin<u64> for EnsureExactly<V> {
+		type Success = u64;
+		fn try_origin(o: u64) -> result::Result<u64, u64> {
+			if o == V::get() { Ok(o) } else { Err(o) }
+		}
+		#[cfg(feature = "runtime-benchmarks")]
+		fn successful_origin() -> u64 { V::get() }
+	}
+
+	struct Double;
+	impl Morph<u64> for Double {
+		type Outcome = u64;
+		fn morph(a: u64) -> u64 { a * 2 }
+	}
+
+	struct OnlyEven;
+	impl TryMorph<u64> for OnlyEven {
+		type Outcome = u64;
+		fn try_morph(a: u64) -> result::Result<u64, ()> {
+			if a % 2 == 0 { Ok(a) } else { Err(()) }
+		}
+	}
+
+	crate::parameter_types! {
+		const One: u64 = 1;
+		const Two: u64 = 2;
+	}
+
+	#[test]
+	fn origin_combinators_work() {
+		type OneOrTwo = EitherOf<EnsureExactly<One>, EnsureExactly<Two>>;
+		assert_eq!(OneOrTwo::try_origin(1), Ok(1));
+		assert_eq!(OneOrTwo::try_origin(2), Ok(2));
+		assert_eq!(OneOrTwo::try_origin(3), Err(3));
+
+		type Doubled = MapSuccess<OneOrTwo, Double>;
+		assert_eq!(Doubled::try_origin(1), Ok(2));
+		assert_eq!(Doubled::try_origin(3), Err(3));
+
+		type Identical = MapSuccess<OneOrTwo, sp_runtime::traits::Identity>;
+		assert_eq!(Identical::try_origin(2), Ok(2));
+
+		type Even = TryMapSuccess<OneOrTwo, OnlyEven>;
+		assert_eq!(Even::try_origin(1), Err(1));
+		assert_eq!(Even::try_origin(2), Ok(2));
+		assert_eq!(Even::try_origin(3), Err(3));
+	}
 }
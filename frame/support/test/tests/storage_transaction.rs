@@ -17,9 +17,12 @@
 
 use codec::{Encode, Decode, EncodeLike};
 use frame_support::{
-	StorageMap, StorageValue, storage::{with_transaction, TransactionOutcome::*},
+	assert_ok, assert_noop, transactional, StorageMap, StorageValue,
+	dispatch::{DispatchError, DispatchResult},
+	storage::{with_transaction, with_storage_layer, TransactionOutcome::*},
 };
 use sp_io::TestExternalities;
+use sp_std::result;
 
 pub trait Trait {
 	type Origin;
@@ -27,7 +30,20 @@ pub trait Trait {
 }
 
 frame_support::decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		#[weight = 0]
+		#[transactional]
+		fn value_commits(_origin, v: u32) {
+			Value::set(v);
+		}
+
+		#[weight = 0]
+		#[transactional]
+		fn value_rollbacks(_origin, v: u32) -> DispatchResult {
+			Value::set(v);
+			Err(DispatchError::Other("nah"))
+		}
+	}
 }
 
 frame_support::decl_storage!{
@@ -157,3 +173,62 @@ fn storage_transaction_commit_then_rollback() {
 		assert_eq!(Map::get("val3"), 0);
 	});
 }
+
+#[test]
+fn storage_layer_commits_on_ok_and_rollbacks_on_err() {
+	TestExternalities::default().execute_with(|| {
+		assert_ok!(with_storage_layer(|| -> result::Result<(), ()> {
+			Value::set(1);
+			Ok(())
+		}));
+		assert_eq!(Value::get(), 1);
+
+		assert_eq!(with_storage_layer(|| {
+			Value::set(2);
+			Map::insert("val1", 2);
+			Err::<(), _>("nah")
+		}), Err("nah"));
+		assert_eq!(Value::get(), 1);
+		assert!(!Map::contains_key("val1"));
+	});
+}
+
+#[test]
+fn transactional_annotation() {
+	#[transactional]
+	fn value_commits(v: u32) -> result::Result<u32, &'static str> {
+		Value::set(v);
+		Ok(v)
+	}
+
+	#[transactional]
+	fn value_rollbacks(v: u32) -> result::Result<u32, &'static str> {
+		Value::set(v);
+		Err("nah")
+	}
+
+	TestExternalities::default().execute_with(|| {
+		assert_ok!(value_commits(2), 2);
+		assert_eq!(Value::get(), 2);
+
+		assert_noop!(value_rollbacks(3), "nah");
+	});
+}
+
+#[test]
+fn transactional_annotation_in_decl_module() {
+	TestExternalities::default().execute_with(|| {
+		let origin = 0;
+		assert_ok!(<Module<Runtime>>::value_commits(origin, 2));
+		assert_eq!(Value::get(), 2);
+
+		assert_noop!(<Module<Runtime>>::value_rollbacks(origin, 3), "nah");
+	});
+}
+
+pub struct Runtime;
+
+impl Trait for Runtime {
+	type Origin = u32;
+	type BlockNumber = u32;
+}
@@ -45,6 +45,13 @@ benchmarks! {
 		let caller = account("caller", 0, SEED);
 	}: _(RawOrigin::Signed(caller), remark_message)
 
+	remark_with_event {
+		// # of Bytes
+		let b in 0 .. 16_384;
+		let remark_message = vec![1; b as usize];
+		let caller = account("caller", 0, SEED);
+	}: _(RawOrigin::Signed(caller), remark_message)
+
 	set_heap_pages {
 		// Heap page size
 		let i in 0 .. u32::max_value();
@@ -145,7 +152,9 @@ benchmarks! {
 		let caller: T::AccountId = account("caller", 0, SEED);
 		let account_info = AccountInfo::<T::Index, T::AccountData> {
 			nonce: n.into(),
-			refcount: 0,
+			consumers: 0,
+			providers: 0,
+			sufficients: 0,
 			data: T::AccountData::default()
 		};
 		frame_system::Account::<T>::insert(&caller, account_info);
@@ -168,6 +177,7 @@ mod tests {
 	fn test_benchmarks() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_remark::<Test>());
+			assert_ok!(test_benchmark_remark_with_event::<Test>());
 			assert_ok!(test_benchmark_set_heap_pages::<Test>());
 			assert_ok!(test_benchmark_set_code_without_checks::<Test>());
 			assert_ok!(test_benchmark_set_changes_trie_config::<Test>());
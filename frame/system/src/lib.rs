@@ -160,6 +160,7 @@ pub fn extrinsics_data_root<H: Hash>(xts: Vec<Vec<u8>>) -> H::Output {
 
 pub trait WeightInfo {
 	fn remark(b: u32, ) -> Weight;
+	fn remark_with_event(b: u32, ) -> Weight;
 	fn set_heap_pages(i: u32, ) -> Weight;
 	fn set_code_without_checks(b: u32, ) -> Weight;
 	fn set_changes_trie_config(d: u32, ) -> Weight;
@@ -171,6 +172,7 @@ pub trait WeightInfo {
 
 impl WeightInfo for () {
 	fn remark(_b: u32, ) -> Weight { 1_000_000_000 }
+	fn remark_with_event(_b: u32, ) -> Weight { 1_000_000_000 }
 	fn set_heap_pages(_i: u32, ) -> Weight { 1_000_000_000 }
 	fn set_code_without_checks(_b: u32, ) -> Weight { 1_000_000_000 }
 	fn set_changes_trie_config(_d: u32, ) -> Weight { 1_000_000_000 }
@@ -365,7 +367,7 @@ fn hash69<T: AsMut<[u8]> + Default>() -> T {
 type EventIndex = u32;
 
 /// Type used to encode the number of references an account has.
-pub type RefCount = u8;
+pub type RefCount = u32;
 
 /// Information of an account.
 #[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode)]
@@ -374,7 +376,13 @@ pub struct AccountInfo<Index, AccountData> {
 	pub nonce: Index,
 	/// The number of other modules that currently depend on this account's existence. The account
 	/// cannot be reaped until this is zero.
-	pub refcount: RefCount,
+	pub consumers: RefCount,
+	/// The number of other modules that allow this account to exist. The account may not be reaped
+	/// until this and `sufficients` are both zero.
+	pub providers: RefCount,
+	/// The number of modules that allow this account to exist for their own purposes only. The
+	/// account may not be reaped until this and `providers` are both zero.
+	pub sufficients: RefCount,
 	/// The additional data that belongs to this account. Used to store the balance(s) in a lot of
 	/// chains.
 	pub data: AccountData,
@@ -469,6 +477,10 @@ decl_storage! {
 
 		/// The execution phase of the block.
 		ExecutionPhase: Option<Phase>;
+
+		/// True if the account information has been migrated to the provider, consumer and
+		/// sufficient reference counts. False (default) if not.
+		UpgradedToTripleRefCount build(|_| true): bool;
 	}
 	add_extra_genesis {
 		config(changes_trie_config): Option<ChangesTrieConfiguration>;
@@ -493,7 +505,7 @@ decl_storage! {
 
 decl_event!(
 	/// Event for the System module.
-	pub enum Event<T> where AccountId = <T as Trait>::AccountId {
+	pub enum Event<T> where AccountId = <T as Trait>::AccountId, Hash = <T as Trait>::Hash {
 		/// An extrinsic completed successfully.
 		ExtrinsicSuccess(DispatchInfo),
 		/// An extrinsic failed.
@@ -504,6 +516,14 @@ decl_event!(
 		NewAccount(AccountId),
 		/// An account was reaped.
 		KilledAccount(AccountId),
+		/// An on-chain remark happened, with the hash of the remark.
+		Remarked(AccountId, Hash),
+		/// Some items of storage were set by root, with the keys that were written.
+		StorageSet(Vec<Key>),
+		/// Some items of storage were killed by root, with the keys that were deleted.
+		StorageKilled(Vec<Key>),
+		/// Items with the given prefix were killed by root, with the number of keys removed.
+		PrefixKilled(Key, u32),
 	}
 );
 
@@ -549,6 +569,24 @@ decl_module! {
 		/// The maximum length of a block (in bytes).
 		const MaximumBlockLength: u32 = T::MaximumBlockLength::get();
 
+		fn on_runtime_upgrade() -> Weight {
+			if !UpgradedToTripleRefCount::get() {
+				Account::<T>::translate::<(T::Index, u8, T::AccountData), _>(
+					|_key, (nonce, rc, data)| Some(AccountInfo {
+						nonce,
+						consumers: rc as RefCount,
+						providers: 1,
+						sufficients: 0,
+						data,
+					})
+				);
+				UpgradedToTripleRefCount::put(true);
+				T::MaximumBlockWeight::get()
+			} else {
+				0
+			}
+		}
+
 		/// A dispatch that will fill the block weight up to the given ratio.
 		// TODO: This should only be available for testing, rather than in general usage, but
 		// that's not possible at present (since it's within the decl_module macro).
@@ -569,6 +607,19 @@ decl_module! {
 			ensure_signed(origin)?;
 		}
 
+		/// Make some on-chain remark and emit an event with its hash.
+		///
+		/// # <weight>
+		/// - `O(b)` where b is the length of the remark.
+		/// - 1 event.
+		/// # </weight>
+		#[weight = 700_000u64.saturating_add((remark.len() as Weight).saturating_mul(1_000))]
+		fn remark_with_event(origin, remark: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+			let hash = T::Hashing::hash(&remark[..]);
+			Self::deposit_event(RawEvent::Remarked(who, hash));
+		}
+
 		/// Set the number of pages in the WebAssembly environment's heap.
 		///
 		/// # <weight>
@@ -650,6 +701,7 @@ decl_module! {
 		/// - `I` storage writes (`O(1)`).
 		/// - Base Weight: 0.568 * i µs
 		/// - Writes: Number of items
+		/// - 1 event.
 		/// # </weight>
 		#[weight = (
 			T::DbWeight::get().writes(items.len() as Weight)
@@ -661,6 +713,8 @@ decl_module! {
 			for i in &items {
 				storage::unhashed::put_raw(&i.0, &i.1);
 			}
+			let keys = items.into_iter().map(|(key, _)| key).collect();
+			Self::deposit_event(RawEvent::StorageSet(keys));
 		}
 
 		/// Kill some items from storage.
@@ -670,6 +724,7 @@ decl_module! {
 		/// - `I` storage deletions.
 		/// - Base Weight: .378 * i µs
 		/// - Writes: Number of items
+		/// - 1 event.
 		/// # </weight>
 		#[weight = (
 			T::DbWeight::get().writes(keys.len() as Weight)
@@ -681,27 +736,31 @@ decl_module! {
 			for key in &keys {
 				storage::unhashed::kill(&key);
 			}
+			Self::deposit_event(RawEvent::StorageKilled(keys));
 		}
 
-		/// Kill all storage items with a key that starts with the given prefix.
+		/// Kill at most `subkeys` storage items with a key that starts with the given prefix.
 		///
 		/// **NOTE:** We rely on the Root origin to provide us the number of subkeys under
-		/// the prefix we are removing to accurately calculate the weight of this function.
+		/// the prefix we are removing to accurately calculate the weight of this function. Any
+		/// items beyond this number are left in storage.
 		///
 		/// # <weight>
 		/// - `O(P)` where `P` amount of keys with prefix `prefix`
 		/// - `P` storage deletions.
 		/// - Base Weight: 0.834 * P µs
 		/// - Writes: Number of subkeys + 1
+		/// - 1 event.
 		/// # </weight>
 		#[weight = (
-			T::DbWeight::get().writes(Weight::from(*_subkeys) + 1)
-				.saturating_add((Weight::from(*_subkeys) + 1).saturating_mul(850_000)),
+			T::DbWeight::get().writes(Weight::from(*subkeys) + 1)
+				.saturating_add((Weight::from(*subkeys) + 1).saturating_mul(850_000)),
 			DispatchClass::Operational,
 		)]
-		fn kill_prefix(origin, prefix: Key, _subkeys: u32) {
+		fn kill_prefix(origin, prefix: Key, subkeys: u32) {
 			ensure_root(origin)?;
-			storage::unhashed::kill_prefix(&prefix);
+			let outcome = storage::unhashed::clear_prefix(&prefix, subkeys, None);
+			Self::deposit_event(RawEvent::PrefixKilled(prefix, outcome.removed));
 		}
 
 		/// Kill the sending account, assuming there are no references outstanding and the composite
@@ -718,7 +777,7 @@ decl_module! {
 		pub fn suicide(origin) {
 			let who = ensure_signed(origin)?;
 			let account = Account::<T>::get(&who);
-			ensure!(account.refcount == 0, Error::<T>::NonZeroRefCount);
+			ensure!(account.consumers == 0, Error::<T>::NonZeroRefCount);
 			ensure!(account.data == T::AccountData::default(), Error::<T>::NonDefaultComposite);
 			Self::kill_account(&who);
 		}
@@ -902,6 +961,38 @@ pub enum RefStatus {
 	Unreferenced,
 }
 
+/// Some resultant status relevant to incrementing a provider or sufficient reference.
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub enum IncRefStatus {
+	/// Account was created.
+	Created,
+	/// Account already existed.
+	Existed,
+}
+
+/// Some resultant status relevant to decrementing a provider or sufficient reference.
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub enum DecRefStatus {
+	/// Account was destroyed.
+	Reaped,
+	/// Account still exists.
+	Exists,
+}
+
+/// Some resultant status relevant to decrementing a provider reference.
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub enum DecRefError {
+	/// Account cannot have the last provider reference removed while there is a consumer.
+	ConsumerRemaining,
+}
+
+/// Some resultant status relevant to incrementing a consumer reference.
+#[derive(Eq, PartialEq, RuntimeDebug)]
+pub enum IncRefError {
+	/// Account cannot introduce a consumer while there are no providers.
+	NoProviders,
+}
+
 impl<T: Trait> Module<T> {
 	/// Deposits an event into this block's event record.
 	pub fn deposit_event(event: impl Into<T::Event>) {
@@ -909,24 +1000,190 @@ impl<T: Trait> Module<T> {
 	}
 
 	/// Increment the reference counter on an account.
+	///
+	/// This increments the consumer counter but, unlike `inc_consumers`, does not require the
+	/// account to have a provider.
 	pub fn inc_ref(who: &T::AccountId) {
-		Account::<T>::mutate(who, |a| a.refcount = a.refcount.saturating_add(1));
+		Account::<T>::mutate(who, |a| a.consumers = a.consumers.saturating_add(1));
 	}
 
 	/// Decrement the reference counter on an account. This *MUST* only be done once for every time
 	/// you called `inc_ref` on `who`.
 	pub fn dec_ref(who: &T::AccountId) {
-		Account::<T>::mutate(who, |a| a.refcount = a.refcount.saturating_sub(1));
+		Self::dec_consumers(who)
 	}
 
 	/// The number of outstanding references for the account `who`.
 	pub fn refs(who: &T::AccountId) -> RefCount {
-		Account::<T>::get(who).refcount
+		Self::consumers(who)
 	}
 
-	/// True if the account has no outstanding references.
+	/// True if the account's last provider may be removed, i.e. it has no outstanding consumer
+	/// references or another provider keeps it alive.
 	pub fn allow_death(who: &T::AccountId) -> bool {
-		Account::<T>::get(who).refcount == 0
+		Self::can_dec_provider(who)
+	}
+
+	/// Increment the provider reference counter on an account, creating it if needed.
+	pub fn inc_providers(who: &T::AccountId) -> IncRefStatus {
+		let status = Account::<T>::mutate(who, |a| {
+			let status = if a.providers == 0 && a.sufficients == 0 {
+				IncRefStatus::Created
+			} else {
+				IncRefStatus::Existed
+			};
+			a.providers = a.providers.saturating_add(1);
+			status
+		});
+		if status == IncRefStatus::Created {
+			Self::on_created_account(who.clone());
+		}
+		status
+	}
+
+	/// Decrement the provider reference counter on an account, reaping it if this was the last
+	/// reference.
+	///
+	/// The last provider of an account with consumers can't be removed; this returns
+	/// `DecRefError::ConsumerRemaining` and leaves the account untouched.
+	pub fn dec_providers(who: &T::AccountId) -> Result<DecRefStatus, DecRefError> {
+		let status = Account::<T>::try_mutate_exists(who, |maybe_account| {
+			let mut account = match maybe_account.take() {
+				Some(account) => account,
+				None => {
+					debug::error!(
+						target: "system",
+						"Logic error: Account already dead when reducing provider"
+					);
+					return Ok(DecRefStatus::Reaped)
+				}
+			};
+			if account.providers == 0 {
+				debug::error!(
+					target: "system",
+					"Logic error: Unexpected underflow in reducing provider"
+				);
+				account.providers = 1;
+			}
+			match (account.providers, account.consumers, account.sufficients) {
+				(1, 0, 0) => Ok(DecRefStatus::Reaped),
+				(1, c, _) if c > 0 => Err(DecRefError::ConsumerRemaining),
+				(p, _, _) => {
+					account.providers = p - 1;
+					*maybe_account = Some(account);
+					Ok(DecRefStatus::Exists)
+				}
+			}
+		})?;
+		if status == DecRefStatus::Reaped {
+			Self::on_killed_account(who.clone());
+		}
+		Ok(status)
+	}
+
+	/// Increment the self-sufficient reference counter on an account, creating it if needed.
+	pub fn inc_sufficients(who: &T::AccountId) -> IncRefStatus {
+		let status = Account::<T>::mutate(who, |a| {
+			let status = if a.providers == 0 && a.sufficients == 0 {
+				IncRefStatus::Created
+			} else {
+				IncRefStatus::Existed
+			};
+			a.sufficients = a.sufficients.saturating_add(1);
+			status
+		});
+		if status == IncRefStatus::Created {
+			Self::on_created_account(who.clone());
+		}
+		status
+	}
+
+	/// Decrement the self-sufficient reference counter on an account, reaping it if there are no
+	/// references left.
+	pub fn dec_sufficients(who: &T::AccountId) -> DecRefStatus {
+		let status = Account::<T>::mutate_exists(who, |maybe_account| {
+			let mut account = match maybe_account.take() {
+				Some(account) => account,
+				None => {
+					debug::error!(
+						target: "system",
+						"Logic error: Account already dead when reducing sufficients"
+					);
+					return DecRefStatus::Reaped
+				}
+			};
+			if account.sufficients == 0 {
+				debug::error!(
+					target: "system",
+					"Logic error: Unexpected underflow in reducing sufficients"
+				);
+				account.sufficients = 1;
+			}
+			match (account.sufficients, account.providers) {
+				(1, 0) => DecRefStatus::Reaped,
+				(s, _) => {
+					account.sufficients = s - 1;
+					*maybe_account = Some(account);
+					DecRefStatus::Exists
+				}
+			}
+		});
+		if status == DecRefStatus::Reaped {
+			Self::on_killed_account(who.clone());
+		}
+		status
+	}
+
+	/// Increment the consumer reference counter on an account.
+	///
+	/// Fails with `IncRefError::NoProviders` if the account has no providers, since nothing would
+	/// keep it alive for the consumer.
+	pub fn inc_consumers(who: &T::AccountId) -> Result<(), IncRefError> {
+		Account::<T>::try_mutate(who, |a| if a.providers > 0 {
+			a.consumers = a.consumers.saturating_add(1);
+			Ok(())
+		} else {
+			Err(IncRefError::NoProviders)
+		})
+	}
+
+	/// Decrement the consumer reference counter on an account. This *MUST* only be done once for
+	/// every time you successfully called `inc_consumers` on `who`.
+	pub fn dec_consumers(who: &T::AccountId) {
+		Account::<T>::mutate(who, |a| if a.consumers > 0 {
+			a.consumers -= 1;
+		} else {
+			debug::error!(
+				target: "system",
+				"Logic error: Unexpected underflow in reducing consumer"
+			);
+		})
+	}
+
+	/// The number of outstanding consumer references for the account `who`.
+	pub fn consumers(who: &T::AccountId) -> RefCount {
+		Account::<T>::get(who).consumers
+	}
+
+	/// The number of outstanding provider references for the account `who`.
+	pub fn providers(who: &T::AccountId) -> RefCount {
+		Account::<T>::get(who).providers
+	}
+
+	/// The number of outstanding self-sufficient references for the account `who`.
+	pub fn sufficients(who: &T::AccountId) -> RefCount {
+		Account::<T>::get(who).sufficients
+	}
+
+	/// True if the account has some outstanding consumer references.
+	pub fn is_provider_required(who: &T::AccountId) -> bool {
+		Account::<T>::get(who).consumers != 0
+	}
+
+	/// True if the account has no outstanding consumer references or more than one provider.
+	pub fn can_dec_provider(who: &T::AccountId) -> bool {
+		let a = Account::<T>::get(who);
+		a.consumers == 0 || a.providers > 1
 	}
 
 	/// Deposits an event into this block's event record adding this event
@@ -1222,7 +1479,7 @@ impl<T: Trait> Module<T> {
 	fn kill_account(who: &T::AccountId) {
 		if Account::<T>::contains_key(who) {
 			let account = Account::<T>::take(who);
-			if account.refcount > 0 {
+			if account.consumers > 0 {
 				debug::debug!(
 					target: "system",
 					"WARNING: Referenced account deleted. This is probably a bug."
@@ -1259,19 +1516,23 @@ impl<T: Trait> Module<T> {
 	}
 }
 
-/// Event handler which calls on_created_account when it happens.
+/// Event handler which adds a provider reference, calling on_created_account if the account is
+/// being created.
 pub struct CallOnCreatedAccount<T>(PhantomData<T>);
 impl<T: Trait> Happened<T::AccountId> for CallOnCreatedAccount<T> {
 	fn happened(who: &T::AccountId) {
-		Module::<T>::on_created_account(who.clone());
+		Module::<T>::inc_providers(who);
 	}
 }
 
-/// Event handler which calls kill_account when it happens.
+/// Event handler which removes a provider reference, killing the account if it was the last one
+/// or consumers are still left on it.
 pub struct CallKillAccount<T>(PhantomData<T>);
 impl<T: Trait> Happened<T::AccountId> for CallKillAccount<T> {
 	fn happened(who: &T::AccountId) {
-		Module::<T>::kill_account(who)
+		if let Err(DecRefError::ConsumerRemaining) = Module::<T>::dec_providers(who) {
+			Module::<T>::kill_account(who)
+		}
 	}
 }
 
@@ -1284,9 +1545,10 @@ impl<T: Trait> BlockNumberProvider for Module<T>
 	}
 }
 
-// Implement StoredMap for a simple single-item, kill-account-on-remove system. This works fine for
-// storing a single item which is required to not be empty/default for the account to exist.
-// Anything more complex will need more sophisticated logic.
+// Implement StoredMap for a simple single-item, kill-account-on-remove system. The account data
+// provides for the account: a non-default value holds a provider reference, which is dropped once
+// the value becomes default or is removed. Anything more complex will need more sophisticated
+// logic.
 impl<T: Trait> StoredMap<T::AccountId, T::AccountData> for Module<T> {
 	fn get(k: &T::AccountId) -> T::AccountData {
 		Account::<T>::get(k).data
@@ -1295,51 +1557,52 @@ impl<T: Trait> StoredMap<T::AccountId, T::AccountData> for Module<T> {
 		Account::<T>::contains_key(k)
 	}
 	fn insert(k: &T::AccountId, data: T::AccountData) {
-		let existed = Account::<T>::contains_key(k);
-		Account::<T>::mutate(k, |a| a.data = data);
-		if !existed {
-			Self::on_created_account(k.clone());
-		}
+		Self::mutate_exists(k, |maybe_data| *maybe_data = Some(data))
 	}
 	fn remove(k: &T::AccountId) {
-		Self::kill_account(k)
+		Self::mutate_exists(k, |maybe_data| *maybe_data = None)
 	}
 	fn mutate<R>(k: &T::AccountId, f: impl FnOnce(&mut T::AccountData) -> R) -> R {
-		let existed = Account::<T>::contains_key(k);
-		let r = Account::<T>::mutate(k, |a| f(&mut a.data));
-		if !existed {
-			Self::on_created_account(k.clone());
-		}
-		r
+		Self::mutate_exists(k, |maybe_data| {
+			let mut data = maybe_data.take().unwrap_or_default();
+			let r = f(&mut data);
+			*maybe_data = Some(data);
+			r
+		})
 	}
 	fn mutate_exists<R>(k: &T::AccountId, f: impl FnOnce(&mut Option<T::AccountData>) -> R) -> R {
 		Self::try_mutate_exists(k, |x| -> Result<R, Infallible> { Ok(f(x)) }).expect("Infallible; qed")
 	}
 	fn try_mutate_exists<R, E>(k: &T::AccountId, f: impl FnOnce(&mut Option<T::AccountData>) -> Result<R, E>) -> Result<R, E> {
-		Account::<T>::try_mutate_exists(k, |maybe_value| {
-			let existed = maybe_value.is_some();
-			let (maybe_prefix, mut maybe_data) = split_inner(
-				maybe_value.take(),
-				|account| ((account.nonce, account.refcount), account.data)
-			);
-			f(&mut maybe_data).map(|result| {
-				*maybe_value = maybe_data.map(|data| {
-					let (nonce, refcount) = maybe_prefix.unwrap_or_default();
-					AccountInfo { nonce, refcount, data }
-				});
-				(existed, maybe_value.is_some(), result)
-			})
-		}).map(|(existed, exists, v)| {
-			if !existed && exists {
-				Self::on_created_account(k.clone());
-			} else if existed && !exists {
-				Self::on_killed_account(k.clone());
+		let account = Account::<T>::get(k);
+		let was_providing = is_providing(&account.data);
+		let mut some_data = if was_providing { Some(account.data) } else { None };
+		let result = f(&mut some_data)?;
+		let is_providing = some_data.as_ref().map_or(false, is_providing);
+		if !was_providing && is_providing {
+			Self::inc_providers(k);
+		} else if was_providing && !is_providing {
+			match Self::dec_providers(k) {
+				Ok(DecRefStatus::Reaped) => return Ok(result),
+				Ok(DecRefStatus::Exists) => (),
+				Err(DecRefError::ConsumerRemaining) => {
+					// The data is gone, so nothing keeps the account alive anymore.
+					Self::kill_account(k);
+					return Ok(result)
+				}
 			}
-			v
-		})
+		} else if !was_providing && !is_providing {
+			return Ok(result)
+		}
+		Account::<T>::mutate(k, |a| a.data = some_data.unwrap_or_default());
+		Ok(result)
 	}
 }
 
+fn is_providing<T: Default + Eq>(d: &T) -> bool {
+	d != &T::default()
+}
+
 /// Split an `option` into two constituent options, as defined by a `splitter` function.
 pub fn split_inner<T, R, S>(option: Option<T>, splitter: impl FnOnce(T) -> (R, S))
 	-> (Option<R>, Option<S>)
@@ -49,6 +49,46 @@ fn stored_map_works() {
 	});
 }
 
+#[test]
+fn provider_ref_handover_to_self_sufficient_ref_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(System::inc_providers(&0), IncRefStatus::Created);
+		assert_eq!(System::inc_consumers(&0), Ok(()));
+		// The last provider can't be removed while there is a consumer.
+		assert_eq!(System::dec_providers(&0), Err(DecRefError::ConsumerRemaining));
+
+		assert_eq!(System::inc_sufficients(&0), IncRefStatus::Existed);
+		System::dec_consumers(&0);
+		assert_eq!(System::dec_providers(&0), Ok(DecRefStatus::Exists));
+		assert_eq!(System::providers(&0), 0);
+		assert!(KILLED.with(|r| r.borrow().is_empty()));
+
+		assert_eq!(System::dec_sufficients(&0), DecRefStatus::Reaped);
+		assert_eq!(KILLED.with(|r| r.borrow().clone()), vec![0u64]);
+	});
+}
+
+#[test]
+fn consumer_requires_provider() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(System::inc_consumers(&0), Err(IncRefError::NoProviders));
+
+		System::insert(&0, 42);
+		assert_eq!(System::providers(&0), 1);
+		assert_eq!(System::inc_consumers(&0), Ok(()));
+		assert!(!System::can_dec_provider(&0));
+
+		assert_eq!(System::inc_providers(&0), IncRefStatus::Existed);
+		assert!(System::can_dec_provider(&0));
+
+		// Removing the data drops its provider while the other one keeps the account alive.
+		System::remove(&0);
+		assert_eq!(System::providers(&0), 1);
+		assert_eq!(System::consumers(&0), 1);
+		assert!(KILLED.with(|r| r.borrow().is_empty()));
+	});
+}
+
 #[test]
 fn deposit_event_should_work() {
 	new_test_ext().execute_with(|| {
@@ -127,6 +167,24 @@ fn deposit_event_should_work() {
 	});
 }
 
+#[test]
+fn remark_with_event_emits_remark_hash() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let remark = b"remark".to_vec();
+		System::remark_with_event(RawOrigin::Signed(1).into(), remark.clone()).unwrap();
+
+		assert_eq!(
+			System::events(),
+			vec![EventRecord {
+				phase: Phase::Initialization,
+				event: SysEvent::Remarked(1, <Test as Trait>::Hashing::hash(&remark)),
+				topics: vec![],
+			}],
+		);
+	});
+}
+
 #[test]
 fn deposit_event_uses_actual_weight() {
 	new_test_ext().execute_with(|| {
@@ -398,6 +456,31 @@ fn runtime_upgraded_with_set_storage() {
 	});
 }
 
+#[test]
+fn root_storage_calls_emit_touched_keys() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		System::set_storage(
+			RawOrigin::Root.into(),
+			vec![(b"ab".to_vec(), vec![1]), (b"ac".to_vec(), vec![2]), (b"b".to_vec(), vec![3])],
+		).unwrap();
+		System::kill_storage(RawOrigin::Root.into(), vec![b"b".to_vec()]).unwrap();
+		System::kill_prefix(RawOrigin::Root.into(), b"a".to_vec(), 1).unwrap();
+
+		assert_eq!(storage::unhashed::get_raw(b"b"), None);
+		assert_eq!(storage::unhashed::get_raw(b"ab"), None);
+		assert_eq!(storage::unhashed::get_raw(b"ac"), Some(vec![2]));
+		assert_eq!(
+			System::events().into_iter().map(|e| e.event).collect::<Vec<_>>(),
+			vec![
+				SysEvent::StorageSet(vec![b"ab".to_vec(), b"ac".to_vec(), b"b".to_vec()]),
+				SysEvent::StorageKilled(vec![b"b".to_vec()]),
+				SysEvent::PrefixKilled(b"a".to_vec(), 1),
+			],
+		);
+	});
+}
+
 #[test]
 fn events_not_emitted_during_genesis() {
 	new_test_ext().execute_with(|| {
@@ -0,0 +1,226 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Asset Transaction Payment Module
+//!
+//! This module provides a signed extension that lets the sender of a transaction choose to pay
+//! its fee in an asset of the assets module instead of the native currency.
+//!
+//! The fee is computed by the transaction payment module in the native currency, exactly as for
+//! [`ChargeTransactionPayment`](../pallet_transaction_payment/struct.ChargeTransactionPayment.html).
+//! When an asset is selected, the fee (including the tip) is handed to
+//! [`Trait::OnChargeAssetTransaction`], which converts it into the asset, e.g. using the rate of
+//! a liquidity pool or an oracle, and withdraws it from the sender. After dispatch the fee is
+//! corrected using the actual weight and the difference is refunded, just like on the native
+//! path. When no asset is selected the native path is used unchanged.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Encode, Decode};
+use frame_support::{
+	dispatch::DispatchResult,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use sp_runtime::{
+	FixedPointOperand,
+	transaction_validity::{
+		TransactionPriority, ValidTransaction, TransactionValidityError, TransactionValidity,
+	},
+	traits::{
+		Zero, SignedExtension, SaturatedConversion, Dispatchable, DispatchInfoOf,
+		PostDispatchInfoOf,
+	},
+};
+use pallet_transaction_payment::ChargeTransactionPayment;
+
+mod payment;
+#[cfg(test)]
+mod tests;
+
+pub use payment::{OnChargeAssetTransaction, FeeConversion, AssetsAdapter};
+
+/// The native balance type of the transaction payment module.
+pub type BalanceOf<T> = pallet_transaction_payment::BalanceOf<T>;
+/// The asset identifier type of the assets module.
+pub type AssetIdOf<T> = <T as pallet_assets::Trait>::AssetId;
+/// The withdrawn fee as tracked by the configured `OnChargeAssetTransaction`.
+pub type LiquidityInfoOf<T> =
+	<<T as Trait>::OnChargeAssetTransaction as OnChargeAssetTransaction<T>>::LiquidityInfo;
+type NativePreOf<T> = <ChargeTransactionPayment<T> as SignedExtension>::Pre;
+
+/// The module configuration trait.
+pub trait Trait: pallet_transaction_payment::Trait + pallet_assets::Trait {
+	/// The logic converting native fees into an asset and charging them.
+	type OnChargeAssetTransaction: OnChargeAssetTransaction<Self>;
+}
+
+/// The fee withdrawn before dispatch, to be corrected afterwards.
+pub enum InitialPayment<T: Trait> {
+	/// Nothing was withdrawn.
+	Nothing,
+	/// The fee was withdrawn in the native currency.
+	Native(NativePreOf<T>),
+	/// The fee was withdrawn in an asset.
+	Asset(AssetIdOf<T>, LiquidityInfoOf<T>),
+}
+
+impl<T: Trait> Default for InitialPayment<T> {
+	fn default() -> Self {
+		InitialPayment::Nothing
+	}
+}
+
+/// Require the transactor pay for themselves, in the native currency or in the asset
+/// `asset_id`, and maybe include a tip to gain additional priority in the queue.
+///
+/// The tip is denominated in the native currency and converted along with the fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeAssetTxPayment<T: Trait + Send + Sync> {
+	#[codec(compact)]
+	tip: BalanceOf<T>,
+	asset_id: Option<AssetIdOf<T>>,
+}
+
+impl<T: Trait + Send + Sync> ChargeAssetTxPayment<T> where
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync + FixedPointOperand,
+{
+	/// utility constructor. Used only in client/factory code.
+	pub fn from(tip: BalanceOf<T>, asset_id: Option<AssetIdOf<T>>) -> Self {
+		Self { tip, asset_id }
+	}
+
+	fn withdraw_fee(
+		&self,
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		info: &DispatchInfoOf<T::Call>,
+		len: usize,
+	) -> Result<(BalanceOf<T>, Option<LiquidityInfoOf<T>>), TransactionValidityError> {
+		let fee = pallet_transaction_payment::Module::<T>::compute_fee(len as u32, info, self.tip);
+
+		// Only mess with balances if fee is not zero.
+		if fee.is_zero() {
+			return Ok((fee, None));
+		}
+
+		T::OnChargeAssetTransaction::withdraw_fee(who, asset_id, fee, self.tip)
+			.map(|liquidity| (fee, Some(liquidity)))
+	}
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeAssetTxPayment<{:?}, {:?}>", self.tip, self.asset_id)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for ChargeAssetTxPayment<T> where
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	AssetIdOf<T>: Send + Sync,
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+	type AccountId = T::AccountId;
+	type Call = T::Call;
+	type AdditionalSigned = ();
+	type Pre = (BalanceOf<T>, Self::AccountId, InitialPayment<T>, BalanceOf<T>);
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		let asset_id = match self.asset_id {
+			Some(asset_id) => asset_id,
+			None => return ChargeTransactionPayment::<T>::from(self.tip)
+				.validate(who, call, info, len),
+		};
+		let (fee, _) = self.withdraw_fee(who, asset_id, info, len)?;
+
+		let mut r = ValidTransaction::default();
+		// Same as for the native currency, the priority is the fee in the native currency.
+		r.priority = fee.saturated_into::<TransactionPriority>();
+		Ok(r)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let asset_id = match self.asset_id {
+			Some(asset_id) => asset_id,
+			None => {
+				let pre = ChargeTransactionPayment::<T>::from(self.tip)
+					.pre_dispatch(who, call, info, len)?;
+				return Ok((self.tip, who.clone(), InitialPayment::Native(pre), Zero::zero()));
+			},
+		};
+		let (fee, liquidity) = self.withdraw_fee(who, asset_id, info, len)?;
+		let initial_payment = match liquidity {
+			Some(liquidity) => InitialPayment::Asset(asset_id, liquidity),
+			None => InitialPayment::Nothing,
+		};
+		Ok((self.tip, who.clone(), initial_payment, fee))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let (tip, who, initial_payment, _fee) = pre;
+		match initial_payment {
+			InitialPayment::Native(pre) => ChargeTransactionPayment::<T>::post_dispatch(
+				pre,
+				info,
+				post_info,
+				len,
+				result,
+			),
+			InitialPayment::Asset(asset_id, already_withdrawn) => {
+				let actual_fee = pallet_transaction_payment::Module::<T>::compute_actual_fee(
+					len as u32,
+					info,
+					post_info,
+					tip,
+				);
+				T::OnChargeAssetTransaction::correct_and_deposit_fee(
+					&who,
+					asset_id,
+					actual_fee,
+					tip,
+					already_withdrawn,
+				)
+			},
+			InitialPayment::Nothing => Ok(()),
+		}
+	}
+}
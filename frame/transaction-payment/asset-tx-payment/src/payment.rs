@@ -0,0 +1,117 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Charging of transaction fees in assets.
+
+use sp_std::marker::PhantomData;
+use frame_support::traits::Get;
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	traits::{Zero, Saturating},
+};
+use crate::{Trait, BalanceOf, AssetIdOf};
+
+type AssetBalanceOf<T> = <T as pallet_assets::Trait>::Balance;
+
+/// Handle withdrawing and refunding transaction fees in assets.
+pub trait OnChargeAssetTransaction<T: Trait> {
+	/// The information kept between withdrawing the fee and correcting it, usually the amount
+	/// of the asset that was withdrawn.
+	type LiquidityInfo;
+
+	/// Withdraw the `fee` of a transaction from `who` in the asset `asset_id`.
+	///
+	/// The `fee` is denominated in the native currency and already includes the `tip`.
+	fn withdraw_fee(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		fee: BalanceOf<T>,
+		tip: BalanceOf<T>,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError>;
+
+	/// Correct the fee of a transaction after dispatch and refund `who` the difference to
+	/// what was `already_withdrawn`.
+	///
+	/// The `corrected_fee` is denominated in the native currency and already includes the
+	/// `tip`.
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		corrected_fee: BalanceOf<T>,
+		tip: BalanceOf<T>,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError>;
+}
+
+/// Convert a fee in the native currency into an amount of an asset, e.g. using the rate of a
+/// liquidity pool or an oracle.
+pub trait FeeConversion<Balance, AssetId, AssetBalance> {
+	/// Convert the native `fee` into the asset `asset_id`, or return `None` if the asset can't
+	/// be used to pay fees.
+	fn to_asset_balance(fee: Balance, asset_id: AssetId) -> Option<AssetBalance>;
+}
+
+/// Charge fees in assets of the assets module, converted with `C`.
+///
+/// The fee is transferred to the account returned by `R`, which also pays the refund after
+/// dispatch.
+pub struct AssetsAdapter<C, R>(PhantomData<(C, R)>);
+
+impl<T, C, R> OnChargeAssetTransaction<T> for AssetsAdapter<C, R> where
+	T: Trait,
+	C: FeeConversion<BalanceOf<T>, AssetIdOf<T>, AssetBalanceOf<T>>,
+	R: Get<T::AccountId>,
+{
+	type LiquidityInfo = AssetBalanceOf<T>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		fee: BalanceOf<T>,
+		_tip: BalanceOf<T>,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let amount = C::to_asset_balance(fee, asset_id).ok_or(InvalidTransaction::Payment)?;
+		if amount.is_zero() {
+			return Ok(amount);
+		}
+		if pallet_assets::Module::<T>::is_frozen(asset_id, who) {
+			return Err(InvalidTransaction::Payment.into());
+		}
+
+		pallet_assets::Module::<T>::do_transfer(asset_id, who.clone(), R::get(), amount)
+			.map_err(|_| InvalidTransaction::Payment)?;
+		Ok(amount)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		corrected_fee: BalanceOf<T>,
+		_tip: BalanceOf<T>,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		// If the asset can't be converted anymore, the up front payment is kept.
+		let amount = C::to_asset_balance(corrected_fee, asset_id).unwrap_or(already_withdrawn);
+		let refund = already_withdrawn.saturating_sub(amount);
+		if refund.is_zero() {
+			return Ok(());
+		}
+
+		pallet_assets::Module::<T>::do_transfer(asset_id, R::get(), who.clone(), refund)
+			.map_err(|_| InvalidTransaction::Payment.into())
+	}
+}
@@ -0,0 +1,255 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for the asset transaction payment module.
+
+use super::*;
+
+use frame_support::{
+	assert_ok, impl_outer_dispatch, impl_outer_origin, parameter_types,
+	weights::{Weight, IdentityFee},
+};
+use pallet_balances::Call as BalancesCall;
+use sp_core::H256;
+use sp_runtime::{
+	Perbill,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::InvalidTransaction,
+};
+
+use frame_system as system;
+impl_outer_origin! {
+	pub enum Origin for Runtime {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Runtime where origin: Origin {
+		pallet_balances::Balances,
+		frame_system::System,
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Runtime;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Runtime {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+parameter_types! {
+	pub const TransactionByteFee: u64 = 1;
+}
+impl pallet_transaction_payment::Trait for Runtime {
+	type Currency = Balances;
+	type OnTransactionPayment = ();
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+parameter_types! {
+	pub const ApprovalDeposit: u64 = 1;
+	pub const MetadataDepositBase: u64 = 1;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const StringLimit: u32 = 4;
+}
+impl pallet_assets::Trait for Runtime {
+	type Event = ();
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ApprovalDeposit = ApprovalDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type StringLimit = StringLimit;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+}
+
+/// Asset `0` is worth half of the native currency; no other asset can pay fees.
+pub struct HalfRate;
+impl FeeConversion<u64, u32, u64> for HalfRate {
+	fn to_asset_balance(fee: u64, asset_id: u32) -> Option<u64> {
+		if asset_id == 0 { Some(fee * 2) } else { None }
+	}
+}
+parameter_types! {
+	pub const FeeReceiver: u64 = 99;
+}
+impl Trait for Runtime {
+	type OnChargeAssetTransaction = AssetsAdapter<HalfRate, FeeReceiver>;
+}
+
+type System = frame_system::Module<Runtime>;
+type Balances = pallet_balances::Module<Runtime>;
+type Assets = pallet_assets::Module<Runtime>;
+
+const CALL: &<Runtime as frame_system::Trait>::Call =
+	&Call::Balances(BalancesCall::transfer(2, 69));
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![(1, 1000), (2, 1000)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| {
+		assert_ok!(Assets::issue(Origin::signed(1), 1000));
+	});
+	ext
+}
+
+fn info_from_weight(w: Weight) -> DispatchInfo {
+	// pays_fee: Pays::Yes -- class: DispatchClass::Normal
+	DispatchInfo { weight: w, ..Default::default() }
+}
+
+fn post_info_from_weight(w: Weight) -> PostDispatchInfo {
+	PostDispatchInfo { actual_weight: Some(w), }
+}
+
+#[test]
+fn native_fees_are_charged_without_asset() {
+	new_test_ext().execute_with(|| {
+		let len = 10;
+		let pre = ChargeAssetTxPayment::<Runtime>::from(5, None)
+			.pre_dispatch(&1, CALL, &info_from_weight(100), len)
+			.unwrap();
+		// 10 byte fee, 100 weight fee, 5 tip
+		assert_eq!(Balances::free_balance(1), 1000 - 10 - 100 - 5);
+		assert_eq!(Assets::balance(0, 1), 1000);
+
+		assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			pre,
+			&info_from_weight(100),
+			&post_info_from_weight(50),
+			len,
+			&Ok(()),
+		));
+		// 10 byte fee, 50 weight fee, 5 tip
+		assert_eq!(Balances::free_balance(1), 1000 - 10 - 50 - 5);
+	});
+}
+
+#[test]
+fn asset_fees_are_charged_and_refunded() {
+	new_test_ext().execute_with(|| {
+		let len = 10;
+		let pre = ChargeAssetTxPayment::<Runtime>::from(5, Some(0))
+			.pre_dispatch(&1, CALL, &info_from_weight(100), len)
+			.unwrap();
+		// (10 byte fee + 100 weight fee + 5 tip) at half the native rate
+		assert_eq!(Assets::balance(0, 1), 1000 - 230);
+		assert_eq!(Assets::balance(0, 99), 230);
+		assert_eq!(Balances::free_balance(1), 1000);
+
+		assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+			pre,
+			&info_from_weight(100),
+			&post_info_from_weight(50),
+			len,
+			&Ok(()),
+		));
+		// (10 byte fee + 50 weight fee + 5 tip) at half the native rate
+		assert_eq!(Assets::balance(0, 1), 1000 - 130);
+		assert_eq!(Assets::balance(0, 99), 130);
+	});
+}
+
+#[test]
+fn unusable_assets_can_not_pay_fees() {
+	new_test_ext().execute_with(|| {
+		let len = 10;
+		let payment_error = Err(TransactionValidityError::from(InvalidTransaction::Payment));
+
+		// Asset `1` has no conversion rate.
+		assert_ok!(Assets::issue(Origin::signed(1), 1000));
+		assert_eq!(
+			ChargeAssetTxPayment::<Runtime>::from(0, Some(1))
+				.validate(&1, CALL, &info_from_weight(5), len),
+			payment_error,
+		);
+
+		// Not enough units of the asset.
+		assert_eq!(
+			ChargeAssetTxPayment::<Runtime>::from(0, Some(0))
+				.validate(&2, CALL, &info_from_weight(5), len),
+			payment_error,
+		);
+
+		// Frozen account.
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 1));
+		assert_eq!(
+			ChargeAssetTxPayment::<Runtime>::from(0, Some(0))
+				.validate(&1, CALL, &info_from_weight(5), len),
+			payment_error,
+		);
+	});
+}
+
+#[test]
+fn priority_is_the_native_fee() {
+	new_test_ext().execute_with(|| {
+		let len = 10;
+		let priority = ChargeAssetTxPayment::<Runtime>::from(5, Some(0))
+			.validate(&1, CALL, &info_from_weight(100), len)
+			.unwrap()
+			.priority;
+		assert_eq!(priority, 10 + 100 + 5);
+	});
+}
@@ -28,7 +28,8 @@
 //!   - The mapping between one unit of weight to one unit of fee via [`Trait::WeightToFee`].
 //!   - A means of updating the fee for the next block, via defining a multiplier, based on the
 //!     final state of the chain at the end of the previous block. This can be configured via
-//!     [`Trait::FeeMultiplierUpdate`]
+//!     [`Trait::FeeMultiplierUpdate`], using any [`MultiplierUpdate`] implementation such as the
+//!     provided [`TargetedFeeAdjustment`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -44,7 +45,7 @@ use frame_support::{
 	dispatch::DispatchResult,
 };
 use sp_runtime::{
-	FixedU128, FixedPointNumber, FixedPointOperand, Perquintill, RuntimeDebug,
+	FixedU128, FixedPointNumber, FixedPointOperand, Perbill, Perquintill, RuntimeDebug,
 	transaction_validity::{
 		TransactionPriority, ValidTransaction, InvalidTransaction, TransactionValidityError,
 		TransactionValidity,
@@ -54,16 +55,39 @@ use sp_runtime::{
 		DispatchInfoOf, PostDispatchInfoOf,
 	},
 };
-use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
+use pallet_transaction_payment_rpc_runtime_api::{RuntimeDispatchInfo, FeeDetails, InclusionFee};
 
 /// Fee multiplier.
 pub type Multiplier = FixedU128;
 
-type BalanceOf<T> =
+/// The balance type of the currency in which fees are paid.
+pub type BalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 
+/// Something that can convert the current multiplier to the next one.
+pub trait MultiplierUpdate: Convert<Multiplier, Multiplier> {
+	/// Minimum multiplier
+	fn min() -> Multiplier;
+	/// Target block saturation level
+	fn target() -> Perquintill;
+	/// Variability factor
+	fn variability() -> Multiplier;
+}
+
+impl MultiplierUpdate for () {
+	fn min() -> Multiplier {
+		Default::default()
+	}
+	fn target() -> Perquintill {
+		Default::default()
+	}
+	fn variability() -> Multiplier {
+		Default::default()
+	}
+}
+
 /// A struct to update the weight multiplier per block. It implements `Convert<Multiplier,
 /// Multiplier>`, meaning that it can convert the previous multiplier to the next one. This should
 /// be called on `on_finalize` of a block, prior to potentially cleaning the weight data from the
@@ -161,6 +185,47 @@ impl<T, S, V, M> Convert<Multiplier, Multiplier> for TargetedFeeAdjustment<T, S,
 	}
 }
 
+impl<T, S, V, M> MultiplierUpdate for TargetedFeeAdjustment<T, S, V, M>
+	where T: frame_system::Trait, S: Get<Perquintill>, V: Get<Multiplier>, M: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	fn variability() -> Multiplier {
+		V::get()
+	}
+}
+
+/// Splits the fees and tips of a transaction between two handlers, e.g. the treasury and the
+/// author of the block (see `pallet_authorship::ToAuthor`). It is meant to be used as
+/// [`Trait::OnTransactionPayment`].
+///
+/// `S` is the share of the fees and tips that goes to `A`, the rest goes to `B`.
+pub struct SplitFees<T, S, A, B>(sp_std::marker::PhantomData<(T, S, A, B)>);
+
+impl<T, S, A, B> OnUnbalanced<NegativeImbalanceOf<T>> for SplitFees<T, S, A, B> where
+	T: Trait,
+	S: Get<Perbill>,
+	A: OnUnbalanced<NegativeImbalanceOf<T>>,
+	B: OnUnbalanced<NegativeImbalanceOf<T>>,
+{
+	fn on_unbalanceds<Balance>(mut fees_then_tips: impl Iterator<Item=NegativeImbalanceOf<T>>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let share = S::get();
+			let (first, second) = (share.deconstruct(), (Perbill::one() - share).deconstruct());
+			let mut split = fees.ration(first, second);
+			if let Some(tips) = fees_then_tips.next() {
+				tips.ration_merge_into(first, second, &mut split);
+			}
+			A::on_unbalanced(split.0);
+			B::on_unbalanced(split.1);
+		}
+	}
+}
+
 /// Storage releases of the module.
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
 enum Releases {
@@ -192,7 +257,7 @@ pub trait Trait: frame_system::Trait {
 	type WeightToFee: WeightToFeePolynomial<Balance=BalanceOf<Self>>;
 
 	/// Update the multiplier of the next block, based on the previous block's weight.
-	type FeeMultiplierUpdate: Convert<Multiplier, Multiplier>;
+	type FeeMultiplierUpdate: MultiplierUpdate;
 }
 
 decl_storage! {
@@ -266,6 +331,28 @@ impl<T: Trait> Module<T> where
 		RuntimeDispatchInfo { weight, class, partial_fee }
 	}
 
+	/// Query the data that we know about the fee of a given `call`, as if it was dispatched in an
+	/// extrinsic of length `len`.
+	pub fn query_call_info(call: T::Call, len: u32) -> RuntimeDispatchInfo<BalanceOf<T>> where
+		T::Call: Dispatchable<Info=DispatchInfo> + GetDispatchInfo,
+	{
+		let dispatch_info = <T::Call as GetDispatchInfo>::get_dispatch_info(&call);
+
+		let partial_fee = Self::compute_fee(len, &dispatch_info, 0u32.into());
+		let DispatchInfo { weight, class, .. } = dispatch_info;
+
+		RuntimeDispatchInfo { weight, class, partial_fee }
+	}
+
+	/// Query the fee details of a given `call`, as if it was dispatched in an extrinsic of
+	/// length `len`.
+	pub fn query_call_fee_details(call: T::Call, len: u32) -> FeeDetails<BalanceOf<T>> where
+		T::Call: Dispatchable<Info=DispatchInfo> + GetDispatchInfo,
+	{
+		let dispatch_info = <T::Call as GetDispatchInfo>::get_dispatch_info(&call);
+		Self::compute_fee_details_raw(len, dispatch_info.weight, 0u32.into(), dispatch_info.pays_fee)
+	}
+
 	/// Compute the final fee value for a particular transaction.
 	///
 	/// The final fee is composed of:
@@ -318,6 +405,15 @@ impl<T: Trait> Module<T> where
 		tip: BalanceOf<T>,
 		pays_fee: Pays,
 	) -> BalanceOf<T> {
+		Self::compute_fee_details_raw(len, weight, tip, pays_fee).final_fee()
+	}
+
+	fn compute_fee_details_raw(
+		len: u32,
+		weight: Weight,
+		tip: BalanceOf<T>,
+		pays_fee: Pays,
+	) -> FeeDetails<BalanceOf<T>> {
 		if pays_fee == Pays::Yes {
 			let len = <BalanceOf<T>>::from(len);
 			let per_byte = T::TransactionByteFee::get();
@@ -332,12 +428,16 @@ impl<T: Trait> Module<T> where
 			let adjusted_weight_fee = multiplier.saturating_mul_int(unadjusted_weight_fee);
 
 			let base_fee = Self::weight_to_fee(T::ExtrinsicBaseWeight::get());
-			base_fee
-				.saturating_add(fixed_len_fee)
-				.saturating_add(adjusted_weight_fee)
-				.saturating_add(tip)
+			FeeDetails {
+				inclusion_fee: Some(InclusionFee {
+					base_fee,
+					len_fee: fixed_len_fee,
+					adjusted_weight_fee,
+				}),
+				tip,
+			}
 		} else {
-			tip
+			FeeDetails { inclusion_fee: None, tip }
 		}
 	}
 
@@ -595,6 +695,8 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
 	}
 	thread_local! {
 		static TRANSACTION_BYTE_FEE: RefCell<u64> = RefCell::new(1);
@@ -891,6 +993,47 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn query_call_info_and_fee_details_work() {
+		let call = Call::Balances(BalancesCall::transfer(2, 69));
+		let info = call.get_dispatch_info();
+		let len = call.using_encoded(|e| e.len()) as u32;
+		ExtBuilder::default()
+			.base_weight(5)
+			.weight_fee(2)
+			.build()
+			.execute_with(||
+		{
+			// all fees should be x1.5
+			NextFeeMultiplier::put(Multiplier::saturating_from_rational(3, 2));
+
+			assert_eq!(
+				TransactionPayment::query_call_info(call.clone(), len),
+				RuntimeDispatchInfo {
+					weight: info.weight,
+					class: info.class,
+					partial_fee:
+						5 * 2 /* base * weight_fee */
+						+ len as u64  /* len * 1 */
+						+ info.weight.min(MaximumBlockWeight::get()) as u64 * 2 * 3 / 2 /* weight */
+				},
+			);
+
+			assert_eq!(
+				TransactionPayment::query_call_fee_details(call, len),
+				FeeDetails {
+					inclusion_fee: Some(InclusionFee {
+						base_fee: 5 * 2,
+						len_fee: len as u64,
+						adjusted_weight_fee:
+							info.weight.min(MaximumBlockWeight::get()) as u64 * 2 * 3 / 2,
+					}),
+					tip: 0,
+				},
+			);
+		});
+	}
+
 	#[test]
 	fn compute_fee_works_without_multiplier() {
 		ExtBuilder::default()
@@ -1149,4 +1292,52 @@ mod tests {
 			assert_eq!(refund_based_fee, actual_fee);
 		});
 	}
+
+	#[test]
+	fn targeted_fee_adjustment_exposes_its_parameters() {
+		parameter_types! {
+			pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+			pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+			pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000);
+		}
+		type Update = TargetedFeeAdjustment<
+			Runtime,
+			TargetBlockFullness,
+			AdjustmentVariable,
+			MinimumMultiplier,
+		>;
+
+		assert_eq!(<Update as MultiplierUpdate>::target(), Perquintill::from_percent(25));
+		assert_eq!(<Update as MultiplierUpdate>::variability(), AdjustmentVariable::get());
+		assert_eq!(<Update as MultiplierUpdate>::min(), MinimumMultiplier::get());
+		assert_eq!(<() as MultiplierUpdate>::min(), Multiplier::zero());
+	}
+
+	#[test]
+	fn split_fees_works() {
+		parameter_types! {
+			pub const FirstShare: Perbill = Perbill::from_percent(80);
+		}
+		pub struct ToTen;
+		impl OnUnbalanced<NegativeImbalanceOf<Runtime>> for ToTen {
+			fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Runtime>) {
+				Balances::resolve_creating(&10, amount);
+			}
+		}
+		pub struct ToEleven;
+		impl OnUnbalanced<NegativeImbalanceOf<Runtime>> for ToEleven {
+			fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Runtime>) {
+				Balances::resolve_creating(&11, amount);
+			}
+		}
+		type Split = SplitFees<Runtime, FirstShare, ToTen, ToEleven>;
+
+		ExtBuilder::default().build().execute_with(|| {
+			let fees = Balances::issue(100);
+			let tips = Balances::issue(50);
+			Split::on_unbalanceds(vec![fees, tips].into_iter());
+			assert_eq!(Balances::free_balance(10), 120);
+			assert_eq!(Balances::free_balance(11), 30);
+		});
+	}
 }
@@ -21,7 +21,7 @@
 
 use super::*;
 
-use frame_system::RawOrigin;
+use frame_system::{self as system, RawOrigin};
 use frame_benchmarking::{benchmarks, account};
 use frame_support::traits::OnInitialize;
 
@@ -36,7 +36,7 @@ fn setup_proposal<T: Trait>(u: u32) -> (
 	<T::Lookup as StaticLookup>::Source,
 ) {
 	let caller = account("caller", u, SEED);
-	let value: BalanceOf<T> = T::ProposalBondMinimum::get().saturating_mul(100.into());
+	let value: BalanceOf<T> = Treasury::<T>::proposal_bond_minimum().saturating_mul(100.into());
 	let _ = T::Currency::make_free_balance_be(&caller, value);
 	let beneficiary = account("beneficiary", u, SEED);
 	let beneficiary_lookup = T::Lookup::unlookup(beneficiary);
@@ -107,10 +107,95 @@ fn create_approved_proposals<T: Trait>(n: u32) -> Result<(), &'static str> {
 	Ok(())
 }
 
+// Create the pre-requisite information needed to create a bounty with a `d` byte description.
+fn setup_bounty<T: Trait>(u: u32, d: u32) -> (
+	T::AccountId,
+	T::AccountId,
+	BalanceOf<T>,
+	BalanceOf<T>,
+	Vec<u8>,
+) {
+	let caller = account("caller", u, SEED);
+	let value: BalanceOf<T> = T::BountyValueMinimum::get().saturating_mul(100.into());
+	let fee = value / 2.into();
+	let deposit = T::BountyDepositBase::get()
+		+ T::DataDepositPerByte::get() * T::MaximumReasonLength::get().into();
+	let _ = T::Currency::make_free_balance_be(&caller, deposit);
+	let curator = account("curator", u, SEED);
+	let _ = T::Currency::make_free_balance_be(&curator, fee);
+	let reason = vec![0; d as usize];
+	(caller, curator, fee, value, reason)
+}
+
+// Create a funded bounty with an active curator. Expects a funded pot.
+fn create_bounty<T: Trait>(u: u32) -> Result<(T::AccountId, BountyIndex), &'static str> {
+	let (caller, curator, fee, value, reason) = setup_bounty::<T>(u, T::MaximumReasonLength::get());
+	let curator_lookup = T::Lookup::unlookup(curator.clone());
+	Treasury::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+	let bounty_id = BountyCount::get() - 1;
+	Treasury::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+	Treasury::<T>::on_initialize(T::BlockNumber::zero());
+	Treasury::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup, fee)?;
+	Treasury::<T>::accept_curator(RawOrigin::Signed(curator.clone()).into(), bounty_id)?;
+	Ok((curator, bounty_id))
+}
+
+// Create bounties that are approved for use in `on_initialize`.
+fn create_approved_bounties<T: Trait>(n: u32) -> Result<(), &'static str> {
+	for i in 0 .. n {
+		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(i, 0);
+		Treasury::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Treasury::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+	}
+	ensure!(BountyApprovals::get().len() == n as usize, "Not all bounty approved");
+	Ok(())
+}
+
+// Make sure the pot is funded.
+fn setup_pot_account<T: Trait>() {
+	let pot_account = Treasury::<T>::account_id();
+	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000.into());
+	let _ = T::Currency::make_free_balance_be(&pot_account, value);
+}
+
+// Create the pre-requisite information needed to call `spend`.
+fn setup_spend<T: Trait>(u: u32) -> (T::AssetKind, AssetBalanceOf<T>, T::Beneficiary)
+	where T::AssetKind: Default, T::Beneficiary: From<T::AccountId>
+{
+	let asset_kind = T::AssetKind::default();
+	let amount: AssetBalanceOf<T> = 100u32.into();
+	let beneficiary = account::<T::AccountId>("beneficiary", u, SEED).into();
+	(asset_kind, amount, beneficiary)
+}
+
+// Approve a spend which is valid immediately, returning its index.
+fn create_spend<T: Trait>(u: u32) -> Result<SpendIndex, &'static str>
+	where T::AssetKind: Default, T::Beneficiary: From<T::AccountId>
+{
+	let (asset_kind, amount, beneficiary) = setup_spend::<T>(u);
+	let beneficiary_lookup = T::BeneficiaryLookup::unlookup(beneficiary);
+	let origin = T::SpendOrigin::successful_origin();
+	Treasury::<T>::spend(origin, asset_kind, amount, beneficiary_lookup, None)?;
+	Ok(Treasury::<T>::spend_count() - 1)
+}
+
+// Approve a spend and make sure its payout will succeed, returning its index.
+fn create_payable_spend<T: Trait>(u: u32) -> Result<SpendIndex, &'static str>
+	where T::AssetKind: Default, T::Beneficiary: From<T::AccountId>
+{
+	let index = create_spend::<T>(u)?;
+	let spend = Treasury::<T>::spends(index).ok_or("spend not created")?;
+	T::Paymaster::ensure_successful(&spend.beneficiary, spend.asset_kind, spend.amount);
+	Ok(index)
+}
+
 const MAX_BYTES: u32 = 16384;
 const MAX_TIPPERS: u32 = 100;
 
 benchmarks! {
+	where_clause { where T::AssetKind: Default, T::Beneficiary: From<T::AccountId> }
+
 	_ { }
 
 	propose_spend {
@@ -208,15 +293,150 @@ benchmarks! {
 		let caller = account("caller", t, SEED);
 	}: _(RawOrigin::Signed(caller), hash)
 
+	propose_bounty {
+		let d in 0 .. T::MaximumReasonLength::get();
+		let (caller, _curator, _fee, value, description) = setup_bounty::<T>(0, d);
+	}: _(RawOrigin::Signed(caller), value, description)
+
+	approve_bounty {
+		let u in 0 .. 1000;
+		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(u, 0);
+		Treasury::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+	}: _(RawOrigin::Root, bounty_id)
+
+	propose_curator {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(u, 0);
+		let curator_lookup = T::Lookup::unlookup(curator);
+		Treasury::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Treasury::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+		Treasury::<T>::on_initialize(T::BlockNumber::zero());
+	}: _(RawOrigin::Root, bounty_id, curator_lookup, fee)
+
+	// Worst case when curator is inactive and any sender unassigns the curator.
+	unassign_curator {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (_curator, bounty_id) = create_bounty::<T>(u)?;
+		let inactivity_timeout = T::BountyUpdatePeriod::get().saturating_add(1.into());
+		system::Module::<T>::set_block_number(inactivity_timeout);
+		let caller = account("caller", u, SEED);
+	}: _(RawOrigin::Signed(caller), bounty_id)
+
+	accept_curator {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(u, 0);
+		let curator_lookup = T::Lookup::unlookup(curator.clone());
+		Treasury::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Treasury::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+		Treasury::<T>::on_initialize(T::BlockNumber::zero());
+		Treasury::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup, fee)?;
+	}: _(RawOrigin::Signed(curator), bounty_id)
+
+	award_bounty {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (curator, bounty_id) = create_bounty::<T>(u)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", u, SEED));
+	}: _(RawOrigin::Signed(curator), bounty_id, beneficiary)
+
+	claim_bounty {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (curator, bounty_id) = create_bounty::<T>(u)?;
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", u, SEED));
+		let origin = RawOrigin::Signed(curator.clone()).into();
+		Treasury::<T>::award_bounty(origin, bounty_id, beneficiary)?;
+		system::Module::<T>::set_block_number(T::BountyDepositPayoutDelay::get());
+	}: _(RawOrigin::Signed(curator), bounty_id)
+
+	close_bounty_proposed {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(u, 0);
+		Treasury::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+	}: close_bounty(RawOrigin::Root, bounty_id)
+
+	close_bounty_active {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (_curator, bounty_id) = create_bounty::<T>(u)?;
+	}: close_bounty(RawOrigin::Root, bounty_id)
+
+	extend_bounty_expiry {
+		let u in 0 .. 1000;
+		setup_pot_account::<T>();
+		let (curator, bounty_id) = create_bounty::<T>(u)?;
+	}: _(RawOrigin::Signed(curator), bounty_id, Vec::new())
+
+	set_params {
+		let u in 0 .. 1000;
+		let spend_period = Some(T::SpendPeriod::get() + u.into());
+		let burn = Some(Permill::from_parts(u));
+		let bond = Some(Permill::from_parts(u));
+		let bond_minimum = Some(T::ProposalBondMinimum::get().saturating_add(u.into()));
+	}: _(RawOrigin::Root, spend_period, burn, bond, bond_minimum)
+	verify {
+		assert_eq!(Treasury::<T>::burn(), Permill::from_parts(u));
+	}
+
+	spend {
+		let u in 0 .. 1000;
+		let (asset_kind, amount, beneficiary) = setup_spend::<T>(u);
+		let beneficiary_lookup = T::BeneficiaryLookup::unlookup(beneficiary);
+		let origin = T::SpendOrigin::successful_origin();
+	}: _(origin, asset_kind, amount, beneficiary_lookup, None)
+	verify {
+		assert!(Treasury::<T>::spends(0).is_some());
+	}
+
+	payout {
+		let u in 0 .. 1000;
+		let index = create_payable_spend::<T>(u)?;
+		let caller: T::AccountId = account("caller", u, SEED);
+	}: _(RawOrigin::Signed(caller), index)
+
+	check_status {
+		let u in 0 .. 1000;
+		let index = create_payable_spend::<T>(u)?;
+		let caller: T::AccountId = account("caller", u, SEED);
+		Treasury::<T>::payout(RawOrigin::Signed(caller.clone()).into(), index)?;
+		let id = match Treasury::<T>::spends(index).ok_or("spend not created")?.status {
+			PaymentState::Attempted { id } => id,
+			_ => return Err("payout not attempted"),
+		};
+		T::Paymaster::ensure_concluded(id);
+	}: _(RawOrigin::Signed(caller), index)
+
+	void_spend {
+		let u in 0 .. 1000;
+		let index = create_spend::<T>(u)?;
+	}: _(RawOrigin::Root, index)
+	verify {
+		assert!(Treasury::<T>::spends(index).is_none());
+	}
+
 	on_initialize {
 		let p in 0 .. 100;
-		let pot_account = Treasury::<T>::account_id();
-		let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000.into());
-		let _ = T::Currency::make_free_balance_be(&pot_account, value);
+		setup_pot_account::<T>();
 		create_approved_proposals::<T>(p)?;
 	}: {
 		Treasury::<T>::on_initialize(T::BlockNumber::zero());
 	}
+
+	on_initialize_bounties {
+		let b in 0 .. 100;
+		setup_pot_account::<T>();
+		create_approved_bounties::<T>(b)?;
+	}: {
+		Treasury::<T>::on_initialize(T::BlockNumber::zero());
+	}
 }
 
 #[cfg(test)]
@@ -236,7 +456,23 @@ mod tests {
 			assert_ok!(test_benchmark_tip_new::<Test>());
 			assert_ok!(test_benchmark_tip::<Test>());
 			assert_ok!(test_benchmark_close_tip::<Test>());
+			assert_ok!(test_benchmark_propose_bounty::<Test>());
+			assert_ok!(test_benchmark_approve_bounty::<Test>());
+			assert_ok!(test_benchmark_propose_curator::<Test>());
+			assert_ok!(test_benchmark_unassign_curator::<Test>());
+			assert_ok!(test_benchmark_accept_curator::<Test>());
+			assert_ok!(test_benchmark_award_bounty::<Test>());
+			assert_ok!(test_benchmark_claim_bounty::<Test>());
+			assert_ok!(test_benchmark_close_bounty_proposed::<Test>());
+			assert_ok!(test_benchmark_close_bounty_active::<Test>());
+			assert_ok!(test_benchmark_extend_bounty_expiry::<Test>());
+			assert_ok!(test_benchmark_set_params::<Test>());
+			assert_ok!(test_benchmark_spend::<Test>());
+			assert_ok!(test_benchmark_payout::<Test>());
+			assert_ok!(test_benchmark_check_status::<Test>());
+			assert_ok!(test_benchmark_void_spend::<Test>());
 			assert_ok!(test_benchmark_on_initialize::<Test>());
+			assert_ok!(test_benchmark_on_initialize_bounties::<Test>());
 		});
 	}
 }
@@ -44,6 +44,31 @@
 //! countdown period, the median of all declared tips is paid to the reported beneficiary, along
 //! with any finders fee, in case of a public (and bonded) original report.
 //!
+//! ### Bounty
+//!
+//! A Bounty Spending is a reward for a specified body of work - or specified set of objectives -
+//! that needs to be executed for a predefined Treasury amount to be paid out. A curator is assigned
+//! after the bounty is approved and funded by Council, to be delegated with the responsibility of
+//! assigning a payout address once the specified set of objectives is completed.
+//!
+//! After the Council has activated a bounty, it delegates the work that requires expertise to a
+//! curator in exchange of a deposit. Once the curator accepts the bounty, they get to close the
+//! Active bounty. Closing the Active bounty enacts a delayed payout to the payout address, the
+//! curator fee and the return of the curator deposit. The delay allows for intervention through
+//! regular democracy. The Council gets to unassign the curator, resulting in a new curator
+//! election. The Council also gets to cancel the bounty if deemed necessary before assigning a
+//! curator or once the bounty is active or payout is pending, resulting in the slash of the
+//! curator's deposit.
+//!
+//! ### Asset Spends
+//!
+//! Besides the proposal process, the `SpendOrigin` may directly approve a spend of any asset kind
+//! supported by the configured `Paymaster`, e.g. a stablecoin held on an assets pallet. The amount
+//! of such a spend, converted into the native asset by `BalanceConverter`, may not exceed the limit
+//! carried by the origin. Once valid, the spend must be paid out within `PayoutPeriod`. Since a
+//! payment may settle asynchronously, its status is later checked and the spend cleaned up once it
+//! has concluded. A failed payment may be retried until the spend expires.
+//!
 //! ### Terminology
 //!
 //! - **Proposal:** A suggestion to allocate funds from the pot to a beneficiary.
@@ -64,6 +89,29 @@
 //! - **Finders Fee:** Some proportion of the tip amount that is paid to the reporter of the tip,
 //!   rather than the main beneficiary.
 //!
+//! Bounty protocol:
+//! - **Bounty spending proposal:** A proposal to reward a predefined body of work upon completion
+//!   by the Treasury.
+//! - **Proposer:** An account proposing a bounty spending.
+//! - **Curator:** An account managing the bounty and assigning a payout address receiving the
+//!   reward for the completion of work.
+//! - **Deposit:** The amount held on deposit for placing a bounty proposal plus the amount held on
+//!   deposit per byte within the bounty description.
+//! - **Curator deposit:** The payment from a candidate willing to curate an approved bounty. The
+//!   deposit is returned when/if the bounty is completed.
+//! - **Bounty value:** The total amount that should be paid to the Payout Address if the bounty is
+//!   rewarded.
+//! - **Payout address:** The account to which the total or part of the bounty is assigned to.
+//! - **Payout Delay:** The delay period for which a bounty beneficiary needs to wait before
+//!   claiming.
+//! - **Curator fee:** The reserved upfront payment for a curator for work related to the bounty.
+//!
+//! Asset spend protocol:
+//! - **Asset kind:** The kind of asset a spend is denominated in, as understood by the
+//!   `Paymaster`.
+//! - **Paymaster:** The means by which an approved spend is paid out to its beneficiary.
+//! - **Payout period:** The period after a spend becomes valid within which it must be paid out.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -74,6 +122,7 @@
 //! - `configure` - Configure the module's proposal requirements.
 //! - `reject_proposal` - Reject a proposal, slashing the deposit.
 //! - `approve_proposal` - Accept the proposal, returning the deposit.
+//! - `set_params` - Change the spend period, the burn and the proposal bond.
 //!
 //! Tipping protocol:
 //! - `report_awesome` - Report something worthy of a tip and register for a finders fee.
@@ -82,6 +131,25 @@
 //! - `tip` - Declare or redeclare an amount to tip for a particular reason.
 //! - `close_tip` - Close and pay out a tip.
 //!
+//! Bounty protocol:
+//! - `propose_bounty` - Propose a specific treasury amount to be earmarked for a predefined set of
+//!   tasks and stake the required deposit.
+//! - `approve_bounty` - Accept a specific treasury amount to be earmarked for a predefined body of
+//!   work.
+//! - `propose_curator` - Assign an account to a bounty as candidate curator.
+//! - `accept_curator` - Accept a bounty assignment from the Council, setting a curator deposit.
+//! - `extend_bounty_expiry` - Extend the expiry block number of the bounty and stay active.
+//! - `award_bounty` - Close and pay out the specified amount for the completed work.
+//! - `claim_bounty` - Claim a specific bounty amount from the Payout Address.
+//! - `unassign_curator` - Unassign an accepted curator from a specific earmark.
+//! - `close_bounty` - Cancel the earmark for a specific treasury amount and close the bounty.
+//!
+//! Asset spend protocol:
+//! - `spend` - Approve a spend of some asset kind to a beneficiary, valid from a given block.
+//! - `payout` - Pay out a valid spend through the `Paymaster`.
+//! - `check_status` - Check the payment status of a spend, cleaning it up once concluded.
+//! - `void_spend` - Void an approved spend before it is paid out.
+//!
 //! ## GenesisConfig
 //!
 //! The Treasury module depends on the [`GenesisConfig`](./struct.GenesisConfig.html).
@@ -93,13 +161,14 @@ use serde::{Serialize, Deserialize};
 use sp_std::prelude::*;
 use frame_support::{decl_module, decl_storage, decl_event, ensure, print, decl_error, Parameter};
 use frame_support::traits::{
-	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::KeepAlive,
-	ReservableCurrency, WithdrawReason
+	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::{KeepAlive, AllowDeath},
+	ReservableCurrency, WithdrawReason, Pay, PaymentStatus, ConversionFromAssetBalance,
 };
 use sp_runtime::{Permill, ModuleId, Percent, RuntimeDebug, traits::{
 	Zero, StaticLookup, AccountIdConversion, Saturating, Hash, BadOrigin
 }};
 use frame_support::weights::{Weight, DispatchClass};
+use frame_support::dispatch::DispatchResult;
 use frame_support::traits::{Contains, ContainsLengthBound, EnsureOrigin};
 use codec::{Encode, Decode};
 use frame_system::{self as system, ensure_signed};
@@ -110,6 +179,8 @@ mod benchmarking;
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 type PositiveImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
 type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+type AssetBalanceOf<T> = <<T as Trait>::Paymaster as Pay>::Balance;
+type PaymentIdOf<T> = <<T as Trait>::Paymaster as Pay>::Id;
 
 pub trait WeightInfo {
 	fn propose_spend(u: u32, ) -> Weight;
@@ -120,7 +191,23 @@ pub trait WeightInfo {
 	fn tip_new(r: u32, t: u32, ) -> Weight;
 	fn tip(t: u32, ) -> Weight;
 	fn close_tip(t: u32, ) -> Weight;
+	fn propose_bounty(d: u32, ) -> Weight;
+	fn approve_bounty(u: u32, ) -> Weight;
+	fn propose_curator(u: u32, ) -> Weight;
+	fn unassign_curator(u: u32, ) -> Weight;
+	fn accept_curator(u: u32, ) -> Weight;
+	fn award_bounty(u: u32, ) -> Weight;
+	fn claim_bounty(u: u32, ) -> Weight;
+	fn close_bounty_proposed(u: u32, ) -> Weight;
+	fn close_bounty_active(u: u32, ) -> Weight;
+	fn extend_bounty_expiry(u: u32, ) -> Weight;
+	fn spend(u: u32, ) -> Weight;
+	fn payout(u: u32, ) -> Weight;
+	fn check_status(u: u32, ) -> Weight;
+	fn void_spend(u: u32, ) -> Weight;
+	fn set_params(u: u32, ) -> Weight;
 	fn on_initialize(p: u32, ) -> Weight;
+	fn on_initialize_bounties(b: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -132,7 +219,23 @@ impl WeightInfo for () {
 	fn tip_new(_r: u32, _t: u32, ) -> Weight { 1_000_000_000 }
 	fn tip(_t: u32, ) -> Weight { 1_000_000_000 }
 	fn close_tip(_t: u32, ) -> Weight { 1_000_000_000 }
+	fn propose_bounty(_d: u32, ) -> Weight { 1_000_000_000 }
+	fn approve_bounty(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn propose_curator(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn unassign_curator(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn accept_curator(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn award_bounty(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn claim_bounty(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn close_bounty_proposed(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn close_bounty_active(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn extend_bounty_expiry(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn spend(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn payout(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn check_status(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn void_spend(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn set_params(_u: u32, ) -> Weight { 1_000_000_000 }
 	fn on_initialize(_p: u32, ) -> Weight { 1_000_000_000 }
+	fn on_initialize_bounties(_b: u32, ) -> Weight { 1_000_000_000 }
 }
 
 pub trait Trait: frame_system::Trait {
@@ -171,19 +274,77 @@ pub trait Trait: frame_system::Trait {
 	/// Handler for the unbalanced decrease when slashing for a rejected proposal.
 	type ProposalRejection: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
-	/// Fraction of a proposal's value that should be bonded in order to place the proposal.
-	/// An accepted proposal gets these back. A rejected proposal does not.
+	/// Fraction of a proposal's value that should be bonded in order to place the proposal, until
+	/// changed with `set_params`. An accepted proposal gets these back. A rejected proposal does
+	/// not.
 	type ProposalBond: Get<Permill>;
 
-	/// Minimum amount of funds that should be placed in a deposit for making a proposal.
+	/// Minimum amount of funds that should be placed in a deposit for making a proposal, until
+	/// changed with `set_params`.
 	type ProposalBondMinimum: Get<BalanceOf<Self>>;
 
-	/// Period between successive spends.
+	/// Period between successive spends, until changed with `set_params`.
 	type SpendPeriod: Get<Self::BlockNumber>;
 
-	/// Percentage of spare funds (if any) that are burnt per spend period.
+	/// Percentage of spare funds (if any) that are burnt per spend period, until changed with
+	/// `set_params`.
 	type Burn: Get<Permill>;
 
+	/// Origin from which the spend period, the burn and the proposal bond may be changed.
+	type ParamsOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The amount held on deposit for placing a bounty proposal.
+	type BountyDepositBase: Get<BalanceOf<Self>>;
+
+	/// The amount held on deposit per byte within the bounty description.
+	type DataDepositPerByte: Get<BalanceOf<Self>>;
+
+	/// The delay period for which a bounty beneficiary need to wait before claim the payout.
+	type BountyDepositPayoutDelay: Get<Self::BlockNumber>;
+
+	/// Bounty duration in blocks.
+	type BountyUpdatePeriod: Get<Self::BlockNumber>;
+
+	/// Percentage of the curator fee that will be reserved upfront as deposit for bounty curator.
+	type BountyCuratorDeposit: Get<Permill>;
+
+	/// Minimum value for a bounty.
+	type BountyValueMinimum: Get<BalanceOf<Self>>;
+
+	/// Maximum acceptable reason length for a bounty description.
+	type MaximumReasonLength: Get<u32>;
+
+	/// The child bounties manager, used to stop a bounty with open child bounties from being
+	/// awarded or closed.
+	type ChildBountyManager: ChildBountyManager;
+
+	/// The kinds of asset which may be spent by `spend`.
+	type AssetKind: Parameter;
+
+	/// The type identifying the beneficiaries of a `spend`.
+	type Beneficiary: Parameter;
+
+	/// Converts a source given to `spend` into a `Beneficiary`.
+	type BeneficiaryLookup: StaticLookup<Target = Self::Beneficiary>;
+
+	/// The means of paying out approved spends.
+	type Paymaster: Pay<Beneficiary = Self::Beneficiary, AssetKind = Self::AssetKind>;
+
+	/// Converts the amount of a spend into the native balance, to be checked against the limit
+	/// of the `SpendOrigin`.
+	type BalanceConverter: ConversionFromAssetBalance<
+		AssetBalanceOf<Self>,
+		Self::AssetKind,
+		BalanceOf<Self>,
+	>;
+
+	/// Origin from which spends must come. The success value is the maximum amount, in the native
+	/// balance, which the origin may spend at once.
+	type SpendOrigin: EnsureOrigin<Self::Origin, Success = BalanceOf<Self>>;
+
+	/// The period after a spend becomes valid within which it must be paid out.
+	type PayoutPeriod: Get<Self::BlockNumber>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -232,6 +393,110 @@ pub struct OpenTip<
 	finders_fee: bool,
 }
 
+/// An index of a bounty. Just a `u32`.
+pub type BountyIndex = u32;
+
+/// A bounty proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Bounty<AccountId, Balance, BlockNumber> {
+	/// The account proposing it.
+	proposer: AccountId,
+	/// The (total) amount that should be paid if the bounty is rewarded.
+	value: Balance,
+	/// The curator fee. Included in value.
+	fee: Balance,
+	/// The deposit of curator.
+	curator_deposit: Balance,
+	/// The amount held on deposit (reserved) for making this proposal.
+	bond: Balance,
+	/// The status of this bounty.
+	status: BountyStatus<AccountId, BlockNumber>,
+}
+
+impl<AccountId: Clone, Balance, BlockNumber: Clone> Bounty<AccountId, Balance, BlockNumber> {
+	/// Getter for bounty status, to be used for child bounties.
+	pub fn get_status(&self) -> BountyStatus<AccountId, BlockNumber> {
+		self.status.clone()
+	}
+}
+
+/// The status of a bounty proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum BountyStatus<AccountId, BlockNumber> {
+	/// The bounty is proposed and waiting for approval.
+	Proposed,
+	/// The bounty is approved and waiting to become active at next spend period.
+	Approved,
+	/// The bounty is funded and waiting for curator assignment.
+	Funded,
+	/// A curator has been proposed by the `ApproveOrigin`. Waiting for acceptance from the
+	/// curator.
+	CuratorProposed {
+		/// The assigned curator of this bounty.
+		curator: AccountId,
+	},
+	/// The bounty is active and waiting to be awarded.
+	Active {
+		/// The curator of this bounty.
+		curator: AccountId,
+		/// An update from the curator is due by this block, else they are considered inactive.
+		update_due: BlockNumber,
+	},
+	/// The bounty is awarded and waiting to released after a delay.
+	PendingPayout {
+		/// The curator of this bounty.
+		curator: AccountId,
+		/// The beneficiary of the bounty.
+		beneficiary: AccountId,
+		/// When the bounty can be claimed.
+		unlock_at: BlockNumber,
+	},
+}
+
+/// An index of an asset spend. Just a `u32`.
+pub type SpendIndex = u32;
+
+/// The state of the payment of an asset spend.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum PaymentState<Id> {
+	/// The spend has not been paid out yet.
+	Pending,
+	/// A payment has been made; its outcome is not yet known.
+	Attempted {
+		/// The identifier of the payment.
+		id: Id,
+	},
+	/// The payment has failed. It may be retried.
+	Failed,
+}
+
+/// An approved spend of some asset kind.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SpendStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, PaymentId> {
+	/// The kind of asset to be spent.
+	asset_kind: AssetKind,
+	/// The amount to be paid out, denominated in `asset_kind`.
+	amount: AssetBalance,
+	/// The beneficiary of the spend.
+	beneficiary: Beneficiary,
+	/// The block from which the spend may be paid out.
+	valid_from: BlockNumber,
+	/// The block from which the spend may no longer be paid out.
+	expire_at: BlockNumber,
+	/// The state of the payment of this spend.
+	status: PaymentState<PaymentId>,
+}
+
+/// Keeps track of the child bounties of a bounty.
+pub trait ChildBountyManager {
+	/// The number of child bounties of `bounty_id` which are not yet claimed or closed.
+	fn child_bounties_count(bounty_id: BountyIndex) -> BountyIndex;
+}
+
+impl ChildBountyManager for () {
+	fn child_bounties_count(_bounty_id: BountyIndex) -> BountyIndex { 0 }
+}
+
 decl_storage! {
 	trait Store for Module<T: Trait> as Treasury {
 		/// Number of proposals that have been made.
@@ -255,6 +520,51 @@ decl_storage! {
 		/// Simple preimage lookup from the reason's hash to the original data. Again, has an
 		/// insecure enumerable hash since the key is guaranteed to be the result of a secure hash.
 		pub Reasons get(fn reasons): map hasher(identity) T::Hash => Option<Vec<u8>>;
+
+		/// Number of bounty proposals that have been made.
+		pub BountyCount get(fn bounty_count): BountyIndex;
+
+		/// Bounties that have been made.
+		pub Bounties get(fn bounties):
+			map hasher(twox_64_concat) BountyIndex
+			=> Option<Bounty<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+		/// The description of each bounty.
+		pub BountyDescriptions get(fn bounty_descriptions):
+			map hasher(twox_64_concat) BountyIndex => Option<Vec<u8>>;
+
+		/// Bounty indices that have been approved but not yet funded.
+		pub BountyApprovals get(fn bounty_approvals): Vec<BountyIndex>;
+
+		/// Number of asset spends that have been approved.
+		pub SpendCount get(fn spend_count): SpendIndex;
+
+		/// Period between successive spends, if set with `set_params`. `T::SpendPeriod` is used
+		/// otherwise.
+		pub SpendPeriod: Option<T::BlockNumber>;
+
+		/// Percentage of spare funds (if any) that are burnt per spend period, if set with
+		/// `set_params`. `T::Burn` is used otherwise.
+		pub Burn: Option<Permill>;
+
+		/// Fraction of a proposal's value that should be bonded in order to place the proposal,
+		/// if set with `set_params`. `T::ProposalBond` is used otherwise.
+		pub ProposalBond: Option<Permill>;
+
+		/// Minimum amount of funds that should be placed in a deposit for making a proposal, if
+		/// set with `set_params`. `T::ProposalBondMinimum` is used otherwise.
+		pub ProposalBondMinimum: Option<BalanceOf<T>>;
+
+		/// Asset spends that have been approved and not yet concluded or voided.
+		pub Spends get(fn spends):
+			map hasher(twox_64_concat) SpendIndex
+			=> Option<SpendStatus<
+				T::AssetKind,
+				AssetBalanceOf<T>,
+				T::Beneficiary,
+				T::BlockNumber,
+				PaymentIdOf<T>,
+			>>;
 	}
 	add_extra_genesis {
 		build(|_config| {
@@ -273,6 +583,11 @@ decl_event!(
 		Balance = BalanceOf<T>,
 		<T as frame_system::Trait>::AccountId,
 		<T as frame_system::Trait>::Hash,
+		<T as frame_system::Trait>::BlockNumber,
+		AssetKind = <T as Trait>::AssetKind,
+		AssetBalance = AssetBalanceOf<T>,
+		Beneficiary = <T as Trait>::Beneficiary,
+		PaymentId = PaymentIdOf<T>,
 	{
 		/// New proposal.
 		Proposed(ProposalIndex),
@@ -296,6 +611,46 @@ decl_event!(
 		TipClosed(Hash, AccountId, Balance),
 		/// A tip suggestion has been retracted.
 		TipRetracted(Hash),
+		/// New bounty proposal.
+		BountyProposed(BountyIndex),
+		/// A bounty proposal was rejected; funds were slashed.
+		BountyRejected(BountyIndex, Balance),
+		/// A bounty proposal is funded and became active.
+		BountyBecameActive(BountyIndex),
+		/// A bounty is awarded to a beneficiary.
+		BountyAwarded(BountyIndex, AccountId),
+		/// A bounty is claimed by beneficiary.
+		BountyClaimed(BountyIndex, Balance, AccountId),
+		/// A bounty is cancelled.
+		BountyCanceled(BountyIndex),
+		/// A bounty expiry is extended.
+		BountyExtended(BountyIndex),
+		/// A spend of an asset kind to a beneficiary was approved, valid from the first given
+		/// block and expiring at the second.
+		AssetSpendApproved(
+			SpendIndex,
+			AssetKind,
+			AssetBalance,
+			Beneficiary,
+			BlockNumber,
+			BlockNumber,
+		),
+		/// An approved spend was voided.
+		AssetSpendVoided(SpendIndex),
+		/// A payment was made for a spend.
+		Paid(SpendIndex, PaymentId),
+		/// The payment for a spend failed; it may be paid out again.
+		PaymentFailed(SpendIndex, PaymentId),
+		/// A spend has concluded, either by being paid out or by expiring, and was removed.
+		SpendProcessed(SpendIndex),
+		/// The period between successive spends was changed.
+		SpendPeriodSet(BlockNumber),
+		/// The percentage of spare funds burnt per spend period was changed.
+		BurnSet(Permill),
+		/// The fraction of a proposal's value to be bonded was changed.
+		ProposalBondSet(Permill),
+		/// The minimum proposal bond was changed.
+		ProposalBondMinimumSet(Balance),
 	}
 );
 
@@ -318,22 +673,55 @@ decl_error! {
 		StillOpen,
 		/// The tip cannot be claimed/closed because it's still in the countdown period.
 		Premature,
+		/// The bounty status is unexpected.
+		UnexpectedStatus,
+		/// Require bounty curator.
+		RequireCurator,
+		/// Invalid bounty value.
+		InvalidValue,
+		/// Invalid bounty fee.
+		InvalidFee,
+		/// A bounty payout is pending.
+		/// To cancel the bounty, you must unassign and slash the curator.
+		PendingPayout,
+		/// The bounty cannot be awarded or closed while it still has child bounties.
+		HasActiveChildBounty,
+		/// The amount of the spend could not be converted into the native balance.
+		FailedToConvertBalance,
+		/// The spend origin may not spend the given amount.
+		InsufficientPermission,
+		/// The spend has expired and cannot be paid out.
+		SpendExpired,
+		/// The spend is not yet valid.
+		EarlyPayout,
+		/// A payment for the spend has already been attempted.
+		AlreadyAttempted,
+		/// The `Paymaster` failed to make the payment.
+		PayoutError,
+		/// No payment has been attempted for the spend.
+		NotAttempted,
+		/// The outcome of the payment is not yet known.
+		Inconclusive,
+		/// The spend period must not be zero.
+		InvalidSpendPeriod,
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		/// Fraction of a proposal's value that should be bonded in order to place the proposal.
-		/// An accepted proposal gets these back. A rejected proposal does not.
+		/// Default fraction of a proposal's value that should be bonded in order to place the
+		/// proposal, used unless changed with `set_params`.
 		const ProposalBond: Permill = T::ProposalBond::get();
 
-		/// Minimum amount of funds that should be placed in a deposit for making a proposal.
+		/// Default minimum amount of funds that should be placed in a deposit for making a
+		/// proposal, used unless changed with `set_params`.
 		const ProposalBondMinimum: BalanceOf<T> = T::ProposalBondMinimum::get();
 
-		/// Period between successive spends.
+		/// Default period between successive spends, used unless changed with `set_params`.
 		const SpendPeriod: T::BlockNumber = T::SpendPeriod::get();
 
-		/// Percentage of spare funds (if any) that are burnt per spend period.
+		/// Default percentage of spare funds (if any) that are burnt per spend period, used unless
+		/// changed with `set_params`.
 		const Burn: Permill = T::Burn::get();
 
 		/// The period for which a tip remains open after is has achieved threshold tippers.
@@ -351,6 +739,31 @@ decl_module! {
 		/// The treasury's module id, used for deriving its sovereign account ID.
 		const ModuleId: ModuleId = T::ModuleId::get();
 
+		/// The amount held on deposit for placing a bounty proposal.
+		const BountyDepositBase: BalanceOf<T> = T::BountyDepositBase::get();
+
+		/// The amount held on deposit per byte within the bounty description.
+		const DataDepositPerByte: BalanceOf<T> = T::DataDepositPerByte::get();
+
+		/// The delay period for which a bounty beneficiary need to wait before claim the payout.
+		const BountyDepositPayoutDelay: T::BlockNumber = T::BountyDepositPayoutDelay::get();
+
+		/// Bounty duration in blocks.
+		const BountyUpdatePeriod: T::BlockNumber = T::BountyUpdatePeriod::get();
+
+		/// Percentage of the curator fee that will be reserved upfront as deposit for bounty
+		/// curator.
+		const BountyCuratorDeposit: Permill = T::BountyCuratorDeposit::get();
+
+		/// Minimum value for a bounty.
+		const BountyValueMinimum: BalanceOf<T> = T::BountyValueMinimum::get();
+
+		/// Maximum acceptable reason length for a bounty description.
+		const MaximumReasonLength: u32 = T::MaximumReasonLength::get();
+
+		/// The period after a spend becomes valid within which it must be paid out.
+		const PayoutPeriod: T::BlockNumber = T::PayoutPeriod::get();
+
 		type Error = Error<T>;
 
 		fn deposit_event() = default;
@@ -622,22 +1035,628 @@ decl_module! {
 			Self::payout_tip(hash, tip);
 		}
 
+		/// Propose a new bounty.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Payment: `BountyDepositBase` will be reserved from the origin account, as well as
+		/// `DataDepositPerByte` for each byte in `description`. It will be unreserved upon
+		/// approval, or slashed when rejected.
+		///
+		/// - `value`: The total payment amount of this bounty, curator fee included.
+		/// - `description`: The description of this bounty.
+		///
+		/// # <weight>
+		/// - Complexity: `O(D)` where `D` is the length of `description`.
+		/// - DbReads: `BountyCount`, `origin account`
+		/// - DbWrites: `BountyCount`, `Bounties`, `BountyDescriptions`, `origin account`
+		/// # </weight>
+		#[weight = 120_000_000 + 1_000 * description.len() as Weight
+			+ T::DbWeight::get().reads_writes(2, 4)]
+		fn propose_bounty(
+			origin,
+			#[compact] value: BalanceOf<T>,
+			description: Vec<u8>,
+		) {
+			let proposer = ensure_signed(origin)?;
+			Self::create_bounty(proposer, description, value)?;
+		}
+
+		/// Approve a bounty proposal. At a later time, the bounty will be funded and become active
+		/// and the original deposit will be returned.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `BountyApprovals`
+		/// - DbWrites: `Bounties`, `BountyApprovals`
+		/// # </weight>
+		#[weight = (60_000_000 + T::DbWeight::get().reads_writes(2, 2), DispatchClass::Operational)]
+		fn approve_bounty(origin, #[compact] bounty_id: BountyIndex) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidProposalIndex)?;
+				ensure!(bounty.status == BountyStatus::Proposed, Error::<T>::UnexpectedStatus);
+
+				bounty.status = BountyStatus::Approved;
+				BountyApprovals::mutate(|v| v.push(bounty_id));
+				Ok(())
+			})?;
+		}
+
+		/// Assign a curator to a funded bounty.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// - `curator`: The account proposed to curate the bounty.
+		/// - `fee`: The curator fee, paid out of the bounty value once it is claimed. Must be
+		///   less than the bounty value.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`
+		/// - DbWrites: `Bounties`
+		/// # </weight>
+		#[weight = (50_000_000 + T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational)]
+		fn propose_curator(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+			curator: <T::Lookup as StaticLookup>::Source,
+			#[compact] fee: BalanceOf<T>,
+		) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			let curator = T::Lookup::lookup(curator)?;
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidProposalIndex)?;
+				match bounty.status {
+					BountyStatus::Funded => {},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				};
+
+				ensure!(fee < bounty.value, Error::<T>::InvalidFee);
+
+				bounty.status = BountyStatus::CuratorProposed { curator };
+				bounty.fee = fee;
+
+				Ok(())
+			})?;
+		}
+
+		/// Unassign curator from a bounty.
+		///
+		/// This function can only be called by the `RejectOrigin` or a signed origin.
+		///
+		/// If this function is called by the `RejectOrigin`, we assume that the curator is
+		/// malicious or inactive. As a result, we will slash the curator when possible.
+		///
+		/// If the origin is the curator, we take this as a sign they are unable to do their job and
+		/// they willingly give up. We could slash them, but for now we allow them to recover their
+		/// deposit and exit without issue. (We may want to change this if it is abused.)
+		///
+		/// Finally, the origin can be anyone if and only if the curator is "inactive". This allows
+		/// anyone in the community to call out that a curator is not doing their due diligence, and
+		/// we should pick a new curator. In this case the curator should also be slashed.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `curator account`
+		/// - DbWrites: `Bounties`, `curator account`
+		/// # </weight>
+		#[weight = (60_000_000 + T::DbWeight::get().reads_writes(2, 2), DispatchClass::Operational)]
+		fn unassign_curator(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+		) {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidProposalIndex)?;
+
+				let slash_curator = |curator: &T::AccountId, curator_deposit: &mut BalanceOf<T>| {
+					let imbalance = T::Currency::slash_reserved(curator, *curator_deposit).0;
+					T::ProposalRejection::on_unbalanced(imbalance);
+					*curator_deposit = Zero::zero();
+				};
+
+				match bounty.status {
+					BountyStatus::Proposed | BountyStatus::Approved | BountyStatus::Funded => {
+						// No curator to unassign at this point.
+						return Err(Error::<T>::UnexpectedStatus.into())
+					}
+					BountyStatus::CuratorProposed { ref curator } => {
+						// A curator has been proposed, but not accepted yet.
+						// Either `RejectOrigin` or the proposed curator can unassign the curator.
+						ensure!(maybe_sender.map_or(true, |sender| sender == *curator), BadOrigin);
+					},
+					BountyStatus::Active { ref curator, ref update_due } => {
+						match maybe_sender {
+							// The `RejectOrigin` is calling this function, slash the curator.
+							None => slash_curator(curator, &mut bounty.curator_deposit),
+							Some(sender) if sender != *curator => {
+								// Anyone else may only unassign a curator who has gone inactive.
+								let now = system::Module::<T>::block_number();
+								ensure!(*update_due < now, Error::<T>::Premature);
+								slash_curator(curator, &mut bounty.curator_deposit);
+							},
+							Some(_) => {
+								// The curator is willingly giving up their role, so give back
+								// their deposit.
+								let _ = T::Currency::unreserve(curator, bounty.curator_deposit);
+								bounty.curator_deposit = Zero::zero();
+							},
+						}
+					},
+					BountyStatus::PendingPayout { ref curator, .. } => {
+						// The bounty is pending payout, so only the `RejectOrigin` can unassign a
+						// curator. By doing so, they are claiming the curator is acting
+						// maliciously, so we slash the curator.
+						ensure!(maybe_sender.is_none(), BadOrigin);
+						slash_curator(curator, &mut bounty.curator_deposit);
+					}
+				};
+
+				bounty.status = BountyStatus::Funded;
+				Ok(())
+			})?;
+		}
+
+		/// Accept the curator role for a bounty.
+		/// A deposit will be reserved from curator and refund upon successful payout.
+		///
+		/// May only be called from the curator.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `curator account`
+		/// - DbWrites: `Bounties`, `curator account`
+		/// # </weight>
+		#[weight = 60_000_000 + T::DbWeight::get().reads_writes(2, 2)]
+		fn accept_curator(origin, #[compact] bounty_id: BountyIndex) {
+			let signer = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidProposalIndex)?;
+
+				match bounty.status {
+					BountyStatus::CuratorProposed { ref curator } => {
+						ensure!(signer == *curator, Error::<T>::RequireCurator);
+
+						let deposit = T::BountyCuratorDeposit::get() * bounty.fee;
+						T::Currency::reserve(curator, deposit)?;
+						bounty.curator_deposit = deposit;
+
+						let update_due = system::Module::<T>::block_number()
+							+ T::BountyUpdatePeriod::get();
+						bounty.status = BountyStatus::Active { curator: signer, update_due };
+
+						Ok(())
+					},
+					_ => Err(Error::<T>::UnexpectedStatus.into()),
+				}
+			})?;
+		}
+
+		/// Award bounty to a beneficiary account. The beneficiary will be able to claim the funds
+		/// after a delay.
+		///
+		/// The dispatch origin for this call must be the curator of this bounty.
+		///
+		/// - `bounty_id`: Bounty ID to award.
+		/// - `beneficiary`: The beneficiary account whom will receive the payout.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`
+		/// - DbWrites: `Bounties`
+		/// # </weight>
+		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn award_bounty(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) {
+			let signer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			ensure!(
+				T::ChildBountyManager::child_bounties_count(bounty_id) == 0,
+				Error::<T>::HasActiveChildBounty,
+			);
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidProposalIndex)?;
+				match &bounty.status {
+					BountyStatus::Active { curator, .. } => {
+						ensure!(signer == *curator, Error::<T>::RequireCurator);
+					},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				}
+				bounty.status = BountyStatus::PendingPayout {
+					curator: signer,
+					beneficiary: beneficiary.clone(),
+					unlock_at: system::Module::<T>::block_number()
+						+ T::BountyDepositPayoutDelay::get(),
+				};
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::BountyAwarded(bounty_id, beneficiary));
+		}
+
+		/// Claim the payout from an awarded bounty after payout delay.
+		///
+		/// The dispatch origin for this call must be _Signed_. Anyone may trigger the payout on
+		/// behalf of the beneficiary.
+		///
+		/// - `bounty_id`: Bounty ID to claim.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `bounty account`, `curator account`, `beneficiary account`
+		/// - DbWrites: `Bounties`, `BountyDescriptions`, `bounty account`, `curator account`,
+		///   `beneficiary account`
+		/// # </weight>
+		#[weight = 200_000_000 + T::DbWeight::get().reads_writes(4, 5)]
+		fn claim_bounty(origin, #[compact] bounty_id: BountyIndex) {
+			let _ = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.take().ok_or(Error::<T>::InvalidProposalIndex)?;
+				if let BountyStatus::PendingPayout {
+					curator, beneficiary, unlock_at
+				} = bounty.status {
+					let now = system::Module::<T>::block_number();
+					ensure!(now >= unlock_at, Error::<T>::Premature);
+
+					let bounty_account = Self::bounty_account_id(bounty_id);
+					let balance = T::Currency::free_balance(&bounty_account);
+					// just to be safe.
+					let fee = bounty.fee.min(balance);
+					let payout = balance.saturating_sub(fee);
+					let _ = T::Currency::unreserve(&curator, bounty.curator_deposit);
+					// should not fail, the bounty account holds at least `fee + payout`.
+					let _ = T::Currency::transfer(&bounty_account, &curator, fee, AllowDeath);
+					let _ = T::Currency::transfer(&bounty_account, &beneficiary, payout, AllowDeath);
+					BountyDescriptions::remove(bounty_id);
+
+					Self::deposit_event(Event::<T>::BountyClaimed(bounty_id, payout, beneficiary));
+					Ok(())
+				} else {
+					Err(Error::<T>::UnexpectedStatus.into())
+				}
+			})?;
+		}
+
+		/// Cancel a proposed or active bounty. All the funds will be sent to treasury and
+		/// the curator deposit will be unreserved if possible.
+		///
+		/// Only `T::RejectOrigin` is able to cancel a bounty.
+		///
+		/// - `bounty_id`: Bounty ID to cancel.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`, `bounty account`, `curator or proposer account`
+		/// - DbWrites: `Bounties`, `BountyDescriptions`, `bounty account`, `treasury account`,
+		///   `curator or proposer account`
+		/// # </weight>
+		#[weight = (200_000_000 + T::DbWeight::get().reads_writes(3, 5), DispatchClass::Operational)]
+		fn close_bounty(origin, #[compact] bounty_id: BountyIndex) {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				T::ChildBountyManager::child_bounties_count(bounty_id) == 0,
+				Error::<T>::HasActiveChildBounty,
+			);
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.take().ok_or(Error::<T>::InvalidProposalIndex)?;
+
+				match bounty.status {
+					BountyStatus::Proposed => {
+						// The reject origin would like to cancel a proposed bounty.
+						BountyDescriptions::remove(bounty_id);
+						let value = bounty.bond;
+						let imbalance = T::Currency::slash_reserved(&bounty.proposer, value).0;
+						T::ProposalRejection::on_unbalanced(imbalance);
+
+						Self::deposit_event(Event::<T>::BountyRejected(bounty_id, value));
+						return Ok(())
+					},
+					BountyStatus::Approved => {
+						// For weight reasons, we don't allow the reject origin to cancel in this
+						// phase. It must wait until the bounty is funded before it can cancel.
+						return Err(Error::<T>::UnexpectedStatus.into())
+					},
+					BountyStatus::Funded | BountyStatus::CuratorProposed { .. } => {
+						// Nothing extra to do besides the removal of the bounty below.
+					},
+					BountyStatus::Active { curator, .. } => {
+						// Cancelled by the reject origin, refund deposit of the working curator.
+						let _ = T::Currency::unreserve(&curator, bounty.curator_deposit);
+					},
+					BountyStatus::PendingPayout { .. } => {
+						// Bounty is already pending payout. If the reject origin wants to cancel
+						// this bounty, it should mean the curator was acting maliciously. So it
+						// should first unassign the curator, slashing their deposit.
+						return Err(Error::<T>::PendingPayout.into())
+					}
+				}
+
+				let bounty_account = Self::bounty_account_id(bounty_id);
+				BountyDescriptions::remove(bounty_id);
+
+				let treasury = Self::account_id();
+				let balance = T::Currency::free_balance(&bounty_account);
+				// should not fail, we are moving the whole free balance of the bounty account.
+				let _ = T::Currency::transfer(&bounty_account, &treasury, balance, AllowDeath);
+
+				Self::deposit_event(Event::<T>::BountyCanceled(bounty_id));
+				Ok(())
+			})?;
+		}
+
+		/// Extend the expiry time of an active bounty.
+		///
+		/// The dispatch origin for this call must be the curator of this bounty.
+		///
+		/// - `bounty_id`: Bounty ID to extend.
+		/// - `_remark`: additional information.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Bounties`
+		/// - DbWrites: `Bounties`
+		/// # </weight>
+		#[weight = 50_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn extend_bounty_expiry(origin, #[compact] bounty_id: BountyIndex, _remark: Vec<u8>) {
+			let signer = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidProposalIndex)?;
+
+				match bounty.status {
+					BountyStatus::Active { ref curator, ref mut update_due } => {
+						ensure!(*curator == signer, Error::<T>::RequireCurator);
+						let extended = system::Module::<T>::block_number()
+							+ T::BountyUpdatePeriod::get();
+						*update_due = extended.max(*update_due);
+					},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				}
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::BountyExtended(bounty_id));
+		}
+
+		/// Change the spend period, the burn and the proposal bond. Parameters given as `None`
+		/// are left unchanged.
+		///
+		/// May only be called from `T::ParamsOrigin`.
+		///
+		/// - `spend_period`: The period between successive spends. Must not be zero.
+		/// - `burn`: The percentage of spare funds burnt per spend period.
+		/// - `proposal_bond`: The fraction of a proposal's value to be bonded.
+		/// - `proposal_bond_minimum`: The minimum bond for a proposal.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbWrites: `SpendPeriod`, `Burn`, `ProposalBond`, `ProposalBondMinimum`
+		/// # </weight>
+		#[weight = (30_000_000 + T::DbWeight::get().writes(4), DispatchClass::Operational)]
+		fn set_params(
+			origin,
+			spend_period: Option<T::BlockNumber>,
+			burn: Option<Permill>,
+			proposal_bond: Option<Permill>,
+			proposal_bond_minimum: Option<BalanceOf<T>>,
+		) {
+			T::ParamsOrigin::ensure_origin(origin)?;
+
+			if let Some(spend_period) = spend_period {
+				ensure!(!spend_period.is_zero(), Error::<T>::InvalidSpendPeriod);
+				SpendPeriod::<T>::put(spend_period);
+				Self::deposit_event(Event::<T>::SpendPeriodSet(spend_period));
+			}
+			if let Some(burn) = burn {
+				Burn::put(burn);
+				Self::deposit_event(Event::<T>::BurnSet(burn));
+			}
+			if let Some(proposal_bond) = proposal_bond {
+				ProposalBond::put(proposal_bond);
+				Self::deposit_event(Event::<T>::ProposalBondSet(proposal_bond));
+			}
+			if let Some(proposal_bond_minimum) = proposal_bond_minimum {
+				ProposalBondMinimum::<T>::put(proposal_bond_minimum);
+				Self::deposit_event(Event::<T>::ProposalBondMinimumSet(proposal_bond_minimum));
+			}
+		}
+
+		/// Approve a spend of `amount` of `asset_kind` to `beneficiary`, to be paid out with
+		/// `payout` once valid.
+		///
+		/// May only be called from `T::SpendOrigin`. The amount, converted into the native balance,
+		/// must not exceed the limit carried by the origin.
+		///
+		/// - `asset_kind`: The kind of asset to be spent.
+		/// - `amount`: The amount to be paid out, denominated in `asset_kind`.
+		/// - `beneficiary`: The beneficiary of the spend.
+		/// - `valid_from`: The block from which the spend may be paid out; the current block if
+		///   `None`. The spend expires `T::PayoutPeriod` blocks later.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `SpendCount`
+		/// - DbWrites: `SpendCount`, `Spends`
+		/// # </weight>
+		#[weight = 60_000_000 + T::DbWeight::get().reads_writes(1, 2)]
+		fn spend(
+			origin,
+			asset_kind: T::AssetKind,
+			#[compact] amount: AssetBalanceOf<T>,
+			beneficiary: <T::BeneficiaryLookup as StaticLookup>::Source,
+			valid_from: Option<T::BlockNumber>,
+		) {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(beneficiary)?;
+
+			let native_amount = T::BalanceConverter::from_asset_balance(amount, asset_kind.clone())
+				.map_err(|_| Error::<T>::FailedToConvertBalance)?;
+			ensure!(native_amount <= max_amount, Error::<T>::InsufficientPermission);
+
+			let now = system::Module::<T>::block_number();
+			let valid_from = valid_from.unwrap_or(now);
+			let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
+			ensure!(expire_at > now, Error::<T>::SpendExpired);
+
+			let index = SpendCount::get();
+			SpendCount::put(index + 1);
+			Spends::<T>::insert(index, SpendStatus {
+				asset_kind: asset_kind.clone(),
+				amount,
+				beneficiary: beneficiary.clone(),
+				valid_from,
+				expire_at,
+				status: PaymentState::Pending,
+			});
+
+			Self::deposit_event(Event::<T>::AssetSpendApproved(
+				index,
+				asset_kind,
+				amount,
+				beneficiary,
+				valid_from,
+				expire_at,
+			));
+		}
+
+		/// Pay out an approved spend to its beneficiary through `T::Paymaster`.
+		///
+		/// May be called by any signed origin once the spend is valid and before it expires. A
+		/// spend whose payment failed may be paid out again.
+		///
+		/// The outcome of the payment is recorded by a later call to `check_status`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1), plus the cost of the payment.
+		/// - DbReads: `Spends`
+		/// - DbWrites: `Spends`
+		/// # </weight>
+		#[weight = 90_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn payout(origin, #[compact] index: SpendIndex) {
+			ensure_signed(origin)?;
+
+			let mut spend = Spends::<T>::get(index).ok_or(Error::<T>::InvalidProposalIndex)?;
+			let now = system::Module::<T>::block_number();
+			ensure!(now >= spend.valid_from, Error::<T>::EarlyPayout);
+			ensure!(spend.expire_at > now, Error::<T>::SpendExpired);
+			ensure!(
+				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T>::AlreadyAttempted
+			);
+
+			let id = T::Paymaster::pay(&spend.beneficiary, spend.asset_kind.clone(), spend.amount)
+				.map_err(|_| Error::<T>::PayoutError)?;
+			spend.status = PaymentState::Attempted { id: id.clone() };
+			Spends::<T>::insert(index, spend);
+
+			Self::deposit_event(Event::<T>::Paid(index, id));
+		}
+
+		/// Check the status of the payment of a spend, removing the spend once it has concluded.
+		///
+		/// May be called by any signed origin. A spend is removed if its payment succeeded, or if
+		/// it expired without a payment in progress. If the payment failed, the spend may be paid
+		/// out again until it expires.
+		///
+		/// # <weight>
+		/// - Complexity: O(1), plus the cost of checking the payment.
+		/// - DbReads: `Spends`
+		/// - DbWrites: `Spends`
+		/// # </weight>
+		#[weight = 60_000_000 + T::DbWeight::get().reads_writes(1, 1)]
+		fn check_status(origin, #[compact] index: SpendIndex) {
+			ensure_signed(origin)?;
+
+			let mut spend = Spends::<T>::get(index).ok_or(Error::<T>::InvalidProposalIndex)?;
+			let now = system::Module::<T>::block_number();
+
+			if now > spend.expire_at && !matches!(spend.status, PaymentState::Attempted { .. }) {
+				Spends::<T>::remove(index);
+				Self::deposit_event(Event::<T>::SpendProcessed(index));
+				return Ok(());
+			}
+
+			let id = match spend.status {
+				PaymentState::Attempted { ref id } => id.clone(),
+				_ => return Err(Error::<T>::NotAttempted.into()),
+			};
+
+			match T::Paymaster::check_payment(id.clone()) {
+				PaymentStatus::Success => {
+					Spends::<T>::remove(index);
+					Self::deposit_event(Event::<T>::SpendProcessed(index));
+				},
+				PaymentStatus::Failure => {
+					spend.status = PaymentState::Failed;
+					Spends::<T>::insert(index, spend);
+					Self::deposit_event(Event::<T>::PaymentFailed(index, id));
+				},
+				PaymentStatus::InProgress | PaymentStatus::Unknown =>
+					return Err(Error::<T>::Inconclusive.into()),
+			}
+		}
+
+		/// Void an approved spend which has not yet been paid out.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		///
+		/// # <weight>
+		/// - Complexity: O(1).
+		/// - DbReads: `Spends`
+		/// - DbWrites: `Spends`
+		/// # </weight>
+		#[weight = (40_000_000 + T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational)]
+		fn void_spend(origin, #[compact] index: SpendIndex) {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let spend = Spends::<T>::get(index).ok_or(Error::<T>::InvalidProposalIndex)?;
+			ensure!(
+				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T>::AlreadyAttempted
+			);
+			Spends::<T>::remove(index);
+
+			Self::deposit_event(Event::<T>::AssetSpendVoided(index));
+		}
+
 		/// # <weight>
 		/// - Complexity: `O(A)` where `A` is the number of approvals
-		/// - Db reads and writes: `Approvals`, `pot account data`
+		/// - Db reads: `SpendPeriod`
+		/// - Db reads and writes: `Approvals`, `BountyApprovals`, `pot account data`
 		/// - Db reads and writes per approval:
 		///   `Proposals`, `proposer account data`, `beneficiary account data`
+		/// - Db reads and writes per bounty approval:
+		///   `Bounties`, `proposer account data`, `bounty account data`
 		/// - The weight is overestimated if some approvals got missed.
 		/// # </weight>
 		fn on_initialize(n: T::BlockNumber) -> Weight {
 			// Check to see if we should spend some funds!
-			if (n % T::SpendPeriod::get()).is_zero() {
+			if (n % Self::spend_period()).is_zero() {
 				let approvals_len = Self::spend_funds();
 
 				270_000_000 * approvals_len
-					+ T::DbWeight::get().reads_writes(2 + approvals_len * 3, 2 + approvals_len * 3)
+					+ T::DbWeight::get().reads_writes(5 + approvals_len * 3, 3 + approvals_len * 3)
 			} else {
-				0
+				T::DbWeight::get().reads(1)
 			}
 		}
 	}
@@ -654,9 +1673,36 @@ impl<T: Trait> Module<T> {
 		T::ModuleId::get().into_account()
 	}
 
+	/// The account ID of a bounty account.
+	pub fn bounty_account_id(id: BountyIndex) -> T::AccountId {
+		// only use two byte prefix to support 16 byte account id (used by test)
+		// "modl" ++ "py/trsry" ++ "bt" is 14 bytes, and two bytes remaining for bounty index
+		T::ModuleId::get().into_sub_account(("bt", id))
+	}
+
+	/// The period between successive spends.
+	pub fn spend_period() -> T::BlockNumber {
+		SpendPeriod::<T>::get().unwrap_or_else(T::SpendPeriod::get)
+	}
+
+	/// The percentage of spare funds (if any) that are burnt per spend period.
+	pub fn burn() -> Permill {
+		Burn::get().unwrap_or_else(T::Burn::get)
+	}
+
+	/// The fraction of a proposal's value that should be bonded in order to place the proposal.
+	pub fn proposal_bond() -> Permill {
+		ProposalBond::get().unwrap_or_else(T::ProposalBond::get)
+	}
+
+	/// The minimum amount of funds that should be placed in a deposit for making a proposal.
+	pub fn proposal_bond_minimum() -> BalanceOf<T> {
+		ProposalBondMinimum::<T>::get().unwrap_or_else(T::ProposalBondMinimum::get)
+	}
+
 	/// The needed bond for a proposal whose spend is `value`.
 	fn calculate_bond(value: BalanceOf<T>) -> BalanceOf<T> {
-		T::ProposalBondMinimum::get().max(T::ProposalBond::get() * value)
+		Self::proposal_bond_minimum().max(Self::proposal_bond() * value)
 	}
 
 	/// Given a mutable reference to an `OpenTip`, insert the tip into it and check whether it
@@ -732,7 +1778,46 @@ impl<T: Trait> Module<T> {
 		Self::deposit_event(RawEvent::TipClosed(hash, tip.who, payout));
 	}
 
-	/// Spend some money! returns number of approvals before spend.
+	/// Create a new bounty proposal and reserve the deposit of the proposer.
+	fn create_bounty(
+		proposer: T::AccountId,
+		description: Vec<u8>,
+		value: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(
+			description.len() <= T::MaximumReasonLength::get() as usize,
+			Error::<T>::ReasonTooBig,
+		);
+		ensure!(value >= T::BountyValueMinimum::get(), Error::<T>::InvalidValue);
+
+		let index = Self::bounty_count();
+
+		// reserve deposit for new bounty
+		let bond = T::BountyDepositBase::get()
+			+ T::DataDepositPerByte::get() * (description.len() as u32).into();
+		T::Currency::reserve(&proposer, bond)
+			.map_err(|_| Error::<T>::InsufficientProposersBalance)?;
+
+		BountyCount::put(index + 1);
+
+		let bounty = Bounty {
+			proposer,
+			value,
+			fee: Zero::zero(),
+			curator_deposit: Zero::zero(),
+			bond,
+			status: BountyStatus::Proposed,
+		};
+
+		Bounties::<T>::insert(index, &bounty);
+		BountyDescriptions::insert(index, description);
+
+		Self::deposit_event(RawEvent::BountyProposed(index));
+
+		Ok(())
+	}
+
+	/// Spend some money! returns number of approvals (bounty approvals included) before spend.
 	fn spend_funds() -> u64 {
 		let mut budget_remaining = Self::pot();
 		Self::deposit_event(RawEvent::Spending(budget_remaining));
@@ -767,9 +1852,43 @@ impl<T: Trait> Module<T> {
 			prior_approvals_len
 		});
 
+		let prior_bounty_approvals_len = BountyApprovals::mutate(|v| {
+			let prior_bounty_approvals_len = v.len() as u64;
+			v.retain(|&index| {
+				Bounties::<T>::mutate(index, |bounty| {
+					// Should always be true, but shouldn't panic if false or we're screwed.
+					if let Some(bounty) = bounty {
+						if bounty.value <= budget_remaining {
+							budget_remaining -= bounty.value;
+
+							bounty.status = BountyStatus::Funded;
+
+							// return their deposit.
+							let _ = T::Currency::unreserve(&bounty.proposer, bounty.bond);
+
+							// fund the bounty account
+							let bounty_account = Self::bounty_account_id(index);
+							imbalance.subsume(
+								T::Currency::deposit_creating(&bounty_account, bounty.value)
+							);
+
+							Self::deposit_event(RawEvent::BountyBecameActive(index));
+							false
+						} else {
+							missed_any = true;
+							true
+						}
+					} else {
+						false
+					}
+				})
+			});
+			prior_bounty_approvals_len
+		});
+
 		if !missed_any {
 			// burn some proportion of the remaining budget if we run a surplus.
-			let burn = (T::Burn::get() * budget_remaining).min(budget_remaining);
+			let burn = (Self::burn() * budget_remaining).min(budget_remaining);
 			budget_remaining -= burn;
 			imbalance.subsume(T::Currency::burn(burn));
 			Self::deposit_event(RawEvent::Burnt(burn))
@@ -792,7 +1911,7 @@ impl<T: Trait> Module<T> {
 
 		Self::deposit_event(RawEvent::Rollover(budget_remaining));
 
-		prior_approvals_len
+		prior_approvals_len + prior_bounty_approvals_len
 	}
 
 	/// Return the amount of money in the pot.
@@ -20,7 +20,7 @@
 #![cfg(test)]
 
 use super::*;
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::BTreeMap};
 use frame_support::{
 	assert_noop, assert_ok, impl_outer_origin, impl_outer_event, parameter_types, weights::Weight,
 	traits::{Contains, OnInitialize}
@@ -67,7 +67,7 @@ impl frame_system::Trait for Test {
 	type Call = ();
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
-	type AccountId = u64;
+	type AccountId = u128;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
 	type Event = Event;
@@ -96,19 +96,21 @@ impl pallet_balances::Trait for Test {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
 }
 thread_local! {
-	static TEN_TO_FOURTEEN: RefCell<Vec<u64>> = RefCell::new(vec![10,11,12,13,14]);
+	static TEN_TO_FOURTEEN: RefCell<Vec<u128>> = RefCell::new(vec![10,11,12,13,14]);
 }
 pub struct TenToFourteen;
-impl Contains<u64> for TenToFourteen {
-	fn sorted_members() -> Vec<u64> {
+impl Contains<u128> for TenToFourteen {
+	fn sorted_members() -> Vec<u128> {
 		TEN_TO_FOURTEEN.with(|v| {
 			v.borrow().clone()
 		})
 	}
 	#[cfg(feature = "runtime-benchmarks")]
-	fn add(new: &u64) {
+	fn add(new: &u128) {
 		TEN_TO_FOURTEEN.with(|v| {
 			let mut members = v.borrow_mut();
 			members.push(*new);
@@ -132,12 +134,86 @@ parameter_types! {
 	pub const TipReportDepositBase: u64 = 1;
 	pub const TipReportDepositPerByte: u64 = 1;
 	pub const TreasuryModuleId: ModuleId = ModuleId(*b"py/trsry");
+	pub const BountyDepositBase: u64 = 80;
+	pub const DataDepositPerByte: u64 = 1;
+	pub const BountyDepositPayoutDelay: u64 = 3;
+	pub const BountyUpdatePeriod: u64 = 20;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: u64 = 1;
+	pub const MaximumReasonLength: u32 = 16384;
+	pub const PayoutPeriod: u64 = 5;
+	pub const Two: u64 = 2;
+}
+thread_local! {
+	static PAID: RefCell<BTreeMap<(u128, u32), u64>> = RefCell::new(BTreeMap::new());
+	static STATUS: RefCell<BTreeMap<u64, PaymentStatus>> = RefCell::new(BTreeMap::new());
+	static LAST_ID: RefCell<u64> = RefCell::new(0);
+}
+/// Paymaster which records each payment and reports whatever status the test sets for it.
+pub struct TestPay;
+impl Pay for TestPay {
+	type Balance = u64;
+	type Beneficiary = u128;
+	type AssetKind = u32;
+	type Id = u64;
+	type Error = ();
+
+	fn pay(who: &u128, asset_kind: u32, amount: u64) -> Result<u64, ()> {
+		PAID.with(|p| *p.borrow_mut().entry((*who, asset_kind)).or_default() += amount);
+		Ok(LAST_ID.with(|l| {
+			let id = *l.borrow();
+			*l.borrow_mut() = id + 1;
+			id
+		}))
+	}
+	fn check_payment(id: u64) -> PaymentStatus {
+		STATUS.with(|s| s.borrow().get(&id).cloned().unwrap_or(PaymentStatus::Unknown))
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(_: &u128, _: u32, _: u64) {}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(id: u64) {
+		set_status(id, PaymentStatus::Failure)
+	}
+}
+fn set_status(id: u64, status: PaymentStatus) {
+	STATUS.with(|s| s.borrow_mut().insert(id, status));
+}
+fn paid(who: u128, asset_kind: u32) -> u64 {
+	PAID.with(|p| p.borrow().get(&(who, asset_kind)).cloned().unwrap_or(0))
+}
+/// Converts an asset balance into the native balance by multiplying it with `N`.
+pub struct MulBy<N>(sp_std::marker::PhantomData<N>);
+impl<N: Get<u64>> ConversionFromAssetBalance<u64, u32, u64> for MulBy<N> {
+	type Error = ();
+	fn from_asset_balance(balance: u64, _asset_kind: u32) -> Result<u64, ()> {
+		balance.checked_mul(N::get()).ok_or(())
+	}
+}
+/// Spend origin allowing root any amount and accounts 10 to 13 increasing limits.
+pub struct TestSpendOrigin;
+impl EnsureOrigin<Origin> for TestSpendOrigin {
+	type Success = u64;
+	fn try_origin(o: Origin) -> Result<u64, Origin> {
+		Result::<frame_system::RawOrigin<u128>, Origin>::from(o).and_then(|o| match o {
+			frame_system::RawOrigin::Root => Ok(u64::max_value()),
+			frame_system::RawOrigin::Signed(10) => Ok(5),
+			frame_system::RawOrigin::Signed(11) => Ok(10),
+			frame_system::RawOrigin::Signed(12) => Ok(20),
+			frame_system::RawOrigin::Signed(13) => Ok(50),
+			r => Err(Origin::from(r)),
+		})
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::root()
+	}
 }
 impl Trait for Test {
 	type ModuleId = TreasuryModuleId;
 	type Currency = pallet_balances::Module<Test>;
-	type ApproveOrigin = frame_system::EnsureRoot<u64>;
-	type RejectOrigin = frame_system::EnsureRoot<u64>;
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type RejectOrigin = frame_system::EnsureRoot<u128>;
 	type Tippers = TenToFourteen;
 	type TipCountdown = TipCountdown;
 	type TipFindersFee = TipFindersFee;
@@ -149,6 +225,22 @@ impl Trait for Test {
 	type ProposalBondMinimum = ProposalBondMinimum;
 	type SpendPeriod = SpendPeriod;
 	type Burn = Burn;
+	type ParamsOrigin = frame_system::EnsureRoot<u128>;
+	type BountyDepositBase = BountyDepositBase;
+	type DataDepositPerByte = DataDepositPerByte;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type MaximumReasonLength = MaximumReasonLength;
+	type ChildBountyManager = ();
+	type AssetKind = u32;
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<u128>;
+	type Paymaster = TestPay;
+	type BalanceConverter = MulBy<Two>;
+	type SpendOrigin = TestSpendOrigin;
+	type PayoutPeriod = PayoutPeriod;
 	type WeightInfo = ();
 }
 type System = frame_system::Module<Test>;
@@ -174,7 +266,7 @@ fn genesis_config_works() {
 }
 
 fn tip_hash() -> H256 {
-	BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 3u64))
+	BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 3u128))
 }
 
 #[test]
@@ -223,7 +315,7 @@ fn report_awesome_from_beneficiary_and_tip_works() {
 		assert_ok!(Treasury::report_awesome(Origin::signed(0), b"awesome.dot".to_vec(), 0));
 		assert_eq!(Balances::reserved_balance(0), 12);
 		assert_eq!(Balances::free_balance(0), 88);
-		let h = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u64));
+		let h = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
 		assert_ok!(Treasury::tip(Origin::signed(10), h.clone(), 10));
 		assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
 		assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10));
@@ -560,6 +652,637 @@ fn inexistent_account_works() {
 	});
 }
 
+#[test]
+fn propose_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_eq!(Treasury::pot(), 100);
+
+		assert_ok!(Treasury::propose_bounty(Origin::signed(0), 10, b"1234567890".to_vec()));
+
+		let deposit: u64 = 80 + 10;
+		assert_eq!(Balances::reserved_balance(0), deposit);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 0,
+			curator_deposit: 0,
+			value: 10,
+			bond: deposit,
+			status: BountyStatus::Proposed,
+		});
+
+		assert_eq!(Treasury::bounty_descriptions(0).unwrap(), b"1234567890".to_vec());
+		assert_eq!(Treasury::bounty_count(), 1);
+	});
+}
+
+#[test]
+fn propose_bounty_validation_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_eq!(Treasury::pot(), 100);
+
+		assert_noop!(
+			Treasury::propose_bounty(Origin::signed(1), 0, [0; 17_000].to_vec()),
+			Error::<Test>::ReasonTooBig
+		);
+
+		assert_noop!(
+			Treasury::propose_bounty(Origin::signed(1), 10, b"12345678901234567890".to_vec()),
+			Error::<Test>::InsufficientProposersBalance
+		);
+
+		assert_noop!(
+			Treasury::propose_bounty(Origin::signed(1), 0, b"12345678901234567890".to_vec()),
+			Error::<Test>::InvalidValue
+		);
+	});
+}
+
+#[test]
+fn close_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_noop!(Treasury::close_bounty(Origin::root(), 0), Error::<Test>::InvalidProposalIndex);
+
+		assert_ok!(Treasury::propose_bounty(Origin::signed(0), 10, b"12345".to_vec()));
+
+		assert_noop!(Treasury::close_bounty(Origin::signed(0), 0), BadOrigin);
+		assert_ok!(Treasury::close_bounty(Origin::root(), 0));
+
+		let deposit: u64 = 80 + 5;
+
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+
+		assert_eq!(Treasury::bounties(0), None);
+		assert_eq!(Treasury::bounty_descriptions(0), None);
+	});
+}
+
+#[test]
+fn approve_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_noop!(
+			Treasury::approve_bounty(Origin::root(), 0),
+			Error::<Test>::InvalidProposalIndex
+		);
+
+		assert_ok!(Treasury::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_noop!(Treasury::approve_bounty(Origin::signed(0), 0), BadOrigin);
+		assert_ok!(Treasury::approve_bounty(Origin::root(), 0));
+
+		let deposit: u64 = 80 + 5;
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 0,
+			value: 50,
+			curator_deposit: 0,
+			bond: deposit,
+			status: BountyStatus::Approved,
+		});
+		assert_eq!(Treasury::bounty_approvals(), vec![0]);
+
+		assert_noop!(Treasury::close_bounty(Origin::root(), 0), Error::<Test>::UnexpectedStatus);
+		assert_noop!(Treasury::approve_bounty(Origin::root(), 0), Error::<Test>::UnexpectedStatus);
+
+		// deposit not returned yet
+		assert_eq!(Balances::reserved_balance(0), deposit);
+		assert_eq!(Balances::free_balance(0), 100 - deposit);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		// return deposit
+		assert_eq!(Balances::reserved_balance(0), 0);
+		assert_eq!(Balances::free_balance(0), 100);
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 0,
+			curator_deposit: 0,
+			value: 50,
+			bond: deposit,
+			status: BountyStatus::Funded,
+		});
+
+		assert_eq!(Treasury::pot(), 100 - 50 - 25); // burn 25
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 50);
+		assert_eq!(Treasury::bounty_approvals(), vec![]);
+	});
+}
+
+#[test]
+fn approved_bounty_waits_for_funds() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Treasury::propose_bounty(Origin::signed(0), 150, b"12345".to_vec()));
+		assert_ok!(Treasury::approve_bounty(Origin::root(), 0));
+
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		// Nothing is funded and nothing is burnt while the bounty is missed.
+		assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Approved);
+		assert_eq!(Treasury::bounty_approvals(), vec![0]);
+		assert_eq!(Treasury::pot(), 100);
+		assert_eq!(Balances::reserved_balance(0), 85);
+	});
+}
+
+fn create_funded_bounty() {
+	Balances::make_free_balance_be(&Treasury::account_id(), 101);
+	assert_ok!(Treasury::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+	assert_ok!(Treasury::approve_bounty(Origin::root(), 0));
+
+	System::set_block_number(2);
+	<Treasury as OnInitialize<u64>>::on_initialize(2);
+	assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Funded);
+}
+
+#[test]
+fn assign_curator_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_noop!(
+			Treasury::propose_curator(Origin::root(), 0, 4, 4),
+			Error::<Test>::InvalidProposalIndex
+		);
+
+		create_funded_bounty();
+
+		assert_noop!(Treasury::propose_curator(Origin::signed(1), 0, 4, 4), BadOrigin);
+		assert_noop!(
+			Treasury::propose_curator(Origin::root(), 0, 4, 50),
+			Error::<Test>::InvalidFee
+		);
+
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 4));
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 4,
+			curator_deposit: 0,
+			value: 50,
+			bond: 85,
+			status: BountyStatus::CuratorProposed {
+				curator: 4,
+			},
+		});
+
+		assert_noop!(Treasury::accept_curator(Origin::signed(1), 0), Error::<Test>::RequireCurator);
+		assert_noop!(
+			Treasury::accept_curator(Origin::signed(4), 0),
+			pallet_balances::Error::<Test, _>::InsufficientBalance
+		);
+
+		Balances::make_free_balance_be(&4, 10);
+
+		assert_ok!(Treasury::accept_curator(Origin::signed(4), 0));
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 4,
+			curator_deposit: 2,
+			value: 50,
+			bond: 85,
+			status: BountyStatus::Active {
+				curator: 4,
+				update_due: 22,
+			},
+		});
+
+		assert_eq!(Balances::free_balance(&4), 8);
+		assert_eq!(Balances::reserved_balance(&4), 2);
+	});
+}
+
+#[test]
+fn unassign_curator_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_funded_bounty();
+
+		assert_noop!(Treasury::unassign_curator(Origin::root(), 0), Error::<Test>::UnexpectedStatus);
+
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 4));
+
+		assert_noop!(Treasury::unassign_curator(Origin::signed(1), 0), BadOrigin);
+
+		assert_ok!(Treasury::unassign_curator(Origin::signed(4), 0));
+
+		assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Funded);
+
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 4));
+
+		Balances::make_free_balance_be(&4, 10);
+
+		assert_ok!(Treasury::accept_curator(Origin::signed(4), 0));
+
+		// The curator giving up the role gets the deposit back.
+		assert_ok!(Treasury::unassign_curator(Origin::signed(4), 0));
+		assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(&4), 10);
+		assert_eq!(Balances::reserved_balance(&4), 0);
+
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 4));
+		assert_ok!(Treasury::accept_curator(Origin::signed(4), 0));
+
+		// The reject origin slashes the curator.
+		assert_ok!(Treasury::unassign_curator(Origin::root(), 0));
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 4,
+			curator_deposit: 0,
+			value: 50,
+			bond: 85,
+			status: BountyStatus::Funded,
+		});
+
+		assert_eq!(Balances::free_balance(&4), 8);
+		assert_eq!(Balances::reserved_balance(&4), 0); // slashed 2
+	});
+}
+
+#[test]
+fn award_and_claim_bounty_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_funded_bounty();
+
+		Balances::make_free_balance_be(&4, 10);
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 4));
+		assert_ok!(Treasury::accept_curator(Origin::signed(4), 0));
+
+		assert_eq!(Balances::free_balance(4), 8); // inital 10 - 2 deposit
+
+		assert_noop!(Treasury::award_bounty(Origin::signed(1), 0, 3), Error::<Test>::RequireCurator);
+
+		assert_ok!(Treasury::award_bounty(Origin::signed(4), 0, 3));
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 4,
+			curator_deposit: 2,
+			value: 50,
+			bond: 85,
+			status: BountyStatus::PendingPayout {
+				curator: 4,
+				beneficiary: 3,
+				unlock_at: 5
+			},
+		});
+
+		assert_noop!(Treasury::claim_bounty(Origin::signed(1), 0), Error::<Test>::Premature);
+
+		System::set_block_number(5);
+		<Treasury as OnInitialize<u64>>::on_initialize(5);
+
+		assert_ok!(Balances::transfer(Origin::signed(0), Treasury::bounty_account_id(0), 10));
+
+		assert_ok!(Treasury::claim_bounty(Origin::signed(1), 0));
+
+		assert_eq!(Balances::free_balance(4), 14); // initial 10 + fee 4
+		assert_eq!(Balances::free_balance(3), 56);
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 0);
+
+		assert_eq!(Treasury::bounties(0), None);
+		assert_eq!(Treasury::bounty_descriptions(0), None);
+	});
+}
+
+#[test]
+fn cancel_and_refund() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_funded_bounty();
+
+		assert_ok!(Balances::transfer(Origin::signed(0), Treasury::bounty_account_id(0), 10));
+
+		assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 60);
+
+		assert_noop!(Treasury::close_bounty(Origin::signed(0), 0), BadOrigin);
+
+		assert_ok!(Treasury::close_bounty(Origin::root(), 0));
+
+		assert_eq!(Treasury::pot(), 85); // - 25 + 10
+		assert_eq!(Treasury::bounties(0), None);
+	});
+}
+
+#[test]
+fn award_and_cancel() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_funded_bounty();
+
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 0, 10));
+		assert_ok!(Treasury::accept_curator(Origin::signed(0), 0));
+
+		assert_eq!(Balances::free_balance(0), 95);
+		assert_eq!(Balances::reserved_balance(0), 5);
+
+		assert_ok!(Treasury::award_bounty(Origin::signed(0), 0, 3));
+
+		// Cannot close bounty directly when payout is happening...
+		assert_noop!(Treasury::close_bounty(Origin::root(), 0), Error::<Test>::PendingPayout);
+
+		// Instead unassign the curator to slash them and then close.
+		assert_ok!(Treasury::unassign_curator(Origin::root(), 0));
+		assert_ok!(Treasury::close_bounty(Origin::root(), 0));
+
+		// Slashed.
+		assert_eq!(Balances::free_balance(0), 95);
+		assert_eq!(Balances::reserved_balance(0), 0);
+
+		assert_eq!(Treasury::bounties(0), None);
+		assert_eq!(Treasury::bounty_descriptions(0), None);
+	});
+}
+
+#[test]
+fn expire_and_unassign() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_funded_bounty();
+
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 1, 10));
+		assert_ok!(Treasury::accept_curator(Origin::signed(1), 0));
+
+		assert_eq!(Balances::free_balance(1), 93);
+		assert_eq!(Balances::reserved_balance(1), 5);
+
+		System::set_block_number(22);
+		<Treasury as OnInitialize<u64>>::on_initialize(22);
+
+		assert_noop!(Treasury::unassign_curator(Origin::signed(0), 0), Error::<Test>::Premature);
+
+		System::set_block_number(23);
+		<Treasury as OnInitialize<u64>>::on_initialize(23);
+
+		assert_ok!(Treasury::unassign_curator(Origin::signed(0), 0));
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 10,
+			curator_deposit: 0,
+			value: 50,
+			bond: 85,
+			status: BountyStatus::Funded,
+		});
+
+		assert_eq!(Balances::free_balance(1), 93);
+		assert_eq!(Balances::reserved_balance(1), 0); // slashed
+	});
+}
+
+#[test]
+fn extend_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_funded_bounty();
+
+		Balances::make_free_balance_be(&4, 10);
+
+		assert_noop!(
+			Treasury::extend_bounty_expiry(Origin::signed(1), 0, Vec::new()),
+			Error::<Test>::UnexpectedStatus
+		);
+
+		assert_ok!(Treasury::propose_curator(Origin::root(), 0, 4, 10));
+		assert_ok!(Treasury::accept_curator(Origin::signed(4), 0));
+
+		assert_eq!(Balances::free_balance(4), 5);
+		assert_eq!(Balances::reserved_balance(4), 5);
+
+		System::set_block_number(10);
+		<Treasury as OnInitialize<u64>>::on_initialize(10);
+
+		assert_noop!(
+			Treasury::extend_bounty_expiry(Origin::signed(0), 0, Vec::new()),
+			Error::<Test>::RequireCurator
+		);
+		assert_ok!(Treasury::extend_bounty_expiry(Origin::signed(4), 0, Vec::new()));
+
+		assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+			proposer: 0,
+			fee: 10,
+			curator_deposit: 5,
+			value: 50,
+			bond: 85,
+			status: BountyStatus::Active { curator: 4, update_due: 30 },
+		});
+
+		System::set_block_number(25);
+
+		assert_noop!(Treasury::unassign_curator(Origin::signed(0), 0), Error::<Test>::Premature);
+		assert_ok!(Treasury::unassign_curator(Origin::signed(4), 0));
+
+		assert_eq!(Balances::free_balance(4), 10); // not slashed
+		assert_eq!(Balances::reserved_balance(4), 0);
+	});
+}
+
+#[test]
+fn set_params_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Treasury::spend_period(), 2);
+		assert_eq!(Treasury::burn(), Permill::from_percent(50));
+		assert_eq!(Treasury::proposal_bond(), Permill::from_percent(5));
+		assert_eq!(Treasury::proposal_bond_minimum(), 1);
+
+		assert_noop!(Treasury::set_params(Origin::signed(1), Some(3), None, None, None), BadOrigin);
+		assert_noop!(
+			Treasury::set_params(Origin::root(), Some(0), None, None, None),
+			Error::<Test>::InvalidSpendPeriod
+		);
+
+		let burn = Some(Permill::zero());
+		assert_ok!(Treasury::set_params(Origin::root(), Some(3), burn, None, Some(10)));
+		assert_eq!(Treasury::spend_period(), 3);
+		assert_eq!(Treasury::burn(), Permill::zero());
+		assert_eq!(Treasury::proposal_bond(), Permill::from_percent(5));
+		assert_eq!(Treasury::proposal_bond_minimum(), 10);
+
+		let events: Vec<_> = System::events().into_iter().map(|r| r.event)
+			.filter_map(|e| {
+				if let Event::treasury(inner) = e { Some(inner) } else { None }
+			})
+			.collect();
+		assert_eq!(events, vec![
+			RawEvent::SpendPeriodSet(3),
+			RawEvent::BurnSet(Permill::zero()),
+			RawEvent::ProposalBondMinimumSet(10),
+		]);
+	});
+}
+
+#[test]
+fn changed_params_take_effect() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		let burn = Some(Permill::zero());
+		assert_ok!(Treasury::set_params(Origin::root(), Some(3), burn, None, Some(10)));
+
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 20, 3));
+		assert_eq!(Balances::reserved_balance(0), 10);
+
+		// Nothing is spent at the old spend period.
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+		assert_eq!(Treasury::pot(), 100);
+
+		// Nothing is burnt at the new one.
+		<Treasury as OnInitialize<u64>>::on_initialize(3);
+		assert_eq!(Treasury::pot(), 100);
+	});
+}
+
+#[test]
+fn spend_origin_works() {
+	new_test_ext().execute_with(|| {
+		// An amount of 1 is worth 2 in the native balance.
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 1, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_noop!(
+			Treasury::spend(Origin::signed(10), 1, 3, 6, None),
+			Error::<Test>::InsufficientPermission
+		);
+		assert_ok!(Treasury::spend(Origin::signed(11), 1, 5, 6, None));
+		assert_noop!(
+			Treasury::spend(Origin::signed(11), 1, 6, 6, None),
+			Error::<Test>::InsufficientPermission
+		);
+		assert_ok!(Treasury::spend(Origin::signed(12), 1, 10, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(13), 1, 25, 6, None));
+		assert_ok!(Treasury::spend(Origin::root(), 1, 1_000, 6, None));
+		assert_noop!(Treasury::spend(Origin::signed(1), 1, 1, 6, None), BadOrigin);
+		assert_noop!(
+			Treasury::spend(Origin::root(), 1, u64::max_value(), 6, None),
+			Error::<Test>::FailedToConvertBalance
+		);
+
+		assert_eq!(Treasury::spend_count(), 6);
+	});
+}
+
+#[test]
+fn spend_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_eq!(Treasury::spends(0).unwrap(), SpendStatus {
+			asset_kind: 1,
+			amount: 2,
+			beneficiary: 6,
+			valid_from: 1,
+			expire_at: 6,
+			status: PaymentState::Pending,
+		});
+		assert_eq!(
+			System::events().last().unwrap().event,
+			Event::treasury(RawEvent::AssetSpendApproved(0, 1, 2, 6, 1, 6)),
+		);
+
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, Some(10)));
+		assert_eq!(Treasury::spends(1).unwrap().expire_at, 15);
+
+		System::set_block_number(20);
+		assert_noop!(
+			Treasury::spend(Origin::signed(10), 1, 2, 6, Some(10)),
+			Error::<Test>::SpendExpired
+		);
+	});
+}
+
+#[test]
+fn payout_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, Some(3)));
+
+		assert_noop!(Treasury::payout(Origin::signed(1), 2), Error::<Test>::InvalidProposalIndex);
+		assert_noop!(Treasury::payout(Origin::signed(1), 1), Error::<Test>::EarlyPayout);
+
+		assert_ok!(Treasury::payout(Origin::signed(1), 0));
+		assert_eq!(paid(6, 1), 2);
+		assert_eq!(Treasury::spends(0).unwrap().status, PaymentState::Attempted { id: 0 });
+		assert_noop!(Treasury::payout(Origin::signed(1), 0), Error::<Test>::AlreadyAttempted);
+
+		System::set_block_number(8);
+		assert_noop!(Treasury::payout(Origin::signed(1), 1), Error::<Test>::SpendExpired);
+	});
+}
+
+#[test]
+fn check_status_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_noop!(Treasury::check_status(Origin::signed(1), 0), Error::<Test>::NotAttempted);
+
+		assert_ok!(Treasury::payout(Origin::signed(1), 0));
+		assert_noop!(Treasury::check_status(Origin::signed(1), 0), Error::<Test>::Inconclusive);
+		set_status(0, PaymentStatus::InProgress);
+		assert_noop!(Treasury::check_status(Origin::signed(1), 0), Error::<Test>::Inconclusive);
+
+		// A failed payment may be retried.
+		set_status(0, PaymentStatus::Failure);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 0));
+		assert_eq!(Treasury::spends(0).unwrap().status, PaymentState::Failed);
+		assert_ok!(Treasury::payout(Origin::signed(1), 0));
+		assert_eq!(paid(6, 1), 4);
+		assert_eq!(Treasury::spends(0).unwrap().status, PaymentState::Attempted { id: 1 });
+
+		set_status(1, PaymentStatus::Success);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 0));
+		assert_eq!(Treasury::spends(0), None);
+
+		// An expired spend is removed, unless a payment is in progress.
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::payout(Origin::signed(1), 2));
+		System::set_block_number(7);
+		assert_ok!(Treasury::check_status(Origin::signed(1), 1));
+		assert_eq!(Treasury::spends(1), None);
+		assert_noop!(Treasury::check_status(Origin::signed(1), 2), Error::<Test>::Inconclusive);
+	});
+}
+
+#[test]
+fn void_spend_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::spend(Origin::signed(10), 1, 2, 6, None));
+		assert_ok!(Treasury::payout(Origin::signed(1), 1));
+
+		assert_noop!(Treasury::void_spend(Origin::signed(1), 0), BadOrigin);
+		assert_noop!(Treasury::void_spend(Origin::root(), 1), Error::<Test>::AlreadyAttempted);
+		assert_noop!(Treasury::void_spend(Origin::root(), 2), Error::<Test>::InvalidProposalIndex);
+
+		assert_ok!(Treasury::void_spend(Origin::root(), 0));
+		assert_eq!(Treasury::spends(0), None);
+	});
+}
+
 #[test]
 fn test_last_reward_migration() {
 	use sp_storage::Storage;
@@ -588,9 +1311,9 @@ fn test_last_reward_migration() {
 	}
 
 	let reason1 = BlakeTwo256::hash(b"reason1");
-	let hash1 = BlakeTwo256::hash_of(&(reason1, 10u64));
+	let hash1 = BlakeTwo256::hash_of(&(reason1, 10u128));
 
-	let old_tip_finder = OldOpenTip::<u64, u64, u64, H256> {
+	let old_tip_finder = OldOpenTip::<u128, u64, u64, H256> {
 		reason: reason1,
 		who: 10,
 		finder: Some((20, 30)),
@@ -599,9 +1322,9 @@ fn test_last_reward_migration() {
 	};
 
 	let reason2 = BlakeTwo256::hash(b"reason2");
-	let hash2 = BlakeTwo256::hash_of(&(reason2, 20u64));
+	let hash2 = BlakeTwo256::hash_of(&(reason2, 20u128));
 
-	let old_tip_no_finder = OldOpenTip::<u64, u64, u64, H256> {
+	let old_tip_no_finder = OldOpenTip::<u128, u64, u64, H256> {
 		reason: reason2,
 		who: 20,
 		finder: None,
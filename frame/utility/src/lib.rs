@@ -27,7 +27,9 @@
 //! - Batch dispatch: A stateless operation, allowing any origin to execute multiple calls in a
 //!   single dispatch. This can be useful to amalgamate proposals, combining `set_code` with
 //!   corresponding `set_storage`s, for efficient multiple payouts with just a single signature
-//!   verify, or in combination with one of the other two dispatch functionality.
+//!   verify, or in combination with one of the other two dispatch functionality. Batches may
+//!   stop at the first failing call (`batch`), be reverted entirely should any call fail
+//!   (`batch_all`) or continue past failing calls (`force_batch`).
 //! - Pseudonymal dispatch: A stateless operation, allowing a signed origin to execute a call from
 //!   an alternative signed origin. Each account has 2 * 2**16 possible "pseudonyms" (alternative
 //!   account IDs) and these can be stacked. This can be useful as a key management tool, where you
@@ -44,7 +46,10 @@
 //! ### Dispatchable Functions
 //!
 //! #### For batch dispatch
-//! * `batch` - Dispatch multiple calls from the sender's origin.
+//! * `batch` - Dispatch multiple calls from the sender's origin, stopping at the first error.
+//! * `batch_all` - Dispatch multiple calls from the sender's origin, reverting all of them if any
+//!   fails.
+//! * `force_batch` - Dispatch multiple calls from the sender's origin, continuing past errors.
 //!
 //! #### For pseudonymal dispatch
 //! * `as_derivative` - Dispatch a call from a derivative signed origin.
@@ -62,7 +67,9 @@ use sp_io::hashing::blake2_256;
 use frame_support::{decl_module, decl_event, decl_storage, Parameter};
 use frame_support::{
 	traits::{OriginTrait, UnfilteredDispatchable},
-	weights::{Weight, GetDispatchInfo, DispatchClass}, dispatch::PostDispatchInfo,
+	weights::{Weight, GetDispatchInfo, DispatchClass, extract_actual_weight},
+	dispatch::{PostDispatchInfo, DispatchResultWithPostInfo},
+	storage::{with_transaction, TransactionOutcome},
 };
 use frame_system::{ensure_signed, ensure_root};
 use sp_runtime::{DispatchError, DispatchResult, traits::Dispatchable};
@@ -73,11 +80,15 @@ mod benchmarking;
 pub trait WeightInfo {
 	fn batch(c: u32, ) -> Weight;
 	fn as_derivative(u: u32, ) -> Weight;
+	fn batch_all(c: u32, ) -> Weight;
+	fn force_batch(c: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
 	fn batch(_c: u32, ) -> Weight { 1_000_000_000 }
 	fn as_derivative(_u: u32, ) -> Weight { 1_000_000_000 }
+	fn batch_all(_c: u32, ) -> Weight { 1_000_000_000 }
+	fn force_batch(_c: u32, ) -> Weight { 1_000_000_000 }
 }
 
 /// Configuration trait.
@@ -106,6 +117,12 @@ decl_event! {
 		BatchInterrupted(u32, DispatchError),
 		/// Batch of dispatches completed fully with no error.
 		BatchCompleted,
+		/// Batch of dispatches completed but has errors.
+		BatchCompletedWithErrors,
+		/// A single item within a Batch of dispatches has completed with no error.
+		ItemCompleted,
+		/// A single item within a Batch of dispatches has completed with error.
+		ItemFailed(DispatchError),
 	}
 }
 
@@ -117,6 +134,29 @@ impl TypeId for IndexedUtilityModuleId {
 	const TYPE_ID: [u8; 4] = *b"suba";
 }
 
+/// The weight of dispatching a batch of `calls` excluding the calls themselves: 15 µs, plus 1 µs
+/// for each call.
+fn batch_base_weight(calls: usize) -> Weight {
+	15_000_000u64.saturating_add(1_000_000u64.saturating_mul(calls as Weight))
+}
+
+/// The weight and class of a batch of `calls`. The class is only `Operational` if all of the
+/// calls are themselves `Operational`.
+fn batch_weight<T: Trait>(calls: &[<T as Trait>::Call]) -> (Weight, DispatchClass) {
+	let weight = calls.iter()
+		.map(|call| call.get_dispatch_info().weight)
+		.fold(batch_base_weight(calls.len()), |a: Weight, n| a.saturating_add(n));
+	let all_operational = calls.iter()
+		.map(|call| call.get_dispatch_info().class)
+		.all(|class| class == DispatchClass::Operational);
+	let class = if all_operational {
+		DispatchClass::Operational
+	} else {
+		DispatchClass::Normal
+	};
+	(weight, class)
+}
+
 decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		/// Deposit one of this module's events by using the default implementation.
@@ -138,39 +178,119 @@ decl_module! {
 		/// # </weight>
 		///
 		/// This will return `Ok` in all circumstances. To determine the success of the batch, an
-		/// event is deposited. If a call failed and the batch was interrupted, then the
-		/// `BatchInterrupted` event is deposited, along with the number of successful calls made
-		/// and the error of the failed call. If all were successful, then the `BatchCompleted`
-		/// event is deposited.
-		#[weight = (
-			calls.iter()
-				.map(|call| call.get_dispatch_info().weight)
-				.fold(15_000_000, |a: Weight, n| a.saturating_add(n).saturating_add(1_000_000)),
-			{
-				let all_operational = calls.iter()
-					.map(|call| call.get_dispatch_info().class)
-					.all(|class| class == DispatchClass::Operational);
-				if all_operational {
-					DispatchClass::Operational
-				} else {
-					DispatchClass::Normal
-				}
-			},
-		)]
-		fn batch(origin, calls: Vec<<T as Trait>::Call>) {
+		/// event is deposited. An `ItemCompleted` event is deposited for each successful call. If
+		/// a call failed and the batch was interrupted, then the `BatchInterrupted` event is
+		/// deposited, along with the number of successful calls made and the error of the failed
+		/// call. If all were successful, then the `BatchCompleted` event is deposited.
+		///
+		/// Only the weight of the calls which were actually dispatched is charged.
+		#[weight = batch_weight::<T>(calls)]
+		fn batch(origin, calls: Vec<<T as Trait>::Call>) -> DispatchResultWithPostInfo {
 			let is_root = ensure_root(origin.clone()).is_ok();
+			let calls_len = calls.len();
+			// Track the actual weight of the dispatched calls.
+			let mut weight: Weight = 0;
 			for (index, call) in calls.into_iter().enumerate() {
-				let result = if is_root {
-					call.dispatch_bypass_filter(origin.clone())
-				} else {
-					call.dispatch(origin.clone())
-				};
+				let info = call.get_dispatch_info();
+				let result = Self::dispatch_one(call, origin.clone(), is_root);
+				weight = weight.saturating_add(extract_actual_weight(&result, &info));
 				if let Err(e) = result {
 					Self::deposit_event(Event::BatchInterrupted(index as u32, e.error));
-					return Ok(());
+					let base_weight = batch_base_weight(index.saturating_add(1));
+					return Ok(Some(base_weight.saturating_add(weight)).into());
 				}
+				Self::deposit_event(Event::ItemCompleted);
 			}
 			Self::deposit_event(Event::BatchCompleted);
+			Ok(Some(batch_base_weight(calls_len).saturating_add(weight)).into())
+		}
+
+		/// Send a batch of dispatch calls and atomically execute them.
+		/// The whole transaction will rollback and fail if any of the calls failed.
+		///
+		/// May be called from any origin.
+		///
+		/// - `calls`: The calls to be dispatched from the same origin.
+		///
+		/// If origin is root then call are dispatch without checking origin filter. (This includes
+		/// bypassing `frame_system::Trait::BaseCallFilter`).
+		///
+		/// # <weight>
+		/// - Base weight: 15 + 1 * c µs
+		/// - Plus the sum of the weights of the `calls`.
+		/// - Plus one additional event. (repeat read/write)
+		/// # </weight>
+		///
+		/// Fails with the error of the first failing call, in which case no changes made by any of
+		/// the calls are kept. Only the weight of the calls which were actually dispatched is
+		/// charged.
+		#[weight = batch_weight::<T>(calls)]
+		fn batch_all(origin, calls: Vec<<T as Trait>::Call>) -> DispatchResultWithPostInfo {
+			let is_root = ensure_root(origin.clone()).is_ok();
+			let calls_len = calls.len();
+			with_transaction(|| {
+				// Track the actual weight of the dispatched calls.
+				let mut weight: Weight = 0;
+				for (index, call) in calls.into_iter().enumerate() {
+					let info = call.get_dispatch_info();
+					let result = Self::dispatch_one(call, origin.clone(), is_root);
+					weight = weight.saturating_add(extract_actual_weight(&result, &info));
+					if let Err(mut e) = result {
+						let base_weight = batch_base_weight(index.saturating_add(1));
+						e.post_info = Some(base_weight.saturating_add(weight)).into();
+						return TransactionOutcome::Rollback(Err(e));
+					}
+					Self::deposit_event(Event::ItemCompleted);
+				}
+				Self::deposit_event(Event::BatchCompleted);
+				let base_weight = batch_base_weight(calls_len);
+				TransactionOutcome::Commit(Ok(Some(base_weight.saturating_add(weight)).into()))
+			})
+		}
+
+		/// Send a batch of dispatch calls, dispatching every call regardless of whether the
+		/// previous ones succeeded.
+		///
+		/// May be called from any origin.
+		///
+		/// - `calls`: The calls to be dispatched from the same origin.
+		///
+		/// If origin is root then call are dispatch without checking origin filter. (This includes
+		/// bypassing `frame_system::Trait::BaseCallFilter`).
+		///
+		/// # <weight>
+		/// - Base weight: 15 + 1 * c µs
+		/// - Plus the sum of the weights of the `calls`.
+		/// - Plus one additional event per call. (repeat read/write)
+		/// # </weight>
+		///
+		/// This will return `Ok` in all circumstances. An `ItemCompleted` or `ItemFailed` event
+		/// is deposited for each call, followed by `BatchCompleted` if all of them were
+		/// successful or `BatchCompletedWithErrors` otherwise.
+		#[weight = batch_weight::<T>(calls)]
+		fn force_batch(origin, calls: Vec<<T as Trait>::Call>) -> DispatchResultWithPostInfo {
+			let is_root = ensure_root(origin.clone()).is_ok();
+			let calls_len = calls.len();
+			// Track the actual weight of the dispatched calls.
+			let mut weight: Weight = 0;
+			let mut has_error = false;
+			for call in calls.into_iter() {
+				let info = call.get_dispatch_info();
+				let result = Self::dispatch_one(call, origin.clone(), is_root);
+				weight = weight.saturating_add(extract_actual_weight(&result, &info));
+				if let Err(e) = result {
+					has_error = true;
+					Self::deposit_event(Event::ItemFailed(e.error));
+				} else {
+					Self::deposit_event(Event::ItemCompleted);
+				}
+			}
+			if has_error {
+				Self::deposit_event(Event::BatchCompletedWithErrors);
+			} else {
+				Self::deposit_event(Event::BatchCompleted);
+			}
+			Ok(Some(batch_base_weight(calls_len).saturating_add(weight)).into())
 		}
 
 		/// Send a call through an indexed pseudonym of the sender.
@@ -206,6 +326,19 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
+	/// Dispatch `call` from `origin`, bypassing the origin's filter if it is root.
+	fn dispatch_one(
+		call: <T as Trait>::Call,
+		origin: T::Origin,
+		is_root: bool,
+	) -> DispatchResultWithPostInfo {
+		if is_root {
+			call.dispatch_bypass_filter(origin)
+		} else {
+			call.dispatch(origin)
+		}
+	}
+
 	/// Derive a derivative account ID from the owner account and the sub-account index.
 	pub fn derivative_account_id(who: T::AccountId, index: u16) -> T::AccountId {
 		let entropy = (b"modlpy/utilisuba", who, index).using_encoded(blake2_256);
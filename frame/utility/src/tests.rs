@@ -95,6 +95,8 @@ impl pallet_balances::Trait for Test {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
 }
 parameter_types! {
 	pub const MultisigDepositBase: u64 = 1;
@@ -123,6 +125,7 @@ type Utility = Module<Test>;
 
 use pallet_balances::Call as BalancesCall;
 use pallet_balances::Error as BalancesError;
+use super::Call as UtilityCall;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
@@ -236,3 +239,92 @@ fn batch_early_exit_works() {
 		assert_eq!(Balances::free_balance(2), 15);
 	});
 }
+
+#[test]
+fn batch_weight_is_refunded_for_undispatched_calls() {
+	new_test_ext().execute_with(|| {
+		let call = Call::Balances(BalancesCall::transfer(2, 5));
+		let failing_call = Call::Balances(BalancesCall::transfer(2, 10));
+		let info = call.get_dispatch_info();
+		let calls = vec![call.clone(), failing_call, call];
+		let batch_info = Call::Utility(UtilityCall::batch(calls.clone())).get_dispatch_info();
+		assert_eq!(batch_info.weight, batch_base_weight(3) + 3 * info.weight);
+
+		// Only the first two calls are executed.
+		let result = Utility::batch(Origin::signed(1), calls);
+		assert_eq!(result.unwrap().actual_weight, Some(batch_base_weight(2) + 2 * info.weight));
+	});
+}
+
+#[test]
+fn batch_all_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		assert_ok!(
+			Utility::batch_all(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 5)),
+				Call::Balances(BalancesCall::transfer(2, 5))
+			]),
+		);
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 20);
+		expect_event(Event::BatchCompleted);
+	});
+}
+
+#[test]
+fn batch_all_revert() {
+	new_test_ext().execute_with(|| {
+		let call = Call::Balances(BalancesCall::transfer(2, 5));
+		let info = call.get_dispatch_info();
+
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		let result = Utility::batch_all(Origin::signed(1), vec![
+			call.clone(),
+			Call::Balances(BalancesCall::transfer(2, 10)),
+			call,
+		]);
+		let err = result.unwrap_err();
+		assert_eq!(err.error, BalancesError::<Test, _>::InsufficientBalance.into());
+		assert_eq!(err.post_info.actual_weight, Some(batch_base_weight(2) + 2 * info.weight));
+		// The first, successful, transfer is reverted as well.
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		assert!(!System::events().iter().any(|r| r.event == Event::ItemCompleted.into()));
+	});
+}
+
+#[test]
+fn force_batch_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		assert_ok!(
+			Utility::force_batch(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 5)),
+				Call::Balances(BalancesCall::transfer(2, 10)),
+				Call::Balances(BalancesCall::transfer(2, 5)),
+			]),
+		);
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 20);
+		let events = System::events().into_iter().rev().take(4).rev()
+			.map(|r| r.event)
+			.collect::<Vec<_>>();
+		assert_eq!(events, vec![
+			Event::ItemCompleted.into(),
+			Event::ItemFailed(BalancesError::<Test, _>::InsufficientBalance.into()).into(),
+			Event::ItemCompleted.into(),
+			Event::BatchCompletedWithErrors.into(),
+		]);
+
+		assert_ok!(
+			Utility::force_batch(Origin::signed(2), vec![
+				Call::Balances(BalancesCall::transfer(1, 5)),
+			]),
+		);
+		expect_event(Event::BatchCompleted);
+	});
+}
@@ -193,6 +193,34 @@ benchmarks! {
 			"Lock not created",
 		);
 	}
+
+	merge_schedules {
+		let l in 0 .. MAX_LOCKS;
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		add_locks::<T>(&caller, l as u8);
+		// Two schedules of 100 each.
+		add_vesting_schedule::<T>(&caller)?;
+		add_vesting_schedule::<T>(&caller)?;
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some(200.into()),
+			"Vesting schedules not added",
+		);
+	}: _(RawOrigin::Signed(caller.clone()), 0, 1)
+	verify {
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).map(|schedules| schedules.len()),
+			Some(1),
+			"Vesting schedules were not merged",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some(200.into()),
+			"Locked amount changed",
+		);
+	}
 }
 
 #[cfg(test)]
@@ -209,6 +237,7 @@ mod tests {
 			assert_ok!(test_benchmark_vest_other_locked::<Test>());
 			assert_ok!(test_benchmark_vest_other_unlocked::<Test>());
 			assert_ok!(test_benchmark_vested_transfer::<Test>());
+			assert_ok!(test_benchmark_merge_schedules::<Test>());
 		});
 	}
 }
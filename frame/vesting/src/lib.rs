@@ -32,6 +32,9 @@
 //! either `vest` (in typical case where the sender is calling on their own behalf) or `vest_other`
 //! in case the sender is calling on another account's behalf.
 //!
+//! An account may have up to `MaxVestingSchedules` vesting schedules at once; the amount locked is
+//! the sum of the amounts unvested under each of them.
+//!
 //! ## Interface
 //!
 //! This module implements the `VestingSchedule` trait.
@@ -41,6 +44,8 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer` - Transfer funds to another account, adding a vesting schedule for them.
+//! - `merge_schedules` - Merge two vesting schedules of the sender into a single one.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
@@ -51,7 +56,7 @@ use sp_std::prelude::*;
 use sp_std::fmt::Debug;
 use codec::{Encode, Decode};
 use sp_runtime::{DispatchResult, RuntimeDebug, traits::{
-	StaticLookup, Zero, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Convert
+	StaticLookup, Zero, One, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Convert
 }};
 use frame_support::{decl_module, decl_event, decl_storage, decl_error, ensure, weights::Weight};
 use frame_support::traits::{
@@ -63,6 +68,7 @@ use frame_system::{ensure_signed, ensure_root};
 mod benchmarking;
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type VestingInfoOf<T> = VestingInfo<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
 
 pub trait WeightInfo {
 	fn vest_locked(l: u32, ) -> Weight;
@@ -70,6 +76,7 @@ pub trait WeightInfo {
 	fn vest_other_locked(l: u32, ) -> Weight;
 	fn vest_other_unlocked(l: u32, ) -> Weight;
 	fn vested_transfer(l: u32, ) -> Weight;
+	fn merge_schedules(l: u32, ) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -78,6 +85,7 @@ impl WeightInfo for () {
 	fn vest_other_locked(_l: u32, ) -> Weight { 1_000_000_000 }
 	fn vest_other_unlocked(_l: u32, ) -> Weight { 1_000_000_000 }
 	fn vested_transfer(_l: u32, ) -> Weight { 1_000_000_000 }
+	fn merge_schedules(_l: u32, ) -> Weight { 1_000_000_000 }
 }
 
 pub trait Trait: frame_system::Trait {
@@ -93,12 +101,18 @@ pub trait Trait: frame_system::Trait {
 	/// The minimum amount transferred to call `vested_transfer`.
 	type MinVestedTransfer: Get<BalanceOf<Self>>;
 
+	/// The maximum number of vesting schedules an account may have at a given moment.
+	type MaxVestingSchedules: Get<u32>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
 
 const VESTING_ID: LockIdentifier = *b"vesting ";
 
+/// The number of locks on an account assumed by call weights which depend on it.
+const MAX_LOCKS: u32 = 50;
+
 /// Struct to encode the vesting schedule of an individual account.
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct VestingInfo<Balance, BlockNumber> {
@@ -130,6 +144,38 @@ impl<
 			Zero::zero()
 		}
 	}
+
+	/// Block at which the schedule is fully vested, converted into a balance. Saturates to
+	/// `Balance::max_value()` if the schedule never vests.
+	pub fn ending_block_as_balance<
+		BlockNumberToBalance: Convert<BlockNumber, Balance>
+	>(&self) -> Balance {
+		if self.per_block.is_zero() {
+			return Balance::max_value()
+		}
+		let starting_block = BlockNumberToBalance::convert(self.starting_block);
+		// Round up, the last block may unlock less than `per_block`.
+		let mut duration = self.locked / self.per_block;
+		if !(self.locked % self.per_block).is_zero() {
+			duration = duration.saturating_add(One::one());
+		}
+		starting_block.saturating_add(duration)
+	}
+}
+
+// A value placed in storage that represents the current version of the Vesting storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V0,
+	V1,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
 }
 
 decl_storage! {
@@ -137,7 +183,12 @@ decl_storage! {
 		/// Information regarding the vesting of a given account.
 		pub Vesting get(fn vesting):
 			map hasher(blake2_128_concat) T::AccountId
-			=> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>>;
+			=> Option<Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>>>;
+
+		/// Storage version of the pallet.
+		///
+		/// New networks start with last version.
+		StorageVersion build(|_| Releases::V1): Releases;
 	}
 	add_extra_genesis {
 		config(vesting): Vec<(T::AccountId, T::BlockNumber, T::BlockNumber, BalanceOf<T>)>;
@@ -156,11 +207,15 @@ decl_storage! {
 				let length_as_balance = T::BlockNumberToBalance::convert(length);
 				let per_block = locked / length_as_balance.max(sp_runtime::traits::One::one());
 
-				Vesting::<T>::insert(who, VestingInfo {
+				assert!(
+					!Vesting::<T>::contains_key(who),
+					"Accounts can only have one vesting schedule at genesis",
+				);
+				Vesting::<T>::insert(who, vec![VestingInfo {
 					locked: locked,
 					per_block: per_block,
 					starting_block: begin
-				});
+				}]);
 				let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
 				T::Currency::set_lock(VESTING_ID, who, locked, reasons);
 			}
@@ -183,10 +238,13 @@ decl_error! {
 	pub enum Error for Module<T: Trait> {
 		/// The account given is not vesting.
 		NotVesting,
-		/// An existing vesting schedule already exists for this account that cannot be clobbered.
-		ExistingVestingSchedule,
+		/// The account already has `MaxVestingSchedules` vesting schedules and cannot be given
+		/// another one.
+		AtMaxVestingSchedules,
 		/// Amount being transferred is too low to create a vesting schedule.
 		AmountLow,
+		/// An index was out of bounds of the vesting schedules.
+		ScheduleIndexOutOfBounds,
 	}
 }
 
@@ -198,8 +256,15 @@ decl_module! {
 		/// The minimum amount to be transferred to create a new vesting schedule.
 		const MinVestedTransfer: BalanceOf<T> = T::MinVestedTransfer::get();
 
+		/// The maximum number of vesting schedules an account may have at a given moment.
+		const MaxVestingSchedules: u32 = T::MaxVestingSchedules::get();
+
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			Self::migrate_to_multiple_schedules()
+		}
+
 		/// Unlock any vested funds of the sender account.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
@@ -276,12 +341,12 @@ decl_module! {
 			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
 
 			let who = T::Lookup::lookup(target)?;
-			ensure!(!Vesting::<T>::contains_key(&who), Error::<T>::ExistingVestingSchedule);
+			Self::ensure_can_add_schedule(&who)?;
 
 			T::Currency::transfer(&transactor, &who, schedule.locked, ExistenceRequirement::AllowDeath)?;
 
 			Self::add_vesting_schedule(&who, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
+				.expect("user does not have the maximum number of vesting schedules; q.e.d.");
 
 			Ok(())
 		}
@@ -317,37 +382,159 @@ decl_module! {
 
 			let target = T::Lookup::lookup(target)?;
 			let source = T::Lookup::lookup(source)?;
-			ensure!(!Vesting::<T>::contains_key(&target), Error::<T>::ExistingVestingSchedule);
+			Self::ensure_can_add_schedule(&target)?;
 
 			T::Currency::transfer(&source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
 
 			Self::add_vesting_schedule(&target, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
+				.expect("user does not have the maximum number of vesting schedules; q.e.d.");
+
+			Ok(())
+		}
+
+		/// Merge two vesting schedules of the sender together, creating a new vesting schedule
+		/// that unlocks over the highest possible start and end blocks. If both schedules have
+		/// already started the current block will be used as the schedule start; with the caveat
+		/// that if one schedule is finished by the current block, the other will be treated as
+		/// the new merged schedule, unmodified.
+		///
+		/// NOTE: If `schedule1_index == schedule2_index` this is a no-op.
+		/// NOTE: This will unlock all schedules through the current block prior to merging.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
+		/// locked under this module.
+		///
+		/// - `schedule1_index`: index of the first schedule to merge.
+		/// - `schedule2_index`: index of the second schedule to merge.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules of the sender, bounded by
+		///   `MaxVestingSchedules`.
+		/// - DbWeight: 2 Reads, 2 Writes
+		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
+		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
+		/// - Assuming less than `MAX_LOCKS` locks on any user.
+		/// # </weight>
+		#[weight = T::WeightInfo::merge_schedules(MAX_LOCKS)]
+		fn merge_schedules(origin, schedule1_index: u32, schedule2_index: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index { return Ok(()) }
+
+			let mut schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+			let (low, high) = if schedule1_index < schedule2_index {
+				(schedule1_index as usize, schedule2_index as usize)
+			} else {
+				(schedule2_index as usize, schedule1_index as usize)
+			};
+			ensure!(high < schedules.len(), Error::<T>::ScheduleIndexOutOfBounds);
+
+			// Remove the higher index first so the lower one does not shift.
+			let schedule2 = schedules.remove(high);
+			let schedule1 = schedules.remove(low);
+			let now = <frame_system::Module<T>>::block_number();
+			if let Some(merged) = Self::merge_vesting_info(now, schedule1, schedule2) {
+				schedules.push(merged);
+			}
 
+			Self::write_schedules(who, schedules);
 			Ok(())
 		}
 	}
 }
 
 impl<T: Trait> Module<T> {
+	/// Migrate the single vesting schedule of every account to a list of vesting schedules.
+	///
+	/// Returns the weight consumed: a single read of the storage version if the migration has
+	/// already run, otherwise one read and one write per translated entry on top of it.
+	pub fn migrate_to_multiple_schedules() -> Weight {
+		if StorageVersion::get() == Releases::V0 {
+			StorageVersion::put(Releases::V1);
+			let mut translated: Weight = 0;
+			Vesting::<T>::translate::<VestingInfoOf<T>, _>(|_, schedule| {
+				translated += 1;
+				Some(vec![schedule])
+			});
+			T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+		} else {
+			T::DbWeight::get().reads(1)
+		}
+	}
+
 	/// (Re)set or remove the module's currency lock on `who`'s account in accordance with their
 	/// current unvested amount.
 	fn update_lock(who: T::AccountId) -> DispatchResult {
-		let vesting = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+		let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+		Self::write_schedules(who, schedules);
+		Ok(())
+	}
+
+	/// Store the `schedules` of `who`, dropping those which have fully vested, and (re)set or
+	/// remove the module's currency lock in accordance with the amount still unvested.
+	fn write_schedules(who: T::AccountId, schedules: Vec<VestingInfoOf<T>>) {
 		let now = <frame_system::Module<T>>::block_number();
-		let locked_now = vesting.locked_at::<T::BlockNumberToBalance>(now);
+		let mut locked_now = BalanceOf::<T>::zero();
+		let schedules = schedules.into_iter()
+			.filter(|schedule| {
+				let locked = schedule.locked_at::<T::BlockNumberToBalance>(now);
+				locked_now = locked_now.saturating_add(locked);
+				!locked.is_zero()
+			})
+			.collect::<Vec<_>>();
 
 		if locked_now.is_zero() {
 			T::Currency::remove_lock(VESTING_ID, &who);
 			Vesting::<T>::remove(&who);
 			Self::deposit_event(RawEvent::VestingCompleted(who));
 		} else {
+			Vesting::<T>::insert(&who, schedules);
 			let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
 			T::Currency::set_lock(VESTING_ID, &who, locked_now, reasons);
 			Self::deposit_event(RawEvent::VestingUpdated(who, locked_now));
 		}
+	}
+
+	/// Ensure `who` may be given another vesting schedule.
+	fn ensure_can_add_schedule(who: &T::AccountId) -> DispatchResult {
+		let count = Self::vesting(who).map_or(0, |schedules| schedules.len());
+		ensure!(
+			(count as u32) < T::MaxVestingSchedules::get(),
+			Error::<T>::AtMaxVestingSchedules,
+		);
 		Ok(())
 	}
+
+	/// Merge two vesting schedules into one which unlocks what remains locked under both of them
+	/// at block `now` linearly from the latest of their starts (or `now`) to the latest of their
+	/// ends. Returns `None` if both schedules have fully vested.
+	fn merge_vesting_info(
+		now: T::BlockNumber,
+		schedule1: VestingInfoOf<T>,
+		schedule2: VestingInfoOf<T>,
+	) -> Option<VestingInfoOf<T>> {
+		let locked1 = schedule1.locked_at::<T::BlockNumberToBalance>(now);
+		let locked2 = schedule2.locked_at::<T::BlockNumberToBalance>(now);
+		match (locked1.is_zero(), locked2.is_zero()) {
+			(true, true) => return None,
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			(false, false) => (),
+		}
+
+		let locked = locked1.saturating_add(locked2);
+		let starting_block = now.max(schedule1.starting_block).max(schedule2.starting_block);
+		let ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>()
+			.max(schedule2.ending_block_as_balance::<T::BlockNumberToBalance>());
+		let duration = ending_block
+			.saturating_sub(T::BlockNumberToBalance::convert(starting_block))
+			.max(One::one());
+		// Rounding down may take the merged schedule at most one block past `ending_block`.
+		let per_block = (locked / duration).max(One::one());
+
+		Some(VestingInfo { locked, per_block, starting_block })
+	}
 }
 
 impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
@@ -358,9 +545,11 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(v) = Self::vesting(who) {
+		if let Some(schedules) = Self::vesting(who) {
 			let now = <frame_system::Module<T>>::block_number();
-			let locked_now = v.locked_at::<T::BlockNumberToBalance>(now);
+			let locked_now = schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+				total.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+			});
 			Some(T::Currency::free_balance(who).min(locked_now))
 		} else {
 			None
@@ -369,7 +558,7 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 
 	/// Adds a vesting schedule to a given account.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
+	/// If the account already has `MaxVestingSchedules` vesting schedules, an `Err` is returned
 	/// and nothing is updated.
 	///
 	/// On success, a linearly reducing amount of funds will be locked. In order to realise any
@@ -384,25 +573,28 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 		starting_block: T::BlockNumber
 	) -> DispatchResult {
 		if locked.is_zero() { return Ok(()) }
-		if Vesting::<T>::contains_key(who) {
-			Err(Error::<T>::ExistingVestingSchedule)?
-		}
+		Self::ensure_can_add_schedule(who)?;
 		let vesting_schedule = VestingInfo {
 			locked,
 			per_block,
 			starting_block
 		};
-		Vesting::<T>::insert(who, vesting_schedule);
-		// it can't fail, but even if somehow it did, we don't really care.
-		let _ = Self::update_lock(who.clone());
+		let mut schedules = Self::vesting(who).unwrap_or_default();
+		schedules.push(vesting_schedule);
+		Self::write_schedules(who.clone(), schedules);
 		Ok(())
 	}
 
-	/// Remove a vesting schedule for a given account.
-	fn remove_vesting_schedule(who: &T::AccountId) {
-		Vesting::<T>::remove(who);
-		// it can't fail, but even if somehow it did, we don't really care.
-		let _ = Self::update_lock(who.clone());
+	/// Remove the vesting schedule at `schedule_index` for a given account.
+	fn remove_vesting_schedule(who: &T::AccountId, schedule_index: u32) -> DispatchResult {
+		let mut schedules = Self::vesting(who).ok_or(Error::<T>::NotVesting)?;
+		ensure!(
+			(schedule_index as usize) < schedules.len(),
+			Error::<T>::ScheduleIndexOutOfBounds,
+		);
+		schedules.remove(schedule_index as usize);
+		Self::write_schedules(who.clone(), schedules);
+		Ok(())
 	}
 }
 
@@ -469,15 +661,19 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
 		type WeightInfo = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
 	}
 	parameter_types! {
 		pub const MinVestedTransfer: u64 = 256 * 2;
+		pub const MaxVestingSchedules: u32 = 3;
 	}
 	impl Trait for Test {
 		type Event = ();
 		type Currency = Balances;
 		type BlockNumberToBalance = Identity;
 		type MinVestedTransfer = MinVestedTransfer;
+		type MaxVestingSchedules = MaxVestingSchedules;
 		type WeightInfo = ();
 	}
 	type System = frame_system::Module<Test>;
@@ -559,9 +755,9 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&1), Some(user1_vesting_schedule)); // Account 1 has a vesting schedule
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule)); // Account 2 has a vesting schedule
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule)); // Account 12 has a vesting schedule
+				assert_eq!(Vesting::vesting(&1), Some(vec![user1_vesting_schedule])); // Account 1 has a vesting schedule
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule])); // Account 2 has a vesting schedule
+				assert_eq!(Vesting::vesting(&12), Some(vec![user12_vesting_schedule])); // Account 12 has a vesting schedule
 
 				// Account 1 has only 128 units vested from their illiquid 256 * 5 units at block 1
 				assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9));
@@ -682,7 +878,7 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule));
+				assert_eq!(Vesting::vesting(&12), Some(vec![user12_vesting_schedule]));
 
 				// Account 12 can still send liquid funds
 				assert_ok!(Balances::transfer(Some(12).into(), 3, 256 * 5));
@@ -709,7 +905,7 @@ mod tests {
 				};
 				assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
 				// Now account 4 should have vesting.
-				assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+				assert_eq!(Vesting::vesting(&4), Some(vec![new_vesting_schedule]));
 				// Ensure the transfer happened correctly.
 				let user3_free_balance_updated = Balances::free_balance(&3);
 				assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -748,17 +944,23 @@ mod tests {
 					per_block: 256, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
 
-				// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+				// Account 2 can be given schedules until it has `MaxVestingSchedules` of them.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+				let user2_free_balance = Balances::free_balance(&2);
+				assert_eq!(user2_free_balance, 256 * 30);
+
+				// The vesting schedule we will try to create, fails as the maximum is reached.
 				assert_noop!(
 					Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule),
-					Error::<Test>::ExistingVestingSchedule,
+					Error::<Test>::AtMaxVestingSchedules,
 				);
 
 				// Fails due to too low transfer amount.
@@ -773,8 +975,8 @@ mod tests {
 				);
 
 				// Verify no currency transfer happened.
-				assert_eq!(user2_free_balance, 256 * 20);
-				assert_eq!(user4_free_balance, 256 * 40);
+				assert_eq!(Balances::free_balance(&2), user2_free_balance);
+				assert_eq!(Balances::free_balance(&4), user4_free_balance);
 			});
 	}
 
@@ -799,7 +1001,7 @@ mod tests {
 				assert_noop!(Vesting::force_vested_transfer(Some(4).into(), 3, 4, new_vesting_schedule), BadOrigin);
 				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, new_vesting_schedule));
 				// Now account 4 should have vesting.
-				assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+				assert_eq!(Vesting::vesting(&4), Some(vec![new_vesting_schedule]));
 				// Ensure the transfer happened correctly.
 				let user3_free_balance_updated = Balances::free_balance(&3);
 				assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -838,17 +1040,23 @@ mod tests {
 					per_block: 256, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
 
-				// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+				// Account 2 can be given schedules until it has `MaxVestingSchedules` of them.
 				let new_vesting_schedule = VestingInfo {
 					locked: 256 * 5,
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
+				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 2, new_vesting_schedule));
+				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 2, new_vesting_schedule));
+				let user2_free_balance = Balances::free_balance(&2);
+				assert_eq!(user2_free_balance, 256 * 30);
+
+				// The vesting schedule we will try to create, fails as the maximum is reached.
 				assert_noop!(
 					Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule),
-					Error::<Test>::ExistingVestingSchedule,
+					Error::<Test>::AtMaxVestingSchedules,
 				);
 
 				// Fails due to too low transfer amount.
@@ -863,8 +1071,108 @@ mod tests {
 				);
 
 				// Verify no currency transfer happened.
-				assert_eq!(user2_free_balance, 256 * 20);
-				assert_eq!(user4_free_balance, 256 * 40);
+				assert_eq!(Balances::free_balance(&2), user2_free_balance);
+				assert_eq!(Balances::free_balance(&4), user4_free_balance);
+			});
+	}
+
+	#[test]
+	fn multiple_schedules_vest_together() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				// Account 1 vests 256 * 5 over blocks 0 to 10, add a second schedule over 10 to 30.
+				let user1_second_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 64, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 1, user1_second_schedule));
+				assert_eq!(Vesting::vesting(&1).unwrap().len(), 2);
+				// 128 * 9 of the first schedule and all of the second are locked at block 1.
+				assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9 + 256 * 5));
+
+				System::set_block_number(20);
+				// The first schedule has fully vested, half of the second remains.
+				assert_eq!(Vesting::vesting_balance(&1), Some(64 * 10));
+				assert_ok!(Vesting::vest(Some(1).into()));
+				// The fully vested schedule was dropped.
+				assert_eq!(Vesting::vesting(&1), Some(vec![user1_second_schedule]));
+				assert_ok!(Balances::transfer(Some(1).into(), 2, 256 * 15 - 64 * 10));
+
+				System::set_block_number(30);
+				assert_ok!(Vesting::vest(Some(1).into()));
+				assert_eq!(Vesting::vesting(&1), None);
+				assert_eq!(Vesting::vesting_balance(&1), None);
+			});
+	}
+
+	#[test]
+	fn merge_schedules_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user2_second_schedule = VestingInfo {
+					locked: 256 * 10,
+					per_block: 256, // Vesting over 10 blocks
+					starting_block: 5,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, user2_second_schedule));
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 30));
+
+				System::set_block_number(10);
+				// Half of the second schedule has vested.
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 25));
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+				// The merged schedule starts now and ends with the first schedule at block 30.
+				let merged_schedule = VestingInfo {
+					locked: 256 * 25,
+					per_block: 320,
+					starting_block: 10,
+				};
+				assert_eq!(Vesting::vesting(&2), Some(vec![merged_schedule]));
+				assert_eq!(Vesting::vesting_balance(&2), Some(256 * 25));
+
+				System::set_block_number(30);
+				assert_eq!(Vesting::vesting_balance(&2), Some(0));
+			});
+	}
+
+	#[test]
+	fn merge_schedules_handles_edge_cases() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				assert_noop!(
+					Vesting::merge_schedules(Some(4).into(), 0, 1),
+					Error::<Test>::NotVesting,
+				);
+				assert_noop!(
+					Vesting::merge_schedules(Some(2).into(), 0, 1),
+					Error::<Test>::ScheduleIndexOutOfBounds,
+				);
+				// Merging a schedule with itself is a no-op.
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 0));
+
+				let user2_second_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 256, // Vesting over 5 blocks
+					starting_block: 1,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, user2_second_schedule));
+
+				System::set_block_number(10);
+				// The second schedule has fully vested, so the first one is kept unchanged.
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 1, 0));
+				let user2_vesting_schedule = VestingInfo {
+					locked: 256 * 20,
+					per_block: 256, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				assert_eq!(Vesting::vesting(&2), Some(vec![user2_vesting_schedule]));
 			});
 	}
 }
@@ -0,0 +1,106 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Benchmarks for Whitelist Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::UnfilteredDispatchable;
+
+use crate::Module as Whitelist;
+
+fn remark_call<T: Trait>(n: u32) -> <T as Trait>::Call where
+	<T as Trait>::Call: From<frame_system::Call<T>>,
+{
+	frame_system::Call::<T>::remark(vec![1; n as usize]).into()
+}
+
+benchmarks! {
+	where_clause { where <T as Trait>::Call: From<frame_system::Call<T>> }
+
+	_ { }
+
+	whitelist_call {
+		let origin = T::WhitelistOrigin::successful_origin();
+		let call_hash = T::Hashing::hash_of(&0u32);
+		let call = Call::<T>::whitelist_call(call_hash);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Whitelist::<T>::whitelisted_call(call_hash).is_some());
+		assert!(T::Preimages::preimage_requested(&call_hash));
+	}
+
+	remove_whitelisted_call {
+		let u in 0 .. 1000;
+		let origin = T::WhitelistOrigin::successful_origin();
+		let call_hash = T::Hashing::hash_of(&u);
+		Whitelist::<T>::whitelist_call(origin.clone(), call_hash)?;
+		let call = Call::<T>::remove_whitelisted_call(call_hash);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Whitelist::<T>::whitelisted_call(call_hash).is_none());
+		assert!(!T::Preimages::preimage_requested(&call_hash));
+	}
+
+	dispatch_whitelisted_call {
+		let n in 0 .. 10_000;
+		let remark = remark_call::<T>(n);
+		let weight = remark.get_dispatch_info().weight;
+		let encoded = remark.encode();
+		let call_hash = T::Hashing::hash(&encoded[..]);
+		Whitelist::<T>::whitelist_call(T::WhitelistOrigin::successful_origin(), call_hash)?;
+		T::Preimages::note_preimage(encoded);
+
+		let origin = T::DispatchWhitelistedOrigin::successful_origin();
+		let call = Call::<T>::dispatch_whitelisted_call(call_hash, weight);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Whitelist::<T>::whitelisted_call(call_hash).is_none());
+	}
+
+	dispatch_whitelisted_call_with_preimage {
+		let n in 1 .. 10_000;
+		let remark = remark_call::<T>(n);
+		let call_hash = T::Hashing::hash_of(&remark);
+		Whitelist::<T>::whitelist_call(T::WhitelistOrigin::successful_origin(), call_hash)?;
+
+		let origin = T::DispatchWhitelistedOrigin::successful_origin();
+		let call = Call::<T>::dispatch_whitelisted_call_with_preimage(Box::new(remark));
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Whitelist::<T>::whitelisted_call(call_hash).is_none());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_whitelist_call::<Test>());
+			assert_ok!(test_benchmark_remove_whitelisted_call::<Test>());
+			assert_ok!(test_benchmark_dispatch_whitelisted_call::<Test>());
+			assert_ok!(test_benchmark_dispatch_whitelisted_call_with_preimage::<Test>());
+		});
+	}
+}
@@ -0,0 +1,221 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Whitelist Pallet
+//!
+//! - [`whitelist::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! The Whitelist pallet splits the dispatch of a privileged call between two origins. The
+//! `WhitelistOrigin`, e.g. a technical collective, whitelists the hash of a call. Later, the
+//! `DispatchWhitelistedOrigin` may dispatch the whitelisted call with the _Root_ origin, exactly
+//! once. Neither origin can dispatch arbitrary calls as _Root_ on its own.
+//!
+//! The preimage of a whitelisted call hash is requested from `Preimages`, so it can be noted
+//! without a deposit. Alternatively, the call may be given in full when it is dispatched.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `whitelist_call` - Whitelist a call hash, called by the `WhitelistOrigin`.
+//! - `remove_whitelisted_call` - Remove a call hash from the whitelist, called by the
+//!   `WhitelistOrigin`.
+//! - `dispatch_whitelisted_call` - Dispatch a whitelisted call from its noted preimage, called by
+//!   the `DispatchWhitelistedOrigin`.
+//! - `dispatch_whitelisted_call_with_preimage` - Dispatch a whitelisted call given in full,
+//!   called by the `DispatchWhitelistedOrigin`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{DispatchResult, traits::Hash};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
+	weights::{Weight, GetDispatchInfo},
+	traits::{EnsureOrigin, PreimageRecipient, UnfilteredDispatchable},
+};
+
+#[cfg(test)]
+mod tests;
+mod benchmarking;
+
+pub trait WeightInfo {
+	fn whitelist_call() -> Weight;
+	fn remove_whitelisted_call() -> Weight;
+	fn dispatch_whitelisted_call() -> Weight;
+	fn dispatch_whitelisted_call_with_preimage(n: u32, ) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn whitelist_call() -> Weight { 1_000_000_000 }
+	fn remove_whitelisted_call() -> Weight { 1_000_000_000 }
+	fn dispatch_whitelisted_call() -> Weight { 1_000_000_000 }
+	fn dispatch_whitelisted_call_with_preimage(_n: u32, ) -> Weight { 1_000_000_000 }
+}
+
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The call type which may be whitelisted and dispatched.
+	type Call: Parameter + UnfilteredDispatchable<Origin=Self::Origin> + GetDispatchInfo;
+
+	/// Origin which may whitelist a call hash, or remove it from the whitelist.
+	type WhitelistOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin which may dispatch a whitelisted call.
+	type DispatchWhitelistedOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The provider of the preimages of whitelisted call hashes.
+	type Preimages: PreimageRecipient<Self::Hash>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Whitelist {
+		/// The hashes of the calls which are whitelisted.
+		pub WhitelistedCall get(fn whitelisted_call): map hasher(identity) T::Hash => Option<()>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where <T as frame_system::Trait>::Hash {
+		/// A call hash has been whitelisted. \[call_hash\]
+		CallWhitelisted(Hash),
+		/// A call hash has been removed from the whitelist. \[call_hash\]
+		WhitelistedCallRemoved(Hash),
+		/// A whitelisted call has been dispatched. \[call_hash, result\]
+		WhitelistedCallDispatched(Hash, DispatchResult),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The preimage of the call hash could not be loaded.
+		UnavailablePreImage,
+		/// The call could not be decoded.
+		UndecodableCall,
+		/// The weight of the decoded call was higher than the witness.
+		InvalidCallWeightWitness,
+		/// The call was not whitelisted.
+		CallIsNotWhitelisted,
+		/// The call was already whitelisted; No-Op.
+		CallAlreadyWhitelisted,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Whitelist the call with the hash `call_hash`, and request its preimage.
+		///
+		/// The dispatch origin of this call must be the `WhitelistOrigin`.
+		#[weight = T::WeightInfo::whitelist_call()]
+		fn whitelist_call(origin, call_hash: T::Hash) {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			ensure!(
+				!WhitelistedCall::<T>::contains_key(call_hash),
+				Error::<T>::CallAlreadyWhitelisted
+			);
+
+			WhitelistedCall::<T>::insert(call_hash, ());
+			T::Preimages::request_preimage(&call_hash);
+
+			Self::deposit_event(RawEvent::CallWhitelisted(call_hash));
+		}
+
+		/// Remove the call with the hash `call_hash` from the whitelist.
+		///
+		/// The dispatch origin of this call must be the `WhitelistOrigin`.
+		#[weight = T::WeightInfo::remove_whitelisted_call()]
+		fn remove_whitelisted_call(origin, call_hash: T::Hash) {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			WhitelistedCall::<T>::take(call_hash).ok_or(Error::<T>::CallIsNotWhitelisted)?;
+
+			T::Preimages::unrequest_preimage(&call_hash);
+
+			Self::deposit_event(RawEvent::WhitelistedCallRemoved(call_hash));
+		}
+
+		/// Dispatch the whitelisted call with the hash `call_hash` with the _Root_ origin, and
+		/// remove it from the whitelist. The preimage of the call must have been noted.
+		///
+		/// The dispatch origin of this call must be the `DispatchWhitelistedOrigin`.
+		///
+		/// - `call_weight_witness`: An upper bound on the weight of the call.
+		#[weight = T::WeightInfo::dispatch_whitelisted_call().saturating_add(*call_weight_witness)]
+		fn dispatch_whitelisted_call(origin, call_hash: T::Hash, call_weight_witness: Weight) {
+			T::DispatchWhitelistedOrigin::ensure_origin(origin)?;
+			ensure!(
+				WhitelistedCall::<T>::contains_key(call_hash),
+				Error::<T>::CallIsNotWhitelisted
+			);
+
+			let call = T::Preimages::get_preimage(&call_hash)
+				.ok_or(Error::<T>::UnavailablePreImage)?;
+			let call = <T as Trait>::Call::decode(&mut &call[..])
+				.map_err(|_| Error::<T>::UndecodableCall)?;
+			ensure!(
+				call.get_dispatch_info().weight <= call_weight_witness,
+				Error::<T>::InvalidCallWeightWitness
+			);
+
+			Self::clean_and_dispatch(call_hash, call);
+		}
+
+		/// Dispatch the whitelisted call `call` with the _Root_ origin, and remove it from the
+		/// whitelist.
+		///
+		/// The dispatch origin of this call must be the `DispatchWhitelistedOrigin`.
+		#[weight = {
+			let call_len = call.encode().len() as u32;
+			T::WeightInfo::dispatch_whitelisted_call_with_preimage(call_len)
+				.saturating_add(call.get_dispatch_info().weight)
+		}]
+		fn dispatch_whitelisted_call_with_preimage(origin, call: Box<<T as Trait>::Call>) {
+			T::DispatchWhitelistedOrigin::ensure_origin(origin)?;
+			let call_hash = T::Hashing::hash_of(&call);
+			ensure!(
+				WhitelistedCall::<T>::contains_key(call_hash),
+				Error::<T>::CallIsNotWhitelisted
+			);
+
+			Self::clean_and_dispatch(call_hash, *call);
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Remove `call_hash` from the whitelist and dispatch `call` with the _Root_ origin.
+	fn clean_and_dispatch(call_hash: T::Hash, call: <T as Trait>::Call) {
+		WhitelistedCall::<T>::remove(call_hash);
+		T::Preimages::unrequest_preimage(&call_hash);
+
+		let result = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+		let result = result.map(|_| ()).map_err(|e| e.error);
+		Self::deposit_event(RawEvent::WhitelistedCallDispatched(call_hash, result));
+	}
+}
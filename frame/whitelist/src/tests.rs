@@ -0,0 +1,250 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for Whitelist Pallet
+
+#![cfg(test)]
+
+use super::*;
+
+use frame_support::{
+	assert_ok, assert_noop, impl_outer_origin, impl_outer_dispatch, parameter_types,
+	ord_parameter_types, traits::PreimageProvider,
+};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill, testing::Header, traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+};
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		pallet_balances::Balances,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 2_000_000_000_000;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+parameter_types! {
+	pub const PreimageMaxSize: u32 = 4096;
+	pub const PreimageBaseDeposit: u64 = 2;
+	pub const PreimageByteDeposit: u64 = 1;
+}
+impl pallet_preimage::Trait for Test {
+	type Event = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type MaxSize = PreimageMaxSize;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+	type WeightInfo = ();
+}
+ord_parameter_types! {
+	pub const One: u64 = 1;
+}
+impl Trait for Test {
+	type Event = ();
+	type Call = Call;
+	type WhitelistOrigin = EnsureRoot<u64>;
+	type DispatchWhitelistedOrigin = EnsureSignedBy<One, u64>;
+	type Preimages = Preimage;
+	type WeightInfo = ();
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Preimage = pallet_preimage::Module<Test>;
+type Whitelist = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
+}
+
+fn set_balance_call(value: u64) -> Call {
+	Call::Balances(pallet_balances::Call::set_balance(42, value, 0))
+}
+
+fn call_hash(call: &Call) -> H256 {
+	BlakeTwo256::hash_of(call)
+}
+
+#[test]
+fn whitelist_call_works() {
+	new_test_ext().execute_with(|| {
+		let hash = call_hash(&set_balance_call(10));
+		assert_noop!(Whitelist::whitelist_call(Origin::signed(1), hash), BadOrigin);
+
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), hash));
+		assert_eq!(Whitelist::whitelisted_call(hash), Some(()));
+		assert!(Preimage::preimage_requested(&hash));
+
+		assert_noop!(
+			Whitelist::whitelist_call(Origin::root(), hash),
+			Error::<Test>::CallAlreadyWhitelisted,
+		);
+	});
+}
+
+#[test]
+fn remove_whitelisted_call_works() {
+	new_test_ext().execute_with(|| {
+		let hash = call_hash(&set_balance_call(10));
+		assert_noop!(
+			Whitelist::remove_whitelisted_call(Origin::root(), hash),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), hash));
+		assert_noop!(Whitelist::remove_whitelisted_call(Origin::signed(1), hash), BadOrigin);
+		assert_ok!(Whitelist::remove_whitelisted_call(Origin::root(), hash));
+		assert_eq!(Whitelist::whitelisted_call(hash), None);
+		assert!(!Preimage::preimage_requested(&hash));
+	});
+}
+
+#[test]
+fn dispatch_whitelisted_call_works() {
+	new_test_ext().execute_with(|| {
+		let call = set_balance_call(10);
+		let hash = call_hash(&call);
+		let weight = call.get_dispatch_info().weight;
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(Origin::signed(1), hash, weight),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), hash));
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(Origin::signed(2), hash, weight),
+			BadOrigin,
+		);
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(Origin::signed(1), hash, weight),
+			Error::<Test>::UnavailablePreImage,
+		);
+
+		// The preimage is requested, so it is noted without a deposit.
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), call.encode()));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(Origin::signed(1), hash, weight - 1),
+			Error::<Test>::InvalidCallWeightWitness,
+		);
+
+		assert_ok!(Whitelist::dispatch_whitelisted_call(Origin::signed(1), hash, weight));
+		assert_eq!(Balances::free_balance(42), 10);
+		assert_eq!(Whitelist::whitelisted_call(hash), None);
+		assert!(!Preimage::have_preimage(&hash));
+
+		// The call may only be dispatched once.
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(Origin::signed(1), hash, weight),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+	});
+}
+
+#[test]
+fn dispatch_whitelisted_call_with_preimage_works() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(set_balance_call(10));
+		let hash = call_hash(&call);
+
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call_with_preimage(Origin::signed(1), call.clone()),
+			Error::<Test>::CallIsNotWhitelisted,
+		);
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), hash));
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call_with_preimage(Origin::signed(2), call.clone()),
+			BadOrigin,
+		);
+
+		assert_ok!(Whitelist::dispatch_whitelisted_call_with_preimage(Origin::signed(1), call));
+		assert_eq!(Balances::free_balance(42), 10);
+		assert_eq!(Whitelist::whitelisted_call(hash), None);
+		assert!(!Preimage::preimage_requested(&hash));
+	});
+}
+
+#[test]
+fn undecodable_preimage_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let bytes = vec![255, 255];
+		let hash = BlakeTwo256::hash(&bytes[..]);
+		assert_ok!(Whitelist::whitelist_call(Origin::root(), hash));
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), bytes));
+		assert_noop!(
+			Whitelist::dispatch_whitelisted_call(Origin::signed(1), hash, 0),
+			Error::<Test>::UndecodableCall,
+		);
+	});
+}
@@ -312,6 +312,12 @@ impl FreeingBumpHeapAllocator {
 		}
 	}
 
+	/// The offset from the beginning of the linear memory up to which memory has been handed
+	/// out by this allocator.
+	pub fn bumper(&self) -> u32 {
+		self.bumper
+	}
+
 	/// Gets requested number of bytes to allocate and returns a pointer.
 	/// The maximum size which can be allocated at once is 16 MiB.
 	/// There is no minimum size, but whatever size is passed into
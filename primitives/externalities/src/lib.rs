@@ -140,6 +140,42 @@ pub trait Externalities: ExtensionStore {
 	/// Clear storage entries which keys are start with the given prefix.
 	fn clear_prefix(&mut self, prefix: &[u8]);
 
+	/// Clear at most `limit` storage entries which keys are start with the given prefix.
+	///
+	/// Entries are removed in lexicographic order of their keys, starting after `cursor` if it is
+	/// given. Returns the number of removed entries, together with the key of the last removed
+	/// entry if there are entries left to remove. This key can be passed as `cursor` to a
+	/// subsequent call to continue where this one stopped.
+	fn clear_prefix_limited(
+		&mut self,
+		prefix: &[u8],
+		limit: u32,
+		cursor: Option<&[u8]>,
+	) -> (u32, Option<Vec<u8>>) {
+		if sp_storage::well_known_keys::is_child_storage_key(prefix) {
+			return (0, None);
+		}
+
+		let mut next = match cursor {
+			Some(cursor) => self.next_storage_key(cursor),
+			None if self.exists_storage(prefix) => Some(prefix.to_vec()),
+			None => self.next_storage_key(prefix),
+		};
+		let mut removed = 0;
+		let mut last = None;
+		while let Some(key) = next.filter(|key| key.starts_with(prefix)) {
+			if removed == limit {
+				return (removed, last);
+			}
+			self.place_storage(key.clone(), None);
+			removed += 1;
+			next = self.next_storage_key(&key);
+			last = Some(key);
+		}
+
+		(removed, None)
+	}
+
 	/// Clear child storage entries which keys are start with the given prefix.
 	fn clear_child_prefix(
 		&mut self,
@@ -42,7 +42,7 @@ use sp_std::{collections::btree_map::{BTreeMap, IntoIter, Entry}, vec::Vec};
 use parking_lot::RwLock;
 
 #[cfg(feature = "std")]
-use std::{sync::Arc, format};
+use std::{sync::Arc, format, future::Future, pin::Pin};
 
 /// An error that can occur within the inherent data system.
 #[cfg(feature = "std")]
@@ -336,6 +336,45 @@ impl InherentDataProviders {
 	}
 }
 
+/// Something that creates the inherent data providers for a block.
+///
+/// It is called for every block that is authored or verified, with the hash of the parent of the
+/// block and `ExtraArgs` given by the caller, so the providers can depend on the parent block.
+/// The inherent data is created by the returned providers in their registration order.
+///
+/// This is implemented for `InherentDataProviders`, which returns itself for every block, and
+/// for async closures taking the parent hash and `ExtraArgs`.
+#[cfg(feature = "std")]
+pub trait CreateInherentDataProviders<Hash, ExtraArgs>: Send + Sync {
+	/// The future creating the providers.
+	type Future: Future<Output = Result<InherentDataProviders, Error>> + Send;
+
+	/// Create the inherent data providers for a block on top of `parent`.
+	fn create_inherent_data_providers(&self, parent: Hash, extra_args: ExtraArgs) -> Self::Future;
+}
+
+#[cfg(feature = "std")]
+impl<Hash, ExtraArgs> CreateInherentDataProviders<Hash, ExtraArgs> for InherentDataProviders {
+	type Future = Pin<Box<dyn Future<Output = Result<InherentDataProviders, Error>> + Send>>;
+
+	fn create_inherent_data_providers(&self, _: Hash, _: ExtraArgs) -> Self::Future {
+		let providers = self.clone();
+		Box::pin(async move { Ok(providers) })
+	}
+}
+
+#[cfg(feature = "std")]
+impl<F, Fut, Hash, ExtraArgs> CreateInherentDataProviders<Hash, ExtraArgs> for F where
+	F: Fn(Hash, ExtraArgs) -> Fut + Send + Sync,
+	Fut: Future<Output = Result<InherentDataProviders, Error>> + Send,
+{
+	type Future = Fut;
+
+	fn create_inherent_data_providers(&self, parent: Hash, extra_args: ExtraArgs) -> Self::Future {
+		(self)(parent, extra_args)
+	}
+}
+
 /// Something that provides inherent data.
 #[cfg(feature = "std")]
 pub trait ProvideInherentData {
@@ -504,6 +543,26 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn inherent_data_providers_are_created_per_parent() {
+		let create = |parent: u32, _: ()| async move {
+			let providers = InherentDataProviders::new();
+			if parent > 0 {
+				providers.register_provider(TestInherentDataProvider::new())?;
+			}
+			Ok::<_, Error>(providers)
+		};
+
+		let providers = futures::executor::block_on(create.create_inherent_data_providers(0, ()))
+			.unwrap();
+		assert_eq!(providers.create_inherent_data().unwrap().len(), 0);
+
+		let providers = futures::executor::block_on(create.create_inherent_data_providers(1, ()))
+			.unwrap();
+		let data = providers.create_inherent_data().unwrap();
+		assert_eq!(data.get_data::<u32>(&TEST_INHERENT_0).unwrap(), Some(42));
+	}
+
 	#[test]
 	fn registering_inherent_provider() {
 		let provider = TestInherentDataProvider::new();
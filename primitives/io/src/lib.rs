@@ -51,7 +51,7 @@ use sp_core::{
 #[cfg(feature = "std")]
 use sp_trie::{TrieConfiguration, trie_types::Layout};
 
-use sp_runtime_interface::{runtime_interface, Pointer};
+use sp_runtime_interface::{runtime_interface, Pointer, pass_by::PassByCodec};
 
 use codec::{Encode, Decode};
 
@@ -75,6 +75,17 @@ pub enum EcdsaVerifyError {
 	BadSignature,
 }
 
+/// The outcome of clearing a limited number of storage entries under a prefix.
+#[derive(PassByCodec, Encode, Decode, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ClearPrefixOutcome {
+	/// The number of removed entries.
+	pub removed: u32,
+	/// The cursor to pass to a subsequent call to continue removing entries, or `None` if all
+	/// entries under the prefix were removed.
+	pub cursor: Option<Vec<u8>>,
+}
+
 /// Interface for accessing the storage from within the runtime.
 #[runtime_interface]
 pub trait Storage {
@@ -114,10 +125,34 @@ pub trait Storage {
 	}
 
 	/// Clear the storage of each key-value pair where the key starts with the given `prefix`.
+	///
+	/// This removes an unbounded number of entries. Prefer [`clear_prefix_limited`] in the
+	/// runtime, which can be continued in a later block.
 	fn clear_prefix(&mut self, prefix: &[u8]) {
 		Externalities::clear_prefix(*self, prefix)
 	}
 
+	/// Clear the storage of at most `limit` key-value pairs where the key starts with the given
+	/// `prefix`.
+	///
+	/// Entries are removed in lexicographic order of their keys, starting after `cursor` if it
+	/// is given. The returned [`ClearPrefixOutcome`] holds a cursor to continue the removal in a
+	/// subsequent call if not all entries could be removed.
+	fn clear_prefix_limited(
+		&mut self,
+		prefix: &[u8],
+		limit: u32,
+		cursor: Option<Vec<u8>>,
+	) -> ClearPrefixOutcome {
+		let (removed, cursor) = Externalities::clear_prefix_limited(
+			*self,
+			prefix,
+			limit,
+			cursor.as_ref().map(|c| &c[..]),
+		);
+		ClearPrefixOutcome { removed, cursor }
+	}
+
 	/// Append the encoded `value` to the storage item at `key`.
 	///
 	/// The storage item needs to implement [`EncodeAppend`](codec::EncodeAppend).
@@ -997,6 +1032,31 @@ pub trait Logging {
 	}
 }
 
+#[cfg(feature = "std")]
+thread_local! {
+	/// The message of the last panic reported by a runtime executed on this thread.
+	static PANIC_MESSAGE: std::cell::RefCell<Option<String>> = std::cell::RefCell::new(None);
+}
+
+/// Take the message of the last panic reported through [`panic_handler::report_panic`] by a
+/// runtime executed on this thread, if any.
+#[cfg(feature = "std")]
+pub fn take_panic_message() -> Option<String> {
+	PANIC_MESSAGE.with(|message| message.borrow_mut().take())
+}
+
+/// Interface that provides functions for reporting panics of the runtime to the host.
+#[runtime_interface]
+pub trait PanicHandler {
+	/// Report the message of a panic of the runtime.
+	///
+	/// The runtime aborts right after calling this. The executor returns the message as the
+	/// reason of the failed call, see [`take_panic_message`].
+	fn report_panic(message: &str) {
+		PANIC_MESSAGE.with(|m| *m.borrow_mut() = Some(message.into()));
+	}
+}
+
 #[cfg(feature = "std")]
 sp_externalities::decl_extension! {
 	/// Extension to allow running traces in wasm via Proxy
@@ -1166,6 +1226,7 @@ pub fn panic(info: &core::panic::PanicInfo) -> ! {
 	unsafe {
 		let message = sp_std::alloc::format!("{}", info);
 		logging::log(LogLevel::Error, "runtime", message.as_bytes());
+		panic_handler::report_panic(&message);
 		core::arch::wasm32::unreachable();
 	}
 }
@@ -1201,6 +1262,7 @@ pub type SubstrateHostFunctions = (
 	sandbox::HostFunctions,
 	crate::trie::HostFunctions,
 	offchain_index::HostFunctions,
+	panic_handler::HostFunctions,
 );
 
 #[cfg(test)]
@@ -1250,6 +1312,35 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn clear_prefix_limited_works() {
+		let mut t = BasicExternalities::new(Storage {
+			top: map![
+				b":a".to_vec() => b"\x0b\0\0\0Hello world".to_vec(),
+				b":abc".to_vec() => b"\x0b\0\0\0Hello world".to_vec(),
+				b":abcd".to_vec() => b"\x0b\0\0\0Hello world".to_vec(),
+				b":abce".to_vec() => b"\x0b\0\0\0Hello world".to_vec(),
+				b":abdd".to_vec() => b"\x0b\0\0\0Hello world".to_vec()
+			],
+			children_default: map![],
+		});
+
+		t.execute_with(|| {
+			let outcome = storage::clear_prefix_limited(b":abc", 2, None);
+			assert_eq!(outcome, ClearPrefixOutcome { removed: 2, cursor: Some(b":abcd".to_vec()) });
+			assert!(storage::get(b":abc").is_none());
+			assert!(storage::get(b":abcd").is_none());
+			assert!(storage::get(b":abce").is_some());
+
+			let outcome = storage::clear_prefix_limited(b":abc", 2, outcome.cursor);
+			assert_eq!(outcome, ClearPrefixOutcome { removed: 1, cursor: None });
+			assert!(storage::get(b":abce").is_none());
+
+			assert!(storage::get(b":a").is_some());
+			assert!(storage::get(b":abdd").is_some());
+		});
+	}
+
 	#[test]
 	fn clear_prefix_works() {
 		let mut t = BasicExternalities::new(Storage {
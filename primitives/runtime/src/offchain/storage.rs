@@ -17,6 +17,7 @@
 
 //! A set of storage helpers for offchain workers.
 
+use sp_std::prelude::*;
 use sp_core::offchain::StorageKind;
 
 /// A storage value with a static key.
@@ -99,6 +100,98 @@ impl<'a> StorageValueRef<'a> {
 	}
 }
 
+/// A namespace of values in the local storage.
+///
+/// Values are stored under `prefix ++ b"/" ++ key`, so namespaces with distinct prefixes never
+/// collide. The namespace also keeps an index of the keys it holds under `prefix ++ b":index"`,
+/// which allows iterating over its keys and clearing all of its values at once.
+pub struct StorageNamespace<'a> {
+	prefix: &'a [u8],
+	index_key: Vec<u8>,
+	kind: StorageKind,
+}
+
+impl<'a> StorageNamespace<'a> {
+	/// Create a new namespace in the persistent local storage.
+	pub fn persistent(prefix: &'a [u8]) -> Self {
+		Self::new(prefix, StorageKind::PERSISTENT)
+	}
+
+	/// Create a new namespace in the fork-aware local storage.
+	pub fn local(prefix: &'a [u8]) -> Self {
+		Self::new(prefix, StorageKind::LOCAL)
+	}
+
+	fn new(prefix: &'a [u8], kind: StorageKind) -> Self {
+		Self { prefix, index_key: [prefix, b":index"].concat(), kind }
+	}
+
+	/// Set the value under `key` to the encoding of the given parameter.
+	pub fn set(&self, key: &[u8], value: &impl codec::Encode) {
+		value.using_encoded(|val| {
+			sp_io::offchain::local_storage_set(self.kind, &self.value_key(key), val)
+		});
+		self.mutate_index(|keys| if !keys.iter().any(|k| &k[..] == key) {
+			keys.push(key.to_vec());
+		});
+	}
+
+	/// Retrieve & decode the value under `key`.
+	///
+	/// Returns `None` if the value was not found, otherwise a decoding of the value to the
+	/// requested type.
+	pub fn get<T: codec::Decode>(&self, key: &[u8]) -> Option<Option<T>> {
+		sp_io::offchain::local_storage_get(self.kind, &self.value_key(key))
+			.map(|val| T::decode(&mut &*val).ok())
+	}
+
+	/// Remove the value under `key`.
+	pub fn remove(&self, key: &[u8]) {
+		sp_io::offchain::local_storage_clear(self.kind, &self.value_key(key));
+		self.mutate_index(|keys| keys.retain(|k| &k[..] != key));
+	}
+
+	/// The keys of all the values in this namespace.
+	pub fn keys(&self) -> Vec<Vec<u8>> {
+		self.index().get::<Vec<Vec<u8>>>().flatten().unwrap_or_default()
+	}
+
+	/// Remove all the values in this namespace, returning the number of values removed.
+	pub fn clear(&self) -> u32 {
+		let mut removed = Vec::new();
+		self.mutate_index(|keys| removed = sp_std::mem::take(keys));
+		for key in &removed {
+			sp_io::offchain::local_storage_clear(self.kind, &self.value_key(key));
+		}
+		let mut index = self.index();
+		index.clear();
+		removed.len() as u32
+	}
+
+	fn value_key(&self, key: &[u8]) -> Vec<u8> {
+		[self.prefix, b"/", key].concat()
+	}
+
+	fn index(&self) -> StorageValueRef<'_> {
+		StorageValueRef { key: &self.index_key, kind: self.kind }
+	}
+
+	/// Update the index, retrying if another worker changed it concurrently.
+	fn mutate_index(&self, mut f: impl FnMut(&mut Vec<Vec<u8>>)) {
+		let index = self.index();
+		loop {
+			let result = index.mutate::<Vec<Vec<u8>>, (), _>(|keys| {
+				let mut keys = keys.flatten().unwrap_or_default();
+				f(&mut keys);
+				Ok(keys)
+			});
+			if let Ok(Ok(_)) = result {
+				break
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -131,6 +224,40 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn namespace_should_track_and_clear_its_keys() {
+		let (offchain, state) = testing::TestOffchainExt::new();
+		let mut t = TestExternalities::default();
+		t.register_extension(OffchainExt::new(offchain));
+
+		t.execute_with(|| {
+			let ns = StorageNamespace::persistent(b"worker");
+			let other = StorageNamespace::persistent(b"other");
+
+			ns.set(b"a", &1_u32);
+			ns.set(b"b", &2_u32);
+			ns.set(b"a", &3_u32);
+			other.set(b"a", &4_u32);
+
+			assert_eq!(ns.keys(), vec![b"a".to_vec(), b"b".to_vec()]);
+			assert_eq!(ns.get::<u32>(b"a"), Some(Some(3_u32)));
+			assert_eq!(
+				state.read().persistent_storage.get(b"", b"worker/b"),
+				Some(vec![2_u8, 0, 0, 0])
+			);
+
+			ns.remove(b"b");
+			assert_eq!(ns.keys(), vec![b"a".to_vec()]);
+			assert_eq!(ns.get::<u32>(b"b"), None);
+
+			assert_eq!(ns.clear(), 1);
+			assert!(ns.keys().is_empty());
+			assert_eq!(ns.get::<u32>(b"a"), None);
+			assert_eq!(state.read().persistent_storage.get(b"", b"worker:index"), None);
+			assert_eq!(other.get::<u32>(b"a"), Some(Some(4_u32)));
+		})
+	}
+
 	#[test]
 	fn should_mutate() {
 		let (offchain, state) = testing::TestOffchainExt::new();
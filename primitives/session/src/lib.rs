@@ -48,6 +48,20 @@ sp_api::decl_runtime_apis! {
 		/// Returns the list of public raw public keys + key type.
 		fn decode_session_keys(encoded: Vec<u8>) -> Option<Vec<(Vec<u8>, KeyTypeId)>>;
 	}
+
+	/// Automated session key rotation runtime api.
+	pub trait SessionKeysRotation {
+		/// Rotate the session keys of a local validator after the block of `header`, unless
+		/// they were rotated in the last `period` blocks already.
+		///
+		/// The new keys should be generated in the keystore exposed via runtime externalities
+		/// and registered with a transaction signed by the validator's controller key, so this
+		/// needs to be called in an offchain context.
+		///
+		/// Returns the raw public keys + key type of earlier rotations which are neither active
+		/// nor queued anymore, which can be removed from the keystore.
+		fn rotate_session_keys(header: &Block::Header, period: u32) -> Vec<(Vec<u8>, KeyTypeId)>;
+	}
 }
 
 /// Number of validators in a given session.
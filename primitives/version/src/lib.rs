@@ -47,6 +47,28 @@ macro_rules! create_apis_vec {
 	( $y:expr ) => { $crate::sp_std::borrow::Cow::Borrowed(& $y) }
 }
 
+/// Name of the custom wasm section in which a runtime declares the number of heap pages it
+/// requires. See [`runtime_heap_pages`].
+pub const HEAP_PAGES_SECTION: &str = "runtime_heap_pages";
+
+/// Declare the number of 64KB heap pages the runtime requires.
+///
+/// The number is stored as a little endian `u64` in the [`HEAP_PAGES_SECTION`] custom section of
+/// the wasm binary. Executors use it unless `:heappages` is set in storage, up to a node-side
+/// maximum.
+///
+/// ```ignore
+/// sp_version::runtime_heap_pages!(2048);
+/// ```
+#[macro_export]
+macro_rules! runtime_heap_pages {
+	( $pages:expr ) => {
+		#[cfg(not(feature = "std"))]
+		#[link_section = "runtime_heap_pages"]
+		static RUNTIME_HEAP_PAGES: [u8; 8] = ($pages as u64).to_le_bytes();
+	}
+}
+
 /// Runtime version.
 /// This should not be thought of as classic Semver (major/minor/tiny).
 /// This triplet have different semantics and mis-interpretation could cause problems.
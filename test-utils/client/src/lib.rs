@@ -254,7 +254,12 @@ impl<Block: BlockT, E, Backend, G: GenesisInit> TestClientBuilder<
 		let executor = executor.into().unwrap_or_else(||
 			NativeExecutor::new(WasmExecutionMethod::Interpreted, None, 8)
 		);
-		let executor = LocalCallExecutor::new(self.backend.clone(), executor, tasks_executor(), Default::default());
+		let executor = LocalCallExecutor::new(
+			self.backend.clone(),
+			executor,
+			tasks_executor(),
+			Default::default(),
+		).expect("Creates LocalCallExecutor");
 
 		self.build_with_executor(executor)
 	}
@@ -350,7 +350,12 @@ pub fn new_light() -> (
 	let blockchain = Arc::new(sc_light::Blockchain::new(storage));
 	let backend = Arc::new(LightBackend::new(blockchain.clone()));
 	let executor = new_native_executor();
-	let local_call_executor = client::LocalCallExecutor::new(backend.clone(), executor, sp_core::tasks::executor(), Default::default());
+	let local_call_executor = client::LocalCallExecutor::new(
+		backend.clone(),
+		executor,
+		sp_core::tasks::executor(),
+		Default::default(),
+	).expect("Creates LocalCallExecutor");
 	let call_executor = LightExecutor::new(
 		backend.clone(),
 		local_call_executor,
@@ -138,6 +138,10 @@ pub enum Extrinsic {
 	IncludeData(Vec<u8>),
 	StorageChange(Vec<u8>, Option<Vec<u8>>),
 	ChangesTrieConfigUpdate(Option<ChangesTrieConfiguration>),
+	/// Valid in the pool, but fails to apply with `UnknownTransaction::CannotLookup`.
+	///
+	/// The data distinguishes otherwise identical transactions.
+	CannotLookup(Vec<u8>),
 }
 
 parity_util_mem::malloc_size_of_is_0!(Extrinsic); // non-opaque extrinsic does not need this
@@ -166,6 +170,7 @@ impl BlindCheckable for Extrinsic {
 			Extrinsic::StorageChange(key, value) => Ok(Extrinsic::StorageChange(key, value)),
 			Extrinsic::ChangesTrieConfigUpdate(new_config) =>
 				Ok(Extrinsic::ChangesTrieConfigUpdate(new_config)),
+			Extrinsic::CannotLookup(data) => Ok(Extrinsic::CannotLookup(data)),
 		}
 	}
 }
@@ -745,6 +750,21 @@ cfg_if! {
 				}
 			}
 
+			impl sp_session::SessionKeysRotation<Block> for Runtime {
+				fn rotate_session_keys(
+					_: &<Block as BlockT>::Header,
+					_: u32,
+				) -> Vec<(Vec<u8>, sp_core::crypto::KeyTypeId)> {
+					// Rotates on every call, which makes all earlier sr25519 keys stale.
+					let stale = sr25519::AppPublic::all()
+						.into_iter()
+						.map(|key| (key.to_raw_vec(), sr25519::AppPublic::ID))
+						.collect();
+					SessionKeys::generate(None);
+					stale
+				}
+			}
+
 			impl sp_finality_grandpa::GrandpaApi<Block> for Runtime {
 				fn grandpa_authorities() -> sp_finality_grandpa::AuthorityList {
 					Vec::new()
@@ -987,6 +1007,21 @@ cfg_if! {
 				}
 			}
 
+			impl sp_session::SessionKeysRotation<Block> for Runtime {
+				fn rotate_session_keys(
+					_: &<Block as BlockT>::Header,
+					_: u32,
+				) -> Vec<(Vec<u8>, sp_core::crypto::KeyTypeId)> {
+					// Rotates on every call, which makes all earlier sr25519 keys stale.
+					let stale = sr25519::AppPublic::all()
+						.into_iter()
+						.map(|key| (key.to_raw_vec(), sr25519::AppPublic::ID))
+						.collect();
+					SessionKeys::generate(None);
+					stale
+				}
+			}
+
 			impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {
 				fn account_nonce(_account: AccountId) -> Index {
 					0
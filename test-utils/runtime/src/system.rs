@@ -28,7 +28,8 @@ use frame_support::{decl_storage, decl_module};
 use sp_runtime::{
 	traits::Header as _, generic, ApplyExtrinsicResult,
 	transaction_validity::{
-		TransactionValidity, ValidTransaction, InvalidTransaction, TransactionValidityError,
+		TransactionValidity, ValidTransaction, InvalidTransaction, UnknownTransaction,
+		TransactionValidityError,
 	},
 };
 use codec::{KeyedVec, Encode, Decode};
@@ -160,6 +161,16 @@ pub fn validate_transaction(utx: Extrinsic) -> TransactionValidity {
 		return InvalidTransaction::BadProof.into();
 	}
 
+	if let Extrinsic::CannotLookup(data) = utx {
+		return Ok(ValidTransaction {
+			priority: data.len() as u64,
+			requires: vec![],
+			provides: vec![data],
+			longevity: 64,
+			propagate: false,
+		});
+	}
+
 	let tx = utx.transfer();
 	let nonce_key = tx.from.to_keyed_vec(NONCE_OF);
 	let expected_nonce: u64 = storage::hashed::get_or(&blake2_256, &nonce_key, 0);
@@ -261,6 +272,7 @@ fn execute_transaction_backend(utx: &Extrinsic, extrinsic_index: u32) -> ApplyEx
 			execute_storage_change(key, value.as_ref().map(|v| &**v)),
 		Extrinsic::ChangesTrieConfigUpdate(ref new_config) =>
 			execute_changes_trie_config_update(new_config.clone()),
+		Extrinsic::CannotLookup(_) => Err(UnknownTransaction::CannotLookup.into()),
 	}
 }
 
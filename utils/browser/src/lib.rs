@@ -73,6 +73,7 @@ where
 
 			DatabaseConfig::Custom(sp_database::as_database(db))
 		},
+		auto_upgrade: true,
 		keystore: KeystoreConfig::InMemory,
 		default_heap_pages: Default::default(),
 		dev_key_seed: Default::default(),
@@ -84,6 +85,9 @@ where
 		offchain_worker: Default::default(),
 		prometheus_config: Default::default(),
 		pruning: Default::default(),
+		keep_blocks: Default::default(),
+		canonicalization_delay: sc_service::DEFAULT_CANONICALIZATION_DELAY,
+		force_pruning_mode: false,
 		rpc_cors: Default::default(),
 		rpc_http: Default::default(),
 		rpc_ipc: Default::default(),
@@ -92,10 +96,12 @@ where
 		rpc_methods: Default::default(),
 		state_cache_child_ratio: Default::default(),
 		state_cache_size: Default::default(),
+		state_node_cache_size: Default::default(),
 		tracing_receiver: Default::default(),
 		tracing_targets: Default::default(),
 		transaction_pool: Default::default(),
 		wasm_method: Default::default(),
+		wasm_cache_path: None,
 		max_runtime_instances: 8,
 		announce_block: true,
 		base_path: None,